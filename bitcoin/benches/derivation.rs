@@ -0,0 +1,87 @@
+use wagyu_bitcoin::{BitcoinDerivationPath, BitcoinExtendedPrivateKey, Mainnet};
+use wagyu_model::{ChildIndex, ExtendedPrivateKey, ExtendedPublicKey};
+
+use core::str::FromStr;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const EXTENDED_PRIVATE_KEY: &str =
+    "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+const COUNTS: [u32; 2] = [100, 1000];
+
+fn derive_children(c: &mut Criterion) {
+    let extended_private_key = BitcoinExtendedPrivateKey::<Mainnet>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+    let extended_public_key = extended_private_key.to_extended_public_key();
+    let paths = (0..COUNTS[COUNTS.len() - 1])
+        .map(|index| BitcoinDerivationPath::from_str(&format!("m/{}", index)).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("extended_private_key");
+    for count in COUNTS.iter() {
+        group.bench_with_input(BenchmarkId::new("derive", count), count, |b, &count| {
+            b.iter(|| {
+                paths[..count as usize]
+                    .iter()
+                    .map(|path| extended_private_key.derive(path).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("derive_range", count), count, |b, &count| {
+            b.iter(|| extended_private_key.derive_range(ChildIndex::Normal(0), count).unwrap())
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("extended_public_key");
+    for count in COUNTS.iter() {
+        group.bench_with_input(BenchmarkId::new("derive", count), count, |b, &count| {
+            b.iter(|| {
+                paths[..count as usize]
+                    .iter()
+                    .map(|path| extended_public_key.derive(path).unwrap())
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("derive_range", count), count, |b, &count| {
+            b.iter(|| extended_public_key.derive_range(ChildIndex::Normal(0), count).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn derive_extended_public_keys(c: &mut Criterion) {
+    let extended_private_key = BitcoinExtendedPrivateKey::<Mainnet>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+    let children = extended_private_key
+        .derive_range(ChildIndex::Normal(0), COUNTS[COUNTS.len() - 1])
+        .unwrap();
+
+    let mut group = c.benchmark_group("extended_private_key");
+    for count in COUNTS.iter() {
+        group.bench_with_input(BenchmarkId::new("to_extended_public_key", count), count, |b, &count| {
+            b.iter(|| {
+                children[..count as usize]
+                    .iter()
+                    .map(|child| child.to_extended_public_key())
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("derive_range_to_extended_public_key", count),
+            count,
+            |b, &count| {
+                b.iter(|| {
+                    extended_private_key
+                        .derive_range(ChildIndex::Normal(0), count)
+                        .unwrap()
+                        .iter()
+                        .map(|child| child.to_extended_public_key())
+                        .collect::<Vec<_>>()
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, derive_children, derive_extended_public_keys);
+criterion_main!(benches);
@@ -0,0 +1,87 @@
+use wagyu_bitcoin::{
+    BitcoinAddress, BitcoinDerivationPath, BitcoinExtendedPrivateKey, BitcoinFormat, BitcoinPublicKey, Mainnet,
+};
+use wagyu_model::{ExtendedPrivateKey, PublicKey};
+
+use core::str::FromStr;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Baseline, in microseconds on an x86-64 Linux machine. Save a baseline of your own with
+// `cargo bench --bench keys -- --save-baseline <name>` and compare a change against it with
+// `cargo bench --bench keys -- --baseline <name>`.
+//
+//   extended_private_key/new_master         70
+//   extended_private_key/derive_normal      72
+//   extended_private_key/derive_hardened    64
+//   extended_private_key/derive_bip44      348
+//   extended_private_key/to_string          10
+//   extended_private_key/from_str           75
+//   public_key/from_private_key             65
+//   address/p2pkh                            2.6
+//   address/bech32                           1.7
+
+type N = Mainnet;
+
+const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+const EXTENDED_PRIVATE_KEY: &str =
+    "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+/// Benchmarks the master key and child key derivation of extended private keys. Each child
+/// derivation multiplies the generator by the parent and child private keys, so these are
+/// bounded by the secp256k1 generator multiplication measured in `public_key/from_private_key`.
+fn extended_private_key(c: &mut Criterion) {
+    let seed = hex::decode(SEED).unwrap();
+    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+    let normal = BitcoinDerivationPath::from_str("m/0").unwrap();
+    let hardened = BitcoinDerivationPath::from_str("m/0'").unwrap();
+    let bip44 = BitcoinDerivationPath::from_str("m/44'/0'/0'/0/0").unwrap();
+
+    let mut group = c.benchmark_group("extended_private_key");
+    group.bench_function("new_master", |b| {
+        b.iter(|| BitcoinExtendedPrivateKey::<N>::new_master(black_box(&seed), &BitcoinFormat::P2PKH).unwrap())
+    });
+    group.bench_function("derive_normal", |b| {
+        b.iter(|| extended_private_key.derive(black_box(&normal)).unwrap())
+    });
+    group.bench_function("derive_hardened", |b| {
+        b.iter(|| extended_private_key.derive(black_box(&hardened)).unwrap())
+    });
+    group.bench_function("derive_bip44", |b| {
+        b.iter(|| extended_private_key.derive(black_box(&bip44)).unwrap())
+    });
+    group.bench_function("to_string", |b| b.iter(|| black_box(&extended_private_key).to_string()));
+    group.bench_function("from_str", |b| {
+        b.iter(|| BitcoinExtendedPrivateKey::<N>::from_str(black_box(EXTENDED_PRIVATE_KEY)).unwrap())
+    });
+    group.finish();
+}
+
+/// Benchmarks the public key computation apart from the address encoding, which is only hashing.
+fn public_key(c: &mut Criterion) {
+    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+    let private_key = extended_private_key.to_private_key();
+
+    let mut group = c.benchmark_group("public_key");
+    group.bench_function("from_private_key", |b| {
+        b.iter(|| BitcoinPublicKey::from_private_key(black_box(&private_key)))
+    });
+    group.finish();
+}
+
+fn address(c: &mut Criterion) {
+    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+    let public_key = extended_private_key.to_public_key();
+
+    let mut group = c.benchmark_group("address");
+    group.bench_function("p2pkh", |b| {
+        b.iter(|| BitcoinAddress::<N>::p2pkh(black_box(&public_key)).unwrap().to_string())
+    });
+    group.bench_function("bech32", |b| {
+        b.iter(|| BitcoinAddress::<N>::bech32(black_box(&public_key)).unwrap().to_string())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, extended_private_key, public_key, address);
+criterion_main!(benches);
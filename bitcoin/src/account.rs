@@ -0,0 +1,313 @@
+//!
+//! Account
+//!
+//! This module tracks the addresses handed out from a watch-only account extended public key,
+//! as a hardware wallet companion does. Each chain hands out its addresses in order, never twice,
+//! and stops once the addresses handed out past the last used one reach the gap limit, so that
+//! account discovery with the same gap limit recovers every used address.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::discovery::{Chain, DEFAULT_GAP_LIMIT};
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, ChildIndex, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError};
+
+use serde::{Deserialize, Serialize};
+
+/// The depth of a BIP44 account extended public key, m/purpose'/coin_type'/account'
+pub const ACCOUNT_DEPTH: u8 = 3;
+
+#[derive(Debug, Fail)]
+pub enum AccountError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(DerivationPathError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(ExtendedPublicKeyError),
+
+    #[fail(display = "the {:?} chain has reached the gap limit of {} unused addresses", _0, _1)]
+    GapLimitReached(Chain, u32),
+
+    #[fail(display = "invalid account depth: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidDepth(u8, u8),
+}
+
+impl From<AddressError> for AccountError {
+    fn from(error: AddressError) -> Self {
+        AccountError::AddressError(error)
+    }
+}
+
+impl From<DerivationPathError> for AccountError {
+    fn from(error: DerivationPathError) -> Self {
+        AccountError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for AccountError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        AccountError::ExtendedPublicKeyError(error)
+    }
+}
+
+/// Represents the addresses handed out from and used on a chain of an account
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChainState {
+    /// The index of the next address to hand out
+    pub next: u32,
+    /// The indices of the used addresses
+    pub used: BTreeSet<u32>,
+}
+
+impl ChainState {
+    /// Returns the number of addresses handed out after the last used address.
+    pub fn gap(&self) -> u32 {
+        let last = self.used.iter().next_back().map_or(0, |index| index + 1);
+        self.next.saturating_sub(last)
+    }
+}
+
+/// Represents a watch-only account of an account extended public key, which hands out the
+/// receiving and change addresses of the account in the given format
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(bound = "")]
+pub struct Account<N: BitcoinNetwork> {
+    /// The account extended public key
+    extended_public_key: BitcoinExtendedPublicKey<N>,
+    /// The format of the addresses
+    format: BitcoinFormat,
+    /// The number of unused addresses that each chain hands out past its last used address
+    gap_limit: u32,
+    /// The external chain, m/0/{index}
+    external: ChainState,
+    /// The change chain, m/1/{index}
+    change: ChainState,
+}
+
+impl<N: BitcoinNetwork> Account<N> {
+    /// Returns a new account of the given account extended public key, or an error if the
+    /// extended public key is not at the depth of a BIP44 account.
+    pub fn new(
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        format: &BitcoinFormat,
+    ) -> Result<Self, AccountError> {
+        match extended_public_key.depth() {
+            ACCOUNT_DEPTH => Ok(Self::new_at_any_depth(extended_public_key, format)),
+            depth => Err(AccountError::InvalidDepth(ACCOUNT_DEPTH, depth)),
+        }
+    }
+
+    /// Returns a new account of the given extended public key at any depth, for accounts that
+    /// do not follow BIP44, e.g. an exported m/0' account.
+    pub fn new_at_any_depth(extended_public_key: &BitcoinExtendedPublicKey<N>, format: &BitcoinFormat) -> Self {
+        Self {
+            extended_public_key: extended_public_key.clone(),
+            format: format.clone(),
+            gap_limit: DEFAULT_GAP_LIMIT,
+            external: ChainState::default(),
+            change: ChainState::default(),
+        }
+    }
+
+    /// Returns the account with the given gap limit.
+    pub fn with_gap_limit(self, gap_limit: u32) -> Self {
+        Self { gap_limit, ..self }
+    }
+
+    /// Returns the account extended public key.
+    pub fn extended_public_key(&self) -> &BitcoinExtendedPublicKey<N> {
+        &self.extended_public_key
+    }
+
+    /// Returns the format of the addresses.
+    pub fn format(&self) -> &BitcoinFormat {
+        &self.format
+    }
+
+    /// Returns the gap limit.
+    pub fn gap_limit(&self) -> u32 {
+        self.gap_limit
+    }
+
+    /// Returns the state of the given chain.
+    pub fn chain(&self, chain: Chain) -> &ChainState {
+        match chain {
+            Chain::External => &self.external,
+            Chain::Change => &self.change,
+        }
+    }
+
+    /// Returns the address of the given chain and index, without handing it out.
+    pub fn peek(&self, chain: Chain, index: u32) -> Result<BitcoinAddress<N>, AccountError> {
+        let index = ChildIndex::normal(index)?;
+        Ok(self
+            .extended_public_key
+            .derive_path(&format!("m/{}/{}", chain.to_index(), index))?
+            .to_address(&self.format)?)
+    }
+
+    /// Returns the index and address of the next receiving address, and hands it out.
+    pub fn next_receive_address(&mut self) -> Result<(u32, BitcoinAddress<N>), AccountError> {
+        self.next_address(Chain::External)
+    }
+
+    /// Returns the index and address of the next change address, and hands it out.
+    pub fn next_change_address(&mut self) -> Result<(u32, BitcoinAddress<N>), AccountError> {
+        self.next_address(Chain::Change)
+    }
+
+    /// Returns the index and address of the next address of the given chain, and hands it out,
+    /// or an error if the chain has reached the gap limit.
+    pub fn next_address(&mut self, chain: Chain) -> Result<(u32, BitcoinAddress<N>), AccountError> {
+        if self.chain(chain).gap() >= self.gap_limit {
+            return Err(AccountError::GapLimitReached(chain, self.gap_limit));
+        }
+
+        let index = self.chain(chain).next;
+        let address = self.peek(chain, index)?;
+        self.chain_mut(chain).next = index + 1;
+        Ok((index, address))
+    }
+
+    /// Marks the address of the given chain and index as used. An address that has not been
+    /// handed out yet, e.g. one found by account discovery, is no longer handed out, nor are
+    /// the addresses before it.
+    pub fn mark_used(&mut self, chain: Chain, index: u32) -> Result<(), AccountError> {
+        let _ = ChildIndex::normal(index)?;
+        let state = self.chain_mut(chain);
+        state.used.insert(index);
+        state.next = state.next.max(index + 1);
+        Ok(())
+    }
+
+    /// Returns `true` if the address of the given chain and index is marked as used.
+    pub fn is_used(&self, chain: Chain, index: u32) -> bool {
+        self.chain(chain).used.contains(&index)
+    }
+
+    fn chain_mut(&mut self, chain: Chain) -> &mut ChainState {
+        match chain {
+            Chain::External => &mut self.external,
+            Chain::Change => &mut self.change,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use core::str::FromStr;
+    use std::collections::HashSet;
+
+    type N = Mainnet;
+
+    // The BIP84 account 0 zpub of the "abandon ... about" mnemonic
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    fn account() -> Account<N> {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ZPUB).unwrap();
+        Account::new(&extended_public_key, &BitcoinFormat::Bech32).unwrap()
+    }
+
+    /// Returns the account restored from its serialized state, as after a restart.
+    fn reload(account: &Account<N>) -> Account<N> {
+        serde_json::from_str(&serde_json::to_string(account).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn peek() {
+        let account = account();
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            account.peek(Chain::External, 0).unwrap().to_string()
+        );
+        assert_eq!(
+            "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el",
+            account.peek(Chain::Change, 0).unwrap().to_string()
+        );
+        assert_eq!(0, account.chain(Chain::External).next);
+        assert!(account.peek(Chain::External, 1 << 31).is_err());
+    }
+
+    #[test]
+    fn allocations_and_reloads() {
+        let mut account = account().with_gap_limit(5);
+        let mut handed_out = HashSet::new();
+        let mut hand_out = |account: &mut Account<N>, chain: Chain| -> Result<u32, AccountError> {
+            let (index, address) = account.next_address(chain)?;
+            assert_eq!(account.peek(chain, index).unwrap(), address);
+            assert!(
+                handed_out.insert(address.to_string()),
+                "{} was handed out twice",
+                address
+            );
+            Ok(index)
+        };
+
+        for expected in 0..3 {
+            assert_eq!(expected, hand_out(&mut account, Chain::External).unwrap());
+        }
+        assert_eq!(0, hand_out(&mut account, Chain::Change).unwrap());
+        account.mark_used(Chain::External, 1).unwrap();
+
+        let mut account = reload(&account);
+        assert!(account.is_used(Chain::External, 1));
+        assert_eq!(1, account.chain(Chain::External).gap());
+        assert_eq!(1, account.chain(Chain::Change).gap());
+        for expected in 3..7 {
+            assert_eq!(expected, hand_out(&mut account, Chain::External).unwrap());
+        }
+        // Addresses 2 to 6 are unused, which is the gap limit.
+        match hand_out(&mut account, Chain::External) {
+            Err(AccountError::GapLimitReached(Chain::External, 5)) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        assert_eq!(1, hand_out(&mut account, Chain::Change).unwrap());
+
+        // An address found used past the handed out addresses is skipped after a reload.
+        account.mark_used(Chain::External, 9).unwrap();
+        let mut account = reload(&account);
+        assert_eq!(10, hand_out(&mut account, Chain::External).unwrap());
+        account.mark_used(Chain::Change, 0).unwrap();
+
+        let mut account = reload(&account);
+        assert_eq!(2, hand_out(&mut account, Chain::Change).unwrap());
+        assert_eq!(11, hand_out(&mut account, Chain::External).unwrap());
+        assert_eq!(5, account.gap_limit());
+        assert_eq!(9 + 3, handed_out.len());
+    }
+
+    #[test]
+    fn depth() {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ZPUB).unwrap();
+        let chain_key = extended_public_key.derive_path("m/0").unwrap();
+        match Account::new(&chain_key, &BitcoinFormat::Bech32) {
+            Err(AccountError::InvalidDepth(3, 4)) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let mut account = Account::new_at_any_depth(&chain_key, &BitcoinFormat::P2PKH);
+        let (index, address) = account.next_receive_address().unwrap();
+        assert_eq!(0, index);
+        assert_eq!(BitcoinFormat::P2PKH, address.format());
+        assert_eq!(
+            chain_key
+                .derive_path("m/0/0")
+                .unwrap()
+                .to_address(&BitcoinFormat::P2PKH)
+                .unwrap(),
+            address
+        );
+    }
+}
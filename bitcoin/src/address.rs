@@ -1,18 +1,24 @@
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::message::{message_hash, MESSAGE_SIGNATURE_HEADER, MESSAGE_SIGNATURE_SIZE};
+use crate::network::{address_network, bech32_network, check_address_prefix, BitcoinNetwork};
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
 use crate::witness_program::WitnessProgram;
 use wagyu_model::no_std::*;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    base58::{decode_check, encode_check},
+    bech32m::{self, Variant},
+    crypto::{hash160, sha256, tagged_hash},
     Address, AddressError, PrivateKey,
 };
 
-use base58::{FromBase58, ToBase58};
-use bech32::{u5, Bech32, FromBase32, ToBase32};
+use bech32::{u5, Bech32, ToBase32};
 use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
-use sha2::{Digest, Sha256};
+
+/// The minimum length of a Base58 or Bech32 address string.
+const MIN_ADDRESS_LENGTH: usize = 14;
+/// The maximum length of a Base58 or Bech32 address string, as specified in BIP173.
+const MAX_ADDRESS_LENGTH: usize = 90;
 
 /// Represents a Bitcoin address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -43,6 +49,7 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             }
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(&public_key),
             BitcoinFormat::Bech32 => Self::bech32(&public_key),
+            BitcoinFormat::P2TR => Self::p2tr(&public_key),
         }
     }
 
@@ -58,6 +65,7 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             }
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(public_key),
             BitcoinFormat::Bech32 => Self::bech32(public_key),
+            BitcoinFormat::P2TR => Self::p2tr(public_key),
         }
     }
 }
@@ -66,19 +74,16 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
     /// Returns a P2PKH address from a given Bitcoin public key.
     pub fn p2pkh(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
         let public_key = match public_key.is_compressed() {
-            true => public_key.to_secp256k1_public_key().serialize_compressed().to_vec(),
-            false => public_key.to_secp256k1_public_key().serialize().to_vec(),
+            true => public_key.to_compressed_bytes().to_vec(),
+            false => public_key.to_uncompressed_bytes().to_vec(),
         };
 
-        let mut address = [0u8; 25];
+        let mut address = [0u8; 21];
         address[0] = N::to_address_prefix(&BitcoinFormat::P2PKH)[0];
         address[1..21].copy_from_slice(&hash160(&public_key));
 
-        let sum = &checksum(&address[0..21])[0..4];
-        address[21..25].copy_from_slice(sum);
-
         Ok(Self {
-            address: address.to_base58(),
+            address: encode_check(&address),
             format: BitcoinFormat::P2PKH,
             _network: PhantomData,
         })
@@ -86,7 +91,7 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
 
     // Returns a P2WSH address in Bech32 format from a given Bitcoin script
     pub fn p2wsh(original_script: &Vec<u8>) -> Result<Self, AddressError> {
-        let script = Sha256::digest(&original_script).to_vec();
+        let script = sha256(&original_script).to_vec();
 
         // Organize as a hash
         let v = N::to_address_prefix(&BitcoinFormat::P2WSH)[0];
@@ -107,15 +112,12 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
 
     /// Returns a P2SH_P2WPKH address from a given Bitcoin public key.
     pub fn p2sh_p2wpkh(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
-        let mut address = [0u8; 25];
+        let mut address = [0u8; 21];
         address[0] = N::to_address_prefix(&BitcoinFormat::P2SH_P2WPKH)[0];
         address[1..21].copy_from_slice(&hash160(&Self::create_redeem_script(public_key)));
 
-        let sum = &checksum(&address[0..21])[0..4];
-        address[21..25].copy_from_slice(sum);
-
         Ok(Self {
-            address: address.to_base58(),
+            address: encode_check(&address),
             format: BitcoinFormat::P2SH_P2WPKH,
             _network: PhantomData,
         })
@@ -138,16 +140,156 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
         })
     }
 
+    /// Returns a P2TR address in Bech32m format from a given Bitcoin public key. The witness program
+    /// is the x-only output key of the public key tweaked without a script tree, as specified in BIP86.
+    pub fn p2tr(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        let hrp = String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32))?;
+        if hrp.is_empty() {
+            return Err(AddressError::IncompatibleFormats(
+                N::NAME.into(),
+                BitcoinFormat::P2TR.to_string(),
+            ));
+        }
+
+        // The internal key is the point of the x-only public key with an even y-coordinate.
+        let internal_key = public_key.to_x_only_bytes();
+        let mut even_key = [0x02u8; 33];
+        even_key[1..].copy_from_slice(&internal_key);
+        let mut output_key = secp256k1::PublicKey::parse_compressed(&even_key)?;
+        let tweak = secp256k1::SecretKey::parse(&tagged_hash("TapTweak", &internal_key))?;
+        output_key.tweak_add_assign(&tweak)?;
+
+        let mut data = N::to_address_prefix(&BitcoinFormat::P2TR);
+        data.extend(bech32m::convert_bits(
+            &output_key.serialize_compressed()[1..],
+            8,
+            5,
+            true,
+        )?);
+
+        Ok(Self {
+            address: bech32m::encode(&hrp, &data, Variant::Bech32m),
+            format: BitcoinFormat::P2TR,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns `true` if the given base64 encoded compact signature of the message was produced
+    /// by the key behind this address, as checked by the `verifymessage` RPC of Bitcoin Core.
+    pub fn verify_message(&self, message: &str, signature: &str) -> Result<bool, AddressError> {
+        let signature =
+            base64::decode(signature).map_err(|error| AddressError::Crate("base64", format!("{:?}", error)))?;
+        if signature.len() != MESSAGE_SIGNATURE_SIZE {
+            return Err(AddressError::InvalidSignatureLength(signature.len()));
+        }
+
+        let header = signature[0];
+        if !(MESSAGE_SIGNATURE_HEADER..MESSAGE_SIGNATURE_HEADER + 16).contains(&header) {
+            return Err(AddressError::InvalidSignatureHeader(header));
+        }
+        let recovery_id = secp256k1::RecoveryId::parse((header - MESSAGE_SIGNATURE_HEADER) % 4)?;
+        let compressed = header >= MESSAGE_SIGNATURE_HEADER + 4;
+
+        let message = secp256k1::Message::parse(&message_hash(message));
+        let signature = secp256k1::Signature::parse_slice(&signature[1..])?;
+        let public_key = match secp256k1::recover(&message, &signature, &recovery_id) {
+            Ok(public_key) => BitcoinPublicKey::<N>::from_secp256k1_public_key(public_key, compressed),
+            Err(_) => return Ok(false),
+        };
+
+        Ok(*self == Self::from_public_key(&public_key, &self.format)?)
+    }
+
     /// Returns the format of the Bitcoin address.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
 
+    /// Returns the type, network, and payload of the given address string. The network is
+    /// detected from the Base58 prefix or the Bech32 human-readable part of the address, and is
+    /// not required to be `N`. Unlike `from_str`, witness version 1 and higher addresses are
+    /// decoded as Bech32m, and each witness version must use its BIP350 checksum.
+    pub fn classify(address: &str) -> Result<BitcoinAddressInfo, AddressError> {
+        if address.len() < MIN_ADDRESS_LENGTH || address.len() > MAX_ADDRESS_LENGTH {
+            return Err(AddressError::InvalidCharacterLength(address.len()));
+        }
+
+        let network = address
+            .rfind('1')
+            .and_then(|separator| bech32_network(&address[..separator].to_lowercase()));
+        if let (Some(network), Err(_)) = (network, decode_check(address)) {
+            let (_, witness_program, address_type) = Self::decode_witness_program(address)?;
+            return Ok(BitcoinAddressInfo {
+                address_type,
+                network,
+                witness_version: Some(witness_program.version),
+                payload: witness_program.program,
+            });
+        }
+
+        let data = decode_check(address)?;
+        if data.len() != 21 {
+            return Err(AddressError::InvalidByteLength(data.len() + 4));
+        }
+
+        let network = address_network(&data[0..2]).ok_or_else(|| AddressError::InvalidPrefix(data[0..2].to_vec()))?;
+        let address_type = match BitcoinFormat::from_address_prefix(&data[0..2])? {
+            BitcoinFormat::P2PKH => BitcoinAddressType::P2PKH,
+            _ => BitcoinAddressType::P2SH,
+        };
+
+        Ok(BitcoinAddressInfo {
+            address_type,
+            network,
+            witness_version: None,
+            payload: data[1..].to_vec(),
+        })
+    }
+
+    /// Returns the classification of each of the given address strings, in order, without stopping at the first invalid address.
+    pub fn validate_batch<I: IntoIterator<Item = S>, S: AsRef<str>>(
+        addresses: I,
+    ) -> Vec<Result<BitcoinAddressInfo, AddressError>> {
+        addresses
+            .into_iter()
+            .map(|address| Self::classify(address.as_ref()))
+            .collect()
+    }
+
+    /// Returns the lowercase human-readable part, the witness program, and the type of the given
+    /// Bech32 or Bech32m address. Witness version 0 addresses must use the Bech32 checksum, and
+    /// witness version 1 to 16 addresses must use the Bech32m checksum, as specified in BIP350.
+    fn decode_witness_program(address: &str) -> Result<(String, WitnessProgram, BitcoinAddressType), AddressError> {
+        let (hrp, data, variant) = bech32m::decode(address)?;
+        let witness_program = match data.split_first() {
+            Some((version, program)) => WitnessProgram {
+                version: *version,
+                program: bech32m::convert_bits(program, 5, 8, false)?,
+            },
+            None => return Err(AddressError::InvalidAddress(address.into())),
+        };
+        witness_program.validate()?;
+
+        let address_type = match (witness_program.version, variant) {
+            (0, Variant::Bech32) => BitcoinAddressType::Bech32,
+            (1..=16, Variant::Bech32m) => BitcoinAddressType::Bech32m,
+            _ => return Err(AddressError::InvalidAddress(address.into())),
+        };
+        Ok((hrp, witness_program, address_type))
+    }
+
+    /// Returns the witness program of a Bech32 or Bech32m address.
+    pub(crate) fn to_witness_program(&self) -> Result<WitnessProgram, AddressError> {
+        let (_, witness_program, _) = Self::decode_witness_program(&self.address)?;
+        Ok(witness_program)
+    }
+
     /// Returns a redeem script for a given Bitcoin public key.
     fn create_redeem_script(public_key: &<Self as Address>::PublicKey) -> [u8; 22] {
         let mut redeem = [0u8; 22];
         redeem[1] = 0x14;
-        redeem[2..].copy_from_slice(&hash160(&public_key.to_secp256k1_public_key().serialize_compressed()));
+        // SegWit programs always commit to the compressed public key.
+        redeem[2..].copy_from_slice(&hash160(&public_key.to_compressed_bytes()));
         redeem
     }
 }
@@ -164,46 +306,51 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
     type Err = AddressError;
 
     fn from_str(address: &str) -> Result<Self, Self::Err> {
-        if address.len() < 14 || address.len() > 74 {
+        if address.len() < MIN_ADDRESS_LENGTH || address.len() > MAX_ADDRESS_LENGTH {
             return Err(AddressError::InvalidCharacterLength(address.len()));
         }
 
-        let prefix = &address.to_lowercase()[0..2];
+        // Base58 addresses may begin with the human-readable part of the network, e.g. "Ltc1...",
+        // so an address is only decoded as Bech32 if it is not a valid Base58Check encoding.
+        let hrp = String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32))?;
+        let is_bech32 = !hrp.is_empty() && address.to_lowercase().starts_with(&format!("{}1", hrp));
+        if is_bech32 && decode_check(address).is_err() {
+            let (hrp, witness_program, _) = Self::decode_witness_program(address)?;
+            // Check that the address prefix corresponds to the correct network.
+            let _ = check_address_prefix::<N>(hrp.as_bytes())?;
+
+            // Version 0 witness programs of 32 bytes are script hashes, and version 1 witness
+            // programs of 32 bytes are Taproot output keys.
+            let format = match (witness_program.version, witness_program.program.len()) {
+                (0, 32) => BitcoinFormat::P2WSH,
+                (1, 32) => BitcoinFormat::P2TR,
+                _ => BitcoinFormat::Bech32,
+            };
+
+            // Bech32 addresses are displayed in their canonical lowercase form.
+            return Ok(Self {
+                address: address.to_lowercase(),
+                format,
+                _network: PhantomData,
+            });
+        }
 
-        if let Ok(format) = BitcoinFormat::from_address_prefix(prefix.as_bytes()) {
-            if BitcoinFormat::Bech32 == format {
-                let bech32 = Bech32::from_str(&address)?;
-                if bech32.data().is_empty() {
-                    return Err(AddressError::InvalidAddress(address.to_owned()));
+        let data = match decode_check(address) {
+            Ok(data) => data,
+            Err(error) => {
+                // Check that a Bech32 address is not of another network.
+                if let Ok((hrp, _, _)) = bech32m::decode(address) {
+                    let _ = check_address_prefix::<N>(hrp.as_bytes())?;
                 }
-
-                let data = bech32.data();
-                let version = data[0].to_u8();
-                let mut program = Vec::from_base32(&data[1..])?;
-
-                let mut data = vec![version, program.len() as u8];
-                data.append(&mut program);
-
-                // Check that the witness program is valid.
-                let _ = WitnessProgram::new(data.as_slice())?;
-                // Check that the address prefix corresponds to the correct network.
-                let _ = N::from_address_prefix(prefix.as_bytes())?;
-
-                return Ok(Self {
-                    address: address.to_owned(),
-                    format: BitcoinFormat::Bech32,
-                    _network: PhantomData,
-                });
+                return Err(error.into());
             }
-        }
-
-        let data = address.from_base58()?;
-        if data.len() != 25 {
-            return Err(AddressError::InvalidByteLength(data.len()));
+        };
+        if data.len() != 21 {
+            return Err(AddressError::InvalidByteLength(data.len() + 4));
         }
 
         // Check that the address prefix corresponds to the correct network.
-        let _ = N::from_address_prefix(&data[0..2])?;
+        let _ = check_address_prefix::<N>(&data[0..2])?;
         let format = BitcoinFormat::from_address_prefix(&data[0..2])?;
 
         Ok(Self {
@@ -220,10 +367,37 @@ impl<N: BitcoinNetwork> fmt::Display for BitcoinAddress<N> {
     }
 }
 
+/// Represents the encoding of a Bitcoin address
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BitcoinAddressType {
+    /// Base58 Pay-to-Pubkey Hash, e.g. 1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT
+    P2PKH,
+    /// Base58 Pay-to-Script Hash, e.g. 3JvL6Ymt8MVWiCNHC7oWU6nLeHNJKLZGLN
+    P2SH,
+    /// Bech32 witness version 0, e.g. bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+    Bech32,
+    /// Bech32m witness version 1 and higher, e.g. bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0
+    Bech32m,
+}
+
+/// Represents the classification of a Bitcoin address
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitcoinAddressInfo {
+    /// The encoding of the address
+    pub address_type: BitcoinAddressType,
+    /// The name of the network of the address
+    pub network: &'static str,
+    /// The witness version of a Bech32 or Bech32m address
+    pub witness_version: Option<u8>,
+    /// The public key hash or script hash of a Base58 address, or the witness program of a Bech32 or Bech32m address
+    pub payload: Vec<u8>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::network::*;
+    use wagyu_model::base58::Base58Error;
     use wagyu_model::public_key::PublicKey;
 
     fn test_from_private_key<N: BitcoinNetwork>(
@@ -622,6 +796,14 @@ mod tests {
 
             let address = "3Pai7Ly86pddxxwZ7rUhXjRJwog4oKqNYK3Pai7Ly86pddxxwZ7rUhXjRJwog4oKqNYK";
             assert!(BitcoinAddress::<N>::from_str(address).is_err());
+
+            // Invalid address checksum
+
+            let address = "3Pai7Ly86pddxxwZ7rUhXjRJwog4oKqNYL";
+            match BitcoinAddress::<N>::from_str(address) {
+                Err(AddressError::Base58Error(Base58Error::InvalidChecksum(_, _))) => (),
+                result => panic!("expected an invalid checksum, found {:?}", result),
+            }
         }
     }
 
@@ -839,6 +1021,301 @@ mod tests {
         }
     }
 
+    mod bech32_regtest {
+        use super::*;
+
+        type N = Regtest;
+
+        // The keys and witness programs of the Bech32 testnet vectors, with the regtest
+        // human-readable part.
+        const KEYPAIRS: [(&str, &str); 5] = [
+            (
+                "cVQmTtLoCjDJAXVj778xyww1ZbpJQt7Vq9sDt8Mdmw97Rg7TaNes",
+                "bcrt1qmkvfprg8pkr3apv9gyykmhe26fexyla0unfacp",
+            ),
+            (
+                "cTxHRG8MgrnSQstuMs5VnQcFBjrs67NmiJGo1kevnJDS7QFGLUAi",
+                "bcrt1qfe0dnfpxp4c9lfdjzvmf5q72jg83emgk3jpt3y",
+            ),
+            (
+                "cSN1N2Vmhg9jPSUpXyQj8WbNUgeLHbC3Yj8SFX2N834YMepMwNZH",
+                "bcrt1qx4jm2s3ks5vadh2ja3flsn4ckjzhdxmxej6w40",
+            ),
+            (
+                "cMvmoqYYzr4dgzNZ22PvaqSnNx98evXc1b7m8FfK9SdCqhiWdP2c",
+                "bcrt1ql0g42pusevlgd0jh9gyr32s0h0pe96wp32e9xj",
+            ),
+            (
+                "cVodD5ifcBjYVUs19GLwz6YzU2hUhdNagBx9QQcZp7TgjLuuFYn3",
+                "bcrt1qwnh7hu5qfrjsk9pyn3vvmzr48v4l8kp47pkx36",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            KEYPAIRS.iter().for_each(|(private_key, address)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                test_from_private_key(address, &private_key, &BitcoinFormat::Bech32);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::Bech32);
+                assert!(BitcoinAddress::<Testnet>::from_str(address).is_err());
+                assert!(BitcoinAddress::<Mainnet>::from_str(address).is_err());
+            });
+        }
+
+        #[test]
+        fn base58() {
+            // Regtest shares the Base58 address prefixes of testnet.
+            let address = "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r";
+            test_from_str::<N>(address, &BitcoinFormat::P2PKH);
+
+            let private_key =
+                BitcoinPrivateKey::<N>::from_str("cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA").unwrap();
+            test_from_private_key(address, &private_key, &BitcoinFormat::P2PKH);
+            test_from_private_key(
+                "2NAUYAHhujozruyzpsFRP63mbrdaU5wnEpN",
+                &private_key,
+                &BitcoinFormat::P2SH_P2WPKH,
+            );
+            test_from_private_key(
+                "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080",
+                &private_key,
+                &BitcoinFormat::Bech32,
+            );
+            assert!(BitcoinAddress::<N>::from_str("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx").is_err());
+        }
+    }
+
+    mod p2tr_mainnet {
+        use super::*;
+        use crate::extended_private_key::BitcoinExtendedPrivateKey;
+        use wagyu_model::ExtendedPrivateKey;
+
+        type N = Mainnet;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki#test-vectors
+        const ACCOUNT_0: &str = "xprv9xgqHN7yz9MwCkxsBPN5qetuNdQSUttZNKw1dcYTV4mkaAFiBVGQziHs3NRSWMkCzvgjEe3n9xV8oYywvM8at9yRqyaZVz6TYYhX98VjsUk";
+
+        // (path, internal key, address)
+        const KEYPAIRS: [(&str, &str, &str); 3] = [
+            (
+                "m/0/0",
+                "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115",
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+            (
+                "m/0/1",
+                "83dfe85a3151d2517290da461fe2815591ef69f2b18a2ce63f01697a8b313145",
+                "bc1p4qhjn9zdvkux4e44uhx8tc55attvtyu358kutcqkudyccelu0was9fqzwh",
+            ),
+            (
+                "m/1/0",
+                "399f1b2f4393f29a18c937859c5dd8a77350103157eb880f02e8c08214277cef",
+                "bc1p3qkhfews2uk44qtvauqyr2ttdsw7svhkl9nkm9s9c3x4ax5h60wqwruhk7",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            let account = BitcoinExtendedPrivateKey::<N>::from_str(ACCOUNT_0).unwrap();
+            KEYPAIRS.iter().for_each(|(path, _, address)| {
+                let private_key = account.derive_path(path).unwrap().to_private_key();
+                test_from_private_key(address, &private_key, &BitcoinFormat::P2TR);
+            });
+        }
+
+        #[test]
+        fn from_public_key() {
+            KEYPAIRS.iter().for_each(|(_, internal_key, address)| {
+                let public_key = BitcoinPublicKey::<N>::from_str(internal_key).unwrap();
+                test_from_public_key(address, &public_key, &BitcoinFormat::P2TR);
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, _, address)| {
+                test_from_str::<N>(address, &BitcoinFormat::P2TR);
+                let info = BitcoinAddress::<N>::classify(address).unwrap();
+                assert_eq!(BitcoinAddressType::Bech32m, info.address_type);
+                assert_eq!(Some(1), info.witness_version);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            KEYPAIRS.iter().for_each(|(_, _, expected_address)| {
+                let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+
+        #[test]
+        fn from_public_key_dogecoin() {
+            let public_key = BitcoinPublicKey::<Dogecoin>::from_str(KEYPAIRS[0].1).unwrap();
+            assert!(BitcoinAddress::from_public_key(&public_key, &BitcoinFormat::P2TR).is_err());
+        }
+    }
+
+    mod bip173 {
+        use super::*;
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki#test-vectors
+        // Witness version 1 and higher addresses use the Bech32m checksum of BIP350.
+        const VALID_MAINNET: [(&str, BitcoinFormat); 4] = [
+            ("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4", BitcoinFormat::Bech32),
+            (
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                BitcoinFormat::Bech32,
+            ),
+            ("BC1SW50QGDZ25J", BitcoinFormat::Bech32),
+            ("bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs", BitcoinFormat::Bech32),
+        ];
+
+        const VALID_TESTNET: [(&str, BitcoinFormat); 2] = [
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                BitcoinFormat::P2WSH,
+            ),
+            (
+                "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+                BitcoinFormat::P2WSH,
+            ),
+        ];
+
+        const INVALID_MAINNET: [&str; 11] = [
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5", // invalid checksum
+            "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx", // witness version 1 with a Bech32 checksum
+            "BC1SW50QA3JX3S",                             // witness version 16 with a Bech32 checksum
+            "bc1zw508d6qejxtdg4y5r3zarvaryvg6kdaj",       // witness version 2 with a Bech32 checksum
+            "BC13W508D6QEJXTDG4Y5R3ZARVARY0C5XW7KN40WF2", // invalid witness version
+            "bc1rw5uspcuh",                               // invalid program length
+            "bc10w508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kw5rljs90", // invalid program length
+            "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",       // invalid program length for witness version 0
+            "bc1zw508d6qejxtdg4y5r3zarvaryvqyzf3du",      // zero padding of more than 4 bits
+            "bc1gmk9yu",                                  // empty data section
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7", // testnet address
+        ];
+
+        const INVALID_TESTNET: [&str; 5] = [
+            "tc1qw508d6qejxtdg4y5r3zarvary0c5xw7kg3g4ty", // invalid human-readable part
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sL5k7", // mixed case
+            "tb1pw508d6qejxtdg4y5r3zarqfsj6c3",           // invalid program length
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3pjxtptv", // non-zero padding in 8-to-5 conversion
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", // mainnet address
+        ];
+
+        #[test]
+        fn from_str_valid() {
+            VALID_MAINNET.iter().for_each(|(address, format)| {
+                test_from_str::<Mainnet>(&address.to_lowercase(), format);
+                let parsed = BitcoinAddress::<Mainnet>::from_str(address).unwrap();
+                assert_eq!(address.to_lowercase(), parsed.to_string());
+            });
+            VALID_TESTNET.iter().for_each(|(address, format)| {
+                test_from_str::<Testnet>(address, format);
+            });
+        }
+
+        #[test]
+        fn from_str_invalid() {
+            INVALID_MAINNET.iter().for_each(|address| {
+                assert!(BitcoinAddress::<Mainnet>::from_str(address).is_err());
+            });
+            INVALID_TESTNET.iter().for_each(|address| {
+                assert!(BitcoinAddress::<Testnet>::from_str(address).is_err());
+            });
+        }
+
+        #[test]
+        fn from_str_mixed_case() {
+            let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+            assert!(BitcoinAddress::<Mainnet>::from_str(address).is_ok());
+            assert!(BitcoinAddress::<Mainnet>::from_str(&address.to_uppercase()).is_ok());
+            let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kV8F3T4";
+            let error = BitcoinAddress::<Mainnet>::from_str(address).unwrap_err();
+            assert_eq!(
+                AddressError::InvalidAddress(address.into()).to_string(),
+                error.to_string()
+            );
+        }
+    }
+
+    mod verify_message {
+        use super::*;
+
+        type N = Mainnet;
+
+        const ADDRESS: &str = "1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV";
+        const MESSAGE: &str = "This is an example of a signed message.";
+        const SIGNATURE: &str =
+            "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=";
+
+        #[test]
+        fn verify_message() {
+            let address = BitcoinAddress::<N>::from_str(ADDRESS).unwrap();
+            assert!(address.verify_message(MESSAGE, SIGNATURE).unwrap());
+        }
+
+        #[test]
+        fn verify_modified_message() {
+            let address = BitcoinAddress::<N>::from_str(ADDRESS).unwrap();
+            assert!(!address
+                .verify_message("This is an example of a signed message!", SIGNATURE)
+                .unwrap());
+        }
+
+        #[test]
+        fn verify_other_address() {
+            let address = BitcoinAddress::<N>::from_str("1GUwicFwsZbdE3XyJYjmPryiiuTiK7mZgS").unwrap();
+            assert!(!address.verify_message(MESSAGE, SIGNATURE).unwrap());
+        }
+
+        #[test]
+        fn verify_formats() {
+            let private_key =
+                BitcoinPrivateKey::<N>::from_str("L4rK1yDtCWekvXuE6oXD9jCYfFNV2cWRpVuPLBcCU2z8TrisoyY1").unwrap();
+            [BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32]
+                .iter()
+                .for_each(|format| {
+                    let address = private_key.to_address(format).unwrap();
+                    assert!(address
+                        .verify_message(MESSAGE, &private_key.sign_message(MESSAGE))
+                        .unwrap());
+                });
+
+            let private_key = private_key.to_uncompressed();
+            let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+            assert!(address
+                .verify_message(MESSAGE, &private_key.sign_message(MESSAGE))
+                .unwrap());
+            assert!(!address.verify_message(MESSAGE, SIGNATURE).unwrap());
+        }
+
+        #[test]
+        fn verify_invalid_signature() {
+            let address = BitcoinAddress::<N>::from_str(ADDRESS).unwrap();
+            assert!(address.verify_message(MESSAGE, "not base64!").is_err());
+
+            let mut signature = base64::decode(SIGNATURE).unwrap();
+            assert!(matches!(
+                address.verify_message(MESSAGE, &base64::encode(&signature[1..])),
+                Err(AddressError::InvalidSignatureLength(64))
+            ));
+
+            signature[0] = 26;
+            assert!(matches!(
+                address.verify_message(MESSAGE, &base64::encode(&signature)),
+                Err(AddressError::InvalidSignatureHeader(26))
+            ));
+        }
+    }
+
     mod p2wsh_testnet {
         use super::*;
 
@@ -873,4 +1350,269 @@ mod tests {
             });
         }
     }
+
+    mod classify {
+        use super::*;
+
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617";
+
+        /// Returns the given address with its last character replaced, in the same case.
+        fn corrupt(address: &str) -> String {
+            let (body, last) = address.split_at(address.len() - 1);
+            match last {
+                "q" => format!("{}p", body),
+                "Q" => format!("{}P", body),
+                _ if last.to_uppercase() == last => format!("{}Q", body),
+                _ => format!("{}q", body),
+            }
+        }
+
+        #[test]
+        fn base58_and_bech32() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let public_key = private_key.to_public_key();
+            let key_hash = hash160(&public_key.to_secp256k1_public_key().serialize_compressed()).to_vec();
+            let script_hash = hash160(&BitcoinAddress::<N>::create_redeem_script(&public_key)).to_vec();
+
+            [
+                (BitcoinFormat::P2PKH, BitcoinAddressType::P2PKH, None, &key_hash),
+                (BitcoinFormat::P2SH_P2WPKH, BitcoinAddressType::P2SH, None, &script_hash),
+                (BitcoinFormat::Bech32, BitcoinAddressType::Bech32, Some(0), &key_hash),
+            ]
+            .iter()
+            .for_each(|(format, address_type, witness_version, payload)| {
+                let address = public_key.to_address(format).unwrap().to_string();
+                let info = BitcoinAddress::<N>::classify(&address).unwrap();
+                assert_eq!(*address_type, info.address_type);
+                assert_eq!("mainnet", info.network);
+                assert_eq!(*witness_version, info.witness_version);
+                assert_eq!(**payload, info.payload);
+
+                assert!(BitcoinAddress::<N>::classify(&corrupt(&address)).is_err());
+                assert_eq!(info, BitcoinAddress::<Testnet>::classify(&address).unwrap());
+            });
+        }
+
+        fn test_network<M: BitcoinNetwork>(expected_network: &str, formats: &[BitcoinFormat]) {
+            let secret_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY)
+                .unwrap()
+                .to_secp256k1_secret_key();
+            let private_key = BitcoinPrivateKey::<M>::from_secp256k1_secret_key(&secret_key, true);
+            formats.iter().for_each(|format| {
+                let address = private_key.to_address(format).unwrap().to_string();
+                assert_eq!(
+                    expected_network,
+                    BitcoinAddress::<N>::classify(&address).unwrap().network
+                );
+            });
+        }
+
+        #[test]
+        fn network() {
+            let formats = [BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32];
+            test_network::<Mainnet>("mainnet", &formats);
+            test_network::<Testnet>("testnet", &formats);
+            test_network::<Litecoin>("litecoin", &[BitcoinFormat::P2PKH, BitcoinFormat::Bech32]);
+            test_network::<Dogecoin>("dogecoin", &[BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH]);
+            // Regtest shares the Base58 prefixes of testnet, but not its human-readable part.
+            test_network::<Regtest>("testnet", &[BitcoinFormat::P2PKH]);
+            test_network::<Regtest>("regtest", &[BitcoinFormat::Bech32]);
+
+            assert_eq!(
+                "testnet",
+                BitcoinAddress::<N>::classify("tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx")
+                    .unwrap()
+                    .network
+            );
+        }
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-v0-v16-native-segregated-witness-addresses
+        // (address, address type, witness version, witness program)
+        const VALID_BIP350: [(&str, BitcoinAddressType, u8, &str); 8] = [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                BitcoinAddressType::Bech32,
+                0,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                BitcoinAddressType::Bech32,
+                0,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                BitcoinAddressType::Bech32m,
+                1,
+                "751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            ("BC1SW50QGDZ25J", BitcoinAddressType::Bech32m, 16, "751e"),
+            (
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+                BitcoinAddressType::Bech32m,
+                2,
+                "751e76e8199196d454941c45d1b3a323",
+            ),
+            (
+                "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+                BitcoinAddressType::Bech32,
+                0,
+                "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                BitcoinAddressType::Bech32m,
+                1,
+                "000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                BitcoinAddressType::Bech32m,
+                1,
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-v0-v16-native-segregated-witness-addresses
+        const INVALID_BIP350: [&str; 15] = [
+            "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut", // invalid human-readable part
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd", // invalid checksum (Bech32 instead of Bech32m)
+            "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf", // invalid checksum (Bech32 instead of Bech32m)
+            "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL", // invalid checksum (Bech32 instead of Bech32m)
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh", // invalid checksum (Bech32m instead of Bech32)
+            "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47", // invalid checksum (Bech32m instead of Bech32)
+            "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4", // invalid character in checksum
+            "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R", // invalid witness version
+            "bc1pw5dgrnzv",                                                   // invalid program length (1 byte)
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav", // invalid program length (41 bytes)
+            "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P", // invalid program length for witness version 0
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq", // mixed case
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf", // zero padding of more than 4 bits
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j", // non-zero padding in 8-to-5 conversion
+            "bc1gmk9yu",                            // empty data section
+        ];
+
+        #[test]
+        fn bip350() {
+            VALID_BIP350
+                .iter()
+                .for_each(|(address, address_type, witness_version, program)| {
+                    let info = BitcoinAddress::<N>::classify(address).unwrap();
+                    assert_eq!(*address_type, info.address_type);
+                    assert_eq!(Some(*witness_version), info.witness_version);
+                    assert_eq!(*program, hex::encode(&info.payload));
+
+                    assert!(BitcoinAddress::<N>::classify(&corrupt(address)).is_err());
+                });
+        }
+
+        #[test]
+        fn invalid_bip350() {
+            INVALID_BIP350
+                .iter()
+                .for_each(|address| assert!(BitcoinAddress::<N>::classify(address).is_err()));
+        }
+
+        #[test]
+        fn from_str_bip350() {
+            // An address is parsed as an address of a network if and only if it is classified as one.
+            fn test_agreement<M: BitcoinNetwork>(address: &str) {
+                let info = BitcoinAddress::<N>::classify(address).ok();
+                let parsed = BitcoinAddress::<M>::from_str(address).ok();
+                match (info, parsed) {
+                    (Some(info), Some(parsed)) => {
+                        assert_eq!(M::NAME, info.network);
+                        assert_eq!(info.payload, parsed.to_witness_program().unwrap().program);
+                    }
+                    (Some(info), None) => assert_ne!(M::NAME, info.network, "{}", address),
+                    (None, parsed) => assert_eq!(None, parsed, "{}", address),
+                }
+            }
+
+            VALID_BIP350
+                .iter()
+                .map(|(address, _, _, _)| *address)
+                .chain(INVALID_BIP350.iter().cloned())
+                .for_each(|address| {
+                    test_agreement::<Mainnet>(address);
+                    test_agreement::<Testnet>(address);
+                });
+        }
+
+        #[test]
+        fn validate_batch() {
+            let addresses = [
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+            ];
+            let results = BitcoinAddress::<N>::validate_batch(addresses.iter());
+            assert_eq!(3, results.len());
+            assert!(results[0].is_ok());
+            assert!(results[1].is_err());
+            assert_eq!(BitcoinAddressType::Bech32m, results[2].as_ref().unwrap().address_type);
+        }
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        fn format() -> impl Strategy<Value = BitcoinFormat> {
+            prop_oneof![
+                Just(BitcoinFormat::P2PKH),
+                Just(BitcoinFormat::P2SH_P2WPKH),
+                Just(BitcoinFormat::Bech32),
+                Just(BitcoinFormat::P2TR),
+            ]
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(private_key: BitcoinPrivateKey<N>, format in format()) {
+                let address = BitcoinAddress::from_private_key(&private_key, &format).unwrap();
+                let parsed = BitcoinAddress::<N>::from_str(&address.to_string()).unwrap();
+                prop_assert_eq!(&address, &parsed);
+                prop_assert_eq!(&format, &parsed.format);
+                prop_assert!(BitcoinAddress::<N>::classify(&address.to_string()).is_ok());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinAddress<N>>(&inp)?;
+                let _ = BitcoinAddress::<N>::classify(&inp);
+            }
+
+            #[test]
+            fn from_str_base58(prefix: u8, payload in vec(any::<u8>(), 0..30)) {
+                let mut data = vec![prefix];
+                data.extend(payload);
+                let address = encode_check(&data);
+                if let Ok(parsed) = BitcoinAddress::<N>::from_str(&address) {
+                    prop_assert_eq!(address, parsed.to_string());
+                }
+                let _ = BitcoinAddress::<N>::classify(&encode_check(&data));
+            }
+
+            #[test]
+            fn from_str_bech32(hrp in "bc|tb|[a-z]{1,4}", data in vec(0..32u8, 0..70), uppercase: bool) {
+                let data = data.into_iter().map(|value| u5::try_from_u8(value).unwrap()).collect();
+                let address = Bech32::new(hrp, data).unwrap().to_string();
+                let address = match uppercase {
+                    true => address.to_uppercase(),
+                    false => address,
+                };
+                if let Ok(parsed) = BitcoinAddress::<N>::from_str(&address) {
+                    prop_assert_eq!(address.to_lowercase(), parsed.to_string());
+                }
+                let _ = BitcoinAddress::<N>::classify(&address);
+            }
+        }
+    }
 }
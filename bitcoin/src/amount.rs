@@ -1,8 +1,12 @@
 use wagyu_model::no_std::*;
-use wagyu_model::{Amount, AmountError};
+use wagyu_model::{base_units_to_decimal, decimal_to_base_units, Amount, AmountError};
 
-use core::fmt;
-use serde::Serialize;
+use core::{
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 // Number of satoshis (base unit) per BTC
 const COIN: i64 = 1_0000_0000;
@@ -11,9 +15,10 @@ const COIN: i64 = 1_0000_0000;
 const MAX_COINS: i64 = 21_000_000 * COIN;
 
 /// Represents the amount of Bitcoin in satoshis
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BitcoinAmount(pub i64);
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Denomination {
     // sat
     Satoshi,
@@ -60,6 +65,22 @@ impl fmt::Display for Denomination {
     }
 }
 
+impl FromStr for Denomination {
+    type Err = AmountError;
+
+    fn from_str(denomination: &str) -> Result<Self, Self::Err> {
+        match denomination.to_ascii_lowercase().as_str() {
+            "sat" | "sats" | "satoshi" | "satoshis" => Ok(Denomination::Satoshi),
+            "ubtc" | "bits" => Ok(Denomination::MicroBit),
+            "mbtc" => Ok(Denomination::MilliBit),
+            "cbtc" => Ok(Denomination::CentiBit),
+            "dbtc" => Ok(Denomination::DeciBit),
+            "btc" => Ok(Denomination::Bitcoin),
+            _ => Err(AmountError::InvalidDenomination(denomination.into())),
+        }
+    }
+}
+
 impl Amount for BitcoinAmount {}
 
 impl BitcoinAmount {
@@ -71,13 +92,13 @@ impl BitcoinAmount {
     pub const ONE_BTC: BitcoinAmount = BitcoinAmount(COIN);
 
     pub fn from_satoshi(satoshis: i64) -> Result<Self, AmountError> {
-        if -MAX_COINS <= satoshis && satoshis <= MAX_COINS {
+        if (-MAX_COINS..=MAX_COINS).contains(&satoshis) {
             Ok(Self(satoshis))
         } else {
-            return Err(AmountError::AmountOutOfBounds(
+            Err(AmountError::AmountOutOfBounds(
                 satoshis.to_string(),
                 MAX_COINS.to_string(),
-            ));
+            ))
         }
     }
 
@@ -111,18 +132,111 @@ impl BitcoinAmount {
         Self::from_satoshi(satoshis)
     }
 
-    pub fn add(self, b: Self) -> Result<Self, AmountError> {
-        Self::from_satoshi(self.0 + b.0)
+    /// Returns the amount of the given decimal value in the given denomination, e.g. "0.001"
+    /// bitcoin, or an error if the value has more decimal places than the denomination allows.
+    pub fn from_denomination(value: &str, denomination: Denomination) -> Result<Self, AmountError> {
+        let (is_negative, magnitude) = match value.strip_prefix('-') {
+            Some(magnitude) => (true, magnitude),
+            None => (false, value),
+        };
+
+        let satoshis = decimal_to_base_units(magnitude, denomination.precision())?;
+        let satoshis = i64::from_str(&satoshis)
+            .map_err(|_| AmountError::AmountOutOfBounds(value.into(), MAX_COINS.to_string()))?;
+
+        Self::from_satoshi(if is_negative { -satoshis } else { satoshis })
+    }
+
+    /// Returns the sum of the amounts, or an error if it exceeds the supply bounds.
+    pub fn checked_add(self, b: Self) -> Result<Self, AmountError> {
+        match self.0.checked_add(b.0) {
+            Some(satoshis) => Self::from_satoshi(satoshis),
+            None => Err(AmountError::Overflow(format!("{} + {}", self.0, b.0))),
+        }
+    }
+
+    /// Returns the difference of the amounts, or an error if it exceeds the supply bounds.
+    pub fn checked_sub(self, b: Self) -> Result<Self, AmountError> {
+        match self.0.checked_sub(b.0) {
+            Some(satoshis) => Self::from_satoshi(satoshis),
+            None => Err(AmountError::Overflow(format!("{} - {}", self.0, b.0))),
+        }
+    }
+
+    /// Returns the amount multiplied by the given factor, or an error if it exceeds the supply bounds.
+    pub fn checked_mul(self, factor: i64) -> Result<Self, AmountError> {
+        match self.0.checked_mul(factor) {
+            Some(satoshis) => Self::from_satoshi(satoshis),
+            None => Err(AmountError::Overflow(format!("{} * {}", self.0, factor))),
+        }
     }
+}
+
+impl Add for BitcoinAmount {
+    type Output = Result<Self, AmountError>;
 
-    pub fn sub(self, b: BitcoinAmount) -> Result<Self, AmountError> {
-        Self::from_satoshi(self.0 - b.0)
+    fn add(self, b: Self) -> Self::Output {
+        self.checked_add(b)
+    }
+}
+
+impl Sub for BitcoinAmount {
+    type Output = Result<Self, AmountError>;
+
+    fn sub(self, b: Self) -> Self::Output {
+        self.checked_sub(b)
+    }
+}
+
+impl FromStr for BitcoinAmount {
+    type Err = AmountError;
+
+    /// Returns the amount of a decimal value and its denomination, e.g. "0.001 btc" or "546 sat".
+    fn from_str(amount: &str) -> Result<Self, Self::Err> {
+        let mut parts = amount.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(value), Some(denomination), None) => {
+                Self::from_denomination(value, Denomination::from_str(denomination)?)
+            }
+            _ => Err(AmountError::InvalidAmount(amount.into())),
+        }
     }
 }
 
 impl fmt::Display for BitcoinAmount {
+    /// Formats the amount in bitcoin from 0.001 BTC, and in satoshi below it, e.g. "0.001 BTC" or "546 satoshi".
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0.to_string())
+        let satoshis = self.0.to_string();
+        let (sign, magnitude) = match satoshis.strip_prefix('-') {
+            Some(magnitude) => ("-", magnitude),
+            None => ("", satoshis.as_str()),
+        };
+
+        let denomination = match self.0.unsigned_abs() >= 10_u64.pow(Denomination::MilliBit.precision()) {
+            true => Denomination::Bitcoin,
+            false => Denomination::Satoshi,
+        };
+        write!(
+            f,
+            "{}{} {}",
+            sign,
+            base_units_to_decimal(magnitude, denomination.precision()),
+            denomination
+        )
+    }
+}
+
+impl Serialize for BitcoinAmount {
+    /// Serializes the amount as its number of satoshis in a string, e.g. "100000".
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for BitcoinAmount {
+    /// Deserializes the amount from its number of satoshis in a string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_denomination(&String::deserialize(deserializer)?, Denomination::Satoshi).map_err(de::Error::custom)
     }
 }
 
@@ -176,6 +290,16 @@ mod tests {
         assert_eq!(result, a.sub(b).unwrap());
     }
 
+    fn test_from_str(amount: &str, expected_amount: BitcoinAmount) {
+        let amount = BitcoinAmount::from_str(amount).unwrap();
+        assert_eq!(expected_amount, amount)
+    }
+
+    fn test_display(amount: BitcoinAmount, expected: &str) {
+        assert_eq!(expected, amount.to_string());
+        assert_eq!(amount, BitcoinAmount::from_str(expected).unwrap());
+    }
+
     pub struct AmountDenominationTestCase {
         satoshi: i64,
         micro_bit: i64,
@@ -274,6 +398,53 @@ mod tests {
         }
     }
 
+    mod valid_parsing {
+        use super::*;
+
+        const TEST_AMOUNTS: [(&str, i64); 10] = [
+            ("0.001 btc", 100000),
+            ("0.001 BTC", 100000),
+            ("0.00000001 btc", 1),
+            ("21000000 btc", 2100000000000000),
+            ("-0.5 btc", -50000000),
+            ("1.5 mBTC", 150000),
+            ("0.00001 mbtc", 1),
+            ("100 bits", 10000),
+            ("546 sat", 546),
+            ("  7   satoshi ", 7),
+        ];
+
+        #[test]
+        fn test_valid_from_str() {
+            TEST_AMOUNTS
+                .iter()
+                .for_each(|(amount, satoshis)| test_from_str(amount, BitcoinAmount(*satoshis)));
+        }
+
+        #[test]
+        fn test_valid_display() {
+            test_display(BitcoinAmount(0), "0 satoshi");
+            test_display(BitcoinAmount(546), "546 satoshi");
+            test_display(BitcoinAmount(99999), "99999 satoshi");
+            test_display(BitcoinAmount(100000), "0.001 BTC");
+            test_display(BitcoinAmount(150000000), "1.5 BTC");
+            test_display(BitcoinAmount(-150000000), "-1.5 BTC");
+            test_display(BitcoinAmount(2100000000000000), "21000000 BTC");
+            test_display(BitcoinAmount(2099999999999999), "20999999.99999999 BTC");
+        }
+
+        #[test]
+        fn test_valid_serde() {
+            let amount = BitcoinAmount(100000);
+            assert_eq!("\"100000\"", serde_json::to_string(&amount).unwrap());
+            assert_eq!(amount, serde_json::from_str("\"100000\"").unwrap());
+            assert_eq!(
+                BitcoinAmount(-546),
+                serde_json::from_str::<BitcoinAmount>("\"-546\"").unwrap()
+            );
+        }
+    }
+
     mod valid_arithmetic {
         use super::*;
 
@@ -296,6 +467,17 @@ mod tests {
         fn test_valid_subtraction() {
             TEST_VALUES.iter().for_each(|(a, b, c)| test_subtraction(c, b, a));
         }
+
+        #[test]
+        fn test_valid_multiplication() {
+            assert_eq!(BitcoinAmount(300000), BitcoinAmount(100000).checked_mul(3).unwrap());
+            assert_eq!(BitcoinAmount(-546), BitcoinAmount(546).checked_mul(-1).unwrap());
+            assert_eq!(BitcoinAmount::ZERO, BitcoinAmount::ONE_BTC.checked_mul(0).unwrap());
+            assert_eq!(
+                BitcoinAmount(2100000000000000),
+                BitcoinAmount::ONE_BTC.checked_mul(21000000).unwrap()
+            );
+        }
     }
 
     mod test_invalid {
@@ -467,6 +649,75 @@ mod tests {
             }
         }
 
+        mod invalid_parsing {
+            use super::*;
+
+            #[test]
+            fn test_invalid_precision() {
+                [
+                    "0.000000001 btc",
+                    "1.000000000 btc",
+                    "1.000001 mbtc",
+                    "0.001 bits",
+                    "0.5 sat",
+                ]
+                .iter()
+                .for_each(|amount| match BitcoinAmount::from_str(amount) {
+                    Err(AmountError::InvalidPrecision(..)) => (),
+                    result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                });
+            }
+
+            #[test]
+            fn test_invalid_scientific_notation() {
+                [
+                    "1e-3 btc",
+                    "1E8 sat",
+                    "5e2 mbtc",
+                    "0x10 sat",
+                    "+1 btc",
+                    "--1 btc",
+                    "1,000 sat",
+                ]
+                .iter()
+                .for_each(|amount| match BitcoinAmount::from_str(amount) {
+                    Err(AmountError::InvalidAmount(_)) => (),
+                    result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                });
+            }
+
+            #[test]
+            fn test_invalid_format() {
+                ["", "1", "1btc", "btc", "1 btc sat"]
+                    .iter()
+                    .for_each(|amount| match BitcoinAmount::from_str(amount) {
+                        Err(AmountError::InvalidAmount(_)) => (),
+                        result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                    });
+                match BitcoinAmount::from_str("1 doge") {
+                    Err(AmountError::InvalidDenomination(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+            }
+
+            #[test]
+            fn test_invalid_out_of_bounds() {
+                ["21000000.00000001 btc", "-21000001 btc", "99999999999999999999 sat"]
+                    .iter()
+                    .for_each(|amount| match BitcoinAmount::from_str(amount) {
+                        Err(AmountError::AmountOutOfBounds(..)) => (),
+                        result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                    });
+            }
+
+            #[test]
+            fn test_invalid_serde() {
+                ["100000", "\"1e5\"", "\"0.001 btc\"", "\"2100000000000001\""]
+                    .iter()
+                    .for_each(|json| assert!(serde_json::from_str::<BitcoinAmount>(json).is_err()));
+            }
+        }
+
         mod invalid_arithmetic {
             use super::*;
 
@@ -492,6 +743,26 @@ mod tests {
             fn test_invalid_subtraction() {
                 TEST_VALUES.iter().for_each(|(a, b, c)| test_subtraction(a, b, c));
             }
+
+            #[test]
+            fn test_overflow() {
+                match BitcoinAmount(i64::MAX).checked_add(BitcoinAmount::ONE_SAT) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                match BitcoinAmount(i64::MIN).checked_sub(BitcoinAmount::ONE_SAT) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                match BitcoinAmount::ONE_BTC.checked_mul(i64::MAX) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                match BitcoinAmount::ONE_BTC.checked_mul(21000001) {
+                    Err(AmountError::AmountOutOfBounds(..)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+            }
         }
     }
 }
@@ -0,0 +1,284 @@
+//!
+//! BIP38
+//!
+//! This module encrypts private keys with a passphrase for paper wallets, as specified in
+//! BIP38 for keys without EC multiplication. The passphrase and the checksum of the P2PKH address
+//! of the key derive a 64-byte key with scrypt, whose first half is XORed with the private key
+//! and whose second half encrypts the result with AES-256. The address checksum is kept in the
+//! encrypted key, so that a wrong passphrase is detected on decryption.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    base58::{decode_check, encode_check, Base58Error},
+    crypto::{checksum, scrypt},
+    Address, AddressError, PrivateKeyError,
+};
+
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, NewBlockCipher};
+use aes::Aes256;
+use unicode_normalization::UnicodeNormalization;
+
+/// The prefix of an encrypted private key without EC multiplication, encoded as `6P`
+pub const BIP38_PREFIX: [u8; 2] = [0x01, 0x42];
+
+/// The flag byte of an encrypted private key without EC multiplication
+const BIP38_FLAG: u8 = 0xc0;
+
+/// The flag bit of an encrypted private key of a compressed public key
+const BIP38_COMPRESSED_FLAG: u8 = 0x20;
+
+/// The scrypt parameters of BIP38, N = 2^14, r = 8, p = 8
+const BIP38_SCRYPT_N: usize = 1 << 14;
+const BIP38_SCRYPT_R: usize = 8;
+const BIP38_SCRYPT_P: usize = 8;
+
+#[derive(Debug, Fail)]
+pub enum Bip38Error {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}", _0)]
+    Base58Error(Base58Error),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid encrypted private key byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid encrypted private key flag: {:#04x}", _0)]
+    InvalidFlag(u8),
+
+    #[fail(display = "invalid encrypted private key prefix: {:?}", _0)]
+    InvalidPrefix(Vec<u8>),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(PrivateKeyError),
+
+    #[fail(display = "EC multiplied encrypted private keys are not supported")]
+    UnsupportedEcMultiply,
+
+    #[fail(display = "wrong passphrase")]
+    WrongPassphrase,
+}
+
+impl From<AddressError> for Bip38Error {
+    fn from(error: AddressError) -> Self {
+        Bip38Error::AddressError(error)
+    }
+}
+
+impl From<Base58Error> for Bip38Error {
+    fn from(error: Base58Error) -> Self {
+        Bip38Error::Base58Error(error)
+    }
+}
+
+impl From<PrivateKeyError> for Bip38Error {
+    fn from(error: PrivateKeyError) -> Self {
+        Bip38Error::PrivateKeyError(error)
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinPrivateKey<N> {
+    /// Returns the BIP38 encrypted private key of the given passphrase, a 58-character string
+    /// beginning with `6P`, which keeps the compression of the private key.
+    pub fn to_bip38(&self, passphrase: &str) -> Result<String, Bip38Error> {
+        let address_hash = bip38_address_hash(self)?;
+        let (derived_half1, derived_half2) = bip38_derived_key(passphrase, &address_hash);
+
+        let mut block = self.to_secp256k1_secret_key().serialize();
+        block
+            .iter_mut()
+            .zip(derived_half1.iter())
+            .for_each(|(byte, key)| *byte ^= key);
+
+        let cipher = Aes256::new(GenericArray::from_slice(&derived_half2));
+        block.chunks_mut(16).for_each(|chunk| {
+            cipher.encrypt_block(GenericArray::from_mut_slice(chunk));
+        });
+
+        let flag = match self.is_compressed() {
+            true => BIP38_FLAG | BIP38_COMPRESSED_FLAG,
+            false => BIP38_FLAG,
+        };
+
+        let mut data = Vec::with_capacity(39);
+        data.extend_from_slice(&BIP38_PREFIX);
+        data.push(flag);
+        data.extend_from_slice(&address_hash);
+        data.extend_from_slice(&block);
+        Ok(encode_check(&data))
+    }
+
+    /// Returns the private key of the given BIP38 encrypted private key and passphrase, or an
+    /// error if the passphrase does not decrypt the key of the address hash it commits to.
+    pub fn from_bip38(encrypted: &str, passphrase: &str) -> Result<Self, Bip38Error> {
+        let data = decode_check(encrypted)?;
+        if data.len() != 39 {
+            return Err(Bip38Error::InvalidByteLength(data.len()));
+        }
+
+        match &data[0..2] {
+            prefix if prefix == BIP38_PREFIX => (),
+            [0x01, 0x43] => return Err(Bip38Error::UnsupportedEcMultiply),
+            prefix => return Err(Bip38Error::InvalidPrefix(prefix.to_vec())),
+        }
+
+        let flag = data[2];
+        if flag & !BIP38_COMPRESSED_FLAG != BIP38_FLAG {
+            return Err(Bip38Error::InvalidFlag(flag));
+        }
+        let compressed = flag & BIP38_COMPRESSED_FLAG != 0;
+
+        let mut address_hash = [0u8; 4];
+        address_hash.copy_from_slice(&data[3..7]);
+        let (derived_half1, derived_half2) = bip38_derived_key(passphrase, &address_hash);
+
+        let mut block = [0u8; 32];
+        block.copy_from_slice(&data[7..39]);
+        let cipher = Aes256::new(GenericArray::from_slice(&derived_half2));
+        block.chunks_mut(16).for_each(|chunk| {
+            cipher.decrypt_block(GenericArray::from_mut_slice(chunk));
+        });
+        block
+            .iter_mut()
+            .zip(derived_half1.iter())
+            .for_each(|(byte, key)| *byte ^= key);
+
+        // A wrong passphrase decrypts to a different, possibly invalid, private key.
+        let secret_key = secp256k1::SecretKey::parse(&block).map_err(|_| Bip38Error::WrongPassphrase)?;
+        let private_key = Self::from_secp256k1_secret_key(&secret_key, compressed);
+        match bip38_address_hash(&private_key)? == address_hash {
+            true => Ok(private_key),
+            false => Err(Bip38Error::WrongPassphrase),
+        }
+    }
+}
+
+/// Returns the first 4 bytes of the double SHA-256 of the P2PKH address of the private key.
+fn bip38_address_hash<N: BitcoinNetwork>(private_key: &BitcoinPrivateKey<N>) -> Result<[u8; 4], Bip38Error> {
+    let address = BitcoinAddress::<N>::from_private_key(private_key, &BitcoinFormat::P2PKH)?;
+    let mut address_hash = [0u8; 4];
+    address_hash.copy_from_slice(&checksum(address.to_string().as_bytes())[0..4]);
+    Ok(address_hash)
+}
+
+/// Returns the halves of the 64-byte scrypt key of the NFC normalized passphrase, salted
+/// with the address hash.
+fn bip38_derived_key(passphrase: &str, address_hash: &[u8; 4]) -> ([u8; 32], [u8; 32]) {
+    let passphrase = passphrase.nfc().collect::<String>();
+
+    let mut derived_key = [0u8; 64];
+    scrypt(
+        passphrase.as_bytes(),
+        address_hash,
+        BIP38_SCRYPT_N,
+        BIP38_SCRYPT_R,
+        BIP38_SCRYPT_P,
+        &mut derived_key,
+    );
+
+    let mut derived_half1 = [0u8; 32];
+    let mut derived_half2 = [0u8; 32];
+    derived_half1.copy_from_slice(&derived_key[0..32]);
+    derived_half2.copy_from_slice(&derived_key[32..64]);
+    (derived_half1, derived_half2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    // (passphrase, encrypted_private_key, private_key), the test vectors of BIP38
+    const ENCRYPTED_PRIVATE_KEYS: [(&str, &str, &str); 5] = [
+        (
+            "TestingOneTwoThree",
+            "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg",
+            "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR",
+        ),
+        (
+            "Satoshi",
+            "6PRNFFkZc2NZ6dJqFfhRoFNMR9Lnyj7dYGrzdgXXVMXcxoKTePPX1dWByq",
+            "5HtasZ6ofTHP6HCwTqTkLDuLQisYPah7aUnSKfC7h4hMUVw2gi5",
+        ),
+        (
+            "\u{03d2}\u{0301}\u{0000}\u{10400}\u{1f4a9}",
+            "6PRW5o9FLp4gJDDVqJQKJFTpMvdsSGJxMYHtHaQBF3ooa8mwD69bapcDQn",
+            "5Jajm8eQ22H3pGWLEVCXyvND8dQZhiQhoLJNKjYXk9roUFTMSZ4",
+        ),
+        (
+            "TestingOneTwoThree",
+            "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+            "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP",
+        ),
+        (
+            "Satoshi",
+            "6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7",
+            "KwYgW8gcxj1JWJXhPSu4Fqwzfhp5Yfi42mdYmMa4XqK7NJxXUSK7",
+        ),
+    ];
+
+    #[test]
+    fn to_bip38() {
+        ENCRYPTED_PRIVATE_KEYS
+            .iter()
+            .for_each(|(passphrase, encrypted_private_key, private_key)| {
+                let private_key = BitcoinPrivateKey::<N>::from_str(private_key).unwrap();
+                assert_eq!(*encrypted_private_key, private_key.to_bip38(passphrase).unwrap());
+            });
+    }
+
+    #[test]
+    fn from_bip38() {
+        ENCRYPTED_PRIVATE_KEYS
+            .iter()
+            .for_each(|(passphrase, encrypted_private_key, private_key)| {
+                let decrypted = BitcoinPrivateKey::<N>::from_bip38(encrypted_private_key, passphrase).unwrap();
+                assert_eq!(*private_key, decrypted.to_string());
+            });
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let (_, encrypted_private_key, _) = ENCRYPTED_PRIVATE_KEYS[3];
+        match BitcoinPrivateKey::<N>::from_bip38(encrypted_private_key, "TestingOneTwoThre") {
+            Err(Bip38Error::WrongPassphrase) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn invalid_encrypted_private_key() {
+        // An EC multiplied encrypted private key of the BIP38 test vectors
+        let encrypted_private_key = "6PfQu77ygVyJLZjfvMLyhLMQbYnu5uguoJJ4kMCLqWwPEdfpwANVS76gTX";
+        match BitcoinPrivateKey::<N>::from_bip38(encrypted_private_key, "TestingOneTwoThree") {
+            Err(Bip38Error::UnsupportedEcMultiply) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let (passphrase, encrypted_private_key, _) = ENCRYPTED_PRIVATE_KEYS[0];
+        let mut data = decode_check(encrypted_private_key).unwrap();
+        data[2] = 0xc1;
+        match BitcoinPrivateKey::<N>::from_bip38(&encode_check(&data), passphrase) {
+            Err(Bip38Error::InvalidFlag(0xc1)) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let wif = "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR";
+        match BitcoinPrivateKey::<N>::from_bip38(wif, "TestingOneTwoThree") {
+            Err(Bip38Error::InvalidByteLength(33)) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//!
+//! BIP85 - Deterministic Entropy From BIP32 Keychains
+//! https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki
+//!
+//! This module derives child mnemonics, private keys, and raw entropy from a single master
+//! extended private key, so that one backup of the master key covers every derived wallet.
+//!
+
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::mnemonic::BitcoinMnemonic;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::wordlist::{BitcoinWordlist, Language};
+use wagyu_model::no_std::*;
+use wagyu_model::{ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, MnemonicError};
+
+use hmac::{Hmac, Mac};
+use secp256k1::SecretKey;
+use sha2::Sha512;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// The purpose of a BIP85 derivation path, the ASCII digits of "BIPS" on a keypad.
+const BIP85_PURPOSE: u32 = 83696968;
+
+/// Represents a BIP85 application, which determines the derivation path and length of the entropy
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Bip85Application {
+    /// BIP39 mnemonic - m/83696968'/39'/{language}'/{words}'/{index}'
+    Bip39 { language: Language, words: u8 },
+    /// WIF private key - m/83696968'/2'/{index}'
+    Wif,
+    /// Hex - m/83696968'/128169'/{num_bytes}'/{index}'
+    Hex { num_bytes: u8 },
+}
+
+impl Bip85Application {
+    /// Returns the derivation path of the application for the given index.
+    pub fn to_vec(&self, index: u32) -> Result<Vec<ChildIndex>, ExtendedPrivateKeyError> {
+        let mut path = vec![ChildIndex::hardened(BIP85_PURPOSE)?];
+        match self {
+            Bip85Application::Bip39 { language, words } => path.extend_from_slice(&[
+                ChildIndex::hardened(39)?,
+                ChildIndex::hardened(Self::language_code(language))?,
+                ChildIndex::hardened(*words as u32)?,
+            ]),
+            Bip85Application::Wif => path.push(ChildIndex::hardened(2)?),
+            Bip85Application::Hex { num_bytes } => {
+                path.extend_from_slice(&[ChildIndex::hardened(128169)?, ChildIndex::hardened(*num_bytes as u32)?])
+            }
+        };
+        path.push(ChildIndex::hardened(index)?);
+        Ok(path)
+    }
+
+    /// Returns the number of bytes of entropy the application uses.
+    pub fn entropy_length(&self) -> Result<usize, ExtendedPrivateKeyError> {
+        match self {
+            Bip85Application::Bip39 { words, .. } => match words {
+                12 | 18 | 24 => Ok(*words as usize * 4 / 3),
+                _ => Err(ExtendedPrivateKeyError::InvalidBip85Application(format!(
+                    "{} words, expected 12, 18, or 24 words",
+                    words
+                ))),
+            },
+            Bip85Application::Wif => Ok(32),
+            Bip85Application::Hex { num_bytes } => match num_bytes {
+                16..=64 => Ok(*num_bytes as usize),
+                _ => Err(ExtendedPrivateKeyError::InvalidBip85Application(format!(
+                    "{} bytes, expected between 16 and 64 bytes",
+                    num_bytes
+                ))),
+            },
+        }
+    }
+
+    /// Returns the BIP85 code of the given wordlist language.
+    fn language_code(language: &Language) -> u32 {
+        match language {
+            Language::English => 0,
+            Language::Japanese => 1,
+            Language::Korean => 2,
+            Language::Spanish => 3,
+            Language::ChineseSimplified => 4,
+            Language::ChineseTraditional => 5,
+            Language::French => 6,
+            Language::Italian => 7,
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinExtendedPrivateKey<N> {
+    /// Returns the BIP85 entropy of the given application and index, derived from this
+    /// extended private key and truncated to the length the application uses.
+    pub fn bip85_entropy(&self, application: Bip85Application, index: u32) -> Result<Vec<u8>, ExtendedPrivateKeyError> {
+        let length = application.entropy_length()?;
+        let path = BitcoinDerivationPath::<N>::from_vec(&application.to_vec(index)?)?;
+        let private_key = self.derive(&path)?.to_private_key();
+
+        let mut mac = HmacSha512::new_varkey(b"bip-entropy-from-k")?;
+        mac.input(&private_key.to_secp256k1_secret_key().serialize());
+        Ok(mac.result().code()[0..length].to_vec())
+    }
+
+    /// Returns the BIP85 mnemonic of the given word count and index in the language of the wordlist,
+    /// or an error if the wordlist has no BIP39 language.
+    pub fn bip85_mnemonic<W: BitcoinWordlist>(
+        &self,
+        words: u8,
+        index: u32,
+    ) -> Result<BitcoinMnemonic<N, W>, MnemonicError> {
+        let language = W::LANGUAGE.ok_or_else(|| {
+            ExtendedPrivateKeyError::InvalidBip85Application("a wordlist without a BIP39 language".into())
+        })?;
+        let application = Bip85Application::Bip39 { language, words };
+        BitcoinMnemonic::from_entropy(&self.bip85_entropy(application, index)?)
+    }
+
+    /// Returns the BIP85 private key of the given index, in compressed form.
+    pub fn bip85_private_key(&self, index: u32) -> Result<BitcoinPrivateKey<N>, ExtendedPrivateKeyError> {
+        let secret_key = SecretKey::parse_slice(&self.bip85_entropy(Bip85Application::Wif, index)?)?;
+        Ok(BitcoinPrivateKey::from_secp256k1_secret_key(&secret_key, true))
+    }
+
+    /// Returns the BIP85 hex-encoded entropy of the given number of bytes and index.
+    pub fn bip85_hex(&self, num_bytes: u8, index: u32) -> Result<String, ExtendedPrivateKeyError> {
+        Ok(hex::encode(
+            self.bip85_entropy(Bip85Application::Hex { num_bytes }, index)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::wordlist::*;
+    use wagyu_model::Mnemonic;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+
+    const MASTER: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    fn master() -> BitcoinExtendedPrivateKey<N> {
+        BitcoinExtendedPrivateKey::<N>::from_str(MASTER).unwrap()
+    }
+
+    #[test]
+    fn bip39() {
+        [
+            (12, "6250b68daf746d12a24d58b4787a714b", "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"),
+            (
+                18,
+                "938033ed8b12698449d4bbca3c853c66b293ea1b1ce9d9dc",
+                "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token",
+            ),
+            (
+                24,
+                "ae131e2312cdc61331542efe0d1077bac5ea803adf24b313a4f0e48e9c51f37f",
+                "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano",
+            ),
+        ]
+        .iter()
+        .for_each(|(words, expected_entropy, expected_phrase)| {
+            let application = Bip85Application::Bip39 {
+                language: Language::English,
+                words: *words,
+            };
+            let entropy = master().bip85_entropy(application, 0).unwrap();
+            assert_eq!(*expected_entropy, hex::encode(entropy));
+
+            let mnemonic = master().bip85_mnemonic::<English>(*words, 0).unwrap();
+            assert_eq!(*expected_phrase, mnemonic.to_phrase().unwrap());
+        });
+    }
+
+    #[test]
+    fn private_key() {
+        assert_eq!(
+            "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp",
+            master().bip85_private_key(0).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn hex() {
+        assert_eq!(
+            "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c",
+            master().bip85_hex(64, 0).unwrap()
+        );
+
+        let key = master().bip85_hex(32, 0).unwrap();
+        assert_eq!(64, key.len());
+        assert_ne!(key, master().bip85_hex(32, 1).unwrap());
+    }
+
+    #[test]
+    fn path() {
+        let application = Bip85Application::Bip39 {
+            language: Language::Japanese,
+            words: 24,
+        };
+        assert_eq!(
+            "m/83696968'/39'/1'/24'/7'",
+            BitcoinDerivationPath::<N>::from_vec(&application.to_vec(7).unwrap())
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "m/83696968'/128169'/32'/0'",
+            BitcoinDerivationPath::<N>::from_vec(&Bip85Application::Hex { num_bytes: 32 }.to_vec(0).unwrap())
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn invalid_application() {
+        assert!(master().bip85_mnemonic::<English>(13, 0).is_err());
+        assert!(master().bip85_hex(15, 0).is_err());
+        assert!(master().bip85_hex(65, 0).is_err());
+        assert!(master().bip85_private_key(1 << 31).is_err());
+    }
+
+    #[test]
+    fn wordlist_without_language() {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        struct Custom;
+
+        impl wagyu_model::wordlist::Wordlist for Custom {}
+
+        impl BitcoinWordlist for Custom {
+            const WORDLIST: &'static str = English::WORDLIST;
+        }
+
+        assert!(master().bip85_mnemonic::<Custom>(12, 0).is_err());
+    }
+}
@@ -0,0 +1,590 @@
+//!
+//! CashAddr
+//!
+//! This module implements the CashAddr address format of Bitcoin Cash, as specified in
+//! https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md
+//!
+
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::{BitcoinNetwork, Mainnet, Testnet};
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    base58::{decode_check, encode_check},
+    bech32m::convert_bits,
+    crypto::hash160,
+    Address, AddressError, Network, PrivateKey,
+};
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+
+/// The base32 character set of CashAddr
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The number of 5-bit groups in a CashAddr checksum
+const CHECKSUM_LENGTH: usize = 8;
+
+/// The hash sizes in bytes, indexed by the size bits of the version byte
+const HASH_SIZES: [usize; 8] = [20, 24, 28, 32, 40, 48, 56, 64];
+
+/// Represents the type of a CashAddr address
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CashAddrType {
+    /// Pay-to-public-key-hash
+    P2PKH,
+    /// Pay-to-script-hash
+    P2SH,
+}
+
+impl CashAddrType {
+    /// Returns the type bits of the version byte.
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            CashAddrType::P2PKH => 0,
+            CashAddrType::P2SH => 1,
+        }
+    }
+
+    /// Returns the address type of the given type bits of the version byte.
+    pub fn from_u8(address_type: u8) -> Result<Self, AddressError> {
+        match address_type {
+            0 => Ok(CashAddrType::P2PKH),
+            1 => Ok(CashAddrType::P2SH),
+            _ => Err(AddressError::InvalidPrefix(vec![address_type])),
+        }
+    }
+}
+
+impl fmt::Display for CashAddrType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CashAddrType::P2PKH => write!(f, "p2pkh"),
+            CashAddrType::P2SH => write!(f, "p2sh"),
+        }
+    }
+}
+
+/// Returns the CashAddr checksum of the given 5-bit values.
+fn polymod(values: &[u8]) -> u64 {
+    const GENERATORS: [u64; 5] = [0x98f2bc8e61, 0x79b76d99e2, 0xf33e5fb3c4, 0xae2eabe2a8, 0x1e4f43e470];
+
+    let mut checksum: u64 = 1;
+    for value in values {
+        let top = checksum >> 35;
+        checksum = ((checksum & 0x07ffffffff) << 5) ^ u64::from(*value);
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum ^ 1
+}
+
+/// Returns the 5-bit checksum values of the given prefix and 5-bit payload.
+fn create_checksum(prefix: &str, payload: &[u8]) -> Vec<u8> {
+    let mut values: Vec<u8> = prefix.bytes().map(|byte| byte & 0x1f).collect();
+    values.push(0);
+    values.extend_from_slice(payload);
+    values.extend_from_slice(&[0u8; CHECKSUM_LENGTH]);
+
+    let checksum = polymod(&values);
+    (0..CHECKSUM_LENGTH)
+        .map(|i| ((checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 0x1f) as u8)
+        .collect()
+}
+
+/// Returns the CashAddr encoding of the given prefix, address type bits and hash.
+pub fn encode(prefix: &str, address_type: u8, hash: &[u8]) -> Result<String, AddressError> {
+    if prefix.is_empty()
+        || !prefix
+            .bytes()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit())
+    {
+        return Err(AddressError::InvalidPrefix(prefix.as_bytes().to_vec()));
+    }
+    if address_type > 0x0f {
+        return Err(AddressError::InvalidPrefix(vec![address_type]));
+    }
+    let size = match HASH_SIZES.iter().position(|size| *size == hash.len()) {
+        Some(size) => size as u8,
+        None => return Err(AddressError::InvalidByteLength(hash.len())),
+    };
+
+    let mut data = vec![(address_type << 3) | size];
+    data.extend_from_slice(hash);
+
+    let mut payload = convert_bits(&data, 8, 5, true)?;
+    let checksum = create_checksum(prefix, &payload);
+    payload.extend_from_slice(&checksum);
+
+    let payload: String = payload.iter().map(|value| CHARSET[*value as usize] as char).collect();
+    Ok(format!("{}:{}", prefix, payload))
+}
+
+/// Returns the prefix, address type bits and hash of the given CashAddr address.
+/// Addresses given without a prefix are checked against the default prefix.
+pub fn decode(address: &str, default_prefix: &str) -> Result<(String, u8, Vec<u8>), AddressError> {
+    let has_lowercase = address.bytes().any(|byte| byte.is_ascii_lowercase());
+    let has_uppercase = address.bytes().any(|byte| byte.is_ascii_uppercase());
+    if has_lowercase && has_uppercase {
+        return Err(AddressError::InvalidAddress(format!(
+            "mixed case cashaddr address: {}",
+            address
+        )));
+    }
+
+    let address = address.to_lowercase();
+    let (prefix, payload) = match address.find(':') {
+        Some(index) => (&address[..index], &address[index + 1..]),
+        None => (default_prefix, &address[..]),
+    };
+    if prefix.is_empty() {
+        return Err(AddressError::InvalidPrefix(vec![]));
+    }
+
+    let payload = payload
+        .bytes()
+        .map(|byte| match CHARSET.iter().position(|c| *c == byte) {
+            Some(value) => Ok(value as u8),
+            None => Err(AddressError::InvalidAddress(address.clone())),
+        })
+        .collect::<Result<Vec<u8>, AddressError>>()?;
+    if payload.len() <= CHECKSUM_LENGTH {
+        return Err(AddressError::InvalidCharacterLength(payload.len()));
+    }
+
+    let (data, checksum) = payload.split_at(payload.len() - CHECKSUM_LENGTH);
+    let expected_checksum = create_checksum(prefix, data);
+    if checksum != &expected_checksum[..] {
+        let to_string = |values: &[u8]| values.iter().map(|value| CHARSET[*value as usize] as char).collect();
+        return Err(AddressError::InvalidChecksum(
+            to_string(&expected_checksum),
+            to_string(checksum),
+        ));
+    }
+
+    let data = convert_bits(data, 5, 8, false)?;
+    let version = data[0];
+    if version & 0x80 != 0 {
+        return Err(AddressError::InvalidPrefix(vec![version]));
+    }
+
+    let hash = data[1..].to_vec();
+    if hash.len() != HASH_SIZES[(version & 0x07) as usize] {
+        return Err(AddressError::InvalidByteLength(hash.len()));
+    }
+
+    Ok((prefix.to_string(), version >> 3, hash))
+}
+
+/// Represents a Bitcoin Cash address in the CashAddr format
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BitcoinCashAddress<N: BitcoinNetwork> {
+    /// The CashAddr address, including its prefix
+    address: String,
+    /// The type of the address
+    address_type: CashAddrType,
+    /// The public key hash or script hash of the address
+    hash: Vec<u8>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: BitcoinNetwork> Address for BitcoinCashAddress<N> {
+    type Format = BitcoinFormat;
+    type PrivateKey = BitcoinPrivateKey<N>;
+    type PublicKey = BitcoinPublicKey<N>;
+
+    /// Returns the CashAddr address corresponding to the given Bitcoin private key.
+    fn from_private_key(private_key: &Self::PrivateKey, format: &Self::Format) -> Result<Self, AddressError> {
+        Self::from_public_key(&private_key.to_public_key(), format)
+    }
+
+    /// Returns the CashAddr address corresponding to the given Bitcoin public key.
+    /// Bitcoin Cash has no SegWit, so only P2PKH addresses can be derived from a public key.
+    fn from_public_key(public_key: &Self::PublicKey, format: &Self::Format) -> Result<Self, AddressError> {
+        match format {
+            BitcoinFormat::P2PKH => Self::p2pkh(public_key),
+            _ => Err(AddressError::IncompatibleFormats(
+                format.to_string(),
+                String::from("cashaddr address"),
+            )),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinCashAddress<N> {
+    /// Returns a P2PKH CashAddr address from a given Bitcoin public key.
+    pub fn p2pkh(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        let public_key = match public_key.is_compressed() {
+            true => public_key.to_secp256k1_public_key().serialize_compressed().to_vec(),
+            false => public_key.to_secp256k1_public_key().serialize().to_vec(),
+        };
+        Self::from_hash(CashAddrType::P2PKH, &hash160(&public_key))
+    }
+
+    /// Returns the CashAddr address of the given address type and hash.
+    pub fn from_hash(address_type: CashAddrType, hash: &[u8]) -> Result<Self, AddressError> {
+        Ok(Self {
+            address: encode(Self::prefix()?, address_type.to_u8(), hash)?,
+            address_type,
+            hash: hash.to_vec(),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the CashAddr prefix of the network.
+    pub fn prefix() -> Result<&'static str, AddressError> {
+        match N::NAME {
+            Mainnet::NAME => Ok("bitcoincash"),
+            Testnet::NAME => Ok("bchtest"),
+            name => Err(AddressError::InvalidNetwork(
+                format!("{} or {}", Mainnet::NAME, Testnet::NAME),
+                name.into(),
+            )),
+        }
+    }
+
+    /// Returns the type of the CashAddr address.
+    pub fn address_type(&self) -> CashAddrType {
+        self.address_type
+    }
+
+    /// Returns the hash of the CashAddr address.
+    pub fn hash(&self) -> &[u8] {
+        &self.hash
+    }
+
+    /// Returns the legacy Base58Check representation of the CashAddr address.
+    pub fn to_legacy(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        if self.hash.len() != 20 {
+            return Err(AddressError::InvalidByteLength(self.hash.len()));
+        }
+
+        let format = match self.address_type {
+            CashAddrType::P2PKH => BitcoinFormat::P2PKH,
+            CashAddrType::P2SH => BitcoinFormat::P2SH_P2WPKH,
+        };
+
+        let mut address = [0u8; 21];
+        address[0] = N::to_address_prefix(&format)[0];
+        address[1..].copy_from_slice(&self.hash);
+        BitcoinAddress::<N>::from_str(&encode_check(&address))
+    }
+
+    /// Returns the CashAddr address of the given legacy Base58Check address.
+    pub fn from_legacy(address: &BitcoinAddress<N>) -> Result<Self, AddressError> {
+        let address_type = match address.format() {
+            BitcoinFormat::P2PKH => CashAddrType::P2PKH,
+            BitcoinFormat::P2SH_P2WPKH => CashAddrType::P2SH,
+            format => {
+                return Err(AddressError::IncompatibleFormats(
+                    format.to_string(),
+                    String::from("cashaddr address"),
+                ))
+            }
+        };
+
+        let data = decode_check(&address.to_string())?;
+        Self::from_hash(address_type, &data[1..])
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinCashAddress<N> {
+    type Err = AddressError;
+
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        let (prefix, address_type, hash) = decode(address, Self::prefix()?)?;
+        if prefix != Self::prefix()? {
+            return Err(AddressError::InvalidPrefix(prefix.into_bytes()));
+        }
+        Self::from_hash(CashAddrType::from_u8(address_type)?, &hash)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinCashAddress<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The test vectors of the CashAddr specification, as (address, address type, hash)
+    const VECTORS: [(&str, u8, &str); 13] = [
+        (
+            "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+            0,
+            "f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+        ),
+        (
+            "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t",
+            1,
+            "f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+        ),
+        (
+            "pref:pr6m7j9njldwwzlg9v7v53unlr4jkmx6ey65nvtks5",
+            1,
+            "f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+        ),
+        (
+            "prefix:0r6m7j9njldwwzlg9v7v53unlr4jkmx6ey3qnjwsrf",
+            15,
+            "f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+        ),
+        (
+            "bitcoincash:q9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2ws4mr9g0",
+            0,
+            "7adbf6c17084bc86c1706827b41a56f5ca32865925e946ea",
+        ),
+        (
+            "bchtest:p9adhakpwzztepkpwp5z0dq62m6u5v5xtyj7j3h2u94tsynr",
+            1,
+            "7adbf6c17084bc86c1706827b41a56f5ca32865925e946ea",
+        ),
+        (
+            "bitcoincash:qgagf7w02x4wnz3mkwnchut2vxphjzccwxgjvvjmlsxqwkcw59jxxuz",
+            0,
+            "3a84f9cf51aae98a3bb3a78bf16a6183790b18719126325bfc0c075b",
+        ),
+        (
+            "bitcoincash:qvch8mmxy0rtfrlarg7ucrxxfzds5pamg73h7370aa87d80gyhqxq5nlegake",
+            0,
+            "3173ef6623c6b48ffd1a3dcc0cc6489b0a07bb47a37f47cfef4fe69de825c060",
+        ),
+        (
+            "bchtest:pvch8mmxy0rtfrlarg7ucrxxfzds5pamg73h7370aa87d80gyhqxq7fqng6m6",
+            1,
+            "3173ef6623c6b48ffd1a3dcc0cc6489b0a07bb47a37f47cfef4fe69de825c060",
+        ),
+        (
+            "bitcoincash:qnq8zwpj8cq05n7pytfmskuk9r4gzzel8qtsvwz79zdskftrzxtar994cgutavfklv39gr3uvz",
+            0,
+            "c07138323e00fa4fc122d3b85b9628ea810b3f381706385e289b0b25631197d194b5c238beb136fb",
+        ),
+        (
+            "bitcoincash:qh3krj5607v3qlqh5c3wq3lrw3wnuxw0sp8dv0zugrrt5a3kj6ucysfz8kxwv2k53krr7n933jfsunqex2w82sl",
+            0,
+            "e361ca9a7f99107c17a622e047e3745d3e19cf804ed63c5c40c6ba763696b98241223d8ce62ad48d863f4cb18c930e4c",
+        ),
+        (
+            "bitcoincash:qmvl5lzvdm6km38lgga64ek5jhdl7e3aqd9895wu04fvhlnare5937w4ywkq57juxsrhvw8ym5d8qx7sz7zz0zvcypqscw8jd03f",
+            0,
+            "d9fa7c4c6ef56dc4ff423baae6d495dbff663d034a72d1dc7d52cbfe7d1e6858f9d523ac0a7a5c34077638e4dd1a701bd017842789982041",
+        ),
+        (
+            "bitcoincash:qlg0x333p4238k0qrc5ej7rzfw5g8e4a4r6vvzyrcy8j3s5k0en7calvclhw46hudk5flttj6ydvjc0pv3nchp52amk97tqa5zygg96mtky5sv5w",
+            0,
+            "d0f346310d5513d9e01e299978624ba883e6bda8f4c60883c10f28c2967e67ec77ecc7eeeaeafc6da89fad72d11ac961e164678b868aeeec5f2c1da08884175b",
+        ),
+    ];
+
+    #[test]
+    fn test_encode() {
+        VECTORS.iter().for_each(|(address, address_type, hash)| {
+            let prefix = &address[..address.find(':').unwrap()];
+            assert_eq!(
+                *address,
+                encode(prefix, *address_type, &hex::decode(hash).unwrap()).unwrap()
+            );
+        });
+    }
+
+    #[test]
+    fn test_decode() {
+        VECTORS.iter().for_each(|(address, address_type, hash)| {
+            let (prefix, payload) = address.split_at(address.find(':').unwrap());
+            let expected = (prefix.to_string(), *address_type, hex::decode(hash).unwrap());
+
+            assert_eq!(expected, decode(address, "bitcoincash").unwrap());
+            assert_eq!(expected, decode(&address.to_uppercase(), "bitcoincash").unwrap());
+            assert_eq!(expected, decode(&payload[1..], prefix).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_invalid_encode() {
+        let hash = [0u8; 20];
+        assert!(encode("", 0, &hash).is_err());
+        assert!(encode("BitcoinCash", 0, &hash).is_err());
+        assert!(encode("bitcoincash", 16, &hash).is_err());
+        assert!(encode("bitcoincash", 0, &[0u8; 21]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_decode() {
+        // Mixed case
+        assert!(decode("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekG2", "bitcoincash").is_err());
+        // Invalid checksum
+        assert!(decode("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg3", "bitcoincash").is_err());
+        // Mismatched prefix
+        assert!(decode("bchtest:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bitcoincash").is_err());
+        assert!(decode("qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2", "bchtest").is_err());
+        // Invalid character
+        assert!(decode("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekgb", "bitcoincash").is_err());
+        // Missing payload
+        assert!(decode("bitcoincash:", "bitcoincash").is_err());
+    }
+
+    mod mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "L52XzL2cMkHxqxBXRyEpnPQZGUs3uKiL3R11XbAdHigRzDozKZeW";
+        const ADDRESS: &str = "bitcoincash:qq6yyxf7rwmsj9hfz32jzukdfckme80czyn2pwwpfn";
+        const LEGACY_ADDRESS: &str = "15mKKb2eos1hWa6tisdPwwDC1a5J1y9nma";
+
+        // (cashaddr address, legacy address)
+        const LEGACY_PAIRS: [(&str, &str); 2] = [
+            (
+                "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a",
+                "1BpEi6DfDAUFd7GtittLSdBeYJvcoaVggu",
+            ),
+            (
+                "bitcoincash:ppm2qsznhks23z7629mms6s4cwef74vcwvn0h829pq",
+                "3CWFddi6m4ndiGyKqzYvsFYagqDLPVMTzC",
+            ),
+        ];
+
+        #[test]
+        fn from_private_key() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let address = BitcoinCashAddress::<N>::from_private_key(&private_key, &BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(ADDRESS, address.to_string());
+            assert_eq!(CashAddrType::P2PKH, address.address_type());
+            assert_eq!(LEGACY_ADDRESS, address.to_legacy().unwrap().to_string());
+
+            let address = BitcoinCashAddress::<N>::from_public_key(&private_key.to_public_key(), &BitcoinFormat::P2PKH);
+            assert_eq!(ADDRESS, address.unwrap().to_string());
+        }
+
+        #[test]
+        fn from_private_key_incompatible_formats() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            [BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::P2WSH, BitcoinFormat::Bech32]
+                .iter()
+                .for_each(|format| assert!(BitcoinCashAddress::<N>::from_private_key(&private_key, format).is_err()));
+        }
+
+        #[test]
+        fn from_str() {
+            let address = BitcoinCashAddress::<N>::from_str(ADDRESS).unwrap();
+            assert_eq!(ADDRESS, address.to_string());
+            assert_eq!(address, BitcoinCashAddress::<N>::from_str(&ADDRESS[12..]).unwrap());
+            assert_eq!(
+                address,
+                BitcoinCashAddress::<N>::from_str(&ADDRESS.to_uppercase()).unwrap()
+            );
+        }
+
+        #[test]
+        fn invalid_from_str() {
+            assert!(BitcoinCashAddress::<N>::from_str("bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t").is_err());
+            assert!(BitcoinCashAddress::<N>::from_str("prefix:0r6m7j9njldwwzlg9v7v53unlr4jkmx6ey3qnjwsrf").is_err());
+            assert!(
+                BitcoinCashAddress::<N>::from_str("bitcoincash:qq6yyxf7rwmsj9hfz32jzukdfckme80czyn2pwwpfN").is_err()
+            );
+            assert!(BitcoinCashAddress::<N>::from_str(LEGACY_ADDRESS).is_err());
+        }
+
+        #[test]
+        fn legacy() {
+            LEGACY_PAIRS.iter().for_each(|(address, legacy_address)| {
+                let address = BitcoinCashAddress::<N>::from_str(address).unwrap();
+                let legacy_address = BitcoinAddress::<N>::from_str(legacy_address).unwrap();
+                assert_eq!(legacy_address, address.to_legacy().unwrap());
+                assert_eq!(address, BitcoinCashAddress::<N>::from_legacy(&legacy_address).unwrap());
+            });
+
+            let address = BitcoinCashAddress::<N>::from_str(VECTORS[7].0).unwrap();
+            assert!(address.to_legacy().is_err());
+
+            let legacy_address = BitcoinAddress::<N>::from_str("bc1qx3ppj0smkuy3d6g525sh9n2w9k7fm7q3x30rtg").unwrap();
+            assert!(BitcoinCashAddress::<N>::from_legacy(&legacy_address).is_err());
+        }
+    }
+
+    mod testnet {
+        use super::*;
+
+        type N = Testnet;
+
+        #[test]
+        fn legacy() {
+            let address = "bchtest:qpc0qh2xc3tfzsljq79w37zx02kwvzm4gydm222qg8";
+            let legacy_address = "mqp7vM7eU7Vu9NPH1V7s7pPg5FFBMo6SWK";
+
+            let address = BitcoinCashAddress::<N>::from_str(address).unwrap();
+            let legacy_address = BitcoinAddress::<N>::from_str(legacy_address).unwrap();
+            assert_eq!(legacy_address, address.to_legacy().unwrap());
+            assert_eq!(address, BitcoinCashAddress::<N>::from_legacy(&legacy_address).unwrap());
+        }
+
+        #[test]
+        fn invalid_network() {
+            assert!(
+                BitcoinCashAddress::<N>::from_str("bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2").is_err()
+            );
+            assert!(BitcoinCashAddress::<crate::network::Litecoin>::prefix().is_err());
+        }
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        fn address_type() -> impl Strategy<Value = CashAddrType> {
+            prop_oneof![Just(CashAddrType::P2PKH), Just(CashAddrType::P2SH)]
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(address_type in address_type(), hash: [u8; 20]) {
+                let address = BitcoinCashAddress::<N>::from_hash(address_type, &hash).unwrap();
+                prop_assert_eq!(&address, &BitcoinCashAddress::from_str(&address.to_string()).unwrap());
+                prop_assert_eq!(&address, &BitcoinCashAddress::from_legacy(&address.to_legacy().unwrap()).unwrap());
+            }
+
+            #[test]
+            fn encode_decode(prefix in "[a-z0-9]{1,12}", address_type in 0..16u8, size in 0..8usize, seed: [u8; 32]) {
+                let hash: Vec<u8> = seed.iter().cycle().take(HASH_SIZES[size]).copied().collect();
+                let address = encode(&prefix, address_type, &hash).unwrap();
+                prop_assert_eq!((prefix.clone(), address_type, hash.clone()), decode(&address, "").unwrap());
+                prop_assert_eq!((prefix, address_type, hash), decode(&address.to_uppercase(), "").unwrap());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinCashAddress<N>>(&inp)?;
+                let _ = decode(&inp, "bitcoincash");
+            }
+
+            #[test]
+            fn from_str_charset(inp in "(bitcoincash:)?[qpzry9x8gf2tvdw0s3jn54khce6mua7l]{0,120}") {
+                if let Ok(address) = BitcoinCashAddress::<N>::from_str(&inp) {
+                    prop_assert_eq!(&address, &BitcoinCashAddress::from_str(&address.to_string()).unwrap());
+                }
+            }
+
+            #[test]
+            fn decode_payload(prefix in "[a-z0-9]{1,12}", payload in vec(0..32u8, 0..120)) {
+                let checksum = create_checksum(&prefix, &payload);
+                let address: String = payload
+                    .iter()
+                    .chain(checksum.iter())
+                    .map(|value| CHARSET[*value as usize] as char)
+                    .collect();
+                let _ = decode(&format!("{}:{}", prefix, address), "");
+            }
+        }
+    }
+}
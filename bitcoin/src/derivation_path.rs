@@ -1,8 +1,12 @@
 use crate::network::BitcoinNetwork;
-use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
+use wagyu_model::derivation_path::{
+    deserialize_derivation_path, parse_derivation_path, serialize_derivation_path, ChildIndex, DerivationPath,
+    DerivationPathError, MAX_DEPTH,
+};
 use wagyu_model::no_std::*;
 
-use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
+use core::{convert::TryFrom, fmt, marker::PhantomData, ops::Index, str::FromStr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents a Bitcoin derivation path
 #[derive(Clone, PartialEq, Eq)]
@@ -16,6 +20,9 @@ pub enum BitcoinDerivationPath<N: BitcoinNetwork> {
     /// BIP49 - m/49'/{0', 1'}/{account}'/{change}/{index} - SegWit Pay-to-Witness-Public-Key Hash
     /// https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki
     BIP49([ChildIndex; 3]),
+    /// BIP84 - m/84'/{0', 1'}/{account}'/{change}/{index} - Native SegWit Pay-to-Witness-Public-Key Hash
+    /// https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+    BIP84([ChildIndex; 3]),
 }
 
 impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
@@ -50,11 +57,27 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
                     false => Err(DerivationPathError::ExpectedBIP49Path),
                 }
             }
+            BitcoinDerivationPath::BIP84(path) => {
+                match path[0].is_hardened() && path[1].is_normal() && path[2].is_normal() {
+                    true => Ok(vec![
+                        ChildIndex::Hardened(84),
+                        N::HD_COIN_TYPE,
+                        path[0],
+                        path[1],
+                        path[2],
+                    ]),
+                    false => Err(DerivationPathError::ExpectedBIP84Path),
+                }
+            }
         }
     }
 
     /// Returns a derivation path given the child index vector.
     fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError> {
+        if path.len() > MAX_DEPTH {
+            return Err(DerivationPathError::MaximumDepthExceeded(path.len()));
+        }
+
         if path.len() == 5 {
             // Path length 5 - BIP44
             if path[0] == ChildIndex::Hardened(44)
@@ -74,7 +97,16 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
             {
                 return Ok(BitcoinDerivationPath::BIP49([path[2], path[3], path[4]]));
             }
-            // Path length 5 - BIP32 (non-BIP44 & non-BIP49 compliant)
+            // Path length 5 - BIP84
+            if path[0] == ChildIndex::Hardened(84)
+                && path[1] == N::HD_COIN_TYPE
+                && path[2].is_hardened()
+                && path[3].is_normal()
+                && path[4].is_normal()
+            {
+                return Ok(BitcoinDerivationPath::BIP84([path[2], path[3], path[4]]));
+            }
+            // Path length 5 - BIP32 (non-BIP44, non-BIP49 & non-BIP84 compliant)
             return Ok(BitcoinDerivationPath::BIP32(path.to_vec(), PhantomData));
         } else {
             // Path length 0 - BIP32 root key
@@ -84,18 +116,82 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
     }
 }
 
-impl<N: BitcoinNetwork> FromStr for BitcoinDerivationPath<N> {
-    type Err = DerivationPathError;
+impl<N: BitcoinNetwork> BitcoinDerivationPath<N> {
+    /// The coin type of BIP44, BIP49, and BIP84 derivation paths, held as a reference for indexing.
+    const COIN_TYPE: &'static ChildIndex = &N::HD_COIN_TYPE;
 
-    fn from_str(path: &str) -> Result<Self, Self::Err> {
-        let mut parts = path.split("/");
+    /// Returns a BIP44 derivation path, m/44'/{coin type}'/{account}'/{change}/{index}.
+    pub fn bip44(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP44([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// Returns a BIP49 derivation path, m/49'/{coin type}'/{account}'/{change}/{index}.
+    pub fn bip49(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP49([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
 
-        if parts.next().unwrap() != "m" {
-            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+    /// Returns a BIP84 derivation path, m/84'/{coin type}'/{account}'/{change}/{index}.
+    pub fn bip84(account: u32, change: u32, index: u32) -> Result<Self, DerivationPathError> {
+        Ok(BitcoinDerivationPath::BIP84([
+            ChildIndex::hardened(account)?,
+            ChildIndex::normal(change)?,
+            ChildIndex::normal(index)?,
+        ]))
+    }
+
+    /// Returns the number of child indices in the derivation path.
+    pub fn len(&self) -> usize {
+        match self {
+            BitcoinDerivationPath::BIP32(path, _) => path.len(),
+            BitcoinDerivationPath::BIP44(_) | BitcoinDerivationPath::BIP49(_) | BitcoinDerivationPath::BIP84(_) => 5,
         }
+    }
+
+    /// Returns `true` if the derivation path is the master path, m.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 
-        let path: Result<Vec<ChildIndex>, Self::Err> = parts.map(str::parse).collect();
-        Self::from_vec(&path?)
+    /// Returns an iterator over the child indices of the derivation path.
+    pub fn iter(&self) -> vec::IntoIter<ChildIndex> {
+        self.into_iter()
+    }
+
+    /// Returns `Ok` if the derivation path follows the BIP44 scheme,
+    /// m/44'/{coin type}'/{account}'/{change}/{index}, otherwise returns the level that violates it.
+    pub fn validate_bip44(&self) -> Result<(), DerivationPathError> {
+        let path = self.to_vec()?;
+        if path.len() != 5 {
+            return Err(DerivationPathError::ExpectedBIP44Path);
+        }
+
+        let expected = [
+            path[0] == ChildIndex::Hardened(44),
+            path[1] == N::HD_COIN_TYPE,
+            path[2].is_hardened(),
+            path[3].is_normal(),
+            path[4].is_normal(),
+        ];
+        match expected.iter().position(|valid| !valid) {
+            Some(level) => Err(DerivationPathError::InvalidBIP44Level(level)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinDerivationPath<N> {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Self::from_vec(&parse_derivation_path(path)?)
     }
 }
 
@@ -115,6 +211,59 @@ impl<'a, N: BitcoinNetwork> TryFrom<&'a [ChildIndex]> for BitcoinDerivationPath<
     }
 }
 
+impl<N: BitcoinNetwork> Index<usize> for BitcoinDerivationPath<N> {
+    type Output = ChildIndex;
+
+    /// Returns the child index at the given level of the derivation path.
+    /// Panics if the level is not less than the length of the derivation path.
+    fn index(&self, level: usize) -> &Self::Output {
+        match self {
+            BitcoinDerivationPath::BIP32(path, _) => &path[level],
+            BitcoinDerivationPath::BIP44(path) => {
+                [&ChildIndex::Hardened(44), Self::COIN_TYPE, &path[0], &path[1], &path[2]][level]
+            }
+            BitcoinDerivationPath::BIP49(path) => {
+                [&ChildIndex::Hardened(49), Self::COIN_TYPE, &path[0], &path[1], &path[2]][level]
+            }
+            BitcoinDerivationPath::BIP84(path) => {
+                [&ChildIndex::Hardened(84), Self::COIN_TYPE, &path[0], &path[1], &path[2]][level]
+            }
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> IntoIterator for BitcoinDerivationPath<N> {
+    type Item = ChildIndex;
+    type IntoIter = vec::IntoIter<ChildIndex>;
+
+    /// Returns an iterator over the child indices of the derivation path.
+    fn into_iter(self) -> Self::IntoIter {
+        (&self).into_iter()
+    }
+}
+
+impl<N: BitcoinNetwork> IntoIterator for &BitcoinDerivationPath<N> {
+    type Item = ChildIndex;
+    type IntoIter = vec::IntoIter<ChildIndex>;
+
+    /// Returns an iterator over the child indices of the derivation path.
+    fn into_iter(self) -> Self::IntoIter {
+        (0..self.len()).map(|level| self[level]).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<N: BitcoinNetwork> Serialize for BitcoinDerivationPath<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_derivation_path(self, serializer)
+    }
+}
+
+impl<'de, N: BitcoinNetwork> Deserialize<'de> for BitcoinDerivationPath<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_derivation_path(deserializer)
+    }
+}
+
 impl<N: BitcoinNetwork> fmt::Debug for BitcoinDerivationPath<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self, f)
@@ -137,6 +286,31 @@ impl<N: BitcoinNetwork> fmt::Display for BitcoinDerivationPath<N> {
     }
 }
 
+#[cfg(any(test, feature = "test-utils"))]
+impl<N: BitcoinNetwork> proptest::arbitrary::Arbitrary for BitcoinDerivationPath<N> {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    /// Returns a strategy over BIP32 paths of up to 8 child indices, and over BIP44, BIP49,
+    /// and BIP84 paths of any account, change, and index.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::{collection::vec, prelude::*};
+
+        const INDEX: core::ops::Range<u32> = 0..1 << 31;
+        prop_oneof![
+            vec(any::<ChildIndex>(), 0..8).prop_filter_map("invalid BIP32 path", |path| Self::from_vec(&path).ok()),
+            (0..3u8, INDEX, INDEX, INDEX).prop_filter_map("invalid BIP44 path", |(purpose, account, change, index)| {
+                match purpose {
+                    0 => Self::bip44(account, change, index).ok(),
+                    1 => Self::bip49(account, change, index).ok(),
+                    _ => Self::bip84(account, change, index).ok(),
+                }
+            }),
+        ]
+        .boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::network::*;
@@ -395,6 +569,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bip84_mainnet() {
+        use super::*;
+
+        type N = Mainnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(0), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/0/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(1), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'/1/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(0), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/1'/0/1").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(1), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/0'/1'/1/0").unwrap()
+        );
+    }
+
+    #[test]
+    fn bip84_testnet() {
+        use super::*;
+
+        type N = Testnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(0), ChildIndex::Normal(0), ChildIndex::Normal(0)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/0'/0/0").unwrap()
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::BIP84([ChildIndex::Hardened(1), ChildIndex::Normal(1), ChildIndex::Normal(1)]),
+            BitcoinDerivationPath::<N>::from_str("m/84'/1'/1'/1/1").unwrap()
+        );
+    }
+
+    #[test]
+    fn constructors() {
+        use super::*;
+
+        fn test_constructors<N: BitcoinNetwork>(coin_type: u32) {
+            let paths = [
+                (BitcoinDerivationPath::<N>::bip44(0, 0, 0).unwrap(), "44", "0", "0", "0"),
+                (BitcoinDerivationPath::<N>::bip44(3, 1, 7).unwrap(), "44", "3", "1", "7"),
+                (BitcoinDerivationPath::<N>::bip49(0, 0, 5).unwrap(), "49", "0", "0", "5"),
+                (BitcoinDerivationPath::<N>::bip49(2, 1, 0).unwrap(), "49", "2", "1", "0"),
+                (BitcoinDerivationPath::<N>::bip84(0, 1, 2).unwrap(), "84", "0", "1", "2"),
+                (
+                    BitcoinDerivationPath::<N>::bip84(9, 0, 2147483647).unwrap(),
+                    "84",
+                    "9",
+                    "0",
+                    "2147483647",
+                ),
+            ];
+            paths.iter().for_each(|(path, purpose, account, change, index)| {
+                let expected = format!("m/{}'/{}'/{}'/{}/{}", purpose, coin_type, account, change, index);
+                assert_eq!(expected, path.to_string());
+                assert_eq!(*path, BitcoinDerivationPath::<N>::from_str(&expected).unwrap());
+            });
+
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                BitcoinDerivationPath::<N>::bip84(2147483648, 0, 0)
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                BitcoinDerivationPath::<N>::bip44(0, 0, 2147483648)
+            );
+        }
+
+        test_constructors::<Mainnet>(0);
+        test_constructors::<Testnet>(1);
+    }
+
     #[test]
     fn valid_path() {
         use super::*;
@@ -405,6 +658,16 @@ mod tests {
             BitcoinDerivationPath::<N>::from_str("m"),
             Ok(vec![].try_into().unwrap())
         );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("M"),
+            Ok(vec![].try_into().unwrap())
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("M/0/1"),
+            Ok(vec![ChildIndex::normal(0).unwrap(), ChildIndex::normal(1).unwrap()]
+                .try_into()
+                .unwrap())
+        );
         assert_eq!(
             BitcoinDerivationPath::<N>::from_str("m/0"),
             Ok(vec![ChildIndex::normal(0).unwrap()].try_into().unwrap())
@@ -568,15 +831,15 @@ mod tests {
 
         assert_eq!(
             BitcoinDerivationPath::<N>::from_str("m/0x"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x".into(), 1))
         );
         assert_eq!(
             BitcoinDerivationPath::<N>::from_str("m/0x0"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x0".into(), 1))
         );
         assert_eq!(
             BitcoinDerivationPath::<N>::from_str("m/0x00"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x00".into(), 1))
         );
 
         assert_eq!(
@@ -585,11 +848,267 @@ mod tests {
         );
         assert_eq!(
             BitcoinDerivationPath::<N>::from_str("m//0"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/0/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 2))
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("M/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("M/0'"),
+            Err(DerivationPathError::ExpectedNormalPath)
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("M/0/1'"),
+            Err(DerivationPathError::ExpectedNormalPath)
         );
         assert_eq!(
             BitcoinDerivationPath::<N>::from_str("m/2147483648"),
             Err(DerivationPathError::InvalidChildNumber(2147483648))
         );
     }
+
+    #[test]
+    fn manipulation() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let mut path = BitcoinDerivationPath::<N>::from_str("m").unwrap();
+        assert!(path.is_empty());
+        assert_eq!(None, path.pop());
+        assert_eq!(None, path.parent());
+
+        path.push(ChildIndex::Hardened(44)).unwrap();
+        path.extend(vec![ChildIndex::Hardened(0), ChildIndex::Hardened(0)])
+            .unwrap();
+        assert_eq!("m/44'/0'/0'", path.to_string());
+        assert_eq!(3, path.len());
+        assert_eq!(Some(ChildIndex::Hardened(0)), path.get(2));
+        assert_eq!(None, path.get(3));
+
+        let account = path.child(ChildIndex::Normal(0)).unwrap();
+        (0..20).for_each(|index| {
+            let child = account.child(ChildIndex::Normal(index)).unwrap();
+            assert_eq!(BitcoinDerivationPath::bip44(0, 0, index).unwrap(), child);
+            assert_eq!(5, child.len());
+            assert_eq!(Some(ChildIndex::Normal(index)), child.get(4));
+            assert_eq!(ChildIndex::Normal(index), child[4]);
+            assert_eq!(account, child.parent().unwrap());
+        });
+
+        assert_eq!(Some(ChildIndex::Hardened(0)), path.pop());
+        assert_eq!("m/44'/0'", path.to_string());
+        assert_eq!(
+            vec![ChildIndex::Hardened(44), ChildIndex::Hardened(0)],
+            (&path).into_iter().collect::<Vec<_>>()
+        );
+
+        let mut path = BitcoinDerivationPath::<N>::bip84(0, 0, 0).unwrap();
+        assert_eq!(Some(ChildIndex::Normal(0)), path.pop());
+        assert_eq!("m/84'/0'/0'/0", path.to_string());
+    }
+
+    #[test]
+    fn index() {
+        use super::*;
+
+        type N = Mainnet;
+
+        ["m", "m/0'/1", "m/44'/0'/0'/0/7", "m/49'/0'/1'/1/5", "m/84'/0'/2'/0/0"]
+            .iter()
+            .for_each(|path| {
+                let path = BitcoinDerivationPath::<N>::from_str(path).unwrap();
+                let expected = path.to_vec().unwrap();
+                assert_eq!(expected, (0..path.len()).map(|level| path[level]).collect::<Vec<_>>());
+                assert_eq!(expected, path.iter().collect::<Vec<_>>());
+            });
+
+        let path = BitcoinDerivationPath::<Testnet>::bip49(2, 1, 5).unwrap();
+        assert_eq!(ChildIndex::Hardened(49), path[0]);
+        assert_eq!(ChildIndex::Hardened(1), path[1]);
+        assert_eq!(ChildIndex::Hardened(2), path[2]);
+
+        // An invalid derivation path still iterates over the child indices it holds.
+        let path = BitcoinDerivationPath::<N>::BIP84([
+            ChildIndex::Hardened(0),
+            ChildIndex::Hardened(1),
+            ChildIndex::Normal(2),
+        ]);
+        assert!(path.to_vec().is_err());
+        assert_eq!(
+            vec![
+                ChildIndex::Hardened(84),
+                ChildIndex::Hardened(0),
+                ChildIndex::Hardened(0),
+                ChildIndex::Hardened(1),
+                ChildIndex::Normal(2)
+            ],
+            path.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        use super::*;
+
+        let _ = BitcoinDerivationPath::<Mainnet>::bip44(0, 0, 0).unwrap()[5];
+    }
+
+    #[test]
+    fn validate_bip44() {
+        use super::*;
+
+        type N = Mainnet;
+
+        let path = BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/0/1").unwrap();
+        assert_eq!(Ok(()), path.validate_bip44());
+        assert_eq!(
+            vec![true, true, true, false, false],
+            (0..5).map(|level| path.is_hardened(level)).collect::<Vec<_>>()
+        );
+        assert!(!path.is_hardened(5));
+        assert_eq!(path.to_vec().unwrap(), path.iter().collect::<Vec<_>>());
+
+        [
+            ("m/49'/0'/0'/0/1", 0),
+            ("m/44'/1'/0'/0/1", 1),
+            ("m/44'/0'/0/0/1", 2),
+            ("m/44'/0'/0'/0'/1", 3),
+            ("m/44'/0'/0'/0/1'", 4),
+        ]
+        .iter()
+        .for_each(|(path, level)| {
+            assert_eq!(
+                Err(DerivationPathError::InvalidBIP44Level(*level)),
+                BitcoinDerivationPath::<N>::from_str(path).unwrap().validate_bip44()
+            );
+        });
+
+        assert_eq!(
+            Err(DerivationPathError::ExpectedBIP44Path),
+            BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/0")
+                .unwrap()
+                .validate_bip44()
+        );
+        assert_eq!(
+            Ok(()),
+            BitcoinDerivationPath::<Testnet>::from_str("m/44'/1'/0'/0/1")
+                .unwrap()
+                .validate_bip44()
+        );
+    }
+
+    #[test]
+    fn serde() {
+        use super::*;
+
+        type N = Mainnet;
+
+        ["m", "m/0'/1", "m/44'/0'/0'/0/0", "m/49'/0'/1'/1/5", "m/84'/0'/0'/0/0"]
+            .iter()
+            .for_each(|expected| {
+                let path = BitcoinDerivationPath::<N>::from_str(expected).unwrap();
+
+                let json = serde_json::to_string(&path).unwrap();
+                assert_eq!(format!("\"{}\"", expected), json);
+                assert_eq!(path, serde_json::from_str::<BitcoinDerivationPath<N>>(&json).unwrap());
+
+                let cbor = serde_cbor::to_vec(&path).unwrap();
+                assert_eq!(
+                    path.iter().map(u32::from).collect::<Vec<u32>>(),
+                    serde_cbor::from_slice::<Vec<u32>>(&cbor).unwrap()
+                );
+                assert_eq!(path, serde_cbor::from_slice::<BitcoinDerivationPath<N>>(&cbor).unwrap());
+            });
+
+        assert_eq!(
+            "invalid derivation path: n/0",
+            serde_json::from_str::<BitcoinDerivationPath<N>>("\"n/0\"")
+                .unwrap_err()
+                .to_string()
+        );
+
+        let cbor = serde_cbor::to_vec(&vec![0u32; MAX_DEPTH + 1]).unwrap();
+        assert_eq!(
+            "derivation path depth 256 exceeds the maximum depth of 255",
+            serde_cbor::from_slice::<BitcoinDerivationPath<N>>(&cbor)
+                .unwrap_err()
+                .to_string()
+        );
+
+        let path = BitcoinDerivationPath::<N>::BIP84([
+            ChildIndex::Hardened(0),
+            ChildIndex::Hardened(1),
+            ChildIndex::Normal(2),
+        ]);
+        assert_eq!(
+            "expected BIP84 path",
+            serde_json::to_string(&path).unwrap_err().to_string()
+        );
+        assert!(serde_cbor::to_vec(&path).is_err());
+    }
+
+    mod proptests {
+        use crate::derivation_path::*;
+        use crate::network::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(path: BitcoinDerivationPath<N>) {
+                prop_assert_eq!(&path, &BitcoinDerivationPath::<N>::from_str(&path.to_string()).unwrap());
+            }
+
+            #[test]
+            fn to_vec_from_vec(path in vec(any::<ChildIndex>(), 0..MAX_DEPTH + 8)) {
+                match BitcoinDerivationPath::<N>::from_vec(&path) {
+                    Ok(derivation_path) => prop_assert_eq!(path, derivation_path.to_vec().unwrap()),
+                    Err(error) => {
+                        prop_assert!(path.len() > MAX_DEPTH);
+                        prop_assert_eq!(DerivationPathError::MaximumDepthExceeded(path.len()), error);
+                    }
+                }
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinDerivationPath<N>>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_indices(inp in "[mM](/[0-9]{1,10}['hH]?){0,8}") {
+                if let Ok(path) = BitcoinDerivationPath::<N>::from_str(&inp) {
+                    prop_assert_eq!(&path, &BitcoinDerivationPath::<N>::from_str(&path.to_string()).unwrap());
+                    if inp.starts_with('M') {
+                        prop_assert!(path.iter().all(|index| index.is_normal()));
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn from_str_maximum_depth() {
+            let path = format!("m{}", "/0".repeat(MAX_DEPTH));
+            assert_eq!(MAX_DEPTH, BitcoinDerivationPath::<N>::from_str(&path).unwrap().len());
+
+            let path = format!("m{}", "/0".repeat(MAX_DEPTH + 1));
+            assert_eq!(
+                Err(DerivationPathError::MaximumDepthExceeded(MAX_DEPTH + 1)),
+                BitcoinDerivationPath::<N>::from_str(&path)
+            );
+        }
+    }
 }
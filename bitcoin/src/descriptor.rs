@@ -0,0 +1,807 @@
+//!
+//! Output Descriptors
+//!
+//! This module renders and parses the single-key output script descriptors of BIP380, e.g.
+//! `wpkh([73c5da0a/84'/0'/0']xpub.../0/*)#wc3n3van`, which describe the scripts of an account for
+//! import into Bitcoin Core and other descriptor wallets.
+//! https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, ChildIndex, DerivationPath, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError,
+    PublicKey, PublicKeyError,
+};
+
+use core::{fmt, str::FromStr};
+
+/// The characters of a descriptor, in the order of their checksum symbols
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// The characters of a descriptor checksum
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The generators of the descriptor checksum code
+const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+
+#[derive(Debug, Fail)]
+pub enum DescriptorError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(DerivationPathError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(ExtendedPublicKeyError),
+
+    #[fail(display = "invalid character: {:?}", _0)]
+    InvalidCharacter(char),
+
+    #[fail(display = "invalid descriptor checksum: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidChecksum(String, String),
+
+    #[fail(display = "invalid descriptor: {}", _0)]
+    InvalidDescriptor(String),
+
+    #[fail(display = "invalid key origin: {}", _0)]
+    InvalidKeyOrigin(String),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(PublicKeyError),
+
+    #[fail(display = "unsupported descriptor: {}", _0)]
+    UnsupportedDescriptor(String),
+}
+
+impl From<AddressError> for DescriptorError {
+    fn from(error: AddressError) -> Self {
+        DescriptorError::AddressError(error)
+    }
+}
+
+impl From<DerivationPathError> for DescriptorError {
+    fn from(error: DerivationPathError) -> Self {
+        DescriptorError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for DescriptorError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        DescriptorError::ExtendedPublicKeyError(error)
+    }
+}
+
+impl From<hex::FromHexError> for DescriptorError {
+    fn from(error: hex::FromHexError) -> Self {
+        DescriptorError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<PublicKeyError> for DescriptorError {
+    fn from(error: PublicKeyError) -> Self {
+        DescriptorError::PublicKeyError(error)
+    }
+}
+
+/// Returns the checksum of the given descriptor, without its `#` separator.
+/// The symbols of the descriptor are folded into the checksum as they are read, so that long
+/// descriptors are not copied into an intermediate buffer.
+pub fn descriptor_checksum(descriptor: &str) -> Result<String, DescriptorError> {
+    let mut checksum = 1u64;
+    let mut group = 0u64;
+    let mut group_length = 0;
+    for character in descriptor.chars() {
+        let value = match INPUT_CHARSET.find(character) {
+            Some(value) => value as u64,
+            None => return Err(DescriptorError::InvalidCharacter(character)),
+        };
+        checksum = descriptor_polymod(checksum, value & 31);
+        group = group * 3 + (value >> 5);
+        group_length += 1;
+        if group_length == 3 {
+            checksum = descriptor_polymod(checksum, group);
+            group = 0;
+            group_length = 0;
+        }
+    }
+    if group_length > 0 {
+        checksum = descriptor_polymod(checksum, group);
+    }
+    let checksum = (0..8).fold(checksum, |checksum, _| descriptor_polymod(checksum, 0)) ^ 1;
+
+    Ok((0..8)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Returns the checksum of the given checksum after appending the given symbol.
+fn descriptor_polymod(checksum: u64, value: u64) -> u64 {
+    let top = checksum >> 35;
+    let checksum = ((checksum & 0x7_ffff_ffff) << 5) ^ value;
+    (0..5)
+        .filter(|i| (top >> i) & 1 == 1)
+        .fold(checksum, |checksum, i| checksum ^ GENERATOR[i])
+}
+
+/// Represents the script type of a descriptor
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DescriptorType {
+    /// Pay-to-Pubkey Hash - pkh(KEY)
+    Pkh,
+    /// SegWit Pay-to-Witness-Public-Key Hash nested in Pay-to-Script Hash - sh(wpkh(KEY))
+    ShWpkh,
+    /// Native SegWit Pay-to-Witness-Public-Key Hash - wpkh(KEY)
+    Wpkh,
+    /// Taproot with a key path only - tr(KEY)
+    Tr,
+}
+
+impl DescriptorType {
+    /// Returns the address format of the descriptor type, or `None` for Taproot.
+    pub fn to_format(&self) -> Option<BitcoinFormat> {
+        match self {
+            DescriptorType::Pkh => Some(BitcoinFormat::P2PKH),
+            DescriptorType::ShWpkh => Some(BitcoinFormat::P2SH_P2WPKH),
+            DescriptorType::Wpkh => Some(BitcoinFormat::Bech32),
+            DescriptorType::Tr => None,
+        }
+    }
+
+    /// Returns the descriptor type of the given address format.
+    pub fn from_format(format: &BitcoinFormat) -> Result<Self, DescriptorError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(DescriptorType::Pkh),
+            BitcoinFormat::P2SH_P2WPKH => Ok(DescriptorType::ShWpkh),
+            BitcoinFormat::Bech32 => Ok(DescriptorType::Wpkh),
+            _ => Err(DescriptorError::UnsupportedDescriptor(format.to_string())),
+        }
+    }
+
+    /// Returns the opening and closing of the script expression of the descriptor type.
+    fn script_expression(&self) -> (&'static str, &'static str) {
+        match self {
+            DescriptorType::Pkh => ("pkh(", ")"),
+            DescriptorType::ShWpkh => ("sh(wpkh(", "))"),
+            DescriptorType::Wpkh => ("wpkh(", ")"),
+            DescriptorType::Tr => ("tr(", ")"),
+        }
+    }
+}
+
+/// Represents the origin of a key, the fingerprint of its master key and the path from it,
+/// e.g. `[73c5da0a/84'/0'/0']`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyOrigin {
+    /// The fingerprint of the master key
+    pub fingerprint: [u8; 4],
+    /// The derivation path from the master key
+    pub path: Vec<ChildIndex>,
+}
+
+impl KeyOrigin {
+    /// Returns the key origin of the given master key fingerprint and derivation path.
+    pub fn new<N: BitcoinNetwork>(
+        fingerprint: [u8; 4],
+        path: &BitcoinDerivationPath<N>,
+    ) -> Result<Self, DescriptorError> {
+        Ok(Self {
+            fingerprint,
+            path: path.to_vec()?,
+        })
+    }
+}
+
+impl FromStr for KeyOrigin {
+    type Err = DescriptorError;
+
+    fn from_str(origin: &str) -> Result<Self, Self::Err> {
+        let inner = match (origin.starts_with('['), origin.ends_with(']')) {
+            (true, true) if origin.len() > 1 => &origin[1..origin.len() - 1],
+            _ => return Err(DescriptorError::InvalidKeyOrigin(origin.into())),
+        };
+
+        let mut parts = inner.split('/');
+        let fingerprint = hex::decode(parts.next().unwrap_or_default())?;
+        if fingerprint.len() != 4 {
+            return Err(DescriptorError::InvalidKeyOrigin(origin.into()));
+        }
+
+        let mut key_origin = Self {
+            fingerprint: [0u8; 4],
+            path: parts.map(ChildIndex::from_str).collect::<Result<_, _>>()?,
+        };
+        key_origin.fingerprint.copy_from_slice(&fingerprint);
+        Ok(key_origin)
+    }
+}
+
+impl fmt::Display for KeyOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}", hex::encode(self.fingerprint))?;
+        for index in self.path.iter() {
+            write!(f, "/{}", index)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Represents an extended public key and the origin it was derived from, e.g.
+/// `[73c5da0a/84'/0'/0']xpub...`, for export to watch-only wallets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivedExtendedPublicKey<N: BitcoinNetwork> {
+    /// The origin of the extended public key
+    pub origin: KeyOrigin,
+    /// The extended public key
+    pub extended_public_key: BitcoinExtendedPublicKey<N>,
+}
+
+impl<N: BitcoinNetwork> DerivedExtendedPublicKey<N> {
+    /// Returns the derived extended public key of the given origin and extended public key.
+    pub fn new(origin: KeyOrigin, extended_public_key: BitcoinExtendedPublicKey<N>) -> Self {
+        Self {
+            origin,
+            extended_public_key,
+        }
+    }
+
+    /// Returns the descriptor of the addresses of the given chain of the derived extended public key.
+    pub fn to_descriptor(
+        &self,
+        descriptor_type: DescriptorType,
+        chain: u32,
+    ) -> Result<BitcoinDescriptor<N>, DescriptorError> {
+        BitcoinDescriptor::from_account(
+            descriptor_type,
+            &self.extended_public_key,
+            Some(self.origin.clone()),
+            chain,
+        )
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for DerivedExtendedPublicKey<N> {
+    type Err = DescriptorError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        match (key.starts_with('['), key.find(']')) {
+            (true, Some(position)) => Ok(Self::new(
+                KeyOrigin::from_str(&key[..=position])?,
+                BitcoinExtendedPublicKey::from_str(&key[position + 1..])?,
+            )),
+            _ => Err(DescriptorError::InvalidKeyOrigin(key.into())),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for DerivedExtendedPublicKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.origin, self.extended_public_key)
+    }
+}
+
+/// Represents the key of a descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DescriptorKey<N: BitcoinNetwork> {
+    /// A public key, e.g. `02c6...`, which is x-only in a Taproot descriptor
+    Single(BitcoinPublicKey<N>),
+    /// An extended public key and the normal child indices below it, e.g. `xpub.../0/*`,
+    /// followed by the index of each address if the path ends in a wildcard
+    Extended {
+        extended_public_key: BitcoinExtendedPublicKey<N>,
+        path: Vec<ChildIndex>,
+        wildcard: bool,
+    },
+}
+
+impl<N: BitcoinNetwork> DescriptorKey<N> {
+    /// Returns the public key of the descriptor key at the given index, which is ignored unless
+    /// the key ends in a wildcard.
+    pub fn to_public_key(&self, index: u32) -> Result<BitcoinPublicKey<N>, DescriptorError> {
+        match self {
+            DescriptorKey::Single(public_key) => Ok(public_key.clone()),
+            DescriptorKey::Extended {
+                extended_public_key,
+                path,
+                wildcard,
+            } => {
+                let mut path = path.clone();
+                if *wildcard {
+                    path.push(ChildIndex::normal(index)?);
+                }
+                let path = BitcoinDerivationPath::from_vec(&path)?;
+                Ok(extended_public_key.derive(&path)?.to_public_key())
+            }
+        }
+    }
+
+    /// Returns the key expression of the descriptor key for the given descriptor type.
+    fn to_key_expression(&self, descriptor_type: &DescriptorType) -> Result<String, DescriptorError> {
+        match self {
+            DescriptorKey::Single(public_key) => match descriptor_type {
+                DescriptorType::Tr => Ok(hex::encode(public_key.to_x_only_bytes())),
+                _ => Ok(public_key.to_string()),
+            },
+            DescriptorKey::Extended {
+                extended_public_key,
+                path,
+                wildcard,
+            } => {
+                // Descriptors carry the script type, so keys are rendered with the xpub version bytes.
+                let mut expression = extended_public_key.to_format(&BitcoinFormat::P2PKH)?.to_string();
+                path.iter()
+                    .for_each(|index| expression.push_str(&format!("/{}", index)));
+                if *wildcard {
+                    expression.push_str("/*");
+                }
+                Ok(expression)
+            }
+        }
+    }
+
+    /// Returns the descriptor key of the given key expression for the given descriptor type.
+    fn from_key_expression(expression: &str, descriptor_type: &DescriptorType) -> Result<Self, DescriptorError> {
+        let is_hex = expression.bytes().all(|byte| byte.is_ascii_hexdigit());
+        match (is_hex, expression.len(), descriptor_type) {
+            (true, 64, DescriptorType::Tr)
+            | (true, 66, DescriptorType::Pkh)
+            | (true, 66, DescriptorType::ShWpkh)
+            | (true, 66, DescriptorType::Wpkh)
+            | (true, 130, DescriptorType::Pkh) => {
+                return Ok(DescriptorKey::Single(BitcoinPublicKey::from_str(expression)?));
+            }
+            (true, _, _) => return Err(DescriptorError::InvalidDescriptor(expression.into())),
+            _ => (),
+        };
+
+        let mut parts = expression.split('/');
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(parts.next().unwrap_or_default())?;
+
+        let mut path = vec![];
+        let mut wildcard = false;
+        for part in parts {
+            if wildcard {
+                return Err(DescriptorError::InvalidDescriptor(expression.into()));
+            }
+            match part {
+                "*" => wildcard = true,
+                "*'" | "*h" | "*H" => return Err(DescriptorError::UnsupportedDescriptor(expression.into())),
+                _ => match ChildIndex::from_str(part)? {
+                    ChildIndex::Hardened(_) => return Err(DescriptorError::UnsupportedDescriptor(expression.into())),
+                    index => path.push(index),
+                },
+            }
+        }
+
+        Ok(DescriptorKey::Extended {
+            extended_public_key,
+            path,
+            wildcard,
+        })
+    }
+}
+
+/// Represents a single-key output descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinDescriptor<N: BitcoinNetwork> {
+    /// The script type of the descriptor
+    pub descriptor_type: DescriptorType,
+    /// The origin of the key, if known
+    pub origin: Option<KeyOrigin>,
+    /// The key of the descriptor
+    pub key: DescriptorKey<N>,
+}
+
+impl<N: BitcoinNetwork> BitcoinDescriptor<N> {
+    /// Returns a descriptor of the given script type, key origin, and key.
+    pub fn new(descriptor_type: DescriptorType, origin: Option<KeyOrigin>, key: DescriptorKey<N>) -> Self {
+        Self {
+            descriptor_type,
+            origin,
+            key,
+        }
+    }
+
+    /// Returns the descriptor of the addresses of the given chain of an account, e.g.
+    /// `wpkh([73c5da0a/84'/0'/0']xpub.../0/*)` for the receiving addresses of a BIP84 account.
+    pub fn from_account(
+        descriptor_type: DescriptorType,
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        origin: Option<KeyOrigin>,
+        chain: u32,
+    ) -> Result<Self, DescriptorError> {
+        Ok(Self::new(
+            descriptor_type,
+            origin,
+            DescriptorKey::Extended {
+                extended_public_key: extended_public_key.clone(),
+                path: vec![ChildIndex::normal(chain)?],
+                wildcard: true,
+            },
+        ))
+    }
+
+    /// Returns the address of the descriptor at the given index, which is ignored unless the key
+    /// of the descriptor ends in a wildcard.
+    pub fn to_address(&self, index: u32) -> Result<BitcoinAddress<N>, DescriptorError> {
+        match self.descriptor_type.to_format() {
+            Some(format) => Ok(self.key.to_public_key(index)?.to_address(&format)?),
+            None => Err(DescriptorError::UnsupportedDescriptor(
+                "Taproot addresses are not supported".into(),
+            )),
+        }
+    }
+
+    /// Returns the descriptor without its checksum.
+    fn to_descriptor_string(&self) -> Result<String, DescriptorError> {
+        let (open, close) = self.descriptor_type.script_expression();
+        let origin = match &self.origin {
+            Some(origin) => origin.to_string(),
+            None => String::new(),
+        };
+        let key = self.key.to_key_expression(&self.descriptor_type)?;
+        Ok(format!("{}{}{}{}", open, origin, key, close))
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinDescriptor<N> {
+    type Err = DescriptorError;
+
+    /// Returns the descriptor of the given string, which is checked against its checksum if present.
+    fn from_str(descriptor: &str) -> Result<Self, Self::Err> {
+        let body = match descriptor.find('#') {
+            Some(position) => {
+                let (body, checksum) = (&descriptor[..position], &descriptor[position + 1..]);
+                let expected = descriptor_checksum(body)?;
+                if expected != checksum {
+                    return Err(DescriptorError::InvalidChecksum(expected, checksum.into()));
+                }
+                body
+            }
+            None => descriptor,
+        };
+
+        let types = [
+            DescriptorType::ShWpkh,
+            DescriptorType::Wpkh,
+            DescriptorType::Pkh,
+            DescriptorType::Tr,
+        ];
+        let (descriptor_type, expression) = types
+            .iter()
+            .find_map(|descriptor_type| {
+                let (open, close) = descriptor_type.script_expression();
+                match body.starts_with(open) && body.ends_with(close) && body.len() >= open.len() + close.len() {
+                    true => Some((*descriptor_type, &body[open.len()..body.len() - close.len()])),
+                    false => None,
+                }
+            })
+            .ok_or_else(|| DescriptorError::UnsupportedDescriptor(body.into()))?;
+
+        // Taproot descriptors with script trees and multi-key descriptors are not supported.
+        if expression.contains(',') || expression.contains('(') {
+            return Err(DescriptorError::UnsupportedDescriptor(body.into()));
+        }
+
+        let (origin, key) = match expression.starts_with('[') {
+            true => match expression.find(']') {
+                Some(position) => (
+                    Some(KeyOrigin::from_str(&expression[..=position])?),
+                    &expression[position + 1..],
+                ),
+                None => return Err(DescriptorError::InvalidKeyOrigin(expression.into())),
+            },
+            false => (None, expression),
+        };
+
+        Ok(Self::new(
+            descriptor_type,
+            origin,
+            DescriptorKey::from_key_expression(key, &descriptor_type)?,
+        ))
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinDescriptor<N> {
+    /// Returns the descriptor with its checksum, e.g. `pkh(02c6...)#8fhd9pwu`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let descriptor = self.to_descriptor_string().map_err(|_| fmt::Error)?;
+        let checksum = descriptor_checksum(&descriptor).map_err(|_| fmt::Error)?;
+        write!(f, "{}#{}", descriptor, checksum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extended_private_key::BitcoinExtendedPrivateKey;
+    use crate::mnemonic::BitcoinMnemonic;
+    use crate::network::*;
+    use crate::wordlist::*;
+    use wagyu_model::mnemonic::MnemonicExtended;
+    use wagyu_model::{ExtendedPrivateKey, Mnemonic};
+
+    type N = Mainnet;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn master() -> BitcoinExtendedPrivateKey<N> {
+        let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+        mnemonic.to_extended_private_key(None).unwrap()
+    }
+
+    #[test]
+    fn checksum() {
+        // The examples of the descriptor documentation of Bitcoin Core
+        [
+            (
+                "pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)",
+                "8fhd9pwu",
+            ),
+            (
+                "wpkh(02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9)",
+                "8zl0zxma",
+            ),
+            (
+                "sh(wpkh(03fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556))",
+                "qkrrc7je",
+            ),
+            (
+                "pkh([d34db33f/44'/0'/0']xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*)",
+                "ml40v0wf",
+            ),
+        ]
+        .iter()
+        .for_each(|(descriptor, checksum)| {
+            assert_eq!(*checksum, descriptor_checksum(descriptor).unwrap());
+
+            let with_checksum = format!("{}#{}", descriptor, checksum);
+            assert_eq!(with_checksum, BitcoinDescriptor::<N>::from_str(&with_checksum).unwrap().to_string());
+            assert_eq!(with_checksum, BitcoinDescriptor::<N>::from_str(descriptor).unwrap().to_string());
+        });
+
+        assert!(descriptor_checksum("pkh(\u{e9})").is_err());
+        assert!(BitcoinDescriptor::<N>::from_str(
+            "pkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)#8fhd9pwv"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn from_account() {
+        let master = master();
+        let account = master.derive_path("m/84'/0'/0'").unwrap();
+        let path = BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'").unwrap();
+        let origin = KeyOrigin::new(master.fingerprint(), &path).unwrap();
+        let descriptor =
+            BitcoinDescriptor::from_account(DescriptorType::Wpkh, &account.to_extended_public_key(), Some(origin), 0)
+                .unwrap();
+
+        assert_eq!(
+            "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van",
+            descriptor.to_string()
+        );
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            descriptor.to_address(0).unwrap().to_string()
+        );
+        assert_eq!(
+            account
+                .derive_path("m/0/7")
+                .unwrap()
+                .to_address(&BitcoinFormat::Bech32)
+                .unwrap(),
+            descriptor.to_address(7).unwrap()
+        );
+        assert_eq!(
+            descriptor,
+            BitcoinDescriptor::from_str(&descriptor.to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn derived_extended_public_key() {
+        // The key origin and xpub of the BIP84 account of the standard test seed, as shown by Sparrow
+        let expected = "[73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+        let path = BitcoinDerivationPath::<N>::from_str("m/84'/0'/0'").unwrap();
+        let derived = master().derive_with_origin(&path).unwrap();
+        assert_eq!(expected, derived.to_string());
+        assert_eq!(derived, DerivedExtendedPublicKey::from_str(expected).unwrap());
+        assert_eq!(
+            "wpkh([73c5da0a/84'/0'/0']xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V/0/*)#wc3n3van",
+            derived.to_descriptor(DescriptorType::Wpkh, 0).unwrap().to_string()
+        );
+
+        let master = master().to_extended_public_key();
+        let derived = DerivedExtendedPublicKey::<N>::from_str(&format!("[73c5da0a]{}", master)).unwrap();
+        assert!(derived.origin.path.is_empty());
+        assert_eq!(master, derived.extended_public_key);
+
+        assert!(DerivedExtendedPublicKey::<N>::from_str(&expected[20..]).is_err());
+        assert!(DerivedExtendedPublicKey::<N>::from_str(&expected[1..]).is_err());
+        assert!(DerivedExtendedPublicKey::<N>::from_str(&expected[..expected.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn descriptor_types() {
+        let master = master();
+        [
+            (DescriptorType::Pkh, "m/44'/0'/0'", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            (
+                DescriptorType::ShWpkh,
+                "m/49'/0'/0'",
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            ),
+            (
+                DescriptorType::Wpkh,
+                "m/84'/0'/0'",
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+        ]
+        .iter()
+        .for_each(|(descriptor_type, path, address)| {
+            let account = master.derive_path(path).unwrap().to_extended_public_key();
+            let descriptor = BitcoinDescriptor::from_account(*descriptor_type, &account, None, 0).unwrap();
+            assert_eq!(*address, descriptor.to_address(0).unwrap().to_string());
+
+            let parsed = BitcoinDescriptor::<N>::from_str(&descriptor.to_string()).unwrap();
+            assert_eq!(*descriptor_type, parsed.descriptor_type);
+            assert_eq!(*address, parsed.to_address(0).unwrap().to_string());
+            assert_eq!(
+                *descriptor_type,
+                DescriptorType::from_format(&descriptor_type.to_format().unwrap()).unwrap()
+            );
+        });
+    }
+
+    #[test]
+    fn taproot() {
+        let descriptor = "tr(c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)";
+        let parsed = BitcoinDescriptor::<N>::from_str(descriptor).unwrap();
+        assert_eq!(DescriptorType::Tr, parsed.descriptor_type);
+        assert_eq!(
+            format!("{}#{}", descriptor, descriptor_checksum(descriptor).unwrap()),
+            parsed.to_string()
+        );
+        assert!(parsed.to_address(0).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        [
+            "pkh()",
+            "wpkh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709e)",
+            "wpkh(04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a)",
+            "sh(02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)",
+            "multi(1,02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5)",
+            "tr(c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5,pk(c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5))",
+            "pkh([d34db33f/44'/0'/0'xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*)",
+            "pkh([d34db3/44'/0'/0']xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*)",
+            "pkh(xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1'/*)",
+            "pkh(xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/*/1)",
+            "pkh(xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL/1/*')",
+        ]
+        .iter()
+        .for_each(|descriptor| {
+            assert!(BitcoinDescriptor::<N>::from_str(descriptor).is_err(), "{}", descriptor);
+        });
+
+        // Testnet keys are rejected on mainnet.
+        let master = BitcoinExtendedPrivateKey::<Testnet>::new_master(&[0u8; 32], &BitcoinFormat::P2PKH).unwrap();
+        let descriptor = format!("wpkh({}/0/*)", master.to_extended_public_key());
+        assert!(BitcoinDescriptor::<Testnet>::from_str(&descriptor).is_ok());
+        assert!(BitcoinDescriptor::<N>::from_str(&descriptor).is_err());
+    }
+
+    mod proptests {
+        use super::*;
+        use crate::private_key::BitcoinPrivateKey;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        fn descriptor_type() -> impl Strategy<Value = DescriptorType> {
+            prop_oneof![
+                Just(DescriptorType::Pkh),
+                Just(DescriptorType::ShWpkh),
+                Just(DescriptorType::Wpkh),
+                Just(DescriptorType::Tr),
+            ]
+        }
+
+        fn key_origin() -> impl Strategy<Value = KeyOrigin> {
+            (any::<[u8; 4]>(), vec(any::<ChildIndex>(), 0..8))
+                .prop_map(|(fingerprint, path)| KeyOrigin { fingerprint, path })
+        }
+
+        fn test_to_string_from_str(descriptor: &BitcoinDescriptor<N>) -> Result<(), TestCaseError> {
+            let expected = descriptor.to_string();
+            prop_assert_eq!(descriptor, &BitcoinDescriptor::from_str(&expected).unwrap());
+
+            // A descriptor without its checksum is also accepted.
+            let body = &expected[..expected.find('#').unwrap()];
+            prop_assert_eq!(descriptor, &BitcoinDescriptor::from_str(body).unwrap());
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn key_origin_to_string_from_str(origin in key_origin()) {
+                prop_assert_eq!(&origin, &KeyOrigin::from_str(&origin.to_string()).unwrap());
+            }
+
+            #[test]
+            fn derived_extended_public_key_to_string_from_str(
+                seed: [u8; 32],
+                path: BitcoinDerivationPath<N>,
+            ) {
+                let master = BitcoinExtendedPrivateKey::<N>::new_master(&seed, &BitcoinFormat::P2PKH).unwrap();
+                let derived = master.derive_with_origin(&path).unwrap();
+                prop_assert_eq!(&derived, &DerivedExtendedPublicKey::from_str(&derived.to_string()).unwrap());
+            }
+
+            #[test]
+            fn single_key_to_string_from_str(
+                descriptor_type in descriptor_type(),
+                origin in proptest::option::of(key_origin()),
+                private_key: BitcoinPrivateKey<N>,
+            ) {
+                // Taproot keys are x-only, and every other script type but P2PKH requires a compressed key.
+                let public_key = BitcoinPublicKey::from_private_key(&private_key);
+                let public_key = match descriptor_type {
+                    DescriptorType::Tr => BitcoinPublicKey::from_bytes(&public_key.to_x_only_bytes()).unwrap(),
+                    DescriptorType::Pkh => public_key,
+                    _ => public_key.to_compressed(),
+                };
+                let descriptor = BitcoinDescriptor::new(descriptor_type, origin, DescriptorKey::Single(public_key));
+                test_to_string_from_str(&descriptor)?;
+            }
+
+            #[test]
+            fn extended_key_to_string_from_str(
+                descriptor_type in descriptor_type(),
+                origin in proptest::option::of(key_origin()),
+                extended_private_key: BitcoinExtendedPrivateKey<N>,
+                path in vec(0..1u32 << 31, 0..3),
+                wildcard: bool,
+            ) {
+                // Descriptors render extended public keys with the P2PKH version bytes.
+                let extended_public_key =
+                    extended_private_key.to_extended_public_key().to_format(&BitcoinFormat::P2PKH).unwrap();
+                let key = DescriptorKey::Extended {
+                    extended_public_key,
+                    path: path.into_iter().map(ChildIndex::Normal).collect(),
+                    wildcard,
+                };
+                test_to_string_from_str(&BitcoinDescriptor::new(descriptor_type, origin, key))?;
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                let _ = descriptor_checksum(&inp);
+                test_from_str_round_trip::<KeyOrigin>(&inp)?;
+                test_from_str_round_trip::<DerivedExtendedPublicKey<N>>(&inp)?;
+                test_from_str_round_trip::<BitcoinDescriptor<N>>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_expression(
+                inp in "(pkh|wpkh|sh\\(wpkh|tr)\\((\\[[0-9a-f]{0,9}(/[0-9]{1,3}'?){0,3}\\]?)?[0-9a-f/*'h,()]{0,70}\\)(#[a-z0-9]{0,9})?",
+            ) {
+                if let Ok(descriptor) = BitcoinDescriptor::<N>::from_str(&inp) {
+                    prop_assert_eq!(&descriptor, &BitcoinDescriptor::from_str(&descriptor.to_string()).unwrap());
+                }
+            }
+        }
+    }
+}
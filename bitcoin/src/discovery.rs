@@ -0,0 +1,280 @@
+//!
+//! Account Discovery
+//! https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki#account-discovery
+//!
+//! This module scans the external and change chains of an account extended public key for
+//! used addresses, stopping each chain after a gap of consecutive unused addresses. The usage
+//! of an address is looked up through an `AddressUsage` backend supplied by the caller, such as
+//! an Electrum or Esplora client.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, ChildIndex, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError};
+
+use core::str::FromStr;
+
+/// The gap limit recommended by BIP44, the number of consecutive unused addresses after which a chain is considered unused.
+pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+#[derive(Debug, Fail)]
+pub enum DiscoveryError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(DerivationPathError),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPublicKeyError(ExtendedPublicKeyError),
+
+    #[fail(display = "{}", _0)]
+    Message(String),
+}
+
+impl From<AddressError> for DiscoveryError {
+    fn from(error: AddressError) -> Self {
+        DiscoveryError::AddressError(error)
+    }
+}
+
+impl From<DerivationPathError> for DiscoveryError {
+    fn from(error: DerivationPathError) -> Self {
+        DiscoveryError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPublicKeyError> for DiscoveryError {
+    fn from(error: ExtendedPublicKeyError) -> Self {
+        DiscoveryError::ExtendedPublicKeyError(error)
+    }
+}
+
+/// The interface for a backend that reports whether an address has been used.
+pub trait AddressUsage<N: BitcoinNetwork> {
+    /// Returns `true` if the given address appears in any transaction.
+    fn is_used(&mut self, address: &BitcoinAddress<N>) -> Result<bool, DiscoveryError>;
+}
+
+/// Represents a chain of an account, m/{chain}/{index}
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Chain {
+    /// The external chain, for receiving addresses - m/0/{index}
+    External,
+    /// The internal chain, for change addresses - m/1/{index}
+    Change,
+}
+
+impl Chain {
+    /// Returns the child index of the chain.
+    pub fn to_index(&self) -> u32 {
+        match self {
+            Chain::External => 0,
+            Chain::Change => 1,
+        }
+    }
+}
+
+/// Represents a used address found by account discovery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsedAddress<N: BitcoinNetwork> {
+    /// The derivation path of the address, relative to the account
+    pub path: BitcoinDerivationPath<N>,
+    /// The address
+    pub address: BitcoinAddress<N>,
+}
+
+/// Represents the result of account discovery
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discovery<N: BitcoinNetwork> {
+    /// The used addresses, external chain first, in order of index
+    pub used: Vec<UsedAddress<N>>,
+    /// The index after the last used address of the external chain
+    pub next_external: u32,
+    /// The index after the last used address of the change chain
+    pub next_change: u32,
+}
+
+impl<N: BitcoinNetwork> Discovery<N> {
+    /// Returns `true` if any address of the account has been used.
+    pub fn is_used(&self) -> bool {
+        !self.used.is_empty()
+    }
+
+    /// Returns the index after the last used address of the given chain.
+    pub fn next_index(&self, chain: Chain) -> u32 {
+        match chain {
+            Chain::External => self.next_external,
+            Chain::Change => self.next_change,
+        }
+    }
+}
+
+/// Returns the used addresses of the given account extended public key, scanning the external
+/// and change chains until `gap_limit` consecutive addresses are unused. The addresses are in
+/// the format of the extended public key, e.g. Bech32 for a zpub.
+pub fn discover_accounts<N: BitcoinNetwork, P: AddressUsage<N>>(
+    extended_public_key: &BitcoinExtendedPublicKey<N>,
+    gap_limit: u32,
+    provider: &mut P,
+) -> Result<Discovery<N>, DiscoveryError> {
+    let mut used = vec![];
+    let next_external = discover_chain(extended_public_key, Chain::External, gap_limit, provider, &mut used)?;
+    let next_change = discover_chain(extended_public_key, Chain::Change, gap_limit, provider, &mut used)?;
+    Ok(Discovery {
+        used,
+        next_external,
+        next_change,
+    })
+}
+
+/// Appends the used addresses of the given chain to `used`, and returns the index after the last used address.
+fn discover_chain<N: BitcoinNetwork, P: AddressUsage<N>>(
+    extended_public_key: &BitcoinExtendedPublicKey<N>,
+    chain: Chain,
+    gap_limit: u32,
+    provider: &mut P,
+    used: &mut Vec<UsedAddress<N>>,
+) -> Result<u32, DiscoveryError> {
+    let format = extended_public_key.format();
+    let chain_key = extended_public_key.derive_path(&format!("m/{}", chain.to_index()))?;
+
+    let mut next = 0;
+    let mut start = 0;
+    // Derive each batch of children to the end of the current gap at once.
+    while start < next + gap_limit {
+        let count = next + gap_limit - start;
+        for (offset, child) in chain_key
+            .derive_range(ChildIndex::normal(start)?, count)?
+            .iter()
+            .enumerate()
+        {
+            let index = start + offset as u32;
+            let address = child.to_address(&format)?;
+            if provider.is_used(&address)? {
+                let path = BitcoinDerivationPath::from_str(&format!("m/{}/{}", chain.to_index(), index))?;
+                used.push(UsedAddress { path, address });
+                next = index + 1;
+            }
+        }
+        start += count;
+    }
+    Ok(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use std::collections::HashSet;
+
+    type N = Mainnet;
+
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    /// An in-memory backend with a fixed set of used addresses, which records every lookup.
+    struct MockProvider {
+        used: HashSet<String>,
+        lookups: usize,
+    }
+
+    impl MockProvider {
+        fn new(extended_public_key: &BitcoinExtendedPublicKey<N>, paths: &[&str]) -> Self {
+            let format = extended_public_key.format();
+            let used = paths
+                .iter()
+                .map(|path| {
+                    extended_public_key
+                        .derive_path(path)
+                        .unwrap()
+                        .to_address(&format)
+                        .unwrap()
+                        .to_string()
+                })
+                .collect();
+            Self { used, lookups: 0 }
+        }
+    }
+
+    impl AddressUsage<N> for MockProvider {
+        fn is_used(&mut self, address: &BitcoinAddress<N>) -> Result<bool, DiscoveryError> {
+            self.lookups += 1;
+            Ok(self.used.contains(&address.to_string()))
+        }
+    }
+
+    /// A backend that is unreachable.
+    struct OfflineProvider;
+
+    impl AddressUsage<N> for OfflineProvider {
+        fn is_used(&mut self, _address: &BitcoinAddress<N>) -> Result<bool, DiscoveryError> {
+            Err(DiscoveryError::Message("connection refused".into()))
+        }
+    }
+
+    fn paths(discovery: &Discovery<N>) -> Vec<String> {
+        discovery.used.iter().map(|used| used.path.to_string()).collect()
+    }
+
+    #[test]
+    fn gap_in_middle() {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ZPUB).unwrap();
+        // The gap between m/0/5 and m/0/25 is 19 unused addresses, one short of the gap limit.
+        let used = ["m/0/0", "m/0/1", "m/0/5", "m/0/25", "m/1/0"];
+        let provider = &mut MockProvider::new(&extended_public_key, &used);
+
+        let discovery = discover_accounts(&extended_public_key, DEFAULT_GAP_LIMIT, provider).unwrap();
+        assert_eq!(used.to_vec(), paths(&discovery));
+        assert_eq!(26, discovery.next_index(Chain::External));
+        assert_eq!(1, discovery.next_index(Chain::Change));
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            discovery.used[0].address.to_string()
+        );
+        // Each chain is scanned up to the gap limit past its last used address.
+        assert_eq!((26 + 20) + (1 + 20), provider.lookups);
+    }
+
+    #[test]
+    fn gap_limit_reached() {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ZPUB).unwrap();
+        // The gap between m/0/5 and m/0/26 is 20 unused addresses, which ends the scan.
+        let provider = &mut MockProvider::new(&extended_public_key, &["m/0/0", "m/0/5", "m/0/26"]);
+
+        let discovery = discover_accounts(&extended_public_key, DEFAULT_GAP_LIMIT, provider).unwrap();
+        assert_eq!(vec!["m/0/0", "m/0/5"], paths(&discovery));
+        assert_eq!(6, discovery.next_external);
+
+        let discovery = discover_accounts(&extended_public_key, 3, provider).unwrap();
+        assert_eq!(vec!["m/0/0"], paths(&discovery));
+        assert_eq!(1, discovery.next_external);
+    }
+
+    #[test]
+    fn unused_account() {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ZPUB).unwrap();
+        let provider = &mut MockProvider::new(&extended_public_key, &[]);
+
+        let discovery = discover_accounts(&extended_public_key, DEFAULT_GAP_LIMIT, provider).unwrap();
+        assert!(!discovery.is_used());
+        assert_eq!(0, discovery.next_external);
+        assert_eq!(0, discovery.next_change);
+        assert_eq!(40, provider.lookups);
+    }
+
+    #[test]
+    fn provider_error() {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(ZPUB).unwrap();
+        match discover_accounts(&extended_public_key, DEFAULT_GAP_LIMIT, &mut OfflineProvider) {
+            Err(DiscoveryError::Message(message)) => assert_eq!("connection refused", message),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
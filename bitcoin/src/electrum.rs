@@ -0,0 +1,424 @@
+//!
+//! Electrum Mnemonics
+//!
+//! This module implements the version 2 seed phrases of Electrum, which are not BIP39 mnemonics:
+//! the phrase carries no checksum word, its seed type is committed to by the prefix of an
+//! HMAC-SHA512 of the phrase, and its seed is stretched with the salt "electrum".
+//! https://electrum.readthedocs.io/en/latest/seedphrase.html
+//!
+
+use crate::address::BitcoinAddress;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use crate::wordlist::BitcoinWordlist;
+use wagyu_model::no_std::*;
+use wagyu_model::{ExtendedPrivateKey, Mnemonic, MnemonicError, MnemonicExtended};
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2;
+use rand::Rng;
+use sha2::Sha512;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+const PBKDF2_ROUNDS: usize = 2048;
+const PBKDF2_BYTES: usize = 64;
+
+/// The number of words of a new Electrum mnemonic, which encodes 132 bits
+const WORD_COUNT: usize = 12;
+
+/// Represents the seed type of an Electrum mnemonic
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ElectrumSeedType {
+    /// Pay-to-Pubkey Hash wallets - version prefix 01, receiving addresses at m/0/{index}
+    Standard,
+    /// Native SegWit wallets - version prefix 100, receiving addresses at m/0'/0/{index}
+    Segwit,
+}
+
+impl ElectrumSeedType {
+    /// Returns the hex prefix of the seed version of the seed type.
+    pub fn to_prefix(self) -> &'static str {
+        match self {
+            ElectrumSeedType::Standard => "01",
+            ElectrumSeedType::Segwit => "100",
+        }
+    }
+
+    /// Returns the address format of the wallets of the seed type.
+    pub fn to_format(self) -> BitcoinFormat {
+        match self {
+            ElectrumSeedType::Standard => BitcoinFormat::P2PKH,
+            ElectrumSeedType::Segwit => BitcoinFormat::Bech32,
+        }
+    }
+
+    /// Returns the seed type of the given phrase, or `None` if it is not an Electrum phrase.
+    pub fn from_phrase(phrase: &str) -> Option<Self> {
+        let version = seed_version(&normalize(phrase));
+        [ElectrumSeedType::Standard, ElectrumSeedType::Segwit]
+            .iter()
+            .find(|seed_type| version.starts_with(seed_type.to_prefix()))
+            .copied()
+    }
+}
+
+impl fmt::Display for ElectrumSeedType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ElectrumSeedType::Standard => write!(f, "standard"),
+            ElectrumSeedType::Segwit => write!(f, "segwit"),
+        }
+    }
+}
+
+/// Represents an Electrum mnemonic
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ElectrumMnemonic<N: BitcoinNetwork, W: BitcoinWordlist> {
+    /// The normalized phrase
+    phrase: String,
+    /// The seed type committed to by the phrase
+    seed_type: ElectrumSeedType,
+    /// PhantomData
+    _network: PhantomData<N>,
+    /// PhantomData
+    _wordlist: PhantomData<W>,
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for ElectrumMnemonic<N, W> {
+    type Address = BitcoinAddress<N>;
+    type Format = BitcoinFormat;
+    type PrivateKey = BitcoinPrivateKey<N>;
+    type PublicKey = BitcoinPublicKey<N>;
+
+    /// Returns a new segwit mnemonic.
+    fn new<R: Rng>(rng: &mut R) -> Result<Self, MnemonicError> {
+        Self::new_with_seed_type(rng, ElectrumSeedType::Segwit)
+    }
+
+    /// Returns the mnemonic for the given phrase.
+    fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+        match ElectrumSeedType::from_phrase(phrase) {
+            Some(seed_type) => Ok(Self {
+                phrase: normalize(phrase),
+                seed_type,
+                _network: PhantomData,
+                _wordlist: PhantomData,
+            }),
+            None => Err(MnemonicError::InvalidPhrase(phrase.into())),
+        }
+    }
+
+    /// Returns the phrase of the corresponding mnemonic.
+    fn to_phrase(&self) -> Result<String, MnemonicError> {
+        Ok(self.phrase.clone())
+    }
+
+    /// Returns the private key of the account of the corresponding mnemonic.
+    fn to_private_key(&self, password: Option<&str>) -> Result<Self::PrivateKey, MnemonicError> {
+        Ok(self.to_extended_private_key(password)?.to_private_key())
+    }
+
+    /// Returns the public key of the account of the corresponding mnemonic.
+    fn to_public_key(&self, password: Option<&str>) -> Result<Self::PublicKey, MnemonicError> {
+        Ok(self.to_extended_private_key(password)?.to_public_key())
+    }
+
+    /// Returns the address of the account of the corresponding mnemonic.
+    fn to_address(&self, password: Option<&str>, format: &Self::Format) -> Result<Self::Address, MnemonicError> {
+        Ok(self.to_extended_private_key(password)?.to_address(format)?)
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicExtended for ElectrumMnemonic<N, W> {
+    type ExtendedPrivateKey = BitcoinExtendedPrivateKey<N>;
+    type ExtendedPublicKey = BitcoinExtendedPublicKey<N>;
+
+    /// Returns the extended private key of the account of the corresponding mnemonic, the master
+    /// key for standard seeds and m/0' for segwit seeds, as displayed by Electrum.
+    fn to_extended_private_key(&self, password: Option<&str>) -> Result<Self::ExtendedPrivateKey, MnemonicError> {
+        let format = self.seed_type.to_format();
+        let master = Self::ExtendedPrivateKey::new_master(self.to_seed(password).as_slice(), &format)?;
+        match self.seed_type {
+            ElectrumSeedType::Standard => Ok(master),
+            ElectrumSeedType::Segwit => Ok(master.derive_path("m/0'")?),
+        }
+    }
+
+    /// Returns the extended public key of the account of the corresponding mnemonic.
+    fn to_extended_public_key(&self, password: Option<&str>) -> Result<Self::ExtendedPublicKey, MnemonicError> {
+        Ok(self.to_extended_private_key(password)?.to_extended_public_key())
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> ElectrumMnemonic<N, W> {
+    /// Returns a new mnemonic of the given seed type.
+    pub fn new_with_seed_type<R: Rng>(rng: &mut R, seed_type: ElectrumSeedType) -> Result<Self, MnemonicError> {
+        let wordlist = W::get_all();
+
+        // Sample a 12 word number in base 2048, least significant word first, and increment it
+        // until its phrase has the version prefix of the seed type.
+        let mut indices = (0..WORD_COUNT)
+            .map(|_| rng.gen_range(0, wordlist.len()))
+            .collect::<Vec<usize>>();
+        indices[WORD_COUNT - 1] = indices[WORD_COUNT - 1].max(1);
+
+        loop {
+            let phrase = indices
+                .iter()
+                .map(|index| wordlist[*index])
+                .collect::<Vec<&str>>()
+                .join(" ");
+            if let Ok(mnemonic) = Self::from_phrase(&phrase) {
+                if mnemonic.seed_type == seed_type {
+                    return Ok(mnemonic);
+                }
+            }
+
+            for index in indices.iter_mut() {
+                *index = (*index + 1) % wordlist.len();
+                if *index != 0 {
+                    break;
+                }
+            }
+            indices[WORD_COUNT - 1] = indices[WORD_COUNT - 1].max(1);
+        }
+    }
+
+    /// Returns the seed type of the corresponding mnemonic.
+    pub fn seed_type(&self) -> ElectrumSeedType {
+        self.seed_type
+    }
+
+    /// Returns `true` if the given phrase is an Electrum phrase of a supported seed type.
+    pub fn is_electrum_phrase(phrase: &str) -> bool {
+        ElectrumSeedType::from_phrase(phrase).is_some()
+    }
+
+    /// Returns the seed of the corresponding mnemonic and the given password.
+    pub fn to_seed(&self, password: Option<&str>) -> Vec<u8> {
+        let mut seed = vec![0u8; PBKDF2_BYTES];
+        let salt = format!("electrum{}", normalize(password.unwrap_or("")));
+        pbkdf2::<Hmac<Sha512>>(self.phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        seed
+    }
+
+    /// Returns the receiving address of the corresponding mnemonic at the given index, which is
+    /// the address that Electrum displays at that index of its receiving addresses.
+    pub fn to_receiving_address(&self, password: Option<&str>, index: u32) -> Result<BitcoinAddress<N>, MnemonicError> {
+        let account = self.to_extended_private_key(password)?;
+        let key = account.derive_path(&format!("m/0/{}", index))?;
+        Ok(key.to_address(&self.seed_type.to_format())?)
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> FromStr for ElectrumMnemonic<N, W> {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_phrase(s)
+    }
+}
+
+impl<N: BitcoinNetwork, W: BitcoinWordlist> fmt::Display for ElectrumMnemonic<N, W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.phrase)
+    }
+}
+
+/// Returns the hex encoded seed version of the given normalized phrase.
+fn seed_version(phrase: &str) -> String {
+    let mut mac = Hmac::<Sha512>::new_varkey(b"Seed version").expect("HMAC accepts keys of any length");
+    mac.input(phrase.as_bytes());
+    hex::encode(mac.result().code())
+}
+
+/// Returns the given text normalized as by Electrum: in NFKD form, lowercase, without accents,
+/// with single spaces between words, and without spaces between CJK characters.
+fn normalize(text: &str) -> String {
+    let text = text
+        .nfkd()
+        .flat_map(char::to_lowercase)
+        .filter(|character| !is_combining_mark(*character))
+        .collect::<String>();
+    let characters = text
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .chars()
+        .collect::<Vec<char>>();
+
+    characters
+        .iter()
+        .enumerate()
+        .filter(|(i, character)| !(**character == ' ' && is_cjk(characters[i - 1]) && is_cjk(characters[i + 1])))
+        .map(|(_, character)| *character)
+        .collect()
+}
+
+/// Returns `true` if the given character is a CJK ideograph, kana, or hangul syllable.
+fn is_cjk(character: char) -> bool {
+    matches!(
+        character as u32,
+        0x1100..=0x11FF
+        | 0x2E80..=0x2FDF
+        | 0x3040..=0x30FF
+        | 0x3130..=0x318F
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xAC00..=0xD7AF
+        | 0xF900..=0xFAFF
+        | 0x20000..=0x2FA1F
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mnemonic::BitcoinMnemonic;
+    use crate::network::*;
+    use crate::wordlist::*;
+
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    type N = Mainnet;
+    type W = English;
+
+    // The seeds of the Electrum wallet tests, with the first receiving address that Electrum
+    // displays for each
+    const SEGWIT: (&str, &str) = (
+        "bitter grass shiver impose acquire brush forget axis eager alone wine silver",
+        "bc1q3g5tmkmlvxryhh843v4dz026avatc0zzr6h3af",
+    );
+    const STANDARD: (&str, &str) = (
+        "cycle rocket west magnet parrot shuffle foot correct salt library feed song",
+        "1NNkttn1YvVGdqBW4PR6zvc3Zx3H5owKRf",
+    );
+
+    #[test]
+    fn from_phrase() {
+        [
+            (SEGWIT, ElectrumSeedType::Segwit),
+            (STANDARD, ElectrumSeedType::Standard),
+        ]
+        .iter()
+        .for_each(|((phrase, address), seed_type)| {
+            let mnemonic = ElectrumMnemonic::<N, W>::from_phrase(phrase).unwrap();
+            assert_eq!(*seed_type, mnemonic.seed_type());
+            assert_eq!(*address, mnemonic.to_receiving_address(None, 0).unwrap().to_string());
+            assert_eq!(*phrase, mnemonic.to_string());
+
+            let account = mnemonic.to_extended_private_key(None).unwrap();
+            assert_eq!(seed_type.to_format(), account.format());
+            assert_eq!(
+                account
+                    .derive_path("m/0/0")
+                    .unwrap()
+                    .to_address(&seed_type.to_format())
+                    .unwrap(),
+                mnemonic.to_receiving_address(None, 0).unwrap()
+            );
+        });
+    }
+
+    #[test]
+    fn normalize() {
+        let phrase = "  Bitter GRASS shiver\timpose acquíre brush forget axis eager alone wine silver ";
+        let mnemonic = ElectrumMnemonic::<N, W>::from_phrase(phrase).unwrap();
+        assert_eq!(SEGWIT.0, mnemonic.to_string());
+        assert_eq!(SEGWIT.1, mnemonic.to_receiving_address(None, 0).unwrap().to_string());
+
+        assert_eq!("あいう", super::normalize("あ い う"));
+        assert_eq!("a あ b", super::normalize("a あ b"));
+    }
+
+    #[test]
+    fn password() {
+        let mnemonic = ElectrumMnemonic::<N, W>::from_phrase(SEGWIT.0).unwrap();
+        assert_ne!(mnemonic.to_seed(None), mnemonic.to_seed(Some("password")));
+        assert_eq!(mnemonic.to_seed(None), mnemonic.to_seed(Some("")));
+    }
+
+    #[test]
+    fn is_electrum_phrase() {
+        assert!(ElectrumMnemonic::<N, W>::is_electrum_phrase(SEGWIT.0));
+        assert!(ElectrumMnemonic::<N, W>::is_electrum_phrase(STANDARD.0));
+
+        // A BIP39 mnemonic is not an Electrum phrase, and an Electrum phrase is not a BIP39 mnemonic.
+        let bip39 = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert!(!ElectrumMnemonic::<N, W>::is_electrum_phrase(bip39));
+        assert!(ElectrumMnemonic::<N, W>::from_phrase(bip39).is_err());
+        assert!(BitcoinMnemonic::<N, W>::from_phrase(SEGWIT.0).is_err());
+    }
+
+    #[test]
+    fn new() {
+        let rng = &mut XorShiftRng::seed_from_u64(0);
+        [ElectrumSeedType::Standard, ElectrumSeedType::Segwit]
+            .iter()
+            .for_each(|seed_type| {
+                let mnemonic = ElectrumMnemonic::<N, W>::new_with_seed_type(rng, *seed_type).unwrap();
+                assert_eq!(*seed_type, mnemonic.seed_type());
+                assert_eq!(12, mnemonic.to_string().split(' ').count());
+                assert_eq!(
+                    mnemonic,
+                    ElectrumMnemonic::<N, W>::from_phrase(&mnemonic.to_string()).unwrap()
+                );
+            });
+        assert_eq!(
+            ElectrumSeedType::Segwit,
+            ElectrumMnemonic::<N, W>::new(rng).unwrap().seed_type()
+        );
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(16))]
+
+            #[test]
+            fn new_to_string_from_str(seed: u64, segwit: bool) {
+                let seed_type = match segwit {
+                    true => ElectrumSeedType::Segwit,
+                    false => ElectrumSeedType::Standard,
+                };
+                let mnemonic = ElectrumMnemonic::<N, W>::new_with_seed_type(&mut XorShiftRng::seed_from_u64(seed), seed_type).unwrap();
+                prop_assert_eq!(seed_type, mnemonic.seed_type());
+                prop_assert_eq!(&mnemonic, &ElectrumMnemonic::from_str(&mnemonic.to_string()).unwrap());
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn from_str_words(indices in vec(0..2048usize, 1..14), uppercase: bool, separator in " |  |\t| \n") {
+                let words = indices.iter().map(|index| W::get(*index).unwrap()).collect::<Vec<String>>();
+                let phrase = words.join(" ");
+                let mnemonic = ElectrumMnemonic::<N, W>::from_str(&phrase);
+                if let Ok(mnemonic) = &mnemonic {
+                    prop_assert_eq!(&phrase, &mnemonic.to_string());
+                }
+
+                // The phrase is normalized before its seed version is checked.
+                let unnormalized = match uppercase {
+                    true => words.join(&separator).to_uppercase(),
+                    false => words.join(&separator),
+                };
+                prop_assert_eq!(mnemonic.ok(), ElectrumMnemonic::<N, W>::from_str(&unnormalized).ok());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<ElectrumMnemonic<N, W>>(&inp)?;
+                let _ = ElectrumMnemonic::<N, W>::is_electrum_phrase(&inp);
+            }
+        }
+    }
+}
@@ -1,26 +1,33 @@
 use crate::address::BitcoinAddress;
 use crate::derivation_path::BitcoinDerivationPath;
+use crate::descriptor::{DerivedExtendedPublicKey, KeyOrigin};
 use crate::extended_public_key::BitcoinExtendedPublicKey;
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::mnemonic::BitcoinMnemonic;
+use crate::network::{check_extended_private_key_version_bytes, BitcoinNetwork};
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
+use crate::wordlist::BitcoinWordlist;
+use wagyu_model::no_std::*;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    base58::{decode_check, encode_check},
+    child_depth,
+    crypto::hash160,
     AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
-    PrivateKey,
+    MnemonicError, PrivateKey, PublicKey,
 };
 
-use base58::{FromBase58, ToBase58};
 use core::{convert::TryFrom, fmt, fmt::Display, str::FromStr};
 use hmac::{Hmac, Mac};
-use secp256k1::{PublicKey, SecretKey};
+use rand::{CryptoRng, Rng};
+use secp256k1::SecretKey;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sha2::Sha512;
 
 type HmacSha512 = Hmac<Sha512>;
 
 /// Represents a Bitcoin extended private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub struct BitcoinExtendedPrivateKey<N: BitcoinNetwork> {
     /// The address format
     pub(super) format: BitcoinFormat,
@@ -34,6 +41,8 @@ pub struct BitcoinExtendedPrivateKey<N: BitcoinNetwork> {
     pub(super) chain_code: [u8; 32],
     /// The Bitcoin private key
     private_key: BitcoinPrivateKey<N>,
+    /// The Bitcoin public key, computed once as each derivation and fingerprint needs it
+    public_key: BitcoinPublicKey<N>,
 }
 
 impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
@@ -51,10 +60,18 @@ impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
 
     /// Returns a new Bitcoin extended private key.
     fn new_master(seed: &[u8], format: &Self::Format) -> Result<Self, ExtendedPrivateKeyError> {
+        // BIP32 specifies seeds between 128 and 512 bits
+        if !(16..=64).contains(&seed.len()) {
+            return Err(ExtendedPrivateKeyError::InvalidSeedLength(seed.len()));
+        }
+
         let mut mac = HmacSha512::new_varkey(b"Bitcoin seed")?;
         mac.input(seed);
         let hmac = mac.result().code();
-        let private_key = Self::PrivateKey::from_secp256k1_secret_key(&SecretKey::parse_slice(&hmac[0..32])?, true);
+        let secret_key =
+            SecretKey::parse_slice(&hmac[0..32]).map_err(|_| ExtendedPrivateKeyError::InvalidMasterSecretKey)?;
+        let private_key = Self::PrivateKey::from_secp256k1_secret_key(&secret_key, true);
+        let public_key = private_key.to_public_key();
 
         let mut chain_code = [0u8; 32];
         chain_code[0..32].copy_from_slice(&hmac[32..]);
@@ -66,59 +83,23 @@ impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
             child_index: ChildIndex::Normal(0),
             chain_code,
             private_key,
+            public_key,
         })
     }
 
     /// Returns the extended private key of the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPrivateKeyError> {
-        if self.depth == 255 {
-            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
-        }
-
         let mut extended_private_key = self.clone();
 
         for index in path.to_vec()?.into_iter() {
-            let public_key = &PublicKey::from_secret_key(&extended_private_key.private_key.to_secp256k1_secret_key())
-                .serialize_compressed()[..];
-
-            let mut mac = HmacSha512::new_varkey(&extended_private_key.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(point(kpar)) || ser32(i)).
-                ChildIndex::Normal(_) => mac.input(public_key),
-                // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
-                // (Note: The 0x00 pads the private key to make it 33 bytes long.)
-                ChildIndex::Hardened(_) => {
-                    mac.input(&[0u8]);
-                    mac.input(&extended_private_key.private_key.to_secp256k1_secret_key().serialize());
-                }
-            }
-            // Append the child index in big-endian format
-            mac.input(&u32::from(index).to_be_bytes());
-            let hmac = mac.result().code();
-
-            let mut secret_key = SecretKey::parse_slice(&hmac[0..32])?;
-            secret_key.tweak_add_assign(&extended_private_key.private_key.to_secp256k1_secret_key())?;
-            let private_key = Self::PrivateKey::from_secp256k1_secret_key(&secret_key, true);
-
-            let mut chain_code = [0u8; 32];
-            chain_code[0..32].copy_from_slice(&hmac[32..]);
-
-            let mut parent_fingerprint = [0u8; 4];
-            parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
-
             let format = match path {
                 BitcoinDerivationPath::BIP49(_) => BitcoinFormat::P2SH_P2WPKH,
+                BitcoinDerivationPath::BIP84(_) => BitcoinFormat::Bech32,
                 _ => extended_private_key.format.clone(),
             };
 
-            extended_private_key = Self {
-                format,
-                depth: extended_private_key.depth + 1,
-                parent_fingerprint,
-                child_index: index,
-                chain_code,
-                private_key,
-            }
+            let public_key = extended_private_key.serialize_public_key();
+            extended_private_key = extended_private_key.derive_child(index, &public_key, format)?;
         }
 
         Ok(extended_private_key)
@@ -136,33 +117,214 @@ impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
 
     /// Returns the public key of the corresponding extended private key.
     fn to_public_key(&self) -> Self::PublicKey {
-        self.private_key.to_public_key()
+        self.public_key.clone()
     }
 
     /// Returns the address of the corresponding extended private key.
     fn to_address(&self, format: &Self::Format) -> Result<Self::Address, AddressError> {
-        self.private_key.to_address(format)
+        self.public_key.to_address(format)
     }
 }
 
 impl<N: BitcoinNetwork> BitcoinExtendedPrivateKey<N> {
+    /// Returns a new master extended private key from the seed of the given mnemonic and password.
+    pub fn from_mnemonic<W: BitcoinWordlist>(
+        mnemonic: &BitcoinMnemonic<N, W>,
+        password: Option<&str>,
+        format: &BitcoinFormat,
+    ) -> Result<Self, MnemonicError> {
+        Ok(Self::new_master(&mnemonic.to_seed(password)?, format)?)
+    }
+
+    /// Returns the extended private key of the given derivation path,
+    /// derived from the seed of the given mnemonic and password.
+    pub fn from_mnemonic_path<W: BitcoinWordlist>(
+        mnemonic: &BitcoinMnemonic<N, W>,
+        password: Option<&str>,
+        path: &BitcoinDerivationPath<N>,
+    ) -> Result<Self, MnemonicError> {
+        Ok(Self::from_mnemonic(mnemonic, password, &BitcoinFormat::P2PKH)?.derive(path)?)
+    }
+
+    /// Returns a randomly-generated master extended private key from a 64-byte seed
+    /// drawn from a cryptographically secure RNG.
+    pub fn new_random<R: Rng + CryptoRng>(
+        rng: &mut R,
+        format: &BitcoinFormat,
+    ) -> Result<Self, ExtendedPrivateKeyError> {
+        let _ = N::to_extended_private_key_version_bytes(format)?;
+        loop {
+            let mut seed = [0u8; 64];
+            rng.fill_bytes(&mut seed);
+            // Rejection sample seeds that do not produce a valid secp256k1 secret key.
+            if let Ok(extended_private_key) = Self::new_master(&seed, format) {
+                return Ok(extended_private_key);
+            }
+        }
+    }
+
+    /// Returns the extended private key of the given derivation path string, e.g. "m/44'/0'/0'/0/5".
+    pub fn derive_path(&self, path: &str) -> Result<Self, ExtendedPrivateKeyError> {
+        self.derive(&BitcoinDerivationPath::from_str(path)?)
+    }
+
+    /// Returns the extended public key of the given derivation path with its key origin, the
+    /// fingerprint of this extended private key and the path from it, e.g. "[73c5da0a/84'/0'/0']xpub...".
+    /// The key origin starts at the master key, so this extended private key must be a master key.
+    pub fn derive_with_origin(
+        &self,
+        path: &BitcoinDerivationPath<N>,
+    ) -> Result<DerivedExtendedPublicKey<N>, ExtendedPrivateKeyError> {
+        if self.depth != 0 {
+            return Err(ExtendedPrivateKeyError::ExpectedMasterKey(self.depth));
+        }
+        let origin = KeyOrigin {
+            fingerprint: self.fingerprint(),
+            path: path.to_vec()?,
+        };
+        Ok(DerivedExtendedPublicKey::new(
+            origin,
+            self.derive(path)?.to_extended_public_key(),
+        ))
+    }
+
+    /// Returns the wallet import format (WIF) of the private key, in compressed form.
+    /// The network of the private key is the network of the extended private key, as both share the type parameter `N`.
+    pub fn to_wif(&self) -> String {
+        self.private_key.to_string()
+    }
+
+    /// Returns the BIP32 serialization of the extended private key, e.g. "xprv9s21ZrQH143K...".
+    /// This is the same as `to_string()`, for call sites that should state that they expose the key.
+    pub fn to_extended_private_key_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Writes the extended private key with its key material redacted, as its version prefix
+    /// and fingerprint, e.g. "xprv…73c5da0a".
+    pub fn fmt_redacted(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let prefix = self.to_string().chars().take(4).collect::<String>();
+        write!(f, "{}…{}", prefix, hex::encode(self.fingerprint()))
+    }
+
     /// Returns the format of the Bitcoin extended private key.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
-}
 
-impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
-    type Err = ExtendedPrivateKeyError;
+    /// Returns the Bitcoin extended private key with the version bytes of the given format,
+    /// e.g. to normalize a zprv to an xprv.
+    pub fn to_format(&self, format: &BitcoinFormat) -> Result<Self, ExtendedPrivateKeyError> {
+        let _ = N::to_extended_private_key_version_bytes(format)?;
+        Ok(Self {
+            format: format.clone(),
+            ..self.clone()
+        })
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s.from_base58()?;
-        if data.len() != 82 {
+    /// Returns the extended private key on the network `M`, for the intentional reuse of a key
+    /// across networks, or an error if the network `M` has no version bytes for its format.
+    pub fn force_network<M: BitcoinNetwork>(&self) -> Result<BitcoinExtendedPrivateKey<M>, ExtendedPrivateKeyError> {
+        let _ = M::to_extended_private_key_version_bytes(&self.format)?;
+        Ok(BitcoinExtendedPrivateKey {
+            format: self.format.clone(),
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_index: self.child_index,
+            chain_code: self.chain_code,
+            private_key: self.private_key.force_network(),
+            public_key: self.public_key.force_network(),
+        })
+    }
+
+    /// Returns the extended private keys of `count` consecutive child indices, beginning at the
+    /// given child index. The parent public key and fingerprint are computed once for the range,
+    /// which makes this much faster than calling `derive` for each child.
+    pub fn derive_range(&self, start: ChildIndex, count: u32) -> Result<Vec<Self>, ExtendedPrivateKeyError> {
+        let public_key = self.serialize_public_key();
+        start
+            .range(count)?
+            .into_iter()
+            .map(|index| self.derive_child(index, &public_key, self.format.clone()))
+            .collect()
+    }
+
+    /// Returns the child extended private key of the given child index, given the
+    /// compressed serialization of the public key of this extended private key.
+    fn derive_child(
+        &self,
+        index: ChildIndex,
+        public_key: &[u8; 33],
+        format: BitcoinFormat,
+    ) -> Result<Self, ExtendedPrivateKeyError> {
+        // The depth is serialized in one byte, so a child of a key at depth 255 cannot be represented
+        let depth = child_depth(self.depth).ok_or(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth))?;
+
+        let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+        match index {
+            // HMAC-SHA512(Key = cpar, Data = serP(point(kpar)) || ser32(i)).
+            ChildIndex::Normal(_) => mac.input(public_key),
+            // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
+            // (Note: The 0x00 pads the private key to make it 33 bytes long.)
+            ChildIndex::Hardened(_) => {
+                mac.input(&[0u8]);
+                mac.input(&self.private_key.to_secp256k1_secret_key().serialize());
+            }
+        }
+        // Append the child index in big-endian format
+        mac.input(&u32::from(index).to_be_bytes());
+        let hmac = mac.result().code();
+
+        let secret_key = Self::derive_secret_key(&hmac[0..32], &self.private_key.to_secp256k1_secret_key(), index)?;
+        let private_key = BitcoinPrivateKey::from_secp256k1_secret_key(&secret_key, true);
+
+        let mut chain_code = [0u8; 32];
+        chain_code[0..32].copy_from_slice(&hmac[32..]);
+
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
+
+        Ok(Self {
+            format,
+            depth,
+            parent_fingerprint,
+            child_index: index,
+            chain_code,
+            public_key: private_key.to_public_key(),
+            private_key,
+        })
+    }
+
+    /// Returns the compressed serialization of the public key, serP(point(k)).
+    fn serialize_public_key(&self) -> [u8; 33] {
+        self.public_key.to_secp256k1_public_key().serialize_compressed()
+    }
+
+    /// Returns the child secret key, parse256(IL) + kpar (mod n), for the given child index.
+    /// If parse256(IL) >= n or the child secret key is zero, the child index is invalid
+    /// and the caller should proceed with the next child index.
+    fn derive_secret_key(
+        tweak: &[u8],
+        parent_secret_key: &SecretKey,
+        index: ChildIndex,
+    ) -> Result<SecretKey, ExtendedPrivateKeyError> {
+        let mut secret_key =
+            SecretKey::parse_slice(tweak).map_err(|_| ExtendedPrivateKeyError::InvalidChildKey(u32::from(index)))?;
+        secret_key
+            .tweak_add_assign(parent_secret_key)
+            .map_err(|_| ExtendedPrivateKeyError::InvalidChildKey(u32::from(index)))?;
+        Ok(secret_key)
+    }
+
+    /// Returns the extended private key of the given 78-byte BIP32 serialization, without the
+    /// Base58Check checksum.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        if data.len() != 78 {
             return Err(ExtendedPrivateKeyError::InvalidByteLength(data.len()));
         }
 
         // Check that the version bytes correspond with the correct network.
-        let _ = N::from_extended_private_key_version_bytes(&data[0..4])?;
+        let _ = check_extended_private_key_version_bytes::<N>(&data[0..4])?;
         let format = BitcoinFormat::from_extended_private_key_version_bytes(&data[0..4])?;
 
         let depth = data[4];
@@ -172,50 +334,163 @@ impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
 
         let child_index = ChildIndex::from(u32::from_be_bytes(<[u8; 4]>::try_from(&data[9..13])?));
 
+        if depth == 0 && (parent_fingerprint != [0u8; 4] || u32::from(child_index) != 0) {
+            return Err(ExtendedPrivateKeyError::InvalidMasterKey);
+        }
+
         let mut chain_code = [0u8; 32];
         chain_code.copy_from_slice(&data[13..45]);
 
-        let private_key = BitcoinPrivateKey::from_secp256k1_secret_key(&SecretKey::parse_slice(&data[46..78])?, true);
-
-        let expected = &data[78..82];
-        let checksum = &checksum(&data[0..78])[0..4];
-        if *expected != *checksum {
-            let expected = expected.to_base58();
-            let found = checksum.to_base58();
-            return Err(ExtendedPrivateKeyError::InvalidChecksum(expected, found));
+        // Check that the private key is prefixed with a 0x00 padding byte.
+        if data[45] != 0 {
+            return Err(ExtendedPrivateKeyError::InvalidPadding(data[45]));
         }
 
+        let private_key = BitcoinPrivateKey::from_secp256k1_secret_key(&SecretKey::parse_slice(&data[46..78])?, true);
+
         Ok(Self {
             format,
             depth,
             parent_fingerprint,
             child_index,
             chain_code,
+            public_key: private_key.to_public_key(),
             private_key,
         })
     }
+
+    /// Returns the 78-byte BIP32 serialization of the extended private key, without the Base58Check
+    /// checksum, or an error if the network has no version bytes for the format of the key.
+    pub fn to_bytes(&self) -> Result<[u8; 78], ExtendedPrivateKeyError> {
+        let mut data = [0u8; 78];
+        data[0..4].copy_from_slice(&N::to_extended_private_key_version_bytes(&self.format)?);
+        data[4] = self.depth;
+        data[5..9].copy_from_slice(&self.parent_fingerprint[..]);
+        data[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
+        data[13..45].copy_from_slice(&self.chain_code[..]);
+        data[45] = 0;
+        data[46..78].copy_from_slice(&self.private_key.to_secp256k1_secret_key().serialize());
+        Ok(data)
+    }
+
+    /// Returns the key identifier, hash160(serP(point(k))), of the Bitcoin extended private key.
+    pub fn key_identifier(&self) -> [u8; 20] {
+        let mut key_identifier = [0u8; 20];
+        key_identifier.copy_from_slice(&hash160(&self.serialize_public_key()));
+        key_identifier
+    }
+
+    /// Returns the fingerprint, the first 32 bits of the key identifier, of the Bitcoin extended private key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&self.key_identifier()[0..4]);
+        fingerprint
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: BitcoinNetwork> Default for BitcoinExtendedPrivateKey<N> {
+    /// Returns a randomly-generated P2PKH master extended private key using the operating system RNG.
+    fn default() -> Self {
+        Self::new_random(&mut rand::rngs::OsRng, &BitcoinFormat::P2PKH).expect("every network supports P2PKH")
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
+    type Err = ExtendedPrivateKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = decode_check(s)?;
+        if data.len() != 78 {
+            return Err(ExtendedPrivateKeyError::InvalidByteLength(data.len() + 4));
+        }
+        Self::from_bytes(&data)
+    }
+}
+
+impl<'a, N: BitcoinNetwork> TryFrom<&'a str> for BitcoinExtendedPrivateKey<N> {
+    type Error = ExtendedPrivateKeyError;
+
+    fn try_from(extended_private_key: &'a str) -> Result<Self, Self::Error> {
+        Self::from_str(extended_private_key)
+    }
+}
+
+impl<'a, N: BitcoinNetwork> TryFrom<&'a [u8]> for BitcoinExtendedPrivateKey<N> {
+    type Error = ExtendedPrivateKeyError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(data)
+    }
+}
+
+impl<N: BitcoinNetwork> TryFrom<[u8; 78]> for BitcoinExtendedPrivateKey<N> {
+    type Error = ExtendedPrivateKeyError;
+
+    fn try_from(data: [u8; 78]) -> Result<Self, Self::Error> {
+        Self::from_bytes(&data)
+    }
 }
 
 impl<N: BitcoinNetwork> Display for BitcoinExtendedPrivateKey<N> {
     /// BIP32 serialization format
     /// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#serialization-format
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let mut result = [0u8; 82];
-        result[0..4].copy_from_slice(match &N::to_extended_private_key_version_bytes(&self.format) {
-            Ok(version) => version,
-            Err(_) => return Err(fmt::Error),
-        });
-        result[4] = self.depth;
-        result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
-        result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
-        result[13..45].copy_from_slice(&self.chain_code[..]);
-        result[45] = 0;
-        result[46..78].copy_from_slice(&self.private_key.to_secp256k1_secret_key().serialize());
-
-        let checksum = &checksum(&result[0..78])[0..4];
-        result[78..82].copy_from_slice(&checksum);
-
-        fmt.write_str(&result.to_base58())
+        match self.to_bytes() {
+            Ok(data) => fmt.write_str(&encode_check(&data)),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Debug for BitcoinExtendedPrivateKey<N> {
+    /// Formats the extended private key in redacted form, so that logging it does not leak the key.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "BitcoinExtendedPrivateKey(")?;
+        self.fmt_redacted(f)?;
+        write!(f, ")")
+    }
+}
+
+impl<N: BitcoinNetwork> Serialize for BitcoinExtendedPrivateKey<N> {
+    /// Serializes the extended private key as its Base58Check string.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, N: BitcoinNetwork> Deserialize<'de> for BitcoinExtendedPrivateKey<N> {
+    /// Deserializes the extended private key from its Base58Check string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<N: BitcoinNetwork> proptest::arbitrary::Arbitrary for BitcoinExtendedPrivateKey<N> {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    /// Returns a strategy over extended private keys of any valid seed, derived by up to 3
+    /// child indices, in each format that the network has version bytes for, falling back to P2PKH.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::{collection::vec, prelude::*};
+
+        let format = prop_oneof![
+            Just(BitcoinFormat::P2PKH),
+            Just(BitcoinFormat::P2SH_P2WPKH),
+            Just(BitcoinFormat::Bech32),
+        ];
+        (vec(any::<u8>(), 16..=64), format, vec(any::<ChildIndex>(), 0..4))
+            .prop_filter_map("invalid extended private key", |(seed, format, path)| {
+                let format = match N::to_extended_private_key_version_bytes(&format) {
+                    Ok(_) => format,
+                    Err(_) => BitcoinFormat::P2PKH,
+                };
+                let path = BitcoinDerivationPath::from_vec(&path).ok()?;
+                Self::new(&seed, &format, &path).ok()
+            })
+            .boxed()
     }
 }
 
@@ -830,47 +1105,1151 @@ mod tests {
         }
     }
 
-    mod test_invalid {
+    mod derive_path {
         use super::*;
 
         type N = Mainnet;
 
-        const INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY: &str = "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fENZ3QzxW";
-        const INVALID_EXTENDED_PRIVATE_KEY_NETWORK: &str = "xprv8s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
-        const INVALID_EXTENDED_PRIVATE_KEY_CHECKSUM: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHj";
-        const VALID_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+        // (path, extended_private_key)
+        const CHAIN: [(&str, &str); 6] = [
+            ("m", "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"),
+            ("m/0'", "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"),
+            ("m/0'/1", "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs"),
+            ("m/0'/1/2'", "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM"),
+            ("m/0'/1/2'/2", "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334"),
+            ("m/0'/1/2'/2/1000000000", "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"),
+        ];
 
         #[test]
-        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidSecretKey\")")]
-        fn from_str_invalid_secret_key() {
-            let _result =
-                BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY).unwrap();
+        fn derive_path() {
+            let master = BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH)
+                .unwrap();
+            CHAIN.iter().for_each(|(path, expected_extended_private_key)| {
+                let extended_private_key = master.derive_path(path).unwrap();
+                assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+                let extended_private_key = master.derive_path(&path.replace("'", "h")).unwrap();
+                assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+            });
         }
 
         #[test]
-        #[should_panic(expected = "InvalidVersionBytes([4, 136, 173, 227])")]
-        fn from_str_invalid_version() {
-            let _result = BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_NETWORK).unwrap();
+        fn fingerprint() {
+            let master =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(
+                "3442193e1bb70916e914552172cd4e2dbc9df811",
+                hex::encode(master.key_identifier())
+            );
+            assert_eq!("3442193e", hex::encode(master.fingerprint()));
+
+            CHAIN.windows(2).for_each(|pair| {
+                let parent = master.derive_path(pair[0].0).unwrap();
+                let child = master.derive_path(pair[1].0).unwrap();
+                assert_eq!(parent.fingerprint(), child.parent_fingerprint);
+            });
         }
 
         #[test]
-        #[should_panic(expected = "InvalidChecksum(\"6vCfku\", \"6vCfkt\")")]
-        fn from_str_invalid_checksum() {
-            let _result = BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_CHECKSUM).unwrap();
+        fn derive_path_invalid() {
+            let master = BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH)
+                .unwrap();
+            ["", "0'/1", "n/0'/1", "m/0x", "m//1", "m/0'/a"].iter().for_each(|path| {
+                assert!(master.derive_path(path).is_err());
+            });
         }
+    }
+
+    mod to_address {
+        use super::*;
+        use crate::mnemonic::BitcoinMnemonic;
+        use crate::wordlist::English;
+        use wagyu_model::mnemonic::{Mnemonic, MnemonicExtended};
+
+        type N = Mainnet;
+
+        const PHRASE: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        // (account path, format, address of m/0/0 under the account)
+        const ACCOUNTS: [(&str, BitcoinFormat, &str); 3] = [
+            (
+                "m/44'/0'/0'",
+                BitcoinFormat::P2PKH,
+                "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            ),
+            (
+                "m/49'/0'/0'",
+                BitcoinFormat::P2SH_P2WPKH,
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            ),
+            (
+                "m/84'/0'/0'",
+                BitcoinFormat::Bech32,
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+        ];
 
         #[test]
-        #[should_panic(expected = "InvalidByteLength(81)")]
-        fn from_str_short() {
-            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&VALID_EXTENDED_PRIVATE_KEY[1..]).unwrap();
+        fn to_address() {
+            let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+            let master = mnemonic.to_extended_private_key(None).unwrap();
+            ACCOUNTS.iter().for_each(|(account, format, expected_address)| {
+                let extended_private_key = master.derive_path(&format!("{}/0/0", account)).unwrap();
+                assert_eq!(
+                    *expected_address,
+                    extended_private_key.to_address(format).unwrap().to_string()
+                );
+                assert_eq!(
+                    *expected_address,
+                    extended_private_key
+                        .to_private_key()
+                        .to_address(format)
+                        .unwrap()
+                        .to_string()
+                );
+
+                let extended_public_key = master
+                    .derive_path(account)
+                    .unwrap()
+                    .to_extended_public_key()
+                    .derive_path("m/0/0")
+                    .unwrap();
+                assert_eq!(
+                    extended_private_key.to_public_key(),
+                    extended_public_key.to_public_key()
+                );
+                assert_eq!(
+                    *expected_address,
+                    extended_public_key.to_address(format).unwrap().to_string()
+                );
+            });
         }
 
         #[test]
-        #[should_panic(expected = "InvalidByteLength(83)")]
-        fn from_str_long() {
-            let mut string = String::from(VALID_EXTENDED_PRIVATE_KEY);
-            string.push('a');
-            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&string).unwrap();
+        fn to_regtest_address() {
+            // The first receiving address of the BIP84 test vector, with the regtest
+            // human-readable part.
+            let mnemonic = BitcoinMnemonic::<Regtest, English>::from_phrase(PHRASE).unwrap();
+            let master = mnemonic.to_extended_private_key(None).unwrap();
+            let extended_private_key = master.derive_path("m/84'/1'/0'/0/0").unwrap();
+            assert_eq!(
+                "bcrt1q6rz28mcfaxtmd6v789l9rrlrusdprr9pz3cppk",
+                extended_private_key
+                    .to_address(&BitcoinFormat::Bech32)
+                    .unwrap()
+                    .to_string()
+            );
+
+            // Regtest shares the extended key version bytes of testnet.
+            let testnet = BitcoinMnemonic::<Testnet, English>::from_phrase(PHRASE).unwrap();
+            let testnet = testnet.to_extended_private_key(None).unwrap();
+            assert_eq!(testnet.to_string(), master.to_string());
+            assert_eq!(
+                master,
+                BitcoinExtendedPrivateKey::<Regtest>::from_str(&testnet.to_string()).unwrap()
+            );
+        }
+
+        #[test]
+        fn to_private_key() {
+            let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+            let master = mnemonic.to_extended_private_key(None).unwrap();
+            let extended_private_key = master.derive_path("m/84'/0'/0'/0/0").unwrap();
+            assert_eq!(
+                "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d",
+                extended_private_key.to_private_key().to_string()
+            );
+            assert_eq!(
+                "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+                extended_private_key.to_public_key().to_string()
+            );
+        }
+    }
+
+    mod equality {
+        use super::*;
+        use std::collections::HashMap;
+
+        type N = Mainnet;
+
+        const EXTENDED_PRIVATE_KEYS: [&str; 3] = [
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+        ];
+
+        #[test]
+        fn eq() {
+            EXTENDED_PRIVATE_KEYS.iter().for_each(|expected_extended_private_key| {
+                let extended_private_key =
+                    BitcoinExtendedPrivateKey::<N>::from_str(expected_extended_private_key).unwrap();
+                let round_trip = BitcoinExtendedPrivateKey::<N>::from_str(&extended_private_key.to_string()).unwrap();
+                assert_eq!(extended_private_key, round_trip);
+                assert_eq!(extended_private_key.clone(), round_trip);
+                assert_eq!(
+                    extended_private_key.to_extended_public_key(),
+                    round_trip.to_extended_public_key()
+                );
+            });
+
+            let first = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEYS[0]).unwrap();
+            let second = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEYS[1]).unwrap();
+            assert_ne!(first, second);
+            assert_ne!(first.to_extended_public_key(), second.to_extended_public_key());
+        }
+
+        #[test]
+        fn hash() {
+            let mut extended_private_keys = HashMap::new();
+            let mut extended_public_keys = HashMap::new();
+            EXTENDED_PRIVATE_KEYS
+                .iter()
+                .enumerate()
+                .for_each(|(account, extended_private_key)| {
+                    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                    extended_public_keys.insert(extended_private_key.to_extended_public_key(), account);
+                    extended_private_keys.insert(extended_private_key, account);
+                });
+
+            EXTENDED_PRIVATE_KEYS
+                .iter()
+                .enumerate()
+                .for_each(|(account, extended_private_key)| {
+                    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                    assert_eq!(
+                        Some(&account),
+                        extended_public_keys.get(&extended_private_key.to_extended_public_key())
+                    );
+                    assert_eq!(Some(&account), extended_private_keys.get(&extended_private_key));
+                });
+        }
+    }
+
+    mod serialize {
+        use super::*;
+
+        const MAINNET: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        const TESTNET: &str = "tprv8ZgxMBicQKsPeDgjzdC36fs6bMjGApWDNLR9erAXMs5skhMv36j9MV5ecvfavji5khqjWaWSFhN3YcCUUdiKH6isR4Pwy3U5y5egddBr16m";
+
+        fn test_serialize<N: BitcoinNetwork>(expected_extended_private_key: &str) {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(expected_extended_private_key).unwrap();
+            let json = serde_json::to_string(&extended_private_key).unwrap();
+            assert_eq!(format!("\"{}\"", expected_extended_private_key), json);
+            assert_eq!(
+                extended_private_key,
+                serde_json::from_str::<BitcoinExtendedPrivateKey<N>>(&json).unwrap()
+            );
+
+            let extended_public_key = extended_private_key.to_extended_public_key();
+            let json = serde_json::to_string(&extended_public_key).unwrap();
+            assert_eq!(format!("\"{}\"", extended_public_key), json);
+            assert_eq!(
+                extended_public_key,
+                serde_json::from_str::<BitcoinExtendedPublicKey<N>>(&json).unwrap()
+            );
+        }
+
+        #[test]
+        fn serialize_mainnet() {
+            test_serialize::<Mainnet>(MAINNET);
+        }
+
+        #[test]
+        fn serialize_testnet() {
+            test_serialize::<Testnet>(TESTNET);
+        }
+
+        #[test]
+        fn deserialize_invalid() {
+            let mut tampered = String::from(&MAINNET[..MAINNET.len() - 1]);
+            tampered.push('j');
+            let json = format!("\"{}\"", tampered);
+            assert!(serde_json::from_str::<BitcoinExtendedPrivateKey<Mainnet>>(&json).is_err());
+
+            let json = format!("\"{}\"", TESTNET);
+            assert!(serde_json::from_str::<BitcoinExtendedPrivateKey<Mainnet>>(&json).is_err());
+            assert!(serde_json::from_str::<BitcoinExtendedPrivateKey<Mainnet>>("0").is_err());
+        }
+    }
+
+    mod from_mnemonic {
+        use super::*;
+        use crate::mnemonic::BitcoinMnemonic;
+        use crate::wordlist::English;
+        use wagyu_model::mnemonic::Mnemonic;
+
+        type N = Mainnet;
+        type W = English;
+
+        const PHRASE: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        #[test]
+        fn from_mnemonic() {
+            let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(PHRASE).unwrap();
+            let extended_private_key =
+                BitcoinExtendedPrivateKey::from_mnemonic(&mnemonic, None, &BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(
+                "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu",
+                extended_private_key.to_string()
+            );
+        }
+
+        #[test]
+        fn from_mnemonic_path() {
+            let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(PHRASE).unwrap();
+
+            let path = BitcoinDerivationPath::from_str("m/44'/0'/0'").unwrap();
+            let extended_private_key = BitcoinExtendedPrivateKey::from_mnemonic_path(&mnemonic, None, &path).unwrap();
+            assert_eq!(
+                "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb",
+                extended_private_key.to_string()
+            );
+
+            let path = BitcoinDerivationPath::bip84(0, 0, 0).unwrap();
+            let extended_private_key = BitcoinExtendedPrivateKey::from_mnemonic_path(&mnemonic, None, &path).unwrap();
+            assert_eq!(
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                extended_private_key
+                    .to_address(&BitcoinFormat::Bech32)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+    }
+
+    mod slip132 {
+        use super::*;
+        use crate::mnemonic::BitcoinMnemonic;
+        use crate::wordlist::English;
+        use wagyu_model::mnemonic::{Mnemonic, MnemonicExtended};
+
+        type N = Mainnet;
+
+        const PHRASE: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        // (path, format, extended_private_key, extended_public_key)
+        const KEYPAIRS: [(&str, BitcoinFormat, &str, &str); 3] = [
+            (
+                "m/44'/0'/0'",
+                BitcoinFormat::P2PKH,
+                "xprv9xpXFhFpqdQK3TmytPBqXtGSwS3DLjojFhTGht8gwAAii8py5X6pxeBnQ6ehJiyJ6nDjWGJfZ95WxByFXVkDxHXrqu53WCRGypk2ttuqncb",
+                "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj",
+            ),
+            (
+                "m/49'/0'/0'",
+                BitcoinFormat::P2SH_P2WPKH,
+                "yprvAHwhK6RbpuS3dgCYHM5jc2ZvEKd7Bi61u9FVhYMpgMSuZS613T1xxQeKTffhrHY79hZ5PsskBjcc6C2V7DrnsMsNaGDaWev3GLRQRgV7hxF",
+                "ypub6Ww3ibxVfGzLrAH1PNcjyAWenMTbbAosGNB6VvmSEgytSER9azLDWCxoJwW7Ke7icmizBMXrzBx9979FfaHxHcrArf3zbeJJJUZPf663zsP",
+            ),
+            (
+                "m/84'/0'/0'",
+                BitcoinFormat::Bech32,
+                "zprvAdG4iTXWBoARxkkzNpNh8r6Qag3irQB8PzEMkAFeTRXxHpbF9z4QgEvBRmfvqWvGp42t42nvgGpNgYSJA9iefm1yYNZKEm7z6qUWCroSQnE",
+                "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+            ),
+        ];
+
+        #[test]
+        fn derive() {
+            let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+            let master = mnemonic.to_extended_private_key(None).unwrap();
+            KEYPAIRS.iter().for_each(
+                |(path, format, expected_extended_private_key, expected_extended_public_key)| {
+                    let extended_private_key = master.to_format(format).unwrap().derive_path(path).unwrap();
+                    assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+                    assert_eq!(
+                        *expected_extended_public_key,
+                        extended_private_key.to_extended_public_key().to_string()
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(
+                |(_, format, expected_extended_private_key, expected_extended_public_key)| {
+                    let extended_private_key =
+                        BitcoinExtendedPrivateKey::<N>::from_str(expected_extended_private_key).unwrap();
+                    assert_eq!(*format, extended_private_key.format());
+                    assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+
+                    let extended_public_key =
+                        BitcoinExtendedPublicKey::<N>::from_str(expected_extended_public_key).unwrap();
+                    assert_eq!(*format, extended_public_key.format());
+                    assert_eq!(*expected_extended_public_key, extended_public_key.to_string());
+                },
+            );
+        }
+
+        #[test]
+        fn to_format() {
+            let (_, _, zprv, zpub) = KEYPAIRS[2];
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(zprv).unwrap();
+            let xprv = extended_private_key.to_format(&BitcoinFormat::P2PKH).unwrap();
+            assert!(xprv.to_string().starts_with("xprv"));
+            assert_eq!(zprv, xprv.to_format(&BitcoinFormat::Bech32).unwrap().to_string());
+
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(zpub).unwrap();
+            let xpub = extended_public_key.to_format(&BitcoinFormat::P2PKH).unwrap();
+            assert!(xpub.to_string().starts_with("xpub"));
+            assert_eq!(xprv.to_extended_public_key(), xpub);
+
+            assert!(extended_private_key.to_format(&BitcoinFormat::P2WSH).is_err());
+            assert!(extended_public_key.to_format(&BitcoinFormat::P2WSH).is_err());
+        }
+
+        #[test]
+        fn derive_format() {
+            let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+            let master = mnemonic.to_extended_private_key(None).unwrap();
+            let bip49 = master.derive(&BitcoinDerivationPath::bip49(0, 0, 0).unwrap()).unwrap();
+            assert_eq!(BitcoinFormat::P2SH_P2WPKH, bip49.format());
+            let bip84 = master.derive(&BitcoinDerivationPath::bip84(0, 0, 0).unwrap()).unwrap();
+            assert_eq!(BitcoinFormat::Bech32, bip84.format());
+            assert_eq!(
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                bip84.to_address(&bip84.format()).unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn testnet() {
+            let mnemonic = BitcoinMnemonic::<Testnet, English>::from_phrase(PHRASE).unwrap();
+            let master = mnemonic.to_extended_private_key(None).unwrap();
+            [
+                (BitcoinFormat::P2PKH, "tprv", "tpub"),
+                (BitcoinFormat::P2SH_P2WPKH, "uprv", "upub"),
+                (BitcoinFormat::Bech32, "vprv", "vpub"),
+            ]
+            .iter()
+            .for_each(|(format, private_prefix, public_prefix)| {
+                let extended_private_key = master.to_format(format).unwrap();
+                let extended_public_key = extended_private_key.to_extended_public_key();
+                assert!(extended_private_key.to_string().starts_with(private_prefix));
+                assert!(extended_public_key.to_string().starts_with(public_prefix));
+                assert_eq!(
+                    extended_private_key,
+                    BitcoinExtendedPrivateKey::<Testnet>::from_str(&extended_private_key.to_string()).unwrap()
+                );
+                assert_eq!(
+                    extended_public_key,
+                    BitcoinExtendedPublicKey::<Testnet>::from_str(&extended_public_key.to_string()).unwrap()
+                );
+            });
+        }
+    }
+
+    mod bip32_leading_zeros {
+        use super::*;
+
+        type N = Mainnet;
+
+        // (seed, path, extended private key, extended public key) of BIP32 test vectors 3 and 4,
+        // whose keys are prone to losing leading zero bytes
+        const VECTORS: [(&str, &str, &str, &str); 5] = [
+            (
+                "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+                "m",
+                "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+                "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+            ),
+            (
+                "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be",
+                "m/0'",
+                "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+                "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+            ),
+            (
+                "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+                "m",
+                "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+                "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+            ),
+            (
+                "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+                "m/0'",
+                "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+                "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+            ),
+            (
+                "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678",
+                "m/0'/1'",
+                "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+                "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+            ),
+        ];
+
+        #[test]
+        fn new() {
+            VECTORS.iter().for_each(
+                |(seed, path, expected_extended_private_key, expected_extended_public_key)| {
+                    let path = BitcoinDerivationPath::<N>::from_str(path).unwrap();
+                    let extended_private_key =
+                        BitcoinExtendedPrivateKey::<N>::new(&hex::decode(seed).unwrap(), &BitcoinFormat::P2PKH, &path)
+                            .unwrap();
+                    assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+                    assert_eq!(
+                        *expected_extended_public_key,
+                        extended_private_key.to_extended_public_key().to_string()
+                    );
+                },
+            );
+        }
+
+        #[test]
+        fn derive() {
+            VECTORS
+                .windows(2)
+                .filter(|pair| pair[0].0 == pair[1].0)
+                .for_each(|pair| {
+                    let parent = BitcoinExtendedPrivateKey::<N>::from_str(pair[0].2).unwrap();
+                    let index = BitcoinDerivationPath::<N>::from_str(pair[1].1)
+                        .unwrap()
+                        .to_vec()
+                        .unwrap();
+                    let child = parent.derive_path(&format!("m/{}", index.last().unwrap())).unwrap();
+                    assert_eq!(pair[1].2, child.to_string());
+                    assert_eq!(parent.fingerprint(), child.parent_fingerprint);
+                });
+        }
+
+        #[test]
+        fn from_str() {
+            VECTORS.iter().for_each(|(_, _, expected_extended_private_key, _)| {
+                let extended_private_key =
+                    BitcoinExtendedPrivateKey::<N>::from_str(expected_extended_private_key).unwrap();
+                assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+                assert_eq!(
+                    extended_private_key,
+                    BitcoinExtendedPrivateKey::<N>::from_bytes(&extended_private_key.to_bytes().unwrap()).unwrap()
+                );
+            });
+        }
+
+        #[test]
+        fn leading_zero_secret_key() {
+            // The master secret key of test vector 3 begins with a zero byte.
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(VECTORS[0].2).unwrap();
+            let secret_key = extended_private_key.private_key.to_secp256k1_secret_key().serialize();
+            assert_eq!(0, secret_key[0]);
+            assert_eq!(secret_key, extended_private_key.to_bytes().unwrap()[46..78]);
+            assert_eq!(64, extended_private_key.private_key.to_hex().len());
+            assert_eq!(
+                extended_private_key.private_key,
+                BitcoinPrivateKey::<N>::from_str(&extended_private_key.to_wif()).unwrap()
+            );
+        }
+    }
+
+    mod p2pkh_testnet {
+        use super::*;
+
+        type N = Testnet;
+
+        const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+        const EXPECTED_MASTER: &str = "tprv8ZgxMBicQKsPeDgjzdC36fs6bMjGApWDNLR9erAXMs5skhMv36j9MV5ecvfavji5khqjWaWSFhN3YcCUUdiKH6isR4Pwy3U5y5egddBr16m";
+
+        #[test]
+        fn new_master() {
+            let extended_private_key =
+                BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(SEED).unwrap(), &BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(EXPECTED_MASTER, extended_private_key.to_string());
+            assert_eq!(
+                extended_private_key,
+                BitcoinExtendedPrivateKey::<N>::from_str(EXPECTED_MASTER).unwrap()
+            );
+        }
+
+        #[test]
+        fn derive() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXPECTED_MASTER).unwrap();
+            let child = extended_private_key.derive_path("m/0'/1/2'/2/1000000000").unwrap();
+            let child_string = child.to_string();
+            assert!(child_string.starts_with("tprv"));
+            assert!(child.to_private_key().to_string().starts_with('c'));
+            assert_eq!(child, BitcoinExtendedPrivateKey::<N>::from_str(&child_string).unwrap());
+            assert!(BitcoinExtendedPrivateKey::<Mainnet>::from_str(&child_string).is_err());
+        }
+    }
+
+    mod invalid_child_key {
+        use super::*;
+
+        type N = Mainnet;
+
+        // The order of the secp256k1 curve
+        const CURVE_ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+        fn parent_secret_key() -> SecretKey {
+            let mut secret_key = [0u8; 32];
+            secret_key[31] = 1;
+            SecretKey::parse(&secret_key).unwrap()
+        }
+
+        #[test]
+        fn derive_secret_key() {
+            let mut tweak = [0u8; 32];
+            tweak[31] = 2;
+            let secret_key =
+                BitcoinExtendedPrivateKey::<N>::derive_secret_key(&tweak, &parent_secret_key(), ChildIndex::Normal(0))
+                    .unwrap();
+
+            let mut expected = [0u8; 32];
+            expected[31] = 3;
+            assert_eq!(expected, secret_key.serialize());
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidChildKey(0)")]
+        fn derive_secret_key_tweak_overflow() {
+            let tweak = hex::decode(CURVE_ORDER).unwrap();
+            let _result =
+                BitcoinExtendedPrivateKey::<N>::derive_secret_key(&tweak, &parent_secret_key(), ChildIndex::Normal(0))
+                    .unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidChildKey(2147483653)")]
+        fn derive_secret_key_zero() {
+            // parse256(IL) + kpar = (n - 1) + 1 = 0 (mod n)
+            let mut tweak = hex::decode(CURVE_ORDER).unwrap();
+            tweak[31] -= 1;
+            let _result = BitcoinExtendedPrivateKey::<N>::derive_secret_key(
+                &tweak,
+                &parent_secret_key(),
+                ChildIndex::Hardened(5),
+            )
+            .unwrap();
+        }
+    }
+
+    mod new_random {
+        use super::*;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        type N = Mainnet;
+
+        #[test]
+        fn new_random() {
+            let rng = &mut StdRng::seed_from_u64(1231275789u64);
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::new_random(rng, &BitcoinFormat::Bech32).unwrap();
+            let rng = &mut StdRng::seed_from_u64(1231275789u64);
+            let expected_extended_private_key =
+                BitcoinExtendedPrivateKey::<N>::new_random(rng, &BitcoinFormat::Bech32).unwrap();
+            assert_eq!(expected_extended_private_key, extended_private_key);
+            assert_eq!(BitcoinFormat::Bech32, extended_private_key.format());
+            assert!(extended_private_key.to_string().starts_with("zprv"));
+        }
+
+        #[test]
+        fn default() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::default();
+            assert_ne!(BitcoinExtendedPrivateKey::<N>::default(), extended_private_key);
+            assert!(extended_private_key.to_string().starts_with("xprv"));
+        }
+    }
+
+    mod to_wif {
+        use super::*;
+
+        #[test]
+        fn mainnet() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<Mainnet>::from_str("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+            let wif = extended_private_key.to_wif();
+            assert!(wif.starts_with('K') || wif.starts_with('L'));
+            assert_eq!(
+                extended_private_key.to_private_key(),
+                BitcoinPrivateKey::<Mainnet>::from_str(&wif).unwrap()
+            );
+        }
+
+        #[test]
+        fn testnet() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<Testnet>::from_str("tprv8ZgxMBicQKsPeDgjzdC36fs6bMjGApWDNLR9erAXMs5skhMv36j9MV5ecvfavji5khqjWaWSFhN3YcCUUdiKH6isR4Pwy3U5y5egddBr16m").unwrap();
+            let wif = extended_private_key.to_wif();
+            assert!(wif.starts_with('c'));
+            assert_eq!(
+                extended_private_key.to_private_key(),
+                BitcoinPrivateKey::<Testnet>::from_str(&wif).unwrap()
+            );
+            assert!(BitcoinPrivateKey::<Mainnet>::from_str(&wif).is_err());
+
+            // Derived keys keep the network of the parent.
+            assert!(extended_private_key
+                .derive_path("m/44'/1'/0'/0/0")
+                .unwrap()
+                .to_wif()
+                .starts_with('c'));
+        }
+    }
+
+    mod derive_range {
+        use super::*;
+
+        type N = Mainnet;
+
+        const EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        fn test_derive_range(start: ChildIndex, count: u32) {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let children = extended_private_key.derive_range(start, count).unwrap();
+            assert_eq!(count as usize, children.len());
+
+            children.iter().zip(0..count).for_each(|(child, offset)| {
+                let index = ChildIndex::from(u32::from(start) + offset);
+                let path = BitcoinDerivationPath::from_str(&format!("m/{}", index)).unwrap();
+                assert_eq!(extended_private_key.derive(&path).unwrap(), *child);
+            });
+        }
+
+        #[test]
+        fn derive_range_normal() {
+            test_derive_range(ChildIndex::Normal(0), 20);
+            test_derive_range(ChildIndex::Normal(2147483640), 8);
+        }
+
+        #[test]
+        fn derive_range_hardened() {
+            test_derive_range(ChildIndex::Hardened(0), 20);
+        }
+
+        #[test]
+        fn derive_range_empty() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            assert!(extended_private_key
+                .derive_range(ChildIndex::Normal(0), 0)
+                .unwrap()
+                .is_empty());
+        }
+
+        #[test]
+        fn derive_range_overflow() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            assert!(extended_private_key
+                .derive_range(ChildIndex::Normal(2147483640), 9)
+                .is_err());
+        }
+    }
+
+    mod altcoins {
+        use super::*;
+
+        /// The seed of BIP32 test vector 1
+        const SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+        // (extended_private_key, extended_public_key, private_key, p2pkh, p2sh_p2wpkh, bech32)
+        fn test_master<N: BitcoinNetwork>(expected: (&str, &str, &str, &str, Option<&str>, Option<&str>)) {
+            let (
+                expected_extended_private_key,
+                expected_extended_public_key,
+                expected_private_key,
+                p2pkh,
+                p2sh_p2wpkh,
+                bech32,
+            ) = expected;
+
+            let seed = hex::decode(SEED).unwrap();
+            let extended_private_key =
+                BitcoinExtendedPrivateKey::<N>::new_master(&seed, &BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(expected_extended_private_key, extended_private_key.to_string());
+            assert_eq!(
+                expected_extended_public_key,
+                extended_private_key.to_extended_public_key().to_string()
+            );
+            assert_eq!(expected_private_key, extended_private_key.to_private_key().to_string());
+
+            let parsed = BitcoinExtendedPrivateKey::<N>::from_str(expected_extended_private_key).unwrap();
+            assert_eq!(extended_private_key, parsed);
+            let parsed = BitcoinExtendedPublicKey::<N>::from_str(expected_extended_public_key).unwrap();
+            assert_eq!(extended_private_key.to_extended_public_key(), parsed);
+            let parsed = BitcoinPrivateKey::<N>::from_str(expected_private_key).unwrap();
+            assert_eq!(extended_private_key.to_private_key(), parsed);
+
+            [
+                (BitcoinFormat::P2PKH, Some(p2pkh)),
+                (BitcoinFormat::P2SH_P2WPKH, p2sh_p2wpkh),
+                (BitcoinFormat::Bech32, bech32),
+            ]
+            .iter()
+            .for_each(|(format, expected_address)| match expected_address {
+                Some(expected_address) => {
+                    let address = extended_private_key.to_address(format).unwrap();
+                    assert_eq!(*expected_address, address.to_string());
+                    assert_eq!(address, BitcoinAddress::<N>::from_str(expected_address).unwrap());
+                    assert_eq!(*format, address.format());
+                }
+                None => assert!(extended_private_key.to_address(format).is_err()),
+            });
+        }
+
+        #[test]
+        fn litecoin() {
+            test_master::<Litecoin>((
+                "Ltpv71G8qDifUiNetP6nmxPA5STrUVmv2J9YSmXajv8VsYBUyuPhvN9xCaQrfX2wo5xxJNtEazYCFRUu5FmokYMM79pcqz8pcdo4rNXAFPgyB4k",
+                "Ltub2SSUS19CirucWFod2ZsYA2J4v4U76YiCXHdcQttnoiy5aGanFHCPDBX7utfG6f95u1cUbZJNafmvzNCzZZJTw1EmyFoL8u1gJbGM8ipu491",
+                "TAroS5Knm8GZcnpPycBgzjwwDLWMyQjDrcuGPPoArgrbW7Ln22qp",
+                "LPzGaoLUtXFkmNo3u1chDxGxDnSaBQTTxm",
+                Some("MW2q8mHT4tTb5KqKcbBrXSssnVLGt6pRK4"),
+                Some("ltc1qx3ppj0smkuy3d6g525sh9n2w9k7fm7q3zd48nc"),
+            ));
+        }
+
+        #[test]
+        fn litecoin_testnet() {
+            test_master::<LitecoinTestnet>((
+                "ttpv96BtqegdxXceQtHEfoRYhynZxUknQHQSYHXTJR8A9ozEjRXPvZvcTu9u1FnaE1KVvPdtxjhzaqaF96SzVC6VYKVA5tuPJc4UrigoPScATTa",
+                "ttub4XNESS7BCg9c2kz4vQuvnZcnQ3SyUXy6codUyPtT5zmqKniUFUy3UWGAFdQtXaVdX2N8yJUAv5sH4CtBJD3cNAuKDAZtpsH6JwRzGvXymhZ",
+                "cVPXTF2TnozE1PenpP3x9huctiATZmp27T9Ue1d8nqLSExoPwfN5",
+                "mkHGce7dctSxHgaWSSbmmrRWsZfzz7MxMk",
+                Some("Qijf1dfkkLAbcnx1owrQQT4ApXPpXfMSiF"),
+                Some("tltc1qx3ppj0smkuy3d6g525sh9n2w9k7fm7q34lkwqj"),
+            ));
+        }
+
+        #[test]
+        fn dogecoin() {
+            test_master::<Dogecoin>((
+                "dgpv51eADS3spNJh9Gjth94XcPwAczvQaDJs9rqx11kvxKs6r3Ek8AgERHhjLs6mzXQFHRzQqGwqdeoDkZmr8jQMBfi43b7sT3sx3cCSk5fGeUR",
+                "dgub8kXBZ7ymNWy2S8Q3jNgVjFUm5ZJ3QLLaSTdAA89ukSv7Q6MSXwE14b7Nv6eDpE9JJXinTKc8LeLVu19uDPrm5uJuhpKNzV2kAgncwo6bNpP",
+                "QWRT9AqbcMn65Uaa2F5cfcFAjWtcwsr8vfgvKDZU25HnS9gkJG7W",
+                "D9uQrqyJ7Guz3aHVTTcxVhNnthobME3o4w",
+                Some("AEZwZiwPBqV4BBvtvqrvwwFrAN7rvtbedK"),
+                None,
+            ));
+        }
+
+        #[test]
+        fn mismatched_networks() {
+            let litecoin = "Ltpv71G8qDifUiNetP6nmxPA5STrUVmv2J9YSmXajv8VsYBUyuPhvN9xCaQrfX2wo5xxJNtEazYCFRUu5FmokYMM79pcqz8pcdo4rNXAFPgyB4k";
+            assert!(BitcoinExtendedPrivateKey::<Mainnet>::from_str(litecoin).is_err());
+            assert!(BitcoinExtendedPrivateKey::<Dogecoin>::from_str(litecoin).is_err());
+            assert!(BitcoinAddress::<Mainnet>::from_str("LPzGaoLUtXFkmNo3u1chDxGxDnSaBQTTxm").is_err());
+            assert!(BitcoinAddress::<Litecoin>::from_str("15mKKb2eos1hWa6tisdPwwDC1a5J1y9nma").is_err());
+            assert!(BitcoinAddress::<Litecoin>::from_str("bc1qx3ppj0smkuy3d6g525sh9n2w9k7fm7q3x30rtg").is_err());
+            assert!(
+                BitcoinPrivateKey::<Litecoin>::from_str("L52XzL2cMkHxqxBXRyEpnPQZGUs3uKiL3R11XbAdHigRzDozKZeW")
+                    .is_err()
+            );
+        }
+    }
+
+    mod generic {
+        use super::*;
+
+        type N = Mainnet;
+
+        /// The BIP39 seed of "abandon abandon ... about" with an empty passphrase
+        const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+        fn first_address<K: ExtendedPrivateKey>(
+            seed: &[u8],
+            format: &K::Format,
+            path: &K::DerivationPath,
+        ) -> K::Address {
+            K::new(seed, format, path).unwrap().to_address(format).unwrap()
+        }
+
+        #[test]
+        fn test_first_address() {
+            let seed = hex::decode(SEED).unwrap();
+            let path = BitcoinDerivationPath::from_str("m/44'/0'/0'/0/0").unwrap();
+            let address = first_address::<BitcoinExtendedPrivateKey<N>>(&seed, &BitcoinFormat::P2PKH, &path);
+            assert_eq!("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", address.to_string());
+
+            let path = BitcoinDerivationPath::bip84(0, 0, 0).unwrap();
+            let address = first_address::<BitcoinExtendedPrivateKey<N>>(&seed, &BitcoinFormat::Bech32, &path);
+            assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", address.to_string());
+        }
+    }
+
+    mod test_invalid {
+        use super::*;
+
+        type N = Mainnet;
+
+        #[test]
+        fn new_master_invalid_seed_length() {
+            [0, 15, 65].iter().for_each(|length| {
+                match BitcoinExtendedPrivateKey::<N>::new_master(&vec![0u8; *length], &BitcoinFormat::P2PKH) {
+                    Err(ExtendedPrivateKeyError::InvalidSeedLength(found)) => assert_eq!(*length, found),
+                    _ => panic!("expected an invalid seed length for {} bytes", length),
+                }
+            });
+            [16, 64].iter().for_each(|length| {
+                assert!(BitcoinExtendedPrivateKey::<N>::new_master(&vec![0u8; *length], &BitcoinFormat::P2PKH).is_ok());
+            });
+        }
+
+        const INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY: &str = "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fENZ3QzxW";
+        const INVALID_EXTENDED_PRIVATE_KEY_NETWORK: &str = "xprv8s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxvKigV";
+        const INVALID_EXTENDED_PRIVATE_KEY_CHECKSUM: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHj";
+        const VALID_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        #[test]
+        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidSecretKey\")")]
+        fn from_str_invalid_secret_key() {
+            let _result =
+                BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidVersionBytes([4, 136, 173, 227])")]
+        fn from_str_invalid_version() {
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_NETWORK).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidChecksum(\"6vCfku\", \"6vCfkt\")")]
+        fn from_str_invalid_checksum() {
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(INVALID_EXTENDED_PRIVATE_KEY_CHECKSUM).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "Base58Error(InvalidCharacter('0', 6))")]
+        fn from_str_invalid_base58_character() {
+            let string = VALID_EXTENDED_PRIVATE_KEY.replacen("2", "0", 1);
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        fn from_str_invalid_input() {
+            let truncated = &VALID_EXTENDED_PRIVATE_KEY[..VALID_EXTENDED_PRIVATE_KEY.len() / 2];
+            let corrupted = VALID_EXTENDED_PRIVATE_KEY.replace("K", "l");
+            ["", " ", "xprv", truncated, &corrupted].iter().for_each(|string| {
+                assert!(BitcoinExtendedPrivateKey::<N>::from_str(string).is_err());
+            });
+        }
+
+        // Returns the extended private key with the given byte replaced and the checksum recomputed.
+        fn replace_byte(extended_private_key: &str, index: usize, byte: u8) -> String {
+            let mut data = decode_check(extended_private_key).unwrap();
+            data[index] = byte;
+            encode_check(&data)
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidPadding(2)")]
+        fn from_str_invalid_padding() {
+            let string = replace_byte(VALID_EXTENDED_PRIVATE_KEY, 45, 2);
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidMasterKey")]
+        fn from_str_invalid_master_parent_fingerprint() {
+            let string = replace_byte(VALID_EXTENDED_PRIVATE_KEY, 5, 1);
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidMasterKey")]
+        fn from_str_invalid_master_child_index() {
+            let string = replace_byte(VALID_EXTENDED_PRIVATE_KEY, 12, 1);
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidByteLength(81)")]
+        fn from_str_short() {
+            let data = decode_check(VALID_EXTENDED_PRIVATE_KEY).unwrap();
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&encode_check(&data[1..])).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidByteLength(83)")]
+        fn from_str_long() {
+            let mut data = decode_check(VALID_EXTENDED_PRIVATE_KEY).unwrap();
+            data.push(0);
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str(&encode_check(&data)).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "ExpectedMasterKey(1)")]
+        fn derive_with_origin_non_master() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(VALID_EXTENDED_PRIVATE_KEY)
+                .unwrap()
+                .derive_path("m/0'")
+                .unwrap();
+            let path = BitcoinDerivationPath::<N>::from_str("m/0").unwrap();
+            let _result = extended_private_key.derive_with_origin(&path).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidLength(3)")]
+        fn from_str_missing_checksum() {
+            let _result = BitcoinExtendedPrivateKey::<N>::from_str("Ldp").unwrap();
+        }
+    }
+
+    mod to_bytes {
+        use super::*;
+
+        type N = Mainnet;
+
+        // (extended private key, hex dump of its BIP32 serialization) of BIP32 test vector 1, for m and m/0'
+        const KEYS: [(&str, &str); 2] = [
+            (
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+                "0488ade4000000000000000000873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d50800e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35",
+            ),
+            (
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+                "0488ade4013442193e8000000047fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae623614100edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+        ];
+
+        #[test]
+        fn to_bytes() {
+            KEYS.iter().for_each(|(extended_private_key, bytes)| {
+                let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                assert_eq!(*bytes, hex::encode(&extended_private_key.to_bytes().unwrap()[..]));
+            });
+        }
+
+        #[test]
+        fn from_bytes() {
+            KEYS.iter().for_each(|(extended_private_key, bytes)| {
+                let from_bytes = BitcoinExtendedPrivateKey::<N>::from_bytes(&hex::decode(bytes).unwrap()).unwrap();
+                assert_eq!(*extended_private_key, from_bytes.to_string());
+            });
+        }
+
+        #[test]
+        fn from_bytes_invalid() {
+            let data = hex::decode(KEYS[0].1).unwrap();
+            // The serialization is the Base58Check payload, without its checksum.
+            assert_eq!(decode_check(KEYS[0].0).unwrap(), data);
+            assert!(BitcoinExtendedPrivateKey::<N>::from_bytes(&data[..77]).is_err());
+            assert!(BitcoinExtendedPrivateKey::<N>::from_bytes(&[&data[..], &[0u8; 4][..]].concat()).is_err());
+            assert!(BitcoinExtendedPrivateKey::<Testnet>::from_bytes(&data).is_err());
+        }
+
+        #[test]
+        fn try_from() {
+            KEYS.iter().for_each(|(extended_private_key, bytes)| {
+                let data = hex::decode(bytes).unwrap();
+                let from_str = BitcoinExtendedPrivateKey::<N>::try_from(*extended_private_key).unwrap();
+                assert_eq!(from_str, BitcoinExtendedPrivateKey::try_from(&data[..]).unwrap());
+                assert_eq!(
+                    from_str,
+                    BitcoinExtendedPrivateKey::try_from(<[u8; 78]>::try_from(&data[..]).unwrap()).unwrap()
+                );
+            });
+            assert!(BitcoinExtendedPrivateKey::<N>::try_from(&[0u8; 77][..]).is_err());
+            assert!(BitcoinExtendedPrivateKey::<Testnet>::try_from(KEYS[0].0).is_err());
+        }
+
+        #[test]
+        fn to_bytes_unsupported_format() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::new_master(&[0u8; 32], &BitcoinFormat::P2WSH);
+            assert!(extended_private_key.unwrap().to_bytes().is_err());
+        }
+    }
+
+    mod debug {
+        use super::*;
+
+        type N = Mainnet;
+
+        const EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        #[test]
+        fn redacted() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let private_key = extended_private_key.to_private_key();
+            let secret_key = hex::encode(private_key.to_secp256k1_secret_key().serialize());
+
+            let debug = format!("{:?}", extended_private_key);
+            assert_eq!(
+                format!(
+                    "BitcoinExtendedPrivateKey(xprv…{})",
+                    hex::encode(extended_private_key.fingerprint())
+                ),
+                debug
+            );
+            assert!(!debug.contains(EXTENDED_PRIVATE_KEY));
+            assert!(!debug.contains(&private_key.to_string()));
+            assert!(!debug.contains(&secret_key));
+
+            assert_eq!(
+                EXTENDED_PRIVATE_KEY,
+                extended_private_key.to_extended_private_key_string()
+            );
+            assert_eq!(EXTENDED_PRIVATE_KEY, extended_private_key.to_string());
+        }
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        fn test_to_string_from_str<N: BitcoinNetwork>(
+            extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        ) -> Result<(), TestCaseError> {
+            let expected = extended_private_key.to_string();
+            let parsed = BitcoinExtendedPrivateKey::<N>::from_str(&expected).unwrap();
+            prop_assert_eq!(extended_private_key, &parsed);
+            prop_assert_eq!(expected, parsed.to_string());
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str_mainnet(extended_private_key: BitcoinExtendedPrivateKey<Mainnet>) {
+                test_to_string_from_str(&extended_private_key)?;
+            }
+
+            #[test]
+            fn to_string_from_str_testnet(extended_private_key: BitcoinExtendedPrivateKey<Testnet>) {
+                test_to_string_from_str(&extended_private_key)?;
+            }
+
+            #[test]
+            fn to_string_from_str_litecoin(extended_private_key: BitcoinExtendedPrivateKey<Litecoin>) {
+                test_to_string_from_str(&extended_private_key)?;
+            }
+
+            #[test]
+            fn to_string_from_str_dogecoin(extended_private_key: BitcoinExtendedPrivateKey<Dogecoin>) {
+                test_to_string_from_str(&extended_private_key)?;
+            }
+
+            #[test]
+            fn to_bytes_from_bytes(extended_private_key: BitcoinExtendedPrivateKey<Mainnet>) {
+                let bytes = extended_private_key.to_bytes().unwrap();
+                prop_assert_eq!(extended_private_key, BitcoinExtendedPrivateKey::from_bytes(&bytes).unwrap());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinExtendedPrivateKey<Mainnet>>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_base58(inp in "xprv[1-9A-HJ-NP-Za-km-z]{0,110}") {
+                let _ = BitcoinExtendedPrivateKey::<Mainnet>::from_str(&inp);
+            }
+
+            #[test]
+            fn from_bytes_arbitrary(data in vec(any::<u8>(), 0..90)) {
+                let _ = BitcoinExtendedPrivateKey::<Mainnet>::from_bytes(&data);
+                let _ = BitcoinExtendedPrivateKey::<Mainnet>::from_str(&encode_check(&data));
+            }
+
+            #[test]
+            fn from_bytes_mutated(extended_private_key: BitcoinExtendedPrivateKey<Mainnet>, position in 0..78usize, byte: u8) {
+                let mut data = extended_private_key.to_bytes().unwrap();
+                data[position] = byte;
+                if let Ok(mutated) = BitcoinExtendedPrivateKey::<Mainnet>::from_bytes(&data) {
+                    prop_assert_eq!(&data, &mutated.to_bytes().unwrap());
+                }
+            }
         }
     }
 }
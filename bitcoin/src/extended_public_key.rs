@@ -2,23 +2,26 @@ use crate::address::BitcoinAddress;
 use crate::derivation_path::BitcoinDerivationPath;
 use crate::extended_private_key::BitcoinExtendedPrivateKey;
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::network::{check_extended_public_key_version_bytes, BitcoinNetwork};
 use crate::public_key::BitcoinPublicKey;
+use wagyu_model::no_std::*;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    base58::{decode_check, encode_check},
+    child_depth,
+    crypto::hash160,
     AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, ExtendedPublicKeyError, PublicKey,
 };
 
-use base58::{FromBase58, ToBase58};
 use core::{convert::TryFrom, fmt, str::FromStr};
 use hmac::{Hmac, Mac};
 use secp256k1::{PublicKey as Secp256k1_PublicKey, SecretKey};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use sha2::Sha512;
 
 type HmacSha512 = Hmac<Sha512>;
 
 /// Represents a Bitcoin extended public key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BitcoinExtendedPublicKey<N: BitcoinNetwork> {
     /// The address format
     format: BitcoinFormat,
@@ -55,46 +58,11 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
 
     /// Returns the extended public key for the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPublicKeyError> {
-        if self.depth == 255 {
-            return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
-        }
-
         let mut extended_public_key = self.clone();
 
         for index in path.to_vec()?.into_iter() {
-            let public_key_serialized = &self.public_key.to_secp256k1_public_key().serialize_compressed()[..];
-
-            let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
-                ChildIndex::Normal(_) => mac.input(public_key_serialized),
-                // Return failure
-                ChildIndex::Hardened(_) => {
-                    return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
-                }
-            }
-            // Append the child index in big-endian format
-            mac.input(&u32::from(index).to_be_bytes());
-            let hmac = mac.result().code();
-
-            let mut chain_code = [0u8; 32];
-            chain_code[0..32].copy_from_slice(&hmac[32..]);
-
-            let mut public_key = self.public_key.to_secp256k1_public_key();
-            public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
-            let public_key = Self::PublicKey::from_secp256k1_public_key(public_key, true);
-
-            let mut parent_fingerprint = [0u8; 4];
-            parent_fingerprint.copy_from_slice(&hash160(public_key_serialized)[0..4]);
-
-            extended_public_key = Self {
-                format: extended_public_key.format.clone(),
-                depth: extended_public_key.depth + 1,
-                parent_fingerprint,
-                child_index: index,
-                chain_code,
-                public_key,
-            };
+            let public_key = extended_public_key.serialize_public_key();
+            extended_public_key = extended_public_key.derive_child(index, &public_key)?;
         }
 
         Ok(extended_public_key)
@@ -112,28 +80,115 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
 }
 
 impl<N: BitcoinNetwork> BitcoinExtendedPublicKey<N> {
-    /// Returns the format of the Bitcoin extended private key.
+    /// Returns the extended public key of the given derivation path string, e.g. "m/0/5".
+    pub fn derive_path(&self, path: &str) -> Result<Self, ExtendedPublicKeyError> {
+        self.derive(&BitcoinDerivationPath::from_str(path)?)
+    }
+
+    /// Returns the format of the Bitcoin extended public key.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
-}
 
-impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
-    type Err = ExtendedPublicKeyError;
+    /// Returns the depth of key derivation, e.g. 3 for a BIP44 account extended public key.
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s.from_base58()?;
-        if data.len() != 82 {
+    /// Returns the Bitcoin extended public key with the version bytes of the given format,
+    /// e.g. to normalize a zpub to an xpub.
+    pub fn to_format(&self, format: &BitcoinFormat) -> Result<Self, ExtendedPublicKeyError> {
+        let _ = N::to_extended_public_key_version_bytes(format)?;
+        Ok(Self {
+            format: format.clone(),
+            ..self.clone()
+        })
+    }
+
+    /// Returns the extended public key on the network `M`, for the intentional reuse of a key
+    /// across networks, or an error if the network `M` has no version bytes for its format.
+    pub fn force_network<M: BitcoinNetwork>(&self) -> Result<BitcoinExtendedPublicKey<M>, ExtendedPublicKeyError> {
+        let _ = M::to_extended_public_key_version_bytes(&self.format)?;
+        Ok(BitcoinExtendedPublicKey {
+            format: self.format.clone(),
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_index: self.child_index,
+            chain_code: self.chain_code,
+            public_key: self.public_key.force_network(),
+        })
+    }
+
+    /// Returns the extended public keys of `count` consecutive normal child indices, beginning at
+    /// the given child index. The parent public key and fingerprint are serialized once for the
+    /// range, which makes this faster than calling `derive` for each child.
+    pub fn derive_range(&self, start: ChildIndex, count: u32) -> Result<Vec<Self>, ExtendedPublicKeyError> {
+        let public_key = self.serialize_public_key();
+        start
+            .range(count)?
+            .into_iter()
+            .map(|index| self.derive_child(index, &public_key))
+            .collect()
+    }
+
+    /// Returns the child extended public key of the given child index, given the
+    /// compressed serialization of the public key of this extended public key.
+    fn derive_child(&self, index: ChildIndex, public_key: &[u8; 33]) -> Result<Self, ExtendedPublicKeyError> {
+        // The depth is serialized in one byte, so a child of a key at depth 255 cannot be represented
+        let depth = child_depth(self.depth).ok_or(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth))?;
+
+        let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+        match index {
+            // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
+            ChildIndex::Normal(_) => mac.input(public_key),
+            // Return failure
+            ChildIndex::Hardened(_) => {
+                return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
+            }
+        }
+        // Append the child index in big-endian format
+        mac.input(&u32::from(index).to_be_bytes());
+        let hmac = mac.result().code();
+
+        let mut chain_code = [0u8; 32];
+        chain_code[0..32].copy_from_slice(&hmac[32..]);
+
+        let mut child_public_key = self.public_key.to_secp256k1_public_key();
+        let tweak = SecretKey::parse_slice(&hmac[..32])
+            .map_err(|_| ExtendedPublicKeyError::InvalidChildKey(u32::from(index)))?;
+        child_public_key
+            .tweak_add_assign(&tweak)
+            .map_err(|_| ExtendedPublicKeyError::InvalidChildKey(u32::from(index)))?;
+
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
+
+        Ok(Self {
+            format: self.format.clone(),
+            depth,
+            parent_fingerprint,
+            child_index: index,
+            chain_code,
+            public_key: BitcoinPublicKey::from_secp256k1_public_key(child_public_key, true),
+        })
+    }
+
+    /// Returns the compressed serialization of the public key, serP(K).
+    fn serialize_public_key(&self) -> [u8; 33] {
+        self.public_key.to_secp256k1_public_key().serialize_compressed()
+    }
+
+    /// Returns the extended public key of the given 78-byte BIP32 serialization, without the
+    /// Base58Check checksum.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
+        if data.len() != 78 {
             return Err(ExtendedPublicKeyError::InvalidByteLength(data.len()));
         }
 
         // Check that the version bytes correspond with the correct network.
-        let _ = N::from_extended_public_key_version_bytes(&data[0..4])?;
+        let _ = check_extended_public_key_version_bytes::<N>(&data[0..4])?;
         let format = BitcoinFormat::from_extended_public_key_version_bytes(&data[0..4])?;
 
-        let mut version = [0u8; 4];
-        version.copy_from_slice(&data[0..4]);
-
         let depth = data[4];
 
         let mut parent_fingerprint = [0u8; 4];
@@ -141,20 +196,21 @@ impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
 
         let child_index = ChildIndex::from(u32::from_be_bytes(<[u8; 4]>::try_from(&data[9..13])?));
 
+        if depth == 0 && (parent_fingerprint != [0u8; 4] || u32::from(child_index) != 0) {
+            return Err(ExtendedPublicKeyError::InvalidMasterKey);
+        }
+
         let mut chain_code = [0u8; 32];
         chain_code.copy_from_slice(&data[13..45]);
 
+        // Check that the public key is in compressed form, as a 0x00 prefix denotes a private key.
+        if data[45] != 0x02 && data[45] != 0x03 {
+            return Err(ExtendedPublicKeyError::InvalidKeyByte(data[45]));
+        }
+
         let secp256k1_public_key = Secp256k1_PublicKey::parse_slice(&data[45..78], None)?;
         let public_key = BitcoinPublicKey::from_secp256k1_public_key(secp256k1_public_key, true);
 
-        let expected = &data[78..82];
-        let checksum = &checksum(&data[0..78])[0..4];
-        if *expected != *checksum {
-            let expected = expected.to_base58();
-            let found = checksum.to_base58();
-            return Err(ExtendedPublicKeyError::InvalidChecksum(expected, found));
-        }
-
         Ok(Self {
             format,
             depth,
@@ -164,27 +220,119 @@ impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
             public_key,
         })
     }
+
+    /// Returns the 78-byte BIP32 serialization of the extended public key, without the Base58Check
+    /// checksum, or an error if the network has no version bytes for the format of the key.
+    pub fn to_bytes(&self) -> Result<[u8; 78], ExtendedPublicKeyError> {
+        let mut data = [0u8; 78];
+        data[0..4].copy_from_slice(&N::to_extended_public_key_version_bytes(&self.format)?);
+        data[4] = self.depth;
+        data[5..9].copy_from_slice(&self.parent_fingerprint[..]);
+        data[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
+        data[13..45].copy_from_slice(&self.chain_code[..]);
+        data[45..78].copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed()[..]);
+        Ok(data)
+    }
+
+    /// Returns the key identifier, hash160(serP(K)), of the Bitcoin extended public key.
+    pub fn key_identifier(&self) -> [u8; 20] {
+        let mut key_identifier = [0u8; 20];
+        key_identifier.copy_from_slice(&hash160(&self.serialize_public_key()));
+        key_identifier
+    }
+
+    /// Returns the fingerprint, the first 32 bits of the key identifier, of the Bitcoin extended public key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&self.key_identifier()[0..4]);
+        fingerprint
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
+    type Err = ExtendedPublicKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = decode_check(s)?;
+        if data.len() != 78 {
+            return Err(ExtendedPublicKeyError::InvalidByteLength(data.len() + 4));
+        }
+        Self::from_bytes(&data)
+    }
+}
+
+impl<'a, N: BitcoinNetwork> From<&'a BitcoinExtendedPrivateKey<N>> for BitcoinExtendedPublicKey<N> {
+    /// Returns the extended public key of the corresponding extended private key.
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use wagyu_bitcoin::{BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, Mainnet};
+    ///
+    /// let xprv = BitcoinExtendedPrivateKey::<Mainnet>::try_from(
+    ///     "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+    /// )
+    /// .unwrap();
+    /// let xpub = BitcoinExtendedPublicKey::from(&xprv);
+    /// assert_eq!(
+    ///     "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+    ///     xpub.to_string()
+    /// );
+    ///
+    /// let bytes = xpub.to_bytes().unwrap();
+    /// assert_eq!(xpub, BitcoinExtendedPublicKey::try_from(bytes).unwrap());
+    /// assert_eq!(xpub, BitcoinExtendedPublicKey::try_from(&bytes[..]).unwrap());
+    /// ```
+    fn from(extended_private_key: &'a BitcoinExtendedPrivateKey<N>) -> Self {
+        Self::from_extended_private_key(extended_private_key)
+    }
+}
+
+impl<'a, N: BitcoinNetwork> TryFrom<&'a str> for BitcoinExtendedPublicKey<N> {
+    type Error = ExtendedPublicKeyError;
+
+    fn try_from(extended_public_key: &'a str) -> Result<Self, Self::Error> {
+        Self::from_str(extended_public_key)
+    }
+}
+
+impl<'a, N: BitcoinNetwork> TryFrom<&'a [u8]> for BitcoinExtendedPublicKey<N> {
+    type Error = ExtendedPublicKeyError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(data)
+    }
+}
+
+impl<N: BitcoinNetwork> TryFrom<[u8; 78]> for BitcoinExtendedPublicKey<N> {
+    type Error = ExtendedPublicKeyError;
+
+    fn try_from(data: [u8; 78]) -> Result<Self, Self::Error> {
+        Self::from_bytes(&data)
+    }
 }
 
 impl<N: BitcoinNetwork> fmt::Display for BitcoinExtendedPublicKey<N> {
     /// BIP32 serialization format
     /// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#serialization-format
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let mut result = [0u8; 82];
-        result[0..4].copy_from_slice(match &N::to_extended_public_key_version_bytes(&self.format) {
-            Ok(version) => version,
-            Err(_) => return Err(fmt::Error),
-        });
-        result[4] = self.depth;
-        result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
-        result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
-        result[13..45].copy_from_slice(&self.chain_code[..]);
-        result[45..78].copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed()[..]);
-
-        let sum = &checksum(&result[0..78])[0..4];
-        result[78..82].copy_from_slice(sum);
-
-        fmt.write_str(&result.to_base58())
+        match self.to_bytes() {
+            Ok(data) => fmt.write_str(&encode_check(&data)),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> Serialize for BitcoinExtendedPublicKey<N> {
+    /// Serializes the extended public key as its Base58Check string.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, N: BitcoinNetwork> Deserialize<'de> for BitcoinExtendedPublicKey<N> {
+    /// Deserializes the extended public key from its Base58Check string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom)
     }
 }
 
@@ -464,23 +612,246 @@ mod tests {
         }
     }
 
-    mod test_invalid {
+    mod bip32_leading_zeros {
         use super::*;
 
         type N = Mainnet;
 
-        const INVALID_EXTENDED_PUBLIC_KEY_SECP256K1_PUBLIC_KEY: &str = "xpub661MyMwAqRbcftXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
-        const INVALID_EXTENDED_PUBLIC_KEY_NETWORK: &str = "xpub561MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
-        const INVALID_EXTENDED_PUBLIC_KEY_CHECKSUM: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet7";
-        const VALID_EXTENDED_PUBLIC_KEY: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        // (extended private key, extended public key) of BIP32 test vectors 3 and 4, for m, m/0',
+        // and m/0'/1' of vector 4
+        const KEYPAIRS: [(&str, &str); 5] = [
+            (
+                "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+                "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+            ),
+            (
+                "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+                "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+            ),
+            (
+                "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+                "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+            ),
+            (
+                "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+                "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+            ),
+            (
+                "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+                "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+            ),
+        ];
 
         #[test]
-        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidPublicKey\")")]
-        fn from_str_invalid_secret_key() {
-            let _result =
-                BitcoinExtendedPublicKey::<N>::from_str(INVALID_EXTENDED_PUBLIC_KEY_SECP256K1_PUBLIC_KEY).unwrap();
+        fn from_extended_private_key() {
+            KEYPAIRS
+                .iter()
+                .for_each(|(extended_private_key, expected_extended_public_key)| {
+                    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                    let extended_public_key =
+                        BitcoinExtendedPublicKey::<N>::from_extended_private_key(&extended_private_key);
+                    assert_eq!(*expected_extended_public_key, extended_public_key.to_string());
+                    assert_eq!(extended_private_key.fingerprint(), extended_public_key.fingerprint());
+                });
         }
 
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(_, expected_extended_public_key)| {
+                let extended_public_key =
+                    BitcoinExtendedPublicKey::<N>::from_str(expected_extended_public_key).unwrap();
+                assert_eq!(*expected_extended_public_key, extended_public_key.to_string());
+                assert_eq!(
+                    extended_public_key,
+                    BitcoinExtendedPublicKey::<N>::from_bytes(&extended_public_key.to_bytes().unwrap()).unwrap()
+                );
+            });
+        }
+    }
+
+    mod derive_normal_path {
+        use super::*;
+
+        type N = Mainnet;
+
+        // (extended_private_key, path)
+        const PATHS: [(&str, &str); 4] = [
+            (
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+                "m/0/1/2/3",
+            ),
+            (
+                "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+                "m/0/2147483647/1/2147483646/2",
+            ),
+            (
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+                "m/2/1000000000",
+            ),
+            (
+                "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+                "m/0/0/0/0/0/0/0/0",
+            ),
+        ];
+
+        // Check: (extended_private_key -> path -> extended_public_key) == (extended_private_key -> extended_public_key -> path)
+        #[test]
+        fn derive() {
+            PATHS.iter().for_each(|(extended_private_key, path)| {
+                let path = BitcoinDerivationPath::<N>::from_str(path).unwrap();
+                let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+
+                let expected_extended_public_key = extended_private_key.derive(&path).unwrap().to_extended_public_key();
+                let extended_public_key = extended_private_key.to_extended_public_key().derive(&path).unwrap();
+
+                assert_eq!(expected_extended_public_key, extended_public_key);
+                assert_eq!(expected_extended_public_key.to_string(), extended_public_key.to_string());
+            });
+        }
+
+        #[test]
+        fn fingerprint() {
+            PATHS.iter().for_each(|(extended_private_key, path)| {
+                let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                let parent = extended_private_key.to_extended_public_key();
+                let child = parent.derive(&BitcoinDerivationPath::from_str(path).unwrap()).unwrap();
+
+                assert_eq!(extended_private_key.key_identifier(), parent.key_identifier());
+                assert_eq!(extended_private_key.fingerprint(), parent.fingerprint());
+
+                let child_index = path.split('/').nth(1).unwrap();
+                let first_child = parent
+                    .derive(&BitcoinDerivationPath::from_str(&format!("m/{}", child_index)).unwrap())
+                    .unwrap();
+                assert_eq!(parent.fingerprint(), first_child.parent_fingerprint);
+                assert_ne!(child.fingerprint(), child.parent_fingerprint);
+            });
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidChildNumber(2147483648, 2147483649)")]
+        fn derive_hardened() {
+            let (extended_private_key, _) = PATHS[0];
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+            let path = BitcoinDerivationPath::<N>::from_str("m/0/1'").unwrap();
+            let _result = extended_private_key.to_extended_public_key().derive(&path).unwrap();
+        }
+    }
+
+    mod derive_range {
+        use super::*;
+
+        type N = Mainnet;
+
+        const EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        #[test]
+        fn derive_range() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let extended_public_key = extended_private_key.to_extended_public_key();
+            let children = extended_public_key.derive_range(ChildIndex::Normal(0), 20).unwrap();
+            let private_children = extended_private_key.derive_range(ChildIndex::Normal(0), 20).unwrap();
+            assert_eq!(20, children.len());
+
+            children
+                .iter()
+                .zip(private_children.iter())
+                .enumerate()
+                .for_each(|(index, (child, private_child))| {
+                    let path = BitcoinDerivationPath::from_str(&format!("m/{}", index)).unwrap();
+                    assert_eq!(extended_public_key.derive(&path).unwrap(), *child);
+                    assert_eq!(private_child.to_extended_public_key(), *child);
+                });
+        }
+
+        #[test]
+        fn derive_range_hardened() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let extended_public_key = extended_private_key.to_extended_public_key();
+            assert!(extended_public_key.derive_range(ChildIndex::Hardened(0), 1).is_err());
+        }
+    }
+
+    mod to_bytes {
+        use super::*;
+
+        type N = Mainnet;
+
+        // (extended public key, hex dump of its BIP32 serialization) of BIP32 test vector 1, for m and m/0'
+        const KEYS: [(&str, &str); 2] = [
+            (
+                "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                "0488b21e000000000000000000873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d5080339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+            ),
+            (
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                "0488b21e013442193e8000000047fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141035a784662a4a20a65bf6aab9ae98a6c068a81c52e4b032c0fb5400c706cfccc56",
+            ),
+        ];
+
+        #[test]
+        fn to_bytes() {
+            KEYS.iter().for_each(|(extended_public_key, bytes)| {
+                let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+                assert_eq!(*bytes, hex::encode(&extended_public_key.to_bytes().unwrap()[..]));
+            });
+        }
+
+        #[test]
+        fn from_bytes() {
+            KEYS.iter().for_each(|(extended_public_key, bytes)| {
+                let from_bytes = BitcoinExtendedPublicKey::<N>::from_bytes(&hex::decode(bytes).unwrap()).unwrap();
+                assert_eq!(*extended_public_key, from_bytes.to_string());
+            });
+        }
+
+        #[test]
+        fn from_bytes_invalid() {
+            let data = hex::decode(KEYS[0].1).unwrap();
+            // The serialization is the Base58Check payload, without its checksum.
+            assert_eq!(decode_check(KEYS[0].0).unwrap(), data);
+            assert!(BitcoinExtendedPublicKey::<N>::from_bytes(&data[..77]).is_err());
+            assert!(BitcoinExtendedPublicKey::<N>::from_bytes(&[&data[..], &[0u8; 4][..]].concat()).is_err());
+            assert!(BitcoinExtendedPublicKey::<Testnet>::from_bytes(&data).is_err());
+        }
+
+        #[test]
+        fn try_from() {
+            KEYS.iter().for_each(|(extended_public_key, bytes)| {
+                let data = hex::decode(bytes).unwrap();
+                let from_str = BitcoinExtendedPublicKey::<N>::try_from(*extended_public_key).unwrap();
+                assert_eq!(from_str, BitcoinExtendedPublicKey::try_from(&data[..]).unwrap());
+                assert_eq!(
+                    from_str,
+                    BitcoinExtendedPublicKey::try_from(<[u8; 78]>::try_from(&data[..]).unwrap()).unwrap()
+                );
+            });
+            assert!(BitcoinExtendedPublicKey::<N>::try_from(&[0u8; 77][..]).is_err());
+            assert!(BitcoinExtendedPublicKey::<Testnet>::try_from(KEYS[0].0).is_err());
+        }
+
+        #[test]
+        fn from_extended_private_key() {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            )
+            .unwrap();
+            let extended_public_key = BitcoinExtendedPublicKey::from(&extended_private_key);
+            assert_eq!(KEYS[0].0, extended_public_key.to_string());
+            assert_eq!(extended_private_key.to_extended_public_key(), extended_public_key);
+        }
+    }
+
+    mod test_invalid {
+        use super::*;
+
+        type N = Mainnet;
+
+        const INVALID_EXTENDED_PUBLIC_KEY_NETWORK: &str = "xpub561MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EDgJnkx";
+        const INVALID_EXTENDED_PUBLIC_KEY_CHECKSUM: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet7";
+        const VALID_EXTENDED_PUBLIC_KEY: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        const EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
         #[test]
         #[should_panic(expected = "InvalidVersionBytes([4, 136, 178, 29])")]
         fn from_str_invalid_version() {
@@ -493,18 +864,130 @@ mod tests {
             let _result = BitcoinExtendedPublicKey::<N>::from_str(INVALID_EXTENDED_PUBLIC_KEY_CHECKSUM).unwrap();
         }
 
+        // Returns the extended public key with the given byte replaced and the checksum recomputed.
+        fn replace_byte(extended_public_key: &str, index: usize, byte: u8) -> String {
+            let mut data = decode_check(extended_public_key).unwrap();
+            data[index] = byte;
+            encode_check(&data)
+        }
+
+        #[test]
+        #[should_panic(expected = "Crate(\"libsecp256k1\", \"InvalidPublicKey\")")]
+        fn from_str_invalid_secret_key() {
+            // There is no point on the curve with an x-coordinate of zero.
+            let mut data = decode_check(VALID_EXTENDED_PUBLIC_KEY).unwrap();
+            data[46..78].copy_from_slice(&[0u8; 32]);
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&encode_check(&data)).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidVersionBytes([4, 136, 173, 228])")]
+        fn from_str_extended_private_key() {
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidKeyByte(0)")]
+        fn from_str_private_key_byte() {
+            let string = replace_byte(VALID_EXTENDED_PUBLIC_KEY, 45, 0);
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidKeyByte(4)")]
+        fn from_str_uncompressed_key_byte() {
+            let string = replace_byte(VALID_EXTENDED_PUBLIC_KEY, 45, 4);
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidMasterKey")]
+        fn from_str_invalid_master_parent_fingerprint() {
+            let string = replace_byte(VALID_EXTENDED_PUBLIC_KEY, 5, 1);
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        #[should_panic(expected = "InvalidMasterKey")]
+        fn from_str_invalid_master_child_index() {
+            let string = replace_byte(VALID_EXTENDED_PUBLIC_KEY, 12, 1);
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&string).unwrap();
+        }
+
+        #[test]
+        fn from_str_to_string() {
+            let string = replace_byte(VALID_EXTENDED_PUBLIC_KEY, 45, 3);
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(&string).unwrap();
+            assert_eq!(string, extended_public_key.to_string());
+
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(VALID_EXTENDED_PUBLIC_KEY).unwrap();
+            assert_eq!(VALID_EXTENDED_PUBLIC_KEY, extended_public_key.to_string());
+        }
+
         #[test]
         #[should_panic(expected = "InvalidByteLength(81)")]
         fn from_str_short() {
-            let _result = BitcoinExtendedPublicKey::<N>::from_str(&VALID_EXTENDED_PUBLIC_KEY[1..]).unwrap();
+            let data = decode_check(VALID_EXTENDED_PUBLIC_KEY).unwrap();
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&encode_check(&data[1..])).unwrap();
         }
 
         #[test]
         #[should_panic(expected = "InvalidByteLength(83)")]
         fn from_str_long() {
-            let mut string = String::from(VALID_EXTENDED_PUBLIC_KEY);
-            string.push('a');
-            let _result = BitcoinExtendedPublicKey::<N>::from_str(&string).unwrap();
+            let mut data = decode_check(VALID_EXTENDED_PUBLIC_KEY).unwrap();
+            data.push(0);
+            let _result = BitcoinExtendedPublicKey::<N>::from_str(&encode_check(&data)).unwrap();
+        }
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(extended_private_key: BitcoinExtendedPrivateKey<N>) {
+                let extended_public_key = extended_private_key.to_extended_public_key();
+                let expected = extended_public_key.to_string();
+                let parsed = BitcoinExtendedPublicKey::<N>::from_str(&expected).unwrap();
+                prop_assert_eq!(&extended_public_key, &parsed);
+                prop_assert_eq!(expected, parsed.to_string());
+            }
+
+            #[test]
+            fn derive_normal_child(extended_private_key: BitcoinExtendedPrivateKey<N>, index in 0..1u32 << 31) {
+                let path = BitcoinDerivationPath::from_vec(&vec![ChildIndex::Normal(index)]).unwrap();
+                let expected = extended_private_key.derive(&path).unwrap().to_extended_public_key();
+                prop_assert_eq!(expected, extended_private_key.to_extended_public_key().derive(&path).unwrap());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinExtendedPublicKey<N>>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_base58(inp in "xpub[1-9A-HJ-NP-Za-km-z]{0,110}") {
+                let _ = BitcoinExtendedPublicKey::<N>::from_str(&inp);
+            }
+
+            #[test]
+            fn from_bytes_arbitrary(data in vec(any::<u8>(), 0..90)) {
+                let _ = BitcoinExtendedPublicKey::<N>::from_bytes(&data);
+                let _ = BitcoinExtendedPublicKey::<N>::from_str(&encode_check(&data));
+            }
+
+            #[test]
+            fn from_bytes_mutated(extended_private_key: BitcoinExtendedPrivateKey<N>, position in 0..78usize, byte: u8) {
+                let mut data = extended_private_key.to_extended_public_key().to_bytes().unwrap();
+                data[position] = byte;
+                if let Ok(mutated) = BitcoinExtendedPublicKey::<N>::from_bytes(&data) {
+                    prop_assert_eq!(&data, &mutated.to_bytes().unwrap());
+                }
+            }
         }
     }
 }
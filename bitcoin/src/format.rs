@@ -3,10 +3,10 @@ use wagyu_model::no_std::*;
 use wagyu_model::{AddressError, ExtendedPrivateKeyError, ExtendedPublicKeyError, Format};
 
 use core::fmt;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 /// Represents the format of a Bitcoin address
-#[derive(Serialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(non_camel_case_types)]
 pub enum BitcoinFormat {
     /// Pay-to-Pubkey Hash, e.g. 1NoZQSmjYHUZMbqLerwmT4xfe8A6mAo8TT
@@ -15,8 +15,10 @@ pub enum BitcoinFormat {
     P2WSH,
     /// SegWit Pay-to-Witness-Public-Key Hash, e.g. 34AgLJhwXrvmkZS1o5TrcdeevMt22Nar53
     P2SH_P2WPKH,
-    /// Bech32, e.g. bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx
+    /// Bech32, e.g. bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y
     Bech32,
+    /// Pay-to-Taproot, e.g. bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr
+    P2TR,
 }
 
 impl Format for BitcoinFormat {}
@@ -33,8 +35,8 @@ impl BitcoinFormat {
             return Err(AddressError::InvalidPrefix(prefix.to_vec()));
         }
         match (prefix[0], prefix[1]) {
-            (0x00, _) | (0x6F, _) => Ok(BitcoinFormat::P2PKH),
-            (0x05, _) | (0xC4, _) => Ok(BitcoinFormat::P2SH_P2WPKH),
+            (0x00, _) | (0x6F, _) | (0x30, _) | (0x1E, _) => Ok(BitcoinFormat::P2PKH),
+            (0x05, _) | (0xC4, _) | (0x32, _) | (0x3A, _) | (0x16, _) => Ok(BitcoinFormat::P2SH_P2WPKH),
             (0x62, 0x63) | (0x74, 0x62) => Ok(BitcoinFormat::Bech32),
             _ => return Err(AddressError::InvalidPrefix(prefix.to_vec())),
         }
@@ -45,7 +47,10 @@ impl BitcoinFormat {
     pub fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
         match prefix[0..4] {
             [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x35, 0x83, 0x94] => Ok(BitcoinFormat::P2PKH),
+            [0x01, 0x9D, 0x9C, 0xFE] | [0x04, 0x36, 0xEF, 0x7D] | [0x02, 0xFA, 0xC3, 0x98] => Ok(BitcoinFormat::P2PKH),
             [0x04, 0x9D, 0x78, 0x78] | [0x04, 0x4A, 0x4E, 0x28] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x01, 0xB2, 0x67, 0x92] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0xB2, 0x43, 0x0C] | [0x04, 0x5F, 0x18, 0xBC] => Ok(BitcoinFormat::Bech32),
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -55,7 +60,10 @@ impl BitcoinFormat {
     pub fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
         match prefix[0..4] {
             [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x35, 0x87, 0xCF] => Ok(BitcoinFormat::P2PKH),
+            [0x01, 0x9D, 0xA4, 0x62] | [0x04, 0x36, 0xF6, 0xE1] | [0x02, 0xFA, 0xCA, 0xFD] => Ok(BitcoinFormat::P2PKH),
             [0x04, 0x9D, 0x7C, 0xB2] | [0x04, 0x4A, 0x52, 0x62] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x01, 0xB2, 0x6E, 0xF6] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0xB2, 0x47, 0x46] | [0x04, 0x5F, 0x1C, 0xF6] => Ok(BitcoinFormat::Bech32),
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -68,6 +76,7 @@ impl fmt::Display for BitcoinFormat {
             BitcoinFormat::P2WSH => write!(f, "p2wsh"),
             BitcoinFormat::P2SH_P2WPKH => write!(f, "p2sh_p2wpkh"),
             BitcoinFormat::Bech32 => write!(f, "bech32"),
+            BitcoinFormat::P2TR => write!(f, "p2tr"),
         }
     }
 }
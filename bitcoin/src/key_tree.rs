@@ -0,0 +1,275 @@
+//!
+//! Key Trees
+//!
+//! This module derives every key of a subtree of an extended private key, given the range of
+//! child indices of each level, e.g. for auditing the addresses of an account without deriving
+//! each child by hand.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::extended_public_key::BitcoinExtendedPublicKey;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError};
+
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+/// Represents the child indices of a level of a key tree
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TreeLevel {
+    /// The first child index of the level
+    pub start: u32,
+    /// The number of child indices of the level
+    pub count: u32,
+    /// If true, the child indices are hardened
+    pub hardened: bool,
+}
+
+impl TreeLevel {
+    /// Returns a level of `count` normal child indices from `start`.
+    pub fn normal(start: u32, count: u32) -> Self {
+        Self {
+            start,
+            count,
+            hardened: false,
+        }
+    }
+
+    /// Returns a level of `count` hardened child indices from `start`.
+    pub fn hardened(start: u32, count: u32) -> Self {
+        Self {
+            start,
+            count,
+            hardened: true,
+        }
+    }
+
+    /// Returns the first child index of the level.
+    fn start_index(&self) -> Result<ChildIndex, ExtendedPrivateKeyError> {
+        Ok(match self.hardened {
+            true => ChildIndex::hardened(self.start)?,
+            false => ChildIndex::normal(self.start)?,
+        })
+    }
+}
+
+/// Represents the levels of a key tree, from the children of the root to the leaves
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TreeSpec {
+    pub levels: Vec<TreeLevel>,
+}
+
+impl TreeSpec {
+    /// Returns a tree specification of the given levels.
+    pub fn new(levels: Vec<TreeLevel>) -> Self {
+        Self { levels }
+    }
+}
+
+/// Represents a key of a key tree and its children
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyNode<N: BitcoinNetwork> {
+    /// The derivation path of the key, relative to the root of the tree
+    pub path: BitcoinDerivationPath<N>,
+    /// The extended private key
+    pub extended_private_key: BitcoinExtendedPrivateKey<N>,
+    /// The extended public key
+    pub extended_public_key: BitcoinExtendedPublicKey<N>,
+    /// The address, in the format of the extended private key
+    pub address: BitcoinAddress<N>,
+    /// The children of the key, in order of child index
+    pub children: Vec<KeyNode<N>>,
+}
+
+/// Serializes the extended private key of the node in clear text.
+#[cfg(feature = "serde")]
+impl<N: BitcoinNetwork> Serialize for KeyNode<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut node = serializer.serialize_struct("KeyNode", 5)?;
+        node.serialize_field("path", &self.path)?;
+        node.serialize_field("extended_private_key", &self.extended_private_key)?;
+        node.serialize_field("extended_public_key", &self.extended_public_key)?;
+        node.serialize_field("address", &self.address.to_string())?;
+        node.serialize_field("children", &self.children)?;
+        node.end()
+    }
+}
+
+/// Represents the keys of a subtree of an extended private key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyTree<N: BitcoinNetwork> {
+    /// The children of the root of the tree
+    pub nodes: Vec<KeyNode<N>>,
+}
+
+impl<N: BitcoinNetwork> KeyTree<N> {
+    /// Returns an iterator over every key of the tree, each parent before its children.
+    pub fn iter(&self) -> KeyTreeIter<'_, N> {
+        KeyTreeIter {
+            stack: self.nodes.iter().rev().collect(),
+        }
+    }
+
+    /// Returns an iterator over the keys of the tree without children.
+    pub fn leaves(&self) -> impl Iterator<Item = &KeyNode<N>> {
+        self.iter().filter(|node| node.children.is_empty())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<N: BitcoinNetwork> Serialize for KeyTree<N> {
+    /// Serializes the tree as the list of the children of its root.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.nodes.serialize(serializer)
+    }
+}
+
+/// An iterator over the keys of a key tree, in depth-first order
+pub struct KeyTreeIter<'a, N: BitcoinNetwork> {
+    stack: Vec<&'a KeyNode<N>>,
+}
+
+impl<'a, N: BitcoinNetwork> Iterator for KeyTreeIter<'a, N> {
+    type Item = &'a KeyNode<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+impl<N: BitcoinNetwork> BitcoinExtendedPrivateKey<N> {
+    /// Returns the tree of keys derived from this extended private key by the levels of the given
+    /// specification. The paths of the keys are relative to this extended private key.
+    pub fn derive_tree(&self, spec: &TreeSpec) -> Result<KeyTree<N>, ExtendedPrivateKeyError> {
+        Ok(KeyTree {
+            nodes: derive_nodes(self, &[], &spec.levels)?,
+        })
+    }
+}
+
+/// Returns the keys of the first of the given levels derived from the parent at the given path, with their children.
+fn derive_nodes<N: BitcoinNetwork>(
+    parent: &BitcoinExtendedPrivateKey<N>,
+    path: &[ChildIndex],
+    levels: &[TreeLevel],
+) -> Result<Vec<KeyNode<N>>, ExtendedPrivateKeyError> {
+    let level = match levels.first() {
+        Some(level) => level,
+        None => return Ok(vec![]),
+    };
+
+    parent
+        .derive_range(level.start_index()?, level.count)?
+        .into_iter()
+        .map(|child| {
+            let mut child_path = path.to_vec();
+            child_path.push(child.child_index);
+            Ok(KeyNode {
+                path: BitcoinDerivationPath::from_vec(&child_path)?,
+                extended_public_key: child.to_extended_public_key(),
+                address: child
+                    .to_address(&child.format())
+                    .map_err(|error| ExtendedPrivateKeyError::Message(error.to_string()))?,
+                children: derive_nodes(&child, &child_path, &levels[1..])?,
+                extended_private_key: child,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mnemonic::BitcoinMnemonic;
+    use crate::network::*;
+    use crate::wordlist::*;
+    use wagyu_model::mnemonic::{Mnemonic, MnemonicExtended};
+
+    type N = Mainnet;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn account() -> BitcoinExtendedPrivateKey<N> {
+        let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+        let master = mnemonic.to_extended_private_key(None).unwrap();
+        master.derive_path("m/44'/0'/0'").unwrap()
+    }
+
+    #[test]
+    fn derive_tree() {
+        let account = account();
+        let spec = TreeSpec::new(vec![TreeLevel::normal(0, 3), TreeLevel::normal(0, 3)]);
+        let tree = account.derive_tree(&spec).unwrap();
+
+        let leaves = tree.leaves().collect::<Vec<_>>();
+        assert_eq!(9, leaves.len());
+        assert_eq!(12, tree.iter().count());
+        assert_eq!(
+            vec!["m/0/0", "m/0/1", "m/0/2", "m/1/0", "m/1/1", "m/1/2", "m/2/0", "m/2/1", "m/2/2"],
+            leaves.iter().map(|leaf| leaf.path.to_string()).collect::<Vec<_>>()
+        );
+        assert_eq!("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", leaves[0].address.to_string());
+
+        // The parent fingerprint of each key is the fingerprint of its parent.
+        tree.nodes.iter().for_each(|node| {
+            assert_eq!(account.fingerprint(), node.extended_private_key.parent_fingerprint);
+            node.children.iter().for_each(|child| {
+                assert_eq!(
+                    node.extended_private_key.fingerprint(),
+                    child.extended_private_key.parent_fingerprint
+                );
+                assert_eq!(
+                    child.extended_private_key.to_extended_public_key(),
+                    child.extended_public_key
+                );
+            });
+        });
+
+        // Each key matches the key derived by its path.
+        tree.iter().for_each(|node| {
+            assert_eq!(account.derive(&node.path).unwrap(), node.extended_private_key);
+        });
+    }
+
+    #[test]
+    fn hardened() {
+        let spec = TreeSpec::new(vec![TreeLevel::hardened(5, 2)]);
+        let tree = account().derive_tree(&spec).unwrap();
+        assert_eq!(
+            vec!["m/5'", "m/6'"],
+            tree.iter().map(|node| node.path.to_string()).collect::<Vec<_>>()
+        );
+        assert!(account()
+            .derive_tree(&TreeSpec::new(vec![TreeLevel::hardened(1 << 31, 1)]))
+            .is_err());
+        assert!(account().derive_tree(&TreeSpec::new(vec![])).unwrap().nodes.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let spec = TreeSpec::new(vec![TreeLevel::normal(0, 1), TreeLevel::normal(3, 1)]);
+        let tree = account().derive_tree(&spec).unwrap();
+        let json = serde_json::to_value(&tree).unwrap();
+
+        let leaf = &tree.nodes[0].children[0];
+        assert_eq!("m/0", json[0]["path"]);
+        assert_eq!("m/0/3", json[0]["children"][0]["path"]);
+        assert_eq!(leaf.address.to_string(), json[0]["children"][0]["address"]);
+        assert_eq!(
+            leaf.extended_public_key.to_string(),
+            json[0]["children"][0]["extended_public_key"]
+        );
+        assert_eq!(
+            leaf.extended_private_key.to_string(),
+            json[0]["children"][0]["extended_private_key"]
+        );
+        assert_eq!(0, json[0]["children"][0]["children"].as_array().unwrap().len());
+    }
+}
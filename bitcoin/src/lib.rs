@@ -9,15 +9,38 @@
 #[macro_use]
 extern crate failure;
 
+pub mod account;
+pub use self::account::*;
+
 pub mod address;
 pub use self::address::*;
 
 pub mod amount;
 pub use self::amount::*;
 
+#[cfg(feature = "bip38")]
+pub mod bip38;
+#[cfg(feature = "bip38")]
+pub use self::bip38::*;
+
+pub mod bip85;
+pub use self::bip85::*;
+
+pub mod cashaddr;
+pub use self::cashaddr::*;
+
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod descriptor;
+pub use self::descriptor::*;
+
+pub mod discovery;
+pub use self::discovery::*;
+
+pub mod electrum;
+pub use self::electrum::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -27,6 +50,12 @@ pub use self::extended_public_key::*;
 pub mod format;
 pub use self::format::*;
 
+pub mod key_tree;
+pub use self::key_tree::*;
+
+pub mod message;
+pub use self::message::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
@@ -36,12 +65,26 @@ pub use self::network::*;
 pub mod private_key;
 pub use self::private_key::*;
 
+pub mod psbt;
+pub use self::psbt::*;
+
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod slip39;
+pub use self::slip39::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
+#[cfg(feature = "std")]
+pub mod vanity;
+#[cfg(feature = "std")]
+pub use self::vanity::*;
+
+pub mod wallet;
+pub use self::wallet::*;
+
 mod witness_program;
 
 pub mod wordlist;
@@ -0,0 +1,49 @@
+//!
+//! Message
+//!
+//! This module contains the Bitcoin Core compatible message hashing used by `signmessage`
+//! and `verifymessage`, along with the header byte layout of compact message signatures.
+//!
+//! A compact signature is 65 bytes, base64 encoded. The first byte is a header of
+//! 27 + recovery id, plus 4 if the signing key is compressed. Values from 35 to 42 are defined
+//! by BIP137 for P2SH-P2WPKH and Bech32 addresses and are accepted during verification.
+//!
+
+use crate::transaction::variable_length_integer;
+use wagyu_model::crypto::checksum;
+use wagyu_model::no_std::*;
+
+/// The prefix prepended to every message before hashing.
+pub const MESSAGE_MAGIC: &str = "Bitcoin Signed Message:\n";
+
+/// The header byte of an uncompressed compact signature with recovery id 0.
+pub const MESSAGE_SIGNATURE_HEADER: u8 = 27;
+
+/// The byte length of a compact message signature.
+pub const MESSAGE_SIGNATURE_SIZE: usize = 65;
+
+/// Returns the double SHA256 hash of the given message, prefixed with the message magic.
+pub fn message_hash(message: &str) -> [u8; 32] {
+    let mut data = vec![];
+    for bytes in [MESSAGE_MAGIC.as_bytes(), message.as_bytes()].iter() {
+        data.extend(variable_length_integer(bytes.len() as u64).expect("variable length integers are infallible"));
+        data.extend_from_slice(bytes);
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&checksum(&data));
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(
+            "02d6c0643e40b0db549cbbd7eb47dcab71a59d7017199ebde6b272f28fbbf95f",
+            hex::encode(message_hash("Hello, world!"))
+        );
+    }
+}
@@ -7,14 +7,15 @@ use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
 use crate::wordlist::BitcoinWordlist;
 use wagyu_model::no_std::*;
-use wagyu_model::{ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
+use wagyu_model::{EntropySize, ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
 
 use bitvec::prelude::*;
-use core::{fmt, marker::PhantomData, ops::Div, str, str::FromStr};
+use core::{convert::TryFrom, fmt, marker::PhantomData, str, str::FromStr};
 use hmac::Hmac;
 use pbkdf2::pbkdf2;
 use rand::Rng;
 use sha2::{Digest, Sha256, Sha512};
+use unicode_normalization::UnicodeNormalization;
 
 const PBKDF2_ROUNDS: usize = 2048;
 const PBKDF2_BYTES: usize = 64;
@@ -33,22 +34,7 @@ pub struct BitcoinMnemonic<N: BitcoinNetwork, W: BitcoinWordlist> {
 impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicCount for BitcoinMnemonic<N, W> {
     /// Returns a new mnemonic given the word count.
     fn new_with_count<R: Rng>(rng: &mut R, word_count: u8) -> Result<Self, MnemonicError> {
-        let length: usize = match word_count {
-            12 => 16,
-            15 => 20,
-            18 => 24,
-            21 => 28,
-            24 => 32,
-            wc => return Err(MnemonicError::InvalidWordCount(wc)),
-        };
-
-        let entropy: [u8; 32] = rng.gen();
-
-        Ok(Self {
-            entropy: entropy[0..length].to_vec(),
-            _network: PhantomData,
-            _wordlist: PhantomData,
-        })
+        Ok(Self::new_with_size(rng, EntropySize::from_word_count(word_count)?))
     }
 }
 
@@ -60,26 +46,17 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
 
     /// Returns a new mnemonic.
     fn new<R: Rng>(rng: &mut R) -> Result<Self, MnemonicError> {
-        let entropy: [u8; 16] = rng.gen();
-        Ok(Self {
-            entropy: entropy.to_vec(),
-            _network: PhantomData,
-            _wordlist: PhantomData,
-        })
+        Ok(Self::new_with_size(rng, EntropySize::Entropy128))
     }
 
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
-        let mnemonic = phrase.split(" ").collect::<Vec<&str>>();
-
-        let length = match mnemonic.len() {
-            12 => 128,
-            15 => 160,
-            18 => 192,
-            21 => 224,
-            24 => 256,
-            wc => return Err(MnemonicError::InvalidWordCount(wc as u8)),
-        };
+        // Normalize the phrase to NFKD form, which also replaces ideographic spaces.
+        let normalized = phrase.nfkd().collect::<String>();
+        let mnemonic = normalized.split(" ").collect::<Vec<&str>>();
+
+        let word_count = u8::try_from(mnemonic.len()).map_err(|_| MnemonicError::InvalidWordCount(mnemonic.len()))?;
+        let size = EntropySize::from_word_count(word_count)?;
 
         let mut entropy: BitVec<Msb0, u8> = BitVec::new();
 
@@ -92,13 +69,13 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
         }
 
         let mnemonic = Self {
-            entropy: entropy[..length].as_slice().to_vec(),
+            entropy: entropy[..size.entropy_bits()].as_slice().to_vec(),
             _network: PhantomData,
             _wordlist: PhantomData,
         };
 
         // Ensures the checksum word matches the checksum word in the given phrase.
-        match phrase == mnemonic.to_phrase()? {
+        match normalized == mnemonic.to_phrase()?.nfkd().collect::<String>() {
             true => Ok(mnemonic),
             false => Err(MnemonicError::InvalidPhrase(phrase.into())),
         }
@@ -106,14 +83,7 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
 
     /// Returns the phrase of the corresponding mnemonic.
     fn to_phrase(&self) -> Result<String, MnemonicError> {
-        let length: i32 = match self.entropy.len() {
-            16 => 12,
-            20 => 15,
-            24 => 18,
-            28 => 21,
-            32 => 24,
-            entropy_len => return Err(MnemonicError::InvalidEntropyLength(entropy_len)),
-        };
+        let size = EntropySize::from_entropy_length(self.entropy.len())?;
 
         // Compute the checksum by taking the first ENT / 32 bits of the SHA256 hash
         let mut sha256 = Sha256::new();
@@ -121,7 +91,7 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
 
         let hash = sha256.result();
         let hash_0 = BitVec::<Msb0, u8>::from_element(hash[0]);
-        let (checksum, _) = hash_0.split_at(length.div(3) as usize);
+        let (checksum, _) = hash_0.split_at(size.checksum_bits());
 
         // Convert the entropy bytes into bits and append the checksum
         let mut encoding = BitVec::<Msb0, u8>::from_vec(self.entropy.clone());
@@ -143,7 +113,7 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
             })
             .collect::<Vec<&str>>();
 
-        Ok(phrase.join(" "))
+        Ok(phrase.join(W::SEPARATOR))
     }
 
     /// Returns the private key of the corresponding mnemonic.
@@ -181,16 +151,69 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicExtended for BitcoinMnemonic
 }
 
 impl<N: BitcoinNetwork, W: BitcoinWordlist> BitcoinMnemonic<N, W> {
+    /// Returns the mnemonic for the given entropy.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        let _ = EntropySize::from_entropy_length(entropy.len())?;
+        Ok(Self {
+            entropy: entropy.to_vec(),
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        })
+    }
+
+    /// Returns a new mnemonic with the given entropy size.
+    pub fn new_with_size<R: Rng>(rng: &mut R, size: EntropySize) -> Self {
+        let entropy: [u8; 32] = rng.gen();
+        Self {
+            entropy: entropy[0..size.entropy_length()].to_vec(),
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        }
+    }
+
+    /// Returns the entropy of the corresponding mnemonic.
+    pub fn to_entropy(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+
+    /// Returns the entropy size of the corresponding mnemonic.
+    pub fn entropy_size(&self) -> EntropySize {
+        EntropySize::from_entropy_length(self.entropy.len()).expect("the entropy length is checked on construction")
+    }
+
+    /// Returns the number of bits of entropy of the corresponding mnemonic.
+    pub fn entropy_bits(&self) -> usize {
+        self.entropy_size().entropy_bits()
+    }
+
+    /// Returns the number of words in the phrase of the corresponding mnemonic.
+    pub fn word_count(&self) -> u8 {
+        self.entropy_size().word_count()
+    }
+
+    /// Returns `Ok` if every word of the given phrase is in the word list,
+    /// otherwise returns the position and text of the first unknown word.
+    pub fn validate_words(phrase: &str) -> Result<(), MnemonicError> {
+        let normalized = phrase.nfkd().collect::<String>();
+        for (position, word) in normalized.split(" ").enumerate() {
+            if W::get_index(word).is_err() {
+                return Err(MnemonicError::InvalidWordAtPosition(position, word.into()));
+            }
+        }
+        Ok(())
+    }
+
     /// Compares the given phrase against the phrase extracted from its entropy.
     pub fn verify_phrase(phrase: &str) -> bool {
         Self::from_phrase(phrase).is_ok()
     }
 
     /// Returns a seed using the given password and mnemonic.
-    fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
+    pub fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
         let mut seed = vec![0u8; PBKDF2_BYTES];
-        let salt = format!("mnemonic{}", password.unwrap_or(""));
-        pbkdf2::<Hmac<Sha512>>(&self.to_phrase()?.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        let phrase = self.to_phrase()?.nfkd().collect::<String>();
+        let salt = format!("mnemonic{}", password.unwrap_or("")).nfkd().collect::<String>();
+        pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
         Ok(seed)
     }
 }
@@ -484,6 +507,50 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_entropy() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, expected_seed, _)| {
+                let entropy = hex::decode(entropy_str).unwrap();
+                let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&entropy).unwrap();
+                assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+                assert_eq!(entropy, mnemonic.to_entropy());
+                assert_eq!(
+                    entropy,
+                    BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap().to_entropy()
+                );
+                test_to_seed::<N, W>(expected_seed, Some(PASSWORD), mnemonic);
+            });
+        }
+
+        #[test]
+        fn entropy_size() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                assert_eq!(entropy_str.len() * 4, mnemonic.entropy_bits());
+                assert_eq!(phrase.split(' ').count(), mnemonic.word_count() as usize);
+            });
+
+            [(EntropySize::Entropy160, "address"), (EntropySize::Entropy224, "admit")]
+                .iter()
+                .for_each(|(size, last_word)| {
+                    let entropy = vec![0u8; size.entropy_length()];
+                    let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&entropy).unwrap();
+                    let phrase = mnemonic.to_phrase().unwrap();
+                    let mut words = vec!["abandon"; size.word_count() as usize - 1];
+                    words.push(last_word);
+                    assert_eq!(words.join(" "), phrase);
+                    assert_eq!(*size, mnemonic.entropy_size());
+
+                    let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&phrase).unwrap();
+                    assert_eq!(entropy, mnemonic.to_entropy());
+                    assert_eq!(size.entropy_bits(), mnemonic.entropy_bits());
+                });
+
+            let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+            let mnemonic = BitcoinMnemonic::<N, W>::new_with_size(rng, EntropySize::Entropy192);
+            assert_eq!(18, mnemonic.word_count());
+        }
+
         #[test]
         fn to_seed_no_password() {
             let (entropy_str, _, _, _) = KEYPAIRS[0];
@@ -504,6 +571,196 @@ mod tests {
                     test_to_extended_private_key::<N, W>(expected_extended_private_key, Some(PASSWORD), phrase);
                 });
         }
+
+        #[test]
+        fn to_seed_new_master() {
+            KEYPAIRS
+                .iter()
+                .for_each(|(_, phrase, _, expected_extended_private_key)| {
+                    let seed = BitcoinMnemonic::<N, W>::from_phrase(phrase)
+                        .unwrap()
+                        .to_seed(Some(PASSWORD))
+                        .unwrap();
+                    let extended_private_key =
+                        BitcoinExtendedPrivateKey::<N>::new_master(&seed, &BitcoinFormat::P2PKH).unwrap();
+                    assert_eq!(*expected_extended_private_key, extended_private_key.to_string());
+                });
+        }
+    }
+
+    /// Test vectors from https://github.com/bip32JP/bip32JP.github.io/blob/master/test_JP_BIP39.json
+    mod japanese {
+        use super::*;
+
+        type N = Mainnet;
+        type W = Japanese;
+
+        const PASSWORD: &str = "㍍ガバヴァぱばぐゞちぢ十人十色";
+
+        // (entropy, phrase, seed)
+        const KEYPAIRS: [(&str, &str, &str); 24] = [
+            (
+                "00000000000000000000000000000000",
+                "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら",
+                "a262d6fb6122ecf45be09c50492b31f92e9beb7d9a845987a02cefda57a15f9c467a17872029a9e92299b5cbdf306e3a0ee620245cbd508959b6cb7ca637bd55"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ",
+                "aee025cbe6ca256862f889e48110a6a382365142f7d16f2b9545285b3af64e542143a577e9c144e101a6bdca18f8d97ec3366ebf5b088b1c1af9bc31346e60d9"
+            ),
+            (
+                "80808080808080808080808080808080",
+                "そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　いよく　そとづら　あまど　おおう　あかちゃん",
+                "e51736736ebdf77eda23fa17e31475fa1d9509c78f1deb6b4aacfbd760a7e2ad769c714352c95143b5c1241985bcb407df36d64e75dd5a2b78ca5d2ba82a3544"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　ろんぶん",
+                "4cd2ef49b479af5e1efbbd1e0bdc117f6a29b1010211df4f78e2ed40082865793e57949236c43b9fe591ec70e5bb4298b8b71dc4b267bb96ed4ed282c8f7761c"
+            ),
+            (
+                "000000000000000000000000000000000000000000000000",
+                "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あらいぐま",
+                "d99e8f1ce2d4288d30b9c815ae981edd923c01aa4ffdc5dee1ab5fe0d4a3e13966023324d119105aff266dac32e5cd11431eeca23bbd7202ff423f30d6776d69"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れいぎ",
+                "eaaf171efa5de4838c758a93d6c86d2677d4ccda4a064a7136344e975f91fe61340ec8a615464b461d67baaf12b62ab5e742f944c7bd4ab6c341fbafba435716"
+            ),
+            (
+                "808080808080808080808080808080808080808080808080",
+                "そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　いよく　そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　いよく　そとづら　いきなり",
+                "aec0f8d3167a10683374c222e6e632f2940c0826587ea0a73ac5d0493b6a632590179a6538287641a9fc9df8e6f24e01bf1be548e1f74fd7407ccd72ecebe425"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffff",
+                "われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　りんご",
+                "f0f738128a65b8d1854d68de50ed97ac1831fc3a978c569e415bbcb431a6a671d4377e3b56abd518daa861676c4da75a19ccb41e00c37d086941e471a4374b95"
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　いってい",
+                "23f500eec4a563bf90cfda87b3e590b211b959985c555d17e88f46f7183590cd5793458b094a4dccc8f05807ec7bd2d19ce269e20568936a751f6f1ec7c14ddd"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　まんきつ",
+                "cd354a40aa2e241e8f306b3b752781b70dfd1c69190e510bc1297a9c5738e833bcdc179e81707d57263fb7564466f73d30bf979725ff783fb3eb4baa86560b05"
+            ),
+            (
+                "8080808080808080808080808080808080808080808080808080808080808080",
+                "そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　いよく　そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　いよく　そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　うめる",
+                "6b7cd1b2cdfeeef8615077cadd6a0625f417f287652991c80206dbd82db17bf317d5c50a80bd9edd836b39daa1b6973359944c46d3fcc0129198dc7dc5cd0e68"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　らいう",
+                "a44ba7054ac2f9226929d56505a51e13acdaa8a9097923ca07ea465c4c7e294c038f3f4e7e4b373726ba0057191aced6e48ac8d183f3a11569c426f0de414623"
+            ),
+            (
+                "77c2b00716cec7213839159e404db50d",
+                "せまい　うちがわ　あずき　かろう　めずらしい　だんち　ますく　おさめる　ていぼう　あたる　すあな　えしゃく",
+                "344cef9efc37d0cb36d89def03d09144dd51167923487eec42c487f7428908546fa31a3c26b7391a2b3afe7db81b9f8c5007336b58e269ea0bd10749a87e0193"
+            ),
+            (
+                "b63a9c59a6e641f288ebc103017f1da9f8290b3da6bdef7b",
+                "ぬすむ　ふっかつ　うどん　こうりつ　しつじ　りょうり　おたがい　せもたれ　あつめる　いちりゅう　はんしゃ　ごますり　そんけい　たいちょう　らしんばん　ぶんせき　やすみ　ほいく",
+                "b14e7d35904cb8569af0d6a016cee7066335a21c1c67891b01b83033cadb3e8a034a726e3909139ecd8b2eb9e9b05245684558f329b38480e262c1d6bc20ecc4"
+            ),
+            (
+                "3e141609b97933b66a060dcddc71fad1d91677db872031e85f4c015c5e7e8982",
+                "くのう　てぬぐい　そんかい　すろっと　ちきゅう　ほあん　とさか　はくしゅ　ひびく　みえる　そざい　てんすう　たんぴん　くしょう　すいようび　みけん　きさらぎ　げざん　ふくざつ　あつかう　はやい　くろう　おやゆび　こすう",
+                "32e78dce2aff5db25aa7a4a32b493b5d10b4089923f3320c8b287a77e512455443298351beb3f7eb2390c4662a2e566eec5217e1a37467af43b46668d515e41b"
+            ),
+            (
+                "0460ef47585604c5660618db2e6a7e7f",
+                "あみもの　いきおい　ふいうち　にげる　ざんしょ　じかん　ついか　はたん　ほあん　すんぽう　てちがい　わかめ",
+                "0acf902cd391e30f3f5cb0605d72a4c849342f62bd6a360298c7013d714d7e58ddf9c7fdf141d0949f17a2c9c37ced1d8cb2edabab97c4199b142c829850154b"
+            ),
+            (
+                "72f60ebac5dd8add8d2a25a797102c3ce21bc029c200076f",
+                "すろっと　にくしみ　なやむ　たとえる　へいこう　すくう　きない　けってい　とくべつ　ねっしん　いたみ　せんせい　おくりがな　まかい　とくい　けあな　いきおい　そそぐ",
+                "9869e220bec09b6f0c0011f46e1f9032b269f096344028f5006a6e69ea5b0b8afabbb6944a23e11ebd021f182dd056d96e4e3657df241ca40babda532d364f73"
+            ),
+            (
+                "2c85efc7f24ee4573d2b81a6ec66cee209b2dcbd09d8eddc51e0215b0b68e416",
+                "かほご　きうい　ゆたか　みすえる　もらう　がっこう　よそう　ずっと　ときどき　したうけ　にんか　はっこう　つみき　すうじつ　よけい　くげん　もくてき　まわり　せめる　げざい　にげる　にんたい　たんそく　ほそく",
+                "713b7e70c9fbc18c831bfd1f03302422822c3727a93a5efb9659bec6ad8d6f2c1b5c8ed8b0b77775feaf606e9d1cc0a84ac416a85514ad59f5541ff5e0382481"
+            ),
+            (
+                "eaebabb2383351fd31d703840b32e9e2",
+                "めいえん　さのう　めだつ　すてる　きぬごし　ろんぱ　はんこ　まける　たいおう　さかいし　ねんいり　はぶらし",
+                "06e1d5289a97bcc95cb4a6360719131a786aba057d8efd603a547bd254261c2a97fcd3e8a4e766d5416437e956b388336d36c7ad2dba4ee6796f0249b10ee961"
+            ),
+            (
+                "7ac45cfe7722ee6c7ba84fbc2d5bd61b45cb2fe5eb65aa78",
+                "せんぱい　おしえる　ぐんかん　もらう　きあい　きぼう　やおや　いせえび　のいず　じゅしん　よゆう　きみつ　さといも　ちんもく　ちわわ　しんせいじ　とめる　はちみつ",
+                "1fef28785d08cbf41d7a20a3a6891043395779ed74503a5652760ee8c24dfe60972105ee71d5168071a35ab7b5bd2f8831f75488078a90f0926c8e9171b2bc4a"
+            ),
+            (
+                "4fa1a8bc3e6d80ee1316050e862c1812031493212b7ec3f3bb1b08f168cabeef",
+                "こころ　いどう　きあつ　そうがんきょう　へいあん　せつりつ　ごうせい　はいち　いびき　きこく　あんい　おちつく　きこえる　けんとう　たいこ　すすめる　はっけん　ていど　はんおん　いんさつ　うなぎ　しねま　れいぼう　みつかる",
+                "43de99b502e152d4c198542624511db3007c8f8f126a30818e856b2d8a20400d29e7a7e3fdd21f909e23be5e3c8d9aee3a739b0b65041ff0b8637276703f65c2"
+            ),
+            (
+                "18ab19a9f54a9274f03e5209a2ac8a91",
+                "うりきれ　さいせい　じゆう　むろん　とどける　ぐうたら　はいれつ　ひけつ　いずれ　うちあわせ　おさめる　おたく",
+                "3d711f075ee44d8b535bb4561ad76d7d5350ea0b1f5d2eac054e869ff7963cdce9581097a477d697a2a9433a0c6884bea10a2193647677977c9820dd0921cbde"
+            ),
+            (
+                "18a2e1d81b8ecfb2a333adcb0c17a5b9eb76cc5d05db91a4",
+                "うりきれ　うねる　せっさたくま　きもち　めんきょ　へいたく　たまご　ぜっく　びじゅつかん　さんそ　むせる　せいじ　ねくたい　しはらい　せおう　ねんど　たんまつ　がいけん",
+                "753ec9e333e616e9471482b4b70a18d413241f1e335c65cd7996f32b66cf95546612c51dcf12ead6f805f9ee3d965846b894ae99b24204954be80810d292fcdd"
+            ),
+            (
+                "15da872c95a13dd738fbf50e427583ad61f18fd99f628c417a61cf8343c90419",
+                "うちゅう　ふそく　ひしょ　がちょう　うけもつ　めいそう　みかん　そざい　いばる　うけとる　さんま　さこつ　おうさま　ぱんつ　しひょう　めした　たはつ　いちぶ　つうじょう　てさぎょう　きつね　みすえる　いりぐち　かめれおん",
+                "346b7321d8c04f6f37b49fdf062a2fddc8e1bf8f1d33171b65074531ec546d1d3469974beccb1a09263440fc92e1042580a557fdce314e27ee4eabb25fa5e5fe"
+            ),
+        ];
+
+        #[test]
+        fn from_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _)| {
+                let entropy: Vec<u8> = Vec::from(hex::decode(entropy_str).unwrap());
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                assert_eq!(entropy, mnemonic.to_entropy());
+            })
+        }
+
+        #[test]
+        fn to_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _)| {
+                let entropy: Vec<u8> = Vec::from(hex::decode(entropy_str).unwrap());
+                let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&entropy).unwrap();
+                let expected_phrase = phrase.nfkd().collect::<String>();
+                assert_eq!(
+                    expected_phrase,
+                    mnemonic.to_phrase().unwrap().nfkd().collect::<String>()
+                );
+                assert_eq!(
+                    phrase.matches(W::SEPARATOR).count(),
+                    mnemonic.to_phrase().unwrap().matches(W::SEPARATOR).count()
+                );
+            })
+        }
+
+        #[test]
+        fn to_seed() {
+            KEYPAIRS.iter().for_each(|(_, phrase, expected_seed)| {
+                let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                test_to_seed::<N, W>(expected_seed, Some(PASSWORD), mnemonic);
+            });
+        }
+
+        #[test]
+        fn detect_language() {
+            KEYPAIRS.iter().for_each(|(_, phrase, _)| {
+                assert_eq!(vec![Language::Japanese], Language::detect(phrase));
+            });
+        }
     }
 
     mod test_invalid {
@@ -547,9 +804,92 @@ mod tests {
             let _mnemonic = BitcoinMnemonic::<N, W>::from_phrase(INVALID_PHRASE_CHECKSUM).unwrap();
         }
 
+        #[test]
+        #[should_panic(expected = "InvalidEntropyLength(15)")]
+        fn from_entropy_invalid_length() {
+            let _mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&[0u8; 15]).unwrap();
+        }
+
+        #[test]
+        fn validate_words() {
+            assert!(BitcoinMnemonic::<N, W>::validate_words(INVALID_PHRASE_CHECKSUM).is_ok());
+            assert_eq!(
+                "Invalid word at position 0: abandoz",
+                BitcoinMnemonic::<N, W>::validate_words(INVALID_PHRASE_WORD)
+                    .unwrap_err()
+                    .to_string()
+            );
+
+            let phrase = "legal winner thank year wave sausage worth usefull legal winner thank yellow";
+            match BitcoinMnemonic::<N, W>::validate_words(phrase) {
+                Err(MnemonicError::InvalidWordAtPosition(position, word)) => {
+                    assert_eq!(7, position);
+                    assert_eq!("usefull", word);
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
         #[test]
         fn verify_invalid_phrase() {
             assert!(!BitcoinMnemonic::<N, W>::verify_phrase(INVALID_PHRASE_LENGTH));
         }
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        fn entropy() -> impl Strategy<Value = Vec<u8>> {
+            (0..5usize, any::<[u8; 32]>()).prop_map(|(length, entropy)| entropy[..16 + 4 * length].to_vec())
+        }
+
+        fn test_to_string_from_str<W: BitcoinWordlist>(entropy: &[u8]) -> Result<(), TestCaseError> {
+            let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(entropy).unwrap();
+            let parsed = BitcoinMnemonic::<N, W>::from_str(&mnemonic.to_string()).unwrap();
+            prop_assert_eq!(&mnemonic, &parsed);
+            prop_assert_eq!(entropy, &parsed.to_entropy()[..]);
+            Ok(())
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str_english(entropy in entropy()) {
+                test_to_string_from_str::<English>(&entropy)?;
+            }
+
+            #[test]
+            fn to_string_from_str_japanese(entropy in entropy()) {
+                test_to_string_from_str::<Japanese>(&entropy)?;
+            }
+
+            #[test]
+            fn from_str_words(count in prop_oneof![Just(12usize), Just(24usize), 0..30usize], seed in vec(0..2048usize, 30)) {
+                let phrase = seed[..count]
+                    .iter()
+                    .map(|index| English::get(*index).unwrap())
+                    .collect::<Vec<String>>()
+                    .join(" ");
+                if let Ok(mnemonic) = BitcoinMnemonic::<N, English>::from_str(&phrase) {
+                    prop_assert_eq!(phrase, mnemonic.to_string());
+                }
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinMnemonic<N, English>>(&inp)?;
+                let _ = BitcoinMnemonic::<N, English>::validate_words(&inp);
+            }
+
+            #[test]
+            fn from_entropy_arbitrary(entropy in vec(any::<u8>(), 0..40)) {
+                if let Ok(mnemonic) = BitcoinMnemonic::<N, English>::from_entropy(&entropy) {
+                    prop_assert_eq!(&mnemonic, &BitcoinMnemonic::from_str(&mnemonic.to_string()).unwrap());
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,107 @@
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, NetworkError, PrivateKeyError,
+};
+
+use core::{fmt, str::FromStr};
+use serde::Serialize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Dogecoin;
+
+impl Network for Dogecoin {
+    const NAME: &'static str = "dogecoin";
+    const ALIASES: &'static [&'static str] = &["doge"];
+}
+
+impl BitcoinNetwork for Dogecoin {
+    const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(3);
+
+    /// Returns the address prefix of the given network.
+    fn to_address_prefix(format: &BitcoinFormat) -> Vec<u8> {
+        match format {
+            BitcoinFormat::P2PKH => vec![0x1E],
+            BitcoinFormat::P2WSH => vec![0x00],
+            BitcoinFormat::P2TR => vec![0x01],
+            BitcoinFormat::P2SH_P2WPKH => vec![0x16],
+            // Dogecoin has not activated SegWit and has no Bech32 human-readable part.
+            BitcoinFormat::Bech32 => vec![],
+        }
+    }
+
+    /// Returns the network of the given address prefix.
+    fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
+        match prefix {
+            [0x1E, ..] | [0x16, ..] => Ok(Self),
+            _ => Err(AddressError::InvalidPrefix(prefix.to_owned())),
+        }
+    }
+
+    /// Returns the wif prefix of the given network.
+    fn to_private_key_prefix() -> u8 {
+        0x9E
+    }
+
+    /// Returns the network of the given wif prefix.
+    fn from_private_key_prefix(prefix: u8) -> Result<Self, PrivateKeyError> {
+        match prefix {
+            0x9E => Ok(Self),
+            _ => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
+        }
+    }
+
+    /// Returns the extended private key version bytes of the given network.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn to_extended_private_key_version_bytes(format: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPrivateKeyError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(vec![0x02, 0xFA, 0xC3, 0x98]), // dgpv
+            _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the network of the given extended private key version bytes.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        match prefix.get(0..4) {
+            Some([0x02, 0xFA, 0xC3, 0x98]) => Ok(Self),
+            _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+
+    /// Returns the extended public key version bytes of the given network.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn to_extended_public_key_version_bytes(format: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPublicKeyError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(vec![0x02, 0xFA, 0xCA, 0xFD]), // dgub
+            _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the network of the given extended public key version bytes.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
+        match prefix.get(0..4) {
+            Some([0x02, 0xFA, 0xCA, 0xFD]) => Ok(Self),
+            _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+}
+
+impl FromStr for Dogecoin {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Dogecoin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Self::NAME)
+    }
+}
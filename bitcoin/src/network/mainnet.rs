@@ -1,5 +1,5 @@
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::network::{BitcoinNetwork, Testnet};
 use wagyu_model::no_std::*;
 use wagyu_model::{
     AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, NetworkError, PrivateKeyError,
@@ -13,6 +13,7 @@ pub struct Mainnet;
 
 impl Network for Mainnet {
     const NAME: &'static str = "mainnet";
+    const ALIASES: &'static [&'static str] = &["main", "bitcoin"];
 }
 
 impl BitcoinNetwork for Mainnet {
@@ -23,6 +24,7 @@ impl BitcoinNetwork for Mainnet {
         match format {
             BitcoinFormat::P2PKH => vec![0x00],
             BitcoinFormat::P2WSH => vec![0x00],
+            BitcoinFormat::P2TR => vec![0x01],
             BitcoinFormat::P2SH_P2WPKH => vec![0x05],
             BitcoinFormat::Bech32 => vec![0x62, 0x63],
         }
@@ -30,8 +32,8 @@ impl BitcoinNetwork for Mainnet {
 
     /// Returns the network of the given address prefix.
     fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
-        match (prefix[0], prefix[1]) {
-            (0x00, _) | (0x05, _) | (0x62, 0x63) => Ok(Self),
+        match prefix {
+            [0x00, ..] | [0x05, ..] | [0x62, 0x63] => Ok(Self),
             _ => Err(AddressError::InvalidPrefix(prefix.to_owned())),
         }
     }
@@ -45,6 +47,10 @@ impl BitcoinNetwork for Mainnet {
     fn from_private_key_prefix(prefix: u8) -> Result<Self, PrivateKeyError> {
         match prefix {
             0x80 => Ok(Self),
+            0xEF => Err(PrivateKeyError::NetworkMismatch {
+                expected: Self::NAME.into(),
+                found: Testnet::NAME.into(),
+            }),
             _ => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
         }
     }
@@ -55,6 +61,7 @@ impl BitcoinNetwork for Mainnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x88, 0xAD, 0xE4]), // xprv
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x9D, 0x78, 0x78]), // yprv
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0xB2, 0x43, 0x0C]), // zprv
             _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -62,8 +69,10 @@ impl BitcoinNetwork for Mainnet {
     /// Returns the network of the given extended private key version bytes.
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
-        match prefix[0..4] {
-            [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x9D, 0x78, 0x78] => Ok(Self),
+        match prefix.get(0..4) {
+            Some([0x04, 0x88, 0xAD, 0xE4]) | Some([0x04, 0x9D, 0x78, 0x78]) | Some([0x04, 0xB2, 0x43, 0x0C]) => {
+                Ok(Self)
+            }
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -74,6 +83,7 @@ impl BitcoinNetwork for Mainnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x88, 0xB2, 0x1E]), // xpub
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x9D, 0x7C, 0xB2]), // ypub
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0xB2, 0x47, 0x46]), // zpub
             _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -81,8 +91,10 @@ impl BitcoinNetwork for Mainnet {
     /// Returns the network of the given extended public key version bytes.
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
-        match prefix[0..4] {
-            [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x9D, 0x7C, 0xB2] => Ok(Self),
+        match prefix.get(0..4) {
+            Some([0x04, 0x88, 0xB2, 0x1E]) | Some([0x04, 0x9D, 0x7C, 0xB2]) | Some([0x04, 0xB2, 0x47, 0x46]) => {
+                Ok(Self)
+            }
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -92,9 +104,9 @@ impl FromStr for Mainnet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
@@ -4,9 +4,21 @@ use wagyu_model::{
     AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, PrivateKeyError,
 };
 
+pub mod dogecoin;
+pub use self::dogecoin::*;
+
+pub mod litecoin;
+pub use self::litecoin::*;
+
+pub mod litecoin_testnet;
+pub use self::litecoin_testnet::*;
+
 pub mod mainnet;
 pub use self::mainnet::*;
 
+pub mod regtest;
+pub use self::regtest::*;
+
 pub mod testnet;
 pub use self::testnet::*;
 
@@ -38,3 +50,360 @@ pub trait BitcoinNetwork: Network {
     /// Returns the network of the given extended public key version bytes.
     fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError>;
 }
+
+/// Represents the network prefix of a serialized key or address
+#[derive(Debug, Copy, Clone)]
+enum Prefix<'a> {
+    Address(&'a [u8]),
+    PrivateKey(u8),
+    ExtendedPrivateKey(&'a [u8]),
+    ExtendedPublicKey(&'a [u8]),
+    Hrp(&'a str),
+}
+
+/// Returns `true` if the given prefix is of the network `N`.
+fn is_network<N: BitcoinNetwork>(prefix: Prefix) -> bool {
+    match prefix {
+        Prefix::Address(prefix) => N::from_address_prefix(prefix).is_ok(),
+        Prefix::PrivateKey(prefix) => N::from_private_key_prefix(prefix).is_ok(),
+        Prefix::ExtendedPrivateKey(prefix) => N::from_extended_private_key_version_bytes(prefix).is_ok(),
+        Prefix::ExtendedPublicKey(prefix) => N::from_extended_public_key_version_bytes(prefix).is_ok(),
+        Prefix::Hrp(hrp) => N::to_address_prefix(&BitcoinFormat::Bech32) == hrp.as_bytes(),
+    }
+}
+
+/// Returns the names of the networks of the given prefix. Testnet is listed before regtest,
+/// which shares its prefixes other than the human-readable part of Bech32 addresses.
+fn detect_networks(prefix: Prefix) -> Vec<&'static str> {
+    let networks = [
+        (Mainnet::NAME, is_network::<Mainnet> as fn(Prefix) -> bool),
+        (Testnet::NAME, is_network::<Testnet>),
+        (Regtest::NAME, is_network::<Regtest>),
+        (Litecoin::NAME, is_network::<Litecoin>),
+        (LitecoinTestnet::NAME, is_network::<LitecoinTestnet>),
+        (Dogecoin::NAME, is_network::<Dogecoin>),
+    ];
+    networks
+        .iter()
+        .filter(|(_, is_network)| is_network(prefix))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Returns the names of the networks, other than `N`, of the given prefix, e.g. "testnet or
+/// regtest" for a prefix that both networks share.
+fn find_network<N: BitcoinNetwork>(prefix: Prefix) -> Option<String> {
+    let found = detect_networks(prefix)
+        .into_iter()
+        .filter(|name| *name != N::NAME)
+        .collect::<Vec<&str>>();
+    match found.is_empty() {
+        true => None,
+        false => Some(found.join(" or ")),
+    }
+}
+
+/// Returns the name of the network of the given Base58 address prefix.
+pub(crate) fn address_network(prefix: &[u8]) -> Option<&'static str> {
+    detect_networks(Prefix::Address(prefix)).first().copied()
+}
+
+/// Returns the name of the network of the given Bech32 human-readable part.
+pub(crate) fn bech32_network(hrp: &str) -> Option<&'static str> {
+    detect_networks(Prefix::Hrp(hrp)).first().copied()
+}
+
+/// Returns the network of the given address prefix, or a network mismatch error if the prefix
+/// is of another network.
+pub(crate) fn check_address_prefix<N: BitcoinNetwork>(prefix: &[u8]) -> Result<N, AddressError> {
+    N::from_address_prefix(prefix).map_err(|error| match find_network::<N>(Prefix::Address(prefix)) {
+        Some(found) => AddressError::NetworkMismatch {
+            expected: N::NAME.into(),
+            found,
+        },
+        None => error,
+    })
+}
+
+/// Returns the network of the given wif prefix, or a network mismatch error if the prefix is
+/// of another network.
+pub(crate) fn check_private_key_prefix<N: BitcoinNetwork>(prefix: u8) -> Result<N, PrivateKeyError> {
+    N::from_private_key_prefix(prefix).map_err(|error| match find_network::<N>(Prefix::PrivateKey(prefix)) {
+        Some(found) => PrivateKeyError::NetworkMismatch {
+            expected: N::NAME.into(),
+            found,
+        },
+        None => error,
+    })
+}
+
+/// Returns the network of the given extended private key version bytes, or a network mismatch
+/// error if the version bytes are of another network.
+pub(crate) fn check_extended_private_key_version_bytes<N: BitcoinNetwork>(
+    prefix: &[u8],
+) -> Result<N, ExtendedPrivateKeyError> {
+    N::from_extended_private_key_version_bytes(prefix).map_err(|error| {
+        match find_network::<N>(Prefix::ExtendedPrivateKey(prefix)) {
+            Some(found) => ExtendedPrivateKeyError::NetworkMismatch {
+                expected: N::NAME.into(),
+                found,
+            },
+            None => error,
+        }
+    })
+}
+
+/// Returns the network of the given extended public key version bytes, or a network mismatch
+/// error if the version bytes are of another network.
+pub(crate) fn check_extended_public_key_version_bytes<N: BitcoinNetwork>(
+    prefix: &[u8],
+) -> Result<N, ExtendedPublicKeyError> {
+    N::from_extended_public_key_version_bytes(prefix).map_err(|error| {
+        match find_network::<N>(Prefix::ExtendedPublicKey(prefix)) {
+            Some(found) => ExtendedPublicKeyError::NetworkMismatch {
+                expected: N::NAME.into(),
+                found,
+            },
+            None => error,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::BitcoinAddress;
+    use crate::extended_private_key::BitcoinExtendedPrivateKey;
+    use crate::extended_public_key::BitcoinExtendedPublicKey;
+    use crate::private_key::BitcoinPrivateKey;
+    use crate::public_key::BitcoinPublicKey;
+    use core::str::FromStr;
+    use wagyu_model::{ExtendedPrivateKey, NetworkError, PublicKey};
+
+    const EXTENDED_PRIVATE_KEY: &str =
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+    fn test_from_str<N: BitcoinNetwork + FromStr<Err = NetworkError>>(names: &[&str]) {
+        names.iter().for_each(|name| {
+            assert_eq!(N::NAME, N::from_str(name).unwrap().to_string());
+            assert_eq!(N::NAME, N::from_str(&name.to_uppercase()).unwrap().to_string());
+        });
+    }
+
+    #[test]
+    fn from_str() {
+        test_from_str::<Mainnet>(&["mainnet", "main", "bitcoin"]);
+        test_from_str::<Testnet>(&["testnet", "test", "testnet3"]);
+        test_from_str::<Regtest>(&["regtest", "reg"]);
+        test_from_str::<Litecoin>(&["litecoin", "ltc"]);
+        test_from_str::<LitecoinTestnet>(&["litecoin_testnet", "ltc-testnet"]);
+        test_from_str::<Dogecoin>(&["dogecoin", "doge"]);
+
+        assert!(Mainnet::from_str("testnet").is_err());
+        assert!(Testnet::from_str("main net").is_err());
+        assert!(Litecoin::from_str("").is_err());
+        assert!(Regtest::from_str("testnet").is_err());
+    }
+
+    fn network_mismatch(expected: &str, found: &str) -> (String, String) {
+        (expected.into(), found.into())
+    }
+
+    #[test]
+    fn network_mismatch_from_str() {
+        let extended_private_key = BitcoinExtendedPrivateKey::<Mainnet>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+        let private_key = extended_private_key.to_private_key();
+        let public_key = BitcoinPublicKey::from_private_key(&private_key);
+
+        // A tprv is not a mainnet extended private key, and may be of testnet or regtest.
+        let tprv = extended_private_key.force_network::<Testnet>().unwrap().to_string();
+        match BitcoinExtendedPrivateKey::<Mainnet>::from_str(&tprv) {
+            Err(ExtendedPrivateKeyError::NetworkMismatch { expected, found }) => {
+                assert_eq!(network_mismatch("mainnet", "testnet or regtest"), (expected, found))
+            }
+            result => panic!("unexpected result: {:?}", result.map(|key| key.to_string())),
+        };
+
+        // A mainnet xpub is not a Dogecoin extended public key.
+        let xpub = extended_private_key.to_extended_public_key().to_string();
+        match BitcoinExtendedPublicKey::<Dogecoin>::from_str(&xpub) {
+            Err(ExtendedPublicKeyError::NetworkMismatch { expected, found }) => {
+                assert_eq!(network_mismatch("dogecoin", "mainnet"), (expected, found))
+            }
+            result => panic!("unexpected result: {:?}", result.map(|key| key.to_string())),
+        };
+
+        // Testnet addresses are not mainnet addresses, in either encoding. Only the human-readable
+        // part of a Bech32 address tells testnet and regtest apart.
+        let formats = [
+            (BitcoinFormat::P2PKH, "testnet or regtest or litecoin_testnet"),
+            (BitcoinFormat::Bech32, "testnet"),
+        ];
+        for (format, network) in &formats {
+            let address = public_key.force_network::<Testnet>().to_address(format).unwrap();
+            match BitcoinAddress::<Mainnet>::from_str(&address.to_string()) {
+                Err(AddressError::NetworkMismatch { expected, found }) => {
+                    assert_eq!(network_mismatch("mainnet", network), (expected, found))
+                }
+                result => panic!("unexpected result: {:?}", result),
+            };
+        }
+
+        // A regtest Bech32 address is not a mainnet address, although its human-readable part
+        // begins with that of mainnet.
+        let address = public_key
+            .force_network::<Regtest>()
+            .to_address(&BitcoinFormat::Bech32)
+            .unwrap();
+        match BitcoinAddress::<Mainnet>::from_str(&address.to_string()) {
+            Err(AddressError::NetworkMismatch { expected, found }) => {
+                assert_eq!(network_mismatch("mainnet", "regtest"), (expected, found))
+            }
+            result => panic!("unexpected result: {:?}", result),
+        };
+        assert!(Mainnet::from_address_prefix(b"bcrt").is_err());
+        assert!(Testnet::from_address_prefix(b"tbx").is_err());
+
+        // A regtest wif is not a mainnet private key, and may be of any network with its prefix.
+        let wif = private_key.force_network::<Regtest>().to_string();
+        match BitcoinPrivateKey::<Mainnet>::from_str(&wif) {
+            Err(PrivateKeyError::NetworkMismatch { expected, found }) => {
+                assert_eq!(
+                    network_mismatch("mainnet", "testnet or regtest or litecoin_testnet"),
+                    (expected, found)
+                )
+            }
+            result => panic!("unexpected result: {:?}", result.map(|key| key.to_string())),
+        };
+
+        // A Litecoin wif is not a mainnet private key.
+        let wif = private_key.force_network::<Litecoin>().to_string();
+        match BitcoinPrivateKey::<Mainnet>::from_str(&wif) {
+            Err(PrivateKeyError::NetworkMismatch { expected, found }) => {
+                assert_eq!(network_mismatch("mainnet", "litecoin"), (expected, found))
+            }
+            result => panic!("unexpected result: {:?}", result.map(|key| key.to_string())),
+        };
+
+        // A prefix of no network is still an invalid prefix.
+        let wif = "L6Cyjtdfq1TimFfgG9qBEHQF2FmaL96H5ceTrip74S4eqFapbcDJ";
+        assert!(matches!(
+            BitcoinPrivateKey::<Mainnet>::from_str(wif),
+            Err(PrivateKeyError::InvalidPrefix(_))
+        ));
+    }
+
+    #[test]
+    fn force_network() {
+        let extended_private_key = BitcoinExtendedPrivateKey::<Mainnet>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+        let private_key = extended_private_key.to_private_key();
+
+        let testnet = extended_private_key.force_network::<Testnet>().unwrap();
+        assert!(testnet.to_string().starts_with("tprv"));
+        assert_eq!(
+            testnet,
+            BitcoinExtendedPrivateKey::<Testnet>::from_str(&testnet.to_string()).unwrap()
+        );
+        assert_eq!(extended_private_key, testnet.force_network::<Mainnet>().unwrap());
+
+        // The derived keys of the forced key are the forced derived keys.
+        let path = "m/44'/1'/0'/0/0";
+        assert_eq!(
+            extended_private_key
+                .derive_path(path)
+                .unwrap()
+                .to_private_key()
+                .force_network::<Testnet>(),
+            testnet.derive_path(path).unwrap().to_private_key()
+        );
+        assert_eq!(
+            extended_private_key
+                .to_extended_public_key()
+                .force_network::<Testnet>()
+                .unwrap(),
+            testnet.to_extended_public_key()
+        );
+        assert_eq!(
+            private_key.to_secp256k1_secret_key(),
+            private_key.force_network::<Regtest>().to_secp256k1_secret_key()
+        );
+
+        // Dogecoin has no version bytes for Bech32 extended keys.
+        let zprv = extended_private_key.to_format(&BitcoinFormat::Bech32).unwrap();
+        assert!(zprv.force_network::<Dogecoin>().is_err());
+        assert!(zprv.to_extended_public_key().force_network::<Dogecoin>().is_err());
+    }
+
+    #[test]
+    fn invalid_version_bytes() {
+        assert!(Mainnet::from_extended_private_key_version_bytes(&[0x04, 0x88, 0xAD, 0xE4]).is_ok());
+        assert!(Mainnet::from_extended_private_key_version_bytes(&[0x04, 0x35, 0x83, 0x94]).is_err());
+        assert!(Testnet::from_extended_public_key_version_bytes(&[0x04, 0x88, 0xB2, 0x1E]).is_err());
+        assert!(Dogecoin::from_extended_public_key_version_bytes(&[0xFF; 4]).is_err());
+        assert!(Litecoin::from_extended_private_key_version_bytes(&[0x01, 0x9D]).is_err());
+    }
+
+    #[test]
+    fn from_prefix_empty() {
+        fn test_from_prefix_empty<N: BitcoinNetwork>() {
+            assert!(N::from_address_prefix(&[]).is_err());
+            assert!(N::from_extended_private_key_version_bytes(&[]).is_err());
+            assert!(N::from_extended_public_key_version_bytes(&[]).is_err());
+        }
+
+        test_from_prefix_empty::<Mainnet>();
+        test_from_prefix_empty::<Testnet>();
+        test_from_prefix_empty::<Regtest>();
+        test_from_prefix_empty::<Litecoin>();
+        test_from_prefix_empty::<LitecoinTestnet>();
+        test_from_prefix_empty::<Dogecoin>();
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::prelude::*;
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        fn test_from_str_arbitrary<N: BitcoinNetwork + FromStr<Err = NetworkError>>(
+            name: &str,
+        ) -> Result<(), TestCaseError> {
+            test_from_str_round_trip::<N>(name)?;
+            match N::from_str(name) {
+                Ok(network) => prop_assert_eq!(N::NAME, network.to_string()),
+                Err(_) => prop_assert!(!N::is_name(name)),
+            };
+            Ok(())
+        }
+
+        fn test_from_prefix_arbitrary<N: BitcoinNetwork>(prefix: &[u8]) {
+            let _ = N::from_address_prefix(prefix);
+            let _ = N::from_extended_private_key_version_bytes(prefix);
+            let _ = N::from_extended_public_key_version_bytes(prefix);
+            if let Some(prefix) = prefix.first() {
+                let _ = N::from_private_key_prefix(*prefix);
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn from_str_arbitrary(name in "\\PC*|(?i)(main|test|reg|litecoin|ltc|doge)[a-z0-9_-]{0,8}") {
+                test_from_str_arbitrary::<Mainnet>(&name)?;
+                test_from_str_arbitrary::<Testnet>(&name)?;
+                test_from_str_arbitrary::<Regtest>(&name)?;
+                test_from_str_arbitrary::<Litecoin>(&name)?;
+                test_from_str_arbitrary::<LitecoinTestnet>(&name)?;
+                test_from_str_arbitrary::<Dogecoin>(&name)?;
+            }
+
+            #[test]
+            fn from_prefix_arbitrary(prefix in proptest::collection::vec(any::<u8>(), 0..6)) {
+                test_from_prefix_arbitrary::<Mainnet>(&prefix);
+                test_from_prefix_arbitrary::<Testnet>(&prefix);
+                test_from_prefix_arbitrary::<Regtest>(&prefix);
+                test_from_prefix_arbitrary::<Litecoin>(&prefix);
+                test_from_prefix_arbitrary::<LitecoinTestnet>(&prefix);
+                test_from_prefix_arbitrary::<Dogecoin>(&prefix);
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use crate::format::BitcoinFormat;
+use crate::network::{BitcoinNetwork, Mainnet};
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, NetworkError, PrivateKeyError,
+};
+
+use core::{fmt, str::FromStr};
+use serde::Serialize;
+
+/// The regression test network of Bitcoin Core shares the Base58 and extended key prefixes of
+/// the test network, and differs only in the human-readable part of its Bech32 addresses.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct Regtest;
+
+impl Network for Regtest {
+    const NAME: &'static str = "regtest";
+    const ALIASES: &'static [&'static str] = &["reg"];
+}
+
+impl BitcoinNetwork for Regtest {
+    const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
+
+    /// Returns the address prefix of the given network.
+    fn to_address_prefix(format: &BitcoinFormat) -> Vec<u8> {
+        match format {
+            BitcoinFormat::P2PKH => vec![0x6F],
+            BitcoinFormat::P2WSH => vec![0x00],
+            BitcoinFormat::P2TR => vec![0x01],
+            BitcoinFormat::P2SH_P2WPKH => vec![0xC4],
+            BitcoinFormat::Bech32 => vec![0x62, 0x63, 0x72, 0x74],
+        }
+    }
+
+    /// Returns the network of the given address prefix.
+    fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
+        match prefix {
+            [0x6F, ..] | [0xC4, ..] | [0x62, 0x63, 0x72, 0x74] => Ok(Self),
+            _ => Err(AddressError::InvalidPrefix(prefix.to_owned())),
+        }
+    }
+
+    /// Returns the wif prefix of the given network.
+    fn to_private_key_prefix() -> u8 {
+        0xEF
+    }
+
+    /// Returns the network of the given wif prefix.
+    fn from_private_key_prefix(prefix: u8) -> Result<Self, PrivateKeyError> {
+        match prefix {
+            0xEF => Ok(Self),
+            0x80 => Err(PrivateKeyError::NetworkMismatch {
+                expected: Self::NAME.into(),
+                found: Mainnet::NAME.into(),
+            }),
+            _ => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
+        }
+    }
+
+    /// Returns the extended private key version bytes of the given network.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn to_extended_private_key_version_bytes(format: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPrivateKeyError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(vec![0x04, 0x35, 0x83, 0x94]), // tpriv
+            BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x4A, 0x4E, 0x28]), // upriv
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0x5F, 0x18, 0xBC]), // vpriv
+            _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the network of the given extended private key version bytes.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        match prefix.get(0..4) {
+            Some([0x04, 0x35, 0x83, 0x94]) | Some([0x04, 0x4A, 0x4E, 0x28]) | Some([0x04, 0x5F, 0x18, 0xBC]) => {
+                Ok(Self)
+            }
+            _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+
+    /// Returns the extended public key version bytes of the given network.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn to_extended_public_key_version_bytes(format: &BitcoinFormat) -> Result<Vec<u8>, ExtendedPublicKeyError> {
+        match format {
+            BitcoinFormat::P2PKH => Ok(vec![0x04, 0x35, 0x87, 0xCF]), // tpub
+            BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x4A, 0x52, 0x62]), // upub
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0x5F, 0x1C, 0xF6]), // vpub
+            _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the network of the given extended public key version bytes.
+    /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+    fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
+        match prefix.get(0..4) {
+            Some([0x04, 0x35, 0x87, 0xCF]) | Some([0x04, 0x4A, 0x52, 0x62]) | Some([0x04, 0x5F, 0x1C, 0xF6]) => {
+                Ok(Self)
+            }
+            _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
+        }
+    }
+}
+
+impl FromStr for Regtest {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
+        }
+    }
+}
+
+impl fmt::Display for Regtest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Self::NAME)
+    }
+}
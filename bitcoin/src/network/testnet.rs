@@ -1,5 +1,5 @@
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::network::{BitcoinNetwork, Mainnet};
 use wagyu_model::no_std::*;
 use wagyu_model::{
     AddressError, ChildIndex, ExtendedPrivateKeyError, ExtendedPublicKeyError, Network, NetworkError, PrivateKeyError,
@@ -13,6 +13,7 @@ pub struct Testnet;
 
 impl Network for Testnet {
     const NAME: &'static str = "testnet";
+    const ALIASES: &'static [&'static str] = &["test", "testnet3"];
 }
 
 impl BitcoinNetwork for Testnet {
@@ -23,6 +24,7 @@ impl BitcoinNetwork for Testnet {
         match format {
             BitcoinFormat::P2PKH => vec![0x6F],
             BitcoinFormat::P2WSH => vec![0x00],
+            BitcoinFormat::P2TR => vec![0x01],
             BitcoinFormat::P2SH_P2WPKH => vec![0xC4],
             BitcoinFormat::Bech32 => vec![0x74, 0x62],
         }
@@ -30,8 +32,8 @@ impl BitcoinNetwork for Testnet {
 
     /// Returns the network of the given address prefix.
     fn from_address_prefix(prefix: &[u8]) -> Result<Self, AddressError> {
-        match (prefix[0], prefix[1]) {
-            (0x6F, _) | (0xC4, _) | (0x74, 0x62) => Ok(Self),
+        match prefix {
+            [0x6F, ..] | [0xC4, ..] | [0x74, 0x62] => Ok(Self),
             _ => Err(AddressError::InvalidPrefix(prefix.to_owned())),
         }
     }
@@ -45,6 +47,10 @@ impl BitcoinNetwork for Testnet {
     fn from_private_key_prefix(prefix: u8) -> Result<Self, PrivateKeyError> {
         match prefix {
             0xEF => Ok(Self),
+            0x80 => Err(PrivateKeyError::NetworkMismatch {
+                expected: Self::NAME.into(),
+                found: Mainnet::NAME.into(),
+            }),
             _ => Err(PrivateKeyError::InvalidPrefix(vec![prefix])),
         }
     }
@@ -55,6 +61,7 @@ impl BitcoinNetwork for Testnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x35, 0x83, 0x94]), // tpriv
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x4A, 0x4E, 0x28]), // upriv
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0x5F, 0x18, 0xBC]), // vpriv
             _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -62,8 +69,10 @@ impl BitcoinNetwork for Testnet {
     /// Returns the network of the given extended private key version bytes.
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
-        match prefix[0..4] {
-            [0x04, 0x35, 0x83, 0x94] | [0x04, 0x4A, 0x4E, 0x28] => Ok(Self),
+        match prefix.get(0..4) {
+            Some([0x04, 0x35, 0x83, 0x94]) | Some([0x04, 0x4A, 0x4E, 0x28]) | Some([0x04, 0x5F, 0x18, 0xBC]) => {
+                Ok(Self)
+            }
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -74,6 +83,7 @@ impl BitcoinNetwork for Testnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x35, 0x87, 0xCF]), // tpub
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x4A, 0x52, 0x62]), // upub
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0x5F, 0x1C, 0xF6]), // vpub
             _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -81,8 +91,10 @@ impl BitcoinNetwork for Testnet {
     /// Returns the network of the given extended public key version bytes.
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
-        match prefix[0..4] {
-            [0x04, 0x35, 0x87, 0xCF] | [0x04, 0x4A, 0x52, 0x62] => Ok(Self),
+        match prefix.get(0..4) {
+            Some([0x04, 0x35, 0x87, 0xCF]) | Some([0x04, 0x4A, 0x52, 0x62]) | Some([0x04, 0x5F, 0x1C, 0xF6]) => {
+                Ok(Self)
+            }
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -92,9 +104,9 @@ impl FromStr for Testnet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
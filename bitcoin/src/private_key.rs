@@ -1,16 +1,27 @@
 use crate::address::BitcoinAddress;
 use crate::format::BitcoinFormat;
-use crate::network::BitcoinNetwork;
+use crate::message::{message_hash, MESSAGE_SIGNATURE_HEADER, MESSAGE_SIGNATURE_SIZE};
+use crate::network::{check_private_key_prefix, BitcoinNetwork};
 use crate::public_key::BitcoinPublicKey;
-use wagyu_model::{crypto::checksum, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
-
-use base58::{FromBase58, ToBase58};
-use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
-use rand::Rng;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    base58::{decode_check, encode_check, Base58Error},
+    crypto::constant_time_eq,
+    parse_hex_32, parse_secp256k1_secret_key, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey,
+};
+
+use core::{
+    fmt,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
+use rand::{CryptoRng, Rng};
 use secp256k1;
 
 /// Represents a Bitcoin private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct BitcoinPrivateKey<N: BitcoinNetwork> {
     /// The ECDSA private key
     secret_key: secp256k1::SecretKey,
@@ -46,6 +57,15 @@ impl<N: BitcoinNetwork> PrivateKey for BitcoinPrivateKey<N> {
 }
 
 impl<N: BitcoinNetwork> BitcoinPrivateKey<N> {
+    /// Returns a randomly-generated compressed Bitcoin private key from a cryptographically secure RNG.
+    pub fn new_random<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        Self {
+            secret_key: secp256k1::SecretKey::random(rng),
+            compressed: true,
+            _network: PhantomData,
+        }
+    }
+
     /// Returns a private key given a secp256k1 secret key.
     pub fn from_secp256k1_secret_key(secret_key: &secp256k1::SecretKey, compressed: bool) -> Self {
         Self {
@@ -60,10 +80,98 @@ impl<N: BitcoinNetwork> BitcoinPrivateKey<N> {
         self.secret_key.clone()
     }
 
+    /// Returns the private key on the network `M`, for the intentional reuse of a secret key
+    /// across networks, which parsing and the network type parameter otherwise prevent.
+    pub fn force_network<M: BitcoinNetwork>(&self) -> BitcoinPrivateKey<M> {
+        BitcoinPrivateKey::<M>::from_secp256k1_secret_key(&self.secret_key, self.compressed)
+    }
+
+    /// Returns a private key given the 64 character hex encoding of its secret key scalar,
+    /// which must be in the range [1, n - 1].
+    pub fn from_hex(hex: &str, compressed: bool) -> Result<Self, PrivateKeyError> {
+        let secret_key = parse_secp256k1_secret_key(&parse_hex_32(hex)?)?;
+        Ok(Self::from_secp256k1_secret_key(&secret_key, compressed))
+    }
+
+    /// Returns the 64 character hex encoding of the secret key scalar of the private key.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.secret_key.serialize())
+    }
+
     /// Returns `true` if the private key is in compressed form.
     pub fn is_compressed(&self) -> bool {
         self.compressed
     }
+
+    /// Returns a copy of the private key that derives a compressed public key.
+    pub fn to_compressed(&self) -> Self {
+        Self::from_secp256k1_secret_key(&self.secret_key, true)
+    }
+
+    /// Returns a copy of the private key that derives an uncompressed public key.
+    pub fn to_uncompressed(&self) -> Self {
+        Self::from_secp256k1_secret_key(&self.secret_key, false)
+    }
+
+    /// Returns the ECDSA signature of the given 32 byte message digest. The nonce is derived
+    /// deterministically from the secret key and the digest (RFC6979), so no RNG is used.
+    pub fn sign(&self, digest: &[u8]) -> Result<secp256k1::Signature, PrivateKeyError> {
+        Ok(self.sign_recoverable(digest)?.0)
+    }
+
+    /// Returns the ECDSA signature of the given 32 byte message digest with its recovery id,
+    /// using an RFC6979 deterministic nonce.
+    pub fn sign_recoverable(
+        &self,
+        digest: &[u8],
+    ) -> Result<(secp256k1::Signature, secp256k1::RecoveryId), PrivateKeyError> {
+        Ok(secp256k1::sign(
+            &secp256k1::Message::parse_slice(digest)?,
+            &self.secret_key,
+        ))
+    }
+
+    /// Returns a base64 encoded compact signature of the given message, compatible with
+    /// the `signmessage` RPC of Bitcoin Core.
+    pub fn sign_message(&self, message: &str) -> String {
+        // The message hash is 32 bytes, so signing does not fail.
+        let (signature, recovery_id) = self.sign_recoverable(&message_hash(message)).unwrap();
+
+        let mut header = MESSAGE_SIGNATURE_HEADER + recovery_id.serialize();
+        if self.compressed {
+            header += 4;
+        }
+
+        let mut compact = [0u8; MESSAGE_SIGNATURE_SIZE];
+        compact[0] = header;
+        compact[1..].copy_from_slice(&signature.serialize());
+        base64::encode(&compact[..])
+    }
+}
+
+impl<N: BitcoinNetwork> PartialEq for BitcoinPrivateKey<N> {
+    /// Compares the secret keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.secret_key.serialize(), &other.secret_key.serialize())
+            && self.compressed == other.compressed
+    }
+}
+
+impl<N: BitcoinNetwork> Eq for BitcoinPrivateKey<N> {}
+
+impl<N: BitcoinNetwork> Hash for BitcoinPrivateKey<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.secret_key.serialize().hash(state);
+        self.compressed.hash(state);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: BitcoinNetwork> Default for BitcoinPrivateKey<N> {
+    /// Returns a randomly-generated compressed Bitcoin private key using the operating system RNG.
+    fn default() -> Self {
+        Self::new_random(&mut rand::rngs::OsRng)
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
@@ -71,26 +179,26 @@ impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
 
     /// Returns a Bitcoin private key from a given WIF.
     fn from_str(wif: &str) -> Result<Self, Self::Err> {
-        let data = wif.from_base58()?;
+        let data = decode_check(wif).map_err(|error| match error {
+            Base58Error::InvalidChecksum(expected, found) => PrivateKeyError::InvalidChecksum(expected, found),
+            error => PrivateKeyError::Base58Error(error),
+        })?;
         let len = data.len();
-        if len != 37 && len != 38 {
-            return Err(PrivateKeyError::InvalidByteLength(len));
-        }
-
-        let expected = &data[len - 4..len];
-        let checksum = &checksum(&data[0..len - 4])[0..4];
-        if *expected != *checksum {
-            let expected = expected.to_base58();
-            let found = checksum.to_base58();
-            return Err(PrivateKeyError::InvalidChecksum(expected, found));
+        if len != 33 && len != 34 {
+            return Err(PrivateKeyError::InvalidByteLength(len + 4));
         }
 
         // Check that the wif prefix corresponds to the correct network.
-        let _ = N::from_private_key_prefix(data[0])?;
+        let _ = check_private_key_prefix::<N>(data[0])?;
+
+        // Compressed keys are suffixed with a single 0x01 byte.
+        if len == 34 && data[33] != 0x01 {
+            return Err(PrivateKeyError::InvalidCompressionFlag(data[33]));
+        }
 
         Ok(Self {
-            secret_key: secp256k1::SecretKey::parse_slice(&data[1..33])?,
-            compressed: len == 38,
+            secret_key: secp256k1::SecretKey::parse_slice(&data[1..33]).map_err(|_| PrivateKeyError::InvalidScalar)?,
+            compressed: len == 34,
             _network: PhantomData,
         })
     }
@@ -98,25 +206,49 @@ impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
 
 impl<N: BitcoinNetwork> Display for BitcoinPrivateKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut wif = [0u8; 38];
+        let mut wif = [0u8; 34];
         wif[0] = N::to_private_key_prefix();
         wif[1..33].copy_from_slice(&self.secret_key.serialize());
 
         let output = if self.compressed {
             wif[33] = 0x01;
-            let sum = &checksum(&wif[0..34])[0..4];
-            wif[34..].copy_from_slice(sum);
-            wif.to_base58()
+            encode_check(&wif)
         } else {
-            let sum = &checksum(&wif[0..33])[0..4];
-            wif[33..37].copy_from_slice(sum);
-            wif[..37].to_base58()
+            encode_check(&wif[..33])
         };
 
         write!(f, "{}", output)
     }
 }
 
+impl<N: BitcoinNetwork> fmt::Debug for BitcoinPrivateKey<N> {
+    /// Formats the private key with the secret key redacted, so that logging it does not leak the key.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BitcoinPrivateKey")
+            .field("secret_key", &format_args!("<redacted>"))
+            .field("compressed", &self.compressed)
+            .finish()
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl<N: BitcoinNetwork> proptest::arbitrary::Arbitrary for BitcoinPrivateKey<N> {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    /// Returns a strategy over compressed and uncompressed private keys of any valid secret key scalar.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        (any::<[u8; 32]>(), any::<bool>())
+            .prop_filter_map("secret key scalar out of range", |(scalar, compressed)| {
+                let secret_key = parse_secp256k1_secret_key(&scalar).ok()?;
+                Some(Self::from_secp256k1_secret_key(&secret_key, compressed))
+            })
+            .boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -785,4 +917,396 @@ mod tests {
             });
         }
     }
+
+    mod new_random {
+        use super::*;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        type N = Mainnet;
+
+        #[test]
+        fn new_random() {
+            let private_key = BitcoinPrivateKey::<N>::new_random(&mut StdRng::seed_from_u64(1231275789u64));
+            let expected_private_key = BitcoinPrivateKey::<N>::new_random(&mut StdRng::seed_from_u64(1231275789u64));
+            assert_eq!(expected_private_key, private_key);
+            assert!(private_key.is_compressed());
+        }
+
+        #[test]
+        fn default() {
+            assert_ne!(BitcoinPrivateKey::<N>::default(), BitcoinPrivateKey::<N>::default());
+        }
+    }
+
+    mod compression {
+        use super::*;
+
+        type N = Mainnet;
+
+        // Example key from https://en.bitcoin.it/wiki/Wallet_import_format
+        const UNCOMPRESSED_WIF: &str = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ";
+        const COMPRESSED_WIF: &str = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617";
+        const UNCOMPRESSED_ADDRESS: &str = "1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S";
+        const COMPRESSED_ADDRESS: &str = "1LoVGDgRs9hTfTNJNuXKSpywcbdvwRXpmK";
+
+        #[test]
+        fn to_uncompressed() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(COMPRESSED_WIF)
+                .unwrap()
+                .to_uncompressed();
+            assert!(!private_key.is_compressed());
+            assert!(!private_key.to_public_key().is_compressed());
+            assert_eq!(UNCOMPRESSED_WIF, private_key.to_string());
+            let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(UNCOMPRESSED_ADDRESS, address.to_string());
+        }
+
+        #[test]
+        fn to_compressed() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(UNCOMPRESSED_WIF)
+                .unwrap()
+                .to_compressed();
+            assert!(private_key.is_compressed());
+            assert!(private_key.to_public_key().is_compressed());
+            assert_eq!(COMPRESSED_WIF, private_key.to_string());
+            let address = private_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(COMPRESSED_ADDRESS, address.to_string());
+        }
+
+        #[test]
+        fn from_str() {
+            let uncompressed = BitcoinPrivateKey::<N>::from_str(UNCOMPRESSED_WIF).unwrap();
+            let compressed = BitcoinPrivateKey::<N>::from_str(COMPRESSED_WIF).unwrap();
+            assert!(!uncompressed.is_compressed());
+            assert!(compressed.is_compressed());
+            assert_eq!(
+                uncompressed.to_secp256k1_secret_key(),
+                compressed.to_secp256k1_secret_key()
+            );
+        }
+    }
+
+    mod wif {
+        use super::*;
+
+        const SECRET_KEY: &str = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+
+        fn test_round_trip<N: BitcoinNetwork>(compressed: bool, expected_wif: &str) {
+            let secret_key = secp256k1::SecretKey::parse_slice(&hex::decode(SECRET_KEY).unwrap()).unwrap();
+            let private_key = BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, compressed);
+            assert_eq!(expected_wif, private_key.to_string());
+
+            let private_key = BitcoinPrivateKey::<N>::from_str(expected_wif).unwrap();
+            assert_eq!(compressed, private_key.is_compressed());
+            assert_eq!(secret_key, private_key.to_secp256k1_secret_key());
+        }
+
+        #[test]
+        fn round_trip() {
+            test_round_trip::<Mainnet>(false, "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ");
+            test_round_trip::<Mainnet>(true, "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617");
+            test_round_trip::<Testnet>(false, "91gGn1HgSap6CbU12F6z3pJri26xzp7Ay1VW6NHCoEayNXwRpu2");
+            test_round_trip::<Testnet>(true, "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx");
+        }
+
+        #[test]
+        fn invalid_network() {
+            let testnet = "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx";
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(testnet),
+                Err(PrivateKeyError::NetworkMismatch { ref expected, ref found }) if expected == "mainnet" && found == "testnet or regtest or litecoin_testnet"
+            ));
+
+            let mainnet = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617";
+            assert!(matches!(
+                BitcoinPrivateKey::<Testnet>::from_str(mainnet),
+                Err(PrivateKeyError::NetworkMismatch { ref expected, ref found }) if expected == "testnet" && found == "mainnet"
+            ));
+        }
+
+        #[test]
+        fn invalid_prefix() {
+            let wif = "L6Cyjtdfq1TimFfgG9qBEHQF2FmaL96H5ceTrip74S4eqFapbcDJ";
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(wif),
+                Err(PrivateKeyError::InvalidPrefix(_))
+            ));
+        }
+
+        #[test]
+        fn invalid_checksum() {
+            let wif = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98618";
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(wif),
+                Err(PrivateKeyError::InvalidChecksum(_, _))
+            ));
+        }
+
+        #[test]
+        fn invalid_length() {
+            let wif = encode_check(&[0x80; 32]);
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(&wif),
+                Err(PrivateKeyError::InvalidByteLength(36))
+            ));
+
+            let wif = encode_check(&[0x80; 35]);
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(&wif),
+                Err(PrivateKeyError::InvalidByteLength(39))
+            ));
+        }
+
+        #[test]
+        fn invalid_compression_flag() {
+            let wif = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvWxyf5d";
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(wif),
+                Err(PrivateKeyError::InvalidCompressionFlag(0x02))
+            ));
+        }
+
+        #[test]
+        fn invalid_scalar() {
+            // The secret key is zero
+            let wif = "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73Nd2Mcv1";
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(wif),
+                Err(PrivateKeyError::InvalidScalar)
+            ));
+
+            // The secret key is equal to the curve order
+            let wif = "L5oLkpV3aqBjhki6LmvChTCV6odsp4SXM6FfU2Gppt5kFqRzExJJ";
+            assert!(matches!(
+                BitcoinPrivateKey::<Mainnet>::from_str(wif),
+                Err(PrivateKeyError::InvalidScalar)
+            ));
+        }
+    }
+
+    mod from_hex {
+        use super::*;
+
+        type N = Mainnet;
+
+        const SECRET_KEY: &str = "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d";
+        const CURVE_ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        const CURVE_ORDER_MINUS_ONE: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+
+        #[test]
+        fn round_trip() {
+            let private_key = BitcoinPrivateKey::<N>::from_hex(SECRET_KEY, true).unwrap();
+            assert_eq!(
+                "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617",
+                private_key.to_string()
+            );
+            assert_eq!(SECRET_KEY, private_key.to_hex());
+
+            let private_key = BitcoinPrivateKey::<N>::from_hex(&SECRET_KEY.to_uppercase(), false).unwrap();
+            assert_eq!(
+                "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ",
+                private_key.to_string()
+            );
+            assert_eq!(SECRET_KEY, private_key.to_hex());
+        }
+
+        #[test]
+        fn curve_order() {
+            let private_key = BitcoinPrivateKey::<N>::from_hex(CURVE_ORDER_MINUS_ONE, true).unwrap();
+            assert_eq!(CURVE_ORDER_MINUS_ONE, private_key.to_hex());
+            assert!(matches!(
+                BitcoinPrivateKey::<N>::from_hex(CURVE_ORDER, true),
+                Err(PrivateKeyError::ScalarOutOfRange)
+            ));
+            assert!(matches!(
+                BitcoinPrivateKey::<N>::from_hex(&"ff".repeat(32), true),
+                Err(PrivateKeyError::ScalarOutOfRange)
+            ));
+            assert!(matches!(
+                BitcoinPrivateKey::<N>::from_hex(&"00".repeat(32), true),
+                Err(PrivateKeyError::ZeroScalar)
+            ));
+        }
+
+        #[test]
+        fn invalid_hex() {
+            assert!(matches!(
+                BitcoinPrivateKey::<N>::from_hex(&SECRET_KEY[..62], true),
+                Err(PrivateKeyError::InvalidCharacterLength(62))
+            ));
+            assert!(matches!(
+                BitcoinPrivateKey::<N>::from_hex(&format!("0x{}", SECRET_KEY), true),
+                Err(PrivateKeyError::InvalidCharacterLength(66))
+            ));
+            assert!(BitcoinPrivateKey::<N>::from_hex(&format!("{}zz", &SECRET_KEY[..62]), true).is_err());
+        }
+    }
+
+    mod sign {
+        use super::*;
+        use sha2::{Digest, Sha256};
+
+        type N = Mainnet;
+
+        // RFC6979 (HMAC-SHA256) deterministic signatures over the SHA-256 digest of the message,
+        // as (secret key, message, r || s, recovery id)
+        const TEST_VECTORS: [(&str, &str, &str, u8); 4] = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "Satoshi Nakamoto",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d82442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+                1,
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+                0,
+            ),
+            (
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "Satoshi Nakamoto",
+                "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d06b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5",
+                0,
+            ),
+            (
+                "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+                "Alan Turing",
+                "7063ae83e7f62bbb171798131b4a0564b956930092b33b07b395615d9ec7e15c58dfcc1e00a35e1572f366ffe34ba0fc47db1e7189759b9fb233c5b05ab388ea",
+                0,
+            ),
+        ];
+
+        fn private_key(secret_key: &str) -> BitcoinPrivateKey<N> {
+            let secret_key = secp256k1::SecretKey::parse_slice(&hex::decode(secret_key).unwrap()).unwrap();
+            BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, true)
+        }
+
+        #[test]
+        fn sign() {
+            TEST_VECTORS.iter().for_each(|(secret_key, message, signature, _)| {
+                let digest = Sha256::digest(message.as_bytes());
+                let private_key = private_key(secret_key);
+                assert_eq!(
+                    *signature,
+                    hex::encode(&private_key.sign(&digest).unwrap().serialize()[..])
+                );
+                assert_eq!(private_key.sign(&digest).unwrap(), private_key.sign(&digest).unwrap());
+            });
+        }
+
+        #[test]
+        fn sign_recoverable() {
+            TEST_VECTORS
+                .iter()
+                .for_each(|(secret_key, message, signature, recovery_id)| {
+                    let digest = Sha256::digest(message.as_bytes());
+                    let private_key = private_key(secret_key);
+                    let (recoverable, id) = private_key.sign_recoverable(&digest).unwrap();
+                    assert_eq!(*signature, hex::encode(&recoverable.serialize()[..]));
+                    assert_eq!(*recovery_id, id.serialize());
+
+                    let public_key =
+                        secp256k1::recover(&secp256k1::Message::parse_slice(&digest).unwrap(), &recoverable, &id)
+                            .unwrap();
+                    assert_eq!(private_key.to_public_key().to_secp256k1_public_key(), public_key);
+                });
+        }
+
+        #[test]
+        fn invalid_digest_length() {
+            let private_key = private_key(TEST_VECTORS[0].0);
+            assert!(private_key.sign(&[0u8; 31]).is_err());
+            assert!(private_key.sign_recoverable(&[0u8; 33]).is_err());
+        }
+    }
+
+    mod sign_message {
+        use super::*;
+
+        type N = Mainnet;
+
+        const PRIVATE_KEY: &str = "L4rK1yDtCWekvXuE6oXD9jCYfFNV2cWRpVuPLBcCU2z8TrisoyY1";
+        const MESSAGE: &str = "This is an example of a signed message.";
+        const SIGNATURE: &str =
+            "H9L5yLFjti0QTHhPyFrZCT1V/MMnBtXKmoiKDZ78NDBjERki6ZTQZdSMCtkgoNmp17By9ItJr8o7ChX0XxY91nk=";
+
+        #[test]
+        fn sign_message() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            assert_eq!(SIGNATURE, private_key.sign_message(MESSAGE));
+        }
+
+        #[test]
+        fn sign_message_header() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let compressed = base64::decode(private_key.sign_message(MESSAGE)).unwrap();
+            let uncompressed = base64::decode(private_key.to_uncompressed().sign_message(MESSAGE)).unwrap();
+            assert_eq!(compressed[0], uncompressed[0] + 4);
+            assert_eq!(compressed[1..], uncompressed[1..]);
+        }
+    }
+
+    mod debug {
+        use super::*;
+
+        type N = Mainnet;
+
+        const WIF: &str = "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617";
+
+        #[test]
+        fn redacted() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(WIF).unwrap();
+            let secret_key = hex::encode(private_key.to_secp256k1_secret_key().serialize());
+            let debug = format!("{:?}", private_key);
+            assert_eq!("BitcoinPrivateKey { secret_key: <redacted>, compressed: true }", debug);
+            assert!(!debug.contains(WIF));
+            assert!(!debug.contains(&secret_key));
+            assert_eq!(WIF, private_key.to_string());
+        }
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        proptest! {
+            #[test]
+            fn to_string_from_str_mainnet(private_key: BitcoinPrivateKey<Mainnet>) {
+                prop_assert_eq!(&private_key, &BitcoinPrivateKey::from_str(&private_key.to_string()).unwrap());
+            }
+
+            #[test]
+            fn to_string_from_str_testnet(private_key: BitcoinPrivateKey<Testnet>) {
+                prop_assert_eq!(&private_key, &BitcoinPrivateKey::from_str(&private_key.to_string()).unwrap());
+            }
+
+            #[test]
+            fn to_hex_from_hex(private_key: BitcoinPrivateKey<Mainnet>) {
+                let hex = private_key.to_hex();
+                let compressed = private_key.is_compressed();
+                prop_assert_eq!(&private_key, &BitcoinPrivateKey::from_hex(&hex, compressed).unwrap());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinPrivateKey<Mainnet>>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_base58(inp in "[1-9A-HJ-NP-Za-km-z]{0,64}") {
+                let _ = BitcoinPrivateKey::<Mainnet>::from_str(&inp);
+            }
+
+            #[test]
+            fn from_str_payload(prefix in prop_oneof![Just(0x80u8), any::<u8>()], payload in vec(any::<u8>(), 0..40)) {
+                let mut data = vec![prefix];
+                data.extend(payload);
+                if let Ok(private_key) = BitcoinPrivateKey::<Mainnet>::from_str(&encode_check(&data)) {
+                    prop_assert_eq!(encode_check(&data), private_key.to_string());
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,1487 @@
+//!
+//! Partially Signed Bitcoin Transactions
+//!
+//! This module implements the PSBT format of BIP174, which carries an unsigned transaction
+//! together with the data needed to sign it, so that a transaction built by wagyu can be
+//! signed elsewhere, e.g. on a hardware wallet, and finalized once all signatures are present.
+//! https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::transaction::{
+    create_script_pub_key, variable_length_integer, BitcoinTransaction, BitcoinTransactionOutput,
+    BitcoinTransactionParameters, Opcode, Outpoint, SignatureHash,
+};
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    crypto::{hash160, sha256d},
+    AddressError, ChildIndex, DerivationPath, DerivationPathError, ExtendedPrivateKey, ExtendedPrivateKeyError,
+    PrivateKey, PrivateKeyError, Transaction, TransactionError,
+};
+
+use core::{fmt, str::FromStr};
+
+/// The magic bytes that begin every PSBT, "psbt" followed by 0xff
+const PSBT_MAGIC: [u8; 5] = [0x70, 0x73, 0x62, 0x74, 0xff];
+
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+const PSBT_IN_WITNESS_SCRIPT: u8 = 0x05;
+const PSBT_IN_BIP32_DERIVATION: u8 = 0x06;
+const PSBT_IN_FINAL_SCRIPTSIG: u8 = 0x07;
+const PSBT_IN_FINAL_SCRIPTWITNESS: u8 = 0x08;
+
+const PSBT_OUT_REDEEM_SCRIPT: u8 = 0x00;
+const PSBT_OUT_WITNESS_SCRIPT: u8 = 0x01;
+const PSBT_OUT_BIP32_DERIVATION: u8 = 0x02;
+
+#[derive(Debug, Fail)]
+pub enum PsbtError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "{}", _0)]
+    DerivationPathError(DerivationPathError),
+
+    #[fail(display = "duplicate psbt key: {:?}", _0)]
+    DuplicateKey(Vec<u8>),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPrivateKeyError(ExtendedPrivateKeyError),
+
+    #[fail(display = "invalid psbt input index: {}", _0)]
+    InvalidInputIndex(usize),
+
+    #[fail(display = "invalid psbt key: {:?}", _0)]
+    InvalidKey(Vec<u8>),
+
+    #[fail(display = "invalid psbt magic bytes")]
+    InvalidMagic,
+
+    #[fail(display = "invalid psbt output index: {}", _0)]
+    InvalidOutputIndex(usize),
+
+    #[fail(display = "invalid redeem script for psbt input {}", _0)]
+    InvalidRedeemScript(usize),
+
+    #[fail(display = "invalid psbt sighash type: {}", _0)]
+    InvalidSighashType(u32),
+
+    #[fail(display = "invalid psbt value for key: {:?}", _0)]
+    InvalidValue(Vec<u8>),
+
+    #[fail(display = "psbts of different transactions cannot be combined")]
+    MismatchedTransaction,
+
+    #[fail(display = "missing signature for psbt input {}", _0)]
+    MissingSignature(usize),
+
+    #[fail(display = "missing psbt unsigned transaction")]
+    MissingUnsignedTransaction,
+
+    #[fail(display = "missing utxo for psbt input {}", _0)]
+    MissingUtxo(usize),
+
+    #[fail(display = "psbt input {} is not finalized", _0)]
+    NonFinalizedInput(usize),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(PrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(TransactionError),
+
+    #[fail(display = "unexpected end of psbt data")]
+    UnexpectedEndOfData,
+
+    #[fail(display = "unsupported script for psbt input {}", _0)]
+    UnsupportedScript(usize),
+}
+
+impl From<AddressError> for PsbtError {
+    fn from(error: AddressError) -> Self {
+        PsbtError::AddressError(error)
+    }
+}
+
+impl From<DerivationPathError> for PsbtError {
+    fn from(error: DerivationPathError) -> Self {
+        PsbtError::DerivationPathError(error)
+    }
+}
+
+impl From<ExtendedPrivateKeyError> for PsbtError {
+    fn from(error: ExtendedPrivateKeyError) -> Self {
+        PsbtError::ExtendedPrivateKeyError(error)
+    }
+}
+
+impl From<PrivateKeyError> for PsbtError {
+    fn from(error: PrivateKeyError) -> Self {
+        PsbtError::PrivateKeyError(error)
+    }
+}
+
+impl From<TransactionError> for PsbtError {
+    fn from(error: TransactionError) -> Self {
+        PsbtError::TransactionError(error)
+    }
+}
+
+impl From<base64::DecodeError> for PsbtError {
+    fn from(error: base64::DecodeError) -> Self {
+        PsbtError::Crate("base64", format!("{:?}", error))
+    }
+}
+
+/// Represents the origin of a public key: the fingerprint of the master key and the derivation path
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KeySource {
+    /// The fingerprint of the master extended key
+    pub fingerprint: [u8; 4],
+    /// The derivation path from the master extended key
+    pub path: Vec<ChildIndex>,
+}
+
+impl KeySource {
+    /// Returns the serialized key source.
+    fn serialize(&self) -> Vec<u8> {
+        let mut source = self.fingerprint.to_vec();
+        self.path
+            .iter()
+            .for_each(|index| source.extend(&u32::from(*index).to_le_bytes()));
+        source
+    }
+
+    /// Returns the key source of the given serialized key source.
+    fn read(key: &[u8], value: &[u8]) -> Result<Self, PsbtError> {
+        if value.len() < 4 || !value.len().is_multiple_of(4) {
+            return Err(PsbtError::InvalidValue(key.to_vec()));
+        }
+
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&value[..4]);
+        let path = value[4..]
+            .chunks(4)
+            .map(|index| ChildIndex::from(u32::from_le_bytes([index[0], index[1], index[2], index[3]])))
+            .collect();
+
+        Ok(Self { fingerprint, path })
+    }
+}
+
+/// Represents the signing data of a PSBT input
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsbtInput {
+    /// The full transaction of the spent output, for non-SegWit inputs
+    pub non_witness_utxo: Option<Vec<u8>>,
+    /// The spent output, for SegWit inputs
+    pub witness_utxo: Option<BitcoinTransactionOutput>,
+    /// The signatures of the input, keyed by their public keys
+    pub partial_signatures: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// The signature hash type to sign the input with
+    pub sighash_type: Option<u32>,
+    /// The redeem script of a P2SH input
+    pub redeem_script: Option<Vec<u8>>,
+    /// The witness script of a P2WSH input
+    pub witness_script: Option<Vec<u8>>,
+    /// The key sources of the public keys of the input
+    pub bip32_derivations: BTreeMap<Vec<u8>, KeySource>,
+    /// The finalized script signature of the input
+    pub final_script_sig: Option<Vec<u8>>,
+    /// The finalized witness stack of the input
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+    /// The unknown key-value pairs of the input
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl PsbtInput {
+    /// Returns `true` if the input has a finalized script signature or witness.
+    pub fn is_finalized(&self) -> bool {
+        self.final_script_sig.is_some() || self.final_script_witness.is_some()
+    }
+
+    /// Returns the serialized input map.
+    fn serialize(&self) -> Result<Vec<u8>, PsbtError> {
+        let mut map = vec![];
+        if let Some(transaction) = &self.non_witness_utxo {
+            write_pair(&mut map, &[PSBT_IN_NON_WITNESS_UTXO], transaction)?;
+        }
+        if let Some(output) = &self.witness_utxo {
+            write_pair(&mut map, &[PSBT_IN_WITNESS_UTXO], &output.serialize()?)?;
+        }
+        for (public_key, signature) in &self.partial_signatures {
+            write_pair(&mut map, &[&[PSBT_IN_PARTIAL_SIG], &public_key[..]].concat(), signature)?;
+        }
+        if let Some(sighash_type) = self.sighash_type {
+            write_pair(&mut map, &[PSBT_IN_SIGHASH_TYPE], &sighash_type.to_le_bytes())?;
+        }
+        if let Some(script) = &self.redeem_script {
+            write_pair(&mut map, &[PSBT_IN_REDEEM_SCRIPT], script)?;
+        }
+        if let Some(script) = &self.witness_script {
+            write_pair(&mut map, &[PSBT_IN_WITNESS_SCRIPT], script)?;
+        }
+        for (public_key, source) in &self.bip32_derivations {
+            let key = [&[PSBT_IN_BIP32_DERIVATION], &public_key[..]].concat();
+            write_pair(&mut map, &key, &source.serialize())?;
+        }
+        if let Some(script) = &self.final_script_sig {
+            write_pair(&mut map, &[PSBT_IN_FINAL_SCRIPTSIG], script)?;
+        }
+        if let Some(witness) = &self.final_script_witness {
+            let mut value = variable_length_integer(witness.len() as u64)?;
+            for item in witness {
+                value.extend(variable_length_integer(item.len() as u64)?);
+                value.extend(item);
+            }
+            write_pair(&mut map, &[PSBT_IN_FINAL_SCRIPTWITNESS], &value)?;
+        }
+        for (key, value) in &self.unknown {
+            write_pair(&mut map, key, value)?;
+        }
+        map.push(0x00);
+        Ok(map)
+    }
+
+    /// Returns the input of the given serialized input map.
+    fn read(data: &mut &[u8]) -> Result<Self, PsbtError> {
+        let mut input = Self::default();
+        for (key, value) in read_map(data)? {
+            match (key[0], key.len()) {
+                (PSBT_IN_NON_WITNESS_UTXO, 1) => input.non_witness_utxo = Some(value),
+                (PSBT_IN_WITNESS_UTXO, 1) => input.witness_utxo = Some(read_output(&key, &value)?),
+                (PSBT_IN_PARTIAL_SIG, 34) | (PSBT_IN_PARTIAL_SIG, 66) => {
+                    input.partial_signatures.insert(key[1..].to_vec(), value);
+                }
+                (PSBT_IN_SIGHASH_TYPE, 1) => match value.len() {
+                    4 => input.sighash_type = Some(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+                    _ => return Err(PsbtError::InvalidValue(key)),
+                },
+                (PSBT_IN_REDEEM_SCRIPT, 1) => input.redeem_script = Some(value),
+                (PSBT_IN_WITNESS_SCRIPT, 1) => input.witness_script = Some(value),
+                (PSBT_IN_BIP32_DERIVATION, 34) | (PSBT_IN_BIP32_DERIVATION, 66) => {
+                    let source = KeySource::read(&key, &value)?;
+                    input.bip32_derivations.insert(key[1..].to_vec(), source);
+                }
+                (PSBT_IN_FINAL_SCRIPTSIG, 1) => input.final_script_sig = Some(value),
+                (PSBT_IN_FINAL_SCRIPTWITNESS, 1) => {
+                    let mut witness = &value[..];
+                    let items = (0..read_compact_size(&mut witness)?)
+                        .map(|_| {
+                            let length = read_compact_size(&mut witness)?;
+                            Ok(read_bytes(&mut witness, length)?.to_vec())
+                        })
+                        .collect::<Result<Vec<Vec<u8>>, PsbtError>>()?;
+                    if !witness.is_empty() {
+                        return Err(PsbtError::InvalidValue(key));
+                    }
+                    input.final_script_witness = Some(items);
+                }
+                (key_type, _) if key_type <= PSBT_IN_FINAL_SCRIPTWITNESS => return Err(PsbtError::InvalidKey(key)),
+                _ => {
+                    input.unknown.insert(key, value);
+                }
+            }
+        }
+        Ok(input)
+    }
+
+    /// Merges the fields of the given input that are missing from this input.
+    fn combine(&mut self, other: &Self) {
+        fn or<T: Clone>(value: &mut Option<T>, other: &Option<T>) {
+            if value.is_none() {
+                *value = other.clone();
+            }
+        }
+
+        or(&mut self.non_witness_utxo, &other.non_witness_utxo);
+        or(&mut self.witness_utxo, &other.witness_utxo);
+        or(&mut self.sighash_type, &other.sighash_type);
+        or(&mut self.redeem_script, &other.redeem_script);
+        or(&mut self.witness_script, &other.witness_script);
+        or(&mut self.final_script_sig, &other.final_script_sig);
+        or(&mut self.final_script_witness, &other.final_script_witness);
+        merge(&mut self.partial_signatures, &other.partial_signatures);
+        merge(&mut self.bip32_derivations, &other.bip32_derivations);
+        merge(&mut self.unknown, &other.unknown);
+    }
+}
+
+/// Represents the metadata of a PSBT output
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsbtOutput {
+    /// The redeem script of a P2SH output
+    pub redeem_script: Option<Vec<u8>>,
+    /// The witness script of a P2WSH output
+    pub witness_script: Option<Vec<u8>>,
+    /// The key sources of the public keys of the output
+    pub bip32_derivations: BTreeMap<Vec<u8>, KeySource>,
+    /// The unknown key-value pairs of the output
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl PsbtOutput {
+    /// Returns the serialized output map.
+    fn serialize(&self) -> Result<Vec<u8>, PsbtError> {
+        let mut map = vec![];
+        if let Some(script) = &self.redeem_script {
+            write_pair(&mut map, &[PSBT_OUT_REDEEM_SCRIPT], script)?;
+        }
+        if let Some(script) = &self.witness_script {
+            write_pair(&mut map, &[PSBT_OUT_WITNESS_SCRIPT], script)?;
+        }
+        for (public_key, source) in &self.bip32_derivations {
+            let key = [&[PSBT_OUT_BIP32_DERIVATION], &public_key[..]].concat();
+            write_pair(&mut map, &key, &source.serialize())?;
+        }
+        for (key, value) in &self.unknown {
+            write_pair(&mut map, key, value)?;
+        }
+        map.push(0x00);
+        Ok(map)
+    }
+
+    /// Returns the output of the given serialized output map.
+    fn read(data: &mut &[u8]) -> Result<Self, PsbtError> {
+        let mut output = Self::default();
+        for (key, value) in read_map(data)? {
+            match (key[0], key.len()) {
+                (PSBT_OUT_REDEEM_SCRIPT, 1) => output.redeem_script = Some(value),
+                (PSBT_OUT_WITNESS_SCRIPT, 1) => output.witness_script = Some(value),
+                (PSBT_OUT_BIP32_DERIVATION, 34) | (PSBT_OUT_BIP32_DERIVATION, 66) => {
+                    let source = KeySource::read(&key, &value)?;
+                    output.bip32_derivations.insert(key[1..].to_vec(), source);
+                }
+                (key_type, _) if key_type <= PSBT_OUT_BIP32_DERIVATION => return Err(PsbtError::InvalidKey(key)),
+                _ => {
+                    output.unknown.insert(key, value);
+                }
+            }
+        }
+        Ok(output)
+    }
+
+    /// Merges the fields of the given output that are missing from this output.
+    fn combine(&mut self, other: &Self) {
+        if self.redeem_script.is_none() {
+            self.redeem_script = other.redeem_script.clone();
+        }
+        if self.witness_script.is_none() {
+            self.witness_script = other.witness_script.clone();
+        }
+        merge(&mut self.bip32_derivations, &other.bip32_derivations);
+        merge(&mut self.unknown, &other.unknown);
+    }
+}
+
+/// Represents a partially signed Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinPsbt<N: BitcoinNetwork> {
+    /// The unsigned transaction
+    unsigned_transaction: BitcoinTransactionParameters<N>,
+    /// The unknown global key-value pairs, such as extended public keys and the version
+    unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// The inputs, in the order of the unsigned transaction inputs
+    inputs: Vec<PsbtInput>,
+    /// The outputs, in the order of the unsigned transaction outputs
+    outputs: Vec<PsbtOutput>,
+}
+
+impl<N: BitcoinNetwork> BitcoinPsbt<N> {
+    /// Returns a PSBT of the given unsigned transaction parameters, with the spent output
+    /// and scripts of each SegWit input taken from its outpoint.
+    pub fn new(parameters: &BitcoinTransactionParameters<N>) -> Result<Self, PsbtError> {
+        let mut unsigned_transaction = parameters.clone();
+        unsigned_transaction.segwit_flag = false;
+
+        let mut inputs = vec![];
+        for input in unsigned_transaction.inputs.iter_mut() {
+            let outpoint = &input.outpoint;
+            let mut psbt_input = PsbtInput::default();
+
+            if let (Some(address), Some(amount), Some(script_pub_key)) =
+                (&outpoint.address, &outpoint.amount, &outpoint.script_pub_key)
+            {
+                let witness_utxo = BitcoinTransactionOutput {
+                    amount: *amount,
+                    script_pub_key: script_pub_key.clone(),
+                };
+                match address.format() {
+                    BitcoinFormat::P2PKH => {}
+                    BitcoinFormat::P2SH_P2WPKH => {
+                        psbt_input.witness_utxo = Some(witness_utxo);
+                        psbt_input.redeem_script = outpoint.redeem_script.clone();
+                    }
+                    BitcoinFormat::P2WSH => {
+                        psbt_input.witness_utxo = Some(witness_utxo);
+                        psbt_input.witness_script = outpoint.redeem_script.clone();
+                    }
+                    BitcoinFormat::Bech32 | BitcoinFormat::P2TR => psbt_input.witness_utxo = Some(witness_utxo),
+                }
+            }
+            if input.sighash_code != SignatureHash::SIGHASH_ALL {
+                psbt_input.sighash_type = Some(input.sighash_code as u32);
+            }
+            inputs.push(psbt_input);
+
+            // The unsigned transaction carries only what its serialization does.
+            input.outpoint = Outpoint::<N>::new(
+                input.outpoint.reverse_transaction_id.clone(),
+                input.outpoint.index,
+                None,
+                None,
+                None,
+                None,
+            )?;
+            input.script_sig = vec![];
+            input.sighash_code = SignatureHash::SIGHASH_ALL;
+            input.witnesses = vec![];
+            input.is_signed = false;
+        }
+
+        let outputs = vec![PsbtOutput::default(); unsigned_transaction.outputs.len()];
+        Ok(Self {
+            unsigned_transaction,
+            unknown: BTreeMap::new(),
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the PSBT of the given serialized PSBT.
+    pub fn from_bytes(psbt: &[u8]) -> Result<Self, PsbtError> {
+        if psbt.len() < PSBT_MAGIC.len() || psbt[..PSBT_MAGIC.len()] != PSBT_MAGIC {
+            return Err(PsbtError::InvalidMagic);
+        }
+        let mut data = &psbt[PSBT_MAGIC.len()..];
+
+        let mut unsigned_transaction = None;
+        let mut unknown = BTreeMap::new();
+        for (key, value) in read_map(&mut data)? {
+            match (key[0], key.len()) {
+                (PSBT_GLOBAL_UNSIGNED_TX, 1) => {
+                    let transaction = BitcoinTransactionParameters::<N>::read(&value[..])?;
+                    let is_unsigned = transaction
+                        .inputs
+                        .iter()
+                        .all(|input| input.script_sig.is_empty() && input.witnesses.is_empty());
+                    if transaction.segwit_flag || !is_unsigned {
+                        return Err(PsbtError::InvalidValue(key));
+                    }
+                    unsigned_transaction = Some(transaction);
+                }
+                (PSBT_GLOBAL_UNSIGNED_TX, _) => return Err(PsbtError::InvalidKey(key)),
+                _ => {
+                    unknown.insert(key, value);
+                }
+            }
+        }
+
+        let unsigned_transaction = unsigned_transaction.ok_or(PsbtError::MissingUnsignedTransaction)?;
+        let inputs = (0..unsigned_transaction.inputs.len())
+            .map(|_| PsbtInput::read(&mut data))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = (0..unsigned_transaction.outputs.len())
+            .map(|_| PsbtOutput::read(&mut data))
+            .collect::<Result<Vec<_>, _>>()?;
+        if !data.is_empty() {
+            return Err(PsbtError::InvalidValue(data.to_vec()));
+        }
+
+        Ok(Self {
+            unsigned_transaction,
+            unknown,
+            inputs,
+            outputs,
+        })
+    }
+
+    /// Returns the serialized PSBT.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, PsbtError> {
+        let mut psbt = PSBT_MAGIC.to_vec();
+        write_pair(
+            &mut psbt,
+            &[PSBT_GLOBAL_UNSIGNED_TX],
+            &self.unsigned_transaction().to_transaction_bytes()?,
+        )?;
+        for (key, value) in &self.unknown {
+            write_pair(&mut psbt, key, value)?;
+        }
+        psbt.push(0x00);
+
+        for input in &self.inputs {
+            psbt.extend(input.serialize()?);
+        }
+        for output in &self.outputs {
+            psbt.extend(output.serialize()?);
+        }
+        Ok(psbt)
+    }
+
+    /// Returns the unsigned transaction of the PSBT.
+    pub fn unsigned_transaction(&self) -> BitcoinTransaction<N> {
+        // Constructing a transaction from its parameters does not fail.
+        BitcoinTransaction::<N>::new(&self.unsigned_transaction).unwrap()
+    }
+
+    /// Returns the inputs of the PSBT.
+    pub fn inputs(&self) -> &[PsbtInput] {
+        &self.inputs
+    }
+
+    /// Returns the outputs of the PSBT.
+    pub fn outputs(&self) -> &[PsbtOutput] {
+        &self.outputs
+    }
+
+    /// Sets the full transaction of the output spent by the given non-SegWit input.
+    pub fn set_non_witness_utxo(&mut self, vin: usize, transaction: &BitcoinTransaction<N>) -> Result<(), PsbtError> {
+        let outpoint = self.input_outpoint(vin)?;
+        let transaction_id = sha256d(&transaction.to_transaction_bytes_without_witness()?);
+        if transaction_id[..] != outpoint.reverse_transaction_id[..] {
+            return Err(PsbtError::InvalidValue(vec![PSBT_IN_NON_WITNESS_UTXO]));
+        }
+
+        self.inputs[vin].non_witness_utxo = Some(transaction.to_transaction_bytes()?);
+        Ok(())
+    }
+
+    /// Adds the key source of the public key that the given master extended private key
+    /// derives at the given path to the given input.
+    pub fn add_input_derivation(
+        &mut self,
+        vin: usize,
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        path: &BitcoinDerivationPath<N>,
+    ) -> Result<(), PsbtError> {
+        let (public_key, source) = key_source(master_extended_private_key, path)?;
+        match self.inputs.get_mut(vin) {
+            Some(input) => input.bip32_derivations.insert(public_key, source),
+            None => return Err(PsbtError::InvalidInputIndex(vin)),
+        };
+        Ok(())
+    }
+
+    /// Adds the key source of the public key that the given master extended private key
+    /// derives at the given path to the given output.
+    pub fn add_output_derivation(
+        &mut self,
+        vout: usize,
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        path: &BitcoinDerivationPath<N>,
+    ) -> Result<(), PsbtError> {
+        let (public_key, source) = key_source(master_extended_private_key, path)?;
+        match self.outputs.get_mut(vout) {
+            Some(output) => output.bip32_derivations.insert(public_key, source),
+            None => return Err(PsbtError::InvalidOutputIndex(vout)),
+        };
+        Ok(())
+    }
+
+    /// Returns the PSBT with a partial signature of the given private key added to every
+    /// P2PKH, P2SH-P2WPKH, and P2WPKH input that spends from an address of the key.
+    pub fn sign(&self, private_key: &BitcoinPrivateKey<N>) -> Result<Self, PsbtError> {
+        let mut psbt = self.clone();
+        let mut has_signer_input = false;
+
+        for vin in 0..self.inputs.len() {
+            let input = &self.inputs[vin];
+            if input.is_finalized() {
+                continue;
+            }
+
+            // Inputs whose spent output is unknown cannot be signed.
+            let (amount, script_pub_key) = match self.input_utxo(vin) {
+                Ok(utxo) => utxo,
+                Err(PsbtError::MissingUtxo(_)) => continue,
+                Err(error) => return Err(error),
+            };
+            let format = [BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32]
+                .iter()
+                .map(|format| Ok((format.clone(), private_key.to_address(format)?)))
+                .collect::<Result<Vec<(BitcoinFormat, BitcoinAddress<N>)>, AddressError>>()?
+                .into_iter()
+                .find(|(_, address)| match create_script_pub_key::<N>(address) {
+                    Ok(script) => script == script_pub_key,
+                    _ => false,
+                });
+            let (format, address) = match format {
+                Some(format) => format,
+                None => continue,
+            };
+            has_signer_input = true;
+
+            let public_key = private_key.to_public_key();
+            let public_key = match (&format, public_key.is_compressed()) {
+                (BitcoinFormat::P2PKH, false) => public_key.to_secp256k1_public_key().serialize().to_vec(),
+                _ => public_key.to_secp256k1_public_key().serialize_compressed().to_vec(),
+            };
+            let redeem_script = match format {
+                BitcoinFormat::P2SH_P2WPKH => Some([vec![0x00, 0x14], hash160(&public_key).to_vec()].concat()),
+                _ => None,
+            };
+
+            // Sign a copy of the transaction that carries the spent output of the input.
+            let sighash = signature_hash(input.sighash_type.unwrap_or(SignatureHash::SIGHASH_ALL as u32))?;
+            let mut parameters = self.unsigned_transaction.clone();
+            parameters.inputs[vin].outpoint = Outpoint::<N>::new(
+                parameters.inputs[vin].outpoint.reverse_transaction_id.clone(),
+                parameters.inputs[vin].outpoint.index,
+                Some(address),
+                Some(amount),
+                redeem_script.clone(),
+                Some(script_pub_key),
+            )?;
+            let transaction = BitcoinTransaction::<N>::new(&parameters)?;
+            let transaction_hash = match format {
+                BitcoinFormat::P2PKH => transaction.p2pkh_hash(vin, sighash)?,
+                _ => sha256d(&transaction.segwit_hash_preimage(vin, sighash)?),
+            };
+
+            let signature = private_key.sign(&transaction_hash)?;
+            let mut signature = signature.serialize_der().as_ref().to_vec();
+            signature.push((sighash as u32).to_le_bytes()[0]);
+
+            let psbt_input = &mut psbt.inputs[vin];
+            psbt_input.partial_signatures.insert(public_key, signature);
+            if psbt_input.redeem_script.is_none() {
+                psbt_input.redeem_script = redeem_script;
+            }
+        }
+
+        if !has_signer_input {
+            return Err(TransactionError::MissingSignerInput.into());
+        }
+        Ok(psbt)
+    }
+
+    /// Returns the PSBT with the signatures and metadata of the given PSBT of the same
+    /// unsigned transaction merged in.
+    pub fn combine(&self, other: &Self) -> Result<Self, PsbtError> {
+        if self.unsigned_transaction().to_transaction_bytes()? != other.unsigned_transaction().to_transaction_bytes()? {
+            return Err(PsbtError::MismatchedTransaction);
+        }
+
+        let mut psbt = self.clone();
+        merge(&mut psbt.unknown, &other.unknown);
+        psbt.inputs
+            .iter_mut()
+            .zip(other.inputs.iter())
+            .for_each(|(input, other)| input.combine(other));
+        psbt.outputs
+            .iter_mut()
+            .zip(other.outputs.iter())
+            .for_each(|(output, other)| output.combine(other));
+        Ok(psbt)
+    }
+
+    /// Returns the PSBT with the script signature and witness of every P2PKH, P2SH-P2WPKH,
+    /// and P2WPKH input built from its partial signature.
+    pub fn finalize(&self) -> Result<Self, PsbtError> {
+        let mut psbt = self.clone();
+        for vin in 0..self.inputs.len() {
+            let input = &self.inputs[vin];
+            if input.is_finalized() {
+                continue;
+            }
+
+            let (_, script_pub_key) = self.input_utxo(vin)?;
+            // Returns the partial signature of the public key with the given hash.
+            let signature_of = |public_key_hash: &[u8]| {
+                input
+                    .partial_signatures
+                    .iter()
+                    .find(|(public_key, _)| hash160(public_key)[..] == *public_key_hash)
+                    .ok_or(PsbtError::MissingSignature(vin))
+            };
+
+            let psbt_input = &mut psbt.inputs[vin];
+            match script_pub_key.len() {
+                // P2WPKH: 0 <20-byte public key hash>
+                22 if script_pub_key[0] == 0x00 && script_pub_key[1] == 0x14 => {
+                    let (public_key, signature) = signature_of(&script_pub_key[2..])?;
+                    psbt_input.final_script_witness = Some(vec![signature.clone(), public_key.clone()]);
+                }
+                // P2SH: OP_HASH160 <20-byte script hash> OP_EQUAL
+                23 if script_pub_key[0] == Opcode::OP_HASH160 as u8 && script_pub_key[22] == Opcode::OP_EQUAL as u8 => {
+                    let redeem_script = match &input.redeem_script {
+                        Some(script) if script.len() == 22 && script[0] == 0x00 && script[1] == 0x14 => script,
+                        _ => return Err(PsbtError::UnsupportedScript(vin)),
+                    };
+                    if script_pub_key[2..22] != hash160(redeem_script)[..] {
+                        return Err(PsbtError::InvalidRedeemScript(vin));
+                    }
+                    let (public_key, signature) = signature_of(&redeem_script[2..])?;
+                    psbt_input.final_script_sig = Some(push_data(redeem_script));
+                    psbt_input.final_script_witness = Some(vec![signature.clone(), public_key.clone()]);
+                }
+                // P2PKH: OP_DUP OP_HASH160 <20-byte public key hash> OP_EQUALVERIFY OP_CHECKSIG
+                25 if script_pub_key[0] == Opcode::OP_DUP as u8
+                    && script_pub_key[1] == Opcode::OP_HASH160 as u8
+                    && script_pub_key[23] == Opcode::OP_EQUALVERIFY as u8
+                    && script_pub_key[24] == Opcode::OP_CHECKSIG as u8 =>
+                {
+                    let (public_key, signature) = signature_of(&script_pub_key[3..23])?;
+                    psbt_input.final_script_sig = Some([push_data(signature), push_data(public_key)].concat());
+                }
+                _ => return Err(PsbtError::UnsupportedScript(vin)),
+            };
+
+            // The finalizer clears the data that is no longer needed.
+            psbt_input.partial_signatures.clear();
+            psbt_input.sighash_type = None;
+            psbt_input.redeem_script = None;
+            psbt_input.witness_script = None;
+            psbt_input.bip32_derivations.clear();
+        }
+        Ok(psbt)
+    }
+
+    /// Returns the signed transaction of a PSBT whose inputs are all finalized.
+    pub fn to_transaction(&self) -> Result<BitcoinTransaction<N>, PsbtError> {
+        let mut parameters = self.unsigned_transaction.clone();
+        for (vin, input) in self.inputs.iter().enumerate() {
+            if !input.is_finalized() {
+                return Err(PsbtError::NonFinalizedInput(vin));
+            }
+
+            let transaction_input = &mut parameters.inputs[vin];
+            transaction_input.script_sig = input.final_script_sig.clone().unwrap_or_default();
+            transaction_input.witnesses = match &input.final_script_witness {
+                Some(witness) => witness
+                    .iter()
+                    .map(|item| Ok([variable_length_integer(item.len() as u64)?, item.clone()].concat()))
+                    .collect::<Result<Vec<Vec<u8>>, TransactionError>>()?,
+                None => vec![],
+            };
+            transaction_input.is_signed = true;
+            parameters.segwit_flag |= !transaction_input.witnesses.is_empty();
+        }
+        Ok(BitcoinTransaction::<N>::new(&parameters)?)
+    }
+
+    /// Returns the outpoint of the given input.
+    fn input_outpoint(&self, vin: usize) -> Result<Outpoint<N>, PsbtError> {
+        match self.unsigned_transaction.inputs.get(vin) {
+            Some(input) => Ok(input.outpoint.clone()),
+            None => Err(PsbtError::InvalidInputIndex(vin)),
+        }
+    }
+
+    /// Returns the amount and script public key of the output spent by the given input.
+    fn input_utxo(&self, vin: usize) -> Result<(BitcoinAmount, Vec<u8>), PsbtError> {
+        let input = &self.inputs[vin];
+        if let Some(output) = &input.witness_utxo {
+            return Ok((output.amount, output.script_pub_key.clone()));
+        }
+
+        let transaction = match &input.non_witness_utxo {
+            Some(transaction) => BitcoinTransactionParameters::<N>::read(&transaction[..])?,
+            None => return Err(PsbtError::MissingUtxo(vin)),
+        };
+        let index = self.input_outpoint(vin)?.index as usize;
+        match transaction.outputs.get(index) {
+            Some(output) => Ok((output.amount, output.script_pub_key.clone())),
+            None => Err(PsbtError::MissingUtxo(vin)),
+        }
+    }
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinPsbt<N> {
+    type Err = PsbtError;
+
+    fn from_str(psbt: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(&base64::decode(psbt)?)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinPsbt<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_bytes() {
+            Ok(psbt) => write!(f, "{}", base64::encode(&psbt)),
+            _ => Err(fmt::Error),
+        }
+    }
+}
+
+/// Returns the compressed public key and key source that the given master extended private key derives at the given path.
+fn key_source<N: BitcoinNetwork>(
+    master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+    path: &BitcoinDerivationPath<N>,
+) -> Result<(Vec<u8>, KeySource), PsbtError> {
+    let extended_private_key = master_extended_private_key.derive(path)?;
+    let public_key = extended_private_key
+        .to_public_key()
+        .to_secp256k1_public_key()
+        .serialize_compressed()
+        .to_vec();
+    let source = KeySource {
+        fingerprint: master_extended_private_key.fingerprint(),
+        path: path.to_vec()?,
+    };
+    Ok((public_key, source))
+}
+
+/// Returns the signature hash of the given PSBT sighash type.
+fn signature_hash(sighash_type: u32) -> Result<SignatureHash, PsbtError> {
+    [
+        SignatureHash::SIGHASH_ALL,
+        SignatureHash::SIGHASH_NONE,
+        SignatureHash::SIGHASH_SINGLE,
+        SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY,
+        SignatureHash::SIGHASH_NONE_SIGHASH_ANYONECANPAY,
+        SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
+    ]
+    .iter()
+    .find(|sighash| **sighash as u32 == sighash_type)
+    .copied()
+    .ok_or(PsbtError::InvalidSighashType(sighash_type))
+}
+
+/// Returns the script that pushes the given data of at most 75 bytes onto the stack.
+fn push_data(data: &[u8]) -> Vec<u8> {
+    [&[data.len() as u8], data].concat()
+}
+
+/// Inserts the entries of the given map that are missing from the map.
+fn merge<V: Clone>(map: &mut BTreeMap<Vec<u8>, V>, other: &BTreeMap<Vec<u8>, V>) {
+    for (key, value) in other {
+        map.entry(key.clone()).or_insert_with(|| value.clone());
+    }
+}
+
+/// Returns the transaction output of the given serialized witness utxo.
+fn read_output(key: &[u8], value: &[u8]) -> Result<BitcoinTransactionOutput, PsbtError> {
+    let mut data = value;
+    let output = BitcoinTransactionOutput::read(&mut data)?;
+    match data.is_empty() && output.serialize()? == value {
+        true => Ok(output),
+        false => Err(PsbtError::InvalidValue(key.to_vec())),
+    }
+}
+
+/// Appends the given key-value pair to the given map.
+fn write_pair(map: &mut Vec<u8>, key: &[u8], value: &[u8]) -> Result<(), PsbtError> {
+    map.extend(variable_length_integer(key.len() as u64)?);
+    map.extend(key);
+    map.extend(variable_length_integer(value.len() as u64)?);
+    map.extend(value);
+    Ok(())
+}
+
+/// Represents a serialized key-value pair of a PSBT map
+type Pair = (Vec<u8>, Vec<u8>);
+
+/// Returns the key-value pairs of the map at the start of the given data, in order.
+fn read_map(data: &mut &[u8]) -> Result<Vec<Pair>, PsbtError> {
+    let mut pairs: Vec<Pair> = vec![];
+    loop {
+        let key_length = read_compact_size(data)?;
+        if key_length == 0 {
+            return Ok(pairs);
+        }
+        let key = read_bytes(data, key_length)?.to_vec();
+        let value_length = read_compact_size(data)?;
+        let value = read_bytes(data, value_length)?.to_vec();
+
+        if pairs.iter().any(|(k, _)| *k == key) {
+            return Err(PsbtError::DuplicateKey(key));
+        }
+        pairs.push((key, value));
+    }
+}
+
+/// Returns the given number of bytes from the start of the given data.
+fn read_bytes<'a>(data: &mut &'a [u8], length: usize) -> Result<&'a [u8], PsbtError> {
+    if data.len() < length {
+        return Err(PsbtError::UnexpectedEndOfData);
+    }
+    let (bytes, rest) = data.split_at(length);
+    *data = rest;
+    Ok(bytes)
+}
+
+/// Returns the variable length integer at the start of the given data.
+fn read_compact_size(data: &mut &[u8]) -> Result<usize, PsbtError> {
+    let flag = read_bytes(data, 1)?[0];
+    let length = match flag {
+        0xfd => 2,
+        0xfe => 4,
+        0xff => 8,
+        _ => return Ok(flag as usize),
+    };
+
+    let mut bytes = [0u8; 8];
+    bytes[..length].copy_from_slice(read_bytes(data, length)?);
+    let value = u64::from_le_bytes(bytes);
+
+    // Values must be encoded in the fewest bytes, as in `variable_length_integer`.
+    let minimum = match length {
+        2 => 0xfd,
+        4 => 0x10000,
+        _ => 0x100000000,
+    };
+    match value >= minimum {
+        true => Ok(value as usize),
+        false => Err(TransactionError::InvalidVariableSizeInteger(value as usize).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::transaction::BitcoinTransactionInput;
+
+    type N = Mainnet;
+
+    const UNSIGNED_PSBT: &str = "cHNidP8BAKABAAAAAv/394gagJmvppQNQtHn9jYr7DgXHqPt9DNUHbTkrZafAAAAAADu////71HhuATMidGC0nllXDqonoFbGzCf4ofZsrVdV7kOxooBAAAAAP////8CICyyBgAAAAAZdqkUgoCzffN425n2b4XJWng6dqx6bVmIrJCTUQ0AAAAAGXapFDveQtvufk2+aiGy1Qzi8BZ/qoFZiKwRAAAAAAABAR8ARsMjAAAAABYAFB0PFyoOy0iu4b4fJofSljrjP3GhAAAA";
+    const SIGNED_PSBT: &str = "cHNidP8BAKABAAAAAv/394gagJmvppQNQtHn9jYr7DgXHqPt9DNUHbTkrZafAAAAAADu////71HhuATMidGC0nllXDqonoFbGzCf4ofZsrVdV7kOxooBAAAAAP////8CICyyBgAAAAAZdqkUgoCzffN425n2b4XJWng6dqx6bVmIrJCTUQ0AAAAAGXapFDveQtvufk2+aiGy1Qzi8BZ/qoFZiKwRAAAAAAABAR8ARsMjAAAAABYAFB0PFyoOy0iu4b4fJofSljrjP3GhIgICVHbC6DGINo2h/z4pLnrK/Ns1ZrsK0lP2L8cPB67uY1dHMEQCIDYJ4XuE9qfTDIC/phC1tFQvMqig1UR6EvsTZtfwHMRKAiBXOpVMRRgzFWFAb5AwDo8zWPUZKNQ8ISqMrtAt5n7r7gEAAAA=";
+
+    // The valid PSBTs of the BIP174 test vectors: one P2PKH input; one P2PKH and one
+    // P2SH-P2WPKH input, the first finalized; one P2PKH input with a sighash type; and
+    // unknown types in the inputs.
+    const BIP174_VALID_PSBTS: [&str; 4] = [
+        "cHNidP8BAHUCAAAAASaBcTce3/KF6Tet7qSze3gADAVmy7OtZGQXE8pCFxv2AAAAAAD+////AtPf9QUAAAAAGXapFNDFmQPFusKGh2DpD9UhpGZap2UgiKwA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65PJwDlIvHh7MuEwAAAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UBAAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHmOuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkqASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAAAA",
+        "cHNidP8BAKACAAAAAqsJSaCMWvfEm4IS9Bfi8Vqz9cM9zxU4IagTn4d6W3vkAAAAAAD+////qwlJoIxa98SbghL0F+LxWrP1wz3PFTghqBOfh3pbe+QBAAAAAP7///8CYDvqCwAAAAAZdqkUdopAu9dAy+gdmI5x3ipNXHE5ax2IrI4kAAAAAAAAGXapFG9GILVT+glechue4O/p+gOcykWXiKwAAAAAAAEHakcwRAIgR1lmF5fAGwNrJZKJSGhiGDR9iYZLcZ4ff89X0eURZYcCIFMJ6r9Wqk2Ikf/REf3xM286KdqGbX+EhtdVRs7tr5MZASEDXNxh/HupccC1AaZGoqg7ECy0OIEhfKaC3Ibi1z+ogpIAAQEgAOH1BQAAAAAXqRQ1RebjO4MsRwUPJNPuuTycA5SLx4cBBBYAFIXRNTfy4mVAWjTbr6nj3aAfuCMIAAAA",
+        "cHNidP8BAHUCAAAAASaBcTce3/KF6Tet7qSze3gADAVmy7OtZGQXE8pCFxv2AAAAAAD+////AtPf9QUAAAAAGXapFNDFmQPFusKGh2DpD9UhpGZap2UgiKwA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65PJwDlIvHh7MuEwAAAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UBAAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHmOuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkqASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAQMEAQAAAAAAAA==",
+        "cHNidP8BAD8CAAAAAf//////////////////////////////////////////AAAAAAD/////AQAAAAAAAAAAA2oBAAAAAAAACg8BAgMEBQYHCAkPAQIDBAUGBwgJCgsMDQ4PAAA=",
+    ];
+
+    /// Returns the private key and parameters of the native P2WPKH example transaction of BIP143.
+    fn native_p2wpkh_parameters() -> (BitcoinPrivateKey<N>, BitcoinTransactionParameters<N>) {
+        let secret_key = hex::decode("619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9").unwrap();
+        let secret_key = secp256k1::SecretKey::parse_slice(&secret_key).unwrap();
+        let private_key = BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, true);
+
+        let inputs = vec![
+            BitcoinTransactionInput::<N>::new(
+                hex::decode("9f96ade4b41d5433f4eda31e1738ec2b36f6e7d1420d94a6af99801a88f7f7ff").unwrap(),
+                0,
+                None,
+                None,
+                None,
+                None,
+                Some(vec![0xee, 0xff, 0xff, 0xff]),
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap(),
+            BitcoinTransactionInput::<N>::new(
+                hex::decode("8ac60eb9575db5b2d987e29f301b5b819ea83a5c6579d282d189cc04b8e151ef").unwrap(),
+                1,
+                Some(private_key.to_address(&BitcoinFormat::Bech32).unwrap()),
+                Some(BitcoinAmount(600000000)),
+                None,
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap(),
+        ];
+        let outputs = vec![
+            BitcoinTransactionOutput {
+                amount: BitcoinAmount(112340000),
+                script_pub_key: hex::decode("76a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac").unwrap(),
+            },
+            BitcoinTransactionOutput {
+                amount: BitcoinAmount(223450000),
+                script_pub_key: hex::decode("76a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac").unwrap(),
+            },
+        ];
+
+        let parameters = BitcoinTransactionParameters::<N> {
+            version: 1,
+            inputs,
+            outputs,
+            lock_time: 17,
+            segwit_flag: false,
+        };
+        (private_key, parameters)
+    }
+
+    /// Returns the parameters of a transaction that spends the first output of the given
+    /// transaction to the address of the given format, sending the amount, less a fee, back to it.
+    fn spend_parameters(
+        private_key: &BitcoinPrivateKey<N>,
+        format: &BitcoinFormat,
+        transaction_id: Vec<u8>,
+    ) -> BitcoinTransactionParameters<N> {
+        let address = private_key.to_address(format).unwrap();
+        let redeem_script = match format {
+            BitcoinFormat::P2SH_P2WPKH => {
+                let public_key = private_key
+                    .to_public_key()
+                    .to_secp256k1_public_key()
+                    .serialize_compressed();
+                Some([vec![0x00, 0x14], hash160(&public_key)].concat())
+            }
+            _ => None,
+        };
+        BitcoinTransactionParameters::<N> {
+            version: 2,
+            inputs: vec![BitcoinTransactionInput::<N>::new(
+                transaction_id,
+                0,
+                Some(address.clone()),
+                Some(BitcoinAmount(100000)),
+                redeem_script,
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap()],
+            outputs: vec![BitcoinTransactionOutput::new(&address, BitcoinAmount(90000)).unwrap()],
+            lock_time: 0,
+            segwit_flag: false,
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let (_, parameters) = native_p2wpkh_parameters();
+        let psbt = BitcoinPsbt::<N>::new(&parameters).unwrap();
+
+        assert_eq!(UNSIGNED_PSBT, psbt.to_string());
+        assert_eq!(None, psbt.inputs()[0].witness_utxo);
+        assert_eq!(
+            "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1",
+            hex::encode(&psbt.inputs()[1].witness_utxo.as_ref().unwrap().script_pub_key)
+        );
+        assert_eq!(2, psbt.outputs().len());
+    }
+
+    #[test]
+    fn test_from_str() {
+        let (_, parameters) = native_p2wpkh_parameters();
+        let psbt = BitcoinPsbt::<N>::new(&parameters).unwrap();
+
+        assert_eq!(psbt, BitcoinPsbt::<N>::from_str(UNSIGNED_PSBT).unwrap());
+        assert_eq!(
+            SIGNED_PSBT,
+            BitcoinPsbt::<N>::from_str(SIGNED_PSBT).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_sign_and_finalize() {
+        let (private_key, parameters) = native_p2wpkh_parameters();
+        let psbt = BitcoinPsbt::<N>::new(&parameters).unwrap().sign(&private_key).unwrap();
+        assert_eq!(SIGNED_PSBT, psbt.to_string());
+
+        // The first input is a P2PK output, finalized with the signature published in BIP143.
+        let mut psbt = BitcoinPsbt::<N>::from_str(SIGNED_PSBT).unwrap();
+        match psbt.finalize() {
+            Err(PsbtError::MissingUtxo(0)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+        psbt.inputs[0].final_script_sig = Some(hex::decode("4830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01").unwrap());
+
+        let psbt = psbt.finalize().unwrap();
+        assert!(psbt.inputs()[1].partial_signatures.is_empty());
+        assert_eq!(
+            "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000",
+            psbt.to_transaction().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn test_finalize_matching_signature() {
+        let mut psbt = BitcoinPsbt::<N>::from_str(SIGNED_PSBT).unwrap();
+        psbt.inputs[0].final_script_sig = Some(vec![0x00]);
+        let expected = psbt.finalize().unwrap();
+
+        // A partial signature of another public key, ordered first, is not used.
+        let (public_key, signature) = psbt.inputs[1].partial_signatures.iter().next().unwrap();
+        let (public_key, signature) = (public_key.clone(), signature.clone());
+        let mut other_public_key = public_key.clone();
+        other_public_key[1] = 0x00;
+        psbt.inputs[1].partial_signatures.insert(other_public_key, signature);
+        assert_eq!(expected, psbt.finalize().unwrap());
+
+        psbt.inputs[1].partial_signatures.remove(&public_key);
+        match psbt.finalize() {
+            Err(PsbtError::MissingSignature(1)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn test_finalize_invalid_redeem_script() {
+        let (private_key, _) = native_p2wpkh_parameters();
+        let parameters = spend_parameters(&private_key, &BitcoinFormat::P2SH_P2WPKH, vec![0x11; 32]);
+        let psbt = BitcoinPsbt::<N>::new(&parameters).unwrap().sign(&private_key).unwrap();
+        assert!(psbt.finalize().is_ok());
+
+        // A P2WPKH redeem script of another public key hash is not the script of the output.
+        let mut other = psbt.clone();
+        other.inputs[0].redeem_script.as_mut().unwrap()[2] ^= 0x01;
+        match other.finalize() {
+            Err(PsbtError::InvalidRedeemScript(0)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        // A redeem script other than P2WPKH is not supported.
+        let mut other = psbt.clone();
+        other.inputs[0].redeem_script = Some(vec![Opcode::OP_CHECKSIG as u8]);
+        match other.finalize() {
+            Err(PsbtError::UnsupportedScript(0)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        let mut other = psbt;
+        other.inputs[0].redeem_script = None;
+        match other.finalize() {
+            Err(PsbtError::UnsupportedScript(0)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn test_sign_matches_transaction_sign() {
+        let (private_key, _) = native_p2wpkh_parameters();
+
+        // A P2PKH input is signed against the full transaction of the output it spends.
+        let funding = spend_parameters(&private_key, &BitcoinFormat::P2PKH, vec![0x11; 32]);
+        let funding = BitcoinTransaction::<N>::new(&funding)
+            .unwrap()
+            .sign(&private_key)
+            .unwrap();
+        let funding_id = hex::decode(funding.to_transaction_id().unwrap().to_string()).unwrap();
+
+        for format in &[BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32] {
+            let parameters = spend_parameters(&private_key, format, funding_id.clone());
+            let expected = BitcoinTransaction::<N>::new(&parameters)
+                .unwrap()
+                .sign(&private_key)
+                .unwrap();
+
+            let mut psbt = BitcoinPsbt::<N>::new(&parameters).unwrap();
+            if *format == BitcoinFormat::P2PKH {
+                match psbt.sign(&private_key) {
+                    Err(PsbtError::TransactionError(TransactionError::MissingSignerInput)) => {}
+                    result => panic!("unexpected result: {:?}", result),
+                };
+                psbt.set_non_witness_utxo(0, &funding).unwrap();
+            }
+
+            let psbt = BitcoinPsbt::<N>::from_str(&psbt.to_string()).unwrap();
+            let transaction = psbt
+                .sign(&private_key)
+                .unwrap()
+                .finalize()
+                .unwrap()
+                .to_transaction()
+                .unwrap();
+            assert_eq!(expected.to_string(), transaction.to_string());
+        }
+    }
+
+    #[test]
+    fn test_combine() {
+        let (private_key, parameters) = native_p2wpkh_parameters();
+        let psbt = BitcoinPsbt::<N>::new(&parameters).unwrap();
+        let signed = BitcoinPsbt::<N>::from_str(SIGNED_PSBT).unwrap();
+
+        assert_eq!(signed, psbt.combine(&signed).unwrap());
+        assert_eq!(signed, signed.combine(&psbt).unwrap());
+
+        let mut other = parameters.clone();
+        other.lock_time = 0;
+        let other = BitcoinPsbt::<N>::new(&other).unwrap().sign(&private_key).unwrap();
+        match psbt.combine(&other) {
+            Err(PsbtError::MismatchedTransaction) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn test_derivations() {
+        let (_, parameters) = native_p2wpkh_parameters();
+        let mut psbt = BitcoinPsbt::<N>::new(&parameters).unwrap();
+
+        // BIP32 test vector 1
+        let master_extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+        let path = BitcoinDerivationPath::<N>::from_str("m/0'/1").unwrap();
+        psbt.add_input_derivation(1, &master_extended_private_key, &path)
+            .unwrap();
+        psbt.add_output_derivation(0, &master_extended_private_key, &path)
+            .unwrap();
+
+        let psbt = BitcoinPsbt::<N>::from_str(&psbt.to_string()).unwrap();
+        let (public_key, source) = psbt.inputs()[1].bip32_derivations.iter().next().unwrap();
+        assert_eq!(
+            "03501e454bf00751f24b1b489aa925215d66af2234e3891c3b21a52bedb3cd711c",
+            hex::encode(public_key)
+        );
+        assert_eq!("3442193e", hex::encode(source.fingerprint));
+        assert_eq!(path.to_vec().unwrap(), source.path);
+        assert_eq!(psbt.inputs()[1].bip32_derivations, psbt.outputs()[0].bip32_derivations);
+
+        match psbt
+            .clone()
+            .add_input_derivation(2, &master_extended_private_key, &path)
+        {
+            Err(PsbtError::InvalidInputIndex(2)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn test_invalid_psbts() {
+        let psbt = base64::decode(UNSIGNED_PSBT).unwrap();
+
+        let mut invalid_magic = psbt.clone();
+        invalid_magic[4] = 0x00;
+        match BitcoinPsbt::<N>::from_bytes(&invalid_magic) {
+            Err(PsbtError::InvalidMagic) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        match BitcoinPsbt::<N>::from_bytes(&psbt[..psbt.len() - 1]) {
+            Err(PsbtError::UnexpectedEndOfData) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        // The global map ends after the unsigned transaction (5 bytes of magic, 3 of key and
+        // value lengths and key type, and 160 of transaction), and the first input map is empty.
+        let duplicate_key = [&psbt[..168], &psbt[5..168], &psbt[168..]].concat();
+        match BitcoinPsbt::<N>::from_bytes(&duplicate_key) {
+            Err(PsbtError::DuplicateKey(key)) => assert_eq!(vec![PSBT_GLOBAL_UNSIGNED_TX], key),
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        match BitcoinPsbt::<N>::from_bytes(&psbt[..psbt.len() - 2]) {
+            Err(PsbtError::UnexpectedEndOfData) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        match BitcoinPsbt::<N>::from_bytes(&[&psbt[..], &[0x00]].concat()) {
+            Err(PsbtError::InvalidValue(_)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        // The unsigned transaction must not carry a script signature.
+        let mut signed_transaction = psbt.clone();
+        signed_transaction[7] = 0xa1;
+        signed_transaction[8 + 41] = 0x01;
+        let signed_transaction = [&signed_transaction[..50], &[0x51], &signed_transaction[50..]].concat();
+        match BitcoinPsbt::<N>::from_bytes(&signed_transaction) {
+            Err(PsbtError::InvalidValue(key)) => assert_eq!(vec![PSBT_GLOBAL_UNSIGNED_TX], key),
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        match BitcoinPsbt::<N>::from_str("cHNidP8B") {
+            Err(PsbtError::UnexpectedEndOfData) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn test_bip174_valid_psbts() {
+        BIP174_VALID_PSBTS.iter().for_each(|psbt| {
+            assert_eq!(*psbt, BitcoinPsbt::<N>::from_str(psbt).unwrap().to_string());
+        });
+    }
+
+    #[test]
+    fn test_bip174_invalid_psbts() {
+        // A network transaction, not in the PSBT format
+        match BitcoinPsbt::<N>::from_str("AgAAAAEmgXE3Ht/yhek3re6ks3t4AAwFZsuzrWRkFxPKQhcb9gAAAABqRzBEAiBwsiRRI+a/R01gxbUMBD1MaRpdJDXwmjSnZiqdwlF5CgIgATKcqdrPKAvfMHQOwDkEIkIsgctFg5RXrrdvwS7dlbMBIQJlfRGNM1e44PTCzUbbezn22cONmnCry5st5dyNv+TOMf7///8C09/1BQAAAAAZdqkU0MWZA8W6woaHYOkP1SGkZlqnZSCIrADh9QUAAAAAF6kUNUXm4zuDLEcFDyTT7rk8nAOUi8eHsy4TAA==") {
+            Err(PsbtError::InvalidMagic) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        // A PSBT missing its outputs
+        match BitcoinPsbt::<N>::from_str("cHNidP8BAHUCAAAAASaBcTce3/KF6Tet7qSze3gADAVmy7OtZGQXE8pCFxv2AAAAAAD+////AtPf9QUAAAAAGXapFNDFmQPFusKGh2DpD9UhpGZap2UgiKwA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65PJwDlIvHh7MuEwAAAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UBAAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHmOuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkqASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAA==") {
+            Err(PsbtError::UnexpectedEndOfData) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        // A PSBT with a filled script signature in its unsigned transaction
+        match BitcoinPsbt::<N>::from_str("cHNidP8BAP0KAQIAAAACqwlJoIxa98SbghL0F+LxWrP1wz3PFTghqBOfh3pbe+QAAAAAakcwRAIgR1lmF5fAGwNrJZKJSGhiGDR9iYZLcZ4ff89X0eURZYcCIFMJ6r9Wqk2Ikf/REf3xM286KdqGbX+EhtdVRs7tr5MZASEDXNxh/HupccC1AaZGoqg7ECy0OIEhfKaC3Ibi1z+ogpL+////qwlJoIxa98SbghL0F+LxWrP1wz3PFTghqBOfh3pbe+QBAAAAAP7///8CYDvqCwAAAAAZdqkUdopAu9dAy+gdmI5x3ipNXHE5ax2IrI4kAAAAAAAAGXapFG9GILVT+glechue4O/p+gOcykWXiKwAAAAAAAABASAA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65PJwDlIvHhwEEFgAUhdE1N/LiZUBaNNuvqePdoB+4IwgAAAA=") {
+            Err(PsbtError::InvalidValue(key)) => assert_eq!(vec![PSBT_GLOBAL_UNSIGNED_TX], key),
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        // A PSBT with inputs and outputs but no unsigned transaction
+        match BitcoinPsbt::<N>::from_str("cHNidP8AAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UBAAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHmOuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkqASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAA==") {
+            Err(PsbtError::MissingUnsignedTransaction) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::btree_map, collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        /// Returns a strategy over serialized unsigned transactions.
+        fn unsigned_transaction() -> impl Strategy<Value = Vec<u8>> {
+            let input = (any::<[u8; 32]>(), any::<u32>(), any::<u32>());
+            let output = (0..21_000_000 * 100_000_000u64, vec(any::<u8>(), 0..40));
+            (any::<u32>(), vec(input, 1..4), vec(output, 0..4), any::<u32>()).prop_map(
+                |(version, inputs, outputs, lock_time)| {
+                    let mut transaction = version.to_le_bytes().to_vec();
+                    transaction.extend(variable_length_integer(inputs.len() as u64).unwrap());
+                    for (hash, index, sequence) in &inputs {
+                        transaction.extend(&hash[..]);
+                        transaction.extend(&index.to_le_bytes());
+                        transaction.push(0x00);
+                        transaction.extend(&sequence.to_le_bytes());
+                    }
+                    transaction.extend(variable_length_integer(outputs.len() as u64).unwrap());
+                    for (amount, script_pub_key) in &outputs {
+                        transaction.extend(&amount.to_le_bytes());
+                        transaction.extend(variable_length_integer(script_pub_key.len() as u64).unwrap());
+                        transaction.extend(script_pub_key);
+                    }
+                    transaction.extend(&lock_time.to_le_bytes());
+                    transaction
+                },
+            )
+        }
+
+        /// Returns a strategy over the public keys of partial signatures and key sources.
+        fn public_key() -> impl Strategy<Value = Vec<u8>> {
+            prop_oneof![vec(any::<u8>(), 33), vec(any::<u8>(), 65)]
+        }
+
+        /// Returns a strategy over key sources.
+        fn key_source() -> impl Strategy<Value = KeySource> {
+            (any::<[u8; 4]>(), vec(any::<ChildIndex>(), 0..4))
+                .prop_map(|(fingerprint, path)| KeySource { fingerprint, path })
+        }
+
+        /// Returns a strategy over unknown key-value pairs, with key types above the given type.
+        fn unknown(key_type: u8) -> impl Strategy<Value = BTreeMap<Vec<u8>, Vec<u8>>> {
+            let key =
+                (key_type + 1.., vec(any::<u8>(), 0..8)).prop_map(|(key_type, key)| [vec![key_type], key].concat());
+            btree_map(key, vec(any::<u8>(), 0..16), 0..3)
+        }
+
+        fn psbt_input() -> impl Strategy<Value = PsbtInput> {
+            let script = || proptest::option::of(vec(any::<u8>(), 0..40));
+            (
+                (
+                    script(),
+                    proptest::option::of((0..21_000_000 * 100_000_000i64, vec(any::<u8>(), 0..40))),
+                    btree_map(public_key(), vec(any::<u8>(), 0..72), 0..3),
+                    proptest::option::of(any::<u32>()),
+                ),
+                (
+                    script(),
+                    script(),
+                    btree_map(public_key(), key_source(), 0..3),
+                    script(),
+                    proptest::option::of(vec(vec(any::<u8>(), 0..16), 0..3)),
+                    unknown(PSBT_IN_FINAL_SCRIPTWITNESS),
+                ),
+            )
+                .prop_map(
+                    |(
+                        (non_witness_utxo, witness_utxo, partial_signatures, sighash_type),
+                        (
+                            redeem_script,
+                            witness_script,
+                            bip32_derivations,
+                            final_script_sig,
+                            final_script_witness,
+                            unknown,
+                        ),
+                    )| PsbtInput {
+                        non_witness_utxo,
+                        witness_utxo: witness_utxo.map(|(amount, script_pub_key)| BitcoinTransactionOutput {
+                            amount: BitcoinAmount(amount),
+                            script_pub_key,
+                        }),
+                        partial_signatures,
+                        sighash_type,
+                        redeem_script,
+                        witness_script,
+                        bip32_derivations,
+                        final_script_sig,
+                        final_script_witness,
+                        unknown,
+                    },
+                )
+        }
+
+        fn psbt_output() -> impl Strategy<Value = PsbtOutput> {
+            (
+                proptest::option::of(vec(any::<u8>(), 0..40)),
+                proptest::option::of(vec(any::<u8>(), 0..40)),
+                btree_map(public_key(), key_source(), 0..3),
+                unknown(PSBT_OUT_BIP32_DERIVATION),
+            )
+                .prop_map(
+                    |(redeem_script, witness_script, bip32_derivations, unknown)| PsbtOutput {
+                        redeem_script,
+                        witness_script,
+                        bip32_derivations,
+                        unknown,
+                    },
+                )
+        }
+
+        /// Returns a strategy over PSBTs, with a map for each input and output of the unsigned
+        /// transaction.
+        fn psbt() -> impl Strategy<Value = BitcoinPsbt<N>> {
+            (unsigned_transaction(), unknown(PSBT_GLOBAL_UNSIGNED_TX))
+                .prop_flat_map(|(transaction, unknown)| {
+                    let unsigned_transaction = BitcoinTransactionParameters::<N>::read(&transaction[..]).unwrap();
+                    let inputs = vec(psbt_input(), unsigned_transaction.inputs.len());
+                    let outputs = vec(psbt_output(), unsigned_transaction.outputs.len());
+                    (Just(unsigned_transaction), Just(unknown), inputs, outputs)
+                })
+                .prop_map(|(unsigned_transaction, unknown, inputs, outputs)| BitcoinPsbt {
+                    unsigned_transaction,
+                    unknown,
+                    inputs,
+                    outputs,
+                })
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(psbt in psbt()) {
+                let bytes = psbt.to_bytes().unwrap();
+                prop_assert_eq!(&psbt, &BitcoinPsbt::<N>::from_bytes(&bytes).unwrap());
+                prop_assert_eq!(&psbt, &BitcoinPsbt::<N>::from_str(&psbt.to_string()).unwrap());
+            }
+
+            #[test]
+            fn from_str_truncated(psbt in psbt(), index: prop::sample::Index) {
+                let bytes = psbt.to_bytes().unwrap();
+                prop_assert!(BitcoinPsbt::<N>::from_bytes(&bytes[..index.index(bytes.len())]).is_err());
+            }
+
+            #[test]
+            fn from_str_arbitrary(string in "\\PC*") {
+                test_from_str_round_trip::<BitcoinPsbt<N>>(&string)?;
+            }
+
+            #[test]
+            fn from_bytes_arbitrary(bytes in vec(any::<u8>(), 0..256)) {
+                let psbt = [&PSBT_MAGIC[..], &bytes[..]].concat();
+                if let Ok(psbt) = BitcoinPsbt::<N>::from_bytes(&psbt) {
+                    prop_assert_eq!(&psbt, &BitcoinPsbt::<N>::from_bytes(&psbt.to_bytes().unwrap()).unwrap());
+                }
+            }
+        }
+    }
+}
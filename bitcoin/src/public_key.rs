@@ -2,9 +2,16 @@ use crate::address::BitcoinAddress;
 use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
+use wagyu_model::no_std::*;
 use wagyu_model::{Address, AddressError, PublicKey, PublicKeyError};
 
-use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
+use core::{
+    fmt,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
 use secp256k1;
 
 /// Represents a Bitcoin public key
@@ -53,32 +60,101 @@ impl<N: BitcoinNetwork> BitcoinPublicKey<N> {
         self.public_key.clone()
     }
 
+    /// Returns the public key on the network `M`, for the intentional reuse of a public key
+    /// across networks, which parsing and the network type parameter otherwise prevent.
+    pub fn force_network<M: BitcoinNetwork>(&self) -> BitcoinPublicKey<M> {
+        BitcoinPublicKey::<M>::from_secp256k1_public_key(self.public_key.clone(), self.compressed)
+    }
+
+    /// Returns a public key given its 33 byte compressed (0x02 or 0x03 prefix), 65 byte uncompressed
+    /// (0x04 prefix), or 32 byte x-only serialization. An x-only public key is the point with
+    /// the even y-coordinate, in compressed form, as in BIP340.
+    pub fn from_bytes(public_key: &[u8]) -> Result<Self, PublicKeyError> {
+        let (public_key, compressed) = match (public_key.len(), public_key.first()) {
+            (32, _) => {
+                let mut compressed = [0x02u8; 33];
+                compressed[1..].copy_from_slice(public_key);
+                (secp256k1::PublicKey::parse_compressed(&compressed), true)
+            }
+            (33, Some(0x02)) | (33, Some(0x03)) => {
+                let mut compressed = [0u8; 33];
+                compressed.copy_from_slice(public_key);
+                (secp256k1::PublicKey::parse_compressed(&compressed), true)
+            }
+            (65, Some(0x04)) => {
+                let mut uncompressed = [0u8; 65];
+                uncompressed.copy_from_slice(public_key);
+                (secp256k1::PublicKey::parse(&uncompressed), false)
+            }
+            (33, Some(prefix)) | (65, Some(prefix)) => {
+                return Err(PublicKeyError::InvalidPrefix(format!("{:02x}", prefix)))
+            }
+            (length, _) => return Err(PublicKeyError::InvalidByteLength(length)),
+        };
+
+        Ok(Self::from_secp256k1_public_key(
+            public_key.map_err(|_| PublicKeyError::InvalidPoint)?,
+            compressed,
+        ))
+    }
+
+    /// Returns the 33 byte compressed serialization of the public key.
+    pub fn to_compressed_bytes(&self) -> [u8; 33] {
+        self.public_key.serialize_compressed()
+    }
+
+    /// Returns the 65 byte uncompressed serialization of the public key.
+    pub fn to_uncompressed_bytes(&self) -> [u8; 65] {
+        self.public_key.serialize()
+    }
+
+    /// Returns the 32 byte x-only serialization of the public key, its x-coordinate, as used by Taproot.
+    pub fn to_x_only_bytes(&self) -> [u8; 32] {
+        let mut x_only = [0u8; 32];
+        x_only.copy_from_slice(&self.to_compressed_bytes()[1..]);
+        x_only
+    }
+
     /// Returns `true` if the public key is in compressed form.
     pub fn is_compressed(&self) -> bool {
         self.compressed
     }
+
+    /// Returns a copy of the public key in compressed form.
+    pub fn to_compressed(&self) -> Self {
+        Self::from_secp256k1_public_key(self.public_key.clone(), true)
+    }
+
+    /// Returns a copy of the public key in uncompressed form.
+    pub fn to_uncompressed(&self) -> Self {
+        Self::from_secp256k1_public_key(self.public_key.clone(), false)
+    }
+}
+
+impl<N: BitcoinNetwork> Hash for BitcoinPublicKey<N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_compressed_bytes().hash(state);
+        self.compressed.hash(state);
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinPublicKey<N> {
     type Err = PublicKeyError;
 
+    /// Returns a public key given its compressed, uncompressed, or x-only hex serialization.
     fn from_str(public_key: &str) -> Result<Self, Self::Err> {
-        Ok(Self {
-            public_key: secp256k1::PublicKey::parse_slice(&hex::decode(public_key)?, None)?,
-            compressed: public_key.len() == 66,
-            _network: PhantomData,
-        })
+        Self::from_bytes(&hex::decode(public_key)?)
     }
 }
 
 impl<N: BitcoinNetwork> Display for BitcoinPublicKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if self.compressed {
-            for s in &self.public_key.serialize_compressed()[..] {
+            for s in &self.to_compressed_bytes()[..] {
                 write!(f, "{:02x}", s)?;
             }
         } else {
-            for s in &self.public_key.serialize()[..] {
+            for s in &self.to_uncompressed_bytes()[..] {
                 write!(f, "{:02x}", s)?;
             }
         }
@@ -522,6 +598,138 @@ mod tests {
         }
     }
 
+    mod compression {
+        use super::*;
+
+        type N = Mainnet;
+
+        // Example key from https://en.bitcoin.it/wiki/Wallet_import_format
+        const PRIVATE_KEY: &str = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ";
+        const UNCOMPRESSED_ADDRESS: &str = "1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S";
+        const COMPRESSED_ADDRESS: &str = "1LoVGDgRs9hTfTNJNuXKSpywcbdvwRXpmK";
+
+        #[test]
+        fn to_uncompressed() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let public_key = BitcoinPublicKey::from_private_key(&private_key)
+                .to_compressed()
+                .to_uncompressed();
+            assert!(!public_key.is_compressed());
+            assert_eq!(130, public_key.to_string().len());
+            let address = public_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(UNCOMPRESSED_ADDRESS, address.to_string());
+        }
+
+        #[test]
+        fn to_compressed() {
+            let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+            let public_key = BitcoinPublicKey::from_private_key(&private_key).to_compressed();
+            assert!(public_key.is_compressed());
+            assert_eq!(66, public_key.to_string().len());
+            let address = public_key.to_address(&BitcoinFormat::P2PKH).unwrap();
+            assert_eq!(COMPRESSED_ADDRESS, address.to_string());
+        }
+    }
+
+    mod encodings {
+        use super::*;
+
+        type N = Mainnet;
+
+        // A public key with an odd y-coordinate
+        const COMPRESSED: &str = "039ed714bf521e96e3f3609b74da898e44d0fb64ba68c62c57852470ffc28e3db5";
+        const X_ONLY: &str = "9ed714bf521e96e3f3609b74da898e44d0fb64ba68c62c57852470ffc28e3db5";
+
+        #[test]
+        fn odd_y_coordinate() {
+            let public_key = BitcoinPublicKey::<N>::from_str(COMPRESSED).unwrap();
+            assert_eq!(COMPRESSED, hex::encode(&public_key.to_compressed_bytes()[..]));
+            assert_eq!(X_ONLY, hex::encode(public_key.to_x_only_bytes()));
+
+            // The last byte of the uncompressed serialization is odd.
+            let uncompressed = public_key.to_uncompressed_bytes();
+            assert_eq!(0x04, uncompressed[0]);
+            assert_eq!(1, uncompressed[64] & 1);
+            assert_eq!(X_ONLY, hex::encode(&uncompressed[1..33]));
+
+            let parsed = BitcoinPublicKey::<N>::from_bytes(&uncompressed).unwrap();
+            assert!(!parsed.is_compressed());
+            assert_eq!(public_key.to_uncompressed(), parsed);
+            assert_eq!(hex::encode(&uncompressed[..]), parsed.to_string());
+        }
+
+        #[test]
+        fn x_only() {
+            // An x-only public key is the point with the even y-coordinate.
+            let public_key = BitcoinPublicKey::<N>::from_str(X_ONLY).unwrap();
+            assert!(public_key.is_compressed());
+            assert_eq!(format!("02{}", X_ONLY), public_key.to_string());
+            assert_eq!(X_ONLY, hex::encode(public_key.to_x_only_bytes()));
+            assert_ne!(BitcoinPublicKey::<N>::from_str(COMPRESSED).unwrap(), public_key);
+            assert_eq!(0, public_key.to_uncompressed_bytes()[64] & 1);
+        }
+
+        #[test]
+        fn segwit_address() {
+            // SegWit addresses commit to the compressed public key, whatever its serialization.
+            let public_key = BitcoinPublicKey::<N>::from_str(COMPRESSED).unwrap();
+            [BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32]
+                .iter()
+                .for_each(|format| {
+                    assert_eq!(
+                        public_key.to_address(format).unwrap(),
+                        public_key.to_uncompressed().to_address(format).unwrap()
+                    );
+                });
+            assert_ne!(
+                public_key.to_address(&BitcoinFormat::P2PKH).unwrap(),
+                public_key.to_uncompressed().to_address(&BitcoinFormat::P2PKH).unwrap()
+            );
+        }
+
+        #[test]
+        fn invalid() {
+            // The x-coordinate of the BIP340 test vector of a public key not on the curve
+            let off_curve = "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34";
+            assert!(matches!(
+                BitcoinPublicKey::<N>::from_str(off_curve),
+                Err(PublicKeyError::InvalidPoint)
+            ));
+            assert!(matches!(
+                BitcoinPublicKey::<N>::from_str(&format!("03{}", off_curve)),
+                Err(PublicKeyError::InvalidPoint)
+            ));
+
+            let uncompressed = hex::encode(
+                &BitcoinPublicKey::<N>::from_str(COMPRESSED)
+                    .unwrap()
+                    .to_uncompressed_bytes()[1..],
+            );
+            assert!(matches!(
+                BitcoinPublicKey::<N>::from_str(&format!("04{}", &uncompressed[..126])),
+                Err(PublicKeyError::InvalidByteLength(64))
+            ));
+            assert!(matches!(
+                BitcoinPublicKey::<N>::from_str(&format!("04{}00", &uncompressed[..126])),
+                Err(PublicKeyError::InvalidPoint)
+            ));
+
+            [
+                format!("04{}", X_ONLY),
+                format!("05{}", X_ONLY),
+                format!("02{}", uncompressed),
+                format!("06{}", uncompressed),
+            ]
+            .iter()
+            .for_each(|public_key| {
+                assert!(matches!(
+                    BitcoinPublicKey::<N>::from_str(public_key),
+                    Err(PublicKeyError::InvalidPrefix(_))
+                ));
+            });
+        }
+    }
+
     #[test]
     fn test_p2pkh_invalid() {
         type N = Mainnet;
@@ -567,4 +775,48 @@ mod tests {
         let public_key = "02468791fee1444df3a6e786e2f9da79198f8902387e1fa5a2c051950c4df51ab402468791fee1444df3a6e786e2f9da79198f8902387e1fa5a2c051950c4df51ab4";
         assert!(BitcoinPublicKey::<N>::from_str(public_key).is_err());
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(private_key: BitcoinPrivateKey<N>) {
+                let public_key = BitcoinPublicKey::from_private_key(&private_key);
+                prop_assert_eq!(&public_key, &BitcoinPublicKey::from_str(&public_key.to_string()).unwrap());
+            }
+
+            #[test]
+            fn to_bytes_from_bytes(private_key: BitcoinPrivateKey<N>) {
+                let public_key = BitcoinPublicKey::<N>::from_private_key(&private_key).to_compressed();
+                let compressed = BitcoinPublicKey::<N>::from_bytes(&public_key.to_compressed_bytes()).unwrap();
+                let uncompressed = BitcoinPublicKey::<N>::from_bytes(&public_key.to_uncompressed_bytes()).unwrap();
+                let x_only = BitcoinPublicKey::<N>::from_bytes(&public_key.to_x_only_bytes()).unwrap();
+                prop_assert_eq!(&public_key, &compressed);
+                prop_assert_eq!(&public_key.to_uncompressed(), &uncompressed);
+                prop_assert_eq!(public_key.to_x_only_bytes(), x_only.to_x_only_bytes());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinPublicKey<N>>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_hex(inp in "(0[234])?[0-9a-f]{0,130}") {
+                if let Ok(public_key) = BitcoinPublicKey::<N>::from_str(&inp) {
+                    prop_assert_eq!(&public_key, &BitcoinPublicKey::from_str(&public_key.to_string()).unwrap());
+                }
+            }
+
+            #[test]
+            fn from_bytes_arbitrary(data in vec(any::<u8>(), 0..70)) {
+                let _ = BitcoinPublicKey::<N>::from_bytes(&data);
+            }
+        }
+    }
 }
@@ -0,0 +1,961 @@
+//!
+//! SLIP-39 - Shamir's Secret-Sharing for Mnemonic Codes
+//! https://github.com/satoshilabs/slips/blob/master/slip-0039.md
+//!
+//! This module splits a master secret into groups of mnemonic shares, of which a threshold of
+//! groups, each with a threshold of member shares, recovers the master secret. The master secret
+//! is encrypted with the passphrase before it is split, and the recovered master secret is the
+//! seed of a master extended private key.
+//!
+
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::wordlist::slip39::ENGLISH;
+use wagyu_model::{ExtendedPrivateKey, ExtendedPrivateKeyError};
+
+use core::{fmt, str::FromStr};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2;
+use rand::{CryptoRng, Rng};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// The number of bits of a word, as the wordlist has 1024 words.
+const RADIX_BITS: usize = 10;
+/// The number of words of the identifier, extendable flag, and iteration exponent.
+const ID_EXP_LENGTH_WORDS: usize = 2;
+/// The number of words of the RS1024 checksum.
+const CHECKSUM_LENGTH_WORDS: usize = 3;
+/// The number of words of a share that do not encode the share value.
+const METADATA_LENGTH_WORDS: usize = 7;
+/// The number of words of a share with a 128-bit share value.
+const MIN_MNEMONIC_LENGTH_WORDS: usize = 20;
+/// The minimum length in bytes of a master secret.
+const MIN_MASTER_SECRET_LENGTH: usize = 16;
+/// The maximum number of groups, and of member shares in a group.
+const MAX_SHARE_COUNT: usize = 16;
+/// The length in bytes of the digest of the shared secret.
+const DIGEST_LENGTH: usize = 4;
+/// The index of the share holding the digest of the shared secret.
+const DIGEST_INDEX: u8 = 254;
+/// The index of the share holding the shared secret.
+const SECRET_INDEX: u8 = 255;
+/// The total number of PBKDF2 iterations of the encryption, before scaling by the iteration exponent.
+const BASE_ITERATION_COUNT: usize = 10000;
+/// The number of rounds of the Feistel cipher encrypting the master secret.
+const ROUND_COUNT: u8 = 4;
+/// The iteration exponent of newly split shares.
+const ITERATION_EXPONENT: u8 = 1;
+
+#[derive(Debug, Clone, Fail, PartialEq, Eq)]
+pub enum Slip39Error {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "duplicate member index {} with different share values", _0)]
+    DuplicateMemberIndex(u8),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPrivateKeyError(String),
+
+    #[fail(display = "insufficient groups of shares: {} required, {} found", _0, _1)]
+    InsufficientGroups(u8, usize),
+
+    #[fail(display = "insufficient shares of group {}: {} required, {} found", _0, _1, _2)]
+    InsufficientShares(u8, u8, usize),
+
+    #[fail(display = "invalid checksum")]
+    InvalidChecksum,
+
+    #[fail(display = "invalid digest of the shared secret")]
+    InvalidDigest,
+
+    #[fail(display = "invalid group threshold: {} of {} groups", _0, _1)]
+    InvalidGroupThreshold(u8, usize),
+
+    #[fail(
+        display = "invalid master secret length: {} bytes, expected an even number of at least 16 bytes",
+        _0
+    )]
+    InvalidMasterSecretLength(usize),
+
+    #[fail(display = "invalid member threshold: {} of {} shares", _0, _1)]
+    InvalidMemberThreshold(u8, u8),
+
+    #[fail(display = "invalid mnemonic length: {} words", _0)]
+    InvalidMnemonicLength(usize),
+
+    #[fail(display = "invalid padding")]
+    InvalidPadding,
+
+    #[fail(display = "invalid passphrase: only printable ASCII characters are allowed")]
+    InvalidPassphrase,
+
+    #[fail(display = "invalid share count: {}, expected at most 16", _0)]
+    InvalidShareCount(usize),
+
+    #[fail(display = "invalid word: {}", _0)]
+    InvalidWord(String),
+
+    #[fail(display = "shares have different lengths")]
+    MismatchedLength,
+
+    #[fail(display = "shares have different group thresholds or group counts")]
+    MismatchedGroupParameters,
+
+    #[fail(display = "shares have different identifiers or iteration exponents")]
+    MismatchedIdentifier,
+
+    #[fail(display = "shares of group {} have different member thresholds", _0)]
+    MismatchedMemberThreshold(u8),
+
+    #[fail(display = "no shares")]
+    NoShares,
+
+    #[fail(display = "too many groups of shares: {} required, {} found", _0, _1)]
+    TooManyGroups(u8, usize),
+
+    #[fail(display = "too many shares of group {}: {} required, {} found", _0, _1, _2)]
+    TooManyShares(u8, u8, usize),
+}
+
+impl From<ExtendedPrivateKeyError> for Slip39Error {
+    fn from(error: ExtendedPrivateKeyError) -> Self {
+        Slip39Error::ExtendedPrivateKeyError(error.to_string())
+    }
+}
+
+/// Represents a SLIP-39 share. The fields are private, as a share is only built from a share
+/// mnemonic or by `split`, so that each parameter fits in the four bits of its mnemonic encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Share {
+    /// The random identifier, common to every share of a master secret
+    identifier: u16,
+    /// Whether the identifier is omitted from the encryption salt
+    extendable: bool,
+    /// The exponent of the number of PBKDF2 iterations of the encryption
+    iteration_exponent: u8,
+    /// The index of the group of the share
+    group_index: u8,
+    /// The number of groups required to recover the master secret
+    group_threshold: u8,
+    /// The total number of groups
+    group_count: u8,
+    /// The index of the share within its group
+    member_index: u8,
+    /// The number of shares of the group required to recover the group secret
+    member_threshold: u8,
+    /// The share value
+    value: Vec<u8>,
+}
+
+impl Share {
+    /// Returns the wordlist indices of the share mnemonic.
+    fn to_words(&self) -> Vec<u16> {
+        let id_exp = (self.identifier as u32) << 5 | (self.extendable as u32) << 4 | self.iteration_exponent as u32;
+        let parameters = [
+            self.group_index,
+            self.group_threshold - 1,
+            self.group_count - 1,
+            self.member_index,
+            self.member_threshold - 1,
+        ]
+        .iter()
+        .fold(0u32, |parameters, parameter| parameters << 4 | *parameter as u32);
+
+        let mut words = vec![];
+        words.extend(int_to_words(id_exp, ID_EXP_LENGTH_WORDS));
+        words.extend(int_to_words(parameters, 2));
+        words.extend(bytes_to_words(&self.value));
+        let checksum = create_checksum(&words, self.extendable);
+        words.extend(checksum);
+        words
+    }
+
+    /// Returns the share of the given wordlist indices of a share mnemonic.
+    fn from_words(words: &[u16]) -> Result<Self, Slip39Error> {
+        if words.len() < MIN_MNEMONIC_LENGTH_WORDS {
+            return Err(Slip39Error::InvalidMnemonicLength(words.len()));
+        }
+
+        let padding = (RADIX_BITS * (words.len() - METADATA_LENGTH_WORDS)) % 16;
+        if padding > 8 {
+            return Err(Slip39Error::InvalidMnemonicLength(words.len()));
+        }
+
+        let id_exp = words_to_int(&words[0..ID_EXP_LENGTH_WORDS]);
+        let extendable = (id_exp >> 4) & 1 == 1;
+        if !verify_checksum(words, extendable) {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+
+        let parameters = words_to_int(&words[ID_EXP_LENGTH_WORDS..ID_EXP_LENGTH_WORDS + 2]);
+        let parameter = |offset: u32| ((parameters >> (4 * offset)) & 0xf) as u8;
+        let share = Self {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 0xf) as u8,
+            group_index: parameter(4),
+            group_threshold: parameter(3) + 1,
+            group_count: parameter(2) + 1,
+            member_index: parameter(1),
+            member_threshold: parameter(0) + 1,
+            value: words_to_bytes(
+                &words[ID_EXP_LENGTH_WORDS + 2..words.len() - CHECKSUM_LENGTH_WORDS],
+                padding,
+            )?,
+        };
+
+        if share.group_threshold as usize > share.group_count as usize {
+            return Err(Slip39Error::InvalidGroupThreshold(
+                share.group_threshold,
+                share.group_count as usize,
+            ));
+        }
+
+        Ok(share)
+    }
+}
+
+impl FromStr for Share {
+    type Err = Slip39Error;
+
+    fn from_str(mnemonic: &str) -> Result<Self, Self::Err> {
+        let wordlist = ENGLISH.lines().collect::<Vec<&str>>();
+        let words = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                let word = word.to_lowercase();
+                match wordlist.binary_search(&word.as_str()) {
+                    Ok(index) => Ok(index as u16),
+                    Err(_) => Err(Slip39Error::InvalidWord(word)),
+                }
+            })
+            .collect::<Result<Vec<u16>, Slip39Error>>()?;
+        Self::from_words(&words)
+    }
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let wordlist = ENGLISH.lines().collect::<Vec<&str>>();
+        let words = self
+            .to_words()
+            .iter()
+            .map(|index| wordlist[*index as usize])
+            .collect::<Vec<&str>>();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// Returns the mnemonic shares of the given master secret, encrypted with the given passphrase,
+/// as one list of shares per group. Each group is given as its member threshold and member count,
+/// and `group_threshold` of the groups recover the master secret.
+pub fn split<R: Rng + CryptoRng>(
+    rng: &mut R,
+    master_secret: &[u8],
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    passphrase: &str,
+) -> Result<Vec<Vec<String>>, Slip39Error> {
+    if master_secret.len() < MIN_MASTER_SECRET_LENGTH || master_secret.len() & 1 == 1 {
+        return Err(Slip39Error::InvalidMasterSecretLength(master_secret.len()));
+    }
+    if groups.len() > MAX_SHARE_COUNT {
+        return Err(Slip39Error::InvalidShareCount(groups.len()));
+    }
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(Slip39Error::InvalidGroupThreshold(group_threshold, groups.len()));
+    }
+    for (member_threshold, member_count) in groups {
+        if *member_count as usize > MAX_SHARE_COUNT {
+            return Err(Slip39Error::InvalidShareCount(*member_count as usize));
+        }
+        // A threshold of one with multiple shares would produce identical shares.
+        if *member_threshold == 0 || member_threshold > member_count || (*member_threshold == 1 && *member_count > 1) {
+            return Err(Slip39Error::InvalidMemberThreshold(*member_threshold, *member_count));
+        }
+    }
+
+    let identifier = rng.gen::<u16>() & 0x7fff;
+    let extendable = true;
+    let encrypted_master_secret = encrypt(master_secret, passphrase, ITERATION_EXPONENT, identifier, extendable)?;
+
+    let group_secrets = split_secret(rng, group_threshold, groups.len() as u8, &encrypted_master_secret)?;
+    group_secrets
+        .iter()
+        .zip(groups)
+        .map(|((group_index, group_secret), (member_threshold, member_count))| {
+            Ok(split_secret(rng, *member_threshold, *member_count, group_secret)?
+                .into_iter()
+                .map(|(member_index, value)| {
+                    Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent: ITERATION_EXPONENT,
+                        group_index: *group_index,
+                        group_threshold,
+                        group_count: groups.len() as u8,
+                        member_index,
+                        member_threshold: *member_threshold,
+                        value,
+                    }
+                    .to_string()
+                })
+                .collect())
+        })
+        .collect()
+}
+
+/// Returns the master secret recovered from the given mnemonic shares, decrypted with the given passphrase.
+pub fn recover<S: AsRef<str>>(mnemonics: &[S], passphrase: &str) -> Result<Vec<u8>, Slip39Error> {
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| Share::from_str(mnemonic.as_ref()))
+        .collect::<Result<Vec<Share>, Slip39Error>>()?;
+    let first = shares.first().ok_or(Slip39Error::NoShares)?;
+
+    // Group the shares by group index, ignoring duplicate shares.
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in &shares {
+        if (share.identifier, share.extendable, share.iteration_exponent)
+            != (first.identifier, first.extendable, first.iteration_exponent)
+        {
+            return Err(Slip39Error::MismatchedIdentifier);
+        }
+        if (share.group_threshold, share.group_count) != (first.group_threshold, first.group_count) {
+            return Err(Slip39Error::MismatchedGroupParameters);
+        }
+
+        let group = groups.entry(share.group_index).or_default();
+        if group
+            .iter()
+            .any(|member| member.member_threshold != share.member_threshold)
+        {
+            return Err(Slip39Error::MismatchedMemberThreshold(share.group_index));
+        }
+        if !group.contains(&share) {
+            group.push(share);
+        }
+    }
+
+    if groups.len() < first.group_threshold as usize {
+        return Err(Slip39Error::InsufficientGroups(first.group_threshold, groups.len()));
+    }
+    if groups.len() > first.group_threshold as usize {
+        return Err(Slip39Error::TooManyGroups(first.group_threshold, groups.len()));
+    }
+
+    let group_secrets = groups
+        .iter()
+        .map(|(group_index, members)| {
+            let member_threshold = members[0].member_threshold;
+            if members.len() < member_threshold as usize {
+                return Err(Slip39Error::InsufficientShares(
+                    *group_index,
+                    member_threshold,
+                    members.len(),
+                ));
+            }
+            if members.len() > member_threshold as usize {
+                return Err(Slip39Error::TooManyShares(
+                    *group_index,
+                    member_threshold,
+                    members.len(),
+                ));
+            }
+            let members = members
+                .iter()
+                .map(|member| (member.member_index, member.value.clone()))
+                .collect::<Vec<_>>();
+            Ok((*group_index, recover_secret(member_threshold, &members)?))
+        })
+        .collect::<Result<Vec<_>, Slip39Error>>()?;
+
+    let encrypted_master_secret = recover_secret(first.group_threshold, &group_secrets)?;
+    decrypt(
+        &encrypted_master_secret,
+        passphrase,
+        first.iteration_exponent,
+        first.identifier,
+        first.extendable,
+    )
+}
+
+impl<N: BitcoinNetwork> BitcoinExtendedPrivateKey<N> {
+    /// Returns the master extended private key of the master secret recovered from the given
+    /// SLIP-39 mnemonic shares, decrypted with the given passphrase.
+    pub fn from_slip39<S: AsRef<str>>(
+        mnemonics: &[S],
+        passphrase: &str,
+        format: &BitcoinFormat,
+    ) -> Result<Self, Slip39Error> {
+        Ok(Self::new_master(&recover(mnemonics, passphrase)?, format)?)
+    }
+}
+
+/// Returns `count` shares of the given secret, of which `threshold` shares recover the secret.
+fn split_secret<R: Rng + CryptoRng>(
+    rng: &mut R,
+    threshold: u8,
+    count: u8,
+    secret: &[u8],
+) -> Result<Vec<(u8, Vec<u8>)>, Slip39Error> {
+    if threshold == 1 {
+        return Ok((0..count).map(|index| (index, secret.to_vec())).collect());
+    }
+
+    let random_bytes = |rng: &mut R, length: usize| {
+        let mut bytes = vec![0u8; length];
+        rng.fill_bytes(&mut bytes);
+        bytes
+    };
+
+    // The first `threshold - 2` shares are random, and the polynomial through them, the digest
+    // share, and the secret share determines the remaining shares.
+    let mut shares = (0..threshold - 2)
+        .map(|index| (index, random_bytes(rng, secret.len())))
+        .collect::<Vec<_>>();
+
+    let random_part = random_bytes(rng, secret.len() - DIGEST_LENGTH);
+    let mut digest_share = digest(&random_part, secret)?;
+    digest_share.extend(&random_part);
+
+    let mut base_shares = shares.clone();
+    base_shares.push((DIGEST_INDEX, digest_share));
+    base_shares.push((SECRET_INDEX, secret.to_vec()));
+
+    for index in threshold - 2..count {
+        shares.push((index, interpolate(&base_shares, index)?));
+    }
+    Ok(shares)
+}
+
+/// Returns the secret of the given `threshold` shares, after verifying its digest.
+fn recover_secret(threshold: u8, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, Slip39Error> {
+    if threshold == 1 {
+        return Ok(shares[0].1.clone());
+    }
+
+    let secret = interpolate(shares, SECRET_INDEX)?;
+    let digest_share = interpolate(shares, DIGEST_INDEX)?;
+    if digest_share[..DIGEST_LENGTH] != digest(&digest_share[DIGEST_LENGTH..], &secret)?[..] {
+        return Err(Slip39Error::InvalidDigest);
+    }
+    Ok(secret)
+}
+
+/// Returns the digest of the secret, keyed by the random part of the digest share.
+fn digest(random_part: &[u8], secret: &[u8]) -> Result<Vec<u8>, Slip39Error> {
+    let mut mac =
+        HmacSha256::new_varkey(random_part).map_err(|error| Slip39Error::Crate("hmac", format!("{:?}", error)))?;
+    mac.input(secret);
+    Ok(mac.result().code()[..DIGEST_LENGTH].to_vec())
+}
+
+/// Returns the exponent and logarithm tables of GF(256) with the Rijndael polynomial, x^8 + x^4 + x^3 + x + 1.
+fn exp_log_tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly = 1u16;
+    for (i, exp) in exp.iter_mut().enumerate() {
+        *exp = poly as u8;
+        log[poly as usize] = i as u8;
+        // Multiply by the generator x + 1.
+        poly = (poly << 1) ^ poly;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11b;
+        }
+    }
+    (exp, log)
+}
+
+/// Returns the value at `x` of the polynomial through the given shares, evaluated bytewise in GF(256).
+fn interpolate(shares: &[(u8, Vec<u8>)], x: u8) -> Result<Vec<u8>, Slip39Error> {
+    for (i, (index, _)) in shares.iter().enumerate() {
+        if shares[..i].iter().any(|(other, _)| other == index) {
+            return Err(Slip39Error::DuplicateMemberIndex(*index));
+        }
+    }
+    let length = shares[0].1.len();
+    if shares.iter().any(|(_, value)| value.len() != length) {
+        return Err(Slip39Error::MismatchedLength);
+    }
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return Ok(value.clone());
+    }
+
+    let (exp, log) = exp_log_tables();
+    let log_product: usize = shares.iter().map(|(index, _)| log[(index ^ x) as usize] as usize).sum();
+
+    let mut result = vec![0u8; length];
+    for (index, value) in shares {
+        // The logarithm of the Lagrange basis polynomial of this share, evaluated at x.
+        let log_denominator: usize = shares
+            .iter()
+            .map(|(other, _)| log[(index ^ other) as usize] as usize)
+            .sum();
+        let log_basis = (log_product + 255 * shares.len() - log[(index ^ x) as usize] as usize - log_denominator) % 255;
+
+        result.iter_mut().zip(value).for_each(|(result, byte)| {
+            if *byte != 0 {
+                *result ^= exp[(log[*byte as usize] as usize + log_basis) % 255];
+            }
+        });
+    }
+    Ok(result)
+}
+
+/// Returns the master secret encrypted with the given passphrase by a four-round Feistel cipher.
+fn encrypt(
+    master_secret: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Result<Vec<u8>, Slip39Error> {
+    feistel(
+        master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        0..ROUND_COUNT,
+    )
+}
+
+/// Returns the encrypted master secret decrypted with the given passphrase.
+fn decrypt(
+    encrypted_master_secret: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Result<Vec<u8>, Slip39Error> {
+    feistel(
+        encrypted_master_secret,
+        passphrase,
+        iteration_exponent,
+        identifier,
+        extendable,
+        (0..ROUND_COUNT).rev(),
+    )
+}
+
+/// Returns the given value after the Feistel rounds of the given round indices, with the halves swapped.
+fn feistel<I: Iterator<Item = u8>>(
+    value: &[u8],
+    passphrase: &str,
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+    rounds: I,
+) -> Result<Vec<u8>, Slip39Error> {
+    if !passphrase.bytes().all(|byte| (32..=126).contains(&byte)) {
+        return Err(Slip39Error::InvalidPassphrase);
+    }
+
+    // The salt of extendable shares omits the identifier, so that new shares may be split from the same master secret.
+    let mut salt = vec![];
+    if !extendable {
+        salt.extend(b"shamir");
+        salt.extend(&identifier.to_be_bytes());
+    }
+
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as usize;
+    let (left, right) = value.split_at(value.len() / 2);
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    for round in rounds {
+        let mut password = vec![round];
+        password.extend(passphrase.as_bytes());
+        let mut round_salt = salt.clone();
+        round_salt.extend(&right);
+
+        let mut output = vec![0u8; right.len()];
+        pbkdf2::<HmacSha256>(&password, &round_salt, iterations, &mut output);
+        let next = left.iter().zip(&output).map(|(left, output)| left ^ output).collect();
+        left = core::mem::replace(&mut right, next);
+    }
+
+    right.extend(left);
+    Ok(right)
+}
+
+/// Returns the RS1024 remainder of the given values.
+fn polymod(values: &[u16]) -> u32 {
+    const GENERATOR: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48, 0x21B1F890, 0x3F3F120,
+    ];
+    values.iter().fold(1u32, |checksum, value| {
+        let top = checksum >> 20;
+        let checksum = (checksum & 0xFFFFF) << 10 ^ *value as u32;
+        (0..10)
+            .filter(|i| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, i| checksum ^ GENERATOR[i])
+    })
+}
+
+/// Returns the customization string of the checksum.
+fn customization(extendable: bool) -> Vec<u16> {
+    let customization: &[u8] = match extendable {
+        true => b"shamir_extendable",
+        false => b"shamir",
+    };
+    customization.iter().map(|byte| *byte as u16).collect()
+}
+
+/// Returns the RS1024 checksum words of the given words.
+fn create_checksum(words: &[u16], extendable: bool) -> Vec<u16> {
+    let mut values = customization(extendable);
+    values.extend(words);
+    values.extend(&[0u16; CHECKSUM_LENGTH_WORDS]);
+    let checksum = polymod(&values) ^ 1;
+    (0..CHECKSUM_LENGTH_WORDS)
+        .rev()
+        .map(|i| ((checksum >> (RADIX_BITS * i)) & 1023) as u16)
+        .collect()
+}
+
+/// Returns `true` if the given words end with a valid RS1024 checksum.
+fn verify_checksum(words: &[u16], extendable: bool) -> bool {
+    let mut values = customization(extendable);
+    values.extend(words);
+    polymod(&values) == 1
+}
+
+/// Returns the given integer as `count` words, most significant word first.
+fn int_to_words(value: u32, count: usize) -> Vec<u16> {
+    (0..count)
+        .rev()
+        .map(|i| ((value >> (RADIX_BITS * i)) & 1023) as u16)
+        .collect()
+}
+
+/// Returns the integer of the given words, most significant word first.
+fn words_to_int(words: &[u16]) -> u32 {
+    words
+        .iter()
+        .fold(0u32, |value, word| value << RADIX_BITS | *word as u32)
+}
+
+/// Returns the given bytes as words, left-padded with zero bits to a whole number of words.
+fn bytes_to_words(bytes: &[u8]) -> Vec<u16> {
+    let padding = (RADIX_BITS - bytes.len() * 8 % RADIX_BITS) % RADIX_BITS;
+    let mut bits = vec![false; padding];
+    bits.extend(
+        bytes
+            .iter()
+            .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1)),
+    );
+    bits.chunks(RADIX_BITS)
+        .map(|chunk| chunk.iter().fold(0u16, |word, bit| word << 1 | *bit as u16))
+        .collect()
+}
+
+/// Returns the bytes of the given words, after verifying that the leading padding bits are zero.
+fn words_to_bytes(words: &[u16], padding: usize) -> Result<Vec<u8>, Slip39Error> {
+    let bits = words
+        .iter()
+        .flat_map(|word| (0..RADIX_BITS).rev().map(move |i| (word >> i) & 1 == 1))
+        .collect::<Vec<bool>>();
+    if bits[..padding].iter().any(|bit| *bit) {
+        return Err(Slip39Error::InvalidPadding);
+    }
+    Ok(bits[padding..]
+        .chunks(8)
+        .map(|chunk| chunk.iter().fold(0u8, |byte, bit| byte << 1 | *bit as u8))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    type N = Mainnet;
+
+    const PASSPHRASE: &str = "TREZOR";
+
+    // (description, mnemonics, master secret)
+    const VALID: [(&str, &[&str], &str); 4] = [
+        (
+            "valid mnemonic without sharing (128 bits)",
+            &["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard"],
+            "bb54aac4b89dc868ba37d9cc21b2cece",
+        ),
+        (
+            "basic sharing 2-of-3 (128 bits)",
+            &[
+                "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed",
+                "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking",
+            ],
+            "b43ceb7e57a0ea8766221624d01b0864",
+        ),
+        (
+            "valid mnemonic without sharing (256 bits)",
+            &["theory painting academic academic armed sweater year military elder discuss acne wildlife boring employer fused large satoshi bundle carbon diagnose anatomy hamster leaves tracks paces beyond phantom capital marvel lips brave detect luck"],
+            "989baf9dcaad5b10ca33dfd8cc75e42477025dce88ae83e75a230086a0e00e92",
+        ),
+        (
+            "basic sharing 2-of-3 (256 bits)",
+            &[
+                "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap",
+                "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade",
+            ],
+            "c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae",
+        ),
+    ];
+
+    #[test]
+    fn recover_valid() {
+        VALID.iter().for_each(|(description, mnemonics, expected)| {
+            assert_eq!(
+                *expected,
+                hex::encode(recover(mnemonics, PASSPHRASE).unwrap()),
+                "{}",
+                description
+            );
+        });
+    }
+
+    #[test]
+    fn from_slip39() {
+        let (_, mnemonics, master_secret) = VALID[0];
+        let extended_private_key =
+            BitcoinExtendedPrivateKey::<N>::from_slip39(mnemonics, PASSPHRASE, &BitcoinFormat::P2PKH).unwrap();
+        assert_eq!(
+            "xprv9s21ZrQH143K4QViKpwKCpS2zVbz8GrZgpEchMDg6KME9HZtjfL7iThE9w5muQA4YPHKN1u5VM1w8D4pvnjxa2BmpGMfXr7hnRrRHZ93awZ",
+            extended_private_key.to_string()
+        );
+        assert_eq!(
+            BitcoinExtendedPrivateKey::<N>::new_master(&hex::decode(master_secret).unwrap(), &BitcoinFormat::P2PKH)
+                .unwrap(),
+            extended_private_key
+        );
+    }
+
+    #[test]
+    fn recover_invalid() {
+        [
+            (
+                // Mnemonic with invalid checksum (128 bits)
+                vec!["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"],
+                Slip39Error::InvalidChecksum,
+            ),
+            (
+                // Mnemonic with invalid padding (128 bits)
+                vec!["duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"],
+                Slip39Error::InvalidPadding,
+            ),
+            (
+                // Basic sharing 2-of-3 with one share (128 bits)
+                vec!["shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed"],
+                Slip39Error::InsufficientShares(0, 2, 1),
+            ),
+            (
+                // Mnemonics with different identifiers (128 bits)
+                vec![
+                    "adequate smoking academic acid debut wine petition glen cluster slow rhyme slow simple epidemic rumor junk tracks treat olympic tolerate",
+                    "adequate stay academic agency agency formal party ting frequent learn upstairs remember smear leaf damage anatomy ladle market hush corner",
+                ],
+                Slip39Error::MismatchedIdentifier,
+            ),
+            (
+                vec!["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision"],
+                Slip39Error::InvalidMnemonicLength(19),
+            ),
+            (
+                vec!["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision abracadabra"],
+                Slip39Error::InvalidWord("abracadabra".into()),
+            ),
+            (vec![], Slip39Error::NoShares),
+        ]
+        .iter()
+        .for_each(|(mnemonics, expected)| {
+            assert_eq!(Err(expected.clone()), recover(mnemonics, PASSPHRASE).map(hex::encode));
+        });
+    }
+
+    #[test]
+    fn split_recover() {
+        let rng = &mut StdRng::seed_from_u64(1231275789u64);
+        let master_secret = hex::decode("bb54aac4b89dc868ba37d9cc21b2cece").unwrap();
+        let groups = split(rng, &master_secret, 2, &[(1, 1), (2, 3), (3, 5)], PASSPHRASE).unwrap();
+        assert_eq!(vec![1, 3, 5], groups.iter().map(Vec::len).collect::<Vec<_>>());
+
+        let recovered = |mnemonics: Vec<&String>| recover(&mnemonics, PASSPHRASE);
+        assert_eq!(
+            master_secret,
+            recovered(vec![&groups[0][0], &groups[1][0], &groups[1][2]]).unwrap()
+        );
+        assert_eq!(
+            master_secret,
+            recovered(vec![
+                &groups[2][4],
+                &groups[2][1],
+                &groups[2][0],
+                &groups[1][1],
+                &groups[1][2]
+            ])
+            .unwrap()
+        );
+        assert_eq!(
+            master_secret,
+            recovered(vec![
+                &groups[0][0],
+                &groups[0][0],
+                &groups[2][2],
+                &groups[2][1],
+                &groups[2][3]
+            ])
+            .unwrap()
+        );
+
+        assert_eq!(
+            Err(Slip39Error::InsufficientGroups(2, 1)),
+            recovered(vec![&groups[1][0], &groups[1][1]])
+        );
+        assert_eq!(
+            Err(Slip39Error::InsufficientShares(1, 2, 1)),
+            recovered(vec![&groups[0][0], &groups[1][1]])
+        );
+        assert_eq!(
+            Err(Slip39Error::TooManyShares(1, 2, 3)),
+            recovered(vec![&groups[0][0], &groups[1][0], &groups[1][1], &groups[1][2]])
+        );
+        assert_eq!(
+            Err(Slip39Error::TooManyGroups(2, 3)),
+            recovered(vec![&groups[0][0], &groups[1][0], &groups[1][1], &groups[2][0]])
+        );
+        assert_ne!(
+            master_secret,
+            recover(&[&groups[0][0], &groups[1][0], &groups[1][2]], "wrong passphrase").unwrap()
+        );
+
+        let other = split(rng, &master_secret, 2, &[(1, 1), (2, 3), (3, 5)], PASSPHRASE).unwrap();
+        assert_eq!(
+            Err(Slip39Error::MismatchedIdentifier),
+            recovered(vec![&groups[0][0], &other[1][0], &other[1][2]])
+        );
+    }
+
+    #[test]
+    fn split_invalid() {
+        let rng = &mut StdRng::seed_from_u64(1231275789u64);
+        let master_secret = [0u8; 16];
+        assert_eq!(
+            Err(Slip39Error::InvalidMasterSecretLength(15)),
+            split(rng, &master_secret[..15], 1, &[(1, 1)], "")
+        );
+        assert_eq!(
+            Err(Slip39Error::InvalidMasterSecretLength(14)),
+            split(rng, &master_secret[..14], 1, &[(1, 1)], "")
+        );
+        assert_eq!(
+            Err(Slip39Error::InvalidGroupThreshold(3, 2)),
+            split(rng, &master_secret, 3, &[(1, 1), (2, 3)], "")
+        );
+        assert_eq!(
+            Err(Slip39Error::InvalidMemberThreshold(1, 3)),
+            split(rng, &master_secret, 1, &[(1, 3)], "")
+        );
+        assert_eq!(
+            Err(Slip39Error::InvalidMemberThreshold(4, 3)),
+            split(rng, &master_secret, 1, &[(4, 3)], "")
+        );
+        assert_eq!(
+            Err(Slip39Error::InvalidShareCount(17)),
+            split(rng, &master_secret, 1, &[(2, 17)], "")
+        );
+        assert_eq!(
+            Err(Slip39Error::InvalidPassphrase),
+            split(rng, &master_secret, 1, &[(1, 1)], "pass\u{e9}")
+        );
+    }
+
+    #[test]
+    fn share_to_string() {
+        VALID.iter().for_each(|(_, mnemonics, _)| {
+            mnemonics.iter().for_each(|mnemonic| {
+                assert_eq!(*mnemonic, Share::from_str(mnemonic).unwrap().to_string());
+            });
+        });
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        /// Returns a strategy over shares of the given iteration exponents, with valid parameters
+        /// and an even share value of 16 to 32 bytes.
+        fn share(iteration_exponent: core::ops::Range<u8>) -> impl Strategy<Value = Share> {
+            (
+                (0..1u16 << 15, any::<bool>(), iteration_exponent),
+                (0..16u8, 1..=16u8, 0..16u8).prop_flat_map(|(group_index, group_threshold, member_index)| {
+                    (
+                        Just(group_index),
+                        Just(group_threshold),
+                        group_threshold..=16u8,
+                        Just(member_index),
+                        1..=16u8,
+                    )
+                }),
+                (8..=16usize).prop_flat_map(|length| vec(any::<u8>(), length * 2)),
+            )
+                .prop_map(
+                    |(
+                        (identifier, extendable, iteration_exponent),
+                        (group_index, group_threshold, group_count, member_index, member_threshold),
+                        value,
+                    )| Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index,
+                        group_threshold,
+                        group_count,
+                        member_index,
+                        member_threshold,
+                        value,
+                    },
+                )
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(share in share(0..16)) {
+                prop_assert_eq!(&share, &Share::from_str(&share.to_string()).unwrap());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<Share>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_words(words in vec(any::<prop::sample::Index>(), 0..40)) {
+                let wordlist = ENGLISH.lines().collect::<Vec<&str>>();
+                let mnemonic = words.iter().map(|index| *index.get(&wordlist)).collect::<Vec<&str>>().join(" ");
+                if let Ok(share) = Share::from_str(&mnemonic) {
+                    prop_assert_eq!(mnemonic, share.to_string());
+                }
+            }
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(32))]
+
+            #[test]
+            fn recover_arbitrary(shares in vec(share(0..1), 1..4)) {
+                let mnemonics = shares.iter().map(Share::to_string).collect::<Vec<String>>();
+                let _ = recover(&mnemonics, "");
+            }
+        }
+    }
+}
@@ -4,16 +4,19 @@ use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
-use crate::witness_program::WitnessProgram;
-use wagyu_model::no_std::{io::Read, *};
-use wagyu_model::{PrivateKey, Transaction, TransactionError, TransactionId};
+use wagyu_model::no_std::{
+    io::{Read, Write},
+    *,
+};
+use wagyu_model::{
+    crypto::{sha256d, Sha256dHasher},
+    PrivateKey, Transaction, TransactionError, TransactionId,
+};
 
 use base58::FromBase58;
 use bech32::{Bech32, FromBase32};
 use core::{fmt, str::FromStr};
-use secp256k1;
 use serde::Serialize;
-use sha2::{Digest, Sha256};
 
 /// Returns the variable length integer of the given value.
 /// https://en.bitcoin.it/wiki/Protocol_documentation#Variable_length_integer
@@ -34,13 +37,13 @@ pub fn variable_length_integer(value: u64) -> Result<Vec<u8>, TransactionError>
 /// https://en.bitcoin.it/wiki/Protocol_documentation#Variable_length_integer
 pub fn read_variable_length_integer<R: Read>(mut reader: R) -> Result<usize, TransactionError> {
     let mut flag = [0u8; 1];
-    reader.read(&mut flag)?;
+    reader.read_exact(&mut flag)?;
 
     match flag[0] {
         0..=252 => Ok(flag[0] as usize),
         0xfd => {
             let mut size = [0u8; 2];
-            reader.read(&mut size)?;
+            reader.read_exact(&mut size)?;
             match u16::from_le_bytes(size) {
                 s if s < 253 => return Err(TransactionError::InvalidVariableSizeInteger(s as usize)),
                 s => Ok(s as usize),
@@ -48,7 +51,7 @@ pub fn read_variable_length_integer<R: Read>(mut reader: R) -> Result<usize, Tra
         }
         0xfe => {
             let mut size = [0u8; 4];
-            reader.read(&mut size)?;
+            reader.read_exact(&mut size)?;
             match u32::from_le_bytes(size) {
                 s if s < 65536 => return Err(TransactionError::InvalidVariableSizeInteger(s as usize)),
                 s => Ok(s as usize),
@@ -56,7 +59,7 @@ pub fn read_variable_length_integer<R: Read>(mut reader: R) -> Result<usize, Tra
         }
         _ => {
             let mut size = [0u8; 8];
-            reader.read(&mut size)?;
+            reader.read_exact(&mut size)?;
             match u64::from_le_bytes(size) {
                 s if s < 4294967296 => return Err(TransactionError::InvalidVariableSizeInteger(s as usize)),
                 s => Ok(s as usize),
@@ -125,15 +128,7 @@ pub fn create_script_pub_key<N: BitcoinNetwork>(address: &BitcoinAddress<N>) ->
             script.push(Opcode::OP_EQUAL as u8);
             Ok(script)
         }
-        BitcoinFormat::Bech32 => {
-            let bech32 = Bech32::from_str(&address.to_string())?;
-            let (v, program) = bech32.data().split_at(1);
-            let program = Vec::from_base32(program)?;
-            let mut program_bytes = vec![v[0].to_u8(), program.len() as u8];
-            program_bytes.extend(program);
-
-            Ok(WitnessProgram::new(&program_bytes)?.to_scriptpubkey())
-        }
+        BitcoinFormat::Bech32 | BitcoinFormat::P2TR => Ok(address.to_witness_program()?.to_scriptpubkey()),
     }
 }
 
@@ -276,6 +271,10 @@ impl<N: BitcoinNetwork> Outpoint<N> {
                         true => return Err(TransactionError::InvalidInputs("Bech32".into())),
                         false => None,
                     },
+                    BitcoinFormat::P2TR => match redeem_script.is_some() {
+                        true => return Err(TransactionError::InvalidInputs("P2TR".into())),
+                        false => None,
+                    },
                 };
 
                 (Some(script_pub_key), redeem_script)
@@ -366,8 +365,8 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
         let mut vin = [0u8; 4];
         let mut sequence = [0u8; 4];
 
-        reader.read(&mut transaction_hash)?;
-        reader.read(&mut vin)?;
+        reader.read_exact(&mut transaction_hash)?;
+        reader.read_exact(&mut vin)?;
 
         let outpoint = Outpoint::<N>::new(
             transaction_hash.to_vec(),
@@ -380,16 +379,19 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
 
         let script_sig: Vec<u8> = BitcoinVector::read(&mut reader, |s| {
             let mut byte = [0u8; 1];
-            s.read(&mut byte)?;
+            s.read_exact(&mut byte)?;
             Ok(byte[0])
         })?;
 
-        reader.read(&mut sequence)?;
+        reader.read_exact(&mut sequence)?;
 
-        let script_sig_len = read_variable_length_integer(&script_sig[..])?;
+        let script_sig_len = match script_sig.is_empty() {
+            true => 0,
+            false => read_variable_length_integer(&script_sig[..])?,
+        };
         let sighash_code = SignatureHash::from_byte(&match script_sig_len {
             0 => 0x01,
-            length => script_sig[length],
+            length => *script_sig.get(length).unwrap_or(&0x01),
         });
 
         Ok(Self {
@@ -464,11 +466,11 @@ impl BitcoinTransactionOutput {
     /// Read and output a Bitcoin transaction output
     pub fn read<R: Read>(mut reader: &mut R) -> Result<Self, TransactionError> {
         let mut amount = [0u8; 8];
-        reader.read(&mut amount)?;
+        reader.read_exact(&mut amount)?;
 
         let script_pub_key: Vec<u8> = BitcoinVector::read(&mut reader, |s| {
             let mut byte = [0u8; 1];
-            s.read(&mut byte)?;
+            s.read_exact(&mut byte)?;
             Ok(byte[0])
         })?;
 
@@ -523,14 +525,14 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
     /// Read and output the Bitcoin transaction parameters
     pub fn read<R: Read>(mut reader: R) -> Result<Self, TransactionError> {
         let mut version = [0u8; 4];
-        reader.read(&mut version)?;
+        reader.read_exact(&mut version)?;
 
         let mut inputs = BitcoinVector::read(&mut reader, BitcoinTransactionInput::<N>::read)?;
 
         let segwit_flag = match inputs.is_empty() {
             true => {
                 let mut flag = [0u8; 1];
-                reader.read(&mut flag)?;
+                reader.read_exact(&mut flag)?;
                 match flag[0] {
                     1 => {
                         inputs = BitcoinVector::read(&mut reader, BitcoinTransactionInput::<N>::read)?;
@@ -549,7 +551,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
                 let witnesses: Vec<Vec<u8>> = BitcoinVector::read(&mut reader, |s| {
                     let (size, witness) = BitcoinVector::read_witness(s, |sr| {
                         let mut byte = [0u8; 1];
-                        sr.read(&mut byte)?;
+                        sr.read_exact(&mut byte)?;
                         Ok(byte[0])
                     })?;
 
@@ -565,7 +567,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
         }
 
         let mut lock_time = [0u8; 4];
-        reader.read(&mut lock_time)?;
+        reader.read_exact(&mut lock_time)?;
 
         let transaction_parameters = BitcoinTransactionParameters::<N> {
             version: u32::from_le_bytes(version),
@@ -579,6 +581,17 @@ impl<N: BitcoinNetwork> BitcoinTransactionParameters<N> {
     }
 }
 
+/// Represents the digests of a transaction shared by the SegWit hash preimages of its inputs
+/// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#specification
+struct SegwitHashes {
+    /// The double SHA-256 of the serialized outpoints of all inputs
+    hash_prev_outputs: Vec<u8>,
+    /// The double SHA-256 of the sequences of all inputs
+    hash_sequence: Vec<u8>,
+    /// The double SHA-256 of the serialized outputs
+    hash_outputs: Vec<u8>,
+}
+
 /// Represents a Bitcoin transaction
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BitcoinTransaction<N: BitcoinNetwork> {
@@ -604,6 +617,8 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
     /// Returns a signed transaction given the private key of the sender.
     fn sign(&self, private_key: &Self::PrivateKey) -> Result<Self, TransactionError> {
         let mut transaction = self.clone();
+        let mut has_signer_input = false;
+        let segwit_hashes = self.segwit_hashes()?;
         for (vin, input) in self.parameters.inputs.iter().enumerate() {
             let address = match &input.outpoint.address {
                 Some(address) => address,
@@ -622,19 +637,21 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                 _ => address == &private_key.to_address(&address.format())?,
             };
 
+            has_signer_input |= address_is_valid;
+
             if address_is_valid && !transaction.parameters.inputs[vin].is_signed {
                 // Transaction hash
-                let preimage = match &address.format() {
-                    BitcoinFormat::P2PKH => transaction.p2pkh_hash_preimage(vin, input.sighash_code)?,
-                    _ => transaction.segwit_hash_preimage(vin, input.sighash_code)?,
+                let transaction_hash = match &address.format() {
+                    BitcoinFormat::P2PKH => transaction.p2pkh_hash(vin, input.sighash_code)?,
+                    _ => sha256d(&transaction.segwit_hash_preimage_with_hashes(
+                        vin,
+                        input.sighash_code,
+                        &segwit_hashes,
+                    )?),
                 };
-                let transaction_hash = Sha256::digest(&Sha256::digest(&preimage));
 
                 // Signature
-                let (signature, _) = secp256k1::sign(
-                    &secp256k1::Message::parse_slice(&transaction_hash)?,
-                    &private_key.to_secp256k1_secret_key(),
-                );
+                let signature = private_key.sign(&transaction_hash)?;
                 let mut signature = signature.serialize_der().as_ref().to_vec();
                 signature.push((input.sighash_code as u32).to_le_bytes()[0]);
                 let signature = [variable_length_integer(signature.len() as u64)?, signature].concat();
@@ -710,10 +727,13 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                             .append(&mut vec![signature.clone(), public_key]);
                         transaction.parameters.inputs[vin].is_signed = true;
                     }
+                    BitcoinFormat::P2TR => return Err(TransactionError::UnsupportedPreimage("P2TR".into())),
                 };
             }
         }
-        // TODO: (raychu86) Raise error if no input was signed
+        if !has_signer_input {
+            return Err(TransactionError::MissingSignerInput);
+        }
         Ok(transaction)
     }
 
@@ -768,8 +788,8 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
 
     /// Returns the transaction id.
     fn to_transaction_id(&self) -> Result<Self::TransactionId, TransactionError> {
-        let mut txid = Sha256::digest(&Sha256::digest(&self.to_transaction_bytes_without_witness()?)).to_vec();
-        let mut wtxid = Sha256::digest(&Sha256::digest(&self.to_transaction_bytes()?)).to_vec();
+        let mut txid = sha256d(&self.to_transaction_bytes_without_witness()?).to_vec();
+        let mut wtxid = sha256d(&self.to_transaction_bytes()?).to_vec();
 
         txid.reverse();
         wtxid.reverse();
@@ -781,37 +801,78 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
 impl<N: BitcoinNetwork> BitcoinTransaction<N> {
     /// Return the P2PKH hash preimage of the raw transaction.
     pub fn p2pkh_hash_preimage(&self, vin: usize, sighash: SignatureHash) -> Result<Vec<u8>, TransactionError> {
-        let mut preimage = self.parameters.version.to_le_bytes().to_vec();
-        preimage.extend(variable_length_integer(self.parameters.inputs.len() as u64)?);
+        let mut preimage = vec![];
+        self.write_p2pkh_hash_preimage(vin, sighash, &mut preimage)?;
+        Ok(preimage)
+    }
+
+    /// Returns the double SHA-256 of the P2PKH hash preimage of the raw transaction, hashed as it is
+    /// serialized, as the preimage serializes every input and output of the transaction.
+    pub fn p2pkh_hash(&self, vin: usize, sighash: SignatureHash) -> Result<[u8; 32], TransactionError> {
+        let mut hasher = Sha256dHasher::new();
+        self.write_p2pkh_hash_preimage(vin, sighash, &mut hasher)?;
+        Ok(hasher.finalize())
+    }
+
+    /// Writes the P2PKH hash preimage of the raw transaction to the given writer.
+    fn write_p2pkh_hash_preimage<W: Write>(
+        &self,
+        vin: usize,
+        sighash: SignatureHash,
+        writer: &mut W,
+    ) -> Result<(), TransactionError> {
+        writer.write_all(&self.parameters.version.to_le_bytes())?;
+        writer.write_all(&variable_length_integer(self.parameters.inputs.len() as u64)?)?;
         for (index, input) in self.parameters.inputs.iter().enumerate() {
-            preimage.extend(input.serialize(index != vin)?);
+            writer.write_all(&input.serialize(index != vin)?)?;
         }
-        preimage.extend(variable_length_integer(self.parameters.outputs.len() as u64)?);
+        writer.write_all(&variable_length_integer(self.parameters.outputs.len() as u64)?)?;
         for output in &self.parameters.outputs {
-            preimage.extend(output.serialize()?);
+            writer.write_all(&output.serialize()?)?;
         }
-        preimage.extend(&self.parameters.lock_time.to_le_bytes());
-        preimage.extend(&(sighash as u32).to_le_bytes());
-        Ok(preimage)
+        writer.write_all(&self.parameters.lock_time.to_le_bytes())?;
+        writer.write_all(&(sighash as u32).to_le_bytes())?;
+        Ok(())
     }
 
     /// Return the SegWit hash preimage of the raw transaction
     /// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#specification
     pub fn segwit_hash_preimage(&self, vin: usize, sighash: SignatureHash) -> Result<Vec<u8>, TransactionError> {
-        let mut prev_outputs = vec![];
-        let mut prev_sequences = vec![];
-        let mut outputs = vec![];
+        self.segwit_hash_preimage_with_hashes(vin, sighash, &self.segwit_hashes()?)
+    }
+
+    /// Returns the double SHA-256 digests of the outpoints, sequences, and outputs of the transaction,
+    /// which are shared by the SegWit hash preimages of every input.
+    fn segwit_hashes(&self) -> Result<SegwitHashes, TransactionError> {
+        let mut prev_outputs = Sha256dHasher::new();
+        let mut prev_sequences = Sha256dHasher::new();
+        let mut outputs = Sha256dHasher::new();
 
         for input in &self.parameters.inputs {
-            prev_outputs.extend(&input.outpoint.reverse_transaction_id);
-            prev_outputs.extend(&input.outpoint.index.to_le_bytes());
-            prev_sequences.extend(&input.sequence);
+            prev_outputs
+                .update(&input.outpoint.reverse_transaction_id)
+                .update(&input.outpoint.index.to_le_bytes());
+            prev_sequences.update(&input.sequence);
         }
 
         for output in &self.parameters.outputs {
-            outputs.extend(&output.serialize()?);
+            outputs.update(&output.serialize()?);
         }
 
+        Ok(SegwitHashes {
+            hash_prev_outputs: prev_outputs.finalize().to_vec(),
+            hash_sequence: prev_sequences.finalize().to_vec(),
+            hash_outputs: outputs.finalize().to_vec(),
+        })
+    }
+
+    /// Returns the SegWit hash preimage of the raw transaction, given the precomputed digests.
+    fn segwit_hash_preimage_with_hashes(
+        &self,
+        vin: usize,
+        sighash: SignatureHash,
+        hashes: &SegwitHashes,
+    ) -> Result<Vec<u8>, TransactionError> {
         let input = &self.parameters.inputs[vin];
         let format = match &input.outpoint.address {
             Some(address) => address.format(),
@@ -832,6 +893,8 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
                 None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
             },
             BitcoinFormat::P2PKH => return Err(TransactionError::UnsupportedPreimage("P2PKH".into())),
+            // Taproot inputs are signed with the BIP341 signature hash, which is not supported.
+            BitcoinFormat::P2TR => return Err(TransactionError::UnsupportedPreimage("P2TR".into())),
         };
 
         let mut script_code = vec![];
@@ -845,9 +908,27 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
             script_code.push(Opcode::OP_CHECKSIG as u8);
         }
         let script_code = [variable_length_integer(script_code.len() as u64)?, script_code].concat();
-        let hash_prev_outputs = Sha256::digest(&Sha256::digest(&prev_outputs));
-        let hash_sequence = Sha256::digest(&Sha256::digest(&prev_sequences));
-        let hash_outputs = Sha256::digest(&Sha256::digest(&outputs));
+
+        // The digests commit to nothing (32 zero bytes) for the parts of the transaction
+        // that the signature hash type leaves open to modification.
+        let anyone_can_pay = (sighash as u32) & 0x80 != 0;
+        let base_sighash = (sighash as u32) & 0x1f;
+        let is_single = base_sighash == SignatureHash::SIGHASH_SINGLE as u32;
+        let is_none = base_sighash == SignatureHash::SIGHASH_NONE as u32;
+
+        let hash_prev_outputs = match anyone_can_pay {
+            true => vec![0u8; 32],
+            false => hashes.hash_prev_outputs.clone(),
+        };
+        let hash_sequence = match anyone_can_pay || is_single || is_none {
+            true => vec![0u8; 32],
+            false => hashes.hash_sequence.clone(),
+        };
+        let hash_outputs = match self.parameters.outputs.get(vin) {
+            _ if !is_single && !is_none => hashes.hash_outputs.clone(),
+            Some(output) if is_single => sha256d(&output.serialize()?).to_vec(),
+            _ => vec![0u8; 32],
+        };
         let outpoint_amount = match &input.outpoint.amount {
             Some(amount) => amount.0.to_le_bytes(),
             None => return Err(TransactionError::MissingOutpointAmount),
@@ -855,22 +936,35 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
 
         let mut preimage = vec![];
         preimage.extend(&self.parameters.version.to_le_bytes());
-        preimage.extend(hash_prev_outputs);
-        preimage.extend(hash_sequence);
+        preimage.extend(&hash_prev_outputs);
+        preimage.extend(&hash_sequence);
         preimage.extend(&input.outpoint.reverse_transaction_id);
         preimage.extend(&input.outpoint.index.to_le_bytes());
         preimage.extend(&script_code);
         preimage.extend(&outpoint_amount);
         preimage.extend(&input.sequence);
-        preimage.extend(hash_outputs);
+        preimage.extend(&hash_outputs);
         preimage.extend(&self.parameters.lock_time.to_le_bytes());
         preimage.extend(&(sighash as u32).to_le_bytes());
 
         Ok(preimage)
     }
 
+    /// Returns the weight of the transaction in weight units.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0141.mediawiki#transaction-size-calculations
+    pub fn weight(&self) -> Result<usize, TransactionError> {
+        let base_size = self.to_transaction_bytes_without_witness()?.len();
+        let total_size = self.to_transaction_bytes()?.len();
+        Ok(base_size * 3 + total_size)
+    }
+
+    /// Returns the virtual size of the transaction in virtual bytes.
+    pub fn virtual_size(&self) -> Result<usize, TransactionError> {
+        Ok(self.weight()?.div_ceil(4))
+    }
+
     /// Returns the transaction with the traditional serialization (no witness).
-    fn to_transaction_bytes_without_witness(&self) -> Result<Vec<u8>, TransactionError> {
+    pub(crate) fn to_transaction_bytes_without_witness(&self) -> Result<Vec<u8>, TransactionError> {
         let mut transaction = self.parameters.version.to_le_bytes().to_vec();
 
         transaction.extend(variable_length_integer(self.parameters.inputs.len() as u64)?);
@@ -911,6 +1005,19 @@ impl<N: BitcoinNetwork> FromStr for BitcoinTransaction<N> {
     }
 }
 
+impl<N: BitcoinNetwork> fmt::Display for BitcoinTransaction<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            &hex::encode(match self.to_transaction_bytes() {
+                Ok(transaction) => transaction,
+                _ => return Err(fmt::Error),
+            })
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1444,7 +1551,7 @@ mod tests {
                 ],
                 outputs: &[
                     Output {
-                        address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx", // witness version 1
+                        address: "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y", // witness version 1
                         amount: BitcoinAmount(100000000)
                     },
                     Output {
@@ -1515,6 +1622,151 @@ mod tests {
             });
         }
 
+        /// Returns the unsigned transaction and the P2WPKH private key of the native P2WPKH example
+        /// https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki#native-p2wpkh
+        fn native_p2wpkh_transaction() -> (BitcoinTransaction<N>, BitcoinPrivateKey<N>) {
+            let secret_key = hex::decode("619c335025c7f4012e556c2a58b2506e30b8511b53ade95ea316fd8c3286feb9").unwrap();
+            let secret_key = secp256k1::SecretKey::parse_slice(&secret_key).unwrap();
+            let private_key = BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, true);
+
+            let inputs = vec![
+                BitcoinTransactionInput::<N>::new(
+                    hex::decode("9f96ade4b41d5433f4eda31e1738ec2b36f6e7d1420d94a6af99801a88f7f7ff").unwrap(),
+                    0,
+                    None,
+                    Some(BitcoinAmount(625000000)),
+                    None,
+                    None,
+                    Some(vec![0xee, 0xff, 0xff, 0xff]),
+                    SignatureHash::SIGHASH_ALL,
+                )
+                .unwrap(),
+                BitcoinTransactionInput::<N>::new(
+                    hex::decode("8ac60eb9575db5b2d987e29f301b5b819ea83a5c6579d282d189cc04b8e151ef").unwrap(),
+                    1,
+                    Some(private_key.to_address(&BitcoinFormat::Bech32).unwrap()),
+                    Some(BitcoinAmount(600000000)),
+                    None,
+                    Some(hex::decode("00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1").unwrap()),
+                    None,
+                    SignatureHash::SIGHASH_ALL,
+                )
+                .unwrap(),
+            ];
+            let outputs = vec![
+                BitcoinTransactionOutput {
+                    amount: BitcoinAmount(112340000),
+                    script_pub_key: hex::decode("76a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac").unwrap(),
+                },
+                BitcoinTransactionOutput {
+                    amount: BitcoinAmount(223450000),
+                    script_pub_key: hex::decode("76a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac").unwrap(),
+                },
+            ];
+
+            let transaction = BitcoinTransaction::<N>::new(&BitcoinTransactionParameters::<N> {
+                version: 1,
+                inputs,
+                outputs,
+                lock_time: 17,
+                segwit_flag: false,
+            })
+            .unwrap();
+
+            (transaction, private_key)
+        }
+
+        #[test]
+        fn test_native_p2wpkh_transaction() {
+            let (transaction, private_key) = native_p2wpkh_transaction();
+
+            let preimage = transaction.segwit_hash_preimage(1, SignatureHash::SIGHASH_ALL).unwrap();
+            assert_eq!(
+                "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670",
+                hex::encode(sha256d(&preimage))
+            );
+
+            // The first input is a P2PK output, signed with the signature published in BIP143.
+            let mut transaction = transaction.sign(&private_key).unwrap();
+            transaction.parameters.inputs[0].script_sig = hex::decode("4830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01").unwrap();
+            transaction.parameters.inputs[0].is_signed = true;
+
+            assert_eq!(
+                "01000000000102fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000494830450221008b9d1dc26ba6a9cb62127b02742fa9d754cd3bebf337f7a55d114c8e5cdd30be022040529b194ba3f9281a99f2b1c0a19c0489bc22ede944ccf4ecbab4cc618ef3ed01eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac000247304402203609e17b84f6a7d30c80bfa610b5b4542f32a8a0d5447a12fb1366d7f01cc44a0220573a954c4518331561406f90300e8f3358f51928d43c212a8caed02de67eebee0121025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee635711000000",
+                transaction.to_string()
+            );
+
+            let transaction_id = transaction.to_transaction_id().unwrap();
+            assert_eq!(
+                "e8151a2af31c368a35053ddd4bdb285a8595c769a3ad83e0fa02314a602d4609",
+                transaction_id.to_string()
+            );
+            assert_eq!(
+                "c36c38370907df2324d9ce9d149d191192f338b37665a82e78e76a12c909b762",
+                hex::encode(&transaction_id.wtxid)
+            );
+            assert_eq!(1042, transaction.weight().unwrap());
+            assert_eq!(261, transaction.virtual_size().unwrap());
+        }
+
+        #[test]
+        fn test_segwit_hash_preimage_sighash_types() {
+            let (transaction, _) = native_p2wpkh_transaction();
+            let zero = [0u8; 32];
+            let all = transaction.segwit_hash_preimage(1, SignatureHash::SIGHASH_ALL).unwrap();
+
+            // The preimage is version (4) | hashPrevouts (32) | hashSequence (32) | outpoint (36) |
+            // scriptCode (26) | amount (8) | sequence (4) | hashOutputs (32) | lock time (4) | sighash (4)
+            let hash_prev_outputs = |preimage: &Vec<u8>| preimage[4..36].to_vec();
+            let hash_sequence = |preimage: &Vec<u8>| preimage[36..68].to_vec();
+            let hash_outputs = |preimage: &Vec<u8>| preimage[preimage.len() - 40..preimage.len() - 8].to_vec();
+
+            let none = transaction
+                .segwit_hash_preimage(1, SignatureHash::SIGHASH_NONE)
+                .unwrap();
+            assert_eq!(hash_prev_outputs(&all), hash_prev_outputs(&none));
+            assert_eq!(zero.to_vec(), hash_sequence(&none));
+            assert_eq!(zero.to_vec(), hash_outputs(&none));
+
+            let single = transaction
+                .segwit_hash_preimage(1, SignatureHash::SIGHASH_SINGLE)
+                .unwrap();
+            let output = transaction.parameters.outputs[1].serialize().unwrap();
+            assert_eq!(zero.to_vec(), hash_sequence(&single));
+            assert_eq!(sha256d(&output).to_vec(), hash_outputs(&single));
+
+            let anyone_can_pay = SignatureHash::SIGHASH_ALL_SIGHASH_ANYONECANPAY;
+            let all_anyone_can_pay = transaction.segwit_hash_preimage(1, anyone_can_pay).unwrap();
+            assert_eq!(zero.to_vec(), hash_prev_outputs(&all_anyone_can_pay));
+            assert_eq!(zero.to_vec(), hash_sequence(&all_anyone_can_pay));
+            assert_eq!(hash_outputs(&all), hash_outputs(&all_anyone_can_pay));
+        }
+
+        #[test]
+        fn test_p2pkh_hash() {
+            let (transaction, _) = native_p2wpkh_transaction();
+            let sighashes = [
+                SignatureHash::SIGHASH_ALL,
+                SignatureHash::SIGHASH_NONE,
+                SignatureHash::SIGHASH_SINGLE_SIGHASH_ANYONECANPAY,
+            ];
+            for vin in 0..2 {
+                for sighash in &sighashes {
+                    let preimage = transaction.p2pkh_hash_preimage(vin, *sighash).unwrap();
+                    assert_eq!(sha256d(&preimage), transaction.p2pkh_hash(vin, *sighash).unwrap());
+                }
+            }
+        }
+
+        #[test]
+        fn test_mainnet_transaction_display() {
+            TRANSACTIONS.iter().for_each(|transaction| {
+                let expected_signed_transaction = transaction.expected_signed_transaction;
+                let transaction = BitcoinTransaction::<N>::from_str(expected_signed_transaction).unwrap();
+                assert_eq!(expected_signed_transaction, transaction.to_string());
+            });
+        }
+
         #[test]
         fn test_reconstructed_mainnet_transactions() {
             TRANSACTIONS.iter().for_each(|transaction| {
@@ -1848,6 +2100,42 @@ mod tests {
                 }
             }
         }
+
+        #[test]
+        fn test_invalid_signer() {
+            let private_key =
+                BitcoinPrivateKey::<N>::from_str("L1uyy5qTuGrVXrmrsvHWHgVzW9kKdrp27wBC7Vs6nZDTF2BRUVwy").unwrap();
+            let input = BitcoinTransactionInput::<N>::new(
+                hex::decode("61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d").unwrap(),
+                0,
+                Some(private_key.to_address(&BitcoinFormat::P2PKH).unwrap()),
+                Some(BitcoinAmount(0)),
+                None,
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )
+            .unwrap();
+            let address = BitcoinAddress::<N>::from_str("1cMh228HTCiwS8ZsaakH8A8wze1JR5ZsP").unwrap();
+            let output = BitcoinTransactionOutput::new(&address, BitcoinAmount(12000)).unwrap();
+
+            let transaction = BitcoinTransaction::<N>::new(&BitcoinTransactionParameters::<N> {
+                version: 1,
+                inputs: vec![input],
+                outputs: vec![output],
+                lock_time: 0,
+                segwit_flag: false,
+            })
+            .unwrap();
+
+            let other_private_key =
+                BitcoinPrivateKey::<N>::from_str("L5BsLN6keEWUuF1JxfG6w5U1FDHs29faMpr9QX2MMVuQt7ymTorX").unwrap();
+            match transaction.sign(&other_private_key) {
+                Err(TransactionError::MissingSignerInput) => {}
+                result => panic!("unexpected result: {:?}", result.map(|_| ())),
+            };
+            assert!(transaction.sign(&private_key).is_ok());
+        }
     }
 
     mod test_multisig_mainnet_transactions {
@@ -1974,4 +2262,105 @@ mod tests {
             });
         }
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        type N = Mainnet;
+
+        /// Returns a strategy over serialized transactions, with a witness on the first input of
+        /// each SegWit transaction. The script signatures begin with a push opcode, as their first
+        /// byte is read as the length of the signature.
+        fn transaction() -> impl Strategy<Value = Vec<u8>> {
+            let input = (
+                any::<[u8; 32]>(),
+                any::<u32>(),
+                proptest::option::of((0..0xfdu8, vec(any::<u8>(), 0..16))),
+                any::<u32>(),
+                vec(vec(any::<u8>(), 0..16), 0..3),
+            );
+            let output = (0..21_000_000 * 100_000_000u64, vec(any::<u8>(), 0..40));
+            (
+                any::<u32>(),
+                vec(input, 1..4),
+                vec(output, 0..4),
+                any::<bool>(),
+                any::<u32>(),
+            )
+                .prop_map(|(version, mut inputs, outputs, segwit, lock_time)| {
+                    if segwit && inputs[0].4.is_empty() {
+                        inputs[0].4.push(vec![0x01]);
+                    }
+
+                    let mut transaction = version.to_le_bytes().to_vec();
+                    if segwit {
+                        transaction.extend(&[0x00, 0x01]);
+                    }
+                    transaction.extend(variable_length_integer(inputs.len() as u64).unwrap());
+                    for (hash, index, script_sig, sequence, _) in &inputs {
+                        transaction.extend(&hash[..]);
+                        transaction.extend(&index.to_le_bytes());
+                        let script_sig = match script_sig {
+                            Some((opcode, script)) => [&[*opcode], &script[..]].concat(),
+                            None => vec![],
+                        };
+                        transaction.extend(variable_length_integer(script_sig.len() as u64).unwrap());
+                        transaction.extend(script_sig);
+                        transaction.extend(&sequence.to_le_bytes());
+                    }
+                    transaction.extend(variable_length_integer(outputs.len() as u64).unwrap());
+                    for (amount, script_pub_key) in &outputs {
+                        transaction.extend(&amount.to_le_bytes());
+                        transaction.extend(variable_length_integer(script_pub_key.len() as u64).unwrap());
+                        transaction.extend(script_pub_key);
+                    }
+                    if segwit {
+                        for (_, _, _, _, witnesses) in &inputs {
+                            transaction.extend(variable_length_integer(witnesses.len() as u64).unwrap());
+                            for witness in witnesses {
+                                transaction.extend(variable_length_integer(witness.len() as u64).unwrap());
+                                transaction.extend(witness);
+                            }
+                        }
+                    }
+                    transaction.extend(&lock_time.to_le_bytes());
+                    transaction
+                })
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(transaction in transaction()) {
+                let parsed = BitcoinTransaction::<N>::from_str(&hex::encode(&transaction)).unwrap();
+                prop_assert_eq!(&transaction, &parsed.to_transaction_bytes().unwrap());
+                prop_assert_eq!(&parsed, &BitcoinTransaction::from_str(&parsed.to_string()).unwrap());
+            }
+
+            #[test]
+            fn from_str_truncated(transaction in transaction(), length: prop::sample::Index) {
+                let length = length.index(transaction.len());
+                prop_assert!(BitcoinTransaction::<N>::from_str(&hex::encode(&transaction[..length])).is_err());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<BitcoinTransaction<N>>(&inp)?;
+            }
+
+            #[test]
+            fn from_transaction_bytes_arbitrary(transaction in vec(any::<u8>(), 0..200)) {
+                let _ = BitcoinTransaction::<N>::from_transaction_bytes(&transaction);
+            }
+
+            #[test]
+            fn read_variable_length_integer_arbitrary(data in vec(any::<u8>(), 0..10)) {
+                if let Ok(value) = read_variable_length_integer(&data[..]) {
+                    let encoded = variable_length_integer(value as u64).unwrap();
+                    prop_assert_eq!(&data[..encoded.len()], &encoded[..]);
+                }
+            }
+        }
+    }
 }
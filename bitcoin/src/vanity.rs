@@ -0,0 +1,388 @@
+//!
+//! Vanity
+//!
+//! This module contains a multi-threaded search for Bitcoin addresses that begin with a
+//! requested pattern, e.g. `1Boat` or `bc1qme`.
+//!
+//! Every worker thread generates random private keys, derives the address in the requested
+//! format, and stops as soon as any worker finds a match or the search is cancelled.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::format::BitcoinFormat;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use wagyu_model::base58::encode_check;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, PrivateKey};
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    mpsc, Arc,
+};
+use std::thread;
+
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+#[derive(Debug, Fail)]
+pub enum VanityError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "vanity search cancelled after {} attempts", _0)]
+    Cancelled(u64),
+
+    #[fail(display = "empty vanity pattern")]
+    EmptyPattern,
+
+    #[fail(display = "invalid character {:?} at position {} for the requested format", _0, _1)]
+    InvalidCharacter(char, usize),
+
+    #[fail(display = "invalid vanity prefix: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidPrefix(String, String),
+
+    #[fail(display = "invalid number of threads: {}", _0)]
+    InvalidThreadCount(usize),
+
+    #[fail(display = "unsupported format: {:?}", _0)]
+    UnsupportedFormat(BitcoinFormat),
+}
+
+impl From<AddressError> for VanityError {
+    fn from(error: AddressError) -> Self {
+        VanityError::AddressError(error)
+    }
+}
+
+/// Represents an address prefix to search for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    /// The address prefix
+    prefix: String,
+    /// If `true`, the prefix must match exactly
+    case_sensitive: bool,
+}
+
+impl Pattern {
+    /// Returns a pattern matching addresses that begin with the given prefix.
+    pub fn prefix(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.into(),
+            case_sensitive: true,
+        }
+    }
+
+    /// Returns a pattern matching addresses that begin with the given prefix, ignoring case.
+    pub fn prefix_case_insensitive(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.into(),
+            case_sensitive: false,
+        }
+    }
+
+    /// Returns `true` if the given address begins with the pattern.
+    pub fn matches(&self, address: &str) -> bool {
+        match address.get(..self.prefix.len()) {
+            Some(start) if self.case_sensitive => start == self.prefix,
+            Some(start) => start.eq_ignore_ascii_case(&self.prefix),
+            None => false,
+        }
+    }
+
+    /// Returns `Ok(())` if addresses of the given network and format can begin with the pattern.
+    pub fn validate<N: BitcoinNetwork>(&self, format: &BitcoinFormat) -> Result<(), VanityError> {
+        if self.prefix.is_empty() {
+            return Err(VanityError::EmptyPattern);
+        }
+
+        match format {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH => self.validate_base58::<N>(format),
+            BitcoinFormat::Bech32 => self.validate_bech32::<N>(),
+            BitcoinFormat::P2WSH | BitcoinFormat::P2TR => Err(VanityError::UnsupportedFormat(format.clone())),
+        }
+    }
+
+    /// Checks the pattern against the base58 alphabet and the leading characters
+    /// implied by the version byte of the format.
+    fn validate_base58<N: BitcoinNetwork>(&self, format: &BitcoinFormat) -> Result<(), VanityError> {
+        let is_valid = |c: char| match self.case_sensitive {
+            true => BASE58_ALPHABET.contains(c),
+            false => {
+                BASE58_ALPHABET.contains(c.to_ascii_lowercase()) || BASE58_ALPHABET.contains(c.to_ascii_uppercase())
+            }
+        };
+
+        if let Some((position, c)) = self.prefix.chars().enumerate().find(|(_, c)| !is_valid(*c)) {
+            return Err(VanityError::InvalidCharacter(c, position));
+        }
+
+        // The smallest and largest payloads bound the first character of every address.
+        let version = N::to_address_prefix(format)[0];
+        let mut payload = [0u8; 21];
+        payload[0] = version;
+        let first = encode_check(&payload).chars().next().unwrap_or('1');
+        payload[1..].copy_from_slice(&[0xff; 20]);
+        let last = encode_check(&payload).chars().next().unwrap_or('1');
+
+        let start = BASE58_ALPHABET.find(first).unwrap_or(0);
+        let end = BASE58_ALPHABET.find(last).unwrap_or(0);
+        let leading = &BASE58_ALPHABET[start..=end];
+
+        let c = self.prefix.chars().next().unwrap_or_default();
+        let is_leading = match self.case_sensitive {
+            true => leading.contains(c),
+            false => leading.contains(c.to_ascii_lowercase()) || leading.contains(c.to_ascii_uppercase()),
+        };
+        match is_leading {
+            true => Ok(()),
+            false => Err(VanityError::InvalidPrefix(leading.into(), c.to_string())),
+        }
+    }
+
+    /// Checks the pattern against the human-readable part, separator, and witness version,
+    /// followed by the bech32 character set.
+    fn validate_bech32<N: BitcoinNetwork>(&self) -> Result<(), VanityError> {
+        // Bech32 addresses are lowercase, so a case-sensitive pattern must be as well.
+        let prefix = match self.case_sensitive {
+            true => self.prefix.clone(),
+            false => self.prefix.to_lowercase(),
+        };
+
+        let hrp = String::from_utf8(N::to_address_prefix(&BitcoinFormat::Bech32)).map_err(AddressError::from)?;
+        let leading = format!("{}1q", hrp);
+        if !leading.starts_with(&prefix) && !prefix.starts_with(&leading) {
+            return Err(VanityError::InvalidPrefix(leading, prefix));
+        }
+
+        match prefix
+            .chars()
+            .enumerate()
+            .skip(leading.len())
+            .find(|(_, c)| !BECH32_CHARSET.contains(*c))
+        {
+            Some((position, c)) => Err(VanityError::InvalidCharacter(c, position)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Represents a handle to cancel a running vanity search
+#[derive(Debug, Clone, Default)]
+pub struct CancellationHandle(Arc<AtomicBool>);
+
+impl CancellationHandle {
+    /// Returns a new cancellation handle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that every search using this handle stops.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the search has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Represents the first match of a vanity search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VanityResult<N: BitcoinNetwork> {
+    /// The private key of the matching address
+    pub private_key: BitcoinPrivateKey<N>,
+    /// The matching address
+    pub address: BitcoinAddress<N>,
+    /// The number of keys generated across all threads
+    pub attempts: u64,
+}
+
+/// Returns the first address of the given format matching the pattern, searching with the
+/// given number of threads.
+pub fn search<N: BitcoinNetwork>(
+    pattern: &Pattern,
+    format: &BitcoinFormat,
+    threads: usize,
+) -> Result<VanityResult<N>, VanityError> {
+    search_with_cancellation(pattern, format, threads, &CancellationHandle::new())
+}
+
+/// Returns the first address of the given format matching the pattern, searching with the
+/// given number of threads until a match is found or the handle is cancelled.
+pub fn search_with_cancellation<N: BitcoinNetwork>(
+    pattern: &Pattern,
+    format: &BitcoinFormat,
+    threads: usize,
+    handle: &CancellationHandle,
+) -> Result<VanityResult<N>, VanityError> {
+    if threads == 0 {
+        return Err(VanityError::InvalidThreadCount(threads));
+    }
+    pattern.validate::<N>(format)?;
+
+    let attempts = Arc::new(AtomicU64::new(0));
+    let found = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel();
+
+    let workers = (0..threads)
+        .map(|_| {
+            let (pattern, format, handle) = (pattern.clone(), format.clone(), handle.clone());
+            let (attempts, found, sender) = (attempts.clone(), found.clone(), sender.clone());
+            thread::spawn(move || {
+                let rng = &mut rand::thread_rng();
+                while !found.load(Ordering::SeqCst) && !handle.is_cancelled() {
+                    let private_key = BitcoinPrivateKey::<N>::new_random(rng);
+                    attempts.fetch_add(1, Ordering::Relaxed);
+
+                    let address = match private_key.to_address(&format) {
+                        Ok(address) => address,
+                        Err(error) => {
+                            found.store(true, Ordering::SeqCst);
+                            let _ = sender.send(Err(error));
+                            return;
+                        }
+                    };
+
+                    if pattern.matches(&address.to_string()) {
+                        found.store(true, Ordering::SeqCst);
+                        let _ = sender.send(Ok((private_key, address)));
+                        return;
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Every worker holds its own sender, so the channel closes once they all stop.
+    drop(sender);
+    let result = receiver.recv();
+    found.store(true, Ordering::SeqCst);
+    workers.into_iter().for_each(|worker| {
+        let _ = worker.join();
+    });
+
+    let attempts = attempts.load(Ordering::SeqCst);
+    match result {
+        Ok(Ok((private_key, address))) => Ok(VanityResult {
+            private_key,
+            address,
+            attempts,
+        }),
+        Ok(Err(error)) => Err(error.into()),
+        Err(_) => Err(VanityError::Cancelled(attempts)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    fn test_search<N: BitcoinNetwork>(pattern: &Pattern, format: &BitcoinFormat) {
+        let result = search::<N>(pattern, format, 2).unwrap();
+        assert!(pattern.matches(&result.address.to_string()));
+        assert_eq!(result.address, result.private_key.to_address(format).unwrap());
+        assert!(result.attempts > 0);
+    }
+
+    #[test]
+    fn search_p2pkh() {
+        test_search::<Mainnet>(&Pattern::prefix("1A"), &BitcoinFormat::P2PKH);
+        test_search::<Mainnet>(&Pattern::prefix_case_insensitive("1a"), &BitcoinFormat::P2PKH);
+        test_search::<Testnet>(&Pattern::prefix("m"), &BitcoinFormat::P2PKH);
+    }
+
+    #[test]
+    fn search_p2sh_p2wpkh() {
+        test_search::<Mainnet>(&Pattern::prefix("3"), &BitcoinFormat::P2SH_P2WPKH);
+    }
+
+    #[test]
+    fn search_bech32() {
+        test_search::<Mainnet>(&Pattern::prefix("bc1qq"), &BitcoinFormat::Bech32);
+        test_search::<Mainnet>(&Pattern::prefix_case_insensitive("BC1QQ"), &BitcoinFormat::Bech32);
+        test_search::<Testnet>(&Pattern::prefix("tb"), &BitcoinFormat::Bech32);
+    }
+
+    #[test]
+    fn search_cancelled() {
+        let handle = CancellationHandle::new();
+        handle.cancel();
+        let pattern = Pattern::prefix("1zzzzzzzzzzzzzzzzzzzz");
+        let result = search_with_cancellation::<Mainnet>(&pattern, &BitcoinFormat::P2PKH, 2, &handle);
+        assert!(matches!(result, Err(VanityError::Cancelled(_))));
+    }
+
+    #[test]
+    fn search_invalid_thread_count() {
+        let result = search::<Mainnet>(&Pattern::prefix("1"), &BitcoinFormat::P2PKH, 0);
+        assert!(matches!(result, Err(VanityError::InvalidThreadCount(0))));
+    }
+
+    #[test]
+    fn validate() {
+        let valid = [
+            ("1Boat", true, BitcoinFormat::P2PKH),
+            ("1boat", false, BitcoinFormat::P2PKH),
+            ("3", true, BitcoinFormat::P2SH_P2WPKH),
+            ("bc", true, BitcoinFormat::Bech32),
+            ("bc1qme", true, BitcoinFormat::Bech32),
+            ("BC1QME", false, BitcoinFormat::Bech32),
+        ];
+        valid.iter().for_each(|(prefix, case_sensitive, format)| {
+            let pattern = match case_sensitive {
+                true => Pattern::prefix(prefix),
+                false => Pattern::prefix_case_insensitive(prefix),
+            };
+            assert!(pattern.validate::<Mainnet>(format).is_ok());
+        });
+    }
+
+    #[test]
+    fn validate_invalid() {
+        let validate = |pattern: Pattern, format: BitcoinFormat| pattern.validate::<Mainnet>(&format).unwrap_err();
+
+        assert!(matches!(
+            validate(Pattern::prefix(""), BitcoinFormat::P2PKH),
+            VanityError::EmptyPattern
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("10"), BitcoinFormat::P2PKH),
+            VanityError::InvalidCharacter('0', 1)
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("1Boatl"), BitcoinFormat::P2PKH),
+            VanityError::InvalidCharacter('l', 5)
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("3"), BitcoinFormat::P2PKH),
+            VanityError::InvalidPrefix(_, _)
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("tb1q"), BitcoinFormat::Bech32),
+            VanityError::InvalidPrefix(_, _)
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("BC1QME"), BitcoinFormat::Bech32),
+            VanityError::InvalidPrefix(_, _)
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("bc1qb"), BitcoinFormat::Bech32),
+            VanityError::InvalidCharacter('b', 4)
+        ));
+        assert!(matches!(
+            validate(Pattern::prefix("bc1"), BitcoinFormat::P2WSH),
+            VanityError::UnsupportedFormat(BitcoinFormat::P2WSH)
+        ));
+    }
+
+    #[test]
+    fn matches() {
+        assert!(Pattern::prefix("1Boat").matches("1BoatSLRHtKNngkdXEeobR76b53LETtpyT"));
+        assert!(!Pattern::prefix("1boat").matches("1BoatSLRHtKNngkdXEeobR76b53LETtpyT"));
+        assert!(Pattern::prefix_case_insensitive("1boat").matches("1BoatSLRHtKNngkdXEeobR76b53LETtpyT"));
+        assert!(!Pattern::prefix("1BoatSLRHtKNngkdXEeobR76b53LETtpyTT").matches("1BoatSLRHtKNngkdXEeobR76b53LETtpyT"));
+    }
+}
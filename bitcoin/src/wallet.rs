@@ -0,0 +1,322 @@
+//!
+//! Wallet
+//!
+//! This module bundles a Bitcoin private key with its public key, address, and, for
+//! hierarchical deterministic wallets, the mnemonic and derivation path it came from.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::derivation_path::BitcoinDerivationPath;
+use crate::extended_private_key::BitcoinExtendedPrivateKey;
+use crate::format::BitcoinFormat;
+use crate::mnemonic::BitcoinMnemonic;
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use crate::wordlist::BitcoinWordlist;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, ExtendedPrivateKey, ExtendedPrivateKeyError, MnemonicError, PrivateKey, PublicKey};
+
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+#[derive(Debug, Fail)]
+pub enum WalletError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "{}", _0)]
+    ExtendedPrivateKeyError(ExtendedPrivateKeyError),
+
+    #[fail(display = "{}", _0)]
+    MnemonicError(MnemonicError),
+}
+
+impl From<AddressError> for WalletError {
+    fn from(error: AddressError) -> Self {
+        WalletError::AddressError(error)
+    }
+}
+
+impl From<ExtendedPrivateKeyError> for WalletError {
+    fn from(error: ExtendedPrivateKeyError) -> Self {
+        WalletError::ExtendedPrivateKeyError(error)
+    }
+}
+
+impl From<MnemonicError> for WalletError {
+    fn from(error: MnemonicError) -> Self {
+        WalletError::MnemonicError(error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::error::Error> for WalletError {
+    fn from(error: serde_json::error::Error) -> Self {
+        WalletError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+/// Represents a Bitcoin wallet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinWallet<N: BitcoinNetwork> {
+    /// The mnemonic phrase, if the wallet was derived from a mnemonic
+    mnemonic: Option<String>,
+    /// The extended private key, if the wallet was derived from an extended private key
+    extended_private_key: Option<BitcoinExtendedPrivateKey<N>>,
+    /// The derivation path, if the wallet was derived from an extended private key
+    path: Option<BitcoinDerivationPath<N>>,
+    /// The private key
+    private_key: BitcoinPrivateKey<N>,
+    /// The public key
+    public_key: BitcoinPublicKey<N>,
+    /// The address
+    address: BitcoinAddress<N>,
+}
+
+/// Represents the serialized fields of a Bitcoin wallet
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct BitcoinWalletJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extended_private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extended_public_key: Option<String>,
+    private_key: String,
+    public_key: String,
+    address: String,
+    format: String,
+    network: String,
+    compressed: bool,
+}
+
+impl<N: BitcoinNetwork> BitcoinWallet<N> {
+    /// Returns the wallet of the given private key, with an address of the given format.
+    pub fn from_private_key(private_key: &BitcoinPrivateKey<N>, format: &BitcoinFormat) -> Result<Self, WalletError> {
+        let public_key = private_key.to_public_key();
+        let address = public_key.to_address(format)?;
+        Ok(Self {
+            mnemonic: None,
+            extended_private_key: None,
+            path: None,
+            private_key: private_key.clone(),
+            public_key,
+            address,
+        })
+    }
+
+    /// Returns the wallet of the given derivation path, derived from the seed of the given
+    /// mnemonic and password, with an address of the given format.
+    pub fn from_mnemonic<W: BitcoinWordlist>(
+        mnemonic: &BitcoinMnemonic<N, W>,
+        password: Option<&str>,
+        path: &BitcoinDerivationPath<N>,
+        format: &BitcoinFormat,
+    ) -> Result<Self, WalletError> {
+        let master_extended_private_key = BitcoinExtendedPrivateKey::<N>::from_mnemonic(mnemonic, password, format)?;
+        Ok(Self {
+            mnemonic: Some(mnemonic.to_string()),
+            ..Self::from_extended_private_key(&master_extended_private_key, path, format)?
+        })
+    }
+
+    /// Returns the wallet of the given derivation path, derived from the given extended
+    /// private key, with an address of the given format.
+    pub fn from_extended_private_key(
+        extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        path: &BitcoinDerivationPath<N>,
+        format: &BitcoinFormat,
+    ) -> Result<Self, WalletError> {
+        let extended_private_key = extended_private_key.derive(path)?;
+        Ok(Self {
+            extended_private_key: Some(extended_private_key.clone()),
+            path: Some(path.clone()),
+            ..Self::from_private_key(&extended_private_key.to_private_key(), format)?
+        })
+    }
+
+    /// Returns the mnemonic phrase of the wallet, if it was derived from a mnemonic.
+    pub fn mnemonic(&self) -> Option<&str> {
+        self.mnemonic.as_deref()
+    }
+
+    /// Returns the extended private key of the wallet, if it was derived from one.
+    pub fn extended_private_key(&self) -> Option<&BitcoinExtendedPrivateKey<N>> {
+        self.extended_private_key.as_ref()
+    }
+
+    /// Returns the derivation path of the wallet, if it was derived from an extended private key.
+    pub fn path(&self) -> Option<&BitcoinDerivationPath<N>> {
+        self.path.as_ref()
+    }
+
+    /// Returns the private key of the wallet.
+    pub fn private_key(&self) -> &BitcoinPrivateKey<N> {
+        &self.private_key
+    }
+
+    /// Returns the public key of the wallet.
+    pub fn public_key(&self) -> &BitcoinPublicKey<N> {
+        &self.public_key
+    }
+
+    /// Returns the address of the wallet.
+    pub fn address(&self) -> &BitcoinAddress<N> {
+        &self.address
+    }
+
+    /// Returns the address of the wallet in the given format.
+    pub fn to_address(&self, format: &BitcoinFormat) -> Result<BitcoinAddress<N>, AddressError> {
+        self.public_key.to_address(format)
+    }
+
+    /// Returns the private key of the wallet in wallet import format.
+    pub fn to_wif(&self) -> String {
+        self.private_key.to_string()
+    }
+
+    /// Returns the wallet as a JSON string, including its private keys in clear text.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, WalletError> {
+        let wallet = BitcoinWalletJson {
+            path: self.path.as_ref().map(|path| path.to_string()),
+            mnemonic: self.mnemonic.clone(),
+            extended_private_key: self.extended_private_key.as_ref().map(|key| key.to_string()),
+            extended_public_key: self
+                .extended_private_key
+                .as_ref()
+                .map(|key| key.to_extended_public_key().to_string()),
+            private_key: self.to_wif(),
+            public_key: self.public_key.to_string(),
+            address: self.address.to_string(),
+            format: self.address.format().to_string(),
+            network: N::NAME.to_string(),
+            compressed: self.private_key.is_compressed(),
+        };
+        Ok(serde_json::to_string_pretty(&wallet)?)
+    }
+}
+
+impl<N: BitcoinNetwork> fmt::Display for BitcoinWallet<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(path) = &self.path {
+            writeln!(f, "Path                 {}", path)?;
+        }
+        if let Some(mnemonic) = &self.mnemonic {
+            writeln!(f, "Mnemonic             {}", mnemonic)?;
+        }
+        if let Some(extended_private_key) = &self.extended_private_key {
+            writeln!(f, "Extended Private Key {}", extended_private_key)?;
+            writeln!(
+                f,
+                "Extended Public Key  {}",
+                extended_private_key.to_extended_public_key()
+            )?;
+        }
+        writeln!(f, "Private Key          {}", self.private_key)?;
+        writeln!(f, "Public Key           {}", self.public_key)?;
+        writeln!(f, "Address              {}", self.address)?;
+        writeln!(f, "Format               {}", self.address.format())?;
+        writeln!(f, "Network              {}", N::NAME)?;
+        write!(f, "Compressed           {}", self.private_key.is_compressed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::wordlist::English;
+    use wagyu_model::Mnemonic;
+
+    use core::str::FromStr;
+
+    type N = Mainnet;
+    type W = English;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const PATH: &str = "m/84'/0'/0'/0/0";
+    const ADDRESS: &str = "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
+    const WIF: &str = "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d";
+
+    fn wallets() -> Vec<BitcoinWallet<N>> {
+        let format = &BitcoinFormat::Bech32;
+        let path = BitcoinDerivationPath::<N>::from_str(PATH).unwrap();
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(PHRASE).unwrap();
+        let master_extended_private_key =
+            BitcoinExtendedPrivateKey::<N>::from_mnemonic(&mnemonic, None, format).unwrap();
+        let private_key = BitcoinPrivateKey::<N>::from_str(WIF).unwrap();
+
+        vec![
+            BitcoinWallet::from_mnemonic(&mnemonic, None, &path, format).unwrap(),
+            BitcoinWallet::from_extended_private_key(&master_extended_private_key, &path, format).unwrap(),
+            BitcoinWallet::from_private_key(&private_key, format).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn test_wallets() {
+        wallets().iter().for_each(|wallet| {
+            assert_eq!(ADDRESS, wallet.address().to_string());
+            assert_eq!(WIF, wallet.to_wif());
+        });
+    }
+
+    #[test]
+    fn test_accessors() {
+        let wallets = wallets();
+
+        assert_eq!(Some(PHRASE), wallets[0].mnemonic());
+        assert_eq!(PATH, wallets[0].path().unwrap().to_string());
+        assert_eq!(wallets[0].extended_private_key(), wallets[1].extended_private_key());
+        assert_eq!(None, wallets[1].mnemonic());
+        assert_eq!(None, wallets[2].path());
+        assert_eq!(None, wallets[2].extended_private_key());
+
+        let address = wallets[2].to_address(&BitcoinFormat::P2PKH).unwrap();
+        assert_eq!(BitcoinFormat::P2PKH, address.format());
+        assert_eq!(
+            ADDRESS,
+            wallets[2].to_address(&BitcoinFormat::Bech32).unwrap().to_string()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json() {
+        let wallets = wallets();
+
+        let json: serde_json::Value = serde_json::from_str(&wallets[0].to_json().unwrap()).unwrap();
+        assert_eq!(PHRASE, json["mnemonic"]);
+        assert_eq!(PATH, json["path"]);
+        assert_eq!(WIF, json["private_key"]);
+        assert_eq!(ADDRESS, json["address"]);
+        assert_eq!("bech32", json["format"]);
+
+        let json: serde_json::Value = serde_json::from_str(&wallets[2].to_json().unwrap()).unwrap();
+        assert!(json.get("mnemonic").is_none());
+        assert!(json.get("path").is_none());
+        assert_eq!(ADDRESS, json["address"]);
+    }
+
+    #[test]
+    fn test_display() {
+        let wallet = wallets()[0].to_string();
+        assert!(wallet.contains(&format!("Mnemonic             {}", PHRASE)));
+        assert!(wallet.contains(&format!("Private Key          {}", WIF)));
+        assert!(wallet.contains(&format!("Address              {}", ADDRESS)));
+
+        let wallet = wallets()[2].to_string();
+        assert!(!wallet.contains("Mnemonic"));
+        assert!(wallet.starts_with("Private Key"));
+    }
+}
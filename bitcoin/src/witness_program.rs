@@ -23,7 +23,7 @@
 use wagyu_model::no_std::*;
 use wagyu_model::{AddressError, TransactionError};
 
-use core::str::FromStr;
+use core::{fmt, str::FromStr};
 
 #[derive(Debug, Fail, PartialEq, Eq)]
 pub enum WitnessProgramError {
@@ -124,6 +124,17 @@ impl WitnessProgram {
     }
 }
 
+impl fmt::Display for WitnessProgram {
+    /// Returns the hex representation of the version, data size, and data of the witness program.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:02x}{:02x}", self.version, self.program.len())?;
+        for byte in &self.program {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
 impl FromStr for WitnessProgram {
     type Err = WitnessProgramError;
 
@@ -351,4 +362,41 @@ mod tests {
             }
         }
     }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+        use wagyu_model::utilities::test_utils::test_from_str_round_trip;
+
+        proptest! {
+            #[test]
+            fn from_str_hex(version in 0..=16u8, program in vec(any::<u8>(), 2..=40)) {
+                let mut data = vec![version, program.len() as u8];
+                data.extend(&program);
+
+                let valid = version != 0 || program.len() == 20 || program.len() == 32;
+                match WitnessProgram::from_str(&hex::encode(&data)) {
+                    Ok(witness_program) => {
+                        prop_assert!(valid);
+                        prop_assert_eq!(version, witness_program.version);
+                        prop_assert_eq!(program, witness_program.program);
+                    }
+                    Err(error) => prop_assert_eq!(
+                        WitnessProgramError::InvalidProgramLengthForVersion(program.len(), version),
+                        error
+                    ),
+                }
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<WitnessProgram>(&inp)?;
+            }
+
+            #[test]
+            fn new_arbitrary(data in vec(any::<u8>(), 0..50)) {
+                let _ = WitnessProgram::new(&data);
+            }
+        }
+    }
 }
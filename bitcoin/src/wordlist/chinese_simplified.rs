@@ -1,4 +1,4 @@
-use crate::wordlist::BitcoinWordlist;
+use crate::wordlist::{BitcoinWordlist, Language};
 use wagyu_model::{bip39::CHINESE_SIMPLIFIED, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,6 +9,9 @@ impl Wordlist for ChineseSimplified {}
 impl BitcoinWordlist for ChineseSimplified {
     /// The wordlist in original form.
     const WORDLIST: &'static str = CHINESE_SIMPLIFIED;
+
+    /// The language of the wordlist.
+    const LANGUAGE: Option<Language> = Some(Language::ChineseSimplified);
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::wordlist::BitcoinWordlist;
+use crate::wordlist::{BitcoinWordlist, Language};
 use wagyu_model::{bip39::CHINESE_TRADITIONAL, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,6 +9,9 @@ impl Wordlist for ChineseTraditional {}
 impl BitcoinWordlist for ChineseTraditional {
     /// The wordlist in original form.
     const WORDLIST: &'static str = CHINESE_TRADITIONAL;
+
+    /// The language of the wordlist.
+    const LANGUAGE: Option<Language> = Some(Language::ChineseTraditional);
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::wordlist::BitcoinWordlist;
+use crate::wordlist::{BitcoinWordlist, Language};
 use wagyu_model::{bip39::ENGLISH, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,6 +9,9 @@ impl Wordlist for English {}
 impl BitcoinWordlist for English {
     /// The wordlist in original form.
     const WORDLIST: &'static str = ENGLISH;
+
+    /// The language of the wordlist.
+    const LANGUAGE: Option<Language> = Some(Language::English);
 }
 
 #[cfg(test)]
@@ -42,4 +45,12 @@ mod tests {
         assert_eq!(2048, list.len());
         assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
     }
+
+    #[test]
+    fn suggest() {
+        assert!(English::suggest(INVALID_WORD).is_empty());
+        assert_eq!(vec!["deposit"], English::suggest("depo"));
+        assert_eq!(vec!["about", "above"], English::suggest("abo"));
+        assert_eq!(2048, English::suggest("").len());
+    }
 }
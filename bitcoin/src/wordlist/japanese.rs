@@ -1,4 +1,4 @@
-use crate::wordlist::BitcoinWordlist;
+use crate::wordlist::{BitcoinWordlist, Language};
 use wagyu_model::{bip39::JAPANESE, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,6 +9,12 @@ impl Wordlist for Japanese {}
 impl BitcoinWordlist for Japanese {
     /// The wordlist in original form.
     const WORDLIST: &'static str = JAPANESE;
+
+    /// The language of the wordlist.
+    const LANGUAGE: Option<Language> = Some(Language::Japanese);
+
+    /// The separator between words of a phrase, an ideographic space.
+    const SEPARATOR: &'static str = "\u{3000}";
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::wordlist::BitcoinWordlist;
+use crate::wordlist::{BitcoinWordlist, Language};
 use wagyu_model::{bip39::KOREAN, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,6 +9,9 @@ impl Wordlist for Korean {}
 impl BitcoinWordlist for Korean {
     /// The wordlist in original form.
     const WORDLIST: &'static str = KOREAN;
+
+    /// The language of the wordlist.
+    const LANGUAGE: Option<Language> = Some(Language::Korean);
 }
 
 #[cfg(test)]
@@ -1,4 +1,4 @@
-use crate::wordlist::BitcoinWordlist;
+use crate::wordlist::{BitcoinWordlist, Language};
 use wagyu_model::{bip39::SPANISH, wordlist::Wordlist};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -9,6 +9,9 @@ impl Wordlist for Spanish {}
 impl BitcoinWordlist for Spanish {
     /// The wordlist in original form.
     const WORDLIST: &'static str = SPANISH;
+
+    /// The language of the wordlist.
+    const LANGUAGE: Option<Language> = Some(Language::Spanish);
 }
 
 #[cfg(test)]
@@ -42,4 +45,12 @@ mod tests {
         assert_eq!(2048, list.len());
         assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
     }
+
+    #[test]
+    fn normalization() {
+        // The composed (NFC) form of the word, as most keyboards type it
+        assert_eq!(VALID_WORD_INDEX, Spanish::get_index("az\u{fa}car").unwrap());
+        assert_eq!(vec![VALID_WORD], Spanish::suggest("az\u{fa}"));
+        assert_eq!(vec![VALID_WORD, "azufre", "azul"], Spanish::suggest("azu"));
+    }
 }
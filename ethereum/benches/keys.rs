@@ -0,0 +1,47 @@
+use wagyu_ethereum::{EthereumAddress, EthereumDerivationPath, EthereumExtendedPrivateKey, EthereumFormat, Mainnet};
+use wagyu_model::{Address, ExtendedPrivateKey};
+
+use core::str::FromStr;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+// Baseline, in microseconds on an x86-64 Linux machine. Save a baseline of your own with
+// `cargo bench --bench keys -- --save-baseline <name>` and compare a change against it with
+// `cargo bench --bench keys -- --baseline <name>`.
+//
+//   address/from_private_key                62
+//   address/from_public_key                  6.3
+//   address/derive_bip44                   545
+
+const EXTENDED_PRIVATE_KEY: &str =
+    "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+/// Benchmarks the address derivation of a private key and of a BIP44 account path, apart from
+/// the address encoding of a public key, which is only the Keccak-256 hash and checksum.
+fn address(c: &mut Criterion) {
+    let extended_private_key = EthereumExtendedPrivateKey::<Mainnet>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+    let private_key = extended_private_key.to_private_key();
+    let public_key = extended_private_key.to_public_key();
+    let path = EthereumDerivationPath::from_str("m/44'/60'/0'/0/0").unwrap();
+    let format = &EthereumFormat::Standard;
+
+    let mut group = c.benchmark_group("address");
+    group.bench_function("from_private_key", |b| {
+        b.iter(|| EthereumAddress::from_private_key(black_box(&private_key), format).unwrap())
+    });
+    group.bench_function("from_public_key", |b| {
+        b.iter(|| EthereumAddress::from_public_key(black_box(&public_key), format).unwrap())
+    });
+    group.bench_function("derive_bip44", |b| {
+        b.iter(|| {
+            extended_private_key
+                .derive(black_box(&path))
+                .unwrap()
+                .to_address(format)
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, address);
+criterion_main!(benches);
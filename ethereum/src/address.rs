@@ -1,12 +1,14 @@
 use crate::format::EthereumFormat;
+use crate::message::{message_hash, parse_signature};
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
-use wagyu_model::{to_hex_string, Address, AddressError, PrivateKey};
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+use wagyu_model::{to_hex_string, Address, AddressError, PrivateKey, PublicKey};
 
 use core::{convert::TryFrom, fmt, str::FromStr};
 use regex::Regex;
 use serde::Serialize;
-use tiny_keccak::keccak256;
 
 /// Represents an Ethereum address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Hash)]
@@ -32,9 +34,75 @@ impl EthereumAddress {
     /// Returns the checksum address given a public key.
     /// Adheres to EIP-55 (https://eips.ethereum.org/EIPS/eip-55).
     pub fn checksum_address(public_key: &EthereumPublicKey) -> Self {
-        let hash = keccak256(&public_key.to_secp256k1_public_key().serialize()[1..]);
-        let address = to_hex_string(&hash[12..]).to_lowercase();
+        let hash = public_key.to_keccak256();
+        EthereumAddress(Self::checksum(&to_hex_string(&hash[12..]).to_lowercase()))
+    }
+
+    /// Returns the EIP-55 mixed-case checksum encoding of the address, e.g. 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed.
+    pub fn to_checksum_address(&self) -> String {
+        self.0.clone()
+    }
+
+    /// Returns `true` if the given r || s || v signature of the message was produced by the key
+    /// behind this address, as checked by `personal_ecRecover`.
+    pub fn verify_message(&self, message: &[u8], signature: &[u8]) -> Result<bool, AddressError> {
+        let (signature, recovery_id) = parse_signature(signature)?;
+        let message = secp256k1::Message::parse(&message_hash(message));
+        let public_key = match secp256k1::recover(&message, &signature, &recovery_id) {
+            Ok(public_key) => EthereumPublicKey::from_secp256k1_public_key(public_key),
+            Err(_) => return Ok(false),
+        };
+
+        Ok(*self == public_key.to_address(&EthereumFormat::Standard)?)
+    }
+
+    /// Returns the checksum status and bytes of the given address string, with or without a 0x prefix.
+    /// Unlike `from_str`, a mixed-case address that does not match its EIP-55 checksum is classified
+    /// rather than rejected.
+    pub fn classify(address: &str) -> Result<EthereumAddressInfo, AddressError> {
+        let hex = match address.get(0..2) {
+            Some("0x") | Some("0X") => &address[2..],
+            _ => address,
+        };
+
+        if hex.len() != 40 {
+            return Err(AddressError::InvalidCharacterLength(hex.len()));
+        }
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AddressError::InvalidAddress(address.into()));
+        }
+
+        let is_lowercase = !hex.chars().any(|c| c.is_ascii_uppercase());
+        let is_uppercase = !hex.chars().any(|c| c.is_ascii_lowercase());
+        let checksum_address = Self::checksum(&hex.to_lowercase());
+        let checksum = match (is_lowercase || is_uppercase, hex == &checksum_address[2..]) {
+            (true, _) => ChecksumStatus::Absent,
+            (false, true) => ChecksumStatus::Valid,
+            (false, false) => ChecksumStatus::Invalid,
+        };
+
+        let mut payload = [0u8; 20];
+        hex::decode_to_slice(hex, &mut payload)?;
+
+        Ok(EthereumAddressInfo {
+            address: EthereumAddress(checksum_address),
+            checksum,
+            payload,
+        })
+    }
 
+    /// Returns the classification of each of the given address strings, in order, without stopping at the first invalid address.
+    pub fn validate_batch<I: IntoIterator<Item = S>, S: AsRef<str>>(
+        addresses: I,
+    ) -> Vec<Result<EthereumAddressInfo, AddressError>> {
+        addresses
+            .into_iter()
+            .map(|address| Self::classify(address.as_ref()))
+            .collect()
+    }
+
+    /// Returns the EIP-55 checksum encoding, with a 0x prefix, of the given lowercase hex address.
+    fn checksum(address: &str) -> String {
         let hash = to_hex_string(&keccak256(address.as_bytes()));
         let mut checksum_address = "0x".to_string();
         for c in 0..40 {
@@ -44,11 +112,32 @@ impl EthereumAddress {
             };
             checksum_address.push_str(&ch);
         }
-
-        EthereumAddress(checksum_address)
+        checksum_address
     }
 }
 
+/// Represents the EIP-55 checksum status of an Ethereum address string
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChecksumStatus {
+    /// The address is mixed-case and matches its checksum
+    Valid,
+    /// The address is all-lowercase or all-uppercase, so it has no checksum
+    Absent,
+    /// The address is mixed-case and does not match its checksum
+    Invalid,
+}
+
+/// Represents the classification of an Ethereum address string
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EthereumAddressInfo {
+    /// The address, in its checksum encoding
+    pub address: EthereumAddress,
+    /// The checksum status of the address string
+    pub checksum: ChecksumStatus,
+    /// The 20 bytes of the address
+    pub payload: [u8; 20],
+}
+
 impl<'a> TryFrom<&'a str> for EthereumAddress {
     type Error = AddressError;
 
@@ -60,23 +149,29 @@ impl<'a> TryFrom<&'a str> for EthereumAddress {
 impl FromStr for EthereumAddress {
     type Err = AddressError;
 
+    /// Returns an Ethereum address from a hex string, with or without a 0x prefix.
+    /// Mixed-case addresses must match their EIP-55 checksum, while all-lowercase
+    /// and all-uppercase addresses are accepted as is.
     fn from_str(address: &str) -> Result<Self, Self::Err> {
-        let regex = Regex::new(r"^0x").unwrap();
-        let address = address.to_lowercase();
-        let address = regex.replace_all(&address, "").to_string();
+        let regex = Regex::new(r"^0[xX]").unwrap();
+        let offset = address.len() - regex.replace(address, "").len();
+        let hex = &address[offset..];
 
-        if address.len() != 40 {
-            return Err(AddressError::InvalidCharacterLength(address.len()));
+        if hex.len() != 40 {
+            return Err(AddressError::InvalidCharacterLength(hex.len()));
+        }
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(AddressError::InvalidAddress(address.into()));
         }
 
-        let hash = to_hex_string(&keccak256(address.as_bytes()));
-        let mut checksum_address = "0x".to_string();
-        for c in 0..40 {
-            let ch = match &hash[c..=c] {
-                "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" => address[c..=c].to_lowercase(),
-                _ => address[c..=c].to_uppercase(),
-            };
-            checksum_address.push_str(&ch);
+        let checksum_address = Self::checksum(&hex.to_lowercase());
+
+        let is_lowercase = !hex.chars().any(|c| c.is_ascii_uppercase());
+        let is_uppercase = !hex.chars().any(|c| c.is_ascii_lowercase());
+        if !is_lowercase && !is_uppercase {
+            if let Some(position) = hex.chars().zip(checksum_address[2..].chars()).position(|(a, b)| a != b) {
+                return Err(AddressError::ChecksumMismatch(offset + position));
+            }
         }
 
         Ok(EthereumAddress(checksum_address))
@@ -85,7 +180,7 @@ impl FromStr for EthereumAddress {
 
 impl fmt::Display for EthereumAddress {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.to_checksum_address())
     }
 }
 
@@ -172,6 +267,88 @@ mod tests {
         }
     }
 
+    mod verify_message {
+        use super::*;
+
+        const ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+        const SIGNATURE: &str = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+
+        #[test]
+        fn verify_message() {
+            let address = EthereumAddress::from_str(ADDRESS).unwrap();
+            let mut signature = hex::decode(SIGNATURE).unwrap();
+            assert!(address.verify_message(b"Some data", &signature).unwrap());
+
+            signature[64] -= 27;
+            assert!(address.verify_message(b"Some data", &signature).unwrap());
+        }
+
+        #[test]
+        fn verify_different_message() {
+            let address = EthereumAddress::from_str(ADDRESS).unwrap();
+            let signature = hex::decode(SIGNATURE).unwrap();
+            assert!(!address.verify_message(b"Some other data", &signature).unwrap());
+        }
+    }
+
+    mod eip55 {
+        use super::*;
+
+        // https://eips.ethereum.org/EIPS/eip-55#test-cases
+        const ADDRESSES: [&str; 4] = [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+
+        #[test]
+        fn from_str() {
+            ADDRESSES.iter().for_each(|address| {
+                test_from_str(address);
+                assert_eq!(
+                    *address,
+                    EthereumAddress::from_str(address).unwrap().to_checksum_address()
+                );
+            });
+        }
+
+        #[test]
+        fn from_str_single_case() {
+            ADDRESSES.iter().for_each(|address| {
+                let lowercase = format!("0x{}", address[2..].to_lowercase());
+                let uppercase = format!("0x{}", address[2..].to_uppercase());
+                assert_eq!(*address, EthereumAddress::from_str(&lowercase).unwrap().to_string());
+                assert_eq!(*address, EthereumAddress::from_str(&uppercase).unwrap().to_string());
+                assert_eq!(*address, EthereumAddress::from_str(&address[2..]).unwrap().to_string());
+            });
+        }
+
+        #[test]
+        fn from_str_checksum_mismatch() {
+            let address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD";
+            assert!(matches!(
+                EthereumAddress::from_str(address),
+                Err(AddressError::ChecksumMismatch(41))
+            ));
+
+            let address = "5AAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+            assert!(matches!(
+                EthereumAddress::from_str(address),
+                Err(AddressError::ChecksumMismatch(1))
+            ));
+        }
+
+        #[test]
+        fn from_str_invalid_character() {
+            let address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg";
+            assert!(matches!(
+                EthereumAddress::from_str(address),
+                Err(AddressError::InvalidAddress(_))
+            ));
+        }
+    }
+
     #[test]
     fn test_checksum_address_invalid() {
         // Mismatched keypair
@@ -204,4 +381,53 @@ mod tests {
         let address = "0x9141B7539E7902872095C408BfA294435e2b8c8a0x9141B7539E7902872095C408BfA294435e2b8c8a";
         assert!(EthereumAddress::from_str(address).is_err());
     }
+
+    mod classify {
+        use super::*;
+
+        const ADDRESS: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+        #[test]
+        fn checksum_status() {
+            [
+                (ADDRESS, ChecksumStatus::Valid),
+                ("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", ChecksumStatus::Absent),
+                ("5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED", ChecksumStatus::Absent),
+                // One letter with the wrong case
+                ("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD", ChecksumStatus::Invalid),
+            ]
+            .iter()
+            .for_each(|(address, checksum)| {
+                let info = EthereumAddress::classify(address).unwrap();
+                assert_eq!(*checksum, info.checksum);
+                assert_eq!(ADDRESS, info.address.to_string());
+                assert_eq!("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", hex::encode(info.payload));
+            });
+        }
+
+        #[test]
+        fn invalid() {
+            [
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe",
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAedd",
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+                "0x0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA",
+            ]
+            .iter()
+            .for_each(|address| assert!(EthereumAddress::classify(address).is_err()));
+        }
+
+        #[test]
+        fn validate_batch() {
+            let results = EthereumAddress::validate_batch(vec![
+                ADDRESS,
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe",
+                "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD",
+            ]);
+            assert_eq!(3, results.len());
+            assert_eq!(ChecksumStatus::Valid, results[0].as_ref().unwrap().checksum);
+            assert!(results[1].is_err());
+            assert_eq!(ChecksumStatus::Invalid, results[2].as_ref().unwrap().checksum);
+        }
+    }
 }
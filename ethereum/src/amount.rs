@@ -1,12 +1,19 @@
-use wagyu_model::{Amount, AmountError};
-
-use core::fmt;
+use wagyu_model::no_std::*;
+use wagyu_model::{base_units_to_decimal, decimal_to_base_units, Amount, AmountError};
+
+use core::{
+    fmt,
+    ops::{Add, Sub},
+    str::FromStr,
+};
 use ethereum_types::U256;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents the amount of Ethereum in wei
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EthereumAmount(pub U256);
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Denomination {
     Wei,
     Kwei,
@@ -50,13 +57,30 @@ impl fmt::Display for Denomination {
     }
 }
 
+impl FromStr for Denomination {
+    type Err = AmountError;
+
+    fn from_str(denomination: &str) -> Result<Self, Self::Err> {
+        match denomination.to_ascii_lowercase().as_str() {
+            "wei" => Ok(Denomination::Wei),
+            "kwei" => Ok(Denomination::Kwei),
+            "mwei" => Ok(Denomination::Mwei),
+            "gwei" => Ok(Denomination::Gwei),
+            "szabo" => Ok(Denomination::Szabo),
+            "finney" => Ok(Denomination::Finney),
+            "eth" | "ether" => Ok(Denomination::Ether),
+            _ => Err(AmountError::InvalidDenomination(denomination.into())),
+        }
+    }
+}
+
 impl Amount for EthereumAmount {}
 
 impl EthereumAmount {
     pub fn u256_from_str(val: &str) -> Result<U256, AmountError> {
         match U256::from_dec_str(val) {
             Ok(wei) => Ok(wei),
-            Err(error) => return Err(AmountError::Crate("uint", format!("{:?}", error))),
+            Err(error) => Err(AmountError::Crate("uint", format!("{:?}", error))),
         }
     }
 
@@ -71,53 +95,130 @@ impl EthereumAmount {
     }
 
     pub fn from_kwei(kwei_value: &str) -> Result<Self, AmountError> {
-        let wei = Self::u256_from_str(kwei_value)? * 10_i64.pow(Denomination::Kwei.precision());
-
-        Ok(Self::from_u256(wei))
+        Self::from_wei(kwei_value)?.checked_mul(U256::exp10(Denomination::Kwei.precision() as usize))
     }
 
     pub fn from_mwei(mwei_value: &str) -> Result<Self, AmountError> {
-        let wei = Self::u256_from_str(mwei_value)? * 10_i64.pow(Denomination::Mwei.precision());
-
-        Ok(Self::from_u256(wei))
+        Self::from_wei(mwei_value)?.checked_mul(U256::exp10(Denomination::Mwei.precision() as usize))
     }
 
     pub fn from_gwei(gwei_value: &str) -> Result<Self, AmountError> {
-        let wei = Self::u256_from_str(gwei_value)? * 10_i64.pow(Denomination::Gwei.precision());
-
-        Ok(Self::from_u256(wei))
+        Self::from_wei(gwei_value)?.checked_mul(U256::exp10(Denomination::Gwei.precision() as usize))
     }
 
     pub fn from_szabo(szabo_value: &str) -> Result<Self, AmountError> {
-        let wei = Self::u256_from_str(szabo_value)? * 10_i64.pow(Denomination::Szabo.precision());
-
-        Ok(Self::from_u256(wei))
+        Self::from_wei(szabo_value)?.checked_mul(U256::exp10(Denomination::Szabo.precision() as usize))
     }
 
     pub fn from_finney(finney_value: &str) -> Result<Self, AmountError> {
-        let wei = Self::u256_from_str(finney_value)? * 10_i64.pow(Denomination::Finney.precision());
-
-        Ok(Self::from_u256(wei))
+        Self::from_wei(finney_value)?.checked_mul(U256::exp10(Denomination::Finney.precision() as usize))
     }
 
     pub fn from_eth(eth_value: &str) -> Result<Self, AmountError> {
-        let wei = Self::u256_from_str(eth_value)? * 10_i64.pow(Denomination::Ether.precision());
+        Self::from_wei(eth_value)?.checked_mul(U256::exp10(Denomination::Ether.precision() as usize))
+    }
+
+    /// Returns the amount of the given decimal value in the given denomination, e.g. "21" gwei,
+    /// or an error if the value is negative or has more decimal places than the denomination allows.
+    pub fn from_denomination(value: &str, denomination: Denomination) -> Result<Self, AmountError> {
+        if value.starts_with('-') {
+            return Err(AmountError::NegativeAmount(value.into()));
+        }
+
+        let wei = decimal_to_base_units(value, denomination.precision())?;
+        let wei = U256::from_dec_str(&wei).map_err(|_| AmountError::Overflow(value.into()))?;
 
         Ok(Self::from_u256(wei))
     }
 
-    pub fn add(self, b: Self) -> Self {
-        Self::from_u256(self.0 + b.0)
+    /// Returns the sum of the amounts, or an error if it overflows.
+    pub fn checked_add(self, b: Self) -> Result<Self, AmountError> {
+        match self.0.checked_add(b.0) {
+            Some(wei) => Ok(Self::from_u256(wei)),
+            None => Err(AmountError::Overflow(format!("{} + {}", self.0, b.0))),
+        }
+    }
+
+    /// Returns the difference of the amounts, or an error if it is negative.
+    pub fn checked_sub(self, b: Self) -> Result<Self, AmountError> {
+        match self.0.checked_sub(b.0) {
+            Some(wei) => Ok(Self::from_u256(wei)),
+            None => Err(AmountError::NegativeAmount(format!("{} - {}", self.0, b.0))),
+        }
+    }
+
+    /// Returns the amount multiplied by the given factor, or an error if it overflows.
+    pub fn checked_mul(self, factor: U256) -> Result<Self, AmountError> {
+        match self.0.checked_mul(factor) {
+            Some(wei) => Ok(Self::from_u256(wei)),
+            None => Err(AmountError::Overflow(format!("{} * {}", self.0, factor))),
+        }
+    }
+}
+
+impl Add for EthereumAmount {
+    type Output = Result<Self, AmountError>;
+
+    fn add(self, b: Self) -> Self::Output {
+        self.checked_add(b)
+    }
+}
+
+impl Sub for EthereumAmount {
+    type Output = Result<Self, AmountError>;
+
+    fn sub(self, b: Self) -> Self::Output {
+        self.checked_sub(b)
     }
+}
+
+impl FromStr for EthereumAmount {
+    type Err = AmountError;
 
-    pub fn sub(self, b: Self) -> Self {
-        Self::from_u256(self.0 - b.0)
+    /// Returns the amount of a decimal value and its denomination, e.g. "0.5 eth" or "21 gwei".
+    fn from_str(amount: &str) -> Result<Self, Self::Err> {
+        let mut parts = amount.split_whitespace();
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(value), Some(denomination), None) => {
+                Self::from_denomination(value, Denomination::from_str(denomination)?)
+            }
+            _ => Err(AmountError::InvalidAmount(amount.into())),
+        }
     }
 }
 
 impl fmt::Display for EthereumAmount {
+    /// Formats the amount in ether from 0.001 ETH, in gwei from 1 gwei, and in wei below it,
+    /// e.g. "0.5 ETH", "21 gwei" or "1 wei".
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.0.to_string())
+        let denomination = if self.0 >= U256::exp10(Denomination::Finney.precision() as usize) {
+            Denomination::Ether
+        } else if self.0 >= U256::exp10(Denomination::Gwei.precision() as usize) {
+            Denomination::Gwei
+        } else {
+            Denomination::Wei
+        };
+
+        write!(
+            f,
+            "{} {}",
+            base_units_to_decimal(&self.0.to_string(), denomination.precision()),
+            denomination
+        )
+    }
+}
+
+impl Serialize for EthereumAmount {
+    /// Serializes the amount as its number of wei in a string, e.g. "21000000000".
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for EthereumAmount {
+    /// Deserializes the amount from its number of wei in a string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::from_denomination(&String::deserialize(deserializer)?, Denomination::Wei).map_err(de::Error::custom)
     }
 }
 
@@ -127,37 +228,37 @@ mod tests {
 
     fn test_from_wei(wei_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_wei(wei_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_from_finney(finney_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_finney(finney_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_from_szabo(szabo_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_szabo(szabo_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_from_gwei(gwei_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_gwei(gwei_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_from_mwei(mwei_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_mwei(mwei_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_from_kwei(kwei_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_kwei(kwei_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_from_eth(eth_value: &str, expected_amount: &str) {
         let amount = EthereumAmount::from_eth(eth_value).unwrap();
-        assert_eq!(expected_amount, amount.to_string())
+        assert_eq!(expected_amount, amount.0.to_string())
     }
 
     fn test_addition(a: &str, b: &str, result: &str) {
@@ -165,7 +266,7 @@ mod tests {
         let b = EthereumAmount::from_wei(b).unwrap();
         let result = EthereumAmount::from_wei(result).unwrap();
 
-        assert_eq!(result, a.add(b));
+        assert_eq!(result, a.add(b).unwrap());
     }
 
     fn test_subtraction(a: &str, b: &str, result: &str) {
@@ -173,7 +274,18 @@ mod tests {
         let b = EthereumAmount::from_wei(b).unwrap();
         let result = EthereumAmount::from_wei(result).unwrap();
 
-        assert_eq!(result, a.sub(b));
+        assert_eq!(result, a.sub(b).unwrap());
+    }
+
+    fn test_from_str(amount: &str, expected_amount: &str) {
+        let amount = EthereumAmount::from_str(amount).unwrap();
+        assert_eq!(expected_amount, amount.0.to_string())
+    }
+
+    fn test_display(wei_value: &str, expected: &str) {
+        let amount = EthereumAmount::from_wei(wei_value).unwrap();
+        assert_eq!(expected, amount.to_string());
+        assert_eq!(amount, EthereumAmount::from_str(expected).unwrap());
     }
 
     pub struct AmountDenominationTestCase {
@@ -287,6 +399,50 @@ mod tests {
         }
     }
 
+    mod valid_parsing {
+        use super::*;
+
+        const TEST_AMOUNTS: [(&str, &str); 9] = [
+            ("21 gwei", "21000000000"),
+            ("21 GWEI", "21000000000"),
+            ("1.5 gwei", "1500000000"),
+            ("0.000000001 gwei", "1"),
+            ("0.5 eth", "500000000000000000"),
+            ("0.000000000000000001 ether", "1"),
+            ("2.5 finney", "2500000000000000"),
+            ("1 wei", "1"),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935 wei",
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+            ),
+        ];
+
+        #[test]
+        fn test_valid_from_str() {
+            TEST_AMOUNTS.iter().for_each(|(amount, wei)| test_from_str(amount, wei));
+        }
+
+        #[test]
+        fn test_valid_display() {
+            test_display("0", "0 wei");
+            test_display("1", "1 wei");
+            test_display("999999999", "999999999 wei");
+            test_display("1000000000", "1 gwei");
+            test_display("21000000000", "21 gwei");
+            test_display("1500000000", "1.5 gwei");
+            test_display("999999999999999", "999999.999999999 gwei");
+            test_display("1000000000000000", "0.001 ETH");
+            test_display("1234567891234567891", "1.234567891234567891 ETH");
+        }
+
+        #[test]
+        fn test_valid_serde() {
+            let amount = EthereumAmount::from_gwei("21").unwrap();
+            assert_eq!("\"21000000000\"", serde_json::to_string(&amount).unwrap());
+            assert_eq!(amount, serde_json::from_str("\"21000000000\"").unwrap());
+        }
+    }
+
     mod valid_arithmetic {
         use super::*;
 
@@ -317,11 +473,150 @@ mod tests {
         fn test_valid_subtraction() {
             TEST_VALUES.iter().for_each(|(a, b, c)| test_subtraction(c, b, a));
         }
+
+        #[test]
+        fn test_valid_checked_arithmetic() {
+            TEST_VALUES.iter().for_each(|(a, b, c)| {
+                let a = EthereumAmount::from_wei(a).unwrap();
+                let b = EthereumAmount::from_wei(b).unwrap();
+                let c = EthereumAmount::from_wei(c).unwrap();
+                assert_eq!(c, a.checked_add(b).unwrap());
+                assert_eq!(a, c.checked_sub(b).unwrap());
+            });
+
+            let gas_price = EthereumAmount::from_gwei("21").unwrap();
+            assert_eq!(
+                EthereumAmount::from_wei("441000000000000").unwrap(),
+                gas_price.checked_mul(U256::from(21000)).unwrap()
+            );
+        }
     }
 
     mod test_invalid {
         use super::*;
 
+        mod invalid_parsing {
+            use super::*;
+
+            #[test]
+            fn test_invalid_precision() {
+                [
+                    "0.0000000001 gwei",
+                    "1.0000000000 gwei",
+                    "0.0000000000000000001 eth",
+                    "0.5 wei",
+                ]
+                .iter()
+                .for_each(|amount| match EthereumAmount::from_str(amount) {
+                    Err(AmountError::InvalidPrecision(..)) => (),
+                    result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                });
+            }
+
+            #[test]
+            fn test_invalid_scientific_notation() {
+                ["1e9 wei", "2.1E10 wei", "1e-3 eth", "0x10 wei", "+1 eth", "1_000 wei"]
+                    .iter()
+                    .for_each(|amount| match EthereumAmount::from_str(amount) {
+                        Err(AmountError::InvalidAmount(_)) => (),
+                        result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                    });
+            }
+
+            #[test]
+            fn test_invalid_format() {
+                ["", "21", "21gwei", "gwei", "21 gwei wei"].iter().for_each(|amount| {
+                    match EthereumAmount::from_str(amount) {
+                        Err(AmountError::InvalidAmount(_)) => (),
+                        result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                    }
+                });
+                match EthereumAmount::from_str("21 shannon") {
+                    Err(AmountError::InvalidDenomination(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+            }
+
+            #[test]
+            fn test_invalid_negative() {
+                ["-1 wei", "-0.5 eth"]
+                    .iter()
+                    .for_each(|amount| match EthereumAmount::from_str(amount) {
+                        Err(AmountError::NegativeAmount(_)) => (),
+                        result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                    });
+            }
+
+            #[test]
+            fn test_invalid_overflow() {
+                [
+                    "115792089237316195423570985008687907853269984665640564039457584007913129639936 wei",
+                    "115792089237316195423570985008687907853269984665640564039457584007913129639935 gwei",
+                ]
+                .iter()
+                .for_each(|amount| match EthereumAmount::from_str(amount) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result for {:?}: {:?}", amount, result),
+                });
+            }
+
+            #[test]
+            fn test_invalid_serde() {
+                ["21000000000", "\"-1\"", "\"2.1e10\"", "\"21 gwei\""]
+                    .iter()
+                    .for_each(|json| assert!(serde_json::from_str::<EthereumAmount>(json).is_err()));
+            }
+        }
+
+        mod invalid_arithmetic {
+            use super::*;
+
+            #[test]
+            fn test_overflow() {
+                let max = EthereumAmount::from_u256(U256::max_value());
+                match max.checked_add(EthereumAmount::from_wei("1").unwrap()) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                match max.checked_mul(U256::from(2)) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                match max + EthereumAmount::from_wei("1").unwrap() {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+
+                let max = U256::max_value().to_string();
+                [
+                    EthereumAmount::from_kwei,
+                    EthereumAmount::from_mwei,
+                    EthereumAmount::from_gwei,
+                    EthereumAmount::from_szabo,
+                    EthereumAmount::from_finney,
+                    EthereumAmount::from_eth,
+                ]
+                .iter()
+                .for_each(|from_denomination| match from_denomination(&max) {
+                    Err(AmountError::Overflow(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                });
+            }
+
+            #[test]
+            fn test_negative() {
+                let one = EthereumAmount::from_wei("1").unwrap();
+                match EthereumAmount::from_wei("0").unwrap().checked_sub(one) {
+                    Err(AmountError::NegativeAmount(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+                match EthereumAmount::from_wei("0").unwrap() - one {
+                    Err(AmountError::NegativeAmount(_)) => (),
+                    result => panic!("unexpected result: {:?}", result),
+                }
+            }
+        }
+
         mod test_invalid_conversion {
             use super::*;
 
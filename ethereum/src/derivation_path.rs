@@ -1,8 +1,13 @@
 use crate::network::EthereumNetwork;
-use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
+use wagyu_model::derivation_path::{
+    deserialize_derivation_path, parse_derivation_path, serialize_derivation_path, ChildIndex, DerivationPath,
+    DerivationPathError, MAX_DEPTH,
+};
+use wagyu_model::no_std::*;
 
 use core::convert::TryFrom;
-use core::{fmt, marker::PhantomData, str::FromStr};
+use core::{fmt, marker::PhantomData, ops::Index, str::FromStr};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 /// Represents a Ethereum derivation path
 #[derive(Clone, PartialEq, Eq)]
@@ -76,7 +81,7 @@ impl<N: EthereumNetwork> DerivationPath for EthereumDerivationPath<N> {
                 false => Err(DerivationPathError::ExpectedValidEthereumDerivationPath),
             },
 
-            EthereumDerivationPath::Custom(path, _) => match path.len() < 256 {
+            EthereumDerivationPath::Custom(path, _) => match path.len() <= MAX_DEPTH {
                 true => Ok(path.clone()),
                 false => Err(DerivationPathError::ExpectedValidEthereumDerivationPath),
             },
@@ -122,18 +127,68 @@ impl<N: EthereumNetwork> DerivationPath for EthereumDerivationPath<N> {
     }
 }
 
-impl<N: EthereumNetwork> FromStr for EthereumDerivationPath<N> {
-    type Err = DerivationPathError;
+impl<N: EthereumNetwork> EthereumDerivationPath<N> {
+    /// The purpose of standard derivation paths, held as a reference for indexing.
+    const PURPOSE: &'static ChildIndex = &N::HD_PURPOSE;
 
-    fn from_str(path: &str) -> Result<Self, Self::Err> {
-        let mut parts = path.split("/");
+    /// The coin type of standard derivation paths, held as a reference for indexing.
+    const COIN_TYPE: &'static ChildIndex = &N::HD_COIN_TYPE;
 
-        if parts.next().unwrap() != "m" {
-            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
+    /// Returns the number of child indices in the derivation path.
+    pub fn len(&self) -> usize {
+        match self {
+            EthereumDerivationPath::Ethereum(_)
+            | EthereumDerivationPath::Exodus(_)
+            | EthereumDerivationPath::Jaxx(_)
+            | EthereumDerivationPath::MetaMask(_)
+            | EthereumDerivationPath::MyEtherWallet(_)
+            | EthereumDerivationPath::Trezor(_)
+            | EthereumDerivationPath::KeepKey(_)
+            | EthereumDerivationPath::LedgerLive(_) => 5,
+            EthereumDerivationPath::Electrum(_)
+            | EthereumDerivationPath::ImToken(_)
+            | EthereumDerivationPath::LedgerLegacy(_) => 4,
+            EthereumDerivationPath::Custom(path, _) => path.len(),
         }
+    }
 
-        let path: Result<Vec<ChildIndex>, Self::Err> = parts.map(str::parse).collect();
-        Self::from_vec(&path?)
+    /// Returns `true` if the derivation path is the master path, m.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the child indices of the derivation path.
+    pub fn iter(&self) -> vec::IntoIter<ChildIndex> {
+        self.into_iter()
+    }
+
+    /// Returns `Ok` if the derivation path follows the BIP44 scheme,
+    /// m/44'/{coin type}'/{account}'/{change}/{index}, otherwise returns the level that violates it.
+    pub fn validate_bip44(&self) -> Result<(), DerivationPathError> {
+        let path = self.to_vec()?;
+        if path.len() != 5 {
+            return Err(DerivationPathError::ExpectedBIP44Path);
+        }
+
+        let expected = [
+            path[0] == N::HD_PURPOSE,
+            path[1] == N::HD_COIN_TYPE,
+            path[2].is_hardened(),
+            path[3].is_normal(),
+            path[4].is_normal(),
+        ];
+        match expected.iter().position(|valid| !valid) {
+            Some(level) => Err(DerivationPathError::InvalidBIP44Level(level)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<N: EthereumNetwork> FromStr for EthereumDerivationPath<N> {
+    type Err = DerivationPathError;
+
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        Self::from_vec(&parse_derivation_path(path)?)
     }
 }
 
@@ -153,6 +208,72 @@ impl<'a, N: EthereumNetwork> TryFrom<&'a [ChildIndex]> for EthereumDerivationPat
     }
 }
 
+impl<N: EthereumNetwork> Index<usize> for EthereumDerivationPath<N> {
+    type Output = ChildIndex;
+
+    /// Returns the child index at the given level of the derivation path.
+    /// Panics if the level is not less than the length of the derivation path.
+    fn index(&self, level: usize) -> &Self::Output {
+        const HARDENED_ZERO: &ChildIndex = &ChildIndex::Hardened(0);
+        const NORMAL_ZERO: &ChildIndex = &ChildIndex::Normal(0);
+
+        match self {
+            EthereumDerivationPath::Ethereum(index)
+            | EthereumDerivationPath::Exodus(index)
+            | EthereumDerivationPath::Jaxx(index)
+            | EthereumDerivationPath::MetaMask(index)
+            | EthereumDerivationPath::MyEtherWallet(index)
+            | EthereumDerivationPath::Trezor(index) => {
+                [Self::PURPOSE, Self::COIN_TYPE, HARDENED_ZERO, NORMAL_ZERO, index][level]
+            }
+
+            EthereumDerivationPath::KeepKey(index) | EthereumDerivationPath::LedgerLive(index) => {
+                [Self::PURPOSE, Self::COIN_TYPE, index, NORMAL_ZERO, NORMAL_ZERO][level]
+            }
+
+            EthereumDerivationPath::Electrum(index)
+            | EthereumDerivationPath::ImToken(index)
+            | EthereumDerivationPath::LedgerLegacy(index) => {
+                [Self::PURPOSE, Self::COIN_TYPE, HARDENED_ZERO, index][level]
+            }
+
+            EthereumDerivationPath::Custom(path, _) => &path[level],
+        }
+    }
+}
+
+impl<N: EthereumNetwork> IntoIterator for EthereumDerivationPath<N> {
+    type Item = ChildIndex;
+    type IntoIter = vec::IntoIter<ChildIndex>;
+
+    /// Returns an iterator over the child indices of the derivation path.
+    fn into_iter(self) -> Self::IntoIter {
+        (&self).into_iter()
+    }
+}
+
+impl<N: EthereumNetwork> IntoIterator for &EthereumDerivationPath<N> {
+    type Item = ChildIndex;
+    type IntoIter = vec::IntoIter<ChildIndex>;
+
+    /// Returns an iterator over the child indices of the derivation path.
+    fn into_iter(self) -> Self::IntoIter {
+        (0..self.len()).map(|level| self[level]).collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl<N: EthereumNetwork> Serialize for EthereumDerivationPath<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_derivation_path(self, serializer)
+    }
+}
+
+impl<'de, N: EthereumNetwork> Deserialize<'de> for EthereumDerivationPath<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_derivation_path(deserializer)
+    }
+}
+
 impl<N: EthereumNetwork> fmt::Debug for EthereumDerivationPath<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         fmt::Display::fmt(&self, f)
@@ -192,6 +313,16 @@ mod tests {
             EthereumDerivationPath::<N>::from_str("m"),
             Ok(vec![].try_into().unwrap())
         );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("M"),
+            Ok(vec![].try_into().unwrap())
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("M/0/1"),
+            Ok(vec![ChildIndex::normal(0).unwrap(), ChildIndex::normal(1).unwrap()]
+                .try_into()
+                .unwrap())
+        );
         assert_eq!(
             EthereumDerivationPath::<N>::from_str("m/0"),
             Ok(vec![ChildIndex::normal(0).unwrap()].try_into().unwrap())
@@ -353,15 +484,15 @@ mod tests {
 
         assert_eq!(
             EthereumDerivationPath::<N>::from_str("m/0x"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x".into(), 1))
         );
         assert_eq!(
             EthereumDerivationPath::<N>::from_str("m/0x0"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x0".into(), 1))
         );
         assert_eq!(
             EthereumDerivationPath::<N>::from_str("m/0x00"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x00".into(), 1))
         );
 
         assert_eq!(
@@ -370,11 +501,300 @@ mod tests {
         );
         assert_eq!(
             EthereumDerivationPath::<N>::from_str("m//0"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/0/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 2))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("M/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("M/0'"),
+            Err(DerivationPathError::ExpectedNormalPath)
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("M/0/1'"),
+            Err(DerivationPathError::ExpectedNormalPath)
         );
         assert_eq!(
             EthereumDerivationPath::<N>::from_str("m/2147483648"),
             Err(DerivationPathError::InvalidChildNumber(2147483648))
         );
     }
+
+    #[test]
+    fn boundary_index() {
+        type N = Mainnet;
+
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/2147483647"),
+            Ok(vec![ChildIndex::Normal(2147483647)].try_into().unwrap())
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/2147483647'"),
+            Ok(vec![ChildIndex::Hardened(2147483647)].try_into().unwrap())
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/0/2147483648"),
+            Err(DerivationPathError::InvalidChildNumber(2147483648))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/2147483648'"),
+            Err(DerivationPathError::InvalidChildNumber(2147483648))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/4294967295"),
+            Err(DerivationPathError::InvalidChildNumber(4294967295))
+        );
+        assert_eq!(
+            EthereumDerivationPath::<N>::from_str("m/4294967296'"),
+            Err(DerivationPathError::InvalidPathSegment("4294967296'".into(), 1))
+        );
+    }
+
+    #[test]
+    fn hardened_markers() {
+        type N = Mainnet;
+
+        let expected: EthereumDerivationPath<N> = vec![
+            ChildIndex::Hardened(44),
+            ChildIndex::Hardened(60),
+            ChildIndex::Hardened(0),
+            ChildIndex::Normal(0),
+            ChildIndex::Normal(1),
+        ]
+        .try_into()
+        .unwrap();
+
+        [
+            "m/44'/60'/0'/0/1",
+            "m/44h/60h/0h/0/1",
+            "m/44H/60H/0H/0/1",
+            "m/44'/60h/0H/0/1",
+        ]
+        .iter()
+        .for_each(|path| {
+            let path = EthereumDerivationPath::<N>::from_str(path).unwrap();
+            assert_eq!(expected, path);
+            assert_eq!("m/44'/60'/0'/0/1", path.to_string());
+        });
+
+        [
+            ("m/44'h/60'/0'/0/1", "44'h", 1),
+            ("m/44''/60'/0'/0/1", "44''", 1),
+            ("m/44'/60'/0'/0/1h'", "1h'", 5),
+            ("m/44'/6'0/0'/0/1", "6'0", 2),
+        ]
+        .iter()
+        .for_each(|(path, segment, position)| {
+            assert_eq!(
+                Err(DerivationPathError::InvalidPathSegment(segment.to_string(), *position)),
+                EthereumDerivationPath::<N>::from_str(path)
+            );
+        });
+    }
+
+    #[test]
+    fn manipulation() {
+        type N = Mainnet;
+
+        let mut path = EthereumDerivationPath::<N>::from_str("m").unwrap();
+        assert!(path.is_empty());
+        assert_eq!(None, path.pop());
+        assert_eq!(None, path.parent());
+
+        path.push(ChildIndex::Hardened(44)).unwrap();
+        path.extend(vec![ChildIndex::Hardened(60), ChildIndex::Hardened(0)])
+            .unwrap();
+        assert_eq!("m/44'/60'/0'", path.to_string());
+        assert_eq!(3, path.len());
+        assert_eq!(Some(ChildIndex::Hardened(60)), path.get(1));
+        assert_eq!(None, path.get(3));
+
+        let account = path.child(ChildIndex::Normal(0)).unwrap();
+        (0..20).for_each(|index| {
+            let child = account.child(ChildIndex::Normal(index)).unwrap();
+            assert_eq!(EthereumDerivationPath::Ethereum(ChildIndex::Normal(index)), child);
+            assert_eq!(5, child.len());
+            assert_eq!(ChildIndex::Normal(index), child[4]);
+            assert_eq!(account.to_vec(), child.parent().unwrap().to_vec());
+        });
+
+        assert_eq!(Some(ChildIndex::Hardened(0)), path.pop());
+        assert_eq!("m/44'/60'", path.to_string());
+        assert_eq!(
+            vec![ChildIndex::Hardened(44), ChildIndex::Hardened(60)],
+            path.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn index() {
+        type N = Mainnet;
+
+        [
+            "m",
+            "m/0'/1",
+            "m/44'/60'/0'/0/7",
+            "m/44'/60'/3'/0/0",
+            "m/44'/60'/0'/5",
+            "m/44'/60'/0'/1/2/3",
+        ]
+        .iter()
+        .for_each(|path| {
+            let path = EthereumDerivationPath::<N>::from_str(path).unwrap();
+            let expected = path.to_vec().unwrap();
+            assert_eq!(expected, (0..path.len()).map(|level| path[level]).collect::<Vec<_>>());
+            assert_eq!(expected, path.iter().collect::<Vec<_>>());
+        });
+
+        let path = EthereumDerivationPath::<N>::KeepKey(ChildIndex::Hardened(3));
+        assert_eq!(ChildIndex::Hardened(3), path[2]);
+        assert_eq!(ChildIndex::Normal(0), path[4]);
+
+        // An invalid derivation path still iterates over the child indices it holds.
+        let path = EthereumDerivationPath::<N>::Ethereum(ChildIndex::Hardened(7));
+        assert!(path.to_vec().is_err());
+        assert_eq!(
+            vec![
+                ChildIndex::Hardened(44),
+                ChildIndex::Hardened(60),
+                ChildIndex::Hardened(0),
+                ChildIndex::Normal(0),
+                ChildIndex::Hardened(7)
+            ],
+            path.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds() {
+        let _ = EthereumDerivationPath::<Mainnet>::Electrum(ChildIndex::Normal(0))[4];
+    }
+
+    #[test]
+    fn validate_bip44() {
+        type N = Mainnet;
+
+        ["m/44'/60'/0'/0/0", "m/44'/60'/1'/0/0"].iter().for_each(|path| {
+            assert_eq!(
+                Ok(()),
+                EthereumDerivationPath::<N>::from_str(path).unwrap().validate_bip44()
+            );
+        });
+
+        let path = EthereumDerivationPath::<N>::from_str("m/44'/60'/0/0/0").unwrap();
+        assert_eq!(Err(DerivationPathError::InvalidBIP44Level(2)), path.validate_bip44());
+        assert!(path.is_hardened(1));
+        assert!(!path.is_hardened(2));
+        assert_eq!(path.to_vec().unwrap(), path.iter().collect::<Vec<_>>());
+
+        assert_eq!(
+            Err(DerivationPathError::InvalidBIP44Level(1)),
+            EthereumDerivationPath::<N>::from_str("m/44'/0'/0'/0/0")
+                .unwrap()
+                .validate_bip44()
+        );
+        assert_eq!(
+            Err(DerivationPathError::ExpectedBIP44Path),
+            EthereumDerivationPath::<N>::from_str("m/44'/60'/0'/0")
+                .unwrap()
+                .validate_bip44()
+        );
+    }
+
+    #[test]
+    fn surrounding_whitespace() {
+        type N = Mainnet;
+
+        ["m/44'/60'/0'/0/0 ", " m/44'/60'/0'/0/0", "\tm/44'/60'/0'/0/0\n"]
+            .iter()
+            .for_each(|path| {
+                assert_eq!(
+                    EthereumDerivationPath::<N>::Ethereum(ChildIndex::Normal(0)),
+                    EthereumDerivationPath::<N>::from_str(path).unwrap()
+                );
+            });
+
+        assert_eq!(
+            Err(DerivationPathError::InvalidPathSegment(" 60'".into(), 2)),
+            EthereumDerivationPath::<N>::from_str("m/44'/ 60'/0'/0/0")
+        );
+    }
+
+    #[test]
+    fn random_ascii() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        type N = Mainnet;
+
+        const ALPHABET: &[u8] = b"mM/0123456789'hH x-+";
+
+        let rng = &mut StdRng::seed_from_u64(1231275789u64);
+        (0..10_000).for_each(|_| {
+            let length = rng.gen_range(0, 32);
+            let path: String = (0..length)
+                .map(|_| match rng.gen_bool(0.5) {
+                    true => ALPHABET[rng.gen_range(0, ALPHABET.len())] as char,
+                    false => rng.gen_range(0x20u8, 0x7f) as char,
+                })
+                .collect();
+            if let Ok(derivation_path) = EthereumDerivationPath::<N>::from_str(&path) {
+                assert_eq!(
+                    derivation_path,
+                    EthereumDerivationPath::<N>::from_str(&derivation_path.to_string()).unwrap()
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn serde() {
+        use super::*;
+
+        type N = Mainnet;
+
+        ["m", "m/0'/1", "m/44'/60'/0'/0/0", "m/44'/60'/0'/7", "m/44'/60'/3'/0/0"]
+            .iter()
+            .for_each(|expected| {
+                let path = EthereumDerivationPath::<N>::from_str(expected).unwrap();
+
+                let json = serde_json::to_string(&path).unwrap();
+                assert_eq!(format!("\"{}\"", expected), json);
+                assert_eq!(path, serde_json::from_str::<EthereumDerivationPath<N>>(&json).unwrap());
+
+                let cbor = serde_cbor::to_vec(&path).unwrap();
+                assert_eq!(
+                    path.iter().map(u32::from).collect::<Vec<u32>>(),
+                    serde_cbor::from_slice::<Vec<u32>>(&cbor).unwrap()
+                );
+                assert_eq!(
+                    path,
+                    serde_cbor::from_slice::<EthereumDerivationPath<N>>(&cbor).unwrap()
+                );
+            });
+
+        assert_eq!(
+            "invalid derivation path segment \"x\" at position 3",
+            serde_json::from_str::<EthereumDerivationPath<N>>("\"m/44'/60'/x\"")
+                .unwrap_err()
+                .to_string()
+        );
+
+        let cbor = serde_cbor::to_vec(&vec![0u32; MAX_DEPTH + 1]).unwrap();
+        assert_eq!(
+            "derivation path depth 256 exceeds the maximum depth of 255",
+            serde_cbor::from_slice::<EthereumDerivationPath<N>>(&cbor)
+                .unwrap_err()
+                .to_string()
+        );
+    }
 }
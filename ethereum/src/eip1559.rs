@@ -0,0 +1,453 @@
+use crate::address::EthereumAddress;
+use crate::amount::EthereumAmount;
+use crate::format::EthereumFormat;
+use crate::network::EthereumNetwork;
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use crate::rlp::{RlpItem, RlpStream};
+use crate::transaction::{decode_receiver, encode_receiver, recover_signer, sign_message, EthereumTransactionId};
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+use wagyu_model::{PrivateKey, Transaction, TransactionError};
+
+use core::{fmt, marker::PhantomData, str::FromStr};
+use ethereum_types::U256;
+
+/// The EIP-2718 transaction type of an EIP-1559 transaction.
+pub const EIP1559_TRANSACTION_TYPE: u8 = 0x02;
+
+/// Represents an entry of an EIP-2930 access list
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthereumAccessListItem {
+    /// The address of the accessed account
+    pub address: EthereumAddress,
+    /// The accessed storage keys of the account
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+/// Represents the parameters for an EIP-1559 transaction
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Eip1559TransactionParameters {
+    /// The address of the receiver, or `None` for a contract creation
+    pub receiver: Option<EthereumAddress>,
+    /// The amount (in wei)
+    pub amount: EthereumAmount,
+    /// The transaction gas limit
+    pub gas: U256,
+    /// The maximum fee per gas paid to the block producer (in wei)
+    pub max_priority_fee_per_gas: EthereumAmount,
+    /// The maximum total fee per gas, including the base fee (in wei)
+    pub max_fee_per_gas: EthereumAmount,
+    /// The nonce of the Ethereum account
+    pub nonce: U256,
+    /// The transaction data
+    pub data: Vec<u8>,
+    /// The accounts and storage keys the transaction plans to access
+    pub access_list: Vec<EthereumAccessListItem>,
+}
+
+/// Represents an EIP-1559 transaction signature
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Eip1559TransactionSignature {
+    /// The parity of the y-coordinate of the signature point
+    y_parity: u8,
+    /// The R field of the signature
+    r: Vec<u8>,
+    /// The S field of the signature
+    s: Vec<u8>,
+}
+
+/// Represents an EIP-1559 (type 2) transaction
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Eip1559Transaction<N: EthereumNetwork> {
+    /// The address of the sender
+    sender: Option<EthereumAddress>,
+    /// The transaction parameters (fees, gas, nonce, data, access list)
+    parameters: Eip1559TransactionParameters,
+    /// The transaction signature
+    signature: Option<Eip1559TransactionSignature>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: EthereumNetwork> Transaction for Eip1559Transaction<N> {
+    type Address = EthereumAddress;
+    type Format = EthereumFormat;
+    type PrivateKey = EthereumPrivateKey;
+    type PublicKey = EthereumPublicKey;
+    type TransactionId = EthereumTransactionId;
+    type TransactionParameters = Eip1559TransactionParameters;
+
+    /// Returns an unsigned transaction given the transaction parameters.
+    fn new(parameters: &Self::TransactionParameters) -> Result<Self, TransactionError> {
+        Ok(Self {
+            sender: None,
+            parameters: parameters.clone(),
+            signature: None,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns a signed transaction given the private key of the sender.
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+    fn sign(&self, private_key: &Self::PrivateKey) -> Result<Self, TransactionError> {
+        match (&self.sender, &self.signature) {
+            (Some(_), Some(_)) => Ok(self.clone()),
+            (Some(_), None) | (None, Some(_)) => Err(TransactionError::InvalidTransactionState),
+            (None, None) => {
+                let (y_parity, r, s) = sign_message(&self.to_transaction_id()?.txid, private_key)?;
+
+                let mut transaction = self.clone();
+                transaction.sender = Some(private_key.to_address(&EthereumFormat::Standard)?);
+                transaction.signature = Some(Eip1559TransactionSignature { y_parity, r, s });
+                Ok(transaction)
+            }
+        }
+    }
+
+    /// Returns a transaction given the transaction bytes.
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-2718.md
+    fn from_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
+        match transaction.first() {
+            Some(&EIP1559_TRANSACTION_TYPE) => (),
+            Some(transaction_type) => return Err(TransactionError::InvalidTransactionType(*transaction_type)),
+            None => return Err(TransactionError::InvalidRlpLength(0)),
+        };
+
+        let transaction = RlpItem::decode(&transaction[1..])?;
+        let list = transaction.as_list()?;
+        if list.len() != 9 && list.len() != 12 {
+            return Err(TransactionError::InvalidRlpLength(list.len()));
+        }
+
+        let chain_id = list[0].as_u256()?;
+        if chain_id != U256::from(N::CHAIN_ID) {
+            return Err(TransactionError::InvalidChainId(chain_id.low_u64()));
+        }
+
+        let mut access_list = vec![];
+        for item in list[8].as_list()? {
+            if item.as_list()?.len() != 2 {
+                return Err(TransactionError::InvalidRlpLength(item.as_list()?.len()));
+            }
+
+            let storage_keys = item
+                .at(1)?
+                .as_list()?
+                .iter()
+                .map(|storage_key| {
+                    let storage_key = storage_key.as_bytes()?;
+                    if storage_key.len() != 32 {
+                        return Err(TransactionError::Message(format!(
+                            "invalid storage key length: {}",
+                            storage_key.len()
+                        )));
+                    }
+                    let mut key = [0u8; 32];
+                    key.copy_from_slice(storage_key);
+                    Ok(key)
+                })
+                .collect::<Result<Vec<_>, TransactionError>>()?;
+
+            access_list.push(EthereumAccessListItem {
+                address: EthereumAddress::from_str(&hex::encode(item.at(0)?.as_bytes()?))?,
+                storage_keys,
+            });
+        }
+
+        let parameters = Eip1559TransactionParameters {
+            receiver: decode_receiver(list[5].as_bytes()?)?,
+            amount: EthereumAmount::from_u256(list[6].as_u256()?),
+            gas: list[4].as_u256()?,
+            max_priority_fee_per_gas: EthereumAmount::from_u256(list[2].as_u256()?),
+            max_fee_per_gas: EthereumAmount::from_u256(list[3].as_u256()?),
+            nonce: list[1].as_u256()?,
+            data: list[7].as_bytes()?.to_vec(),
+            access_list,
+        };
+
+        let mut transaction = Self {
+            sender: None,
+            parameters,
+            signature: None,
+            _network: PhantomData,
+        };
+
+        if list.len() == 12 {
+            // Signed transaction
+            let y_parity = match list[9].as_u64()? {
+                y_parity @ 0..=1 => y_parity as u8,
+                y_parity => return Err(TransactionError::InvalidSignatureV(y_parity as u32)),
+            };
+            transaction.signature = Some(Eip1559TransactionSignature {
+                y_parity,
+                r: list[10].as_bytes()?.to_vec(),
+                s: list[11].as_bytes()?.to_vec(),
+            });
+            transaction.sender = Some(transaction.recover_sender()?);
+        }
+
+        Ok(transaction)
+    }
+
+    /// Returns the transaction in bytes, as `0x02 || rlp([...])`.
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1559.md
+    fn to_transaction_bytes(&self) -> Result<Vec<u8>, TransactionError> {
+        let parameters = &self.parameters;
+
+        let mut transaction_rlp = RlpStream::new();
+        transaction_rlp.begin_list(match self.signature {
+            Some(_) => 12,
+            None => 9,
+        });
+        transaction_rlp.append(&N::CHAIN_ID);
+        transaction_rlp.append(&parameters.nonce);
+        transaction_rlp.append(&parameters.max_priority_fee_per_gas.0);
+        transaction_rlp.append(&parameters.max_fee_per_gas.0);
+        transaction_rlp.append(&parameters.gas);
+        encode_receiver(&mut transaction_rlp, &parameters.receiver)?;
+        transaction_rlp.append(&parameters.amount.0);
+        transaction_rlp.append(&parameters.data);
+
+        transaction_rlp.begin_list(parameters.access_list.len());
+        for item in &parameters.access_list {
+            transaction_rlp.begin_list(2);
+            transaction_rlp.append(&hex::decode(&item.address.to_string()[2..])?);
+            transaction_rlp.begin_list(item.storage_keys.len());
+            for storage_key in &item.storage_keys {
+                transaction_rlp.append(&storage_key[..]);
+            }
+        }
+
+        if let Some(signature) = &self.signature {
+            transaction_rlp.append(&signature.y_parity);
+            transaction_rlp.append(&signature.r);
+            transaction_rlp.append(&signature.s);
+        }
+
+        let mut transaction = vec![EIP1559_TRANSACTION_TYPE];
+        transaction.extend(transaction_rlp.out());
+        Ok(transaction)
+    }
+
+    /// Returns the hash of the signed transaction, if the signature is present.
+    /// Otherwise, returns the hash of the unsigned transaction, which is the message that is signed.
+    fn to_transaction_id(&self) -> Result<Self::TransactionId, TransactionError> {
+        Ok(Self::TransactionId {
+            txid: keccak256(&self.to_transaction_bytes()?).to_vec(),
+        })
+    }
+}
+
+impl<N: EthereumNetwork> Eip1559Transaction<N> {
+    /// Returns the address of the sender, recovered from the signature of the transaction.
+    pub fn recover_sender(&self) -> Result<EthereumAddress, TransactionError> {
+        let signature = match &self.signature {
+            Some(signature) => signature,
+            None => return Err(TransactionError::InvalidTransactionState),
+        };
+
+        let unsigned_transaction = Self {
+            sender: None,
+            parameters: self.parameters.clone(),
+            signature: None,
+            _network: PhantomData,
+        };
+        recover_signer(
+            &unsigned_transaction.to_transaction_id()?.txid,
+            signature.y_parity,
+            &signature.r,
+            &signature.s,
+        )
+    }
+}
+
+impl<N: EthereumNetwork> FromStr for Eip1559Transaction<N> {
+    type Err = TransactionError;
+
+    fn from_str(transaction: &str) -> Result<Self, Self::Err> {
+        Self::from_transaction_bytes(&hex::decode(transaction.trim_start_matches("0x"))?)
+    }
+}
+
+impl<N: EthereumNetwork> fmt::Display for Eip1559Transaction<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "0x{}",
+            &hex::encode(match self.to_transaction_bytes() {
+                Ok(transaction) => transaction,
+                _ => return Err(fmt::Error),
+            })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mainnet;
+    use wagyu_model::{ChildIndex, Network, NetworkError};
+
+    /// A local development network, as used by the ethers-js fixtures.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct Devnet;
+
+    impl Network for Devnet {
+        const NAME: &'static str = "devnet";
+    }
+
+    impl EthereumNetwork for Devnet {
+        const CHAIN_ID: u32 = 1337;
+        const NETWORK_ID: u32 = 1337;
+        const HD_COIN_TYPE: ChildIndex = ChildIndex::Hardened(1);
+    }
+
+    impl FromStr for Devnet {
+        type Err = NetworkError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                Self::NAME => Ok(Self),
+                _ => Err(NetworkError::InvalidNetwork(s.into())),
+            }
+        }
+    }
+
+    impl fmt::Display for Devnet {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", Self::NAME)
+        }
+    }
+
+    fn storage_key(key: &str) -> [u8; 32] {
+        let mut storage_key = [0u8; 32];
+        storage_key.copy_from_slice(&hex::decode(key).unwrap());
+        storage_key
+    }
+
+    mod access_list {
+        use super::*;
+
+        type N = Devnet;
+
+        const UNSIGNED_TRANSACTION: &str = "0x02f86b8205390284773594008477359400830186a09496216849c49358b10257cb55b28ea603c874b05e865af3107a4000825544f838f7940000000000000000000000000000000000000001e1a00100000000000000000000000000000000000000000000000000000000000000";
+        const SIGNING_HASH: &str = "0x090b19818d9d087a49c3d2ecee4829ee4acea46089c1381ac5e588188627466d";
+
+        fn parameters() -> Eip1559TransactionParameters {
+            Eip1559TransactionParameters {
+                receiver: Some(EthereumAddress::from_str("0x96216849c49358B10257cb55b28eA603c874b05E").unwrap()),
+                amount: EthereumAmount::from_wei("100000000000000").unwrap(),
+                gas: U256::from(100000),
+                max_priority_fee_per_gas: EthereumAmount::from_wei("2000000000").unwrap(),
+                max_fee_per_gas: EthereumAmount::from_wei("2000000000").unwrap(),
+                nonce: U256::from(2),
+                data: vec![0x55, 0x44],
+                access_list: vec![EthereumAccessListItem {
+                    address: EthereumAddress::from_str("0x0000000000000000000000000000000000000001").unwrap(),
+                    storage_keys: vec![storage_key(
+                        "0100000000000000000000000000000000000000000000000000000000000000",
+                    )],
+                }],
+            }
+        }
+
+        #[test]
+        fn to_transaction_bytes() {
+            let transaction = Eip1559Transaction::<N>::new(&parameters()).unwrap();
+            assert_eq!(UNSIGNED_TRANSACTION, transaction.to_string());
+            assert_eq!(SIGNING_HASH, transaction.to_transaction_id().unwrap().to_string());
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            let transaction = Eip1559Transaction::<N>::from_str(UNSIGNED_TRANSACTION).unwrap();
+            assert_eq!(None, transaction.sender);
+            assert_eq!(parameters(), transaction.parameters);
+            assert_eq!(UNSIGNED_TRANSACTION, transaction.to_string());
+        }
+
+        #[test]
+        fn sign() {
+            let private_key =
+                EthereumPrivateKey::from_str("4646464646464646464646464646464646464646464646464646464646464646")
+                    .unwrap();
+            let transaction = Eip1559Transaction::<N>::new(&parameters()).unwrap();
+            let signed_transaction = transaction.sign(&private_key).unwrap();
+
+            let expected_sender = private_key.to_address(&EthereumFormat::Standard).unwrap();
+            assert_eq!(Some(expected_sender.clone()), signed_transaction.sender);
+            assert_eq!(expected_sender, signed_transaction.recover_sender().unwrap());
+
+            let decoded_transaction = Eip1559Transaction::<N>::from_str(&signed_transaction.to_string()).unwrap();
+            assert_eq!(signed_transaction, decoded_transaction);
+        }
+
+        #[test]
+        fn invalid_chain_id() {
+            assert!(matches!(
+                Eip1559Transaction::<Mainnet>::from_str(UNSIGNED_TRANSACTION),
+                Err(TransactionError::InvalidChainId(1337))
+            ));
+        }
+    }
+
+    mod empty_access_list {
+        use super::*;
+
+        type N = Mainnet;
+
+        const SIGNED_TRANSACTION: &str = "0x02f899018085602b94278b85b2f7a17de88302cf5c940aa7420c43b8c1a7b165d216948870c8ecfe1ee18802c68af0bb140000a46ecd23060000000000000000000000000000000000000000000000000000000000000002c080a0c5f35bf1cc6ab13053e33b1af7400c267be17218aeadcdb4ae3eefd4795967e8a04f6871044dd6368aea8deecd1c29f55b5531020f5506502e3f79ad457051bc4a";
+        const TRANSACTION_HASH: &str = "0x206e4c71335333f8658e995cc0c4ee54395d239acb08587ab8e5409bfdd94a6f";
+        const SENDER: &str = "0x1acadd971da208d25122b645b2ef879868a83e21";
+
+        fn parameters() -> Eip1559TransactionParameters {
+            Eip1559TransactionParameters {
+                receiver: Some(EthereumAddress::from_str("0x0aa7420c43b8c1a7b165d216948870c8ecfe1ee1").unwrap()),
+                amount: EthereumAmount::from_wei("200000000000000000").unwrap(),
+                gas: U256::from(184156),
+                max_priority_fee_per_gas: EthereumAmount::from_wei("413047990155").unwrap(),
+                max_fee_per_gas: EthereumAmount::from_wei("768658734568").unwrap(),
+                nonce: U256::zero(),
+                data: hex::decode("6ecd23060000000000000000000000000000000000000000000000000000000000000002").unwrap(),
+                access_list: vec![],
+            }
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            let transaction = Eip1559Transaction::<N>::from_str(SIGNED_TRANSACTION).unwrap();
+            assert_eq!(parameters(), transaction.parameters);
+            assert_eq!(
+                EthereumAddress::from_str(SENDER).unwrap(),
+                transaction.recover_sender().unwrap()
+            );
+            assert_eq!(SIGNED_TRANSACTION, transaction.to_string());
+            assert_eq!(TRANSACTION_HASH, transaction.to_transaction_id().unwrap().to_string());
+        }
+
+        #[test]
+        fn contract_creation() {
+            let private_key =
+                EthereumPrivateKey::from_str("4646464646464646464646464646464646464646464646464646464646464646")
+                    .unwrap();
+            let mut parameters = parameters();
+            parameters.receiver = None;
+
+            let transaction = Eip1559Transaction::<N>::new(&parameters).unwrap();
+            let signed_transaction = transaction.sign(&private_key).unwrap();
+
+            let decoded_transaction = Eip1559Transaction::<N>::from_str(&signed_transaction.to_string()).unwrap();
+            assert_eq!(None, decoded_transaction.parameters.receiver);
+            assert_eq!(signed_transaction, decoded_transaction);
+        }
+
+        #[test]
+        fn invalid_transaction_type() {
+            assert!(matches!(
+                Eip1559Transaction::<N>::from_str(&SIGNED_TRANSACTION.replace("0x02", "0x01")),
+                Err(TransactionError::InvalidTransactionType(1))
+            ));
+        }
+    }
+}
@@ -0,0 +1,571 @@
+//!
+//! EIP-712
+//!
+//! This module contains the hashing of typed structured data used by `eth_signTypedData_v4`,
+//! e.g. for token permits and exchange orders.
+//! https://eips.ethereum.org/EIPS/eip-712
+//!
+//! A struct is hashed as `keccak256(typeHash || encodeData(s))`, where the type hash is the hash
+//! of the type and its referenced struct types, and each member is encoded in 32 bytes. Arrays and
+//! nested structs are encoded as the hash of their encoding, so the message hash commits to the
+//! entire message.
+//!
+
+use crate::address::EthereumAddress;
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+
+use core::str::FromStr;
+use ethereum_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The name of the struct type of the domain.
+pub const EIP712_DOMAIN: &str = "EIP712Domain";
+
+#[derive(Debug, Fail)]
+pub enum Eip712Error {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid type: {}", _0)]
+    InvalidType(String),
+
+    #[fail(display = "missing type definition: {}", _0)]
+    MissingType(String),
+
+    #[fail(display = "missing value for {}.{}", _0, _1)]
+    MissingValue(String, String),
+
+    #[fail(display = "mismatched type and value: {{ type: {:?}, value: {} }}", _0, _1)]
+    TypeMismatch(String, Value),
+}
+
+impl From<hex::FromHexError> for Eip712Error {
+    fn from(error: hex::FromHexError) -> Self {
+        Eip712Error::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::Error> for Eip712Error {
+    fn from(error: serde_json::Error) -> Self {
+        Eip712Error::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+/// Represents a member of a struct type, e.g. `{ "name": "wallet", "type": "address" }`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedDataField {
+    /// The name of the member
+    pub name: String,
+    /// The type of the member
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// Represents the typed data of `eth_signTypedData_v4`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// The struct types, by name
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    /// The name of the struct type of the message
+    pub primary_type: String,
+    /// The domain, a struct of type `EIP712Domain`
+    pub domain: Value,
+    /// The message, a struct of the primary type
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Returns the type encoding of the given struct type, e.g.
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    pub fn encode_type(&self, struct_type: &str) -> Result<String, Eip712Error> {
+        let mut dependencies = vec![];
+        self.find_dependencies(struct_type, &mut dependencies)?;
+        dependencies[1..].sort();
+
+        dependencies.iter().try_fold(String::new(), |mut encoding, name| {
+            let fields = self.fields(name)?;
+            let members = fields
+                .iter()
+                .map(|field| format!("{} {}", field.field_type, field.name))
+                .collect::<Vec<_>>();
+            encoding.push_str(&format!("{}({})", name, members.join(",")));
+            Ok(encoding)
+        })
+    }
+
+    /// Returns the Keccak-256 hash of the type encoding of the given struct type.
+    pub fn type_hash(&self, struct_type: &str) -> Result<[u8; 32], Eip712Error> {
+        Ok(keccak256(self.encode_type(struct_type)?.as_bytes()))
+    }
+
+    /// Returns the type hash of the given struct type followed by the 32 byte encoding of each
+    /// member of the given value.
+    pub fn encode_data(&self, struct_type: &str, value: &Value) -> Result<Vec<u8>, Eip712Error> {
+        let mut data = self.type_hash(struct_type)?.to_vec();
+        for field in self.fields(struct_type)? {
+            let member = value
+                .get(&field.name)
+                .ok_or_else(|| Eip712Error::MissingValue(struct_type.into(), field.name.clone()))?;
+            data.extend_from_slice(&self.encode_value(&field.field_type, member)?);
+        }
+        Ok(data)
+    }
+
+    /// Returns the hashStruct of the given value of the given struct type.
+    pub fn hash_struct(&self, struct_type: &str, value: &Value) -> Result<[u8; 32], Eip712Error> {
+        Ok(keccak256(&self.encode_data(struct_type, value)?))
+    }
+
+    /// Returns the domain separator, the hashStruct of the domain.
+    pub fn domain_separator(&self) -> Result<[u8; 32], Eip712Error> {
+        self.hash_struct(EIP712_DOMAIN, &self.domain)
+    }
+
+    /// Returns the 32 byte digest signed by `eth_signTypedData_v4`,
+    /// `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`.
+    pub fn signing_hash(&self) -> Result<[u8; 32], Eip712Error> {
+        let mut data = vec![0x19, 0x01];
+        data.extend_from_slice(&self.domain_separator()?);
+        if self.primary_type != EIP712_DOMAIN {
+            data.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Ok(keccak256(&data))
+    }
+
+    /// Returns the members of the given struct type. The domain type is inferred from the members
+    /// of the domain if it is not defined.
+    fn fields(&self, struct_type: &str) -> Result<Vec<TypedDataField>, Eip712Error> {
+        match self.types.get(struct_type) {
+            Some(fields) => Ok(fields.clone()),
+            None if struct_type == EIP712_DOMAIN => Ok([
+                ("name", "string"),
+                ("version", "string"),
+                ("chainId", "uint256"),
+                ("verifyingContract", "address"),
+                ("salt", "bytes32"),
+            ]
+            .iter()
+            .filter(|(name, _)| self.domain.get(name).is_some())
+            .map(|(name, field_type)| TypedDataField {
+                name: (*name).into(),
+                field_type: (*field_type).into(),
+            })
+            .collect()),
+            None => Err(Eip712Error::MissingType(struct_type.into())),
+        }
+    }
+
+    /// Appends the given struct type and the struct types it references, in the order found.
+    fn find_dependencies(&self, struct_type: &str, dependencies: &mut Vec<String>) -> Result<(), Eip712Error> {
+        if dependencies.iter().any(|dependency| dependency == struct_type) {
+            return Ok(());
+        }
+        dependencies.push(struct_type.into());
+
+        for field in self.fields(struct_type)? {
+            let base_type = field.field_type.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base_type) {
+                self.find_dependencies(base_type, dependencies)?;
+            } else if !is_atomic_type(base_type) {
+                return Err(Eip712Error::MissingType(base_type.into()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the 32 byte encoding of the given value of the given member type.
+    fn encode_value(&self, field_type: &str, value: &Value) -> Result<[u8; 32], Eip712Error> {
+        let mismatch = || Eip712Error::TypeMismatch(field_type.into(), value.clone());
+
+        // Arrays are encoded as the hash of the concatenated encodings of their elements.
+        if field_type.ends_with(']') {
+            let open = field_type
+                .rfind('[')
+                .ok_or_else(|| Eip712Error::InvalidType(field_type.into()))?;
+            let (element_type, length) = (&field_type[..open], &field_type[open + 1..field_type.len() - 1]);
+            let elements = value.as_array().ok_or_else(mismatch)?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(elements.len()) {
+                return Err(mismatch());
+            }
+
+            let mut data = vec![];
+            for element in elements {
+                data.extend_from_slice(&self.encode_value(element_type, element)?);
+            }
+            return Ok(keccak256(&data));
+        }
+
+        if self.types.contains_key(field_type) {
+            return self.hash_struct(field_type, value);
+        }
+
+        let mut encoding = [0u8; 32];
+        match field_type {
+            "string" => encoding = keccak256(value.as_str().ok_or_else(mismatch)?.as_bytes()),
+            "bytes" => encoding = keccak256(&decode_hex(value).ok_or_else(mismatch)?),
+            "bool" => encoding[31] = value.as_bool().ok_or_else(mismatch)? as u8,
+            "address" => {
+                let address =
+                    EthereumAddress::classify(value.as_str().ok_or_else(mismatch)?).map_err(|_| mismatch())?;
+                encoding[12..].copy_from_slice(&address.payload);
+            }
+            _ if field_type.starts_with("bytes") => {
+                let length = parse_size(&field_type[5..], 1, 32, 1)
+                    .ok_or_else(|| Eip712Error::InvalidType(field_type.into()))?;
+                let bytes = decode_hex(value).ok_or_else(mismatch)?;
+                if bytes.len() != length {
+                    return Err(mismatch());
+                }
+                encoding[..length].copy_from_slice(&bytes);
+            }
+            _ if field_type.starts_with("uint") || field_type.starts_with("int") => {
+                let signed = field_type.starts_with("int");
+                let bits = match signed {
+                    true => &field_type[3..],
+                    false => &field_type[4..],
+                };
+                let bits = parse_size(bits, 8, 256, 8).ok_or_else(|| Eip712Error::InvalidType(field_type.into()))?;
+                encode_integer(value, bits, signed)
+                    .ok_or_else(mismatch)?
+                    .to_big_endian(&mut encoding);
+            }
+            _ => return Err(Eip712Error::MissingType(field_type.into())),
+        };
+        Ok(encoding)
+    }
+}
+
+impl FromStr for TypedData {
+    type Err = Eip712Error;
+
+    fn from_str(typed_data: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(typed_data)?)
+    }
+}
+
+/// Returns `true` if the given type is a string, bytes, bool, address, or sized integer type.
+fn is_atomic_type(field_type: &str) -> bool {
+    match field_type {
+        "string" | "bytes" | "bool" | "address" => true,
+        _ if field_type.starts_with("bytes") => parse_size(&field_type[5..], 1, 32, 1).is_some(),
+        _ if field_type.starts_with("uint") => parse_size(&field_type[4..], 8, 256, 8).is_some(),
+        _ if field_type.starts_with("int") => parse_size(&field_type[3..], 8, 256, 8).is_some(),
+        _ => false,
+    }
+}
+
+/// Returns the size suffix of a type, e.g. 256 of `uint256`, if it is a multiple of `step`
+/// between `min` and `max`. An empty suffix is `max`, as `uint` is an alias of `uint256`.
+fn parse_size(suffix: &str, min: usize, max: usize, step: usize) -> Option<usize> {
+    if suffix.is_empty() && max == 256 {
+        return Some(max);
+    }
+    match suffix.parse::<usize>() {
+        Ok(size) if suffix.starts_with(|c: char| c != '0') && size >= min && size <= max && size % step == 0 => {
+            Some(size)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the bytes of the given 0x-prefixed hex string value.
+fn decode_hex(value: &Value) -> Option<Vec<u8>> {
+    let hex = value.as_str()?;
+    match hex.get(0..2) {
+        Some("0x") | Some("0X") => hex::decode(&hex[2..]).ok(),
+        _ => None,
+    }
+}
+
+/// Returns the two's complement encoding of the given integer value, a JSON number or a decimal
+/// or 0x-prefixed hex string, if it fits in the given number of bits.
+fn encode_integer(value: &Value, bits: usize, signed: bool) -> Option<U256> {
+    let (negative, magnitude) = match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(number), _) => (false, U256::from(number)),
+            (None, Some(number)) => (true, U256::from(number.unsigned_abs())),
+            _ => return None,
+        },
+        Value::String(string) => {
+            let (negative, digits) = match string.strip_prefix('-') {
+                Some(digits) => (true, digits),
+                None => (false, string.as_str()),
+            };
+            let magnitude = match digits.get(0..2) {
+                Some("0x") | Some("0X") => {
+                    let hex = &digits[2..];
+                    let bytes = match hex.len() % 2 {
+                        0 => hex::decode(hex).ok()?,
+                        _ => hex::decode(format!("0{}", hex)).ok()?,
+                    };
+                    if hex.is_empty() || bytes.len() > 32 {
+                        return None;
+                    }
+                    U256::from_big_endian(&bytes)
+                }
+                _ => U256::from_dec_str(digits).ok()?,
+            };
+            (negative, magnitude)
+        }
+        _ => return None,
+    };
+
+    match (signed, negative) {
+        (false, true) if !magnitude.is_zero() => None,
+        (false, _) if magnitude.bits() > bits => None,
+        (false, _) => Some(magnitude),
+        // A negative value may be as large as 2^(bits - 1), and a positive value must be smaller.
+        (true, true) if magnitude > U256::one() << (bits - 1) => None,
+        (true, true) => Some((!magnitude).overflowing_add(U256::one()).0),
+        (true, false) if magnitude.bits() >= bits => None,
+        (true, false) => Some(magnitude),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::EthereumFormat;
+    use crate::private_key::EthereumPrivateKey;
+    use crate::public_key::EthereumPublicKey;
+    use wagyu_model::PublicKey;
+
+    /// The example of the EIP
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    /// The example of `eth_signTypedData_v4` with arrays of structs and addresses, as hashed by
+    /// ethers-js `TypedDataEncoder` and MetaMask
+    const MAIL_ARRAYS: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallets", "type": "address[]" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person[]" },
+                { "name": "contents", "type": "string" }
+            ],
+            "Group": [
+                { "name": "name", "type": "string" },
+                { "name": "members", "type": "Person[]" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": {
+                "name": "Cow",
+                "wallets": [
+                    "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                    "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"
+                ]
+            },
+            "to": [
+                {
+                    "name": "Bob",
+                    "wallets": [
+                        "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                        "0xB0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57",
+                        "0xB0B0b0b0b0b0B000000000000000000000000000"
+                    ]
+                }
+            ],
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    /// The private key of "Cow", keccak256("cow")
+    const PRIVATE_KEY: &str = "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4";
+
+    /// Returns the address of the key that produced the given r || s || v signature of the digest.
+    fn recover(digest: &[u8; 32], signature: &[u8]) -> String {
+        let message = secp256k1::Message::parse(digest);
+        let recovery_id = secp256k1::RecoveryId::parse(signature[64] - 27).unwrap();
+        let signature = secp256k1::Signature::parse_slice(&signature[..64]).unwrap();
+        let public_key = secp256k1::recover(&message, &signature, &recovery_id).unwrap();
+        EthereumPublicKey::from_secp256k1_public_key(public_key)
+            .to_address(&EthereumFormat::Standard)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn mail() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        assert_eq!(
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+            typed_data.encode_type("Mail").unwrap()
+        );
+        assert_eq!(
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2",
+            hex::encode(typed_data.type_hash("Mail").unwrap())
+        );
+        assert_eq!(
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e",
+            hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap())
+        );
+        assert_eq!(
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+            hex::encode(typed_data.domain_separator().unwrap())
+        );
+        assert_eq!(
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+            hex::encode(typed_data.signing_hash().unwrap())
+        );
+
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let signature = private_key.sign_typed_data(&typed_data).unwrap();
+        assert_eq!(
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+            hex::encode(&signature[..])
+        );
+        assert_eq!(
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+            recover(&typed_data.signing_hash().unwrap(), &signature)
+        );
+    }
+
+    #[test]
+    fn nested_arrays() {
+        let typed_data = TypedData::from_str(MAIL_ARRAYS).unwrap();
+        assert_eq!(
+            "Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)",
+            typed_data.encode_type("Mail").unwrap()
+        );
+        assert_eq!(
+            "Group(string name,Person[] members)Person(string name,address[] wallets)",
+            typed_data.encode_type("Group").unwrap()
+        );
+        assert_eq!(
+            "eb4221181ff3f1a83ea7313993ca9218496e424604ba9492bb4052c03d5c3df8",
+            hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap())
+        );
+        assert_eq!(
+            "a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2",
+            hex::encode(typed_data.signing_hash().unwrap())
+        );
+
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let signature = private_key.sign_typed_data(&typed_data).unwrap();
+        assert_eq!(
+            "65cbd956f2fae28a601bebc9b906cea0191744bd4c4247bcd27cd08f8eb6b71c78efdf7a31dc9abee78f492292721f362d296cf86b4538e07b51303b67f749061b",
+            hex::encode(&signature[..])
+        );
+        assert_eq!(
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+            recover(&typed_data.signing_hash().unwrap(), &signature)
+        );
+    }
+
+    #[test]
+    fn inferred_domain() {
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        let domain_separator = typed_data.domain_separator().unwrap();
+        typed_data.types.remove(EIP712_DOMAIN);
+        assert_eq!(domain_separator, typed_data.domain_separator().unwrap());
+    }
+
+    #[test]
+    fn integers() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        let encode = |field_type: &str, value: Value| typed_data.encode_value(field_type, &value).map(hex::encode);
+
+        assert_eq!(format!("{:0>64}", "ff"), encode("uint8", Value::from(255)).unwrap());
+        assert_eq!(format!("{:0>64}", "ff"), encode("uint", Value::from("0xff")).unwrap());
+        assert_eq!("f".repeat(64), encode("int256", Value::from(-1)).unwrap());
+        assert_eq!(
+            format!("{}{}", "f".repeat(62), "80"),
+            encode("int8", Value::from("-128")).unwrap()
+        );
+        assert!(encode("uint8", Value::from(256)).is_err());
+        assert!(encode("int8", Value::from(128)).is_err());
+        assert!(encode("int8", Value::from(-129)).is_err());
+        assert!(encode("uint256", Value::from(-1)).is_err());
+        assert!(encode("uint7", Value::from(1)).is_err());
+        assert!(encode("uint08", Value::from(1)).is_err());
+    }
+
+    #[test]
+    fn invalid() {
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.message["to"]["wallet"] = Value::from(1);
+        assert!(matches!(
+            typed_data.signing_hash(),
+            Err(Eip712Error::TypeMismatch(ref field_type, _)) if field_type == "address"
+        ));
+
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.message["contents"] = Value::from(vec!["Hello, Bob!"]);
+        assert!(matches!(
+            typed_data.signing_hash(),
+            Err(Eip712Error::TypeMismatch(ref field_type, _)) if field_type == "string"
+        ));
+
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.message["from"].as_object_mut().unwrap().remove("name");
+        assert!(matches!(
+            typed_data.signing_hash(),
+            Err(Eip712Error::MissingValue(ref struct_type, ref name)) if struct_type == "Person" && name == "name"
+        ));
+
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.types.remove("Person");
+        assert!(matches!(
+            typed_data.signing_hash(),
+            Err(Eip712Error::MissingType(ref struct_type)) if struct_type == "Person"
+        ));
+
+        let typed_data = TypedData::from_str(MAIL_ARRAYS).unwrap();
+        let mut typed_data = typed_data.clone();
+        typed_data.types.get_mut("Person").unwrap()[1].field_type = "address[2]".into();
+        assert!(matches!(
+            typed_data.signing_hash(),
+            Err(Eip712Error::TypeMismatch(ref field_type, _)) if field_type == "address[2]"
+        ));
+    }
+}
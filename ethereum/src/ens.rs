@@ -0,0 +1,167 @@
+//!
+//! ENS
+//!
+//! This module contains the name hashing of the Ethereum Name Service, which maps a name such as
+//! "foo.eth" to the 32 byte node of its resolver records.
+//! https://eips.ethereum.org/EIPS/eip-137
+//!
+//! Names are normalized by lowercasing and NFC composition, a subset of the UTS-46 mapping used
+//! by ENS. Labels may contain letters, digits, hyphens, and underscores; names with other
+//! characters, such as whitespace, punctuation, or emoji, are rejected by `normalize`.
+//!
+
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+
+use core::{fmt, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum EnsError {
+    #[fail(display = "empty label in ens name: {:?}", _0)]
+    EmptyLabel(String),
+
+    #[fail(display = "invalid character in ens name: {:?}", _0)]
+    InvalidCharacter(char),
+}
+
+/// Returns the given name lowercased and NFC composed, without checking its characters.
+fn map(name: &str) -> String {
+    name.to_lowercase().nfc().collect()
+}
+
+/// Returns the normalized form of the given name, or an error if the name has an empty label
+/// or a character other than a letter, digit, hyphen, or underscore.
+pub fn normalize(name: &str) -> Result<String, EnsError> {
+    let name = map(name);
+    if name.is_empty() {
+        return Ok(name);
+    }
+
+    for label in name.split('.') {
+        if label.is_empty() {
+            return Err(EnsError::EmptyLabel(name.clone()));
+        }
+        if let Some(character) = label.chars().find(|c| !(c.is_alphanumeric() || *c == '-' || *c == '_')) {
+            return Err(EnsError::InvalidCharacter(character));
+        }
+    }
+
+    Ok(name)
+}
+
+/// Returns the Keccak-256 hash of the given label, which is hashed as is.
+pub fn labelhash(label: &str) -> [u8; 32] {
+    keccak256(label.as_bytes())
+}
+
+/// Returns the node of the given name, the recursive hash
+/// `namehash(label.rest) = keccak256(namehash(rest) || labelhash(label))` with the empty name
+/// as 32 zero bytes. The name is lowercased and NFC composed, but its characters are not checked.
+pub fn namehash(name: &str) -> [u8; 32] {
+    let name = map(name);
+    if name.is_empty() {
+        return [0u8; 32];
+    }
+
+    name.rsplit('.').fold([0u8; 32], |node, label| {
+        let mut data = [0u8; 64];
+        data[..32].copy_from_slice(&node);
+        data[32..].copy_from_slice(&labelhash(label));
+        keccak256(&data)
+    })
+}
+
+/// Represents a normalized ENS name
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EnsName(String);
+
+impl EnsName {
+    /// Returns the ENS name of the given name, after normalization.
+    pub fn new(name: &str) -> Result<Self, EnsError> {
+        Ok(Self(normalize(name)?))
+    }
+
+    /// Returns the node of the ENS name.
+    pub fn namehash(&self) -> [u8; 32] {
+        namehash(&self.0)
+    }
+
+    /// Returns the labels of the ENS name, from the leftmost label to the top-level label.
+    pub fn labels(&self) -> Vec<&str> {
+        match self.0.is_empty() {
+            true => vec![],
+            false => self.0.split('.').collect(),
+        }
+    }
+}
+
+impl FromStr for EnsName {
+    type Err = EnsError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::new(name)
+    }
+}
+
+impl fmt::Display for EnsName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eip137() {
+        [
+            ("", "0000000000000000000000000000000000000000000000000000000000000000"),
+            (
+                "eth",
+                "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae",
+            ),
+            (
+                "foo.eth",
+                "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f",
+            ),
+        ]
+        .iter()
+        .for_each(|(name, node)| {
+            assert_eq!(*node, hex::encode(namehash(name)));
+            assert_eq!(*node, hex::encode(EnsName::new(name).unwrap().namehash()));
+        });
+
+        assert_eq!(
+            "4f5b812789fc606be1b3b16908db13fc7a9adf7ca72641f84d75b47069d3d7f0",
+            hex::encode(labelhash("eth"))
+        );
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!("foo.eth", normalize("Foo.ETH").unwrap());
+        assert_eq!(namehash("foo.eth"), namehash("FOO.eth"));
+
+        let name = EnsName::from_str("Vitalik.Eth").unwrap();
+        assert_eq!("vitalik.eth", name.to_string());
+        assert_eq!(vec!["vitalik", "eth"], name.labels());
+
+        // A decomposed "é" is composed before hashing.
+        assert_eq!(
+            normalize("caf\u{e9}.eth").unwrap(),
+            normalize("cafe\u{301}.eth").unwrap()
+        );
+        assert_eq!(namehash("caf\u{e9}.eth"), namehash("CAFE\u{301}.eth"));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Err(EnsError::InvalidCharacter(' ')), normalize("foo bar.eth"));
+        assert_eq!(Err(EnsError::InvalidCharacter('!')), normalize("foo!.eth"));
+        assert_eq!(Err(EnsError::EmptyLabel("foo..eth".into())), normalize("foo..eth"));
+        assert!(EnsName::new(".eth").is_err());
+        assert!(EnsName::new("foo.eth.").is_err());
+    }
+}
@@ -2,13 +2,17 @@ use crate::address::EthereumAddress;
 use crate::derivation_path::EthereumDerivationPath;
 use crate::extended_public_key::EthereumExtendedPublicKey;
 use crate::format::EthereumFormat;
+use crate::mnemonic::EthereumMnemonic;
 use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
+use crate::wordlist::EthereumWordlist;
+use wagyu_model::no_std::*;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    child_depth,
+    crypto::{checksum, constant_time_eq, hash160},
     AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
-    PrivateKey,
+    MnemonicError, PrivateKey,
 };
 
 use base58::{FromBase58, ToBase58};
@@ -51,10 +55,17 @@ impl<N: EthereumNetwork> ExtendedPrivateKey for EthereumExtendedPrivateKey<N> {
 
     /// Returns a new Ethereum extended private key.
     fn new_master(seed: &[u8], _format: &Self::Format) -> Result<Self, ExtendedPrivateKeyError> {
+        // BIP32 specifies seeds between 128 and 512 bits
+        if !(16..=64).contains(&seed.len()) {
+            return Err(ExtendedPrivateKeyError::InvalidSeedLength(seed.len()));
+        }
+
         let mut mac = HmacSha512::new_varkey(b"Bitcoin seed")?; // This is correct. Ethereum uses BIP32.
         mac.input(seed);
         let hmac = mac.result().code();
-        let private_key = Self::PrivateKey::from_secp256k1_secret_key(&SecretKey::parse_slice(&hmac[0..32])?);
+        let secret_key =
+            SecretKey::parse_slice(&hmac[0..32]).map_err(|_| ExtendedPrivateKeyError::InvalidMasterSecretKey)?;
+        let private_key = Self::PrivateKey::from_secp256k1_secret_key(&secret_key);
 
         let mut chain_code = [0u8; 32];
         chain_code[0..32].copy_from_slice(&hmac[32..]);
@@ -71,49 +82,11 @@ impl<N: EthereumNetwork> ExtendedPrivateKey for EthereumExtendedPrivateKey<N> {
 
     /// Returns the extended private key of the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPrivateKeyError> {
-        if self.depth == 255 {
-            return Err(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth));
-        }
-
         let mut extended_private_key = self.clone();
 
         for index in path.to_vec()?.into_iter() {
-            let public_key = &PublicKey::from_secret_key(&extended_private_key.private_key.to_secp256k1_secret_key())
-                .serialize_compressed();
-
-            let mut mac = HmacSha512::new_varkey(&extended_private_key.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(point(kpar)) || ser32(i)).
-                ChildIndex::Normal(_) => mac.input(public_key),
-                // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
-                // (Note: The 0x00 pads the private key to make it 33 bytes long.)
-                ChildIndex::Hardened(_) => {
-                    mac.input(&[0u8]);
-                    mac.input(&extended_private_key.private_key.to_secp256k1_secret_key().serialize());
-                }
-            }
-            // Append the child index in big-endian format
-            mac.input(&u32::from(index).to_be_bytes());
-            let hmac = mac.result().code();
-
-            let mut secret_key = SecretKey::parse_slice(&hmac[0..32])?;
-            secret_key.tweak_add_assign(&extended_private_key.private_key.to_secp256k1_secret_key())?;
-            let private_key = Self::PrivateKey::from_secp256k1_secret_key(&secret_key);
-
-            let mut chain_code = [0u8; 32];
-            chain_code[0..32].copy_from_slice(&hmac[32..]);
-
-            let mut parent_fingerprint = [0u8; 4];
-            parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
-
-            extended_private_key = Self {
-                depth: extended_private_key.depth + 1,
-                parent_fingerprint,
-                child_index: index,
-                chain_code,
-                private_key,
-                _network: PhantomData,
-            }
+            let public_key = extended_private_key.serialize_public_key();
+            extended_private_key = extended_private_key.derive_child(index, &public_key)?;
         }
 
         Ok(extended_private_key)
@@ -140,21 +113,104 @@ impl<N: EthereumNetwork> ExtendedPrivateKey for EthereumExtendedPrivateKey<N> {
     }
 }
 
-impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
-    type Err = ExtendedPrivateKeyError;
+impl<N: EthereumNetwork> EthereumExtendedPrivateKey<N> {
+    /// Returns a new master extended private key from the seed of the given mnemonic and password.
+    pub fn from_mnemonic<W: EthereumWordlist>(
+        mnemonic: &EthereumMnemonic<N, W>,
+        password: Option<&str>,
+    ) -> Result<Self, MnemonicError> {
+        Ok(Self::new_master(
+            &mnemonic.to_seed(password)?,
+            &EthereumFormat::Standard,
+        )?)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s.from_base58()?;
-        if data.len() != 82 {
+    /// Returns the extended private key of the given derivation path,
+    /// derived from the seed of the given mnemonic and password.
+    pub fn from_mnemonic_path<W: EthereumWordlist>(
+        mnemonic: &EthereumMnemonic<N, W>,
+        password: Option<&str>,
+        path: &EthereumDerivationPath<N>,
+    ) -> Result<Self, MnemonicError> {
+        Ok(Self::from_mnemonic(mnemonic, password)?.derive(path)?)
+    }
+
+    /// Returns the extended private key of the given derivation path string, e.g. "m/44'/60'/0'/0/5".
+    pub fn derive_path(&self, path: &str) -> Result<Self, ExtendedPrivateKeyError> {
+        self.derive(&EthereumDerivationPath::from_str(path)?)
+    }
+
+    /// Returns the extended private keys of `count` consecutive child indices, beginning at the
+    /// given child index. The parent public key and fingerprint are computed once for the range,
+    /// which makes this much faster than calling `derive` for each child.
+    pub fn derive_range(&self, start: ChildIndex, count: u32) -> Result<Vec<Self>, ExtendedPrivateKeyError> {
+        let public_key = self.serialize_public_key();
+        start
+            .range(count)?
+            .into_iter()
+            .map(|index| self.derive_child(index, &public_key))
+            .collect()
+    }
+
+    /// Returns the child extended private key of the given child index, given the
+    /// compressed serialization of the public key of this extended private key.
+    fn derive_child(&self, index: ChildIndex, public_key: &[u8; 33]) -> Result<Self, ExtendedPrivateKeyError> {
+        // The depth is serialized in one byte, so a child of a key at depth 255 cannot be represented
+        let depth = child_depth(self.depth).ok_or(ExtendedPrivateKeyError::MaximumChildDepthReached(self.depth))?;
+
+        let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+        match index {
+            // HMAC-SHA512(Key = cpar, Data = serP(point(kpar)) || ser32(i)).
+            ChildIndex::Normal(_) => mac.input(public_key),
+            // HMAC-SHA512(Key = cpar, Data = 0x00 || ser256(kpar) || ser32(i))
+            // (Note: The 0x00 pads the private key to make it 33 bytes long.)
+            ChildIndex::Hardened(_) => {
+                mac.input(&[0u8]);
+                mac.input(&self.private_key.to_secp256k1_secret_key().serialize());
+            }
+        }
+        // Append the child index in big-endian format
+        mac.input(&u32::from(index).to_be_bytes());
+        let hmac = mac.result().code();
+
+        let mut secret_key = SecretKey::parse_slice(&hmac[0..32])?;
+        secret_key.tweak_add_assign(&self.private_key.to_secp256k1_secret_key())?;
+        let private_key = EthereumPrivateKey::from_secp256k1_secret_key(&secret_key);
+
+        let mut chain_code = [0u8; 32];
+        chain_code[0..32].copy_from_slice(&hmac[32..]);
+
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
+
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_index: index,
+            chain_code,
+            private_key,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the compressed serialization of the public key, serP(point(k)).
+    fn serialize_public_key(&self) -> [u8; 33] {
+        PublicKey::from_secret_key(&self.private_key.to_secp256k1_secret_key()).serialize_compressed()
+    }
+
+    /// Returns the extended private key of the given 78-byte BIP32 serialization, without the
+    /// Base58Check checksum.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
+        if data.len() != 78 {
             return Err(ExtendedPrivateKeyError::InvalidByteLength(data.len()));
         }
 
         // Ethereum xkeys are mainnet only
-        if &data[0..4] != [0x04u8, 0x88, 0xAD, 0xE4] {
+        if data[0..4] != [0x04u8, 0x88, 0xAD, 0xE4] {
             return Err(ExtendedPrivateKeyError::InvalidVersionBytes(data[0..4].to_vec()));
         };
 
-        let depth = data[4] as u8;
+        let depth = data[4];
 
         let mut parent_fingerprint = [0u8; 4];
         parent_fingerprint.copy_from_slice(&data[5..9]);
@@ -166,14 +222,6 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
 
         let private_key = EthereumPrivateKey::from_secp256k1_secret_key(&SecretKey::parse_slice(&data[46..78])?);
 
-        let expected = &data[78..82];
-        let checksum = &checksum(&data[0..78])[0..4];
-        if *expected != *checksum {
-            let expected = expected.to_base58();
-            let found = checksum.to_base58();
-            return Err(ExtendedPrivateKeyError::InvalidChecksum(expected, found));
-        }
-
         Ok(Self {
             depth,
             parent_fingerprint,
@@ -185,6 +233,53 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
     }
 }
 
+impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
+    type Err = ExtendedPrivateKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = s.from_base58()?;
+        if data.len() != 82 {
+            return Err(ExtendedPrivateKeyError::InvalidByteLength(data.len()));
+        }
+
+        let extended_private_key = Self::from_bytes(&data[0..78])?;
+
+        let expected = &data[78..82];
+        let checksum = &checksum(&data[0..78])[0..4];
+        if !constant_time_eq(expected, checksum) {
+            let expected = expected.to_base58();
+            let found = checksum.to_base58();
+            return Err(ExtendedPrivateKeyError::InvalidChecksum(expected, found));
+        }
+
+        Ok(extended_private_key)
+    }
+}
+
+impl<'a, N: EthereumNetwork> TryFrom<&'a str> for EthereumExtendedPrivateKey<N> {
+    type Error = ExtendedPrivateKeyError;
+
+    fn try_from(extended_private_key: &'a str) -> Result<Self, Self::Error> {
+        Self::from_str(extended_private_key)
+    }
+}
+
+impl<'a, N: EthereumNetwork> TryFrom<&'a [u8]> for EthereumExtendedPrivateKey<N> {
+    type Error = ExtendedPrivateKeyError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(data)
+    }
+}
+
+impl<N: EthereumNetwork> TryFrom<[u8; 78]> for EthereumExtendedPrivateKey<N> {
+    type Error = ExtendedPrivateKeyError;
+
+    fn try_from(data: [u8; 78]) -> Result<Self, Self::Error> {
+        Self::from_bytes(&data)
+    }
+}
+
 impl<N: EthereumNetwork> Display for EthereumExtendedPrivateKey<N> {
     /// BIP32 serialization format:
     /// https://github.com/ethereum/bips/blob/master/bip-0032.mediawiki#serialization-format
@@ -592,11 +687,179 @@ mod tests {
         }
     }
 
+    mod from_mnemonic {
+        use super::*;
+        use crate::mnemonic::EthereumMnemonic;
+        use crate::wordlist::English;
+        use wagyu_model::mnemonic::Mnemonic;
+
+        type N = Mainnet;
+        type W = English;
+
+        const PHRASE: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        #[test]
+        fn from_mnemonic() {
+            let mnemonic = EthereumMnemonic::<N, W>::from_phrase(PHRASE).unwrap();
+            let extended_private_key = EthereumExtendedPrivateKey::from_mnemonic(&mnemonic, None).unwrap();
+            assert_eq!(
+                "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu",
+                extended_private_key.to_string()
+            );
+        }
+
+        #[test]
+        fn from_mnemonic_path() {
+            let mnemonic = EthereumMnemonic::<N, W>::from_phrase(PHRASE).unwrap();
+            let path = EthereumDerivationPath::from_str("m/44'/60'/0'/0/0").unwrap();
+            let extended_private_key = EthereumExtendedPrivateKey::from_mnemonic_path(&mnemonic, None, &path).unwrap();
+            assert_eq!(
+                "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+                extended_private_key
+                    .to_address(&EthereumFormat::Standard)
+                    .unwrap()
+                    .to_string()
+            );
+        }
+    }
+
+    mod to_address {
+        use super::*;
+        use crate::mnemonic::EthereumMnemonic;
+        use crate::wordlist::English;
+        use wagyu_model::mnemonic::Mnemonic;
+
+        type N = Mainnet;
+
+        const PHRASE: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        const ADDRESS: &str = "0x9858EfFD232B4033E47d90003D41EC34EcaEda94";
+
+        #[test]
+        fn to_address() {
+            let mnemonic = EthereumMnemonic::<N, English>::from_phrase(PHRASE).unwrap();
+            let master = EthereumExtendedPrivateKey::from_mnemonic(&mnemonic, None).unwrap();
+            let format = &EthereumFormat::Standard;
+
+            let extended_private_key = master.derive_path("m/44'/60'/0'/0/0").unwrap();
+            assert_eq!(ADDRESS, extended_private_key.to_address(format).unwrap().to_string());
+            assert_eq!(
+                ADDRESS,
+                extended_private_key
+                    .to_private_key()
+                    .to_address(format)
+                    .unwrap()
+                    .to_string()
+            );
+            assert_eq!(
+                extended_private_key.to_public_key(),
+                extended_private_key.to_extended_public_key().to_public_key()
+            );
+
+            let account = master.derive_path("m/44'/60'/0'").unwrap().to_extended_public_key();
+            let extended_public_key = account.derive_path("m/0/0").unwrap();
+            assert_eq!(ADDRESS, extended_public_key.to_address(format).unwrap().to_string());
+            assert!(account.derive_path("m/0'").is_err());
+        }
+    }
+
+    mod derive_range {
+        use super::*;
+
+        type N = Mainnet;
+
+        const EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        fn test_derive_range(start: ChildIndex, count: u32) {
+            let extended_private_key = EthereumExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let children = extended_private_key.derive_range(start, count).unwrap();
+            assert_eq!(count as usize, children.len());
+
+            children.iter().zip(0..count).for_each(|(child, offset)| {
+                let index = ChildIndex::from(u32::from(start) + offset);
+                let path = EthereumDerivationPath::from_str(&format!("m/{}", index)).unwrap();
+                assert_eq!(extended_private_key.derive(&path).unwrap(), *child);
+            });
+        }
+
+        #[test]
+        fn derive_range_normal() {
+            test_derive_range(ChildIndex::Normal(0), 20);
+        }
+
+        #[test]
+        fn derive_range_hardened() {
+            test_derive_range(ChildIndex::Hardened(0), 20);
+        }
+    }
+
+    mod generic {
+        use super::*;
+
+        type N = Mainnet;
+
+        /// The BIP39 seed of "abandon abandon ... about" with an empty passphrase
+        const SEED: &str = "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc19a5ac40b389cd370d086206dec8aa6c43daea6690f20ad3d8d48b2d2ce9e38e4";
+
+        fn first_address<K: ExtendedPrivateKey>(
+            seed: &[u8],
+            format: &K::Format,
+            path: &K::DerivationPath,
+        ) -> K::Address {
+            K::new(seed, format, path).unwrap().to_address(format).unwrap()
+        }
+
+        #[test]
+        fn test_first_address() {
+            let seed = hex::decode(SEED).unwrap();
+            let path = EthereumDerivationPath::from_str("m/44'/60'/0'/0/0").unwrap();
+            let address = first_address::<EthereumExtendedPrivateKey<N>>(&seed, &EthereumFormat::Standard, &path);
+            assert_eq!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", address.to_string());
+        }
+    }
+
+    mod try_from {
+        use super::*;
+
+        type N = Mainnet;
+
+        const KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        #[test]
+        fn try_from() {
+            let data = KEY.from_base58().unwrap();
+            let from_str = EthereumExtendedPrivateKey::<N>::try_from(KEY).unwrap();
+            assert_eq!(KEY, from_str.to_string());
+            assert_eq!(from_str, EthereumExtendedPrivateKey::try_from(&data[0..78]).unwrap());
+            assert_eq!(
+                from_str,
+                EthereumExtendedPrivateKey::try_from(<[u8; 78]>::try_from(&data[0..78]).unwrap()).unwrap()
+            );
+            assert!(EthereumExtendedPrivateKey::<N>::try_from(&data[..]).is_err());
+        }
+    }
+
     mod test_invalid {
         use super::*;
 
         type N = Mainnet;
 
+        #[test]
+        fn new_master_invalid_seed_length() {
+            [0, 15, 65].iter().for_each(|length| {
+                match EthereumExtendedPrivateKey::<N>::new_master(&vec![0u8; *length], &EthereumFormat::Standard) {
+                    Err(ExtendedPrivateKeyError::InvalidSeedLength(found)) => assert_eq!(*length, found),
+                    _ => panic!("expected an invalid seed length for {} bytes", length),
+                }
+            });
+            [16, 64].iter().for_each(|length| {
+                assert!(
+                    EthereumExtendedPrivateKey::<N>::new_master(&vec![0u8; *length], &EthereumFormat::Standard).is_ok()
+                );
+            });
+        }
+
         const INVALID_EXTENDED_PRIVATE_KEY_SECP256K1_SECRET_KEY: &str = "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fENZ3QzxW";
         const INVALID_EXTENDED_PRIVATE_KEY_NETWORK: &str = "xprv8s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
         const INVALID_EXTENDED_PRIVATE_KEY_CHECKSUM: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHj";
@@ -4,8 +4,10 @@ use crate::extended_private_key::EthereumExtendedPrivateKey;
 use crate::format::EthereumFormat;
 use crate::network::EthereumNetwork;
 use crate::public_key::EthereumPublicKey;
+use wagyu_model::no_std::*;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    child_depth,
+    crypto::{checksum, constant_time_eq, hash160},
     AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey, ExtendedPublicKeyError, PublicKey,
 };
 
@@ -56,46 +58,11 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
 
     /// Returns the extended public key for the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPublicKeyError> {
-        if self.depth == 255 {
-            return Err(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth));
-        }
-
         let mut extended_public_key = self.clone();
 
         for index in path.to_vec()?.into_iter() {
-            let public_key_serialized = &self.public_key.to_secp256k1_public_key().serialize()[..];
-
-            let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
-                ChildIndex::Normal(_) => mac.input(public_key_serialized),
-                // Return failure
-                ChildIndex::Hardened(_) => {
-                    return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
-                }
-            }
-            // Append the child index in big-endian format
-            mac.input(&u32::from(index).to_be_bytes());
-            let hmac = mac.result().code();
-
-            let mut chain_code = [0u8; 32];
-            chain_code[0..32].copy_from_slice(&hmac[32..]);
-
-            let mut public_key = self.public_key.to_secp256k1_public_key();
-            public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
-            let public_key = Self::PublicKey::from_secp256k1_public_key(public_key);
-
-            let mut parent_fingerprint = [0u8; 4];
-            parent_fingerprint.copy_from_slice(&hash160(public_key_serialized)[0..4]);
-
-            extended_public_key = Self {
-                depth: extended_public_key.depth + 1,
-                parent_fingerprint,
-                child_index: index,
-                chain_code,
-                public_key,
-                _network: PhantomData,
-            };
+            let public_key = extended_public_key.serialize_public_key();
+            extended_public_key = extended_public_key.derive_child(index, &public_key)?;
         }
 
         Ok(extended_public_key)
@@ -112,20 +79,79 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
     }
 }
 
-impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
-    type Err = ExtendedPublicKeyError;
+impl<N: EthereumNetwork> EthereumExtendedPublicKey<N> {
+    /// Returns the extended public key of the given derivation path string, e.g. "m/0/5".
+    pub fn derive_path(&self, path: &str) -> Result<Self, ExtendedPublicKeyError> {
+        self.derive(&EthereumDerivationPath::from_str(path)?)
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let data = s.from_base58()?;
-        if data.len() != 82 {
+    /// Returns the extended public keys of `count` consecutive normal child indices, beginning at
+    /// the given child index. The parent public key and fingerprint are serialized once for the
+    /// range, which makes this faster than calling `derive` for each child.
+    pub fn derive_range(&self, start: ChildIndex, count: u32) -> Result<Vec<Self>, ExtendedPublicKeyError> {
+        let public_key = self.serialize_public_key();
+        start
+            .range(count)?
+            .into_iter()
+            .map(|index| self.derive_child(index, &public_key))
+            .collect()
+    }
+
+    /// Returns the child extended public key of the given child index, given the
+    /// compressed serialization of the public key of this extended public key.
+    fn derive_child(&self, index: ChildIndex, public_key: &[u8; 33]) -> Result<Self, ExtendedPublicKeyError> {
+        // The depth is serialized in one byte, so a child of a key at depth 255 cannot be represented
+        let depth = child_depth(self.depth).ok_or(ExtendedPublicKeyError::MaximumChildDepthReached(self.depth))?;
+
+        let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
+        match index {
+            // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
+            ChildIndex::Normal(_) => mac.input(public_key),
+            // Return failure
+            ChildIndex::Hardened(_) => {
+                return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
+            }
+        }
+        // Append the child index in big-endian format
+        mac.input(&u32::from(index).to_be_bytes());
+        let hmac = mac.result().code();
+
+        let mut chain_code = [0u8; 32];
+        chain_code[0..32].copy_from_slice(&hmac[32..]);
+
+        let mut child_public_key = self.public_key.to_secp256k1_public_key();
+        child_public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
+
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&hash160(public_key)[0..4]);
+
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_index: index,
+            chain_code,
+            public_key: EthereumPublicKey::from_secp256k1_public_key(child_public_key),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the compressed serialization of the public key, serP(K).
+    fn serialize_public_key(&self) -> [u8; 33] {
+        self.public_key.to_secp256k1_public_key().serialize_compressed()
+    }
+
+    /// Returns the extended public key of the given 78-byte BIP32 serialization, without the
+    /// Base58Check checksum.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
+        if data.len() != 78 {
             return Err(ExtendedPublicKeyError::InvalidByteLength(data.len()));
         }
 
-        if &data[0..4] != [0x04u8, 0x88, 0xB2, 0x1E] {
+        if data[0..4] != [0x04u8, 0x88, 0xB2, 0x1E] {
             return Err(ExtendedPublicKeyError::InvalidVersionBytes(data[0..4].to_vec()));
         };
 
-        let depth = data[4] as u8;
+        let depth = data[4];
 
         let mut parent_fingerprint = [0u8; 4];
         parent_fingerprint.copy_from_slice(&data[5..9]);
@@ -139,14 +165,6 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
             &Secp256k1_PublicKey::parse_slice(&data[45..78], None)?.serialize()[1..],
         ))?;
 
-        let expected = &data[78..82];
-        let checksum = &checksum(&data[0..78])[0..4];
-        if *expected != *checksum {
-            let expected = expected.to_base58();
-            let found = checksum.to_base58();
-            return Err(ExtendedPublicKeyError::InvalidChecksum(expected, found));
-        }
-
         Ok(Self {
             depth,
             parent_fingerprint,
@@ -158,6 +176,75 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
     }
 }
 
+impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
+    type Err = ExtendedPublicKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = s.from_base58()?;
+        if data.len() != 82 {
+            return Err(ExtendedPublicKeyError::InvalidByteLength(data.len()));
+        }
+
+        let extended_public_key = Self::from_bytes(&data[0..78])?;
+
+        let expected = &data[78..82];
+        let checksum = &checksum(&data[0..78])[0..4];
+        if !constant_time_eq(expected, checksum) {
+            let expected = expected.to_base58();
+            let found = checksum.to_base58();
+            return Err(ExtendedPublicKeyError::InvalidChecksum(expected, found));
+        }
+
+        Ok(extended_public_key)
+    }
+}
+
+impl<'a, N: EthereumNetwork> From<&'a EthereumExtendedPrivateKey<N>> for EthereumExtendedPublicKey<N> {
+    /// Returns the extended public key of the corresponding extended private key.
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use wagyu_ethereum::{EthereumExtendedPrivateKey, EthereumExtendedPublicKey, Mainnet};
+    ///
+    /// let xprv = EthereumExtendedPrivateKey::<Mainnet>::try_from(
+    ///     "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+    /// )
+    /// .unwrap();
+    /// let xpub = EthereumExtendedPublicKey::from(&xprv);
+    /// assert_eq!(
+    ///     "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+    ///     xpub.to_string()
+    /// );
+    /// ```
+    fn from(extended_private_key: &'a EthereumExtendedPrivateKey<N>) -> Self {
+        Self::from_extended_private_key(extended_private_key)
+    }
+}
+
+impl<'a, N: EthereumNetwork> TryFrom<&'a str> for EthereumExtendedPublicKey<N> {
+    type Error = ExtendedPublicKeyError;
+
+    fn try_from(extended_public_key: &'a str) -> Result<Self, Self::Error> {
+        Self::from_str(extended_public_key)
+    }
+}
+
+impl<'a, N: EthereumNetwork> TryFrom<&'a [u8]> for EthereumExtendedPublicKey<N> {
+    type Error = ExtendedPublicKeyError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(data)
+    }
+}
+
+impl<N: EthereumNetwork> TryFrom<[u8; 78]> for EthereumExtendedPublicKey<N> {
+    type Error = ExtendedPublicKeyError;
+
+    fn try_from(data: [u8; 78]) -> Result<Self, Self::Error> {
+        Self::from_bytes(&data)
+    }
+}
+
 impl<N: EthereumNetwork> fmt::Display for EthereumExtendedPublicKey<N> {
     /// BIP32 serialization format
     /// https://github.com/ethereum/bips/blob/master/bip-0032.mediawiki#serialization-format
@@ -459,6 +546,94 @@ mod tests {
         }
     }
 
+    mod derive_normal_path {
+        use super::*;
+        use crate::mnemonic::EthereumMnemonic;
+        use crate::wordlist::English;
+        use wagyu_model::mnemonic::Mnemonic;
+
+        type N = Mainnet;
+
+        const EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+
+        #[test]
+        fn derive() {
+            let extended_private_key = EthereumExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let extended_public_key = extended_private_key.to_extended_public_key();
+
+            ["m/0", "m/0/1", "m/0/1/2/3", "m/0/2147483647/1/2147483646/2"]
+                .iter()
+                .for_each(|path| {
+                    let path = EthereumDerivationPath::from_str(path).unwrap();
+                    let expected_extended_public_key =
+                        extended_private_key.derive(&path).unwrap().to_extended_public_key();
+                    assert_eq!(expected_extended_public_key, extended_public_key.derive(&path).unwrap());
+                });
+        }
+
+        #[test]
+        fn derive_address() {
+            let phrase =
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+            let mnemonic = EthereumMnemonic::<N, English>::from_phrase(phrase).unwrap();
+            let account = EthereumDerivationPath::from_str("m/44'/60'/0'").unwrap();
+            let extended_private_key =
+                EthereumExtendedPrivateKey::from_mnemonic_path(&mnemonic, None, &account).unwrap();
+            let extended_public_key = extended_private_key.to_extended_public_key();
+
+            let path = EthereumDerivationPath::from_str("m/0/0").unwrap();
+            let address = extended_public_key
+                .derive(&path)
+                .unwrap()
+                .to_address(&EthereumFormat::Standard)
+                .unwrap();
+            assert_eq!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", address.to_string());
+        }
+
+        #[test]
+        fn derive_range() {
+            let extended_private_key = EthereumExtendedPrivateKey::<N>::from_str(EXTENDED_PRIVATE_KEY).unwrap();
+            let extended_public_key = extended_private_key.to_extended_public_key();
+            let children = extended_public_key.derive_range(ChildIndex::Normal(0), 10).unwrap();
+            assert_eq!(10, children.len());
+
+            children.iter().enumerate().for_each(|(index, child)| {
+                let path = EthereumDerivationPath::from_str(&format!("m/{}", index)).unwrap();
+                assert_eq!(extended_public_key.derive(&path).unwrap(), *child);
+            });
+            assert!(extended_public_key.derive_range(ChildIndex::Hardened(0), 1).is_err());
+        }
+    }
+
+    mod try_from {
+        use super::*;
+
+        type N = Mainnet;
+
+        const KEY: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+        #[test]
+        fn try_from() {
+            let data = KEY.from_base58().unwrap();
+            let from_str = EthereumExtendedPublicKey::<N>::try_from(KEY).unwrap();
+            assert_eq!(KEY, from_str.to_string());
+            assert_eq!(from_str, EthereumExtendedPublicKey::try_from(&data[0..78]).unwrap());
+            assert_eq!(
+                from_str,
+                EthereumExtendedPublicKey::try_from(<[u8; 78]>::try_from(&data[0..78]).unwrap()).unwrap()
+            );
+            assert!(EthereumExtendedPublicKey::<N>::try_from(&data[..]).is_err());
+        }
+
+        #[test]
+        fn from_extended_private_key() {
+            let extended_private_key = EthereumExtendedPrivateKey::<N>::from_str("xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi").unwrap();
+            let extended_public_key = EthereumExtendedPublicKey::from(&extended_private_key);
+            assert_eq!(KEY, extended_public_key.to_string());
+            assert_eq!(extended_private_key.to_extended_public_key(), extended_public_key);
+        }
+    }
+
     mod test_invalid {
         use super::*;
 
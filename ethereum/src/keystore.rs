@@ -0,0 +1,579 @@
+//!
+//! Keystore
+//!
+//! This module contains the Web3 Secret Storage Definition (version 3) used by geth and
+//! MetaMask to store encrypted private keys as JSON (also known as UTC / JSON keystore files).
+//!
+//! The password is stretched with scrypt or PBKDF2 into a 32 byte derived key. The first half
+//! of the derived key encrypts the private key with AES-128-CTR, and the second half is
+//! prepended to the ciphertext and hashed with Keccak-256 to form the MAC.
+//!
+
+use crate::address::EthereumAddress;
+use crate::format::EthereumFormat;
+use crate::private_key::EthereumPrivateKey;
+use wagyu_model::crypto::{constant_time_eq, keccak256, scrypt};
+use wagyu_model::no_std::*;
+use wagyu_model::{Address, AddressError, PrivateKeyError};
+
+use aes::cipher::{generic_array::GenericArray, NewCipher, StreamCipher};
+use aes::Aes128Ctr;
+use core::{fmt, str::FromStr};
+use hmac::Hmac;
+use pbkdf2::pbkdf2;
+use rand::{CryptoRng, Rng};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// The keystore version implemented by this module.
+pub const KEYSTORE_VERSION: u32 = 3;
+
+/// The only cipher supported by version 3 keystores.
+const KEYSTORE_CIPHER: &str = "aes-128-ctr";
+
+/// The only pseudorandom function supported for PBKDF2 keystores.
+const KEYSTORE_PRF: &str = "hmac-sha256";
+
+/// The byte length of the derived key.
+const KEYSTORE_DKLEN: u32 = 32;
+
+/// The largest derived key length accepted from a keystore.
+const MAX_KEYSTORE_DKLEN: u32 = 64;
+
+/// The largest scrypt memory cost (`128 * r * n` bytes) accepted from a keystore, which is
+/// the cost of the parameters used by geth.
+const MAX_SCRYPT_MEMORY: usize = 256 * 1024 * 1024;
+
+/// The largest scrypt work factor (`n * r * p`) accepted from a keystore, twice that of the
+/// parameters used by geth.
+const MAX_SCRYPT_WORK: usize = 1 << 22;
+
+/// The largest PBKDF2 iteration count accepted from a keystore, sixteen times that of the
+/// parameters used by geth.
+const MAX_PBKDF2_ITERATIONS: u32 = 1 << 22;
+
+#[derive(Debug, Fail)]
+pub enum KeystoreError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(
+        display = "invalid derived key length: {{ expected: {:?} to {:?}, found: {:?} }}",
+        _0, _1, _2
+    )]
+    InvalidDerivedKeyLength(u32, u32, u32),
+
+    #[fail(display = "invalid iterations: {{ expected: at most {:?}, found: {:?} }}", _0, _1)]
+    InvalidIterations(u32, u32),
+
+    #[fail(display = "invalid iv length: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidIvLength(usize, usize),
+
+    #[fail(display = "invalid mac: the password is incorrect or the keystore is corrupted")]
+    InvalidMac,
+
+    #[fail(display = "invalid scrypt parameters: {{ n: {:?}, r: {:?}, p: {:?} }}", _0, _1, _2)]
+    InvalidScryptParameters(u32, u32, u32),
+
+    #[fail(display = "{}", _0)]
+    PrivateKeyError(PrivateKeyError),
+
+    #[fail(display = "unsupported cipher: {}", _0)]
+    UnsupportedCipher(String),
+
+    #[fail(display = "unsupported pseudorandom function: {}", _0)]
+    UnsupportedPrf(String),
+
+    #[fail(display = "unsupported keystore version: {}", _0)]
+    UnsupportedVersion(u32),
+}
+
+impl From<AddressError> for KeystoreError {
+    fn from(error: AddressError) -> Self {
+        KeystoreError::AddressError(error)
+    }
+}
+
+impl From<hex::FromHexError> for KeystoreError {
+    fn from(error: hex::FromHexError) -> Self {
+        KeystoreError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<PrivateKeyError> for KeystoreError {
+    fn from(error: PrivateKeyError) -> Self {
+        KeystoreError::PrivateKeyError(error)
+    }
+}
+
+impl From<secp256k1::Error> for KeystoreError {
+    fn from(error: secp256k1::Error) -> Self {
+        KeystoreError::PrivateKeyError(PrivateKeyError::from(error))
+    }
+}
+
+impl From<serde_json::Error> for KeystoreError {
+    fn from(error: serde_json::Error) -> Self {
+        KeystoreError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+/// Represents the scrypt parameters of a keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParameters {
+    pub dklen: u32,
+    pub n: u32,
+    pub p: u32,
+    pub r: u32,
+    pub salt: String,
+}
+
+/// Represents the PBKDF2 parameters of a keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pbkdf2Parameters {
+    pub c: u32,
+    pub dklen: u32,
+    pub prf: String,
+    pub salt: String,
+}
+
+/// Represents the key derivation function of a keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum KeystoreKdf {
+    Scrypt(ScryptParameters),
+    Pbkdf2(Pbkdf2Parameters),
+}
+
+impl KeystoreKdf {
+    /// Returns the scrypt parameters used by geth, with the given salt.
+    pub fn scrypt(salt: &[u8; 32]) -> Self {
+        KeystoreKdf::Scrypt(ScryptParameters {
+            dklen: KEYSTORE_DKLEN,
+            n: 262144,
+            p: 1,
+            r: 8,
+            salt: hex::encode(salt),
+        })
+    }
+
+    /// Returns the PBKDF2 parameters used by geth, with the given salt.
+    pub fn pbkdf2(salt: &[u8; 32]) -> Self {
+        KeystoreKdf::Pbkdf2(Pbkdf2Parameters {
+            c: 262144,
+            dklen: KEYSTORE_DKLEN,
+            prf: KEYSTORE_PRF.into(),
+            salt: hex::encode(salt),
+        })
+    }
+
+    /// Returns the key derived from the given password.
+    fn derive_key(&self, password: &str) -> Result<Vec<u8>, KeystoreError> {
+        match self {
+            KeystoreKdf::Scrypt(params) => {
+                validate_dklen(params.dklen)?;
+                let (n, r, p) = (params.n as usize, params.r as usize, params.p as usize);
+                let valid = n > 1
+                    && n.is_power_of_two()
+                    && r > 0
+                    && p > 0
+                    && n.checked_mul(r)
+                        .and_then(|work| work.checked_mul(p))
+                        .unwrap_or(usize::max_value())
+                        <= MAX_SCRYPT_WORK
+                    && n.checked_mul(r)
+                        .and_then(|size| size.checked_mul(128))
+                        .unwrap_or(usize::max_value())
+                        <= MAX_SCRYPT_MEMORY;
+                if !valid {
+                    return Err(KeystoreError::InvalidScryptParameters(params.n, params.r, params.p));
+                }
+
+                let mut derived_key = vec![0u8; params.dklen as usize];
+                scrypt(
+                    password.as_bytes(),
+                    &hex::decode(&params.salt)?,
+                    n,
+                    r,
+                    p,
+                    &mut derived_key,
+                );
+                Ok(derived_key)
+            }
+            KeystoreKdf::Pbkdf2(params) => {
+                validate_dklen(params.dklen)?;
+                if params.c == 0 || params.c > MAX_PBKDF2_ITERATIONS {
+                    return Err(KeystoreError::InvalidIterations(MAX_PBKDF2_ITERATIONS, params.c));
+                }
+                if params.prf != KEYSTORE_PRF {
+                    return Err(KeystoreError::UnsupportedPrf(params.prf.clone()));
+                }
+
+                let mut derived_key = vec![0u8; params.dklen as usize];
+                pbkdf2::<Hmac<Sha256>>(
+                    password.as_bytes(),
+                    &hex::decode(&params.salt)?,
+                    params.c as usize,
+                    &mut derived_key,
+                );
+                Ok(derived_key)
+            }
+        }
+    }
+}
+
+/// Returns an error if the given derived key length is too short for the cipher and MAC keys,
+/// or longer than any keystore needs.
+fn validate_dklen(dklen: u32) -> Result<(), KeystoreError> {
+    match (KEYSTORE_DKLEN..=MAX_KEYSTORE_DKLEN).contains(&dklen) {
+        true => Ok(()),
+        false => Err(KeystoreError::InvalidDerivedKeyLength(
+            KEYSTORE_DKLEN,
+            MAX_KEYSTORE_DKLEN,
+            dklen,
+        )),
+    }
+}
+
+/// Represents the cipher parameters of a keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCipherParameters {
+    pub iv: String,
+}
+
+/// Represents the encrypted contents of a keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: KeystoreCipherParameters,
+    pub ciphertext: String,
+    #[serde(flatten)]
+    pub kdf: KeystoreKdf,
+    pub mac: String,
+}
+
+/// Represents a Web3 Secret Storage (version 3) keystore
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(alias = "Crypto")]
+    pub crypto: KeystoreCrypto,
+    pub id: String,
+    pub version: u32,
+}
+
+impl KeystoreJson {
+    /// Returns a keystore encrypting the given private key with the given password and key
+    /// derivation function, using a randomly-generated iv and id.
+    pub fn new<R: Rng + CryptoRng>(
+        private_key: &EthereumPrivateKey,
+        password: &str,
+        kdf: KeystoreKdf,
+        rng: &mut R,
+    ) -> Result<Self, KeystoreError> {
+        let iv: [u8; 16] = rng.gen();
+        let id: [u8; 16] = rng.gen();
+        Self::encrypt(private_key, password, kdf, &iv, &id)
+    }
+
+    /// Returns the private key decrypted from the keystore with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<EthereumPrivateKey, KeystoreError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(KeystoreError::UnsupportedVersion(self.version));
+        }
+        if self.crypto.cipher != KEYSTORE_CIPHER {
+            return Err(KeystoreError::UnsupportedCipher(self.crypto.cipher.clone()));
+        }
+
+        let derived_key = self.crypto.kdf.derive_key(password)?;
+        let mut ciphertext = hex::decode(&self.crypto.ciphertext)?;
+        let mac = hex::decode(&self.crypto.mac)?;
+
+        // Compare in constant time to avoid leaking how much of the MAC matched.
+        let expected_mac = keccak256(&[&derived_key[16..32], &ciphertext[..]].concat());
+        if !constant_time_eq(&mac, &expected_mac) {
+            return Err(KeystoreError::InvalidMac);
+        }
+
+        apply_keystream(
+            &derived_key[..16],
+            &hex::decode(&self.crypto.cipherparams.iv)?,
+            &mut ciphertext,
+        )?;
+        let secret_key = secp256k1::SecretKey::parse_slice(&ciphertext)?;
+        Ok(EthereumPrivateKey::from_secp256k1_secret_key(&secret_key))
+    }
+
+    /// Returns a keystore encrypting the given private key with the given parameters.
+    fn encrypt(
+        private_key: &EthereumPrivateKey,
+        password: &str,
+        kdf: KeystoreKdf,
+        iv: &[u8; 16],
+        id: &[u8; 16],
+    ) -> Result<Self, KeystoreError> {
+        let derived_key = kdf.derive_key(password)?;
+
+        let mut ciphertext = private_key.to_secp256k1_secret_key().serialize().to_vec();
+        apply_keystream(&derived_key[..16], iv, &mut ciphertext)?;
+        let mac = keccak256(&[&derived_key[16..32], &ciphertext[..]].concat());
+
+        let address = EthereumAddress::from_private_key(private_key, &EthereumFormat::Standard)?;
+
+        Ok(Self {
+            address: Some(address.to_string()[2..].to_lowercase()),
+            crypto: KeystoreCrypto {
+                cipher: KEYSTORE_CIPHER.into(),
+                cipherparams: KeystoreCipherParameters { iv: hex::encode(iv) },
+                ciphertext: hex::encode(ciphertext),
+                kdf,
+                mac: hex::encode(mac),
+            },
+            id: uuid_v4(id),
+            version: KEYSTORE_VERSION,
+        })
+    }
+}
+
+impl FromStr for KeystoreJson {
+    type Err = KeystoreError;
+
+    fn from_str(keystore: &str) -> Result<Self, KeystoreError> {
+        Ok(serde_json::from_str(keystore)?)
+    }
+}
+
+impl fmt::Display for KeystoreJson {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(json) => write!(f, "{}", json),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// Applies the AES-128-CTR keystream of the given key and iv to the given data.
+fn apply_keystream(key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), KeystoreError> {
+    if iv.len() != 16 {
+        return Err(KeystoreError::InvalidIvLength(16, iv.len()));
+    }
+    Aes128Ctr::new(GenericArray::from_slice(key), GenericArray::from_slice(iv)).apply_keystream(data);
+    Ok(())
+}
+
+/// Returns the given bytes formatted as a random (version 4) UUID.
+fn uuid_v4(bytes: &[u8; 16]) -> String {
+    let mut bytes = *bytes;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    format!(
+        "{}-{}-{}-{}-{}",
+        hex::encode(&bytes[0..4]),
+        hex::encode(&bytes[4..6]),
+        hex::encode(&bytes[6..8]),
+        hex::encode(&bytes[8..10]),
+        hex::encode(&bytes[10..16])
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+    const PASSWORD: &str = "testpassword";
+
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    const SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+            "ciphertext": "d172bf743a674da9cdad04534d56926ef8358534d458fffccd4e6ad2fbde479c",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 262144,
+                "p": 8,
+                "r": 1,
+                "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+            },
+            "mac": "2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    fn test_decrypt(keystore: &str) {
+        let keystore = KeystoreJson::from_str(keystore).unwrap();
+        let private_key = keystore.decrypt(PASSWORD).unwrap();
+        assert_eq!(PRIVATE_KEY, private_key.to_string());
+    }
+
+    fn test_encrypt(keystore: &str) {
+        let expected = KeystoreJson::from_str(keystore).unwrap();
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+
+        let mut iv = [0u8; 16];
+        iv.copy_from_slice(&hex::decode(&expected.crypto.cipherparams.iv).unwrap());
+
+        let keystore =
+            KeystoreJson::encrypt(&private_key, PASSWORD, expected.crypto.kdf.clone(), &iv, &[0u8; 16]).unwrap();
+        assert_eq!(expected.crypto, keystore.crypto);
+    }
+
+    fn test_invalid_password(kdf: KeystoreKdf) {
+        let rng = &mut StdRng::seed_from_u64(1231275789u64);
+        let private_key = EthereumPrivateKey::new_random(rng);
+        let keystore = KeystoreJson::new(&private_key, PASSWORD, kdf, rng).unwrap();
+        assert!(matches!(
+            keystore.decrypt("wrongpassword"),
+            Err(KeystoreError::InvalidMac)
+        ));
+    }
+
+    mod pbkdf2 {
+        use super::*;
+
+        #[test]
+        fn decrypt() {
+            test_decrypt(PBKDF2_KEYSTORE);
+        }
+
+        #[test]
+        fn encrypt() {
+            test_encrypt(PBKDF2_KEYSTORE);
+        }
+
+        #[test]
+        fn invalid_password() {
+            test_invalid_password(KeystoreKdf::Pbkdf2(Pbkdf2Parameters {
+                c: 1024,
+                dklen: 32,
+                prf: KEYSTORE_PRF.into(),
+                salt: hex::encode([1u8; 32]),
+            }));
+        }
+    }
+
+    mod scrypt {
+        use super::*;
+
+        #[test]
+        fn decrypt() {
+            test_decrypt(SCRYPT_KEYSTORE);
+        }
+
+        #[test]
+        fn encrypt() {
+            test_encrypt(SCRYPT_KEYSTORE);
+        }
+
+        #[test]
+        fn invalid_password() {
+            test_invalid_password(KeystoreKdf::Scrypt(ScryptParameters {
+                dklen: 32,
+                n: 1024,
+                p: 1,
+                r: 8,
+                salt: hex::encode([1u8; 32]),
+            }));
+        }
+    }
+
+    #[test]
+    fn keystore_round_trip() {
+        let rng = &mut StdRng::seed_from_u64(1231275789u64);
+        let private_key = EthereumPrivateKey::new_random(rng);
+        let kdf = KeystoreKdf::Scrypt(ScryptParameters {
+            dklen: 32,
+            n: 1024,
+            p: 1,
+            r: 8,
+            salt: hex::encode(rng.gen::<[u8; 32]>()),
+        });
+
+        let keystore = KeystoreJson::new(&private_key, PASSWORD, kdf, rng).unwrap();
+        let keystore = KeystoreJson::from_str(&keystore.to_string()).unwrap();
+        assert_eq!(private_key, keystore.decrypt(PASSWORD).unwrap());
+        assert_eq!(
+            EthereumAddress::from_private_key(&private_key, &EthereumFormat::Standard)
+                .unwrap()
+                .to_string()[2..]
+                .to_lowercase(),
+            keystore.address.unwrap()
+        );
+        assert_eq!(Some('4'), keystore.id.chars().nth(14));
+    }
+
+    #[test]
+    fn excessive_kdf_parameters() {
+        let mut keystore = KeystoreJson::from_str(SCRYPT_KEYSTORE).unwrap();
+        for (n, r, p) in [(1 << 30, 1, 1), (1 << 18, 16, 1), (1 << 18, 1, 32)].iter() {
+            keystore.crypto.kdf = KeystoreKdf::Scrypt(ScryptParameters {
+                dklen: 32,
+                n: *n,
+                p: *p,
+                r: *r,
+                salt: String::new(),
+            });
+            assert!(matches!(
+                keystore.decrypt(PASSWORD),
+                Err(KeystoreError::InvalidScryptParameters(..))
+            ));
+        }
+
+        keystore.crypto.kdf = KeystoreKdf::Pbkdf2(Pbkdf2Parameters {
+            c: u32::max_value(),
+            dklen: 32,
+            prf: KEYSTORE_PRF.into(),
+            salt: String::new(),
+        });
+        assert!(matches!(
+            keystore.decrypt(PASSWORD),
+            Err(KeystoreError::InvalidIterations(..))
+        ));
+
+        keystore.crypto.kdf = KeystoreKdf::Pbkdf2(Pbkdf2Parameters {
+            c: 1,
+            dklen: u32::max_value(),
+            prf: KEYSTORE_PRF.into(),
+            salt: String::new(),
+        });
+        assert!(matches!(
+            keystore.decrypt(PASSWORD),
+            Err(KeystoreError::InvalidDerivedKeyLength(..))
+        ));
+    }
+
+    #[test]
+    fn unsupported_cipher() {
+        let mut keystore = KeystoreJson::from_str(PBKDF2_KEYSTORE).unwrap();
+        keystore.crypto.cipher = "aes-128-cbc".into();
+        assert!(matches!(
+            keystore.decrypt(PASSWORD),
+            Err(KeystoreError::UnsupportedCipher(_))
+        ));
+    }
+}
@@ -6,6 +6,9 @@
 #![warn(unused_extern_crates, dead_code)]
 #![forbid(unsafe_code)]
 
+#[macro_use]
+extern crate failure;
+
 pub mod address;
 pub use self::address::*;
 
@@ -15,6 +18,15 @@ pub use self::amount::*;
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod eip1559;
+pub use self::eip1559::*;
+
+pub mod eip712;
+pub use self::eip712::*;
+
+pub mod ens;
+pub use self::ens::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -24,6 +36,12 @@ pub use self::extended_public_key::*;
 pub mod format;
 pub use self::format::*;
 
+pub mod keystore;
+pub use self::keystore::*;
+
+pub mod message;
+pub use self::message::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
@@ -36,6 +54,9 @@ pub use self::private_key::*;
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod rlp;
+pub use self::rlp::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
@@ -0,0 +1,98 @@
+//!
+//! Message
+//!
+//! This module contains the EIP-191 (version 0x45) message hashing used by `personal_sign`
+//! and `eth_sign`, along with the layout of the resulting 65 byte r || s || v signatures.
+//!
+//! Wallets encode v as 27 + recovery id, while some signers use the raw recovery id.
+//! Both conventions are accepted during verification.
+//!
+
+use crate::address::EthereumAddress;
+use crate::format::EthereumFormat;
+use crate::public_key::EthereumPublicKey;
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, PublicKey};
+
+/// The prefix prepended to every message before hashing.
+pub const MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+/// The v byte of a message signature with recovery id 0.
+pub const MESSAGE_SIGNATURE_V: u8 = 27;
+
+/// The byte length of a message signature.
+pub const MESSAGE_SIGNATURE_SIZE: usize = 65;
+
+/// Returns the Keccak-256 hash of the given message, prefixed with the message prefix and
+/// the decimal byte length of the message.
+pub fn message_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = format!("{}{}", MESSAGE_PREFIX, message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+/// Returns the address of the key that produced the given r || s || v signature of the message.
+pub fn recover_address(message: &[u8], signature: &[u8]) -> Result<EthereumAddress, AddressError> {
+    let (signature, recovery_id) = parse_signature(signature)?;
+    let message = secp256k1::Message::parse(&message_hash(message));
+    let public_key =
+        EthereumPublicKey::from_secp256k1_public_key(secp256k1::recover(&message, &signature, &recovery_id)?);
+    public_key.to_address(&EthereumFormat::Standard)
+}
+
+/// Returns the signature and recovery id of the given r || s || v signature.
+pub(crate) fn parse_signature(signature: &[u8]) -> Result<(secp256k1::Signature, secp256k1::RecoveryId), AddressError> {
+    if signature.len() != MESSAGE_SIGNATURE_SIZE {
+        return Err(AddressError::InvalidSignatureLength(signature.len()));
+    }
+
+    let recovery_id = match signature[64] {
+        v @ 0..=1 => v,
+        v @ 27..=28 => v - MESSAGE_SIGNATURE_V,
+        v => return Err(AddressError::InvalidSignatureHeader(v)),
+    };
+
+    Ok((
+        secp256k1::Signature::parse_slice(&signature[..64])?,
+        secp256k1::RecoveryId::parse(recovery_id)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[test]
+    fn test_message_hash() {
+        assert_eq!(
+            "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655",
+            hex::encode(message_hash(b"Some data"))
+        );
+    }
+
+    #[test]
+    fn test_recover_address() {
+        let signature = hex::decode("b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c").unwrap();
+        assert_eq!(
+            EthereumAddress::from_str("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23").unwrap(),
+            recover_address(b"Some data", &signature).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_signature() {
+        assert!(matches!(
+            recover_address(b"Some data", &[0u8; 64]),
+            Err(AddressError::InvalidSignatureLength(64))
+        ));
+
+        let mut signature = [0u8; 65];
+        signature[64] = 29;
+        assert!(matches!(
+            recover_address(b"Some data", &signature),
+            Err(AddressError::InvalidSignatureHeader(29))
+        ));
+    }
+}
@@ -6,14 +6,16 @@ use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
 use crate::wordlist::EthereumWordlist;
-use wagyu_model::{ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
+use wagyu_model::no_std::*;
+use wagyu_model::{EntropySize, ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
 
 use bitvec::prelude::*;
-use core::{fmt, marker::PhantomData, ops::Div, str, str::FromStr};
+use core::{convert::TryFrom, fmt, marker::PhantomData, str, str::FromStr};
 use hmac::Hmac;
 use pbkdf2::pbkdf2;
 use rand::Rng;
 use sha2::{Digest, Sha256, Sha512};
+use unicode_normalization::UnicodeNormalization;
 
 const PBKDF2_ROUNDS: usize = 2048;
 const PBKDF2_BYTES: usize = 64;
@@ -32,22 +34,7 @@ pub struct EthereumMnemonic<N: EthereumNetwork, W: EthereumWordlist> {
 impl<N: EthereumNetwork, W: EthereumWordlist> MnemonicCount for EthereumMnemonic<N, W> {
     /// Returns a new mnemonic given the word count.
     fn new_with_count<R: Rng>(rng: &mut R, word_count: u8) -> Result<Self, MnemonicError> {
-        let length: usize = match word_count {
-            12 => 16,
-            15 => 20,
-            18 => 24,
-            21 => 28,
-            24 => 32,
-            wc => return Err(MnemonicError::InvalidWordCount(wc)),
-        };
-
-        let entropy: [u8; 32] = rng.gen();
-
-        Ok(Self {
-            entropy: entropy[0..length].to_vec(),
-            _network: PhantomData,
-            _wordlist: PhantomData,
-        })
+        Ok(Self::new_with_size(rng, EntropySize::from_word_count(word_count)?))
     }
 }
 
@@ -59,26 +46,17 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
 
     /// Returns a new mnemonic.
     fn new<R: Rng>(rng: &mut R) -> Result<Self, MnemonicError> {
-        let entropy: [u8; 16] = rng.gen();
-        Ok(Self {
-            entropy: entropy.to_vec(),
-            _network: PhantomData,
-            _wordlist: PhantomData,
-        })
+        Ok(Self::new_with_size(rng, EntropySize::Entropy128))
     }
 
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
-        let mnemonic = phrase.split(" ").collect::<Vec<&str>>();
-
-        let length = match mnemonic.len() {
-            12 => 128,
-            15 => 160,
-            18 => 192,
-            21 => 224,
-            24 => 256,
-            wc => return Err(MnemonicError::InvalidWordCount(wc as u8)),
-        };
+        // Normalize the phrase to NFKD form, which also replaces ideographic spaces.
+        let normalized = phrase.nfkd().collect::<String>();
+        let mnemonic = normalized.split(" ").collect::<Vec<&str>>();
+
+        let word_count = u8::try_from(mnemonic.len()).map_err(|_| MnemonicError::InvalidWordCount(mnemonic.len()))?;
+        let size = EntropySize::from_word_count(word_count)?;
 
         let mut entropy: BitVec<Msb0, u8> = BitVec::new();
 
@@ -91,13 +69,13 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
         }
 
         let mnemonic = Self {
-            entropy: entropy[..length].as_slice().to_vec(),
+            entropy: entropy[..size.entropy_bits()].as_slice().to_vec(),
             _network: PhantomData,
             _wordlist: PhantomData,
         };
 
         // Ensures the checksum word matches the checksum word in the given phrase.
-        match phrase == mnemonic.to_phrase()? {
+        match normalized == mnemonic.to_phrase()?.nfkd().collect::<String>() {
             true => Ok(mnemonic),
             false => Err(MnemonicError::InvalidPhrase(phrase.into())),
         }
@@ -105,14 +83,7 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
 
     /// Returns the phrase of the corresponding mnemonic.
     fn to_phrase(&self) -> Result<String, MnemonicError> {
-        let length: i32 = match self.entropy.len() {
-            16 => 12,
-            20 => 15,
-            24 => 18,
-            28 => 21,
-            32 => 24,
-            entropy_len => return Err(MnemonicError::InvalidEntropyLength(entropy_len)),
-        };
+        let size = EntropySize::from_entropy_length(self.entropy.len())?;
 
         // Compute the checksum by taking the first ENT / 32 bits of the SHA256 hash
         let mut sha256 = Sha256::new();
@@ -120,7 +91,7 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
 
         let hash = sha256.result();
         let hash_0 = BitVec::<Msb0, u8>::from_element(hash[0]);
-        let (checksum, _) = hash_0.split_at(length.div(3) as usize);
+        let (checksum, _) = hash_0.split_at(size.checksum_bits());
 
         // Convert the entropy bytes into bits and append the checksum
         let mut encoding = BitVec::<Msb0, u8>::from_vec(self.entropy.clone());
@@ -142,7 +113,7 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
             })
             .collect::<Vec<&str>>();
 
-        Ok(phrase.join(" "))
+        Ok(phrase.join(W::SEPARATOR))
     }
 
     /// Returns the private key of the corresponding mnemonic.
@@ -180,16 +151,69 @@ impl<N: EthereumNetwork, W: EthereumWordlist> MnemonicExtended for EthereumMnemo
 }
 
 impl<N: EthereumNetwork, W: EthereumWordlist> EthereumMnemonic<N, W> {
+    /// Returns the mnemonic for the given entropy.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        let _ = EntropySize::from_entropy_length(entropy.len())?;
+        Ok(Self {
+            entropy: entropy.to_vec(),
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        })
+    }
+
+    /// Returns a new mnemonic with the given entropy size.
+    pub fn new_with_size<R: Rng>(rng: &mut R, size: EntropySize) -> Self {
+        let entropy: [u8; 32] = rng.gen();
+        Self {
+            entropy: entropy[0..size.entropy_length()].to_vec(),
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        }
+    }
+
+    /// Returns the entropy of the corresponding mnemonic.
+    pub fn to_entropy(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+
+    /// Returns the entropy size of the corresponding mnemonic.
+    pub fn entropy_size(&self) -> EntropySize {
+        EntropySize::from_entropy_length(self.entropy.len()).expect("the entropy length is checked on construction")
+    }
+
+    /// Returns the number of bits of entropy of the corresponding mnemonic.
+    pub fn entropy_bits(&self) -> usize {
+        self.entropy_size().entropy_bits()
+    }
+
+    /// Returns the number of words in the phrase of the corresponding mnemonic.
+    pub fn word_count(&self) -> u8 {
+        self.entropy_size().word_count()
+    }
+
+    /// Returns `Ok` if every word of the given phrase is in the word list,
+    /// otherwise returns the position and text of the first unknown word.
+    pub fn validate_words(phrase: &str) -> Result<(), MnemonicError> {
+        let normalized = phrase.nfkd().collect::<String>();
+        for (position, word) in normalized.split(" ").enumerate() {
+            if W::get_index(word).is_err() {
+                return Err(MnemonicError::InvalidWordAtPosition(position, word.into()));
+            }
+        }
+        Ok(())
+    }
+
     /// Compares the given phrase against the phrase extracted from its entropy.
     pub fn verify_phrase(phrase: &str) -> bool {
         Self::from_phrase(phrase).is_ok()
     }
 
     /// Returns a seed using the given password and mnemonic.
-    fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
+    pub fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
         let mut seed = vec![0u8; PBKDF2_BYTES];
-        let salt = format!("mnemonic{}", password.unwrap_or(""));
-        pbkdf2::<Hmac<Sha512>>(&self.to_phrase()?.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        let phrase = self.to_phrase()?.nfkd().collect::<String>();
+        let salt = format!("mnemonic{}", password.unwrap_or("")).nfkd().collect::<String>();
+        pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
         Ok(seed)
     }
 }
@@ -483,6 +507,50 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_entropy() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, expected_seed, _)| {
+                let entropy = hex::decode(entropy_str).unwrap();
+                let mnemonic = EthereumMnemonic::<N, W>::from_entropy(&entropy).unwrap();
+                assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+                assert_eq!(entropy, mnemonic.to_entropy());
+                assert_eq!(
+                    entropy,
+                    EthereumMnemonic::<N, W>::from_phrase(phrase).unwrap().to_entropy()
+                );
+                test_to_seed::<N, W>(expected_seed, Some(PASSWORD), mnemonic);
+            });
+        }
+
+        #[test]
+        fn entropy_size() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let mnemonic = EthereumMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                assert_eq!(entropy_str.len() * 4, mnemonic.entropy_bits());
+                assert_eq!(phrase.split(' ').count(), mnemonic.word_count() as usize);
+            });
+
+            [(EntropySize::Entropy160, "address"), (EntropySize::Entropy224, "admit")]
+                .iter()
+                .for_each(|(size, last_word)| {
+                    let entropy = vec![0u8; size.entropy_length()];
+                    let mnemonic = EthereumMnemonic::<N, W>::from_entropy(&entropy).unwrap();
+                    let phrase = mnemonic.to_phrase().unwrap();
+                    let mut words = vec!["abandon"; size.word_count() as usize - 1];
+                    words.push(last_word);
+                    assert_eq!(words.join(" "), phrase);
+                    assert_eq!(*size, mnemonic.entropy_size());
+
+                    let mnemonic = EthereumMnemonic::<N, W>::from_phrase(&phrase).unwrap();
+                    assert_eq!(entropy, mnemonic.to_entropy());
+                    assert_eq!(size.entropy_bits(), mnemonic.entropy_bits());
+                });
+
+            let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+            let mnemonic = EthereumMnemonic::<N, W>::new_with_size(rng, EntropySize::Entropy192);
+            assert_eq!(18, mnemonic.word_count());
+        }
+
         #[test]
         fn to_seed_no_password() {
             let (entropy_str, _, _, _) = KEYPAIRS[0];
@@ -546,6 +614,32 @@ mod tests {
             let _mnemonic = EthereumMnemonic::<N, W>::from_phrase(INVALID_PHRASE_CHECKSUM).unwrap();
         }
 
+        #[test]
+        #[should_panic(expected = "InvalidEntropyLength(15)")]
+        fn from_entropy_invalid_length() {
+            let _mnemonic = EthereumMnemonic::<N, W>::from_entropy(&[0u8; 15]).unwrap();
+        }
+
+        #[test]
+        fn validate_words() {
+            assert!(EthereumMnemonic::<N, W>::validate_words(INVALID_PHRASE_CHECKSUM).is_ok());
+            assert_eq!(
+                "Invalid word at position 0: abandoz",
+                EthereumMnemonic::<N, W>::validate_words(INVALID_PHRASE_WORD)
+                    .unwrap_err()
+                    .to_string()
+            );
+
+            let phrase = "legal winner thank year wave sausage worth usefull legal winner thank yellow";
+            match EthereumMnemonic::<N, W>::validate_words(phrase) {
+                Err(MnemonicError::InvalidWordAtPosition(position, word)) => {
+                    assert_eq!(7, position);
+                    assert_eq!("usefull", word);
+                }
+                result => panic!("unexpected result: {:?}", result),
+            }
+        }
+
         #[test]
         fn verify_invalid_phrase() {
             assert!(!EthereumMnemonic::<N, W>::verify_phrase(INVALID_PHRASE_LENGTH));
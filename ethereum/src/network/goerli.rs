@@ -1,8 +1,8 @@
 use crate::network::EthereumNetwork;
 use wagyu_model::{ChildIndex, Network, NetworkError};
 
+use core::{fmt, str::FromStr};
 use serde::Serialize;
-use std::{fmt, str::FromStr};
 
 /// Represents an Ethereum test network (PoA).
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
@@ -22,9 +22,9 @@ impl FromStr for Goerli {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
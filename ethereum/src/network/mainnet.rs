@@ -1,8 +1,8 @@
 use crate::network::EthereumNetwork;
 use wagyu_model::{ChildIndex, Network, NetworkError};
 
+use core::{fmt, str::FromStr};
 use serde::Serialize;
-use std::{fmt, str::FromStr};
 
 /// Represents an Ethereum main network.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
@@ -10,6 +10,7 @@ pub struct Mainnet;
 
 impl Network for Mainnet {
     const NAME: &'static str = "mainnet";
+    const ALIASES: &'static [&'static str] = &["main", "homestead"];
 }
 
 impl EthereumNetwork for Mainnet {
@@ -22,9 +23,9 @@ impl FromStr for Mainnet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
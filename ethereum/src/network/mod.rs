@@ -22,3 +22,29 @@ pub trait EthereumNetwork: Network {
     const HD_PURPOSE: ChildIndex = ChildIndex::Hardened(44);
     const HD_COIN_TYPE: ChildIndex;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use wagyu_model::NetworkError;
+
+    fn test_from_str<N: EthereumNetwork + FromStr<Err = NetworkError>>(names: &[&str]) {
+        names.iter().for_each(|name| {
+            assert_eq!(N::NAME, N::from_str(name).unwrap().to_string());
+            assert_eq!(N::NAME, N::from_str(&name.to_uppercase()).unwrap().to_string());
+        });
+    }
+
+    #[test]
+    fn from_str() {
+        test_from_str::<Mainnet>(&["mainnet", "main", "homestead"]);
+        test_from_str::<Goerli>(&["goerli"]);
+        test_from_str::<Kovan>(&["kovan"]);
+        test_from_str::<Rinkeby>(&["rinkeby"]);
+        test_from_str::<Ropsten>(&["ropsten"]);
+
+        assert!(Mainnet::from_str("ropsten").is_err());
+        assert!(Goerli::from_str("test").is_err());
+    }
+}
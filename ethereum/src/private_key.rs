@@ -1,14 +1,20 @@
 use crate::address::EthereumAddress;
+use crate::eip712::{Eip712Error, TypedData};
 use crate::format::EthereumFormat;
+use crate::keystore::{KeystoreError, KeystoreJson, KeystoreKdf};
+use crate::message::{message_hash, MESSAGE_SIGNATURE_SIZE, MESSAGE_SIGNATURE_V};
 use crate::public_key::EthereumPublicKey;
-use wagyu_model::{Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    parse_hex_32, parse_secp256k1_secret_key, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey,
+};
 
 use core::{fmt, fmt::Display, str::FromStr};
-use rand::Rng;
+use rand::{CryptoRng, Rng};
 use secp256k1;
 
 /// Represents an Ethereum private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct EthereumPrivateKey(secp256k1::SecretKey);
 
 impl PrivateKey for EthereumPrivateKey {
@@ -18,8 +24,7 @@ impl PrivateKey for EthereumPrivateKey {
 
     /// Returns a randomly-generated Ethereum private key.
     fn new<R: Rng>(rng: &mut R) -> Result<Self, PrivateKeyError> {
-        let random: [u8; 32] = rng.gen();
-        Ok(Self(secp256k1::SecretKey::parse_slice(&random)?))
+        Ok(Self(secp256k1::SecretKey::random(rng)))
     }
 
     /// Returns the public key of the corresponding Ethereum private key.
@@ -34,6 +39,11 @@ impl PrivateKey for EthereumPrivateKey {
 }
 
 impl EthereumPrivateKey {
+    /// Returns a randomly-generated Ethereum private key from a cryptographically secure RNG.
+    pub fn new_random<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        Self(secp256k1::SecretKey::random(rng))
+    }
+
     /// Returns a private key given a secp256k1 secret key.
     pub fn from_secp256k1_secret_key(secret_key: &secp256k1::SecretKey) -> Self {
         Self(secret_key.clone())
@@ -43,6 +53,78 @@ impl EthereumPrivateKey {
     pub fn to_secp256k1_secret_key(&self) -> secp256k1::SecretKey {
         self.0.clone()
     }
+
+    /// Returns a private key given the 64 character hex encoding of its secret key scalar,
+    /// optionally prefixed with "0x", which must be in the range [1, n - 1].
+    pub fn from_hex(hex: &str) -> Result<Self, PrivateKeyError> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        Ok(Self(parse_secp256k1_secret_key(&parse_hex_32(hex)?)?))
+    }
+
+    /// Returns the 64 character hex encoding of the secret key scalar of the private key,
+    /// without a "0x" prefix.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0.serialize())
+    }
+
+    /// Returns the ECDSA signature of the given 32 byte message digest. The nonce is derived
+    /// deterministically from the secret key and the digest (RFC6979), so no RNG is used.
+    pub fn sign(&self, digest: &[u8]) -> Result<secp256k1::Signature, PrivateKeyError> {
+        Ok(self.sign_recoverable(digest)?.0)
+    }
+
+    /// Returns the ECDSA signature of the given 32 byte message digest with its recovery id,
+    /// using an RFC6979 deterministic nonce.
+    pub fn sign_recoverable(
+        &self,
+        digest: &[u8],
+    ) -> Result<(secp256k1::Signature, secp256k1::RecoveryId), PrivateKeyError> {
+        Ok(secp256k1::sign(&secp256k1::Message::parse_slice(digest)?, &self.0))
+    }
+
+    /// Returns the 65 byte r || s || v signature of the given message, compatible with
+    /// `personal_sign` (EIP-191). The v byte is 27 + recovery id.
+    pub fn sign_message(&self, message: &[u8]) -> [u8; MESSAGE_SIGNATURE_SIZE] {
+        // The message hash is 32 bytes, so signing does not fail.
+        let (signature, recovery_id) = self.sign_recoverable(&message_hash(message)).unwrap();
+
+        let mut signature_bytes = [0u8; MESSAGE_SIGNATURE_SIZE];
+        signature_bytes[..64].copy_from_slice(&signature.serialize());
+        signature_bytes[64] = MESSAGE_SIGNATURE_V + recovery_id.serialize();
+        signature_bytes
+    }
+
+    /// Returns the 65 byte r || s || v signature of the given typed data, compatible with
+    /// `eth_signTypedData_v4` (EIP-712). The v byte is 27 + recovery id.
+    pub fn sign_typed_data(&self, typed_data: &TypedData) -> Result<[u8; MESSAGE_SIGNATURE_SIZE], Eip712Error> {
+        // The signing hash is 32 bytes, so signing does not fail.
+        let (signature, recovery_id) = self.sign_recoverable(&typed_data.signing_hash()?).unwrap();
+
+        let mut signature_bytes = [0u8; MESSAGE_SIGNATURE_SIZE];
+        signature_bytes[..64].copy_from_slice(&signature.serialize());
+        signature_bytes[64] = MESSAGE_SIGNATURE_V + recovery_id.serialize();
+        Ok(signature_bytes)
+    }
+
+    /// Returns a version 3 keystore of the private key, encrypted with the given password
+    /// using scrypt with a randomly-generated salt.
+    pub fn to_keystore<R: Rng + CryptoRng>(&self, password: &str, rng: &mut R) -> Result<KeystoreJson, KeystoreError> {
+        let salt: [u8; 32] = rng.gen();
+        KeystoreJson::new(self, password, KeystoreKdf::scrypt(&salt), rng)
+    }
+
+    /// Returns the private key decrypted from the given version 3 keystore JSON and password.
+    pub fn from_keystore(keystore: &str, password: &str) -> Result<Self, KeystoreError> {
+        KeystoreJson::from_str(keystore)?.decrypt(password)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for EthereumPrivateKey {
+    /// Returns a randomly-generated Ethereum private key using the operating system RNG.
+    fn default() -> Self {
+        Self::new_random(&mut rand::rngs::OsRng)
+    }
 }
 
 impl FromStr for EthereumPrivateKey {
@@ -66,6 +148,13 @@ impl Display for EthereumPrivateKey {
     }
 }
 
+impl fmt::Debug for EthereumPrivateKey {
+    /// Formats the private key with the secret key redacted, so that logging it does not leak the key.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "EthereumPrivateKey(<redacted>)")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -223,4 +312,157 @@ mod tests {
         let private_key = "8279d7c0ae2c3266b557845d50ede43e22a7e60408b7c90ee279b8848dbac7718279d7c0ae2c3266b557845d50ede43e22a7e60408b7c90ee279b8848dbac771";
         assert!(EthereumPrivateKey::from_str(private_key).is_err());
     }
+
+    mod from_hex {
+        use super::*;
+
+        const SECRET_KEY: &str = "8279d7c0ae2c3266b557845d50ede43e22a7e60408b7c90ee279b8848dbac771";
+        const CURVE_ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        const CURVE_ORDER_MINUS_ONE: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+
+        #[test]
+        fn round_trip() {
+            let private_key = EthereumPrivateKey::from_hex(SECRET_KEY).unwrap();
+            assert_eq!(EthereumPrivateKey::from_str(SECRET_KEY).unwrap(), private_key);
+            assert_eq!(SECRET_KEY, private_key.to_hex());
+            assert_eq!(
+                private_key,
+                EthereumPrivateKey::from_hex(&format!("0x{}", SECRET_KEY)).unwrap()
+            );
+        }
+
+        #[test]
+        fn curve_order() {
+            let private_key = EthereumPrivateKey::from_hex(CURVE_ORDER_MINUS_ONE).unwrap();
+            assert_eq!(CURVE_ORDER_MINUS_ONE, private_key.to_hex());
+            assert!(matches!(
+                EthereumPrivateKey::from_hex(CURVE_ORDER),
+                Err(PrivateKeyError::ScalarOutOfRange)
+            ));
+            assert!(matches!(
+                EthereumPrivateKey::from_hex(&format!("0x{}", "00".repeat(32))),
+                Err(PrivateKeyError::ZeroScalar)
+            ));
+        }
+
+        #[test]
+        fn invalid_hex() {
+            assert!(matches!(
+                EthereumPrivateKey::from_hex(&SECRET_KEY[..63]),
+                Err(PrivateKeyError::InvalidCharacterLength(63))
+            ));
+            assert!(EthereumPrivateKey::from_hex(&format!("0x0x{}", &SECRET_KEY[..62])).is_err());
+            assert!(EthereumPrivateKey::from_hex(&format!("{}g1", &SECRET_KEY[..62])).is_err());
+        }
+    }
+
+    mod new_random {
+        use super::*;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        #[test]
+        fn new_random() {
+            let private_key = EthereumPrivateKey::new_random(&mut StdRng::seed_from_u64(1231275789u64));
+            let expected_private_key = EthereumPrivateKey::new_random(&mut StdRng::seed_from_u64(1231275789u64));
+            assert_eq!(expected_private_key, private_key);
+        }
+
+        #[test]
+        fn default() {
+            assert_ne!(EthereumPrivateKey::default(), EthereumPrivateKey::default());
+        }
+    }
+
+    mod keystore {
+        use super::*;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        #[test]
+        fn to_keystore() {
+            let private_key = EthereumPrivateKey::new_random(&mut StdRng::seed_from_u64(1231275789u64));
+            let keystore = private_key
+                .to_keystore("password", &mut StdRng::seed_from_u64(1231275789u64))
+                .unwrap();
+            assert_eq!(
+                private_key,
+                EthereumPrivateKey::from_keystore(&keystore.to_string(), "password").unwrap()
+            );
+            assert!(EthereumPrivateKey::from_keystore(&keystore.to_string(), "passw0rd").is_err());
+        }
+    }
+
+    mod sign {
+        use super::*;
+        use wagyu_model::crypto::keccak256;
+
+        const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+        #[test]
+        fn sign() {
+            // RFC6979 (HMAC-SHA256) deterministic signature of the SHA-256 digest of "Satoshi Nakamoto"
+            let private_key =
+                EthereumPrivateKey::from_str("0000000000000000000000000000000000000000000000000000000000000001")
+                    .unwrap();
+            let digest = hex::decode("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e").unwrap();
+            assert_eq!(
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d82442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+                hex::encode(&private_key.sign(&digest).unwrap().serialize()[..])
+            );
+        }
+
+        #[test]
+        fn sign_recoverable() {
+            let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+            let digest = keccak256(b"Some data");
+
+            let (signature, recovery_id) = private_key.sign_recoverable(&digest).unwrap();
+            assert_eq!(
+                (signature.clone(), recovery_id),
+                private_key.sign_recoverable(&digest).unwrap()
+            );
+            assert_eq!(signature, private_key.sign(&digest).unwrap());
+
+            let message = secp256k1::Message::parse(&digest);
+            let public_key = secp256k1::recover(&message, &signature, &recovery_id).unwrap();
+            assert_eq!(private_key.to_public_key().to_secp256k1_public_key(), public_key);
+            assert!(private_key.sign(&digest[1..]).is_err());
+        }
+    }
+
+    mod sign_message {
+        use super::*;
+        use crate::message::recover_address;
+
+        #[test]
+        fn sign_message() {
+            let private_key =
+                EthereumPrivateKey::from_str("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318")
+                    .unwrap();
+            let signature = private_key.sign_message(b"Some data");
+            assert_eq!(
+                "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c",
+                hex::encode(&signature[..])
+            );
+            assert_eq!(
+                private_key.to_address(&EthereumFormat::Standard).unwrap(),
+                recover_address(b"Some data", &signature).unwrap()
+            );
+        }
+    }
+
+    mod debug {
+        use super::*;
+
+        const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+        #[test]
+        fn redacted() {
+            let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+            let debug = format!("{:?}", private_key);
+            assert_eq!("EthereumPrivateKey(<redacted>)", debug);
+            assert!(!debug.contains(PRIVATE_KEY));
+            assert!(!format!("{:?}", Some(private_key.clone())).contains(PRIVATE_KEY));
+            assert_eq!(PRIVATE_KEY, private_key.to_string());
+        }
+    }
 }
@@ -1,6 +1,8 @@
 use crate::address::EthereumAddress;
 use crate::format::EthereumFormat;
 use crate::private_key::EthereumPrivateKey;
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
 use wagyu_model::{Address, AddressError, PublicKey, PublicKeyError};
 
 use core::{fmt, fmt::Display, str::FromStr};
@@ -38,14 +40,37 @@ impl EthereumPublicKey {
     pub fn to_secp256k1_public_key(&self) -> secp256k1::PublicKey {
         self.0.clone()
     }
+
+    /// Returns the 64-byte uncompressed public key, x || y, without the 0x04 prefix.
+    pub fn to_uncompressed_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&self.0.serialize()[1..]);
+        bytes
+    }
+
+    /// Returns the keccak-256 hash of the uncompressed public key, of which the last 20 bytes are the address.
+    pub fn to_keccak256(&self) -> [u8; 32] {
+        keccak256(&self.to_uncompressed_bytes())
+    }
 }
 
 impl FromStr for EthereumPublicKey {
     type Err = PublicKeyError;
 
+    /// Returns the public key of the given uncompressed public key, either 128 hex characters or 130 hex characters with a 04 prefix.
     fn from_str(public_key: &str) -> Result<Self, Self::Err> {
+        let public_key = match public_key.len() {
+            128 => format!("04{}", public_key),
+            130 if public_key.starts_with("04") => public_key.to_string(),
+            130 => {
+                return Err(PublicKeyError::InvalidPrefix(
+                    public_key.get(0..2).unwrap_or(public_key).to_string(),
+                ))
+            }
+            length => return Err(PublicKeyError::InvalidCharacterLength(length)),
+        };
         Ok(Self(secp256k1::PublicKey::parse_slice(
-            hex::decode(format!("04{}", public_key).as_str())?.as_slice(),
+            hex::decode(public_key)?.as_slice(),
             None,
         )?))
     }
@@ -150,6 +175,52 @@ mod tests {
         }
     }
 
+    mod uncompressed {
+        use super::*;
+        use wagyu_model::PrivateKey;
+
+        const PRIVATE_KEY: &str = "0000000000000000000000000000000000000000000000000000000000000001";
+        const PUBLIC_KEY: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+        const ADDRESS: &str = "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf";
+
+        #[test]
+        fn to_address() {
+            let public_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap().to_public_key();
+            assert_eq!(PUBLIC_KEY, hex::encode(&public_key.to_uncompressed_bytes()[..]));
+            assert_eq!(
+                ADDRESS.to_lowercase(),
+                format!("0x{}", hex::encode(&public_key.to_keccak256()[12..]))
+            );
+            assert_eq!(
+                ADDRESS,
+                public_key.to_address(&EthereumFormat::Standard).unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn from_str() {
+            let public_key = EthereumPublicKey::from_str(PUBLIC_KEY).unwrap();
+            assert_eq!(
+                public_key,
+                EthereumPublicKey::from_str(&format!("04{}", PUBLIC_KEY)).unwrap()
+            );
+            assert_eq!(PUBLIC_KEY, public_key.to_string());
+
+            match EthereumPublicKey::from_str(&format!("02{}", PUBLIC_KEY)) {
+                Err(PublicKeyError::InvalidPrefix(prefix)) => assert_eq!("02", prefix),
+                result => panic!("unexpected result: {:?}", result),
+            };
+            match EthereumPublicKey::from_str(&format!("02{}", &PUBLIC_KEY[..64])) {
+                Err(PublicKeyError::InvalidCharacterLength(length)) => assert_eq!(66, length),
+                result => panic!("unexpected result: {:?}", result),
+            };
+            // A non-ASCII character across the prefix boundary
+            let non_ascii = format!("0\u{e9}{}", &PUBLIC_KEY[1..]);
+            assert_eq!(130, non_ascii.len());
+            assert!(EthereumPublicKey::from_str(&non_ascii).is_err());
+        }
+    }
+
     #[test]
     fn test_checksum_address_invalid() {
         // Invalid public key length
@@ -0,0 +1,583 @@
+//!
+//! RLP
+//!
+//! This module contains the Recursive Length Prefix serialization used by Ethereum transactions.
+//! https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/
+//!
+//! The decoder only accepts canonical encodings, so every successfully decoded item
+//! re-encodes to the exact input bytes.
+//!
+
+use wagyu_model::no_std::*;
+use wagyu_model::TransactionError;
+
+use core::mem::size_of;
+use ethereum_types::U256;
+
+/// The offset of the prefix of a byte string.
+const RLP_STRING_OFFSET: u8 = 0x80;
+
+/// The offset of the prefix of a list.
+const RLP_LIST_OFFSET: u8 = 0xc0;
+
+/// The largest payload length encoded in the prefix itself.
+const RLP_SHORT_LENGTH: usize = 55;
+
+/// The maximum nesting depth of lists accepted by the decoder.
+pub const RLP_MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum RlpError {
+    #[fail(display = "expected an rlp byte string")]
+    ExpectedBytes,
+
+    #[fail(display = "expected an rlp list")]
+    ExpectedList,
+
+    #[fail(display = "rlp list index out of bounds: {}", _0)]
+    IndexOutOfBounds(usize),
+
+    #[fail(display = "rlp integer does not fit in {} bytes", _0)]
+    IntegerOverflow(usize),
+
+    #[fail(display = "rlp integer has leading zero bytes")]
+    LeadingZeros,
+
+    #[fail(display = "rlp length prefix overflows")]
+    LengthOverflow,
+
+    #[fail(display = "rlp lists are nested deeper than {}", _0)]
+    MaxDepthExceeded(usize),
+
+    #[fail(display = "non-canonical rlp length prefix")]
+    NonCanonicalSize,
+
+    #[fail(display = "rlp is followed by {} trailing bytes", _0)]
+    TrailingBytes(usize),
+
+    #[fail(display = "truncated rlp: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    Truncated(usize, usize),
+}
+
+impl From<RlpError> for TransactionError {
+    fn from(error: RlpError) -> Self {
+        TransactionError::Crate("rlp", format!("{:?}", error))
+    }
+}
+
+/// The interface for a value that can be appended to an RLP stream.
+pub trait RlpEncodable {
+    /// Appends the encoding of the value to the given stream.
+    fn rlp_append(&self, stream: &mut RlpStream);
+}
+
+impl RlpEncodable for [u8] {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self);
+    }
+}
+
+impl RlpEncodable for Vec<u8> {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self);
+    }
+}
+
+impl RlpEncodable for str {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(self.as_bytes());
+    }
+}
+
+impl RlpEncodable for u8 {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(trim_leading_zeros(&self.to_be_bytes()));
+    }
+}
+
+impl RlpEncodable for u32 {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(trim_leading_zeros(&self.to_be_bytes()));
+    }
+}
+
+impl RlpEncodable for u64 {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        stream.append_bytes(trim_leading_zeros(&self.to_be_bytes()));
+    }
+}
+
+impl RlpEncodable for U256 {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        stream.append_bytes(trim_leading_zeros(&bytes));
+    }
+}
+
+impl RlpEncodable for RlpItem {
+    fn rlp_append(&self, stream: &mut RlpStream) {
+        match self {
+            RlpItem::Bytes(bytes) => {
+                stream.append_bytes(bytes);
+            }
+            RlpItem::List(items) => {
+                stream.begin_list(items.len());
+                items.iter().for_each(|item| item.rlp_append(stream));
+            }
+        }
+    }
+}
+
+/// Represents an RLP encoder
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RlpStream {
+    /// The encoded bytes
+    buffer: Vec<u8>,
+    /// The start offset and the number of missing items of each unfinished list
+    lists: Vec<(usize, usize)>,
+}
+
+impl RlpStream {
+    /// Returns an empty RLP stream.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns an RLP stream beginning with a list of the given number of items.
+    pub fn new_list(count: usize) -> Self {
+        let mut stream = Self::new();
+        stream.begin_list(count);
+        stream
+    }
+
+    /// Appends the given value to the stream.
+    pub fn append<E: RlpEncodable + ?Sized>(&mut self, value: &E) -> &mut Self {
+        value.rlp_append(self);
+        self
+    }
+
+    /// Appends the given bytes to the stream as a byte string.
+    pub fn append_bytes(&mut self, bytes: &[u8]) -> &mut Self {
+        match bytes {
+            [byte] if *byte < RLP_STRING_OFFSET => self.buffer.push(*byte),
+            _ => {
+                self.buffer.extend(encode_header(RLP_STRING_OFFSET, bytes.len()));
+                self.buffer.extend_from_slice(bytes);
+            }
+        }
+        self.finish_item();
+        self
+    }
+
+    /// Appends an empty byte string to the stream, which also encodes zero and `None`.
+    pub fn append_empty_data(&mut self) -> &mut Self {
+        self.append_bytes(&[])
+    }
+
+    /// Begins a list of the given number of items. The list is complete once
+    /// the given number of items have been appended.
+    pub fn begin_list(&mut self, count: usize) -> &mut Self {
+        match count {
+            0 => {
+                self.buffer.push(RLP_LIST_OFFSET);
+                self.finish_item();
+            }
+            _ => self.lists.push((self.buffer.len(), count)),
+        }
+        self
+    }
+
+    /// Returns `true` if every list in the stream is complete.
+    pub fn is_finished(&self) -> bool {
+        self.lists.is_empty()
+    }
+
+    /// Returns the encoded bytes of the stream.
+    ///
+    /// Panics if a list in the stream is missing items.
+    pub fn out(self) -> Vec<u8> {
+        assert!(self.is_finished(), "rlp stream has an unfinished list");
+        self.buffer
+    }
+
+    /// Records an appended item, prefixing every list completed by it with its header.
+    fn finish_item(&mut self) {
+        while let Some((start, remaining)) = self.lists.last_mut() {
+            *remaining -= 1;
+            if *remaining > 0 {
+                break;
+            }
+
+            let start = *start;
+            self.lists.pop();
+            let header = encode_header(RLP_LIST_OFFSET, self.buffer.len() - start);
+            self.buffer.splice(start..start, header);
+        }
+    }
+}
+
+/// Represents a decoded RLP item
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RlpItem {
+    Bytes(Vec<u8>),
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    /// Returns the item encoded by the given bytes, which must contain exactly one canonical item.
+    pub fn decode(data: &[u8]) -> Result<Self, RlpError> {
+        let (item, length) = Self::decode_item(data, 0)?;
+        match length == data.len() {
+            true => Ok(item),
+            false => Err(RlpError::TrailingBytes(data.len() - length)),
+        }
+    }
+
+    /// Returns the RLP encoding of the item.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new();
+        stream.append(self);
+        stream.out()
+    }
+
+    /// Returns the bytes of the item, if it is a byte string.
+    pub fn as_bytes(&self) -> Result<&[u8], RlpError> {
+        match self {
+            RlpItem::Bytes(bytes) => Ok(bytes),
+            RlpItem::List(_) => Err(RlpError::ExpectedBytes),
+        }
+    }
+
+    /// Returns the items of the item, if it is a list.
+    pub fn as_list(&self) -> Result<&[RlpItem], RlpError> {
+        match self {
+            RlpItem::Bytes(_) => Err(RlpError::ExpectedList),
+            RlpItem::List(items) => Ok(items),
+        }
+    }
+
+    /// Returns the item at the given index, if the item is a list.
+    pub fn at(&self, index: usize) -> Result<&RlpItem, RlpError> {
+        self.as_list()?.get(index).ok_or(RlpError::IndexOutOfBounds(index))
+    }
+
+    /// Returns the canonical big-endian integer of the item as a u64.
+    pub fn as_u64(&self) -> Result<u64, RlpError> {
+        let bytes = self.as_integer_bytes(size_of::<u64>())?;
+        Ok(bytes.iter().fold(0u64, |value, byte| (value << 8) | u64::from(*byte)))
+    }
+
+    /// Returns the canonical big-endian integer of the item as a U256.
+    pub fn as_u256(&self) -> Result<U256, RlpError> {
+        Ok(U256::from_big_endian(self.as_integer_bytes(32)?))
+    }
+
+    /// Returns the bytes of the item, if they are a canonical integer of at most the given size.
+    fn as_integer_bytes(&self, size: usize) -> Result<&[u8], RlpError> {
+        let bytes = self.as_bytes()?;
+        if bytes.len() > size {
+            return Err(RlpError::IntegerOverflow(size));
+        }
+        if bytes.first() == Some(&0) {
+            return Err(RlpError::LeadingZeros);
+        }
+        Ok(bytes)
+    }
+
+    /// Returns the first item of the given bytes and the length of its encoding.
+    fn decode_item(data: &[u8], depth: usize) -> Result<(Self, usize), RlpError> {
+        let prefix = *data.first().ok_or(RlpError::Truncated(1, 0))?;
+        match prefix {
+            0x00..=0x7f => Ok((RlpItem::Bytes(vec![prefix]), 1)),
+            0x80..=0xbf => {
+                let (header_length, length) = decode_header(data, RLP_STRING_OFFSET)?;
+                let payload = slice(data, header_length, length)?;
+                if let [byte] = payload {
+                    if *byte < RLP_STRING_OFFSET {
+                        return Err(RlpError::NonCanonicalSize);
+                    }
+                }
+                Ok((RlpItem::Bytes(payload.to_vec()), header_length + length))
+            }
+            0xc0..=0xff => {
+                if depth >= RLP_MAX_DEPTH {
+                    return Err(RlpError::MaxDepthExceeded(RLP_MAX_DEPTH));
+                }
+
+                let (header_length, length) = decode_header(data, RLP_LIST_OFFSET)?;
+                let payload = slice(data, header_length, length)?;
+
+                let mut items = vec![];
+                let mut position = 0;
+                while position < payload.len() {
+                    let (item, item_length) = Self::decode_item(&payload[position..], depth + 1)?;
+                    items.push(item);
+                    position += item_length;
+                }
+                Ok((RlpItem::List(items), header_length + length))
+            }
+        }
+    }
+}
+
+/// Returns the header of a byte string or list (given its offset) of the given payload length.
+fn encode_header(offset: u8, length: usize) -> Vec<u8> {
+    match length {
+        0..=RLP_SHORT_LENGTH => vec![offset + length as u8],
+        _ => {
+            let length_bytes = length.to_be_bytes();
+            let length_bytes = trim_leading_zeros(&length_bytes);
+            let mut header = vec![offset + RLP_SHORT_LENGTH as u8 + length_bytes.len() as u8];
+            header.extend_from_slice(length_bytes);
+            header
+        }
+    }
+}
+
+/// Returns the header length and payload length of the byte string or list (given its offset)
+/// at the start of the given bytes.
+fn decode_header(data: &[u8], offset: u8) -> Result<(usize, usize), RlpError> {
+    let short_length = (data[0] - offset) as usize;
+    if short_length <= RLP_SHORT_LENGTH {
+        return Ok((1, short_length));
+    }
+
+    let length_of_length = short_length - RLP_SHORT_LENGTH;
+    let length_bytes = slice(data, 1, length_of_length)?;
+    if length_bytes[0] == 0 {
+        return Err(RlpError::NonCanonicalSize);
+    }
+    if length_of_length > size_of::<usize>() {
+        return Err(RlpError::LengthOverflow);
+    }
+
+    let length = length_bytes
+        .iter()
+        .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+    if length <= RLP_SHORT_LENGTH {
+        return Err(RlpError::NonCanonicalSize);
+    }
+    Ok((1 + length_of_length, length))
+}
+
+/// Returns the given number of bytes at the given offset, or an error if the bytes are truncated.
+fn slice(data: &[u8], offset: usize, length: usize) -> Result<&[u8], RlpError> {
+    let end = offset.checked_add(length).ok_or(RlpError::LengthOverflow)?;
+    match data.get(offset..end) {
+        Some(bytes) => Ok(bytes),
+        None => Err(RlpError::Truncated(end, data.len())),
+    }
+}
+
+/// Returns the given big-endian integer without leading zero bytes.
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    &bytes[leading_zeros..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const LOREM: &str = "Lorem ipsum dolor sit amet, consectetur adipisicing elit";
+
+    fn bytes(value: &str) -> RlpItem {
+        RlpItem::Bytes(value.as_bytes().to_vec())
+    }
+
+    fn test_round_trip(expected_encoding: &str, item: &RlpItem) {
+        assert_eq!(expected_encoding, hex::encode(item.encode()));
+        assert_eq!(
+            *item,
+            RlpItem::decode(&hex::decode(expected_encoding).unwrap()).unwrap()
+        );
+    }
+
+    fn test_invalid(encoding: &str, expected_error: RlpError) {
+        assert_eq!(
+            expected_error,
+            RlpItem::decode(&hex::decode(encoding).unwrap()).unwrap_err()
+        );
+    }
+
+    mod canonical {
+        use super::*;
+
+        #[test]
+        fn strings() {
+            test_round_trip("83646f67", &bytes("dog"));
+            test_round_trip("80", &bytes(""));
+            test_round_trip("00", &RlpItem::Bytes(vec![0x00]));
+            test_round_trip("0f", &RlpItem::Bytes(vec![0x0f]));
+            test_round_trip("8180", &RlpItem::Bytes(vec![0x80]));
+        }
+
+        #[test]
+        fn lists() {
+            test_round_trip("c0", &RlpItem::List(vec![]));
+            test_round_trip("c88363617483646f67", &RlpItem::List(vec![bytes("cat"), bytes("dog")]));
+
+            // The set theoretical representation of three, [ [], [[]], [ [], [[]] ] ]
+            let empty = RlpItem::List(vec![]);
+            let one = RlpItem::List(vec![empty.clone()]);
+            let two = RlpItem::List(vec![empty.clone(), one.clone()]);
+            test_round_trip("c7c0c1c0c3c0c1c0", &RlpItem::List(vec![empty, one, two]));
+        }
+
+        #[test]
+        fn length_boundaries() {
+            let string_55 = &LOREM[..55];
+            test_round_trip(&format!("b7{}", hex::encode(string_55)), &bytes(string_55));
+            test_round_trip(&format!("b838{}", hex::encode(LOREM)), &bytes(LOREM));
+
+            let list_55 = RlpItem::List(vec![bytes(&LOREM[..54])]);
+            test_round_trip(&format!("f7b6{}", hex::encode(&LOREM[..54])), &list_55);
+            let list_56 = RlpItem::List(vec![bytes(&LOREM[..55])]);
+            test_round_trip(&format!("f838b7{}", hex::encode(&LOREM[..55])), &list_56);
+
+            let string_1024 = "a".repeat(1024);
+            test_round_trip(&format!("b90400{}", hex::encode(&string_1024)), &bytes(&string_1024));
+        }
+
+        #[test]
+        fn integers() {
+            let mut stream = RlpStream::new_list(5);
+            stream
+                .append(&0u8)
+                .append(&15u32)
+                .append(&1024u64)
+                .append(&U256::zero());
+            stream.append(&U256::from_dec_str("1000000000000000000").unwrap());
+            let encoded = stream.out();
+            assert_eq!("cf800f82040080880de0b6b3a7640000", hex::encode(&encoded));
+
+            let item = RlpItem::decode(&encoded).unwrap();
+            assert_eq!(0, item.at(0).unwrap().as_u64().unwrap());
+            assert_eq!(15, item.at(1).unwrap().as_u64().unwrap());
+            assert_eq!(1024, item.at(2).unwrap().as_u64().unwrap());
+            assert_eq!(U256::zero(), item.at(3).unwrap().as_u256().unwrap());
+            assert_eq!(
+                U256::from_dec_str("1000000000000000000").unwrap(),
+                item.at(4).unwrap().as_u256().unwrap()
+            );
+        }
+
+        #[test]
+        fn nested_stream() {
+            let mut stream = RlpStream::new_list(3);
+            stream.append("cat");
+            stream.begin_list(2).append("dog").begin_list(0);
+            stream.append_empty_data();
+            assert_eq!(
+                RlpItem::List(vec![
+                    bytes("cat"),
+                    RlpItem::List(vec![bytes("dog"), RlpItem::List(vec![])]),
+                    bytes("")
+                ])
+                .encode(),
+                stream.out()
+            );
+        }
+    }
+
+    mod invalid {
+        use super::*;
+
+        #[test]
+        fn truncated() {
+            test_invalid("", RlpError::Truncated(1, 0));
+            test_invalid("83646f", RlpError::Truncated(4, 3));
+            test_invalid("c88363617483646f", RlpError::Truncated(9, 8));
+            test_invalid("b9", RlpError::Truncated(3, 1));
+        }
+
+        #[test]
+        fn non_canonical() {
+            test_invalid("8100", RlpError::NonCanonicalSize);
+            test_invalid("817f", RlpError::NonCanonicalSize);
+            test_invalid("b803646f67", RlpError::NonCanonicalSize);
+            test_invalid("b9003800", RlpError::NonCanonicalSize);
+            test_invalid("f803c0c0c0", RlpError::NonCanonicalSize);
+        }
+
+        #[test]
+        fn oversized_length() {
+            test_invalid("bfffffffffffffffff", RlpError::LengthOverflow);
+            test_invalid("ffffffffffffffffff00", RlpError::LengthOverflow);
+            test_invalid("b9ffff", RlpError::Truncated(65538, 3));
+        }
+
+        #[test]
+        fn trailing_bytes() {
+            test_invalid("83646f6700", RlpError::TrailingBytes(1));
+        }
+
+        #[test]
+        fn max_depth() {
+            let mut item = RlpItem::List(vec![]);
+            for _ in 1..RLP_MAX_DEPTH {
+                item = RlpItem::List(vec![item]);
+            }
+            assert_eq!(item, RlpItem::decode(&item.encode()).unwrap());
+
+            let item = RlpItem::List(vec![item]);
+            assert_eq!(
+                RlpError::MaxDepthExceeded(RLP_MAX_DEPTH),
+                RlpItem::decode(&item.encode()).unwrap_err()
+            );
+        }
+
+        #[test]
+        fn integers() {
+            let leading_zeros = RlpItem::decode(&hex::decode("820001").unwrap()).unwrap();
+            assert_eq!(RlpError::LeadingZeros, leading_zeros.as_u64().unwrap_err());
+
+            let oversized = RlpItem::Bytes(vec![1u8; 9]);
+            assert_eq!(RlpError::IntegerOverflow(8), oversized.as_u64().unwrap_err());
+            assert_eq!(RlpError::ExpectedBytes, RlpItem::List(vec![]).as_u256().unwrap_err());
+        }
+    }
+
+    mod fuzz {
+        use super::*;
+
+        fn test_decode(data: &[u8]) {
+            if let Ok(item) = RlpItem::decode(data) {
+                assert_eq!(data, &item.encode()[..]);
+            }
+        }
+
+        #[test]
+        fn random_bytes() {
+            let rng = &mut StdRng::seed_from_u64(1231275789u64);
+            for _ in 0..100000 {
+                let length = rng.gen_range(0, 64);
+                let data: Vec<u8> = (0..length).map(|_| rng.gen()).collect();
+                test_decode(&data);
+            }
+        }
+
+        #[test]
+        fn mutated_encodings() {
+            let rng = &mut StdRng::seed_from_u64(1231275789u64);
+            let item = RlpItem::List(vec![
+                bytes("cat"),
+                RlpItem::List(vec![bytes(LOREM), RlpItem::List(vec![])]),
+                RlpItem::Bytes(vec![0x7f]),
+            ]);
+            let encoding = item.encode();
+
+            for _ in 0..100000 {
+                let mut data = encoding.clone();
+                for _ in 0..rng.gen_range(1, 4) {
+                    let index = rng.gen_range(0, data.len());
+                    data[index] = rng.gen();
+                }
+                data.truncate(rng.gen_range(0, data.len() + 1));
+                test_decode(&data);
+            }
+        }
+    }
+}
@@ -4,43 +4,103 @@ use crate::format::EthereumFormat;
 use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
+use crate::rlp::{RlpItem, RlpStream};
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
 use wagyu_model::{PrivateKey, PublicKey, Transaction, TransactionError, TransactionId};
 
 use core::{fmt, marker::PhantomData, str::FromStr};
 use ethereum_types::U256;
-use rlp::{decode_list, RlpStream};
 use secp256k1;
-use tiny_keccak::keccak256;
 
+/// Returns the minimal big-endian encoding of the given value, as used for RLP integers.
 pub fn to_bytes(value: u32) -> Result<Vec<u8>, TransactionError> {
-    match value {
-        // bounded by u8::max_value()
-        0..=255 => Ok(vec![value as u8]),
-        // bounded by u16::max_value()
-        256..=65535 => Ok((value as u16).to_le_bytes().to_vec()),
-        // bounded by u32::max_value()
-        _ => Ok(value.to_le_bytes().to_vec()),
-    }
+    let bytes = value.to_be_bytes();
+    let leading_zeros = bytes.iter().take(3).take_while(|byte| **byte == 0).count();
+    Ok(bytes[leading_zeros..].to_vec())
 }
 
+/// Returns the value of the given big-endian encoding, as used for RLP integers.
 pub fn from_bytes(value: &Vec<u8>) -> Result<u32, TransactionError> {
     match value.len() {
-        0 => Ok(0u32),
-        1 => Ok(u32::from_le_bytes([value[0], 0, 0, 0])),
-        2 => Ok(u32::from_le_bytes([value[0], value[1], 0, 0])),
-        3 => Ok(u32::from_le_bytes([value[0], value[1], value[2], 0])),
-        4 => Ok(u32::from_le_bytes([value[0], value[1], value[2], value[3]])),
+        0..=4 => Ok(value.iter().fold(0u32, |acc, byte| (acc << 8) | *byte as u32)),
         _ => Err(TransactionError::Message(
             "invalid byte length for u32 value".to_string(),
         )),
     }
 }
 
+/// Returns the given big-endian integer without leading zero bytes, as used for RLP integers.
+pub(crate) fn trim_leading_zeros(value: &[u8]) -> Vec<u8> {
+    value.iter().skip_while(|byte| **byte == 0).cloned().collect()
+}
+
+/// Returns the given big-endian integer left-padded with zero bytes to 32 bytes.
+pub(crate) fn pad_to_32(value: &[u8]) -> Result<[u8; 32], TransactionError> {
+    if value.len() > 32 {
+        return Err(TransactionError::Message(
+            "invalid byte length for signature value".to_string(),
+        ));
+    }
+    let mut padded = [0u8; 32];
+    padded[32 - value.len()..].copy_from_slice(value);
+    Ok(padded)
+}
+
+/// Appends the given receiver to the RLP stream, where `None` encodes a contract creation.
+pub(crate) fn encode_receiver(
+    transaction_rlp: &mut RlpStream,
+    receiver: &Option<EthereumAddress>,
+) -> Result<(), TransactionError> {
+    match receiver {
+        Some(receiver) => transaction_rlp.append(&hex::decode(&receiver.to_string()[2..])?),
+        None => transaction_rlp.append_empty_data(),
+    };
+    Ok(())
+}
+
+/// Returns the receiver of the given RLP item, where an empty item encodes a contract creation.
+pub(crate) fn decode_receiver(receiver: &[u8]) -> Result<Option<EthereumAddress>, TransactionError> {
+    match receiver.is_empty() {
+        true => Ok(None),
+        false => Ok(Some(EthereumAddress::from_str(&hex::encode(receiver))?)),
+    }
+}
+
+/// Returns the recovery id and the (r, s) values of the signature of the given message hash.
+pub(crate) fn sign_message(
+    message: &[u8],
+    private_key: &EthereumPrivateKey,
+) -> Result<(u8, Vec<u8>, Vec<u8>), TransactionError> {
+    let (signature, recovery_id) = private_key.sign_recoverable(message)?;
+    let signature = signature.serialize();
+    Ok((
+        recovery_id.serialize(),
+        trim_leading_zeros(&signature[0..32]),
+        trim_leading_zeros(&signature[32..64]),
+    ))
+}
+
+/// Returns the address of the signer of the given message hash.
+pub(crate) fn recover_signer(
+    message: &[u8],
+    recovery_id: u8,
+    r: &[u8],
+    s: &[u8],
+) -> Result<EthereumAddress, TransactionError> {
+    let public_key = EthereumPublicKey::from_secp256k1_public_key(secp256k1::recover(
+        &secp256k1::Message::parse_slice(message)?,
+        &secp256k1::Signature::parse_slice(&[pad_to_32(r)?, pad_to_32(s)?].concat())?,
+        &secp256k1::RecoveryId::parse(recovery_id)?,
+    )?);
+    Ok(public_key.to_address(&EthereumFormat::Standard)?)
+}
+
 /// Represents the parameters for an Ethereum transaction
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct EthereumTransactionParameters {
-    /// The address of the receiver
-    pub receiver: EthereumAddress,
+    /// The address of the receiver, or `None` for a contract creation
+    pub receiver: Option<EthereumAddress>,
     /// The amount (in wei)
     pub amount: EthereumAmount,
     /// The transaction gas limit
@@ -116,18 +176,14 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             (Some(_), Some(_)) => Ok(self.clone()),
             (Some(_), None) | (None, Some(_)) => Err(TransactionError::InvalidTransactionState),
             (None, None) => {
-                let (signature, v) = secp256k1::sign(
-                    &secp256k1::Message::parse_slice(&self.to_transaction_id()?.txid)?,
-                    &private_key.to_secp256k1_secret_key(),
-                );
-                let signature = signature.serialize();
+                let (recovery_id, r, s) = sign_message(&self.to_transaction_id()?.txid, private_key)?;
 
                 let mut transaction = self.clone();
                 transaction.sender = Some(private_key.to_address(&EthereumFormat::Standard)?);
                 transaction.signature = Some(EthereumTransactionSignature {
-                    v: to_bytes(Into::<i32>::into(v) as u32 + N::CHAIN_ID * 2 + 35)?, // EIP155
-                    r: signature[0..32].to_vec(),
-                    s: signature[32..64].to_vec(),
+                    v: to_bytes(recovery_id as u32 + N::CHAIN_ID * 2 + 35)?, // EIP155
+                    r,
+                    s,
                 });
                 Ok(transaction)
             }
@@ -137,33 +193,22 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
     /// Returns a transaction given the transaction bytes.
     /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
     fn from_transaction_bytes(transaction: &Vec<u8>) -> Result<Self, TransactionError> {
-        let list: Vec<Vec<u8>> = decode_list(&transaction);
+        let transaction = RlpItem::decode(transaction)?;
+        let list = transaction.as_list()?;
         if list.len() != 9 {
             return Err(TransactionError::InvalidRlpLength(list.len()));
         }
 
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(&hex::encode(&list[3]))?,
-            amount: match list[4].is_empty() {
-                true => EthereumAmount::from_u256(U256::zero()),
-                false => EthereumAmount::from_u256(U256::from(list[4].as_slice())),
-            },
-            gas: match list[2].is_empty() {
-                true => U256::zero(),
-                false => U256::from(list[2].as_slice()),
-            },
-            gas_price: match list[1].is_empty() {
-                true => EthereumAmount::from_u256(U256::zero()),
-                false => EthereumAmount::from_u256(U256::from(list[1].as_slice())),
-            },
-            nonce: match list[0].is_empty() {
-                true => U256::zero(),
-                false => U256::from(list[0].as_slice()),
-            },
-            data: list[5].clone(),
+            receiver: decode_receiver(list[3].as_bytes()?)?,
+            amount: EthereumAmount::from_u256(list[4].as_u256()?),
+            gas: list[2].as_u256()?,
+            gas_price: EthereumAmount::from_u256(list[1].as_u256()?),
+            nonce: list[0].as_u256()?,
+            data: list[5].as_bytes()?.to_vec(),
         };
 
-        match list[7].is_empty() && list[8].is_empty() {
+        match list[7].as_bytes()?.is_empty() && list[8].as_bytes()?.is_empty() {
             true => {
                 // Raw transaction
                 Ok(Self {
@@ -175,34 +220,18 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             }
             false => {
                 // Signed transaction
-                let v = from_bytes(&list[6])?;
-                let recovery_id = secp256k1::RecoveryId::parse((v - N::CHAIN_ID * 2 - 35) as u8)?;
-                let mut signature = list[7].clone();
-                signature.extend_from_slice(&list[8]);
-
-                let raw_transaction = Self {
+                let mut transaction = Self {
                     sender: None,
-                    parameters: parameters.clone(),
-                    signature: None,
-                    _network: PhantomData,
-                };
-                let message = secp256k1::Message::parse_slice(&raw_transaction.to_transaction_id()?.txid)?;
-                let public_key = EthereumPublicKey::from_secp256k1_public_key(secp256k1::recover(
-                    &message,
-                    &secp256k1::Signature::parse_slice(signature.as_slice())?,
-                    &recovery_id,
-                )?);
-
-                Ok(Self {
-                    sender: Some(public_key.to_address(&EthereumFormat::Standard)?),
                     parameters,
                     signature: Some(EthereumTransactionSignature {
-                        v: list[6].clone(),
-                        r: list[7].clone(),
-                        s: list[8].clone(),
+                        v: list[6].as_bytes()?.to_vec(),
+                        r: list[7].as_bytes()?.to_vec(),
+                        s: list[8].as_bytes()?.to_vec(),
                     }),
                     _network: PhantomData,
-                })
+                };
+                transaction.sender = Some(transaction.recover_sender()?);
+                Ok(transaction)
             }
         }
     }
@@ -219,7 +248,7 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
             transaction_rlp.append(&parameters.nonce);
             transaction_rlp.append(&parameters.gas_price.0);
             transaction_rlp.append(&parameters.gas);
-            transaction_rlp.append(&hex::decode(&parameters.receiver.to_string()[2..])?);
+            encode_receiver(transaction_rlp, &parameters.receiver)?;
             transaction_rlp.append(&parameters.amount.0);
             transaction_rlp.append(&parameters.data);
             Ok(())
@@ -267,6 +296,36 @@ impl<N: EthereumNetwork> Transaction for EthereumTransaction<N> {
     }
 }
 
+impl<N: EthereumNetwork> EthereumTransaction<N> {
+    /// Returns the address of the sender, recovered from the signature of the transaction.
+    /// https://github.com/ethereum/EIPs/blob/master/EIPS/eip-155.md
+    pub fn recover_sender(&self) -> Result<EthereumAddress, TransactionError> {
+        let signature = match &self.signature {
+            Some(signature) => signature,
+            None => return Err(TransactionError::InvalidTransactionState),
+        };
+
+        let v = from_bytes(&signature.v)?;
+        let recovery_id = match v.checked_sub(N::CHAIN_ID * 2 + 35) {
+            Some(recovery_id) if recovery_id < 2 => recovery_id as u8,
+            _ => return Err(TransactionError::InvalidSignatureV(v)),
+        };
+
+        let raw_transaction = Self {
+            sender: None,
+            parameters: self.parameters.clone(),
+            signature: None,
+            _network: PhantomData,
+        };
+        recover_signer(
+            &raw_transaction.to_transaction_id()?.txid,
+            recovery_id,
+            &signature.r,
+            &signature.s,
+        )
+    }
+}
+
 impl<N: EthereumNetwork> FromStr for EthereumTransaction<N> {
     type Err = TransactionError;
 
@@ -308,12 +367,19 @@ mod tests {
         pub signed_transaction_hash: &'static str,
     }
 
+    fn receiver(to: &str) -> Option<EthereumAddress> {
+        match to {
+            "" => None,
+            to => Some(EthereumAddress::from_str(to).unwrap()),
+        }
+    }
+
     fn test_new<N: EthereumNetwork>(transaction: &TransactionTestCase) {
         let expected_signed_transaction = transaction.signed_transaction;
         let expected_signed_transaction_hash = transaction.signed_transaction_hash;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: receiver(transaction.to),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
@@ -334,7 +400,7 @@ mod tests {
         let expected_signed_transaction = transaction.signed_transaction;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: receiver(transaction.to),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
@@ -359,7 +425,7 @@ mod tests {
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let expected_sender = Some(private_key.to_address(&EthereumFormat::Standard).unwrap());
         let expected_parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: receiver(transaction.to),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
@@ -378,7 +444,7 @@ mod tests {
         let expected_signed_transaction_bytes = hex::decode(&transaction.signed_transaction[2..]).unwrap();
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: receiver(transaction.to),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
@@ -398,7 +464,7 @@ mod tests {
         let expected_signed_transaction_hash = transaction.signed_transaction_hash;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: receiver(transaction.to),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
@@ -414,11 +480,19 @@ mod tests {
         );
     }
 
+    fn test_recover_sender<N: EthereumNetwork>(transaction: &TransactionTestCase) {
+        let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
+        let expected_sender = private_key.to_address(&EthereumFormat::Standard).unwrap();
+
+        let transaction = EthereumTransaction::<N>::from_str(&transaction.signed_transaction[2..]).unwrap();
+        assert_eq!(expected_sender, transaction.recover_sender().unwrap());
+    }
+
     fn test_to_string<N: EthereumNetwork>(transaction: &TransactionTestCase) {
         let expected_signed_transaction = transaction.signed_transaction;
         let private_key = EthereumPrivateKey::from_str(transaction.private_key).unwrap();
         let parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(transaction.to).unwrap(),
+            receiver: receiver(transaction.to),
             amount: EthereumAmount::from_wei(transaction.value).unwrap(),
             gas: U256::from_dec_str(transaction.gas).unwrap(),
             gas_price: EthereumAmount::from_wei(transaction.gas_price).unwrap(),
@@ -494,6 +568,75 @@ mod tests {
         }
     }
 
+    mod eip155 {
+        use super::*;
+
+        type N = Mainnet;
+
+        const TRANSACTIONS: [TransactionTestCase; 2] = [
+            TransactionTestCase {
+                nonce: "9",
+                gas_price: "20000000000",
+                gas: "21000",
+                to: "0x3535353535353535353535353535353535353535",
+                value: "1000000000000000000",
+                data: "",
+                chain_id: Mainnet::CHAIN_ID as u8,
+                private_key: "4646464646464646464646464646464646464646464646464646464646464646",
+                signed_transaction: "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+                signed_transaction_hash: "0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788"
+            },
+            TransactionTestCase {
+                nonce: "0",
+                gas_price: "20000000000",
+                gas: "100000",
+                to: "",
+                value: "0",
+                data: "Contract Code",
+                chain_id: Mainnet::CHAIN_ID as u8,
+                private_key: "4646464646464646464646464646464646464646464646464646464646464646",
+                signed_transaction: "0xf85e808504a817c800830186a080808d436f6e747261637420436f646525a0b3ad7681c126c14cec386721c8bdf6522818113ea526bc192e3aed5107ca058da005d0d9879cd0514fb10abef3888b22dc1d5f034222f819667656ebd7e8263fea",
+                signed_transaction_hash: "0xf796192f6f14834b4c755fa2589664336ecaa4b0db9d6c3541bf4e87362da736"
+            },
+        ];
+
+        #[test]
+        fn new() {
+            TRANSACTIONS.iter().for_each(test_new::<N>);
+        }
+
+        #[test]
+        fn sign() {
+            TRANSACTIONS.iter().for_each(test_sign::<N>);
+        }
+
+        #[test]
+        fn from_transaction_bytes() {
+            TRANSACTIONS.iter().for_each(test_from_transaction_bytes::<N>);
+        }
+
+        #[test]
+        fn recover_sender() {
+            TRANSACTIONS.iter().for_each(test_recover_sender::<N>);
+        }
+
+        #[test]
+        fn recover_sender_invalid_chain_id() {
+            let transaction = EthereumTransaction::<Ropsten>::new(&EthereumTransactionParameters {
+                receiver: receiver(TRANSACTIONS[0].to),
+                amount: EthereumAmount::from_wei(TRANSACTIONS[0].value).unwrap(),
+                gas: U256::from_dec_str(TRANSACTIONS[0].gas).unwrap(),
+                gas_price: EthereumAmount::from_wei(TRANSACTIONS[0].gas_price).unwrap(),
+                nonce: U256::from_dec_str(TRANSACTIONS[0].nonce).unwrap(),
+                data: vec![],
+            })
+            .unwrap();
+            let private_key = EthereumPrivateKey::from_str(TRANSACTIONS[0].private_key).unwrap();
+            let signed_transaction = transaction.sign(&private_key).unwrap().to_string();
+            assert!(EthereumTransaction::<N>::from_str(&signed_transaction[2..]).is_err());
+        }
+    }
+
     mod rinkeby {
         use super::*;
 
@@ -42,4 +42,12 @@ mod tests {
         assert_eq!(2048, list.len());
         assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
     }
+
+    #[test]
+    fn suggest() {
+        assert!(English::suggest(INVALID_WORD).is_empty());
+        assert_eq!(vec!["deposit"], English::suggest("depo"));
+        assert_eq!(vec!["about", "above"], English::suggest("abo"));
+        assert_eq!(2048, English::suggest("").len());
+    }
 }
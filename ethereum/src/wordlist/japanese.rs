@@ -9,6 +9,9 @@ impl Wordlist for Japanese {}
 impl EthereumWordlist for Japanese {
     /// The wordlist in original form.
     const WORDLIST: &'static str = JAPANESE;
+
+    /// The separator between words of a phrase, an ideographic space.
+    const SEPARATOR: &'static str = "\u{3000}";
 }
 
 #[cfg(test)]
@@ -1,5 +1,8 @@
+use wagyu_model::no_std::*;
 use wagyu_model::wordlist::{Wordlist, WordlistError};
 
+use unicode_normalization::UnicodeNormalization;
+
 pub mod chinese_simplified;
 pub use self::chinese_simplified::*;
 
@@ -29,6 +32,9 @@ pub trait EthereumWordlist: Wordlist {
     /// The wordlist in original form.
     const WORDLIST: &'static str;
 
+    /// The separator between words of a phrase.
+    const SEPARATOR: &'static str = " ";
+
     /// Returns the word of a given index from the word list.
     fn get(index: usize) -> Result<String, WordlistError> {
         if index >= 2048 {
@@ -37,9 +43,10 @@ pub trait EthereumWordlist: Wordlist {
         Ok(Self::get_all()[index].into())
     }
 
-    /// Returns the index of a given word from the word list.
+    /// Returns the index of a given word from the word list, after NFKD normalization.
     fn get_index(word: &str) -> Result<usize, WordlistError> {
-        match Self::get_all().iter().position(|element| element == &word) {
+        let normalized = word.nfkd().collect::<String>();
+        match Self::get_all().iter().position(|element| *element == normalized) {
             Some(index) => Ok(index),
             None => Err(WordlistError::InvalidWord(word.into())),
         }
@@ -49,4 +56,68 @@ pub trait EthereumWordlist: Wordlist {
     fn get_all() -> Vec<&'static str> {
         Self::WORDLIST.lines().collect::<Vec<&str>>()
     }
+
+    /// Returns the words from the word list that start with the given prefix, after NFKD normalization.
+    fn suggest(prefix: &str) -> Vec<&'static str> {
+        let normalized = prefix.nfkd().collect::<String>();
+        Self::WORDLIST
+            .lines()
+            .filter(|word| word.starts_with(&normalized))
+            .collect()
+    }
+}
+
+/// Represents the language of a BIP39 wordlist.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Language {
+    ChineseSimplified,
+    ChineseTraditional,
+    English,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Spanish,
+}
+
+impl Language {
+    /// The languages with a supported wordlist.
+    pub const ALL: [Language; 8] = [
+        Language::ChineseSimplified,
+        Language::ChineseTraditional,
+        Language::English,
+        Language::French,
+        Language::Italian,
+        Language::Japanese,
+        Language::Korean,
+        Language::Spanish,
+    ];
+
+    /// Returns the words of the wordlist in this language.
+    pub fn get_all(&self) -> Vec<&'static str> {
+        match self {
+            Language::ChineseSimplified => ChineseSimplified::get_all(),
+            Language::ChineseTraditional => ChineseTraditional::get_all(),
+            Language::English => English::get_all(),
+            Language::French => French::get_all(),
+            Language::Italian => Italian::get_all(),
+            Language::Japanese => Japanese::get_all(),
+            Language::Korean => Korean::get_all(),
+            Language::Spanish => Spanish::get_all(),
+        }
+    }
+
+    /// Returns the languages whose wordlist contains every word of the given phrase.
+    /// A phrase may be found in more than one wordlist, as Chinese simplified and traditional share words.
+    pub fn detect(phrase: &str) -> Vec<Language> {
+        let phrase = phrase.nfkd().collect::<String>();
+        Self::ALL
+            .iter()
+            .filter(|language| {
+                let wordlist = language.get_all();
+                phrase.split(" ").all(|word| wordlist.contains(&word))
+            })
+            .copied()
+            .collect()
+    }
 }
@@ -42,4 +42,12 @@ mod tests {
         assert_eq!(2048, list.len());
         assert_eq!(VALID_WORD, list[VALID_WORD_INDEX]);
     }
+
+    #[test]
+    fn normalization() {
+        // The composed (NFC) form of the word, as most keyboards type it
+        assert_eq!(VALID_WORD_INDEX, Spanish::get_index("az\u{fa}car").unwrap());
+        assert_eq!(vec![VALID_WORD], Spanish::suggest("az\u{fa}"));
+        assert_eq!(vec![VALID_WORD, "azufre", "azul"], Spanish::suggest("azu"));
+    }
 }
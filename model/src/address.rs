@@ -1,3 +1,4 @@
+use crate::base58::Base58Error;
 use crate::format::Format;
 use crate::private_key::{PrivateKey, PrivateKeyError};
 use crate::public_key::{PublicKey, PublicKeyError};
@@ -26,9 +27,18 @@ pub trait Address:
 
 #[derive(Debug, Fail)]
 pub enum AddressError {
+    #[fail(display = "{}", _0)]
+    Base58Error(Base58Error),
+
+    #[fail(display = "invalid checksum address: mismatched case at position {}", _0)]
+    ChecksumMismatch(usize),
+
     #[fail(display = "{}: {}", _0, _1)]
     Crate(&'static str, String),
 
+    #[fail(display = "duplicate receiver typecode: {}", _0)]
+    DuplicateTypecode(u32),
+
     #[fail(display = "invalid format conversion from {:?} to {:?}", _0, _1)]
     IncompatibleFormats(String, String),
 
@@ -47,18 +57,36 @@ pub enum AddressError {
     #[fail(display = "invalid network: {{ expected: {:?}, found: {:?} }}", _0, _1)]
     InvalidNetwork(String, String),
 
+    #[fail(display = "invalid padding")]
+    InvalidPadding,
+
     #[fail(display = "invalid address prefix: {:?}", _0)]
     InvalidPrefix(Vec<u8>),
 
     #[fail(display = "invalid address prefix length: {:?}", _0)]
     InvalidPrefixLength(usize),
 
+    #[fail(display = "invalid receiver length for typecode {}: {} bytes", _0, _1)]
+    InvalidReceiverLength(u32, usize),
+
+    #[fail(display = "invalid signature header: {}", _0)]
+    InvalidSignatureHeader(u8),
+
+    #[fail(display = "invalid signature length: {}", _0)]
+    InvalidSignatureLength(usize),
+
+    #[fail(display = "invalid receiver typecode order: {} after {}", _0, _1)]
+    InvalidTypecodeOrder(u32, u32),
+
     #[fail(display = "{}", _0)]
     Message(String),
 
     #[fail(display = "missing public spend key and/or public view key")]
     MissingPublicKey,
 
+    #[fail(display = "network mismatch: {{ expected: {:?}, found: {:?} }}", expected, found)]
+    NetworkMismatch { expected: String, found: String },
+
     #[fail(display = "{}", _0)]
     PrivateKeyError(PrivateKeyError),
 
@@ -66,6 +94,12 @@ pub enum AddressError {
     PublicKeyError(PublicKeyError),
 }
 
+impl From<Base58Error> for AddressError {
+    fn from(error: Base58Error) -> Self {
+        AddressError::Base58Error(error)
+    }
+}
+
 impl From<crate::no_std::io::Error> for AddressError {
     fn from(error: crate::no_std::io::Error) -> Self {
         AddressError::Crate("crate::no_std::io", format!("{:?}", error))
@@ -131,3 +165,9 @@ impl From<rand_core::Error> for AddressError {
         AddressError::Crate("rand", format!("{:?}", error))
     }
 }
+
+impl From<secp256k1::Error> for AddressError {
+    fn from(error: secp256k1::Error) -> Self {
+        AddressError::Crate("libsecp256k1", format!("{:?}", error))
+    }
+}
@@ -17,4 +17,118 @@ pub enum AmountError {
 
     #[fail(display = "invalid amount: {}", _0)]
     InvalidAmount(String),
+
+    #[fail(display = "invalid denomination: {}", _0)]
+    InvalidDenomination(String),
+
+    #[fail(display = "the amount: {} has more than {} decimal places", _0, _1)]
+    InvalidPrecision(String, u32),
+
+    #[fail(display = "the amount: {} is negative", _0)]
+    NegativeAmount(String),
+
+    #[fail(display = "the amount overflows: {}", _0)]
+    Overflow(String),
+}
+
+/// Returns the digits of the given decimal amount in base units, for a denomination with the
+/// given number of decimal places, e.g. "0.001" with 8 decimal places is "100000".
+/// Only plain decimals are accepted, so signs, exponents and excess decimal places are rejected.
+pub fn decimal_to_base_units(amount: &str, precision: u32) -> Result<String, AmountError> {
+    let (integer, fraction) = match amount.find('.') {
+        Some(index) => (&amount[..index], &amount[index + 1..]),
+        None => (amount, ""),
+    };
+
+    let is_digits = |digits: &str| digits.bytes().all(|byte| byte.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(AmountError::InvalidAmount(amount.into()));
+    }
+    if fraction.len() > precision as usize {
+        return Err(AmountError::InvalidPrecision(amount.into(), precision));
+    }
+
+    let mut digits = String::with_capacity(integer.len() + precision as usize);
+    digits.push_str(integer);
+    digits.push_str(fraction);
+    (fraction.len()..precision as usize).for_each(|_| digits.push('0'));
+
+    match digits.trim_start_matches('0') {
+        "" => Ok("0".into()),
+        digits => Ok(digits.into()),
+    }
+}
+
+/// Returns the decimal amount of the given digits in base units, for a denomination with the
+/// given number of decimal places, without trailing zeros, e.g. "100000" with 8 decimal places is "0.001".
+pub fn base_units_to_decimal(digits: &str, precision: u32) -> String {
+    let precision = precision as usize;
+    let padded = match digits.len() > precision {
+        true => digits.to_string(),
+        false => format!("{:0>width$}", digits, width = precision + 1),
+    };
+
+    let (integer, fraction) = padded.split_at(padded.len() - precision);
+    match fraction.trim_end_matches('0') {
+        "" => integer.into(),
+        fraction => format!("{}.{}", integer, fraction),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_to_base_units() {
+        [
+            ("0", 8, "0"),
+            ("0.001", 8, "100000"),
+            ("1", 8, "100000000"),
+            ("21000000.00000001", 8, "2100000000000001"),
+            (".5", 3, "500"),
+            ("5.", 3, "5000"),
+            ("007", 0, "7"),
+            ("21", 9, "21000000000"),
+        ]
+        .iter()
+        .for_each(|(amount, precision, expected)| {
+            assert_eq!(*expected, decimal_to_base_units(amount, *precision).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_invalid_decimal_to_base_units() {
+        ["", ".", "-1", "+1", "1e8", "1E-3", "0x10", "1.2.3", "1,000", " 1", "1 "]
+            .iter()
+            .for_each(|amount| match decimal_to_base_units(amount, 8) {
+                Err(AmountError::InvalidAmount(_)) => (),
+                result => panic!("unexpected result for {:?}: {:?}", amount, result),
+            });
+
+        ["0.000000001", "1.000000000"]
+            .iter()
+            .for_each(|amount| match decimal_to_base_units(amount, 8) {
+                Err(AmountError::InvalidPrecision(_, 8)) => (),
+                result => panic!("unexpected result for {:?}: {:?}", amount, result),
+            });
+        assert!(decimal_to_base_units("1.5", 0).is_err());
+    }
+
+    #[test]
+    fn test_base_units_to_decimal() {
+        [
+            ("0", 8, "0"),
+            ("1", 8, "0.00000001"),
+            ("100000", 8, "0.001"),
+            ("100000000", 8, "1"),
+            ("2100000000000001", 8, "21000000.00000001"),
+            ("21000000000", 9, "21"),
+            ("546", 0, "546"),
+        ]
+        .iter()
+        .for_each(|(digits, precision, expected)| {
+            assert_eq!(*expected, base_units_to_decimal(digits, *precision));
+        });
+    }
 }
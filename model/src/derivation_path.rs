@@ -4,6 +4,19 @@ use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The maximum number of child indices in a derivation path, as BIP32 serializes the depth as a single byte.
+pub const MAX_DEPTH: usize = 255;
+
+/// Returns the depth of a child of a key at the given depth, or `None` if the child would exceed
+/// the maximum depth, as BIP32 serializes the depth in a single byte.
+pub fn child_depth(depth: u8) -> Option<u8> {
+    match (depth as usize) < MAX_DEPTH {
+        true => Some(depth + 1),
+        false => None,
+    }
+}
 
 /// The interface for a generic derivation path.
 pub trait DerivationPath: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
@@ -12,6 +25,124 @@ pub trait DerivationPath: Clone + Debug + Display + FromStr + Send + Sync + 'sta
 
     /// Returns a derivation path given the child index vector.
     fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError>;
+
+    /// Returns the child index at the given level of the derivation path.
+    fn get(&self, level: usize) -> Option<ChildIndex> {
+        self.to_vec().ok()?.get(level).copied()
+    }
+
+    /// Returns `true` if the child index at the given level of the derivation path is hardened.
+    fn is_hardened(&self, level: usize) -> bool {
+        match self.get(level) {
+            Some(index) => index.is_hardened(),
+            None => false,
+        }
+    }
+
+    /// Appends the given child index to the derivation path.
+    fn push(&mut self, index: ChildIndex) -> Result<(), DerivationPathError> {
+        self.extend(Some(index))
+    }
+
+    /// Removes the last child index from the derivation path and returns it,
+    /// or `None` if the derivation path is the master path.
+    fn pop(&mut self) -> Option<ChildIndex> {
+        let mut path = self.to_vec().ok()?;
+        let index = path.pop()?;
+        *self = Self::from_vec(&path).ok()?;
+        Some(index)
+    }
+
+    /// Appends the given child indices to the derivation path.
+    fn extend<I: IntoIterator<Item = ChildIndex>>(&mut self, indices: I) -> Result<(), DerivationPathError> {
+        let mut path = self.to_vec()?;
+        path.extend(indices);
+        *self = Self::from_vec(&path)?;
+        Ok(())
+    }
+
+    /// Returns the parent derivation path, or `None` if the derivation path is the master path.
+    fn parent(&self) -> Option<Self> {
+        let mut parent = self.clone();
+        parent.pop()?;
+        Some(parent)
+    }
+
+    /// Returns the derivation path of the given child index.
+    fn child(&self, index: ChildIndex) -> Result<Self, DerivationPathError> {
+        let mut child = self.clone();
+        child.push(index)?;
+        Ok(child)
+    }
+}
+
+/// Returns the child indices of the given derivation path string, e.g. "m/44'/0'/0'/0/0", ignoring any
+/// surrounding whitespace. A derivation path prefixed with "M" is a public derivation path, and may only
+/// contain normal child indices. A child index that fails to parse is reported with its position,
+/// where the first child index after "m" is at position 1.
+pub fn parse_derivation_path(path: &str) -> Result<Vec<ChildIndex>, DerivationPathError> {
+    let path = path.trim();
+
+    // Reject paths deeper than BIP32 can serialize before parsing any child index.
+    let depth = path.matches('/').count();
+    if depth > MAX_DEPTH {
+        return Err(DerivationPathError::MaximumDepthExceeded(depth));
+    }
+
+    let mut parts = path.split('/');
+
+    let public = match parts.next() {
+        Some("m") => false,
+        Some("M") => true,
+        _ => return Err(DerivationPathError::InvalidDerivationPath(path.to_string())),
+    };
+
+    let indices = parts
+        .enumerate()
+        .map(|(i, part)| match part.parse() {
+            Err(DerivationPathError::InvalidChildNumberFormat) => {
+                Err(DerivationPathError::InvalidPathSegment(part.to_string(), i + 1))
+            }
+            result => result,
+        })
+        .collect::<Result<Vec<ChildIndex>, DerivationPathError>>()?;
+
+    // Check that a public derivation path does not contain hardened indices.
+    if public && indices.iter().any(ChildIndex::is_hardened) {
+        return Err(DerivationPathError::ExpectedNormalPath);
+    }
+
+    Ok(indices)
+}
+
+/// Serializes the derivation path as its string form, e.g. "m/44'/0'/0'/0/0", in human-readable
+/// formats, and as a sequence of raw u32 child indices otherwise.
+pub fn serialize_derivation_path<P: DerivationPath, S: Serializer>(path: &P, serializer: S) -> Result<S::Ok, S::Error> {
+    let indices = path.to_vec().map_err(ser::Error::custom)?;
+    match serializer.is_human_readable() {
+        true => serializer.serialize_str(&path.to_string()),
+        false => serializer.collect_seq(indices.into_iter().map(u32::from)),
+    }
+}
+
+/// Deserializes the derivation path from its string form in human-readable formats,
+/// and from a sequence of raw u32 child indices otherwise.
+pub fn deserialize_derivation_path<'de, P, D>(deserializer: D) -> Result<P, D::Error>
+where
+    P: DerivationPath,
+    P::Err: Display,
+    D: Deserializer<'de>,
+{
+    match deserializer.is_human_readable() {
+        true => P::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+        false => {
+            let path = Vec::<u32>::deserialize(deserializer)?;
+            if path.len() > MAX_DEPTH {
+                return Err(de::Error::custom(DerivationPathError::MaximumDepthExceeded(path.len())));
+            }
+            P::from_vec(&path.into_iter().map(ChildIndex::from).collect()).map_err(de::Error::custom)
+        }
+    }
 }
 
 #[derive(Debug, Fail, PartialEq, Eq)]
@@ -25,12 +156,18 @@ pub enum DerivationPathError {
     #[fail(display = "expected BIP49 path")]
     ExpectedBIP49Path,
 
+    #[fail(display = "expected BIP84 path")]
+    ExpectedBIP84Path,
+
     #[fail(display = "expected valid Ethereum derivation path")]
     ExpectedValidEthereumDerivationPath,
 
     #[fail(display = "expected ZIP32 path")]
     ExpectedZIP32Path,
 
+    #[fail(display = "expected BIP44 path, found invalid index at level {}", _0)]
+    InvalidBIP44Level(usize),
+
     #[fail(display = "expected hardened path")]
     ExpectedHardenedPath,
 
@@ -45,10 +182,16 @@ pub enum DerivationPathError {
 
     #[fail(display = "invalid derivation path: {}", _0)]
     InvalidDerivationPath(String),
+
+    #[fail(display = "invalid derivation path segment {:?} at position {}", _0, _1)]
+    InvalidPathSegment(String, usize),
+
+    #[fail(display = "derivation path depth {} exceeds the maximum depth of 255", _0)]
+    MaximumDepthExceeded(usize),
 }
 
-/// Represents a child index for a derivation path
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// Represents a child index for a derivation path, ordered by its raw index
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum ChildIndex {
     // A non-hardened index: Normal(n) == n in path notation
     Normal(u32),
@@ -95,6 +238,50 @@ impl ChildIndex {
             &ChildIndex::Normal(i) => i,
         }
     }
+
+    /// Returns the child index of the given raw index, where the hardened bit is set for a [`Hardened`] value.
+    pub fn from_index(index: u32) -> Self {
+        Self::from(index)
+    }
+
+    /// Returns the next child index of the same kind, or errors if the next index would cross
+    /// from the normal into the hardened range, or overflow the hardened range.
+    pub fn increment(&self) -> Result<Self, DerivationPathError> {
+        match *self {
+            ChildIndex::Normal(index) => Self::normal(Self::next(index)?),
+            ChildIndex::Hardened(index) => Self::hardened(Self::next(index)?),
+        }
+    }
+
+    /// Returns `count` consecutive child indices of the same kind, beginning at this child index, or errors
+    /// if the range would cross from the normal into the hardened range, or overflow the hardened range.
+    pub fn range(&self, count: u32) -> Result<Vec<Self>, DerivationPathError> {
+        let start = match *self {
+            ChildIndex::Normal(index) | ChildIndex::Hardened(index) => index,
+        };
+        let child_index = |index| match self {
+            ChildIndex::Normal(_) => Self::normal(index),
+            ChildIndex::Hardened(_) => Self::hardened(index),
+        };
+
+        // Check the last child index before building the range.
+        if let Some(offset) = count.checked_sub(1) {
+            child_index(
+                start
+                    .checked_add(offset)
+                    .ok_or(DerivationPathError::InvalidChildNumber(start))?,
+            )?;
+        }
+
+        (0..count).map(|offset| child_index(start + offset)).collect()
+    }
+
+    /// Returns the index after the given index, or errors if it overflows.
+    fn next(index: u32) -> Result<u32, DerivationPathError> {
+        index
+            .checked_add(1)
+            .ok_or(DerivationPathError::InvalidChildNumber(index))
+    }
 }
 
 impl From<u32> for ChildIndex {
@@ -119,15 +306,25 @@ impl From<ChildIndex> for u32 {
 impl FromStr for ChildIndex {
     type Err = DerivationPathError;
 
+    /// Returns the child index of the given string, where a hardened index
+    /// is suffixed with one of the markers `'`, `h`, or `H`, e.g. 44', 44h, or 44H.
     fn from_str(inp: &str) -> Result<Self, Self::Err> {
-        Ok(match inp.chars().last().map_or(false, |l| l == '\'' || l == 'h') {
-            true => Self::hardened(
-                inp[0..inp.len() - 1]
-                    .parse()
-                    .map_err(|_| DerivationPathError::InvalidChildNumberFormat)?,
-            )?,
-            false => Self::normal(inp.parse().map_err(|_| DerivationPathError::InvalidChildNumberFormat)?)?,
-        })
+        let (index, hardened) = match inp.char_indices().last() {
+            Some((i, '\'')) | Some((i, 'h')) | Some((i, 'H')) => (&inp[..i], true),
+            _ => (inp, false),
+        };
+
+        if index.is_empty() || !index.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(DerivationPathError::InvalidChildNumberFormat);
+        }
+
+        let index = index
+            .parse()
+            .map_err(|_| DerivationPathError::InvalidChildNumberFormat)?;
+        match hardened {
+            true => Self::hardened(index),
+            false => Self::normal(index),
+        }
     }
 }
 
@@ -140,12 +337,152 @@ impl fmt::Display for ChildIndex {
     }
 }
 
+impl fmt::Debug for ChildIndex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl Serialize for ChildIndex {
+    /// Serializes the child index as its string form, e.g. "44'", in human-readable formats,
+    /// and as its raw u32 index otherwise.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => serializer.serialize_str(&self.to_string()),
+            false => serializer.serialize_u32(u32::from(*self)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ChildIndex {
+    /// Deserializes the child index from its string form in human-readable formats,
+    /// and from its raw u32 index otherwise.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => Self::from_str(&String::deserialize(deserializer)?).map_err(de::Error::custom),
+            false => Ok(Self::from(u32::deserialize(deserializer)?)),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+impl proptest::arbitrary::Arbitrary for ChildIndex {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    /// Returns a strategy over every child index, normal and hardened, by its raw index.
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+        proptest::num::u32::ANY.prop_map(Self::from).boxed()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn child_depth() {
+        let mut depth = 0u8;
+        for expected in 1..=MAX_DEPTH {
+            depth = super::child_depth(depth).unwrap();
+            assert_eq!(expected, depth as usize);
+        }
+        assert_eq!(None, super::child_depth(depth));
+        assert_eq!(None, super::child_depth(core::u8::MAX));
+    }
+
+    mod parse_derivation_path {
+        use super::*;
+
+        #[test]
+        fn valid() {
+            assert_eq!(Ok(vec![]), parse_derivation_path("m"));
+            assert_eq!(Ok(vec![]), parse_derivation_path("M"));
+            assert_eq!(
+                Ok(vec![ChildIndex::Normal(0), ChildIndex::Normal(1)]),
+                parse_derivation_path("M/0/1")
+            );
+            assert_eq!(
+                Ok(vec![
+                    ChildIndex::Hardened(44),
+                    ChildIndex::Hardened(0),
+                    ChildIndex::Hardened(0),
+                    ChildIndex::Normal(0),
+                    ChildIndex::Normal(1)
+                ]),
+                parse_derivation_path("m/44'/0h/0H/0/1")
+            );
+        }
+
+        #[test]
+        fn invalid() {
+            ["", "n", "1/0", "0/m", "mM/0", "m /0"].iter().for_each(|path| {
+                assert_eq!(
+                    Err(DerivationPathError::InvalidDerivationPath(path.to_string())),
+                    parse_derivation_path(path)
+                );
+            });
+
+            [
+                ("m/", "", 1),
+                ("M/", "", 1),
+                ("m//1", "", 1),
+                ("m/0/", "", 2),
+                ("m/0x", "0x", 1),
+                ("m/0/ 1", " 1", 2),
+            ]
+            .iter()
+            .for_each(|(path, segment, position)| {
+                assert_eq!(
+                    Err(DerivationPathError::InvalidPathSegment(segment.to_string(), *position)),
+                    parse_derivation_path(path)
+                );
+            });
+
+            assert_eq!(
+                Err(DerivationPathError::ExpectedNormalPath),
+                parse_derivation_path("M/0/1'")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                parse_derivation_path("m/2147483648")
+            );
+        }
+
+        #[test]
+        fn surrounding_whitespace() {
+            ["m/44'/0 ", " m/44'/0", "\tm/44'/0\n"].iter().for_each(|path| {
+                assert_eq!(
+                    Ok(vec![ChildIndex::Hardened(44), ChildIndex::Normal(0)]),
+                    parse_derivation_path(path)
+                );
+            });
+        }
+
+        #[test]
+        fn maximum_depth() {
+            let path = format!("m{}", "/0".repeat(MAX_DEPTH));
+            assert_eq!(MAX_DEPTH, parse_derivation_path(&path).unwrap().len());
+
+            let path = format!("m{}", "/0".repeat(MAX_DEPTH + 1));
+            assert_eq!(
+                Err(DerivationPathError::MaximumDepthExceeded(MAX_DEPTH + 1)),
+                parse_derivation_path(&path)
+            );
+
+            let path = format!("m{}", "/x".repeat(100_000));
+            assert_eq!(
+                Err(DerivationPathError::MaximumDepthExceeded(100_000)),
+                parse_derivation_path(&path)
+            );
+        }
+    }
+
     mod child_index {
         use super::*;
+        use crate::utilities::test_utils::test_from_str_round_trip;
+        use proptest::prelude::*;
 
         #[test]
         fn normal() {
@@ -214,15 +551,207 @@ mod tests {
                     ChildIndex::Hardened(i),
                     ChildIndex::from_str(&format!("{}h", i)).unwrap()
                 );
+                assert_eq!(
+                    ChildIndex::Hardened(i),
+                    ChildIndex::from_str(&format!("{}H", i)).unwrap()
+                );
             }
         }
 
+        #[test]
+        fn from_str_invalid() {
+            [
+                "", "'", "h", "H", "44''", "44'h", "44h'", "44hh", "44'H", "4'4", "+44", "+44'", "-1", " 44", "44 ",
+                "44x",
+            ]
+            .iter()
+            .for_each(|inp| {
+                assert_eq!(
+                    Err(DerivationPathError::InvalidChildNumberFormat),
+                    ChildIndex::from_str(inp)
+                );
+            });
+        }
+
+        #[test]
+        fn from_str_boundary() {
+            assert_eq!(
+                ChildIndex::Normal(2147483647),
+                ChildIndex::from_str("2147483647").unwrap()
+            );
+            assert_eq!(
+                ChildIndex::Hardened(2147483647),
+                ChildIndex::from_str("2147483647'").unwrap()
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                ChildIndex::from_str("2147483648")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                ChildIndex::from_str("2147483648'")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberFormat),
+                ChildIndex::from_str("4294967296")
+            );
+        }
+
         #[test]
         fn to_string() {
             for i in (0..1 << 31).step_by(1 << 10) {
                 assert_eq!(format!("{}", i), ChildIndex::Normal(i).to_string());
                 assert_eq!(format!("{}\'", i), ChildIndex::Hardened(i).to_string());
             }
+            assert_eq!("0'", format!("{}", ChildIndex::Hardened(0)));
+            assert_eq!("0'", format!("{:?}", ChildIndex::Hardened(0)));
+            assert_eq!(
+                "[0, 1']",
+                format!("{:?}", [ChildIndex::Normal(0), ChildIndex::Hardened(1)])
+            );
+        }
+
+        #[test]
+        fn from_index() {
+            let indices = (0..1 << 31).step_by(1 << 10).chain(vec![(1 << 31) - 1]);
+            for i in indices {
+                for index in [ChildIndex::Normal(i), ChildIndex::Hardened(i)].iter() {
+                    assert_eq!(*index, ChildIndex::from_index(index.to_index()));
+                }
+                assert_eq!(i, ChildIndex::from_index(i).to_index());
+                assert_eq!(i | (1 << 31), ChildIndex::from_index(i | (1 << 31)).to_index());
+            }
+        }
+
+        #[test]
+        fn increment() {
+            for i in (0..(1 << 31) - 1).step_by(1 << 10) {
+                assert_eq!(ChildIndex::Normal(i + 1), ChildIndex::Normal(i).increment().unwrap());
+                assert_eq!(
+                    ChildIndex::Hardened(i + 1),
+                    ChildIndex::Hardened(i).increment().unwrap()
+                );
+            }
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(1 << 31)),
+                ChildIndex::Normal((1 << 31) - 1).increment()
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(1 << 31)),
+                ChildIndex::Hardened((1 << 31) - 1).increment()
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(core::u32::MAX)),
+                ChildIndex::Normal(core::u32::MAX).increment()
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(core::u32::MAX)),
+                ChildIndex::Hardened(core::u32::MAX).increment()
+            );
+        }
+
+        #[test]
+        fn range() {
+            assert_eq!(Ok(vec![]), ChildIndex::Normal(0).range(0));
+            assert_eq!(
+                Ok(vec![
+                    ChildIndex::Normal(5),
+                    ChildIndex::Normal(6),
+                    ChildIndex::Normal(7)
+                ]),
+                ChildIndex::Normal(5).range(3)
+            );
+            assert_eq!(
+                Ok(vec![ChildIndex::Hardened(0), ChildIndex::Hardened(1)]),
+                ChildIndex::Hardened(0).range(2)
+            );
+            assert_eq!(
+                Ok(vec![ChildIndex::Normal((1 << 31) - 1)]),
+                ChildIndex::Normal((1 << 31) - 1).range(1)
+            );
+            assert_eq!(20, ChildIndex::Normal(0).range(20).unwrap().len());
+
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(1 << 31)),
+                ChildIndex::Normal((1 << 31) - 8).range(9)
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(1 << 31)),
+                ChildIndex::Hardened((1 << 31) - 1).range(2)
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(1 << 31)),
+                ChildIndex::Normal(1 << 31).range(1)
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(core::u32::MAX)),
+                ChildIndex::Hardened(core::u32::MAX).range(2)
+            );
+        }
+
+        #[test]
+        fn ordering() {
+            let indices = [
+                ChildIndex::Hardened((1 << 31) - 1),
+                ChildIndex::Normal(1),
+                ChildIndex::Hardened(0),
+                ChildIndex::Normal((1 << 31) - 1),
+                ChildIndex::Normal(0),
+                ChildIndex::Hardened(1),
+            ];
+            let mut sorted = indices.to_vec();
+            sorted.sort();
+            let mut raw: Vec<u32> = indices.iter().map(ChildIndex::to_index).collect();
+            raw.sort();
+            assert_eq!(raw, sorted.iter().map(ChildIndex::to_index).collect::<Vec<u32>>());
+            assert!(ChildIndex::Normal((1 << 31) - 1) < ChildIndex::Hardened(0));
+        }
+
+        proptest! {
+            #[test]
+            fn to_string_from_str(index: ChildIndex) {
+                prop_assert_eq!(index, ChildIndex::from_str(&index.to_string()).unwrap());
+            }
+
+            #[test]
+            fn from_str_arbitrary(inp in "\\PC*") {
+                test_from_str_round_trip::<ChildIndex>(&inp)?;
+            }
+
+            #[test]
+            fn from_str_digits(inp in "[0-9]{0,12}['hH]?") {
+                let _ = ChildIndex::from_str(&inp);
+            }
+        }
+
+        #[test]
+        fn serde() {
+            [
+                ChildIndex::Normal(0),
+                ChildIndex::Hardened(44),
+                ChildIndex::Normal((1 << 31) - 1),
+            ]
+            .iter()
+            .for_each(|index| {
+                let json = serde_json::to_string(index).unwrap();
+                assert_eq!(format!("\"{}\"", index), json);
+                assert_eq!(*index, serde_json::from_str::<ChildIndex>(&json).unwrap());
+
+                let cbor = serde_cbor::to_vec(index).unwrap();
+                assert_eq!(u32::from(*index), serde_cbor::from_slice::<u32>(&cbor).unwrap());
+                assert_eq!(*index, serde_cbor::from_slice::<ChildIndex>(&cbor).unwrap());
+            });
+
+            assert_eq!(
+                ChildIndex::Hardened(44),
+                serde_json::from_str::<ChildIndex>("\"44h\"").unwrap()
+            );
+            assert_eq!(
+                "invalid child number: 2147483648",
+                serde_json::from_str::<ChildIndex>("\"2147483648\"")
+                    .unwrap_err()
+                    .to_string()
+            );
         }
     }
 }
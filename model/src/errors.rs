@@ -0,0 +1,68 @@
+//! # Errors
+//!
+//! The error types shared by every wallet implementation, gathered in one place.
+//!
+//! Each error derives `Fail`, which provides `Display` and lets the errors compose with `?`
+//! through their `From` conversions. Use `failure::Fail::compat` to obtain a value
+//! implementing `std::error::Error` when passing an error into a non-`failure` application.
+
+pub use crate::address::AddressError;
+pub use crate::amount::AmountError;
+pub use crate::derivation_path::DerivationPathError;
+pub use crate::extended_private_key::ExtendedPrivateKeyError;
+pub use crate::extended_public_key::ExtendedPublicKeyError;
+pub use crate::format::FormatError;
+pub use crate::mnemonic::MnemonicError;
+pub use crate::network::NetworkError;
+pub use crate::private_key::PrivateKeyError;
+pub use crate::public_key::PublicKeyError;
+pub use crate::transaction::TransactionError;
+pub use crate::utilities::base58::Base58Error;
+pub use crate::wordlist::WordlistError;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode_private_key(private_key: &str) -> Result<Vec<u8>, PrivateKeyError> {
+        Ok(hex::decode(private_key)?)
+    }
+
+    fn decode_address(private_key: &str) -> Result<Vec<u8>, AddressError> {
+        Ok(decode_private_key(private_key)?)
+    }
+
+    fn decode_mnemonic(private_key: &str) -> Result<Vec<u8>, MnemonicError> {
+        Ok(decode_address(private_key)?)
+    }
+
+    #[test]
+    fn test_error_conversions() {
+        match decode_mnemonic("zz") {
+            Err(MnemonicError::AddressError(AddressError::PrivateKeyError(PrivateKeyError::Crate("hex", _)))) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        match secp256k1::SecretKey::parse_slice(&[0u8; 31]).map_err(ExtendedPrivateKeyError::from) {
+            Err(ExtendedPrivateKeyError::Crate("libsecp256k1", _)) => {}
+            result => panic!("unexpected result: {:?}", result.map(|_| ())),
+        };
+
+        match hex::decode("0").map_err(ExtendedPublicKeyError::from) {
+            Err(ExtendedPublicKeyError::Crate("hex", _)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn test_std_error_compat() {
+        use failure::Fail;
+
+        fn to_std_error<E: Fail>(error: E) -> Box<dyn std::error::Error> {
+            Box::new(error.compat())
+        }
+
+        let error = to_std_error(DerivationPathError::InvalidDerivationPath("m/x".into()));
+        assert_eq!("invalid derivation path: m/x", error.to_string());
+    }
+}
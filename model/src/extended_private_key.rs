@@ -1,4 +1,5 @@
 use crate::address::{Address, AddressError};
+use crate::base58::Base58Error;
 use crate::derivation_path::{DerivationPath, DerivationPathError};
 use crate::extended_public_key::ExtendedPublicKey;
 use crate::format::Format;
@@ -45,12 +46,21 @@ pub trait ExtendedPrivateKey: Clone + Debug + Display + FromStr + Send + Sync +
 
 #[derive(Debug, Fail)]
 pub enum ExtendedPrivateKeyError {
+    #[fail(display = "{}", _0)]
+    Base58Error(Base58Error),
+
     #[fail(display = "{}: {}", _0, _1)]
     Crate(&'static str, String),
 
     #[fail(display = "{}", _0)]
     DerivationPathError(DerivationPathError),
 
+    #[fail(display = "expected a master key, found a key of depth {}", _0)]
+    ExpectedMasterKey(u8),
+
+    #[fail(display = "invalid BIP85 application: {}", _0)]
+    InvalidBip85Application(String),
+
     #[fail(display = "invalid byte length: {}", _0)]
     InvalidByteLength(usize),
 
@@ -60,6 +70,21 @@ pub enum ExtendedPrivateKeyError {
     )]
     InvalidChecksum(String, String),
 
+    #[fail(display = "invalid child key for child index: {}", _0)]
+    InvalidChildKey(u32),
+
+    #[fail(display = "invalid master key: a depth of 0 requires a zero parent fingerprint and child index")]
+    InvalidMasterKey,
+
+    #[fail(display = "invalid master key: the seed does not produce a valid secret key")]
+    InvalidMasterSecretKey,
+
+    #[fail(display = "invalid padding byte: {}", _0)]
+    InvalidPadding(u8),
+
+    #[fail(display = "invalid seed length: {} bytes, expected between 16 and 64 bytes", _0)]
+    InvalidSeedLength(usize),
+
     #[fail(display = "invalid version bytes: {:?}", _0)]
     InvalidVersionBytes(Vec<u8>),
 
@@ -69,6 +94,9 @@ pub enum ExtendedPrivateKeyError {
     #[fail(display = "{}", _0)]
     Message(String),
 
+    #[fail(display = "network mismatch: {{ expected: {:?}, found: {:?} }}", expected, found)]
+    NetworkMismatch { expected: String, found: String },
+
     #[fail(display = "{}", _0)]
     NetworkError(NetworkError),
 
@@ -76,6 +104,12 @@ pub enum ExtendedPrivateKeyError {
     UnsupportedFormat(String),
 }
 
+impl From<Base58Error> for ExtendedPrivateKeyError {
+    fn from(error: Base58Error) -> Self {
+        ExtendedPrivateKeyError::Base58Error(error)
+    }
+}
+
 impl From<crate::no_std::io::Error> for ExtendedPrivateKeyError {
     fn from(error: crate::no_std::io::Error) -> Self {
         ExtendedPrivateKeyError::Crate("crate::no_std::io", format!("{:?}", error))
@@ -124,6 +158,12 @@ impl From<crypto_mac::InvalidKeyLength> for ExtendedPrivateKeyError {
     }
 }
 
+impl From<hex::FromHexError> for ExtendedPrivateKeyError {
+    fn from(error: hex::FromHexError) -> Self {
+        ExtendedPrivateKeyError::Crate("hex", format!("{:?}", error))
+    }
+}
+
 impl From<secp256k1::Error> for ExtendedPrivateKeyError {
     fn from(error: secp256k1::Error) -> Self {
         ExtendedPrivateKeyError::Crate("libsecp256k1", format!("{:?}", error))
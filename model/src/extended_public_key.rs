@@ -1,4 +1,5 @@
 use crate::address::{Address, AddressError};
+use crate::base58::Base58Error;
 use crate::derivation_path::{DerivationPath, DerivationPathError};
 use crate::extended_private_key::ExtendedPrivateKey;
 use crate::format::Format;
@@ -34,6 +35,9 @@ pub trait ExtendedPublicKey: Clone + Debug + Display + FromStr + Send + Sync + '
 
 #[derive(Debug, Fail)]
 pub enum ExtendedPublicKeyError {
+    #[fail(display = "{}", _0)]
+    Base58Error(Base58Error),
+
     #[fail(display = "{}: {}", _0, _1)]
     Crate(&'static str, String),
 
@@ -49,9 +53,18 @@ pub enum ExtendedPublicKeyError {
     )]
     InvalidChecksum(String, String),
 
+    #[fail(display = "invalid child key for child index: {}", _0)]
+    InvalidChildKey(u32),
+
     #[fail(display = "invalid child number: {{ expected: {:?}, found: {:?} }}", _0, _1)]
     InvalidChildNumber(u32, u32),
 
+    #[fail(display = "invalid public key prefix byte: {}", _0)]
+    InvalidKeyByte(u8),
+
+    #[fail(display = "invalid master key: a depth of 0 requires a zero parent fingerprint and child index")]
+    InvalidMasterKey,
+
     #[fail(display = "invalid version bytes: {:?}", _0)]
     InvalidVersionBytes(Vec<u8>),
 
@@ -61,6 +74,9 @@ pub enum ExtendedPublicKeyError {
     #[fail(display = "{}", _0)]
     Message(String),
 
+    #[fail(display = "network mismatch: {{ expected: {:?}, found: {:?} }}", expected, found)]
+    NetworkMismatch { expected: String, found: String },
+
     #[fail(display = "{}", _0)]
     NetworkError(NetworkError),
 
@@ -71,6 +87,12 @@ pub enum ExtendedPublicKeyError {
     UnsupportedFormat(String),
 }
 
+impl From<Base58Error> for ExtendedPublicKeyError {
+    fn from(error: Base58Error) -> Self {
+        ExtendedPublicKeyError::Base58Error(error)
+    }
+}
+
 impl From<crate::no_std::io::Error> for ExtendedPublicKeyError {
     fn from(error: crate::no_std::io::Error) -> Self {
         ExtendedPublicKeyError::Crate("crate::no_std::io", format!("{:?}", error))
@@ -125,6 +147,12 @@ impl From<crypto_mac::InvalidKeyLength> for ExtendedPublicKeyError {
     }
 }
 
+impl From<hex::FromHexError> for ExtendedPublicKeyError {
+    fn from(error: hex::FromHexError) -> Self {
+        ExtendedPublicKeyError::Crate("hex", format!("{:?}", error))
+    }
+}
+
 impl From<secp256k1::Error> for ExtendedPublicKeyError {
     fn from(error: secp256k1::Error) -> Self {
         ExtendedPublicKeyError::Crate("libsecp256k1", format!("{:?}", error))
@@ -26,6 +26,8 @@ pub use self::amount::*;
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod errors;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -57,6 +57,68 @@ pub trait MnemonicExtended: Mnemonic {
     fn to_extended_public_key(&self, password: Option<&str>) -> Result<Self::ExtendedPublicKey, MnemonicError>;
 }
 
+/// Represents the size of the initial entropy of a BIP39 mnemonic, in bits
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EntropySize {
+    Entropy128,
+    Entropy160,
+    Entropy192,
+    Entropy224,
+    Entropy256,
+}
+
+impl EntropySize {
+    /// Returns the entropy size of a mnemonic with the given word count.
+    pub fn from_word_count(word_count: u8) -> Result<Self, MnemonicError> {
+        match word_count {
+            12 => Ok(EntropySize::Entropy128),
+            15 => Ok(EntropySize::Entropy160),
+            18 => Ok(EntropySize::Entropy192),
+            21 => Ok(EntropySize::Entropy224),
+            24 => Ok(EntropySize::Entropy256),
+            _ => Err(MnemonicError::InvalidWordCount(word_count as usize)),
+        }
+    }
+
+    /// Returns the entropy size of the given entropy length in bytes.
+    pub fn from_entropy_length(length: usize) -> Result<Self, MnemonicError> {
+        match length {
+            16 => Ok(EntropySize::Entropy128),
+            20 => Ok(EntropySize::Entropy160),
+            24 => Ok(EntropySize::Entropy192),
+            28 => Ok(EntropySize::Entropy224),
+            32 => Ok(EntropySize::Entropy256),
+            _ => Err(MnemonicError::InvalidEntropyLength(length)),
+        }
+    }
+
+    /// Returns the number of bits of entropy, ENT.
+    pub fn entropy_bits(&self) -> usize {
+        match self {
+            EntropySize::Entropy128 => 128,
+            EntropySize::Entropy160 => 160,
+            EntropySize::Entropy192 => 192,
+            EntropySize::Entropy224 => 224,
+            EntropySize::Entropy256 => 256,
+        }
+    }
+
+    /// Returns the number of bytes of entropy.
+    pub fn entropy_length(&self) -> usize {
+        self.entropy_bits() / 8
+    }
+
+    /// Returns the number of checksum bits, CS = ENT / 32.
+    pub fn checksum_bits(&self) -> usize {
+        self.entropy_bits() / 32
+    }
+
+    /// Returns the number of words, (ENT + CS) / 11.
+    pub fn word_count(&self) -> u8 {
+        ((self.entropy_bits() + self.checksum_bits()) / 11) as u8
+    }
+}
+
 #[derive(Debug, Fail)]
 pub enum MnemonicError {
     #[fail(display = "{}", _0)]
@@ -86,8 +148,11 @@ pub enum MnemonicError {
     #[fail(display = "Invalid word not found in monero: {}", _0)]
     InvalidWord(String),
 
+    #[fail(display = "Invalid word at position {}: {}", _0, _1)]
+    InvalidWordAtPosition(usize, String),
+
     #[fail(display = "Invalid mnemonic word count: {}", _0)]
-    InvalidWordCount(u8),
+    InvalidWordCount(usize),
 
     #[fail(display = "Missing the last word (checksum)")]
     MissingChecksumWord,
@@ -8,6 +8,16 @@ use core::{
 /// The interface for a generic network.
 pub trait Network: Copy + Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Ord + Sized + Hash {
     const NAME: &'static str;
+
+    /// The alternative names of the network, e.g. "main" for "mainnet"
+    const ALIASES: &'static [&'static str] = &[];
+
+    /// Returns true if the given name is the name or an alias of the network, ignoring case.
+    fn is_name(name: &str) -> bool {
+        core::iter::once(&Self::NAME)
+            .chain(Self::ALIASES)
+            .any(|network| network.eq_ignore_ascii_case(name))
+    }
 }
 
 #[derive(Debug, Fail)]
@@ -1,4 +1,5 @@
 use crate::address::{Address, AddressError};
+use crate::base58::Base58Error;
 use crate::format::Format;
 use crate::public_key::PublicKey;
 
@@ -25,8 +26,39 @@ pub trait PrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static
     fn to_address(&self, format: &Self::Format) -> Result<Self::Address, AddressError>;
 }
 
+/// The order n of the secp256k1 curve, in big-endian form
+const SECP256K1_ORDER: [u8; 32] = [
+    0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFE, 0xBA, 0xAE, 0xDC,
+    0xE6, 0xAF, 0x48, 0xA0, 0x3B, 0xBF, 0xD2, 0x5E, 0x8C, 0xD0, 0x36, 0x41, 0x41,
+];
+
+/// Returns the 32 bytes of the given hex string, which must be exactly 64 hex characters.
+pub fn parse_hex_32(hex: &str) -> Result<[u8; 32], PrivateKeyError> {
+    if hex.len() != 64 {
+        return Err(PrivateKeyError::InvalidCharacterLength(hex.len()));
+    }
+
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hex, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Returns the secp256k1 secret key of the given big-endian scalar, which must be in [1, n - 1].
+pub fn parse_secp256k1_secret_key(scalar: &[u8; 32]) -> Result<secp256k1::SecretKey, PrivateKeyError> {
+    if scalar.iter().all(|byte| *byte == 0) {
+        return Err(PrivateKeyError::ZeroScalar);
+    }
+    if scalar[..] >= SECP256K1_ORDER[..] {
+        return Err(PrivateKeyError::ScalarOutOfRange);
+    }
+    Ok(secp256k1::SecretKey::parse(scalar)?)
+}
+
 #[derive(Debug, Fail)]
 pub enum PrivateKeyError {
+    #[fail(display = "{}", _0)]
+    Base58Error(Base58Error),
+
     #[fail(display = "{}: {}", _0, _1)]
     Crate(&'static str, String),
 
@@ -39,17 +71,38 @@ pub enum PrivateKeyError {
     #[fail(display = "invalid private key checksum: {{ expected: {:?}, found: {:?} }}", _0, _1)]
     InvalidChecksum(String, String),
 
+    #[fail(display = "invalid compression flag: {:?}", _0)]
+    InvalidCompressionFlag(u8),
+
     #[fail(display = "invalid network: {{ expected: {:?}, found: {:?} }}", _0, _1)]
     InvalidNetwork(String, String),
 
     #[fail(display = "invalid private key prefix: {:?}", _0)]
     InvalidPrefix(Vec<u8>),
 
+    #[fail(display = "invalid secret key scalar")]
+    InvalidScalar,
+
     #[fail(display = "{}", _0)]
     Message(String),
 
+    #[fail(display = "network mismatch: {{ expected: {:?}, found: {:?} }}", expected, found)]
+    NetworkMismatch { expected: String, found: String },
+
+    #[fail(display = "invalid secret key scalar: not less than the curve order")]
+    ScalarOutOfRange,
+
     #[fail(display = "unsupported format")]
     UnsupportedFormat,
+
+    #[fail(display = "invalid secret key scalar: zero")]
+    ZeroScalar,
+}
+
+impl From<Base58Error> for PrivateKeyError {
+    fn from(error: Base58Error) -> Self {
+        PrivateKeyError::Base58Error(error)
+    }
 }
 
 impl From<crate::no_std::io::Error> for PrivateKeyError {
@@ -32,6 +32,9 @@ pub enum PublicKeyError {
     #[fail(display = "invalid character length: {}", _0)]
     InvalidCharacterLength(usize),
 
+    #[fail(display = "invalid public key: not a point on the curve")]
+    InvalidPoint,
+
     #[fail(display = "invalid public key prefix: {:?}", _0)]
     InvalidPrefix(String),
 
@@ -64,7 +64,7 @@ pub enum TransactionError {
     InvalidBindingSig(),
 
     #[fail(display = "invalid chain id {:?}", _0)]
-    InvalidChainId(u8),
+    InvalidChainId(u64),
 
     #[fail(display = "invalid ephemeral key {}", _0)]
     InvalidEphemeralKey(String),
@@ -87,6 +87,9 @@ pub enum TransactionError {
     #[fail(display = "invalid segwit flag: {:?}", _0)]
     InvalidSegwitFlag(usize),
 
+    #[fail(display = "invalid signature v value: {:?}", _0)]
+    InvalidSignatureV(u32),
+
     #[fail(display = "invalid spend description for address")]
     InvalidSpendDescription,
 
@@ -96,6 +99,9 @@ pub enum TransactionError {
     #[fail(display = "invalid transaction - either both sender and signature should be present, or neither")]
     InvalidTransactionState,
 
+    #[fail(display = "invalid transaction type: {:?}", _0)]
+    InvalidTransactionType(u8),
+
     #[fail(display = "invalid variable size integer: {:?}", _0)]
     InvalidVariableSizeInteger(usize),
 
@@ -117,6 +123,9 @@ pub enum TransactionError {
     #[fail(display = "missing output parameters")]
     MissingOutputParameters,
 
+    #[fail(display = "missing transaction input for the signing key")]
+    MissingSignerInput,
+
     #[fail(display = "missing spend description")]
     MissingSpendDescription,
 
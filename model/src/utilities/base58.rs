@@ -0,0 +1,90 @@
+use crate::no_std::*;
+use crate::utilities::crypto::{checksum, constant_time_eq};
+
+use base58::{FromBase58, FromBase58Error, ToBase58};
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum Base58Error {
+    #[fail(display = "invalid base58 length")]
+    InvalidBase58Length,
+
+    #[fail(display = "invalid base58 character: {:?} at index {}", _0, _1)]
+    InvalidCharacter(char, usize),
+
+    #[fail(display = "invalid base58 checksum: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    InvalidChecksum(String, String),
+
+    #[fail(display = "invalid base58 payload length: {}", _0)]
+    InvalidLength(usize),
+}
+
+impl From<FromBase58Error> for Base58Error {
+    fn from(error: FromBase58Error) -> Self {
+        match error {
+            FromBase58Error::InvalidBase58Character(character, index) => {
+                Base58Error::InvalidCharacter(character, index)
+            }
+            FromBase58Error::InvalidBase58Length => Base58Error::InvalidBase58Length,
+        }
+    }
+}
+
+/// Returns the Base58Check encoding of the given payload, which appends
+/// the first four bytes of the double SHA-256 of the payload as a checksum.
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut data = payload.to_vec();
+    data.extend_from_slice(&checksum(payload)[0..4]);
+    data.to_base58()
+}
+
+/// Returns the payload of the given Base58Check string, after validating its checksum.
+pub fn decode_check(string: &str) -> Result<Vec<u8>, Base58Error> {
+    let data = string.from_base58()?;
+    if data.len() < 4 {
+        return Err(Base58Error::InvalidLength(data.len()));
+    }
+
+    let (payload, expected) = data.split_at(data.len() - 4);
+    let checksum = &checksum(payload)[0..4];
+    if !constant_time_eq(expected, checksum) {
+        return Err(Base58Error::InvalidChecksum(expected.to_base58(), checksum.to_base58()));
+    }
+
+    Ok(payload.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (payload, encoding) of a P2PKH address
+    const PAYLOAD: &str = "00f54a5851e9372b87810a8e60cdd2e7cfd80b6e31";
+    const ENCODING: &str = "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs";
+
+    #[test]
+    fn encode_check() {
+        assert_eq!(ENCODING, super::encode_check(&hex::decode(PAYLOAD).unwrap()));
+    }
+
+    #[test]
+    fn decode_check() {
+        assert_eq!(hex::decode(PAYLOAD).unwrap(), super::decode_check(ENCODING).unwrap());
+        assert_eq!(
+            Vec::<u8>::new(),
+            super::decode_check(&super::encode_check(&[])).unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_check_invalid() {
+        assert_eq!(
+            Err(Base58Error::InvalidCharacter('0', 1)),
+            super::decode_check("10Mycacn")
+        );
+        assert_eq!(Err(Base58Error::InvalidLength(3)), super::decode_check("Ldp"));
+        assert!(matches!(
+            super::decode_check("1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt"),
+            Err(Base58Error::InvalidChecksum(_, _))
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+//!
+//! BIP173 - Bech32 and BIP350 - Bech32m
+//!
+//! Unlike the `bech32` crate, the encoding is not limited to 90 characters and the checksum
+//! variant is returned on decoding, as required by segwit v1+ and unified addresses.
+//!
+
+use crate::address::AddressError;
+use crate::no_std::*;
+
+/// The Bech32 character set.
+const CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+/// The length of a Bech32 checksum in 5-bit values.
+const CHECKSUM_LENGTH: usize = 6;
+
+/// Represents the checksum variant of a Bech32 string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// BIP173 - Bech32
+    Bech32,
+    /// BIP350 - Bech32m
+    Bech32m,
+}
+
+impl Variant {
+    /// Returns the checksum constant of the variant.
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+
+    /// Returns the name of the variant.
+    pub fn name(self) -> &'static str {
+        match self {
+            Variant::Bech32 => "bech32",
+            Variant::Bech32m => "bech32m",
+        }
+    }
+}
+
+/// Returns the Bech32 or Bech32m encoding of the given 5-bit values with the given
+/// human-readable part. Unlike BIP173, the length of the encoding is not limited.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> String {
+    let mut values = hrp_expand(hrp);
+    values.extend(data);
+    values.extend(&[0u8; CHECKSUM_LENGTH]);
+    let checksum = polymod(&values) ^ variant.constant();
+
+    let mut encoding = format!("{}1", hrp);
+    encoding.extend(data.iter().map(|value| CHARSET[*value as usize] as char));
+    encoding.extend(
+        (0..CHECKSUM_LENGTH).map(|i| CHARSET[((checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 31) as usize] as char),
+    );
+    encoding
+}
+
+/// Returns the lowercase human-readable part, the 5-bit values, and the checksum variant of
+/// the given Bech32 or Bech32m string. Unlike BIP173, the length of the string is not limited.
+pub fn decode(encoding: &str) -> Result<(String, Vec<u8>, Variant), AddressError> {
+    if encoding.to_lowercase() != encoding && encoding.to_uppercase() != encoding {
+        return Err(AddressError::InvalidAddress(encoding.into()));
+    }
+    let encoding = encoding.to_lowercase();
+    let (hrp, data) = match encoding.rfind('1') {
+        Some(separator) => (&encoding[..separator], &encoding[separator + 1..]),
+        None => return Err(AddressError::InvalidAddress(encoding.clone())),
+    };
+    if hrp.is_empty() || data.len() < CHECKSUM_LENGTH {
+        return Err(AddressError::InvalidCharacterLength(encoding.len()));
+    }
+    if hrp.bytes().any(|byte| !(33..=126).contains(&byte)) {
+        return Err(AddressError::InvalidAddress(encoding.clone()));
+    }
+
+    let data = data
+        .bytes()
+        .map(|character| match CHARSET.iter().position(|c| *c == character) {
+            Some(position) => Ok(position as u8),
+            None => Err(AddressError::InvalidAddress(encoding.clone())),
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend(&data);
+    let variant = match polymod(&values) {
+        checksum if checksum == Variant::Bech32.constant() => Variant::Bech32,
+        checksum if checksum == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err(AddressError::InvalidChecksum("bech32".into(), encoding.clone())),
+    };
+
+    Ok((hrp.into(), data[..data.len() - CHECKSUM_LENGTH].to_vec(), variant))
+}
+
+/// Returns the given data regrouped from `from`-bit values into `to`-bit values, padding the
+/// final group with zeros if `pad` is true, or rejecting nonzero or excess padding otherwise.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>, AddressError> {
+    let (mut accumulator, mut bits) = (0u32, 0u32);
+    let max_value = (1u32 << to) - 1;
+    let mut output = vec![];
+    for value in data {
+        if u32::from(*value) >> from != 0 {
+            return Err(AddressError::InvalidAddress(format!(
+                "invalid {}-bit value: {}",
+                from, value
+            )));
+        }
+        accumulator = (accumulator << from) | u32::from(*value);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            output.push(((accumulator >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            output.push(((accumulator << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from || ((accumulator << (to - bits)) & max_value) != 0 {
+        return Err(AddressError::InvalidPadding);
+    }
+    Ok(output)
+}
+
+/// Returns the Bech32 checksum remainder of the given 5-bit values.
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    values.iter().fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = (checksum & 0x1ff_ffff) << 5 ^ u32::from(*value);
+        (0..5)
+            .filter(|i| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, i| checksum ^ GENERATOR[i])
+    })
+}
+
+/// Returns the human-readable part expanded for the checksum computation.
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut values = hrp.bytes().map(|byte| byte >> 5).collect::<Vec<u8>>();
+    values.push(0);
+    values.extend(hrp.bytes().map(|byte| byte & 31));
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid() {
+        // BIP173 and BIP350 test vectors
+        [
+            ("A12UEL5L", Variant::Bech32),
+            (
+                "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+                Variant::Bech32,
+            ),
+            ("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw", Variant::Bech32),
+            (
+                "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+                Variant::Bech32,
+            ),
+            ("?1ezyfcl", Variant::Bech32),
+            ("A1LQFN3A", Variant::Bech32m),
+            ("a1lqfn3a", Variant::Bech32m),
+            ("abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", Variant::Bech32m),
+            (
+                "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+                Variant::Bech32m,
+            ),
+            ("?1v759aa", Variant::Bech32m),
+        ]
+        .iter()
+        .for_each(|(encoding, expected)| {
+            let (hrp, data, variant) = decode(encoding).unwrap();
+            assert_eq!(*expected, variant);
+            assert_eq!(encoding.to_lowercase(), encode(&hrp, &data, variant));
+        });
+    }
+
+    #[test]
+    fn test_invalid() {
+        [
+            "pzry9x0s0muk",
+            "1pzry9x0s0muk",
+            "x1b4n0q5v",
+            "li1dgmt3",
+            "A1G7SGD8",
+            "10a06t8",
+            "1qzzfhee",
+            "a1lqfn3A",
+            "\x201xj0phk",
+            "qyrz8wqd2c9m",
+        ]
+        .iter()
+        .for_each(|encoding| {
+            assert!(decode(encoding).is_err(), "{}", encoding);
+        });
+    }
+
+    #[test]
+    fn test_convert_bits() {
+        let data = [0x00, 0x14, 0x75, 0x1e, 0x76, 0xe8];
+        let converted = convert_bits(&data, 8, 5, true).unwrap();
+        assert_eq!(data.to_vec(), convert_bits(&converted, 5, 8, false).unwrap());
+
+        // Nonzero padding bits and values out of range are rejected.
+        assert!(convert_bits(&[0x01], 5, 8, false).is_err());
+        assert!(convert_bits(&[0x20], 5, 8, true).is_err());
+    }
+}
@@ -1,13 +1,273 @@
 use crate::no_std::*;
+use blake2b_simd::Params;
+use hmac::Hmac;
+use pbkdf2::pbkdf2;
 use ripemd160::Ripemd160;
 use sha2::{Digest, Sha256};
 
+/// The size of the buffer used to read data into a streaming hasher
+const READ_BUFFER_SIZE: usize = 8192;
+
+/// Represents a streaming double SHA-256 hasher, which hashes data as it is written instead of
+/// requiring it in a single slice. `Sha256dHasher::new().update(data).finalize()` equals `sha256d(data)`.
+#[derive(Debug, Clone, Default)]
+pub struct Sha256dHasher(Sha256);
+
+impl Sha256dHasher {
+    /// Returns a new hasher of no data.
+    pub fn new() -> Self {
+        Self(Sha256::new())
+    }
+
+    /// Appends the given data to the data of the hasher.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.0.input(data);
+        self
+    }
+
+    /// Appends the data of the given reader, until its end, to the data of the hasher, and
+    /// returns the number of bytes read.
+    pub fn update_reader<R: io::Read>(&mut self, reader: R) -> Result<u64, io::Error> {
+        read_into(reader, |data| {
+            self.update(data);
+        })
+    }
+
+    /// Returns the double SHA-256 hash of the data of the hasher.
+    pub fn finalize(&self) -> [u8; 32] {
+        sha256(&self.0.clone().result())
+    }
+}
+
+/// Represents a streaming hasher of the RIPEMD-160 of the SHA-256 of data, as used for Bitcoin
+/// public key and script hashes. `Hash160Hasher::new().update(data).finalize()` equals `hash160(data)`.
+#[derive(Debug, Clone, Default)]
+pub struct Hash160Hasher(Sha256);
+
+impl Hash160Hasher {
+    /// Returns a new hasher of no data.
+    pub fn new() -> Self {
+        Self(Sha256::new())
+    }
+
+    /// Appends the given data to the data of the hasher.
+    pub fn update(&mut self, data: &[u8]) -> &mut Self {
+        self.0.input(data);
+        self
+    }
+
+    /// Appends the data of the given reader, until its end, to the data of the hasher, and
+    /// returns the number of bytes read.
+    pub fn update_reader<R: io::Read>(&mut self, reader: R) -> Result<u64, io::Error> {
+        read_into(reader, |data| {
+            self.update(data);
+        })
+    }
+
+    /// Returns the RIPEMD-160 of the SHA-256 of the data of the hasher.
+    pub fn finalize(&self) -> [u8; 20] {
+        ripemd160(&self.0.clone().result())
+    }
+}
+
+/// Implements `io::Write` for a streaming hasher, so that serializers can write into it directly.
+macro_rules! impl_write_for_hasher {
+    ($hasher:ty) => {
+        #[cfg(feature = "std")]
+        impl io::Write for $hasher {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.update(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        #[cfg(not(feature = "std"))]
+        impl io::Write for $hasher {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, io::Error> {
+                self.update(buf);
+                Ok(buf.len())
+            }
+
+            fn write_all(&mut self, buf: &[u8]) -> Result<(), io::Error> {
+                self.update(buf);
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_write_for_hasher!(Sha256dHasher);
+impl_write_for_hasher!(Hash160Hasher);
+
+/// Passes the data of the given reader, until its end, to the given function in chunks, and
+/// returns the number of bytes read.
+fn read_into<R: io::Read, F: FnMut(&[u8])>(mut reader: R, mut update: F) -> Result<u64, io::Error> {
+    let mut buffer = [0u8; READ_BUFFER_SIZE];
+    let mut length = 0u64;
+    loop {
+        match reader.read(&mut buffer)? {
+            0 => return Ok(length),
+            read => {
+                update(&buffer[..read]);
+                length += read as u64;
+            }
+        }
+    }
+}
+
+/// Returns the double SHA-256 hash of the given data, of which Bitcoin checksums are the first 4 bytes.
 pub fn checksum(data: &[u8]) -> Vec<u8> {
-    Sha256::digest(&Sha256::digest(&data)).to_vec()
+    sha256d(data).to_vec()
 }
 
+/// Returns the RIPEMD-160 of the SHA-256 of the given data.
 pub fn hash160(bytes: &[u8]) -> Vec<u8> {
-    Ripemd160::digest(&Sha256::digest(&bytes)).to_vec()
+    Hash160Hasher::new().update(bytes).finalize().to_vec()
+}
+
+/// Returns the Keccak-256 hash of the given data, as used by Ethereum and Monero.
+/// This is the original Keccak padding, not the NIST SHA3-256 standard.
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    tiny_keccak::keccak256(data)
+}
+
+/// Returns the SHA-256 hash of the given data.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(data));
+    hash
+}
+
+/// Returns the double SHA-256 hash of the given data, as used by Bitcoin for checksums and transaction ids.
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256dHasher::new().update(data).finalize()
+}
+
+/// Returns the SHA-256 hash of the given data tagged with the SHA-256 hash of the given tag, as
+/// specified in BIP340 and used by Taproot.
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag = sha256(tag.as_bytes());
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::new().chain(&tag).chain(&tag).chain(data).result());
+    hash
+}
+
+/// Returns the RIPEMD-160 hash of the given data.
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&Ripemd160::digest(data));
+    hash
+}
+
+/// Returns the 32-byte BLAKE2b hash of the given data with the given 16-byte personalization,
+/// as used by Zcash. An all-zero personalization gives the unpersonalized hash.
+pub fn blake2b(personalization: &[u8; 16], data: &[u8]) -> [u8; 32] {
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(
+        Params::new()
+            .hash_length(32)
+            .personal(personalization)
+            .hash(data)
+            .as_bytes(),
+    );
+    hash
+}
+
+/// Returns `true` if the given byte slices are equal, comparing every byte regardless of
+/// where the first difference occurs. Slices of different lengths are never equal.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b.iter())
+        .fold(0u8, |difference, (x, y)| difference | (x ^ y))
+        == 0
+}
+
+/// Computes the scrypt key derivation function of RFC 7914 into the given output, as used by
+/// BIP38 and Ethereum keystores. The cost `n` must be a power of two greater than 1, and the
+/// caller is responsible for bounding the `128 * r * n` bytes of memory it allocates.
+pub fn scrypt(password: &[u8], salt: &[u8], n: usize, r: usize, p: usize, output: &mut [u8]) {
+    let block_words = 32 * r;
+    let mut blocks = vec![0u8; p * block_words * 4];
+    pbkdf2::<Hmac<Sha256>>(password, salt, 1, &mut blocks);
+
+    let mut v = vec![0u32; n * block_words];
+    let mut x = vec![0u32; block_words];
+    let mut y = vec![0u32; block_words];
+    for block in blocks.chunks_mut(block_words * 4) {
+        for (word, bytes) in x.iter_mut().zip(block.chunks(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+
+        for i in 0..n {
+            v[i * block_words..(i + 1) * block_words].copy_from_slice(&x);
+            block_mix(&x, &mut y);
+            core::mem::swap(&mut x, &mut y);
+        }
+
+        for _ in 0..n {
+            let j = x[block_words - 16] as usize & (n - 1);
+            for (word, other) in x.iter_mut().zip(&v[j * block_words..(j + 1) * block_words]) {
+                *word ^= other;
+            }
+            block_mix(&x, &mut y);
+            core::mem::swap(&mut x, &mut y);
+        }
+
+        for (bytes, word) in block.chunks_mut(4).zip(x.iter()) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+    }
+
+    pbkdf2::<Hmac<Sha256>>(password, &blocks, 1, output);
+}
+
+/// Computes the scrypt BlockMix of the given input into the given output.
+fn block_mix(input: &[u32], output: &mut [u32]) {
+    let count = input.len() / 16;
+    let mut x = [0u32; 16];
+    x.copy_from_slice(&input[(count - 1) * 16..]);
+
+    for i in 0..count {
+        for (word, other) in x.iter_mut().zip(&input[i * 16..(i + 1) * 16]) {
+            *word ^= other;
+        }
+        salsa20_8(&mut x);
+
+        let offset = if i % 2 == 0 { i / 2 } else { count / 2 + i / 2 } * 16;
+        output[offset..offset + 16].copy_from_slice(&x);
+    }
+}
+
+/// Applies the Salsa20/8 core to the given block.
+fn salsa20_8(block: &mut [u32; 16]) {
+    fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+        x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+        x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+        x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+    }
+
+    let mut x = *block;
+    for _ in 0..4 {
+        quarter_round(&mut x, 0, 4, 8, 12);
+        quarter_round(&mut x, 5, 9, 13, 1);
+        quarter_round(&mut x, 10, 14, 2, 6);
+        quarter_round(&mut x, 15, 3, 7, 11);
+        quarter_round(&mut x, 0, 1, 2, 3);
+        quarter_round(&mut x, 5, 6, 7, 4);
+        quarter_round(&mut x, 10, 11, 8, 9);
+        quarter_round(&mut x, 15, 12, 13, 14);
+    }
+    for (word, mixed) in block.iter_mut().zip(x.iter()) {
+        *word = word.wrapping_add(*mixed);
+    }
 }
 
 #[cfg(test)]
@@ -36,6 +296,111 @@ mod tests {
         test_checksum(b"00000000000000000000000000000000", &expected_bytes)
     }
 
+    #[test]
+    fn test_keccak256() {
+        assert_eq!(
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            hex::encode(keccak256(b""))
+        );
+        assert_eq!(
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+            hex::encode(keccak256(b"abc"))
+        );
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            hex::encode(sha256(b""))
+        );
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            hex::encode(sha256(b"abc"))
+        );
+    }
+
+    #[test]
+    fn test_sha256d() {
+        assert_eq!(
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456",
+            hex::encode(sha256d(b""))
+        );
+        assert_eq!(
+            "4f8b42c22dd3729b519ba6f68d2da7cc5b2d606d05daed5ad5128cc03e6c6358",
+            hex::encode(sha256d(b"abc"))
+        );
+    }
+
+    #[test]
+    fn test_tagged_hash() {
+        let tag = sha256(b"TapTweak");
+        let data = [tag.as_ref(), tag.as_ref(), b"abc"].concat();
+        assert_eq!(sha256(&data), tagged_hash("TapTweak", b"abc"));
+        assert_ne!(tagged_hash("TapTweak", b"abc"), tagged_hash("TapLeaf", b"abc"));
+    }
+
+    #[test]
+    fn test_ripemd160() {
+        assert_eq!("9c1185a5c5e9fc54612808977ee8f548b2258d31", hex::encode(ripemd160(b"")));
+        assert_eq!(
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc",
+            hex::encode(ripemd160(b"abc"))
+        );
+    }
+
+    #[test]
+    fn test_blake2b() {
+        // The unpersonalized BLAKE2b-256 hashes
+        assert_eq!(
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+            hex::encode(blake2b(&[0u8; 16], b""))
+        );
+        assert_eq!(
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+            hex::encode(blake2b(&[0u8; 16], b"abc"))
+        );
+        assert_ne!(blake2b(&[0u8; 16], b"abc"), blake2b(b"ZcashPrevoutHash", b"abc"));
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(&[], &[]));
+        assert!(constant_time_eq(&[0, 1, 2, 3], &[0, 1, 2, 3]));
+        assert!(!constant_time_eq(&[0, 1, 2, 3], &[0, 1, 2, 4]));
+        assert!(!constant_time_eq(&[0, 1, 2, 3], &[1, 1, 2, 3]));
+        assert!(!constant_time_eq(&[0, 1, 2, 3], &[0, 1, 2]));
+        assert!(!constant_time_eq(&[0, 1, 2], &[0, 1, 2, 3]));
+        assert!(!constant_time_eq(&[], &[0]));
+    }
+
+    #[test]
+    fn test_streaming_hashers() {
+        let data = (0..=255u8).cycle().take(1000).collect::<Vec<u8>>();
+        for length in &[0, 1, 31, 32, 33, 63, 64, 65, 1000] {
+            let data = &data[..*length];
+            for chunk_size in &[1, 31, 32, 33] {
+                let mut sha256d_hasher = Sha256dHasher::new();
+                let mut hash160_hasher = Hash160Hasher::new();
+                data.chunks(*chunk_size).for_each(|chunk| {
+                    sha256d_hasher.update(chunk);
+                    hash160_hasher.update(chunk);
+                });
+                assert_eq!(sha256d(data), sha256d_hasher.finalize());
+                assert_eq!(checksum(data), sha256d_hasher.finalize().to_vec());
+                assert_eq!(hash160(data), hash160_hasher.finalize().to_vec());
+            }
+
+            let mut sha256d_hasher = Sha256dHasher::new();
+            assert_eq!(*length as u64, sha256d_hasher.update_reader(data).unwrap());
+            assert_eq!(sha256d(data), sha256d_hasher.finalize());
+
+            let mut hash160_hasher = Hash160Hasher::new();
+            io::Write::write_all(&mut hash160_hasher, data).unwrap();
+            assert_eq!(hash160(data), hash160_hasher.finalize().to_vec());
+        }
+    }
+
     #[test]
     fn test_functionality_hash160() {
         let expected_bytes: [u8; 20] = [
@@ -43,4 +408,22 @@ mod tests {
         ];
         test_hash160(b"00000000000000000000000000000000", &expected_bytes)
     }
+
+    #[test]
+    fn test_scrypt() {
+        let mut output = [0u8; 64];
+        scrypt(b"", b"", 16, 1, 1, &mut output);
+        assert_eq!(
+            "77d6576238657b203b19ca42c18a0497f16b4844e3074ae8dfdffa3fede21442\
+             fcd0069ded0948f8326a753a0fc81f17e8d3e0fb2e0d3628cf35e20c38d18906",
+            hex::encode(&output[..])
+        );
+
+        scrypt(b"password", b"NaCl", 1024, 8, 16, &mut output);
+        assert_eq!(
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b373162\
+             2eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640",
+            hex::encode(&output[..])
+        );
+    }
 }
@@ -1,8 +1,14 @@
 use crate::no_std::*;
 
+pub mod base58;
+pub mod bech32m;
+
 #[cfg_attr(test, macro_use)]
 pub mod crypto;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
 pub fn to_hex_string(bytes: &[u8]) -> String {
     bytes
         .iter()
@@ -0,0 +1,26 @@
+//!
+//! Test Utilities
+//!
+//! This module provides property test helpers shared by the wallet crates.
+//!
+
+use crate::no_std::*;
+
+use core::{fmt, str::FromStr};
+use proptest::{prop_assert_eq, test_runner::TestCaseError};
+
+/// Checks that the given string, if it parses to a value, parses again from the display of
+/// that value to an equal value. A string that does not parse passes the check.
+pub fn test_from_str_round_trip<T>(input: &str) -> Result<(), TestCaseError>
+where
+    T: FromStr + fmt::Display + fmt::Debug + PartialEq,
+{
+    if let Ok(value) = T::from_str(input) {
+        let displayed = value.to_string();
+        match T::from_str(&displayed) {
+            Ok(parsed) => prop_assert_eq!(value, parsed),
+            Err(_) => return Err(TestCaseError::fail(format!("{} does not parse", displayed))),
+        }
+    }
+    Ok(())
+}
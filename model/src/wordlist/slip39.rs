@@ -0,0 +1 @@
+pub const ENGLISH: &str = include_str!("./slip39/english.txt");
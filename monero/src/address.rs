@@ -2,12 +2,12 @@ use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
 use crate::private_key::MoneroPrivateKey;
 use crate::public_key::MoneroPublicKey;
+use wagyu_model::crypto::keccak256;
 use wagyu_model::no_std::{vec, String, ToString};
 use wagyu_model::{Address, AddressError, PrivateKey};
 
 use base58_monero as base58;
 use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
-use tiny_keccak::keccak256;
 
 /// Represents a Monero address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -105,14 +105,33 @@ impl<N: MoneroNetwork> MoneroAddress<N> {
         })
     }
 
+    /// Returns the integrated address of the Monero address with the given payment ID,
+    /// or an error if the address is a subaddress.
+    pub fn new_integrated(&self, payment_id: [u8; 8]) -> Result<Self, AddressError> {
+        Self::generate_address(&self.to_public_key()?, &MoneroFormat::Integrated(payment_id))
+    }
+
     /// Returns the payment ID of a Monero integrated address, or returns `None`.
-    pub fn to_payment_id(&self) -> Option<String> {
-        if let Ok(format) = self.format() {
-            if let MoneroFormat::Integrated(payment_id) = format {
-                return Some(hex::encode(payment_id));
-            }
+    pub fn decode_payment_id(&self) -> Option<[u8; 8]> {
+        match self.format() {
+            Ok(MoneroFormat::Integrated(payment_id)) => Some(payment_id),
+            _ => None,
         }
-        None
+    }
+
+    /// Returns the hex-encoded payment ID of a Monero integrated address, or returns `None`.
+    pub fn to_payment_id(&self) -> Option<String> {
+        self.decode_payment_id().map(hex::encode)
+    }
+
+    /// Returns `true` if the Monero address is an integrated address.
+    pub fn is_integrated(&self) -> bool {
+        matches!(self.format(), Ok(MoneroFormat::Integrated(_)))
+    }
+
+    /// Returns `true` if the Monero address is a subaddress.
+    pub fn is_subaddress(&self) -> bool {
+        matches!(self.format(), Ok(MoneroFormat::Subaddress(_, _)))
     }
 
     /// Returns the format of the Monero address.
@@ -187,6 +206,7 @@ impl<N: MoneroNetwork> fmt::Display for MoneroAddress<N> {
 mod tests {
     use super::*;
     use crate::network::*;
+    use crate::payment_id::payment_id_from_hex;
     use wagyu_model::public_key::PublicKey;
 
     fn test_from_private_key<N: MoneroNetwork>(
@@ -341,6 +361,38 @@ mod tests {
             });
         }
 
+        #[test]
+        fn to_payment_id() {
+            KEYPAIRS.iter().for_each(|(_, expected_payment_id, address)| {
+                let address = MoneroAddress::<N>::from_str(address).unwrap();
+                assert!(address.is_integrated());
+                assert!(!address.is_subaddress());
+                assert_eq!(Some(expected_payment_id.to_string()), address.to_payment_id());
+            });
+        }
+
+        #[test]
+        fn new_integrated() {
+            KEYPAIRS.iter().for_each(|(seed, payment_id, expected_address)| {
+                let private_key = MoneroPrivateKey::<N>::from_seed(seed, &MoneroFormat::Standard).unwrap();
+                let standard = MoneroAddress::<N>::from_private_key(&private_key, &MoneroFormat::Standard).unwrap();
+                assert_eq!(None, standard.decode_payment_id());
+
+                let payment_id = payment_id_from_hex(payment_id).unwrap();
+                let address = standard.new_integrated(payment_id).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+                assert_eq!(Some(payment_id), address.decode_payment_id());
+
+                // The payment ID of an integrated address is replaced.
+                let address = address.new_integrated([0u8; 8]).unwrap();
+                assert_eq!(Some([0u8; 8]), address.decode_payment_id());
+                assert_eq!(
+                    standard.to_public_key().unwrap().to_public_view_key(),
+                    address.to_public_key().unwrap().to_public_view_key()
+                );
+            });
+        }
+
         #[test]
         fn to_str() {
             KEYPAIRS.iter().for_each(|(_, _, expected_address)| {
@@ -414,6 +466,25 @@ mod tests {
             });
         }
 
+        #[test]
+        fn is_subaddress() {
+            KEYPAIRS.iter().for_each(|(_, major, minor, address)| {
+                let address = MoneroAddress::<N>::from_str(address).unwrap();
+                // The (0, 0) subaddress is the standard address of the account.
+                assert_eq!(*major != 0 || *minor != 0, address.is_subaddress());
+                assert!(!address.is_integrated());
+                assert_eq!(None, address.to_payment_id());
+            });
+        }
+
+        #[test]
+        fn new_integrated() {
+            KEYPAIRS.iter().skip(1).for_each(|(_, _, _, address)| {
+                let address = MoneroAddress::<N>::from_str(address).unwrap();
+                assert!(address.new_integrated([0u8; 8]).is_err());
+            });
+        }
+
         #[test]
         fn to_str() {
             KEYPAIRS.iter().for_each(|(_, _, _, expected_address)| {
@@ -474,6 +545,11 @@ mod tests {
             .unwrap();
             let address = MoneroFormat::from_address(&address);
             assert!(address.is_err());
+
+            assert!(MoneroFormat::from_address(&[]).is_err());
+            assert!(MoneroFormat::from_address(&[18u8; 77]).is_err());
+            assert!(MoneroFormat::from_address(&[19u8; 69]).is_err());
+            assert!(MoneroFormat::from_address(&[42u8; 77]).is_err());
         }
 
         #[test]
@@ -0,0 +1,764 @@
+//!
+//! Ed25519 Curve Arithmetic
+//!
+//! This module wraps the scalars and points of curve25519-dalek in the types Monero key
+//! derivation works with, and implements the Monero hash functions onto the curve,
+//! `hash_to_scalar` (Hs) and `hash_to_point` (Hp).
+//!
+
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+
+use core::{
+    fmt,
+    ops::{Add, Mul, Neg, Sub},
+    str::FromStr,
+};
+use curve25519_dalek::{
+    constants::{ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_TABLE},
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar as DalekScalar,
+    traits::{Identity, IsIdentity},
+};
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum CurveError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "invalid point encoding: {}", _0)]
+    InvalidPoint(String),
+
+    #[fail(display = "non-canonical scalar encoding: {}", _0)]
+    NonCanonicalScalar(String),
+}
+
+impl From<hex::FromHexError> for CurveError {
+    fn from(error: hex::FromHexError) -> Self {
+        CurveError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+/// Represents a scalar modulo the order of the ed25519 basepoint, l = 2^252 + 27742317777372353535851937790883648493
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Scalar(DalekScalar);
+
+impl Scalar {
+    /// Returns the scalar zero.
+    pub fn zero() -> Self {
+        Scalar(DalekScalar::zero())
+    }
+
+    /// Returns the scalar one.
+    pub fn one() -> Self {
+        Scalar(DalekScalar::one())
+    }
+
+    /// Returns the scalar of the given canonical little-endian encoding, which must be less than l.
+    pub fn from_canonical_bytes(bytes: [u8; 32]) -> Result<Self, CurveError> {
+        match DalekScalar::from_canonical_bytes(bytes) {
+            Some(scalar) => Ok(Scalar(scalar)),
+            None => Err(CurveError::NonCanonicalScalar(hex::encode(bytes))),
+        }
+    }
+
+    /// Returns the scalar of the given little-endian integer reduced modulo l (sc_reduce32).
+    pub fn from_bytes_mod_order(bytes: [u8; 32]) -> Self {
+        Scalar(DalekScalar::from_bytes_mod_order(bytes))
+    }
+
+    /// Returns the scalar of the given 64-byte little-endian integer reduced modulo l (sc_reduce).
+    pub fn from_bytes_mod_order_wide(bytes: &[u8; 64]) -> Self {
+        Scalar(DalekScalar::from_bytes_mod_order_wide(bytes))
+    }
+
+    /// Returns the canonical little-endian encoding of the scalar.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Returns the hex-encoded canonical encoding of the scalar.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Returns the scalar multiplied by the ed25519 basepoint.
+    pub fn mul_base(&self) -> Point {
+        Point(&self.0 * &ED25519_BASEPOINT_TABLE)
+    }
+
+    /// Returns the multiplicative inverse of the scalar, or zero for the scalar zero.
+    pub fn invert(&self) -> Self {
+        Scalar(self.0.invert())
+    }
+}
+
+impl FromStr for Scalar {
+    type Err = CurveError;
+
+    /// Returns the scalar of the given hex-encoded canonical encoding.
+    fn from_str(scalar: &str) -> Result<Self, Self::Err> {
+        Self::from_canonical_bytes(to_array(&hex::decode(scalar)?)?)
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl Add for Scalar {
+    type Output = Scalar;
+
+    fn add(self, other: Scalar) -> Scalar {
+        Scalar(self.0 + other.0)
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Scalar;
+
+    fn sub(self, other: Scalar) -> Scalar {
+        Scalar(self.0 - other.0)
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Scalar;
+
+    fn mul(self, other: Scalar) -> Scalar {
+        Scalar(self.0 * other.0)
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Scalar;
+
+    fn neg(self) -> Scalar {
+        Scalar(-self.0)
+    }
+}
+
+/// Represents a point on the ed25519 curve
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Point(EdwardsPoint);
+
+impl Point {
+    /// Returns the identity point.
+    pub fn identity() -> Self {
+        Point(EdwardsPoint::identity())
+    }
+
+    /// Returns the ed25519 basepoint G.
+    pub fn basepoint() -> Self {
+        Point(ED25519_BASEPOINT_POINT)
+    }
+
+    /// Returns the point of the given compressed encoding, which must be canonical.
+    pub fn from_bytes(bytes: [u8; 32]) -> Result<Self, CurveError> {
+        match CompressedEdwardsY(bytes).decompress() {
+            // A y-coordinate of at least p or a negative x-coordinate of zero decompresses
+            // to a point with a different canonical encoding.
+            Some(point) if point.compress().to_bytes() == bytes => Ok(Point(point)),
+            _ => Err(CurveError::InvalidPoint(hex::encode(bytes))),
+        }
+    }
+
+    /// Returns the compressed encoding of the point.
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.compress().to_bytes()
+    }
+
+    /// Returns the hex-encoded compressed encoding of the point.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Returns the point multiplied by the cofactor 8.
+    pub fn mul_by_cofactor(&self) -> Self {
+        Point(self.0.mul_by_cofactor())
+    }
+
+    /// Returns `true` if the point is the identity point.
+    pub fn is_identity(&self) -> bool {
+        self.0.is_identity()
+    }
+
+    /// Returns `true` if the point is in the prime-order subgroup generated by the basepoint.
+    pub fn is_torsion_free(&self) -> bool {
+        self.0.is_torsion_free()
+    }
+}
+
+impl FromStr for Point {
+    type Err = CurveError;
+
+    /// Returns the point of the given hex-encoded compressed encoding.
+    fn from_str(point: &str) -> Result<Self, Self::Err> {
+        Self::from_bytes(to_array(&hex::decode(point)?)?)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point(self.0 + other.0)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point(self.0 - other.0)
+    }
+}
+
+impl Mul<Scalar> for Point {
+    type Output = Point;
+
+    fn mul(self, scalar: Scalar) -> Point {
+        Point(self.0 * scalar.0)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point(-self.0)
+    }
+}
+
+/// Returns the Monero hash of the given data to a scalar, Hs(data) = sc_reduce32(keccak256(data)).
+pub fn hash_to_scalar(data: &[u8]) -> Scalar {
+    Scalar::from_bytes_mod_order(keccak256(data))
+}
+
+/// Returns the Monero hash of the given data to a point of the prime-order subgroup,
+/// Hp(data) = 8 * ge_fromfe_frombytes_vartime(keccak256(data)), as used for key images.
+pub fn hash_to_point(data: &[u8]) -> Point {
+    let (x, y) = ge_fromfe_frombytes(&keccak256(data));
+    let mut bytes = y.to_bytes();
+    bytes[31] |= (x.is_negative() as u8) << 7;
+    let point = CompressedEdwardsY(bytes)
+        .decompress()
+        .expect("the mapped point is on the curve");
+    Point(point).mul_by_cofactor()
+}
+
+/// Returns the affine coordinates of the point the given field element maps to, following
+/// `ge_fromfe_frombytes_vartime` of the Monero source tree.
+fn ge_fromfe_frombytes(bytes: &[u8; 32]) -> (FieldElement, FieldElement) {
+    let a = FieldElement::from_u64(486662);
+    let sqrt_m1 = FieldElement::sqrt_m1();
+    let a_a_2 = &a * &(&a + &FieldElement::from_u64(2));
+    let two = FieldElement::from_u64(2);
+    let sqrt = |value: &FieldElement| value.sqrt().expect("the constant is a square");
+
+    let u = FieldElement::from_bytes_wide(bytes);
+    let v = &two * &u.square();
+    let w = &v + &FieldElement::one();
+    let mut x = &w.square() - &(&(&two * &a.square()) * &u.square());
+    let mut r_x = w.div_pow_m1(&x);
+    let mut z = -&a;
+
+    let negative = {
+        x = &r_x.square() * &x;
+        if !(&w - &x).is_zero() {
+            if !(&w + &x).is_zero() {
+                true
+            } else {
+                r_x = &r_x * &sqrt(&-&(&two * &a_a_2));
+                false
+            }
+        } else {
+            r_x = &r_x * &sqrt(&(&two * &a_a_2));
+            false
+        }
+    };
+
+    let sign = match negative {
+        false => {
+            // r_x = u * sqrt(2 * A * (A + 2) * w / x), z = -2 * A * u^2
+            r_x = &r_x * &u;
+            z = &z * &v;
+            false
+        }
+        true => {
+            // r_x = sqrt(A * (A + 2) * w / x), z = -A
+            x = &x * &sqrt_m1;
+            match (&w - &x).is_zero() {
+                false => r_x = &r_x * &sqrt(&-&(&sqrt_m1 * &a_a_2)),
+                true => r_x = &r_x * &sqrt(&(&sqrt_m1 * &a_a_2)),
+            };
+            true
+        }
+    };
+
+    if r_x.is_negative() != sign {
+        r_x = -&r_x;
+    }
+
+    // The projective point is (r_x * (z + w) : z - w : z + w).
+    let y = &(&z - &w) * &(&z + &w).invert();
+    (r_x, y)
+}
+
+/// Returns the given bytes as a 32-byte array.
+fn to_array(bytes: &[u8]) -> Result<[u8; 32], CurveError> {
+    if bytes.len() != 32 {
+        return Err(CurveError::InvalidByteLength(bytes.len()));
+    }
+    let mut array = [0u8; 32];
+    array.copy_from_slice(bytes);
+    Ok(array)
+}
+
+/// The mask of a 51-bit limb.
+const LOW_51_BITS: u64 = (1 << 51) - 1;
+
+/// Represents an element of the field of integers modulo p = 2^255 - 19, in five 51-bit limbs
+#[derive(Debug, Clone)]
+struct FieldElement([u64; 5]);
+
+impl FieldElement {
+    /// Returns the field element zero.
+    fn zero() -> Self {
+        FieldElement([0; 5])
+    }
+
+    /// Returns the field element one.
+    fn one() -> Self {
+        Self::from_u64(1)
+    }
+
+    /// Returns the field element of the given integer.
+    fn from_u64(value: u64) -> Self {
+        FieldElement([value, 0, 0, 0, 0]).carry()
+    }
+
+    /// Returns the square root of -1, 2^((p - 1) / 4).
+    fn sqrt_m1() -> Self {
+        // (p - 1) / 4 = 2^253 - 5
+        let mut exponent = [0xffu8; 32];
+        exponent[0] = 0xfb;
+        exponent[31] = 0x1f;
+        Self::from_u64(2).pow(&exponent)
+    }
+
+    /// Returns the field element of the given 256-bit little-endian integer, reduced modulo p.
+    fn from_bytes_wide(bytes: &[u8; 32]) -> Self {
+        let load = |i: usize| {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[i..i + 8]);
+            u64::from_le_bytes(word)
+        };
+        let high = bytes[31] >> 7;
+        let mut limbs = [
+            load(0) & LOW_51_BITS,
+            (load(6) >> 3) & LOW_51_BITS,
+            (load(12) >> 6) & LOW_51_BITS,
+            (load(19) >> 1) & LOW_51_BITS,
+            (load(24) >> 12) & LOW_51_BITS,
+        ];
+        // 2^255 = 19 mod p
+        limbs[0] += 19 * high as u64;
+        FieldElement(limbs).carry()
+    }
+
+    /// Returns the canonical little-endian encoding of the field element.
+    fn to_bytes(&self) -> [u8; 32] {
+        let mut limbs = self.carry().0;
+
+        // Subtract p if the element is at least p, i.e. if the element plus 19 overflows 2^255.
+        let mut q = (limbs[0] + 19) >> 51;
+        for limb in limbs.iter().skip(1) {
+            q = (limb + q) >> 51;
+        }
+        limbs[0] += 19 * q;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= LOW_51_BITS;
+        }
+        limbs[4] &= LOW_51_BITS;
+
+        let mut bytes = [0u8; 32];
+        let mut accumulator: u128 = 0;
+        let mut bits = 0;
+        let mut index = 0;
+        for limb in limbs.iter() {
+            accumulator |= (*limb as u128) << bits;
+            bits += 51;
+            while bits >= 8 && index < 32 {
+                bytes[index] = accumulator as u8;
+                accumulator >>= 8;
+                bits -= 8;
+                index += 1;
+            }
+        }
+        if index < 32 {
+            bytes[index] = accumulator as u8;
+        }
+        bytes
+    }
+
+    /// Returns the field element with each limb carried into the next, so that each limb is at most 52 bits.
+    fn carry(&self) -> Self {
+        let mut limbs = self.0;
+        for i in 0..4 {
+            limbs[i + 1] += limbs[i] >> 51;
+            limbs[i] &= LOW_51_BITS;
+        }
+        limbs[0] += 19 * (limbs[4] >> 51);
+        limbs[4] &= LOW_51_BITS;
+        FieldElement(limbs)
+    }
+
+    /// Returns `true` if the field element is zero.
+    fn is_zero(&self) -> bool {
+        self.to_bytes() == [0u8; 32]
+    }
+
+    /// Returns `true` if the canonical encoding of the field element is odd.
+    fn is_negative(&self) -> bool {
+        self.to_bytes()[0] & 1 == 1
+    }
+
+    /// Returns the square of the field element.
+    fn square(&self) -> Self {
+        self * self
+    }
+
+    /// Returns the field element raised to the given 256-bit little-endian exponent.
+    fn pow(&self, exponent: &[u8; 32]) -> Self {
+        let mut result = Self::one();
+        for byte in exponent.iter().rev() {
+            for bit in (0..8).rev() {
+                result = result.square();
+                if (byte >> bit) & 1 == 1 {
+                    result = &result * self;
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns the multiplicative inverse of the field element, self^(p - 2).
+    fn invert(&self) -> Self {
+        // p - 2 = 2^255 - 21
+        let mut exponent = [0xffu8; 32];
+        exponent[0] = 0xeb;
+        exponent[31] = 0x7f;
+        self.pow(&exponent)
+    }
+
+    /// Returns self * v^3 * (self * v^7)^((p - 5) / 8), following `fe_divpowm1`.
+    fn div_pow_m1(&self, v: &Self) -> Self {
+        // (p - 5) / 8 = 2^252 - 3
+        let mut exponent = [0xffu8; 32];
+        exponent[0] = 0xfd;
+        exponent[31] = 0x0f;
+        let v3 = &v.square() * v;
+        let v7 = &v3.square() * v;
+        &(self * &v3) * &(self * &v7).pow(&exponent)
+    }
+
+    /// Returns a square root of the field element, if the field element is a square.
+    fn sqrt(&self) -> Option<Self> {
+        // (p + 3) / 8 = 2^252 - 2
+        let mut exponent = [0xffu8; 32];
+        exponent[0] = 0xfe;
+        exponent[31] = 0x0f;
+        let root = self.pow(&exponent);
+        if (&root.square() - self).is_zero() {
+            Some(root)
+        } else if (&root.square() + self).is_zero() {
+            Some(&root * &Self::sqrt_m1())
+        } else {
+            None
+        }
+    }
+}
+
+impl Add<&FieldElement> for &FieldElement {
+    type Output = FieldElement;
+
+    fn add(self, other: &FieldElement) -> FieldElement {
+        let mut limbs = self.0;
+        for (limb, other) in limbs.iter_mut().zip(other.0.iter()) {
+            *limb += other;
+        }
+        FieldElement(limbs).carry()
+    }
+}
+
+impl Sub<&FieldElement> for &FieldElement {
+    type Output = FieldElement;
+
+    fn sub(self, other: &FieldElement) -> FieldElement {
+        // Add 16 * p to keep each limb positive, as each limb of the subtrahend is at most 52 bits.
+        let other = other.carry().0;
+        FieldElement([
+            (self.0[0] + 36028797018963664) - other[0],
+            (self.0[1] + 36028797018963952) - other[1],
+            (self.0[2] + 36028797018963952) - other[2],
+            (self.0[3] + 36028797018963952) - other[3],
+            (self.0[4] + 36028797018963952) - other[4],
+        ])
+        .carry()
+    }
+}
+
+impl Mul<&FieldElement> for &FieldElement {
+    type Output = FieldElement;
+
+    fn mul(self, other: &FieldElement) -> FieldElement {
+        let (a, b) = (self.carry().0, other.carry().0);
+        let m = |x: u64, y: u64| x as u128 * y as u128;
+        let (b1, b2, b3, b4) = (b[1] * 19, b[2] * 19, b[3] * 19, b[4] * 19);
+
+        let c0 = m(a[0], b[0]) + m(a[1], b4) + m(a[2], b3) + m(a[3], b2) + m(a[4], b1);
+        let mut c1 = m(a[0], b[1]) + m(a[1], b[0]) + m(a[2], b4) + m(a[3], b3) + m(a[4], b2);
+        let mut c2 = m(a[0], b[2]) + m(a[1], b[1]) + m(a[2], b[0]) + m(a[3], b4) + m(a[4], b3);
+        let mut c3 = m(a[0], b[3]) + m(a[1], b[2]) + m(a[2], b[1]) + m(a[3], b[0]) + m(a[4], b4);
+        let mut c4 = m(a[0], b[4]) + m(a[1], b[3]) + m(a[2], b[2]) + m(a[3], b[1]) + m(a[4], b[0]);
+
+        c1 += c0 >> 51;
+        c2 += c1 >> 51;
+        c3 += c2 >> 51;
+        c4 += c3 >> 51;
+        let carry = (c4 >> 51) as u64;
+
+        FieldElement([
+            (c0 as u64 & LOW_51_BITS) + carry * 19,
+            c1 as u64 & LOW_51_BITS,
+            c2 as u64 & LOW_51_BITS,
+            c3 as u64 & LOW_51_BITS,
+            c4 as u64 & LOW_51_BITS,
+        ])
+        .carry()
+    }
+}
+
+impl Neg for &FieldElement {
+    type Output = FieldElement;
+
+    fn neg(self) -> FieldElement {
+        &FieldElement::zero() - self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASEPOINT: &str = "5866666666666666666666666666666666666666666666666666666666666666";
+    /// The order l of the basepoint
+    const ORDER: &str = "edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+    /// The order l of the basepoint minus one
+    const ORDER_MINUS_ONE: &str = "ecd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010";
+
+    #[test]
+    fn scalar() {
+        assert_eq!(Point::basepoint(), Scalar::one().mul_base());
+        assert_eq!(BASEPOINT, Scalar::one().mul_base().to_string());
+        assert!(Scalar::zero().mul_base().is_identity());
+
+        let order_minus_one = Scalar::from_str(ORDER_MINUS_ONE).unwrap();
+        assert_eq!(ORDER_MINUS_ONE, order_minus_one.to_string());
+        assert_eq!(Scalar::zero(), order_minus_one + Scalar::one());
+        assert_eq!(-Scalar::one(), order_minus_one);
+        assert_eq!(Scalar::one(), order_minus_one * order_minus_one);
+        assert_eq!(Scalar::one(), order_minus_one * order_minus_one.invert());
+
+        let mut order = [0u8; 32];
+        order.copy_from_slice(&hex::decode(ORDER).unwrap());
+        assert_eq!(Scalar::zero(), Scalar::from_bytes_mod_order(order));
+    }
+
+    #[test]
+    fn non_canonical_scalar() {
+        [
+            ORDER,
+            "edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000020",
+        ]
+        .iter()
+        .for_each(|scalar| match Scalar::from_str(scalar) {
+            Err(CurveError::NonCanonicalScalar(found)) => assert_eq!(*scalar, found),
+            result => panic!("unexpected result: {:?}", result),
+        });
+        assert_eq!(
+            Err(CurveError::InvalidByteLength(31)),
+            Scalar::from_str(&"00".repeat(31))
+        );
+        assert!(Scalar::from_str("zz").is_err());
+    }
+
+    #[test]
+    fn key_pair() {
+        // (private spend key, private view key, public spend key, public view key) of a Monero wallet
+        let (private_spend, private_view, public_spend, public_view) = (
+            "3eb8e283b45559d4d2fb6b3a4f52443b420e6da2b38832ea0eb642100c92d600",
+            "5177c436f032666c572df97ab591cc6ac2da96ab6818a2f38d72b430aebbdc0a",
+            "b9c5610a07f4344b27625155614fb1341dd0392c68482f101b820bc1e2b908e5",
+            "0df7c88054ae3c5f75c364257d064f42d660e6ea1184bd2a3af0d7455cb4e9ee",
+        );
+        let private_spend = Scalar::from_str(private_spend).unwrap();
+        let private_view = Scalar::from_str(private_view).unwrap();
+        assert_eq!(private_view, super::hash_to_scalar(&private_spend.to_bytes()));
+        assert_eq!(public_spend, private_spend.mul_base().to_string());
+        assert_eq!(public_view, private_view.mul_base().to_string());
+        assert_eq!(
+            Point::from_str(public_spend).unwrap() + Point::from_str(public_view).unwrap(),
+            (private_spend + private_view).mul_base()
+        );
+        assert_eq!(
+            Point::from_str(public_spend).unwrap() * private_view,
+            Point::from_str(public_view).unwrap() * private_spend
+        );
+    }
+
+    #[test]
+    fn point() {
+        assert_eq!(Point::basepoint(), Point::from_str(BASEPOINT).unwrap());
+        assert_eq!(
+            Point::identity(),
+            Point::from_str(&format!("01{}", "00".repeat(31))).unwrap()
+        );
+        assert_eq!(Point::identity(), Point::basepoint() - Point::basepoint());
+        assert_eq!(Point::identity(), Point::basepoint() + -Point::basepoint());
+        assert!(Point::basepoint().is_torsion_free());
+
+        // The generator H of the Pedersen commitments of RingCT, 8 * decompress(keccak256(G))
+        let h = Point(
+            CompressedEdwardsY(keccak256(&Point::basepoint().to_bytes()))
+                .decompress()
+                .unwrap(),
+        );
+        assert_eq!(
+            "8b655970153799af2aeadc9ff1add0ea6c7251d54154cfa92c173a0dd39c1f94",
+            h.mul_by_cofactor().to_string()
+        );
+    }
+
+    #[test]
+    fn non_canonical_point() {
+        [
+            // y = p, which decompresses to the point y = 0
+            "edffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+            // y = 1 with the sign bit set, the negative zero x-coordinate
+            "0100000000000000000000000000000000000000000000000000000000000080",
+            // y = 2, which is not on the curve
+            "0200000000000000000000000000000000000000000000000000000000000000",
+        ]
+        .iter()
+        .for_each(|point| match Point::from_str(point) {
+            Err(CurveError::InvalidPoint(found)) => assert_eq!(*point, found),
+            result => panic!("unexpected result: {:?}", result),
+        });
+        assert_eq!(
+            Err(CurveError::InvalidByteLength(33)),
+            Point::from_str(&"00".repeat(33))
+        );
+    }
+
+    #[test]
+    fn hash_to_scalar() {
+        assert_eq!(Scalar::from_bytes_mod_order(keccak256(b"")), super::hash_to_scalar(b""));
+        // keccak256("") is above l, so it is reduced.
+        assert_ne!(keccak256(b""), super::hash_to_scalar(b"").to_bytes());
+    }
+
+    #[test]
+    fn hash_to_point() {
+        // (public key, hash_to_ec of the public key)
+        // https://github.com/monero-project/monero/blob/master/tests/crypto/tests.txt
+        [
+            (
+                "da66e9ba613919dec28ef367a125bb310d6d83fb9052e71034164b6dc4f392d0",
+                "52b3f38753b4e13b74624862e253072cf12f745d43fcfafbe8c217701a6e5875",
+            ),
+            (
+                "a7fbdeeccb597c2d5fdaf2ea2e10cbfcd26b5740903e7f6d46bcbf9a90384fc6",
+                "f055ba2d0d9828ce2e203d9896bfda494d7830e7e3a27fa27d5eaa825a79a19c",
+            ),
+            (
+                "ed6e6579368caba2cc4851672972e949c0ee586fee4d6d6a9476d4a908f64070",
+                "da3ceda9a2ef6316bf9272566e6dffd785ac71f57855c0202f422bbb86af4ec0",
+            ),
+        ]
+        .iter()
+        .for_each(|(public_key, expected)| {
+            let point = super::hash_to_point(&hex::decode(public_key).unwrap());
+            assert_eq!(*expected, point.to_string());
+            assert!(point.is_torsion_free());
+            assert!(!point.is_identity());
+        });
+
+        (0u8..64).for_each(|i| assert!(super::hash_to_point(&[i]).is_torsion_free()));
+    }
+
+    #[test]
+    fn ge_fromfe_frombytes() {
+        // (hash, ge_fromfe_frombytes_vartime of the hash)
+        // https://github.com/monero-project/monero/blob/master/tests/crypto/tests.txt
+        [
+            (
+                "83efb774657700e37291f4b8dd10c839d1c739fd135c07a2fd7382334dafdd6a",
+                "2789ecbaf36e4fcb41c6157228001538b40ca379464b718d830c58caae7ea4ca",
+            ),
+            (
+                "5c380f98794ab7a9be7c2d3259b92772125ce93527be6a76210631fdd8001498",
+                "31a1feb4986d42e2137ae061ea031838d24fa523234954cf8860bcd42421ae94",
+            ),
+            (
+                "4775d39f91a466262f0ccf21f5a7ee446f79a05448861e212be063a1063298f0",
+                "897b3589f29ea40e576a91506d9aeca4c05a494922a80de57276f4b40c0a98bc",
+            ),
+        ]
+        .iter()
+        .for_each(|(hash, expected)| {
+            let (x, y) = super::ge_fromfe_frombytes(&to_array(&hex::decode(hash).unwrap()).unwrap());
+            let mut point = y.to_bytes();
+            point[31] |= (x.is_negative() as u8) << 7;
+            assert_eq!(*expected, hex::encode(point));
+        });
+    }
+
+    #[test]
+    fn field_element() {
+        let p_minus_one = {
+            let mut bytes = [0xffu8; 32];
+            bytes[0] = 0xec;
+            bytes[31] = 0x7f;
+            bytes
+        };
+        let minus_one = FieldElement::from_bytes_wide(&p_minus_one);
+        assert_eq!(p_minus_one, (-&FieldElement::one()).to_bytes());
+        assert!((&minus_one + &FieldElement::one()).is_zero());
+        assert!((&FieldElement::sqrt_m1().square() - &minus_one).is_zero());
+        // 2^256 - 1 = 2 * 19 - 1 mod p
+        assert_eq!(
+            FieldElement::from_u64(37).to_bytes(),
+            FieldElement::from_bytes_wide(&[0xff; 32]).to_bytes()
+        );
+
+        let two = FieldElement::from_u64(2);
+        assert!((&(&two * &two.invert()) - &FieldElement::one()).is_zero());
+        let four = &two * &two;
+        assert!((&four.sqrt().unwrap().square() - &four).is_zero());
+        assert!(two.sqrt().is_none());
+    }
+}
@@ -24,16 +24,30 @@ impl MoneroFormat {
     }
 
     /// Returns the format of the given address.
+    /// The decoded address must be 69 bytes for standard addresses and subaddresses,
+    /// and 77 bytes for integrated addresses.
     pub fn from_address(address: &[u8]) -> Result<Self, AddressError> {
-        match address[0] {
-            18 | 24 | 53 => Ok(MoneroFormat::Standard),
+        if address.is_empty() {
+            return Err(AddressError::InvalidByteLength(0));
+        }
+
+        let (format, length) = match address[0] {
+            18 | 24 | 53 => (MoneroFormat::Standard, 69),
             19 | 25 | 54 => {
+                if address.len() != 77 {
+                    return Err(AddressError::InvalidByteLength(address.len()));
+                }
                 let mut data = [0u8; 8];
                 data.copy_from_slice(&address[65..73]);
-                Ok(MoneroFormat::Integrated(data))
+                (MoneroFormat::Integrated(data), 77)
             }
-            42 | 36 | 63 => Ok(MoneroFormat::Subaddress(u32::max_value(), u32::max_value())),
+            42 | 36 | 63 => (MoneroFormat::Subaddress(u32::max_value(), u32::max_value()), 69),
             _ => return Err(AddressError::InvalidPrefix(vec![address[0]])),
+        };
+
+        match address.len() == length {
+            true => Ok(format),
+            false => Err(AddressError::InvalidByteLength(address.len())),
         }
     }
 }
@@ -13,6 +13,9 @@ pub use self::address::*;
 pub mod amount;
 pub use self::amount::*;
 
+pub mod curve;
+pub use self::curve::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
@@ -25,6 +28,9 @@ pub use self::network::*;
 pub mod one_time_key;
 pub use self::one_time_key::*;
 
+pub mod payment_id;
+pub use self::payment_id::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
@@ -36,5 +42,8 @@ pub mod transaction;
 #[cfg(transaction)]
 pub use self::transaction::*;
 
+pub mod view_only;
+pub use self::view_only::*;
+
 pub mod wordlist;
 pub use self::wordlist::*;
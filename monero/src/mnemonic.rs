@@ -44,13 +44,15 @@ impl<N: MoneroNetwork, W: MoneroWordlist> Mnemonic for MoneroMnemonic<N, W> {
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
         let length = 1626;
-        let words = phrase.split(" ").collect::<Vec<&str>>();
+        let words = phrase.split_whitespace().collect::<Vec<&str>>();
         let mut phrase = words.iter().map(|word| word.to_string()).collect::<Vec<String>>();
 
         if phrase.len() % 3 == 2 {
             return Err(MnemonicError::MissingWord);
         } else if phrase.len() % 3 == 0 {
             return Err(MnemonicError::MissingChecksumWord);
+        } else if phrase.len() != 25 {
+            return Err(MnemonicError::InvalidWordCount(phrase.len()));
         }
 
         let checksum = match phrase.pop() {
@@ -156,6 +158,11 @@ impl<N: MoneroNetwork, W: MoneroWordlist> MoneroMnemonic<N, W> {
         }
     }
 
+    /// Returns the private spend key of the corresponding mnemonic.
+    pub fn to_private_spend_key(&self) -> [u8; 32] {
+        Scalar::from_bytes_mod_order(self.seed).to_bytes()
+    }
+
     /// Compares the given phrase against the phrase extracted from its entropy.
     pub fn verify_phrase(phrase: &str) -> bool {
         Self::from_phrase(phrase).is_ok()
@@ -315,5 +322,53 @@ mod tests {
                     test_to_private_key::<N, W>(expected_private_spend_key, expected_private_view_key, phrase);
                 });
         }
+
+        #[test]
+        fn to_private_spend_key() {
+            KEYPAIRS
+                .iter()
+                .for_each(|(_, phrase, (expected_private_spend_key, _))| {
+                    let mnemonic = MoneroMnemonic::<N, W>::from_phrase(phrase).unwrap();
+                    let private_spend_key = mnemonic.to_private_spend_key();
+                    assert_eq!(*expected_private_spend_key, hex::encode(private_spend_key));
+
+                    let mnemonic = MoneroMnemonic::<N, W>::from_private_spend_key(&private_spend_key);
+                    assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+                });
+        }
+
+        #[test]
+        fn to_address() {
+            // The primary wallet of the Monero functional tests
+            let phrase = "velvet lymph giddy number token physics poetry unquoted nibs useful sabotage limits benches lifestyle eden nitrogen anvil fewest avoid batch vials washing fences goat unquoted";
+            let expected_address =
+                "42ey1afDFnn4886T7196doS9GPMzexD9gXpsZJDwVjeRVdFCSoHnv7KPbBeGpzJBzHRCAs9UxqeoyFQMYbqSWYTfJJQAWDm";
+
+            let mnemonic = MoneroMnemonic::<N, W>::from_phrase(phrase).unwrap();
+            let address = mnemonic.to_address(None, &MoneroFormat::Standard).unwrap();
+            assert_eq!(expected_address, address.to_string());
+        }
+
+        #[test]
+        fn test_invalid_phrase() {
+            // Valid length modulo three, but too short to encode a 32-byte seed
+            let phrase = "reruns today hookup itself thorn nirvana symptoms jukebox patio unquoted sushi long diode";
+            assert!(MoneroMnemonic::<N, W>::from_phrase(phrase).is_err());
+
+            // Invalid checksum word
+            let phrase = "reruns today hookup itself thorn nirvana symptoms jukebox patio unquoted sushi long diode digit rewind hacksaw obvious soothe nightly return agile hobby algebra awesome today";
+            assert!(MoneroMnemonic::<N, W>::from_phrase(phrase).is_err());
+
+            // Missing checksum word
+            let phrase = "reruns today hookup itself thorn nirvana symptoms jukebox patio unquoted sushi long diode digit rewind hacksaw obvious soothe nightly return agile hobby algebra awesome";
+            assert!(MoneroMnemonic::<N, W>::from_phrase(phrase).is_err());
+
+            // Word counts beyond 255 are reported in full
+            let phrase = vec!["reruns"; 256].join(" ");
+            match MoneroMnemonic::<N, W>::from_phrase(&phrase) {
+                Err(MnemonicError::InvalidWordCount(256)) => {}
+                result => panic!("unexpected result: {:?}", result.map(|mnemonic| mnemonic.to_string())),
+            };
+        }
     }
 }
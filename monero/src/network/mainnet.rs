@@ -11,6 +11,7 @@ pub struct Mainnet;
 
 impl Network for Mainnet {
     const NAME: &'static str = "mainnet";
+    const ALIASES: &'static [&'static str] = &["main"];
 }
 
 impl MoneroNetwork for Mainnet {
@@ -38,9 +39,9 @@ impl FromStr for Mainnet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
@@ -18,3 +18,34 @@ pub trait MoneroNetwork: Network {
     /// Returns the network of the given address prefix.
     fn from_address_prefix(prefix: u8) -> Result<Self, AddressError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+    use wagyu_model::NetworkError;
+
+    fn test_from_str<N: MoneroNetwork + FromStr<Err = NetworkError>>(names: &[&str]) {
+        names.iter().for_each(|name| {
+            assert_eq!(N::NAME, N::from_str(name).unwrap().to_string());
+            assert_eq!(N::NAME, N::from_str(&name.to_uppercase()).unwrap().to_string());
+        });
+    }
+
+    #[test]
+    fn from_str() {
+        test_from_str::<Mainnet>(&["mainnet", "main"]);
+        test_from_str::<Stagenet>(&["stagenet", "stage"]);
+        test_from_str::<Testnet>(&["testnet", "test"]);
+
+        assert!(Mainnet::from_str("stagenet").is_err());
+        assert!(Testnet::from_str("stage").is_err());
+    }
+
+    #[test]
+    fn invalid_address_prefix() {
+        assert!(Mainnet::from_address_prefix(18).is_ok());
+        assert!(Mainnet::from_address_prefix(53).is_err());
+        assert!(Stagenet::from_address_prefix(0).is_err());
+    }
+}
@@ -11,6 +11,7 @@ pub struct Stagenet;
 
 impl Network for Stagenet {
     const NAME: &'static str = "stagenet";
+    const ALIASES: &'static [&'static str] = &["stage"];
 }
 
 impl MoneroNetwork for Stagenet {
@@ -38,9 +39,9 @@ impl FromStr for Stagenet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
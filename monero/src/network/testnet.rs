@@ -11,6 +11,7 @@ pub struct Testnet;
 
 impl Network for Testnet {
     const NAME: &'static str = "testnet";
+    const ALIASES: &'static [&'static str] = &["test"];
 }
 
 impl MoneroNetwork for Testnet {
@@ -38,9 +39,9 @@ impl FromStr for Testnet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
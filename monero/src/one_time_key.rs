@@ -3,13 +3,13 @@
 use crate::network::MoneroNetwork;
 use crate::private_key::MoneroPrivateKey;
 use crate::public_key::MoneroPublicKey;
+use wagyu_model::crypto::keccak256;
 use wagyu_model::no_std::{vec, String, Vec};
 use wagyu_model::{PublicKeyError, TransactionError};
 
 use core::marker::PhantomData;
 use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
 use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::EdwardsBasepointTable, scalar::Scalar};
-use tiny_keccak::keccak256;
 
 #[derive(Debug, Fail)]
 pub enum OneTimeKeyError {
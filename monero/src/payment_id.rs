@@ -0,0 +1,218 @@
+//!
+//! Payment ID
+//!
+//! This module parses the 8-byte payment IDs of integrated addresses, and encrypts them for the
+//! transaction extra field as the reference wallet does, by XOR with the first 8 bytes of
+//! keccak(8 * r * A || 0x8d) for the transaction private key r and the public view key A.
+//! The recipient decrypts the payment ID with the shared secret 8 * a * R from the private
+//! view key a and the transaction public key R.
+//!
+
+use crate::curve::{CurveError, Point, Scalar};
+use wagyu_model::crypto::keccak256;
+use wagyu_model::no_std::*;
+
+/// The byte appended to the key derivation to derive the payment ID encryption key
+pub const ENCRYPTED_PAYMENT_ID_TAIL: u8 = 0x8d;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum PaymentIdError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "{}", _0)]
+    CurveError(CurveError),
+
+    #[fail(display = "invalid payment ID byte length: {}", _0)]
+    InvalidByteLength(usize),
+
+    #[fail(display = "long payment IDs of 32 bytes are not supported by integrated addresses")]
+    LongPaymentId,
+}
+
+impl From<CurveError> for PaymentIdError {
+    fn from(error: CurveError) -> Self {
+        PaymentIdError::CurveError(error)
+    }
+}
+
+impl From<hex::FromHexError> for PaymentIdError {
+    fn from(error: hex::FromHexError) -> Self {
+        PaymentIdError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+/// Returns the 8-byte payment ID of the given hex string, or an error for a legacy 32-byte payment ID.
+pub fn payment_id_from_hex(payment_id: &str) -> Result<[u8; 8], PaymentIdError> {
+    let bytes = hex::decode(payment_id)?;
+    match bytes.len() {
+        8 => {
+            let mut payment_id = [0u8; 8];
+            payment_id.copy_from_slice(&bytes);
+            Ok(payment_id)
+        }
+        32 => Err(PaymentIdError::LongPaymentId),
+        length => Err(PaymentIdError::InvalidByteLength(length)),
+    }
+}
+
+/// Returns the payment ID encrypted for the transaction extra field, given the transaction
+/// private key and the public view key of the recipient.
+pub fn encrypt_payment_id(
+    payment_id: &[u8; 8],
+    transaction_private_key: &[u8; 32],
+    public_view_key: &[u8; 32],
+) -> Result<[u8; 8], PaymentIdError> {
+    xor_payment_id(payment_id, transaction_private_key, public_view_key)
+}
+
+/// Returns the payment ID decrypted from the transaction extra field, given the transaction
+/// public key and the private view key of the recipient.
+pub fn decrypt_payment_id(
+    encrypted_payment_id: &[u8; 8],
+    transaction_public_key: &[u8; 32],
+    private_view_key: &[u8; 32],
+) -> Result<[u8; 8], PaymentIdError> {
+    xor_payment_id(encrypted_payment_id, private_view_key, transaction_public_key)
+}
+
+/// Returns the key derivation 8 * secret * public, as `generate_key_derivation` of the reference wallet.
+fn key_derivation(secret: &[u8; 32], public: &[u8; 32]) -> Result<[u8; 32], PaymentIdError> {
+    let derivation = (Point::from_bytes(*public)? * Scalar::from_bytes_mod_order(*secret)).mul_by_cofactor();
+    Ok(derivation.to_bytes())
+}
+
+/// Returns the payment ID XOR the encryption key derived from the shared secret 8 * secret * public.
+fn xor_payment_id(payment_id: &[u8; 8], secret: &[u8; 32], public: &[u8; 32]) -> Result<[u8; 8], PaymentIdError> {
+    let mut data = key_derivation(secret, public)?.to_vec();
+    data.push(ENCRYPTED_PAYMENT_ID_TAIL);
+    let key = keccak256(&data);
+
+    let mut result = [0u8; 8];
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = payment_id[i] ^ key[i];
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::MoneroAddress;
+    use crate::network::Stagenet;
+
+    use core::str::FromStr;
+
+    fn decode(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes
+    }
+
+    // (public key, secret key, key derivation)
+    // https://github.com/monero-project/monero/blob/master/tests/crypto/tests.txt
+    const KEY_DERIVATIONS: [(&str, &str, &str); 2] = [
+        (
+            "fdfd97d2ea9f1c25df773ff2c973d885653a3ee643157eb0ae2b6dd98f0b6984",
+            "eb2bd1cf0c5e074f9dbf38ebbc99c316f54e21803048c687a3bb359f7a713b02",
+            "4e0bd2c41325a1b89a9f7413d4d05e0a5a4936f241dccc3c7d0c539ffe00ef67",
+        ),
+        (
+            "1ebf8c3c296bb91708b09d9a8e0639ccfd72556976419c7dc7e6dfd7599218b9",
+            "e49f363fd5c8fc1f8645983647ca33d7ec9db2d255d94cd538a3cc83153c5f04",
+            "72903ec8f9919dfcec6efb5535490527b573b3d77f9890386d373c02bf368934",
+        ),
+    ];
+
+    #[test]
+    fn key_derivation() {
+        KEY_DERIVATIONS.iter().for_each(|(public_key, secret_key, derivation)| {
+            assert_eq!(
+                *derivation,
+                hex::encode(super::key_derivation(&decode(secret_key), &decode(public_key)).unwrap())
+            );
+        });
+    }
+
+    #[test]
+    fn encrypt() {
+        let payment_id = payment_id_from_hex("b5a615cb2a72673e").unwrap();
+        KEY_DERIVATIONS.iter().for_each(|(public_key, secret_key, derivation)| {
+            let mut data = hex::decode(derivation).unwrap();
+            data.push(ENCRYPTED_PAYMENT_ID_TAIL);
+            let key = keccak256(&data);
+
+            let encrypted = encrypt_payment_id(&payment_id, &decode(secret_key), &decode(public_key)).unwrap();
+            (0..8).for_each(|i| assert_eq!(payment_id[i] ^ key[i], encrypted[i]));
+        });
+    }
+
+    // (private key, public key) pairs of a Monero wallet, as the transaction key and the view key
+    const KEY_PAIRS: [(&str, &str); 2] = [
+        (
+            "3eb8e283b45559d4d2fb6b3a4f52443b420e6da2b38832ea0eb642100c92d600",
+            "b9c5610a07f4344b27625155614fb1341dd0392c68482f101b820bc1e2b908e5",
+        ),
+        (
+            "5177c436f032666c572df97ab591cc6ac2da96ab6818a2f38d72b430aebbdc0a",
+            "0df7c88054ae3c5f75c364257d064f42d660e6ea1184bd2a3af0d7455cb4e9ee",
+        ),
+    ];
+
+    #[test]
+    fn encrypt_decrypt() {
+        let (transaction_private_key, transaction_public_key) = KEY_PAIRS[0];
+        let (private_view_key, public_view_key) = KEY_PAIRS[1];
+        ["b5a615cb2a72673e", "420fa29b2d9a49f5"].iter().for_each(|payment_id| {
+            let payment_id = payment_id_from_hex(payment_id).unwrap();
+            let encrypted =
+                encrypt_payment_id(&payment_id, &decode(transaction_private_key), &decode(public_view_key)).unwrap();
+            assert_ne!(payment_id, encrypted);
+
+            let decrypted =
+                decrypt_payment_id(&encrypted, &decode(transaction_public_key), &decode(private_view_key)).unwrap();
+            assert_eq!(payment_id, decrypted);
+        });
+    }
+
+    /// The make_integrated_address example of the monero-wallet-rpc documentation, on stagenet
+    /// https://www.getmonero.org/resources/developer-guides/wallet-rpc.html#make_integrated_address
+    const WALLET_RPC_STANDARD_ADDRESS: &str =
+        "55LTR8KniP4LQGJSPtbYDacR7dz8RBFnsfAKMaMuwUNYX6aQbBcovzDPyrQF9KXF9tVU6Xk3K8no1BywnJX6GvZX8yJsXvt";
+    const WALLET_RPC_PAYMENT_ID: &str = "420fa29b2d9a49f5";
+    const WALLET_RPC_INTEGRATED_ADDRESS: &str =
+        "5F38Rw9HKeaLQGJSPtbYDacR7dz8RBFnsfAKMaMuwUNYX6aQbBcovzDPyrQF9KXF9tVU6Xk3K8no1BywnJX6GvZXCkbHUXdPHyiUeRyokn";
+
+    #[test]
+    fn wallet_rpc_integrated_address() {
+        let standard_address = MoneroAddress::<Stagenet>::from_str(WALLET_RPC_STANDARD_ADDRESS).unwrap();
+        let integrated_address = MoneroAddress::<Stagenet>::from_str(WALLET_RPC_INTEGRATED_ADDRESS).unwrap();
+        assert_eq!(Some(WALLET_RPC_PAYMENT_ID.into()), integrated_address.to_payment_id());
+
+        let payment_id = payment_id_from_hex(WALLET_RPC_PAYMENT_ID).unwrap();
+        assert_eq!(integrated_address, standard_address.new_integrated(payment_id).unwrap());
+    }
+
+    #[test]
+    fn invalid_payment_id() {
+        assert_eq!(
+            Err(PaymentIdError::LongPaymentId),
+            payment_id_from_hex("b5a615cb2a72673eb5a615cb2a72673eb5a615cb2a72673eb5a615cb2a72673e")
+        );
+        assert_eq!(
+            Err(PaymentIdError::InvalidByteLength(4)),
+            payment_id_from_hex("b5a615cb")
+        );
+        assert!(payment_id_from_hex("b5a615cb2a72673g").is_err());
+    }
+
+    #[test]
+    fn invalid_public_key() {
+        let (transaction_private_key, _) = KEY_PAIRS[0];
+        let payment_id = payment_id_from_hex("b5a615cb2a72673e").unwrap();
+        // The y-coordinate 2 is not on the curve.
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        assert!(encrypt_payment_id(&payment_id, &decode(transaction_private_key), &invalid).is_err());
+    }
+}
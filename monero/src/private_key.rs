@@ -1,14 +1,14 @@
 use crate::address::MoneroAddress;
+use crate::curve::{hash_to_scalar, Scalar};
 use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
 use crate::public_key::MoneroPublicKey;
-use wagyu_model::{no_std::Vec, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use crate::view_only::MoneroViewOnlyKey;
+use wagyu_model::{no_std::String, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
 
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
-use curve25519_dalek::scalar::Scalar;
 use hex;
 use rand::Rng;
-use tiny_keccak::keccak256;
 
 /// Represents a Monero private key
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -46,7 +46,7 @@ impl<N: MoneroNetwork> PrivateKey for MoneroPrivateKey<N> {
 }
 
 impl<N: MoneroNetwork> MoneroPrivateKey<N> {
-    /// Returns a private key given seed bytes.
+    /// Returns a private key given a hex-encoded seed.
     pub fn from_seed(seed: &str, format: &MoneroFormat) -> Result<Self, PrivateKeyError> {
         let seed = hex::decode(seed)?;
         if seed.len() != 32 {
@@ -55,56 +55,49 @@ impl<N: MoneroNetwork> MoneroPrivateKey<N> {
 
         let mut s = [0u8; 32];
         s.copy_from_slice(seed.as_slice());
-        let spend_key = Scalar::from_bytes_mod_order(s).to_bytes();
-
-        let format = match format {
-            MoneroFormat::Subaddress(major, minor) if *major == 0 && *minor == 0 => MoneroFormat::Standard,
-            _ => *format,
-        };
+        Ok(Self::from_seed_bytes(s, format))
+    }
 
-        Ok(Self {
-            spend_key,
-            view_key: Scalar::from_bytes_mod_order(keccak256(&spend_key)).to_bytes(),
-            format,
-            _network: PhantomData,
-        })
+    /// Returns a private key given seed bytes, reducing the seed modulo the curve order (sc_reduce32)
+    /// for the private spend key and deriving the private view key from it.
+    pub fn from_seed_bytes(seed: [u8; 32], format: &MoneroFormat) -> Self {
+        let spend_key = Scalar::from_bytes_mod_order(seed).to_bytes();
+        Self::from_keys(spend_key, hash_to_scalar(&spend_key).to_bytes(), format)
     }
 
     /// Returns a private key given a private spend key.
     pub fn from_private_spend_key(private_spend_key: &str, format: &MoneroFormat) -> Result<Self, PrivateKeyError> {
-        let key = hex::decode(private_spend_key)?;
-        if key.len() != 32 {
-            return Err(PrivateKeyError::InvalidByteLength(key.len()));
-        }
-
-        let mut spend_key = [0u8; 32];
-        spend_key.copy_from_slice(key.as_slice());
-
-        let format = match format {
-            MoneroFormat::Subaddress(major, minor) if *major == 0 && *minor == 0 => MoneroFormat::Standard,
-            _ => *format,
-        };
-
-        Ok(Self {
+        let spend_key = Self::decode_key(private_spend_key)?;
+        Ok(Self::from_keys(
             spend_key,
-            view_key: Scalar::from_bytes_mod_order(keccak256(&spend_key)).to_bytes(),
+            hash_to_scalar(&spend_key).to_bytes(),
             format,
-            _network: PhantomData,
-        })
+        ))
+    }
+
+    /// Returns a private key given a private spend key and a private view key, for wallets
+    /// whose private view key is not derived from the private spend key.
+    pub fn from_private_keys(
+        private_spend_key: &str,
+        private_view_key: &str,
+        format: &MoneroFormat,
+    ) -> Result<Self, PrivateKeyError> {
+        Ok(Self::from_keys(
+            Self::decode_key(private_spend_key)?,
+            Self::decode_key(private_view_key)?,
+            format,
+        ))
+    }
+
+    /// Returns the view-only key of the private key, which derives the same addresses but cannot spend.
+    pub fn to_view_only(&self) -> MoneroViewOnlyKey<N> {
+        let public_spend = Scalar::from_bytes_mod_order(self.spend_key).mul_base();
+        MoneroViewOnlyKey::from_keys(self.view_key, public_spend.to_bytes(), &self.format)
     }
 
     /// Update the private key format and returns a subaddress private view key.
     pub fn to_subaddress_private_view_key(&self, major: u32, minor: u32) -> [u8; 32] {
-        if major == 0 && minor == 0 {
-            [0u8; 32]
-        } else {
-            let mut derivation: Vec<u8> = b"SubAddr\x00"[..].into();
-            derivation.extend(&self.view_key);
-            derivation.extend(&major.to_le_bytes());
-            derivation.extend(&minor.to_le_bytes());
-
-            Scalar::from_bytes_mod_order(keccak256(&derivation)).to_bytes()
-        }
+        self.to_view_only().to_subaddress_private_view_key(major, minor)
     }
 
     /// Returns the private spend key of the Monero private key.
@@ -112,15 +105,52 @@ impl<N: MoneroNetwork> MoneroPrivateKey<N> {
         self.spend_key
     }
 
-    /// Returns the private spend key of the Monero private key.
+    /// Returns the private view key of the Monero private key.
     pub fn to_private_view_key(&self) -> [u8; 32] {
         self.view_key
     }
 
+    /// Returns the hex-encoded private spend key of the Monero private key.
+    pub fn to_private_spend_key_hex(&self) -> String {
+        hex::encode(self.spend_key)
+    }
+
+    /// Returns the hex-encoded private view key of the Monero private key.
+    pub fn to_private_view_key_hex(&self) -> String {
+        hex::encode(self.view_key)
+    }
+
     /// Returns the format of the Monero address.
     pub fn format(&self) -> MoneroFormat {
         self.format.clone()
     }
+
+    /// Returns a private key given the bytes of a private spend key and private view key.
+    fn from_keys(spend_key: [u8; 32], view_key: [u8; 32], format: &MoneroFormat) -> Self {
+        let format = match format {
+            MoneroFormat::Subaddress(major, minor) if *major == 0 && *minor == 0 => MoneroFormat::Standard,
+            _ => *format,
+        };
+
+        Self {
+            spend_key,
+            view_key,
+            format,
+            _network: PhantomData,
+        }
+    }
+
+    /// Returns the bytes of the given hex-encoded 32-byte key.
+    fn decode_key(key: &str) -> Result<[u8; 32], PrivateKeyError> {
+        let key = hex::decode(key)?;
+        if key.len() != 32 {
+            return Err(PrivateKeyError::InvalidByteLength(key.len()));
+        }
+
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(key.as_slice());
+        Ok(bytes)
+    }
 }
 
 impl<N: MoneroNetwork> FromStr for MoneroPrivateKey<N> {
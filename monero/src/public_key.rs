@@ -5,7 +5,7 @@ use crate::private_key::MoneroPrivateKey;
 use wagyu_model::{Address, AddressError, PublicKey, PublicKeyError};
 
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
-use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::EdwardsBasepointTable, scalar::Scalar};
+use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, scalar::Scalar};
 
 /// Represents a Monero public key
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -27,41 +27,7 @@ impl<N: MoneroNetwork> PublicKey for MoneroPublicKey<N> {
 
     /// Returns the address corresponding to the given public key.
     fn from_private_key(private_key: &Self::PrivateKey) -> Self {
-        const G: &EdwardsBasepointTable = &ED25519_BASEPOINT_TABLE;
-
-        match private_key.format() {
-            MoneroFormat::Subaddress(major, minor) if major != 0 || minor != 0 => {
-                let private_spend = &Scalar::from_bits(private_key.to_private_spend_key());
-                let private_view = &Scalar::from_bits(private_key.to_private_view_key());
-
-                let private_view_subaddress = private_key.to_subaddress_private_view_key(major, minor);
-                let private_view_subaddress = &Scalar::from_bits(private_view_subaddress);
-
-                let public_spend_subaddress = &(private_spend + private_view_subaddress) * G;
-                let public_view_subaddress = private_view * public_spend_subaddress;
-
-                Self {
-                    spend_key: Some(*public_spend_subaddress.compress().as_bytes()),
-                    view_key: Some(*public_view_subaddress.compress().as_bytes()),
-                    format: MoneroFormat::Subaddress(major, minor),
-                    _network: PhantomData,
-                }
-            }
-            _ => {
-                let private_spend = &Scalar::from_bits(private_key.to_private_spend_key());
-                let public_spend = private_spend * G;
-
-                let private_view = &Scalar::from_bits(private_key.to_private_view_key());
-                let public_view = private_view * G;
-
-                Self {
-                    spend_key: Some(*public_spend.compress().as_bytes()),
-                    view_key: Some(*public_view.compress().as_bytes()),
-                    format: private_key.format(),
-                    _network: PhantomData,
-                }
-            }
-        }
+        private_key.to_view_only().to_public_key()
     }
 
     /// Returns the address of the corresponding private key.
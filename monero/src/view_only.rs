@@ -0,0 +1,270 @@
+use crate::address::MoneroAddress;
+use crate::curve::{hash_to_scalar, Point, Scalar};
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::public_key::MoneroPublicKey;
+use wagyu_model::no_std::*;
+use wagyu_model::{Address, AddressError, PrivateKeyError};
+
+use core::{fmt, fmt::Display, marker::PhantomData};
+
+/// Represents a Monero view-only key, the private view key and public spend key of a wallet,
+/// which derives the addresses and subaddresses of the wallet but cannot spend from it
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoneroViewOnlyKey<N: MoneroNetwork> {
+    /// The private viewing key
+    private_view_key: [u8; 32],
+    /// The public spending key
+    public_spend_key: [u8; 32],
+    /// Format
+    format: MoneroFormat,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroViewOnlyKey<N> {
+    /// Returns a view-only key given a private view key and public spend key.
+    pub fn from(
+        private_view_key: &str,
+        public_spend_key: &str,
+        format: &MoneroFormat,
+    ) -> Result<Self, PrivateKeyError> {
+        let private_view_key = hex::decode(private_view_key)?;
+        if private_view_key.len() != 32 {
+            return Err(PrivateKeyError::InvalidByteLength(private_view_key.len()));
+        }
+
+        let public_spend_key = hex::decode(public_spend_key)?;
+        if public_spend_key.len() != 32 {
+            return Err(PrivateKeyError::InvalidByteLength(public_spend_key.len()));
+        }
+
+        let mut view_key = [0u8; 32];
+        view_key.copy_from_slice(private_view_key.as_slice());
+
+        let mut spend_key = [0u8; 32];
+        spend_key.copy_from_slice(public_spend_key.as_slice());
+        if let Err(error) = Point::from_bytes(spend_key) {
+            return Err(PrivateKeyError::Message(error.to_string()));
+        }
+
+        Ok(Self::from_keys(view_key, spend_key, format))
+    }
+
+    /// Returns a view-only key given the bytes of a private view key and public spend key.
+    pub(crate) fn from_keys(private_view_key: [u8; 32], public_spend_key: [u8; 32], format: &MoneroFormat) -> Self {
+        let format = match format {
+            MoneroFormat::Subaddress(major, minor) if *major == 0 && *minor == 0 => MoneroFormat::Standard,
+            _ => *format,
+        };
+
+        Self {
+            private_view_key,
+            public_spend_key,
+            format,
+            _network: PhantomData,
+        }
+    }
+
+    /// Returns the public key of the view-only key, in the format of the view-only key.
+    pub fn to_public_key(&self) -> MoneroPublicKey<N> {
+        let private_view = Scalar::from_bytes_mod_order(self.private_view_key);
+        let public_spend = Point::from_bytes(self.public_spend_key).expect("the public spend key is a valid point");
+
+        let (spend_key, view_key) = match self.format {
+            MoneroFormat::Subaddress(major, minor) => {
+                let private_view_subaddress =
+                    Scalar::from_bytes_mod_order(self.to_subaddress_private_view_key(major, minor));
+                let public_spend_subaddress = public_spend + private_view_subaddress.mul_base();
+                let public_view_subaddress = public_spend_subaddress * private_view;
+                (public_spend_subaddress, public_view_subaddress)
+            }
+            _ => (public_spend, private_view.mul_base()),
+        };
+
+        MoneroPublicKey::from(&spend_key.to_hex(), &view_key.to_hex(), &self.format)
+            .expect("the public keys are 32 bytes")
+    }
+
+    /// Returns the address of the view-only key in the given format.
+    pub fn to_address(&self, format: &MoneroFormat) -> Result<MoneroAddress<N>, AddressError> {
+        let view_only_key = Self::from_keys(self.private_view_key, self.public_spend_key, format);
+        MoneroAddress::from_public_key(&view_only_key.to_public_key(), format)
+    }
+
+    /// Returns the subaddress of the view-only key at the given major and minor index.
+    pub fn to_subaddress(&self, major: u32, minor: u32) -> Result<MoneroAddress<N>, AddressError> {
+        self.to_address(&MoneroFormat::Subaddress(major, minor))
+    }
+
+    /// Returns the subaddress private view key at the given major and minor index.
+    pub fn to_subaddress_private_view_key(&self, major: u32, minor: u32) -> [u8; 32] {
+        if major == 0 && minor == 0 {
+            [0u8; 32]
+        } else {
+            let mut derivation: Vec<u8> = b"SubAddr\x00"[..].into();
+            derivation.extend(&self.private_view_key);
+            derivation.extend(&major.to_le_bytes());
+            derivation.extend(&minor.to_le_bytes());
+
+            hash_to_scalar(&derivation).to_bytes()
+        }
+    }
+
+    /// Returns the private view key of the view-only key.
+    pub fn to_private_view_key(&self) -> [u8; 32] {
+        self.private_view_key
+    }
+
+    /// Returns the public spend key of the view-only key.
+    pub fn to_public_spend_key(&self) -> [u8; 32] {
+        self.public_spend_key
+    }
+
+    /// Returns the format of the Monero address.
+    pub fn format(&self) -> MoneroFormat {
+        self.format
+    }
+}
+
+impl<N: MoneroNetwork> Display for MoneroViewOnlyKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for byte in &self.private_view_key {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, ", ")?;
+        for byte in &self.public_spend_key {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use crate::private_key::MoneroPrivateKey;
+    use wagyu_model::PrivateKey;
+
+    type N = Mainnet;
+
+    const SEED: &str = "3eb8e283b45559d4d2fb6b3a4f52443b420e6da2b38832ea0eb642100c92d600";
+    const PRIVATE_VIEW_KEY: &str = "5177c436f032666c572df97ab591cc6ac2da96ab6818a2f38d72b430aebbdc0a";
+    const PUBLIC_SPEND_KEY: &str = "b9c5610a07f4344b27625155614fb1341dd0392c68482f101b820bc1e2b908e5";
+    const PUBLIC_VIEW_KEY: &str = "0df7c88054ae3c5f75c364257d064f42d660e6ea1184bd2a3af0d7455cb4e9ee";
+    const ADDRESS: &str =
+        "48fRSJiQSp3Da61k8NSR5J9ibWMBkrJHL3hGDxSaZJvsfK7jpigPWyyGy5jqs8MSgeCBQb1HR4NDS84goPRaLV2xTungkh5";
+
+    #[test]
+    fn from_seed_bytes() {
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&hex::decode(SEED).unwrap());
+        let private_key = MoneroPrivateKey::<N>::from_seed_bytes(seed, &MoneroFormat::Standard);
+        assert_eq!(SEED, private_key.to_private_spend_key_hex());
+        assert_eq!(PRIVATE_VIEW_KEY, private_key.to_private_view_key_hex());
+
+        let public_key = private_key.to_public_key();
+        assert_eq!(PUBLIC_SPEND_KEY, hex::encode(public_key.to_public_spend_key().unwrap()));
+        assert_eq!(PUBLIC_VIEW_KEY, hex::encode(public_key.to_public_view_key().unwrap()));
+        assert_eq!(
+            ADDRESS,
+            private_key.to_address(&MoneroFormat::Standard).unwrap().to_string()
+        );
+
+        // A seed above the curve order is reduced to the private spend key.
+        let private_key = MoneroPrivateKey::<N>::from_seed_bytes([0xff; 32], &MoneroFormat::Standard);
+        assert_eq!(
+            Scalar::from_bytes_mod_order([0xff; 32]).to_bytes(),
+            private_key.to_private_spend_key()
+        );
+    }
+
+    #[test]
+    fn to_view_only() {
+        let private_key = MoneroPrivateKey::<N>::from_seed(SEED, &MoneroFormat::Standard).unwrap();
+        let view_only_key = private_key.to_view_only();
+        assert_eq!(PRIVATE_VIEW_KEY, hex::encode(view_only_key.to_private_view_key()));
+        assert_eq!(PUBLIC_SPEND_KEY, hex::encode(view_only_key.to_public_spend_key()));
+        assert_eq!(private_key.to_public_key(), view_only_key.to_public_key());
+        assert_eq!(
+            ADDRESS,
+            view_only_key.to_address(&MoneroFormat::Standard).unwrap().to_string()
+        );
+        assert_eq!(
+            format!("({}, {})", PRIVATE_VIEW_KEY, PUBLIC_SPEND_KEY),
+            view_only_key.to_string()
+        );
+
+        let imported =
+            MoneroViewOnlyKey::<N>::from(PRIVATE_VIEW_KEY, PUBLIC_SPEND_KEY, &MoneroFormat::Standard).unwrap();
+        assert_eq!(view_only_key, imported);
+    }
+
+    #[test]
+    fn to_subaddress() {
+        // (seed, major index, minor index, subaddress)
+        [
+            (
+                "ebb4c2fd6815fa634e76a2f7dee6b96d6f4e64ede83e35c0a1963b0ae369550c",
+                0,
+                1,
+                "83pEcCq6G9TcmrLoC7MrcFGEM7TsEsNpLQ7FZmkq6pPAd1pC5ajkHUDeDL4dV724JDPuhGXJyajf1dtJCjJ6MTYqDP2bECQ",
+            ),
+            (
+                "71d7544ca57116d3cc460e65bffbb124ae876a3e2d75e65e6e205053b951520b",
+                18,
+                225,
+                "89sosC3dvS3bGbkFfapXx2U47upjsn9SPbC6w4voYzhgYdWV6kotq4Z76J8TUUCw2aiVZ7yxsBySi21sfH5Zyh8nJkYpwHf",
+            ),
+            (
+                "ed8b4cae6db5548a129280750583bda7774d4ed9d4d44ecd7bc6cd7b17ace908",
+                20000,
+                35000,
+                "88rDu7NuEyiY1GGgLnFbXbDDJ1X85oa38bMeYrzmMMRL2LGTQqr7DgjciAqsntKTzhMLiaLFbKkMHUdH1tN7FBcPVYWuNYw",
+            ),
+        ]
+        .iter()
+        .for_each(|(seed, major, minor, subaddress)| {
+            let private_key = MoneroPrivateKey::<N>::from_seed(seed, &MoneroFormat::Standard).unwrap();
+            let view_only_key = private_key.to_view_only();
+            assert_eq!(
+                *subaddress,
+                view_only_key.to_subaddress(*major, *minor).unwrap().to_string()
+            );
+            assert_eq!(
+                private_key
+                    .to_address(&MoneroFormat::Subaddress(*major, *minor))
+                    .unwrap(),
+                view_only_key.to_subaddress(*major, *minor).unwrap()
+            );
+        });
+
+        // The subaddress at index (0, 0) is the primary address.
+        let view_only_key =
+            MoneroViewOnlyKey::<N>::from(PRIVATE_VIEW_KEY, PUBLIC_SPEND_KEY, &MoneroFormat::Standard).unwrap();
+        assert_eq!(ADDRESS, view_only_key.to_subaddress(0, 0).unwrap().to_string());
+    }
+
+    #[test]
+    fn from_private_keys() {
+        let private_key =
+            MoneroPrivateKey::<N>::from_private_keys(SEED, PRIVATE_VIEW_KEY, &MoneroFormat::Standard).unwrap();
+        assert_eq!(
+            MoneroPrivateKey::<N>::from_seed(SEED, &MoneroFormat::Standard).unwrap(),
+            private_key
+        );
+        assert!(MoneroPrivateKey::<N>::from_private_keys(SEED, "5177c4", &MoneroFormat::Standard).is_err());
+    }
+
+    #[test]
+    fn invalid_view_only_key() {
+        assert!(MoneroViewOnlyKey::<N>::from("", PUBLIC_SPEND_KEY, &MoneroFormat::Standard).is_err());
+        assert!(MoneroViewOnlyKey::<N>::from(PRIVATE_VIEW_KEY, "b9c561", &MoneroFormat::Standard).is_err());
+        // The y-coordinate 2 is not on the curve.
+        let invalid_point = format!("02{}", "00".repeat(31));
+        assert!(MoneroViewOnlyKey::<N>::from(PRIVATE_VIEW_KEY, &invalid_point, &MoneroFormat::Standard).is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const PASSWORD: &str = "TREZOR";
+
+/// Returns the output of the wagyu binary for the given arguments and environment variables,
+/// with the given input piped to its standard input.
+fn wagyu(arguments: &[&str], variables: &[(&str, &str)], input: &str) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_wagyu"));
+    command
+        .args(arguments)
+        .env_remove("WAGYU_PASSWORD")
+        .envs(variables.iter().cloned())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = command.spawn().unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Returns the JSON wallet printed by the given output, after checking that the command succeeded.
+fn wallet(output: &Output) -> serde_json::Value {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// Runs the wagyu binary for each case of the given fixture, and checks that it prints the
+/// expected wallet, or fails with the expected error.
+fn fixture(name: &str) {
+    let path = format!("{}/tests/fixtures/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+    let cases: serde_json::Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    for case in cases.as_array().unwrap() {
+        let arguments: Vec<&str> = case["arguments"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|argument| argument.as_str().unwrap())
+            .collect();
+        let output = wagyu(&arguments, &[], "");
+        match case.get("error") {
+            Some(error) => {
+                assert!(!output.status.success(), "{:?}", arguments);
+                let printed: serde_json::Value = serde_json::from_str(stderr(&output).lines().last().unwrap()).unwrap();
+                assert_eq!(error, &printed["error"], "{:?}", arguments);
+            }
+            None => assert_eq!(case["wallet"], wallet(&output), "{:?}", arguments),
+        }
+    }
+}
+
+#[test]
+fn bitcoin_fixtures() {
+    fixture("bitcoin");
+}
+
+#[test]
+fn bitcoin_import_hd_stdin() {
+    let argv = wagyu(
+        &["bitcoin", "import-hd", "--json", "-m", MNEMONIC, "-p", PASSWORD],
+        &[],
+        "",
+    );
+    assert!(stderr(&argv).contains("--mnemonic-stdin"));
+    assert!(stderr(&argv).contains("--password-stdin"));
+
+    let stdin = wagyu(
+        &["bitcoin", "import-hd", "--json", "--mnemonic-stdin", "--password-stdin"],
+        &[],
+        &format!("{}\n{}\n", MNEMONIC, PASSWORD),
+    );
+    assert_eq!("", stderr(&stdin));
+    assert_eq!(wallet(&argv), wallet(&stdin));
+    assert_eq!(PASSWORD, wallet(&stdin)["password"]);
+
+    // Input without a trailing newline or with a carriage return is accepted too.
+    let stdin = wagyu(
+        &["bitcoin", "import-hd", "--json", "--mnemonic-stdin", "--password-stdin"],
+        &[],
+        &format!("{}\r\n{}", MNEMONIC, PASSWORD),
+    );
+    assert_eq!(wallet(&argv), wallet(&stdin));
+}
+
+#[test]
+fn bitcoin_import_stdin() {
+    let path = "m/84'/0'/0'/0/0";
+    let argv = wagyu(
+        &["bitcoin", "import", "--json", "-m", MNEMONIC, "--path", path],
+        &[],
+        "",
+    );
+    let stdin = wagyu(
+        &["bitcoin", "import", "--json", "--mnemonic-stdin", "--path", path],
+        &[],
+        &format!("{}\n", MNEMONIC),
+    );
+    assert_eq!(wallet(&argv), wallet(&stdin));
+}
+
+#[test]
+fn bitcoin_derive_network() {
+    let tpub = "tpubDFyVTyt1QU6J43gNuoQ99MDD3aNQe27VzBvGpPNjJSV97PXGzGKPgzjbevpA52zuzUY2dHi9yHM2D4Ndn7Zm4m8dsrEkK9daBFZaLjHFAce";
+    let output = wagyu(
+        &[
+            "bitcoin",
+            "derive",
+            "--json",
+            "--extended-public-key",
+            tpub,
+            "--path",
+            "m/0",
+        ],
+        &[],
+        "",
+    );
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("the extended public key is for testnet, but mainnet was requested"));
+
+    let output = wagyu(
+        &[
+            "bitcoin",
+            "derive",
+            "--json",
+            "--extended-public-key",
+            tpub,
+            "--path",
+            "m/0",
+            "--network",
+            "testnet",
+        ],
+        &[],
+        "",
+    );
+    assert_eq!("testnet", wallet(&output)["network"]);
+}
+
+#[test]
+fn monero_fixtures() {
+    fixture("monero");
+}
+
+#[test]
+fn monero_generate() {
+    let wallet = wallet(&wagyu(
+        &["monero", "--json", "--format", "subaddress", "--network", "stagenet"],
+        &[],
+        "",
+    ));
+    assert_eq!("subaddress(0,1)", wallet["format"]);
+    assert_eq!("stagenet", wallet["network"]);
+    assert!(wallet["address"].as_str().unwrap().starts_with('7'));
+}
+
+#[test]
+fn generate_json_newline() {
+    // A single JSON wallet is printed with one trailing newline.
+    let output = wagyu(&["monero", "--json"], &[], "");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with("}\n") && !stdout.ends_with("\n\n"), "{:?}", stdout);
+}
+
+#[test]
+fn password_variable() {
+    let argv = wagyu(
+        &["ethereum", "import-hd", "--json", "-m", MNEMONIC, "-p", PASSWORD],
+        &[],
+        "",
+    );
+    let variable = wagyu(
+        &["ethereum", "import-hd", "--json", "--mnemonic-stdin"],
+        &[("WAGYU_PASSWORD", PASSWORD)],
+        &format!("{}\n", MNEMONIC),
+    );
+    assert_eq!("", stderr(&variable));
+    assert_eq!(wallet(&argv), wallet(&variable));
+
+    // The password from standard input takes precedence over the environment.
+    let stdin = wagyu(
+        &[
+            "ethereum",
+            "import-hd",
+            "--json",
+            "--mnemonic-stdin",
+            "--password-stdin",
+        ],
+        &[("WAGYU_PASSWORD", "other")],
+        &format!("{}\n{}\n", MNEMONIC, PASSWORD),
+    );
+    assert_eq!(wallet(&argv), wallet(&stdin));
+}
+
+#[test]
+fn monero_import_stdin() {
+    let generated = wallet(&wagyu(&["monero", "--json"], &[], ""));
+    let mnemonic = generated["mnemonic"].as_str().unwrap();
+    let stdin = wagyu(
+        &["monero", "import", "--json", "--mnemonic-stdin"],
+        &[],
+        &format!("{}\n", mnemonic),
+    );
+    assert_eq!(generated, wallet(&stdin));
+}
+
+#[test]
+fn missing_stdin() {
+    let output = wagyu(&["zcash", "import-hd", "--mnemonic-stdin"], &[], "");
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("missing the mnemonic on standard input"));
+
+    let output = wagyu(&["bitcoin", "import-hd", "--mnemonic-stdin", "-m", MNEMONIC], &[], "");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn zcash_fixtures() {
+    fixture("zcash");
+}
+
+#[test]
+fn zcash_generate() {
+    let wallet = wallet(&wagyu(
+        &["zcash", "--json", "--format", "transparent", "--network", "testnet"],
+        &[],
+        "",
+    ));
+    assert_eq!("p2pkh", wallet["format"]);
+    assert!(wallet["address"].as_str().unwrap().starts_with("tm"));
+
+    let output = wagyu(
+        &["zcash", "hd", "--json", "--count", "2", "--network", "testnet"],
+        &[],
+        "",
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let wallets: Vec<serde_json::Value> = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        vec!["m/32'/1'/0'/0", "m/32'/1'/0'/1"],
+        wallets
+            .iter()
+            .map(|wallet| wallet["path"].as_str().unwrap())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(wallets[0]["mnemonic"], wallets[1]["mnemonic"]);
+    assert!(wallets[0]["address"].as_str().unwrap().starts_with("ztestsapling"));
+}
+
+#[test]
+fn hd_count_custom_path() {
+    let output = wagyu(
+        &["bitcoin", "hd", "--json", "--count", "2", "-d", "m/44'/0'/0'/0"],
+        &[],
+        "",
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let wallets: Vec<serde_json::Value> = serde_json::Deserializer::from_slice(&output.stdout)
+        .into_iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(
+        vec!["m/44'/0'/0'/0/0", "m/44'/0'/0'/0/1"],
+        wallets
+            .iter()
+            .map(|wallet| wallet["path"].as_str().unwrap())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(wallets[0]["mnemonic"], wallets[1]["mnemonic"]);
+    assert_ne!(wallets[0]["address"], wallets[1]["address"]);
+
+    // A single wallet is derived from the custom path itself.
+    let wallet = wallet(&wagyu(&["bitcoin", "hd", "--json", "-d", "m/44'/0'/0'/0"], &[], ""));
+    assert_eq!("m/44'/0'/0'/0", wallet["path"]);
+
+    let output = wagyu(&["ethereum", "hd", "--index", "2147483647", "--count", "2"], &[], "");
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("beyond the last child index"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn network_aliases() {
+    [
+        (vec!["bitcoin", "--json", "--network", "test"], "testnet"),
+        (vec!["bitcoin", "hd", "--json", "-n", "testnet3"], "testnet"),
+        (vec!["monero", "--json", "--network", "stage"], "stagenet"),
+        (vec!["zcash", "--json", "--network", "main"], "mainnet"),
+    ]
+    .iter()
+    .for_each(|(arguments, network)| {
+        assert_eq!(
+            *network,
+            wallet(&wagyu(arguments, &[], ""))["network"],
+            "{:?}",
+            arguments
+        );
+    });
+}
@@ -2,14 +2,17 @@ use crate::bitcoin::{
     format::BitcoinFormat, wordlist::*, BitcoinAddress, BitcoinAmount, BitcoinDerivationPath,
     BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, BitcoinMnemonic, BitcoinNetwork, BitcoinPrivateKey,
     BitcoinPublicKey, BitcoinTransaction, BitcoinTransactionInput, BitcoinTransactionOutput,
-    BitcoinTransactionParameters, BitcoinWordlist, Mainnet as BitcoinMainnet, Outpoint, SignatureHash,
-    Testnet as BitcoinTestnet,
+    BitcoinTransactionParameters, BitcoinWordlist, ElectrumMnemonic, Mainnet as BitcoinMainnet, Outpoint,
+    SignatureHash, Testnet as BitcoinTestnet,
+};
+use crate::cli::{
+    self, batch_end, flag, generate, option, subcommand, to_json, types::*, CLIError, CLI, PASSWORD_VARIABLE,
 };
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
 use crate::model::{
-    crypto::hash160, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, PrivateKey,
-    PublicKey, Transaction,
+    crypto::hash160, ChildIndex, DerivationPath, DerivationPathError, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic,
+    MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey, Transaction,
 };
+use crate::wallet::Wallet;
 
 use clap::{ArgMatches, Values};
 use colored::*;
@@ -18,21 +21,22 @@ use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
+use std::io;
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct BitcoinWallet {
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct BitcoinWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "xpriv", skip_serializing_if = "Option::is_none")]
     pub extended_private_key: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "xpub", skip_serializing_if = "Option::is_none")]
     pub extended_public_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
@@ -51,6 +55,10 @@ struct BitcoinWallet {
     pub transaction_id: Option<String>,
 }
 
+impl Wallet for BitcoinWallet {
+    const COIN: &'static str = "bitcoin";
+}
+
 impl BitcoinWallet {
     pub fn new<N: BitcoinNetwork, R: Rng>(rng: &mut R, format: &BitcoinFormat) -> Result<Self, CLIError> {
         let private_key = BitcoinPrivateKey::<N>::new(rng)?;
@@ -67,20 +75,20 @@ impl BitcoinWallet {
         })
     }
 
-    pub fn new_hd<N: BitcoinNetwork, W: BitcoinWordlist, R: Rng>(
-        rng: &mut R,
-        word_count: u8,
-        password: Option<&str>,
+    pub fn from_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
         path: &str,
+        format: Option<&BitcoinFormat>,
     ) -> Result<Self, CLIError> {
-        let mnemonic = BitcoinMnemonic::<N, W>::new_with_count(rng, word_count)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic)?;
+        let master_extended_private_key = mnemonic.to_extended_private_key(password.clone())?;
         let derivation_path = BitcoinDerivationPath::from_str(path)?;
         let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(&extended_private_key.format())?;
+        let address = public_key.to_address(format.unwrap_or(&extended_private_key.format()))?;
         let compressed = private_key.is_compressed();
         Ok(Self {
             path: Some(path.to_string()),
@@ -98,39 +106,34 @@ impl BitcoinWallet {
         })
     }
 
-    pub fn from_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+    /// Returns the wallet of the given mnemonic, trying each supported wordlist in turn.
+    /// If the mnemonic is not BIP39 but is an Electrum seed, returns an error saying so.
+    pub fn from_mnemonic_in_any_language<N: BitcoinNetwork>(
         mnemonic: &str,
         password: &Option<&str>,
         path: &str,
+        format: Option<&BitcoinFormat>,
     ) -> Result<Self, CLIError> {
-        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(&mnemonic)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password.clone())?;
-        let derivation_path = BitcoinDerivationPath::from_str(path)?;
-        let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
-        let extended_public_key = extended_private_key.to_extended_public_key();
-        let private_key = extended_private_key.to_private_key();
-        let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(&extended_private_key.format())?;
-        let compressed = private_key.is_compressed();
-        Ok(Self {
-            path: Some(path.to_string()),
-            password: password.map(String::from),
-            mnemonic: Some(mnemonic.to_string()),
-            extended_private_key: Some(extended_private_key.to_string()),
-            extended_public_key: Some(extended_public_key.to_string()),
-            private_key: Some(private_key.to_string()),
-            public_key: Some(public_key.to_string()),
-            address: Some(address.to_string()),
-            format: Some(address.format().to_string()),
-            network: Some(N::NAME.to_string()),
-            compressed: Some(compressed),
-            ..Default::default()
-        })
+        Self::from_mnemonic::<N, ChineseSimplified>(mnemonic, password, path, format)
+            .or_else(|_| Self::from_mnemonic::<N, ChineseTraditional>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, English>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, French>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Italian>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Japanese>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Korean>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Spanish>(mnemonic, password, path, format))
+            .map_err(
+                |error| match ElectrumMnemonic::<N, English>::is_electrum_phrase(mnemonic) {
+                    true => CLIError::ElectrumMnemonic,
+                    false => error,
+                },
+            )
     }
 
     pub fn from_extended_private_key<N: BitcoinNetwork>(
         extended_private_key: &str,
         path: &Option<String>,
+        format: Option<&BitcoinFormat>,
     ) -> Result<Self, CLIError> {
         let mut extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?;
         if let Some(derivation_path) = path {
@@ -140,7 +143,7 @@ impl BitcoinWallet {
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(&extended_private_key.format())?;
+        let address = public_key.to_address(format.unwrap_or(&extended_private_key.format()))?;
         let compressed = private_key.is_compressed();
         Ok(Self {
             path: path.clone(),
@@ -159,6 +162,7 @@ impl BitcoinWallet {
     pub fn from_extended_public_key<N: BitcoinNetwork>(
         extended_public_key: &str,
         path: &Option<String>,
+        format: Option<&BitcoinFormat>,
     ) -> Result<Self, CLIError> {
         let mut extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key)?;
         if let Some(derivation_path) = path {
@@ -166,7 +170,7 @@ impl BitcoinWallet {
             extended_public_key = extended_public_key.derive(&derivation_path)?;
         }
         let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(&extended_public_key.format())?;
+        let address = public_key.to_address(format.unwrap_or(&extended_public_key.format()))?;
         let compressed = public_key.is_compressed();
         Ok(Self {
             path: path.clone(),
@@ -180,6 +184,54 @@ impl BitcoinWallet {
         })
     }
 
+    /// Returns the watch-only wallets of the given path of the extended public key, or of the
+    /// `(start, count)` range of sequential child indices under the path, using public child
+    /// key derivation only.
+    pub fn derive<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        path: &str,
+        range: Option<(u32, u32)>,
+        format: Option<&BitcoinFormat>,
+    ) -> Result<Vec<Self>, CLIError> {
+        let derivation_path = BitcoinDerivationPath::<N>::from_str(path)?;
+        if derivation_path.to_vec()?.iter().any(ChildIndex::is_hardened) {
+            return Err(CLIError::HardenedDerivation(path.into()));
+        }
+
+        let (start, count) = match range {
+            Some(range) => range,
+            None => {
+                return Ok(vec![Self::from_extended_public_key::<N>(
+                    extended_public_key,
+                    &Some(path.into()),
+                    format,
+                )?])
+            }
+        };
+
+        let extended_public_key =
+            BitcoinExtendedPublicKey::<N>::from_str(extended_public_key)?.derive(&derivation_path)?;
+        extended_public_key
+            .derive_range(ChildIndex::normal(start)?, count)?
+            .iter()
+            .zip(start..)
+            .map(|(extended_public_key, index)| {
+                let public_key = extended_public_key.to_public_key();
+                let address = public_key.to_address(format.unwrap_or(&extended_public_key.format()))?;
+                Ok(Self {
+                    path: Some(format!("{}/{}", path, index)),
+                    extended_public_key: Some(extended_public_key.to_string()),
+                    public_key: Some(public_key.to_string()),
+                    address: Some(address.to_string()),
+                    format: Some(address.format().to_string()),
+                    network: Some(N::NAME.to_string()),
+                    compressed: Some(public_key.is_compressed()),
+                    ..Default::default()
+                })
+            })
+            .collect()
+    }
+
     pub fn from_private_key<N: BitcoinNetwork>(private_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
         let private_key = BitcoinPrivateKey::<N>::from_str(private_key)?;
         let public_key = private_key.to_public_key();
@@ -421,6 +473,7 @@ pub struct BitcoinOptions {
     json: bool,
     network: String,
     subcommand: Option<String>,
+    threads: usize,
     // HD and Import HD subcommands
     account: u32,
     chain: u32,
@@ -433,10 +486,14 @@ pub struct BitcoinOptions {
     password: Option<String>,
     path: Option<String>,
     word_count: u8,
+    // Derive subcommand
+    start: Option<u32>,
     // Import subcommand
     address: Option<String>,
+    import_format: Option<BitcoinFormat>,
     private: Option<String>,
     public: Option<String>,
+    wif: Option<String>,
     // Transaction subcommand
     transaction_inputs: Option<String>,
     transaction_hex: Option<String>,
@@ -454,6 +511,7 @@ impl Default for BitcoinOptions {
             json: false,
             network: "mainnet".into(),
             subcommand: None,
+            threads: 1,
             // HD and Import HD subcommands
             account: 0,
             chain: 0,
@@ -466,10 +524,14 @@ impl Default for BitcoinOptions {
             password: None,
             path: None,
             word_count: 12,
+            // Derive subcommand
+            start: None,
             // Import subcommand
             address: None,
+            import_format: None,
             private: None,
             public: None,
+            wif: None,
             // Transaction subcommand
             transaction_inputs: None,
             transaction_hex: None,
@@ -496,12 +558,14 @@ impl BitcoinOptions {
             "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "language" => self.language(arguments.value_of(option)),
             "lock time" => self.lock_time(clap::value_t!(arguments.value_of(*option), u32).ok()),
-            "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
-            "password" => self.password(arguments.value_of(option)),
+            "path" => self.path(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "start" => self.start(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "wif" => self.wif(arguments.value_of(option)),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             "version" => self.version(clap::value_t!(arguments.value_of(*option), u32).ok()),
             _ => (),
@@ -641,8 +705,8 @@ impl BitcoinOptions {
     /// If the specified argument is `None`, then no change occurs.
     fn network(&mut self, argument: Option<&str>) {
         match argument {
-            Some("mainnet") => self.network = "mainnet".into(),
-            Some("testnet") => self.network = "testnet".into(),
+            Some(network) if BitcoinMainnet::is_name(network) => self.network = BitcoinMainnet::NAME.into(),
+            Some(network) if BitcoinTestnet::is_name(network) => self.network = BitcoinTestnet::NAME.into(),
             _ => (),
         };
     }
@@ -655,6 +719,15 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `path` to the specified custom derivation path, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn path(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.derivation = "custom".into();
+            self.path = Some(path.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -681,6 +754,30 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `start` to the specified child index, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn start(&mut self, argument: Option<u32>) {
+        if let Some(start) = argument {
+            self.start = Some(start);
+        }
+    }
+
+    /// Sets `threads` to the specified number of threads, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = threads;
+        }
+    }
+
+    /// Imports a wallet for the specified private key in wallet import format, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn wif(&mut self, argument: Option<&str>) {
+        if let Some(wif) = argument {
+            self.wif = Some(wif.to_string());
+        }
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -704,6 +801,37 @@ impl BitcoinOptions {
         }
     }
 
+    /// Returns the derivation path of a wallet of a batch of `count` wallets. A custom path is the
+    /// parent of a batch of more than one wallet, whose children are numbered from the index.
+    fn to_batch_derivation_path(&self, count: usize) -> Option<String> {
+        match (self.derivation.as_str(), &self.path) {
+            ("custom", Some(path)) if count > 1 => Some(format!("{}/{}", path, self.index)),
+            _ => self.to_derivation_path(true),
+        }
+    }
+
+    /// Returns the derivation path to derive from an extended public key, and the start index and
+    /// count of its sequential child addresses, given either as a final `start..end` path segment
+    /// or with `start` and `count`. If neither is given, then only the path itself is derived.
+    fn to_derivation_range(&self) -> Result<(String, Option<(u32, u32)>), CLIError> {
+        let path = self.path.clone().unwrap_or_else(|| "m".into());
+        match path.rsplitn(2, '/').collect::<Vec<&str>>()[..] {
+            [range, parent] if range.contains("..") => {
+                let invalid_range =
+                    || CLIError::DerivationPathError(DerivationPathError::InvalidDerivationPath(path.clone()));
+                let (start, end) = match range.splitn(2, "..").collect::<Vec<&str>>()[..] {
+                    [start, end] => (start.parse::<u32>()?, end.parse::<u32>()?),
+                    _ => return Err(invalid_range()),
+                };
+                match start < end {
+                    true => Ok((parent.into(), Some((start, end - start)))),
+                    false => Err(invalid_range()),
+                }
+            }
+            _ => Ok((path.clone(), self.start.map(|start| (start, self.count as u32)))),
+        }
+    }
+
     /// Sets `version` to the specified transaction version, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn version(&mut self, argument: Option<u32>) {
@@ -713,6 +841,20 @@ impl BitcoinOptions {
     }
 }
 
+/// Returns the wallet imported for the network `N`, or a network mismatch error if the
+/// imported material is only valid for the other network.
+fn import<N: BitcoinNetwork, T>(
+    material: &'static str,
+    mainnet: &dyn Fn() -> Result<T, CLIError>,
+    testnet: &dyn Fn() -> Result<T, CLIError>,
+) -> Result<T, CLIError> {
+    cli::import(
+        material,
+        N::NAME,
+        &[(BitcoinMainnet::NAME, mainnet), (BitcoinTestnet::NAME, testnet)],
+    )
+}
+
 pub struct BitcoinCLI;
 
 impl CLI for BitcoinCLI {
@@ -721,8 +863,14 @@ impl CLI for BitcoinCLI {
     const NAME: NameType = "bitcoin";
     const ABOUT: AboutType = "Generates a Bitcoin wallet (include -h for more options)";
     const FLAGS: &'static [FlagType] = &[flag::JSON];
-    const OPTIONS: &'static [OptionType] = &[option::COUNT, option::FORMAT_BITCOIN, option::NETWORK_BITCOIN];
+    const OPTIONS: &'static [OptionType] = &[
+        option::COUNT,
+        option::FORMAT_BITCOIN,
+        option::NETWORK_BITCOIN,
+        option::THREADS,
+    ];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::DERIVE_BITCOIN,
         subcommand::HD_BITCOIN,
         subcommand::IMPORT_BITCOIN,
         subcommand::IMPORT_HD_BITCOIN,
@@ -733,18 +881,38 @@ impl CLI for BitcoinCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
         let mut options = BitcoinOptions::default();
-        options.parse(arguments, &["count", "format", "json", "network"]);
+        options.parse(arguments, &["count", "format", "json", "network", "threads"]);
 
         match arguments.subcommand() {
+            ("derive", Some(arguments)) => {
+                options.subcommand = Some("derive".into());
+                options.parse(arguments, &["count", "format", "json", "network"]);
+                options.parse(arguments, &["extended public", "path", "start"]);
+                if arguments.is_present("format") {
+                    options.import_format = Some(options.format.clone());
+                }
+                if arguments.is_present("count") && options.start.is_none() {
+                    options.start = Some(0);
+                }
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
-                options.parse(arguments, &["count", "json", "network"]);
-                options.parse(arguments, &["derivation", "language", "password", "word count"]);
+                options.parse(arguments, &["count", "json", "network", "threads"]);
+                options.parse(arguments, &["derivation", "language", "word count"]);
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
                 options.parse(arguments, &["format", "json", "network"]);
-                options.parse(arguments, &["address", "private", "public"]);
+                options.parse(
+                    arguments,
+                    &["address", "extended private", "path", "private", "public", "wif"],
+                );
+                if arguments.is_present("format") {
+                    options.import_format = Some(options.format.clone());
+                }
+                options.mnemonic(cli::secret(arguments, "mnemonic", None)?.as_deref());
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("import-hd", Some(arguments)) => {
                 options.subcommand = Some("import-hd".into());
@@ -758,10 +926,10 @@ impl CLI for BitcoinCLI {
                         "extended private",
                         "extended public",
                         "index",
-                        "mnemonic",
-                        "password",
                     ],
                 );
+                options.mnemonic(cli::secret(arguments, "mnemonic", None)?.as_deref());
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
@@ -782,34 +950,87 @@ impl CLI for BitcoinCLI {
         fn output<N: BitcoinNetwork, W: BitcoinWordlist>(options: BitcoinOptions) -> Result<(), CLIError> {
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
-                    Some("hd") => match options.to_derivation_path(true) {
-                        Some(path) => (0..options.count)
-                            .flat_map(|_| {
-                                match BitcoinWallet::new_hd::<N, W, _>(
-                                    &mut StdRng::from_entropy(),
-                                    options.word_count,
-                                    options.password.as_ref().map(String::as_str),
-                                    &path,
-                                ) {
-                                    Ok(wallet) => vec![wallet],
-                                    _ => vec![],
-                                }
-                            })
-                            .collect(),
+                    Some("derive") => match &options.extended_public_key {
+                        Some(key) => {
+                            let format = options.import_format.as_ref();
+                            let (path, range) = options.to_derivation_range()?;
+                            import::<N, _>(
+                                "extended public key",
+                                &|| BitcoinWallet::derive::<BitcoinMainnet>(key, &path, range, format),
+                                &|| BitcoinWallet::derive::<BitcoinTestnet>(key, &path, range, format),
+                            )?
+                        }
                         None => vec![],
                     },
+                    Some("hd") => {
+                        // Sample one mnemonic and derive the wallets from sequential indices of it
+                        let mnemonic =
+                            BitcoinMnemonic::<N, W>::new_with_count(&mut StdRng::from_entropy(), options.word_count)?;
+                        let password = options.password.as_deref();
+                        let master_extended_private_key = mnemonic.to_extended_private_key(password)?.to_string();
+                        let mnemonic = mnemonic.to_string();
+                        batch_end(options.index, options.count)?;
+
+                        return generate(
+                            &mut io::stdout(),
+                            options.count,
+                            options.threads,
+                            options.json,
+                            |_, index| {
+                                let mut options = options.clone();
+                                options.index(Some(options.index + index as u32));
+                                let path = &options.to_batch_derivation_path(options.count);
+                                Ok(BitcoinWallet {
+                                    password: password.map(String::from),
+                                    mnemonic: Some(mnemonic.clone()),
+                                    ..BitcoinWallet::from_extended_private_key::<N>(
+                                        &master_extended_private_key,
+                                        path,
+                                        None,
+                                    )?
+                                })
+                            },
+                        );
+                    }
                     Some("import") => {
-                        if let Some(private_key) = options.private {
-                            vec![
-                                BitcoinWallet::from_private_key::<BitcoinMainnet>(&private_key, &options.format).or(
-                                    BitcoinWallet::from_private_key::<BitcoinTestnet>(&private_key, &options.format),
-                                )?,
-                            ]
+                        if let Some(private_key) = &options.private {
+                            vec![import::<N, _>(
+                                "private key",
+                                &|| BitcoinWallet::from_private_key::<BitcoinMainnet>(private_key, &options.format),
+                                &|| BitcoinWallet::from_private_key::<BitcoinTestnet>(private_key, &options.format),
+                            )?]
                         } else if let Some(public_key) = options.public {
                             vec![BitcoinWallet::from_public_key::<N>(&public_key, &options.format)?]
-                        } else if let Some(address) = options.address {
-                            vec![BitcoinWallet::from_address::<BitcoinMainnet>(&address)
-                                .or(BitcoinWallet::from_address::<BitcoinTestnet>(&address))?]
+                        } else if let Some(wif) = &options.wif {
+                            vec![import::<N, _>(
+                                "private key",
+                                &|| BitcoinWallet::from_private_key::<BitcoinMainnet>(wif, &options.format),
+                                &|| BitcoinWallet::from_private_key::<BitcoinTestnet>(wif, &options.format),
+                            )?]
+                        } else if let Some(mnemonic) = &options.mnemonic {
+                            let password = &options.password.as_deref();
+                            match options.to_derivation_path(true) {
+                                Some(path) => vec![BitcoinWallet::from_mnemonic_in_any_language::<N>(
+                                    mnemonic,
+                                    password,
+                                    &path,
+                                    options.import_format.as_ref(),
+                                )?],
+                                None => vec![],
+                            }
+                        } else if let Some(key) = &options.extended_private_key {
+                            let (path, format) = (&options.path, options.import_format.as_ref());
+                            vec![import::<N, _>(
+                                "extended private key",
+                                &|| BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path, format),
+                                &|| BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path, format),
+                            )?]
+                        } else if let Some(address) = &options.address {
+                            vec![import::<N, _>(
+                                "address",
+                                &|| BitcoinWallet::from_address::<BitcoinMainnet>(address),
+                                &|| BitcoinWallet::from_address::<BitcoinTestnet>(address),
+                            )?]
                         } else {
                             vec![]
                         }
@@ -819,32 +1040,29 @@ impl CLI for BitcoinCLI {
                             let password = &options.password.as_ref().map(String::as_str);
 
                             match options.to_derivation_path(true) {
-                                Some(path) => vec![BitcoinWallet::from_mnemonic::<N, ChineseSimplified>(
-                                    &mnemonic, password, &path,
-                                )
-                                .or(BitcoinWallet::from_mnemonic::<N, ChineseTraditional>(
-                                    &mnemonic, password, &path,
-                                ))
-                                .or(BitcoinWallet::from_mnemonic::<N, English>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, French>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Italian>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Japanese>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Korean>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Spanish>(&mnemonic, password, &path))?],
+                                Some(path) => vec![BitcoinWallet::from_mnemonic_in_any_language::<N>(
+                                    &mnemonic, password, &path, None,
+                                )?],
                                 None => vec![],
                             }
                         } else if let Some(extended_private_key) = options.extended_private_key.clone() {
                             let key = &extended_private_key;
                             let path = &options.to_derivation_path(false);
 
-                            vec![BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path))?]
+                            vec![
+                                BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path, None).or(
+                                    BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path, None),
+                                )?,
+                            ]
                         } else if let Some(extended_public_key) = options.extended_public_key.clone() {
                             let key = &extended_public_key;
                             let path = &options.to_derivation_path(false);
 
-                            vec![BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, path))?]
+                            vec![
+                                BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path, None).or(
+                                    BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, path, None),
+                                )?,
+                            ]
                         } else {
                             vec![]
                         }
@@ -879,18 +1097,19 @@ impl CLI for BitcoinCLI {
                             vec![]
                         }
                     }
-                    _ => (0..options.count)
-                        .flat_map(
-                            |_| match BitcoinWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            },
+                    _ => {
+                        return generate(
+                            &mut io::stdout(),
+                            options.count,
+                            options.threads,
+                            options.json,
+                            |rng, _| BitcoinWallet::new::<N, _>(rng, &options.format),
                         )
-                        .collect(),
+                    }
                 };
 
             match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
+                true => println!("{}\n", to_json(&wallets)?),
                 false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
             };
 
@@ -937,3 +1156,209 @@ impl CLI for BitcoinCLI {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type N = BitcoinMainnet;
+
+    #[test]
+    fn test_json_schema() {
+        let wallet = BitcoinWallet::new::<N, _>(&mut StdRng::seed_from_u64(0), &BitcoinFormat::Bech32).unwrap();
+        let expected = serde_json::json!({
+            "private_key": "L3DbtqeYurm1Vr7pdfKzvmt5WdDwhUvS6Z9BcMegrrMM5uxGcxTA",
+            "public_key": "0337963c702155e2fda1c28d7e372b3501737cdb03d87b66b44e2c66cf723a26f9",
+            "address": "bc1q323u5qdkp7kpfe59pw24v3gjwtwdhpnfry3su8",
+            "format": "bech32",
+            "network": "mainnet",
+            "compressed": true
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+
+        let mnemonic = BitcoinMnemonic::<N, English>::new_with_count(&mut StdRng::seed_from_u64(0), 12).unwrap();
+        let master_extended_private_key = mnemonic.to_extended_private_key(None).unwrap().to_string();
+        let path = Some("m/84'/0'/0'/0/0".into());
+        let wallet = BitcoinWallet {
+            mnemonic: Some(mnemonic.to_string()),
+            ..BitcoinWallet::from_extended_private_key::<N>(&master_extended_private_key, &path, None).unwrap()
+        };
+        let expected = serde_json::json!({
+            "path": "m/84'/0'/0'/0/0",
+            "mnemonic": "ready relief cabbage liar nation leisure genuine wolf juice logic scale boy",
+            "xpriv": "zprvAgNvrtuxyEgDKPJivjRTDAARzxgSPmX6znsm1VGcsfoRoczTqhWxQfA4BUu5MvvibymkbiDKVnBFq46VmZv8uwwnrFp7j4A2QuUx5zniYzA",
+            "xpub": "zpub6uNHGQSrocEWXsPC2kxTaJ7AYzWvoEExN1oMosgES1LQgRKcPEqCxTUY2kP2UnEG27EteMe3MezgvtXBVdsbSLZZcXjUMuYC2ELXSANTNby",
+            "private_key": "KwgmoX7WqFUis5B43Eu5PwVgnGkASU6tcvTtgQ5Y67u6F3REr2Y2",
+            "public_key": "02496044c8e474808f20fd83c6024bb102163a84cff257ef16bd550c1d0a99686b",
+            "address": "bc1q6ydte3x3wulsr3h7e8hey8n9v4gavvk37pxkyk",
+            "format": "bech32",
+            "network": "mainnet",
+            "compressed": true
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+    }
+
+    mod derive {
+        use super::*;
+
+        // The BIP84 account 0 zpub of the "abandon ... about" mnemonic, as shown by Electrum
+        const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+        fn addresses(wallets: Vec<BitcoinWallet>) -> Vec<(String, String)> {
+            wallets
+                .into_iter()
+                .map(|wallet| (wallet.path.unwrap(), wallet.address.unwrap()))
+                .collect()
+        }
+
+        fn range(path: &str, start: Option<u32>, count: usize) -> Result<(String, Option<(u32, u32)>), CLIError> {
+            BitcoinOptions {
+                path: Some(path.into()),
+                start,
+                count,
+                ..Default::default()
+            }
+            .to_derivation_range()
+        }
+
+        #[test]
+        fn test_derive_electrum() {
+            let receive = BitcoinWallet::derive::<N>(ZPUB, "m/0", Some((0, 2)), None).unwrap();
+            let change = BitcoinWallet::derive::<N>(ZPUB, "m/1/0", None, None).unwrap();
+            assert_eq!(
+                vec![
+                    ("m/0/0".into(), "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu".into()),
+                    ("m/0/1".into(), "bc1qnjg0jd8228aq7egyzacy8cys3knf9xvrerkf9g".into()),
+                    ("m/1/0".into(), "bc1q8c6fshw2dlwun7ekn9qwf37cu2rn755upcp6el".into()),
+                ],
+                [addresses(receive), addresses(change)].concat()
+            );
+
+            let legacy = BitcoinWallet::derive::<N>(ZPUB, "m/1", Some((0, 1)), Some(&BitcoinFormat::P2PKH)).unwrap();
+            assert_eq!(
+                "16fuuGhkywq9pB7BBxi3btQ3C3s4f4dz1N",
+                legacy[0].address.as_ref().unwrap()
+            );
+        }
+
+        #[test]
+        fn test_derive_hardened() {
+            match BitcoinWallet::derive::<N>(ZPUB, "m/0'", Some((0, 2)), None) {
+                Err(CLIError::HardenedDerivation(path)) => assert_eq!("m/0'", path),
+                _ => panic!("expected a hardened derivation error"),
+            };
+        }
+
+        #[test]
+        fn test_to_derivation_range() {
+            assert_eq!(("m/0".into(), Some((0, 20))), range("m/0/0..20", None, 1).unwrap());
+            assert_eq!(("m/1".into(), Some((5, 3))), range("m/1", Some(5), 3).unwrap());
+            assert_eq!(("m/1/5".into(), None), range("m/1/5", None, 1).unwrap());
+            assert!(range("m/0/5..2", None, 1).is_err());
+            assert!(range("m/0/0'..2", None, 1).is_err());
+        }
+    }
+
+    mod generate {
+        use super::*;
+        use std::collections::HashSet;
+
+        fn addresses(output: Vec<u8>) -> Vec<String> {
+            String::from_utf8(output)
+                .unwrap()
+                .lines()
+                .map(|line| {
+                    from_str::<serde_json::Value>(line).unwrap()["address"]
+                        .as_str()
+                        .unwrap()
+                        .into()
+                })
+                .collect()
+        }
+
+        #[test]
+        fn test_count() {
+            let output = &mut Vec::new();
+            generate(output, 100, 4, true, |rng, _| {
+                BitcoinWallet::new::<N, _>(rng, &BitcoinFormat::Bech32)
+            })
+            .unwrap();
+            let addresses = addresses(output.clone());
+            assert_eq!(100, addresses.len());
+            assert_eq!(100, addresses.iter().collect::<HashSet<_>>().len());
+        }
+    }
+
+    mod import {
+        use super::*;
+
+        const PHRASE: &str =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        const WIF: &str = "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d";
+        const ADDRESS: &str = "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu";
+
+        #[test]
+        fn test_import_wif() {
+            let wallet = BitcoinWallet::from_private_key::<N>(WIF, &BitcoinFormat::Bech32).unwrap();
+            assert_eq!(ADDRESS, wallet.address.unwrap());
+        }
+
+        #[test]
+        fn test_import_mnemonic() {
+            let path = "m/84'/0'/0'/0/0";
+            let wallet = BitcoinWallet::from_mnemonic_in_any_language::<N>(PHRASE, &None, path, None).unwrap();
+            assert_eq!(ADDRESS, wallet.address.unwrap());
+            assert_eq!(WIF, wallet.private_key.unwrap());
+
+            let format = Some(&BitcoinFormat::P2PKH);
+            let wallet = BitcoinWallet::from_mnemonic_in_any_language::<N>(PHRASE, &None, path, format).unwrap();
+            assert_eq!("p2pkh", wallet.format.unwrap());
+        }
+
+        #[test]
+        fn test_import_electrum_mnemonic() {
+            let phrase = "bitter grass shiver impose acquire brush forget axis eager alone wine silver";
+            let result = BitcoinWallet::from_mnemonic_in_any_language::<N>(phrase, &None, "m/0", None);
+            assert_eq!(
+                "this looks like an Electrum seed, not a BIP39 mnemonic",
+                result.unwrap_err().to_string()
+            );
+        }
+
+        #[test]
+        fn test_import_extended_private_key() {
+            let key = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+            let wallet = BitcoinWallet::from_extended_private_key::<N>(key, &Some("m/0'/1".into()), None).unwrap();
+            assert_eq!(
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+                wallet.extended_private_key.unwrap()
+            );
+            assert_eq!("1JQheacLPdM5ySCkrZkV66G2ApAXe1mqLj", wallet.address.unwrap());
+        }
+
+        #[test]
+        fn test_network_mismatch() {
+            let wif = "cNJFgo1driFnPcBdBX8BrJrpxchBWXwXCvNH5SoSkdcF6JXXwHMm";
+            let format = &BitcoinFormat::P2PKH;
+            let result = import::<N, _>(
+                "private key",
+                &|| BitcoinWallet::from_private_key::<BitcoinMainnet>(wif, format),
+                &|| BitcoinWallet::from_private_key::<BitcoinTestnet>(wif, format),
+            );
+            match result {
+                Err(error @ CLIError::NetworkMismatch(..)) => assert_eq!(
+                    "the private key is for testnet, but mainnet was requested",
+                    error.to_string()
+                ),
+                _ => panic!("expected a network mismatch"),
+            }
+
+            let result = import::<BitcoinTestnet, _>(
+                "private key",
+                &|| BitcoinWallet::from_private_key::<BitcoinMainnet>(wif, format),
+                &|| BitcoinWallet::from_private_key::<BitcoinTestnet>(wif, format),
+            );
+            assert!(result.is_ok());
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+use crate::cli::{
+    self, batch_end, flag, generate, option, subcommand, to_json, types::*, CLIError, CLI, PASSWORD_VARIABLE,
+};
 use crate::ethereum::{
     wordlist::*, EthereumAddress, EthereumAmount, EthereumDerivationPath, EthereumExtendedPrivateKey,
     EthereumExtendedPublicKey, EthereumFormat, EthereumMnemonic, EthereumNetwork, EthereumPrivateKey,
@@ -9,6 +11,7 @@ use crate::model::{
     ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey,
     Transaction,
 };
+use crate::wallet::Wallet;
 
 use clap::{ArgMatches, Values};
 use colored::*;
@@ -17,21 +20,22 @@ use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
+use std::io;
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct EthereumWallet {
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct EthereumWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "xpriv", skip_serializing_if = "Option::is_none")]
     pub extended_private_key: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "xpub", skip_serializing_if = "Option::is_none")]
     pub extended_public_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
@@ -47,6 +51,10 @@ struct EthereumWallet {
     pub transaction_hex: Option<String>,
 }
 
+impl Wallet for EthereumWallet {
+    const COIN: &'static str = "ethereum";
+}
+
 impl EthereumWallet {
     pub fn new<R: Rng>(rng: &mut R) -> Result<Self, CLIError> {
         let private_key = EthereumPrivateKey::new(rng)?;
@@ -60,33 +68,6 @@ impl EthereumWallet {
         })
     }
 
-    pub fn new_hd<N: EthereumNetwork, W: EthereumWordlist, R: Rng>(
-        rng: &mut R,
-        word_count: u8,
-        password: Option<&str>,
-        path: &str,
-    ) -> Result<Self, CLIError> {
-        let mnemonic = EthereumMnemonic::<N, W>::new_with_count(rng, word_count)?;
-        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
-        let derivation_path = EthereumDerivationPath::from_str(path)?;
-        let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
-        let extended_public_key = extended_private_key.to_extended_public_key();
-        let private_key = extended_private_key.to_private_key();
-        let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(&EthereumFormat::Standard)?;
-        Ok(Self {
-            path: Some(path.to_string()),
-            password: password.map(String::from),
-            mnemonic: Some(mnemonic.to_string()),
-            extended_private_key: Some(extended_private_key.to_string()),
-            extended_public_key: Some(extended_public_key.to_string()),
-            private_key: Some(private_key.to_string()),
-            public_key: Some(public_key.to_string()),
-            address: Some(address.to_string()),
-            ..Default::default()
-        })
-    }
-
     pub fn from_mnemonic<N: EthereumNetwork, W: EthereumWordlist>(
         mnemonic: &str,
         password: Option<&str>,
@@ -189,7 +170,7 @@ impl EthereumWallet {
 
     pub fn to_raw_transaction<N: EthereumNetwork>(parameters: EthereumInput) -> Result<Self, CLIError> {
         let transaction_parameters = EthereumTransactionParameters {
-            receiver: EthereumAddress::from_str(&parameters.to)?,
+            receiver: Some(EthereumAddress::from_str(&parameters.to)?),
             amount: EthereumAmount::from_wei(&parameters.value)?,
             gas: EthereumAmount::u256_from_str(&parameters.gas)?,
             gas_price: EthereumAmount::from_wei(&parameters.gas_price)?,
@@ -314,6 +295,7 @@ pub struct EthereumOptions {
     count: usize,
     json: bool,
     subcommand: Option<String>,
+    threads: usize,
     // HD and Import HD subcommands
     derivation: String,
     extended_private_key: Option<String>,
@@ -343,6 +325,7 @@ impl Default for EthereumOptions {
             count: 1,
             json: false,
             subcommand: None,
+            threads: 1,
             // HD and Import HD subcommands
             derivation: "ethereum".into(),
             extended_private_key: None,
@@ -380,12 +363,11 @@ impl EthereumOptions {
             "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "indices" => self.indices(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "language" => self.language(arguments.value_of(option)),
-            "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
-            "password" => self.password(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             _ => (),
         });
@@ -535,6 +517,14 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `threads` to the specified number of threads, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = threads;
+        }
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -560,6 +550,15 @@ impl EthereumOptions {
         }
     }
 
+    /// Returns the derivation path of a wallet of a batch of `count` wallets. A custom path is the
+    /// parent of a batch of more than one wallet, whose children are numbered from the index.
+    fn to_batch_derivation_path(&self, count: usize) -> Option<String> {
+        match (self.derivation.as_str(), &self.path) {
+            ("custom", Some(path)) if count > 1 => Some(format!("{}/{}", path, self.index)),
+            _ => self.to_derivation_path(true),
+        }
+    }
+
     /// Returns the derivation paths with the specified account, chain, derivation, indices, and path.
     /// If `default` is enabled, then return the default path if no derivation was provided.
     fn to_derivation_paths(&self, default: bool) -> Vec<Option<String>> {
@@ -585,7 +584,7 @@ impl CLI for EthereumCLI {
     const ABOUT: AboutType = "Generates a Ethereum wallet (include -h for more options)";
     const FLAGS: &'static [FlagType] = &[flag::JSON];
     const NAME: NameType = "ethereum";
-    const OPTIONS: &'static [OptionType] = &[option::COUNT];
+    const OPTIONS: &'static [OptionType] = &[option::COUNT, option::THREADS];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
         subcommand::HD_ETHEREUM,
         subcommand::IMPORT_ETHEREUM,
@@ -597,16 +596,14 @@ impl CLI for EthereumCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
         let mut options = EthereumOptions::default();
-        options.parse(arguments, &["count", "json"]);
+        options.parse(arguments, &["count", "json", "threads"]);
 
         match arguments.subcommand() {
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
-                options.parse(arguments, &["count", "json"]);
-                options.parse(
-                    arguments,
-                    &["derivation", "index", "indices", "language", "password", "word count"],
-                );
+                options.parse(arguments, &["count", "json", "threads"]);
+                options.parse(arguments, &["derivation", "index", "indices", "language", "word count"]);
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
@@ -626,10 +623,10 @@ impl CLI for EthereumCLI {
                         "extended public",
                         "index",
                         "indices",
-                        "mnemonic",
-                        "password",
                     ],
                 );
+                options.mnemonic(cli::secret(arguments, "mnemonic", None)?.as_deref());
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
@@ -647,36 +644,25 @@ impl CLI for EthereumCLI {
         fn output<N: EthereumNetwork, W: EthereumWordlist>(options: EthereumOptions) -> Result<(), CLIError> {
             let wallets = match options.subcommand.as_ref().map(String::as_str) {
                 Some("hd") => {
-                    let password = options.password.as_ref().map(String::as_str);
-                    (0..options.count)
-                        .flat_map(|_| {
-                            // Sample a new HD wallet
-                            let wallet = EthereumWallet::new_hd::<N, W, _>(
-                                &mut StdRng::from_entropy(),
-                                options.word_count,
-                                password,
-                                &options.to_derivation_path(true).unwrap(),
-                            )
-                            .unwrap();
-                            let mnemonic = &wallet.mnemonic.unwrap();
-
-                            // Generate the HD wallet, from `index` to a number of specified `indices`
-                            options
-                                .to_derivation_paths(true)
-                                .iter()
-                                .flat_map(|path| {
-                                    match EthereumWallet::from_mnemonic::<N, W>(
-                                        mnemonic,
-                                        password,
-                                        path.as_ref().unwrap(),
-                                    ) {
-                                        Ok(wallet) => vec![wallet],
-                                        _ => vec![],
-                                    }
-                                })
-                                .collect::<Vec<EthereumWallet>>()
+                    // Sample one mnemonic and derive the wallets from sequential indices of it
+                    let mnemonic =
+                        EthereumMnemonic::<N, W>::new_with_count(&mut StdRng::from_entropy(), options.word_count)?;
+                    let password = options.password.as_deref();
+                    let master_extended_private_key = mnemonic.to_extended_private_key(password)?.to_string();
+                    let mnemonic = mnemonic.to_string();
+                    let count = options.count.saturating_mul(options.indices as usize);
+                    batch_end(options.index, count)?;
+
+                    return generate(&mut io::stdout(), count, options.threads, options.json, |_, index| {
+                        let mut options = options.clone();
+                        options.index(Some(options.index + index as u32));
+                        let path = &options.to_batch_derivation_path(count);
+                        Ok(EthereumWallet {
+                            password: password.map(String::from),
+                            mnemonic: Some(mnemonic.clone()),
+                            ..EthereumWallet::from_extended_private_key::<N>(&master_extended_private_key, path)?
                         })
-                        .collect()
+                    });
                 }
                 Some("import") => {
                     if let Some(private_key) = options.private {
@@ -784,16 +770,19 @@ impl CLI for EthereumCLI {
                         vec![]
                     }
                 }
-                _ => (0..options.count)
-                    .flat_map(|_| match EthereumWallet::new::<_>(&mut StdRng::from_entropy()) {
-                        Ok(wallet) => vec![wallet],
-                        _ => vec![],
-                    })
-                    .collect(),
+                _ => {
+                    return generate(
+                        &mut io::stdout(),
+                        options.count,
+                        options.threads,
+                        options.json,
+                        |rng, _| EthereumWallet::new::<_>(rng),
+                    )
+                }
             };
 
             match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
+                true => println!("{}\n", to_json(&wallets)?),
                 false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
             };
 
@@ -813,3 +802,35 @@ impl CLI for EthereumCLI {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type N = EthereumMainnet;
+
+    #[test]
+    fn test_json_schema() {
+        let wallet = EthereumWallet::new(&mut StdRng::seed_from_u64(0)).unwrap();
+        let expected = serde_json::json!({
+            "private_key": "b2f7f581d6de3c06a822fd6e7e8265fbc00f8401696a5bdc34f5a6d2ff3f922f",
+            "public_key": "37963c702155e2fda1c28d7e372b3501737cdb03d87b66b44e2c66cf723a26f98fbfdc2b330401cc26cb1ceee6d437eec914f00ef0ba3032f9197ca18ab51c2b",
+            "address": "0xf23694f9c6D4837fc596c4Eb7c3c3d8a8BaE69Ca"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+
+        let mnemonic = EthereumMnemonic::<N, English>::new_with_count(&mut StdRng::seed_from_u64(0), 12).unwrap();
+        let wallet =
+            EthereumWallet::from_mnemonic::<N, English>(&mnemonic.to_string(), None, "m/44'/60'/0'/0/0").unwrap();
+        let expected = serde_json::json!({
+            "path": "m/44'/60'/0'/0/0",
+            "mnemonic": "ready relief cabbage liar nation leisure genuine wolf juice logic scale boy",
+            "xpriv": "xprvA2txpvRpWsKki2XhahMmmfhrFngY1BFGn3jmnLmDQAb1fKvPXpQm3tSZvmnt7hNqin4EU54hyeNexkP8RmLgbeNUspa4BryLRpAKX4xrYWq",
+            "xpub": "xpub6FtKERxiMEt3vWcAgitn8oeaopX2Qdy89GfNajApxW7zY8FY5Mj1bgm3n34BERnv4uL1nGrLz2psjBnWa1D7GnNprnU1QZ75C4HDa4PmhK3",
+            "private_key": "700f1dc2ca3478a08cc41b6a849f74054eefb85189dc288d38e9abe0e74bb6b3",
+            "public_key": "8ec729ffcb53561f66b44d5f87341357f7577e6ea4b20a2e2bfb640367894de00408571997e2f16552ceee2eaab68d545e3edd317ecb9181ba626dba4770e1e8",
+            "address": "0x1Fb62B55EF29F89fb87333A9dA5e0eC9Ca2a7cAc"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+    }
+}
@@ -14,8 +14,19 @@ pub use self::parameters::*;
 use types::*;
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use core::{convert::TryFrom, fmt::Display};
+use rand::rngs::StdRng;
+use rand_core::SeedableRng;
+use serde::Serialize;
+use std::{
+    env,
+    io::{self, BufRead, ErrorKind, IsTerminal, Write},
+    process::Command,
+    sync::mpsc,
+    thread,
+};
 
-use crate::model::no_std::{format, String, Vec};
+use crate::model::no_std::{format, BTreeMap, String, Vec};
 
 pub trait CLI {
     type Options;
@@ -81,6 +92,153 @@ pub trait CLI {
     fn print(options: Self::Options) -> Result<(), CLIError>;
 }
 
+/// Represents the name of a network and the import of a wallet for the network
+pub type NetworkImport<'a, T> = (&'static str, &'a dyn Fn() -> Result<T, CLIError>);
+
+/// Returns the wallet imported for the `requested` network, or a network mismatch error if the
+/// imported material is only valid for one of the other networks. Each network is given by its
+/// name and the import of the material for the network.
+pub fn import<T>(material: &'static str, requested: &str, networks: &[NetworkImport<T>]) -> Result<T, CLIError> {
+    let (_, import) = networks
+        .iter()
+        .find(|(network, _)| *network == requested)
+        .ok_or_else(|| CLIError::UnsupportedNetwork(requested.into()))?;
+    import().map_err(|error| {
+        match networks
+            .iter()
+            .find(|(network, import)| *network != requested && import().is_ok())
+        {
+            Some((network, _)) => CLIError::NetworkMismatch(material, (*network).into(), requested.into()),
+            None => error,
+        }
+    })
+}
+
+/// Returns the given wallets as a JSON object, or as a JSON array if there is more than one wallet.
+pub fn to_json<T: Serialize>(wallets: &[T]) -> Result<String, CLIError> {
+    match wallets {
+        [wallet] => Ok(serde_json::to_string_pretty(wallet)?),
+        wallets => Ok(serde_json::to_string_pretty(wallets)?),
+    }
+}
+
+/// Returns the index of the last wallet of a batch of `count` wallets derived from sequential
+/// indices starting at `start`, or an error if it is beyond the last child index.
+pub fn batch_end(start: u32, count: usize) -> Result<u32, CLIError> {
+    u32::try_from(count.saturating_sub(1))
+        .ok()
+        .and_then(|offset| start.checked_add(offset))
+        .filter(|end| *end < 1 << 31)
+        .ok_or(CLIError::IndexOverflow(start, count))
+}
+
+/// Writes `count` wallets sampled by `wallet` to the given output, as a JSON object per line if
+/// `json` is enabled, while the wallets are sampled on `threads` threads with one RNG per thread.
+/// The wallets are written in the order of the index passed to `wallet`, as soon as they are sampled.
+pub fn generate<T, F, O>(output: &mut O, count: usize, threads: usize, json: bool, wallet: F) -> Result<(), CLIError>
+where
+    T: Display + Serialize + Send,
+    F: Fn(&mut StdRng, usize) -> Result<T, CLIError> + Sync,
+    O: Write,
+{
+    let threads = threads.max(1).min(count.max(1));
+    let wallet = &wallet;
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::sync_channel(threads * 2);
+        for thread in 0..threads {
+            let sender = sender.clone();
+            scope.spawn(move || {
+                let rng = &mut StdRng::from_entropy();
+                for index in (thread..count).step_by(threads) {
+                    if sender.send((index, wallet(rng, index))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result?);
+            while let Some(wallet) = pending.remove(&next) {
+                let wallet = match (json, count) {
+                    (true, 1) => serde_json::to_string_pretty(&wallet)?,
+                    (true, _) => serde_json::to_string(&wallet)?,
+                    (false, _) => format!("{}\n", wallet),
+                };
+                // Stop quietly once the reader of the output has gone away
+                match writeln!(output, "{}", wallet) {
+                    Err(error) if error.kind() == ErrorKind::BrokenPipe => return Ok(()),
+                    result => result?,
+                };
+                next += 1;
+            }
+        }
+
+        Ok(())
+    })
+}
+
+/// The environment variable of the mnemonic password.
+pub const PASSWORD_VARIABLE: &str = "WAGYU_PASSWORD";
+
+/// Returns the secret of the `name` option, read from standard input if the `<name> stdin` flag
+/// is present, or from the given environment variable if the option is absent. A secret passed as
+/// an argument is still accepted, with a warning, as it is exposed in the shell history and the
+/// process list.
+pub fn secret(arguments: &ArgMatches, name: &'static str, variable: Option<&str>) -> Result<Option<String>, CLIError> {
+    if arguments.is_present(format!("{} stdin", name)) {
+        return Ok(Some(read_secret(name)?));
+    }
+    if let Some(secret) = arguments.value_of(name) {
+        eprintln!(
+            "Warning: a {} passed as an argument is exposed in the shell history and the process list, use --{}-stdin instead",
+            name, name
+        );
+        return Ok(Some(secret.into()));
+    }
+    Ok(variable.and_then(|variable| env::var(variable).ok()))
+}
+
+/// Returns the next line of standard input, without its line ending. If standard input is a
+/// terminal, the user is prompted for the secret, which is not echoed.
+fn read_secret(name: &'static str) -> Result<String, CLIError> {
+    let stdin = io::stdin();
+    let terminal = stdin.is_terminal();
+    if terminal {
+        eprint!("Enter the {}: ", name);
+        set_echo(false);
+    }
+
+    let mut line = String::new();
+    let result = stdin.lock().read_line(&mut line);
+    if terminal {
+        set_echo(true);
+        eprintln!();
+    }
+
+    match result? {
+        0 => Err(CLIError::MissingInput(name)),
+        _ => Ok(line.trim_end_matches(&['\r', '\n'][..]).into()),
+    }
+}
+
+/// Turns the echo of the terminal on standard input on or off with `stty`, if it is available.
+fn set_echo(echo: bool) {
+    let _ = Command::new("stty").arg(if echo { "echo" } else { "-echo" }).status();
+}
+
+/// Returns `true` if JSON output was requested for the command or its subcommand.
+pub fn is_json(arguments: &ArgMatches) -> bool {
+    match arguments.subcommand() {
+        (_, Some(subcommand)) => arguments.is_present("json") || subcommand.is_present("json"),
+        _ => arguments.is_present("json"),
+    }
+}
+
 #[derive(Debug, Fail)]
 pub enum CLIError {
     #[fail(display = "{}", _0)]
@@ -95,15 +253,33 @@ pub enum CLIError {
     #[fail(display = "{}", _0)]
     DerivationPathError(DerivationPathError),
 
+    #[fail(display = "this looks like an Electrum seed, not a BIP39 mnemonic")]
+    ElectrumMnemonic,
+
     #[fail(display = "{}", _0)]
     ExtendedPrivateKeyError(ExtendedPrivateKeyError),
 
     #[fail(display = "{}", _0)]
     ExtendedPublicKeyError(ExtendedPublicKeyError),
 
+    #[fail(display = "cannot derive the hardened path {} from an extended public key", _0)]
+    HardenedDerivation(String),
+
+    #[fail(
+        display = "a batch of {} wallets from index {} is beyond the last child index",
+        _1, _0
+    )]
+    IndexOverflow(u32, usize),
+
     #[fail(display = "invalid derived mnemonic for a given private spend key")]
     InvalidMnemonicForPrivateSpendKey,
 
+    #[fail(display = "missing the {} on standard input", _0)]
+    MissingInput(&'static str),
+
+    #[fail(display = "the {} is for {}, but {} was requested", _0, _1, _2)]
+    NetworkMismatch(&'static str, String, String),
+
     #[fail(display = "{}", _0)]
     PrivateKeyError(PrivateKeyError),
 
@@ -118,6 +294,9 @@ pub enum CLIError {
 
     #[fail(display = "unsupported mnemonic language")]
     UnsupportedLanguage,
+
+    #[fail(display = "unsupported network {}", _0)]
+    UnsupportedNetwork(String),
 }
 
 impl From<AddressError> for CLIError {
@@ -162,6 +341,12 @@ impl From<hex::FromHexError> for CLIError {
     }
 }
 
+impl From<std::io::Error> for CLIError {
+    fn from(error: std::io::Error) -> Self {
+        CLIError::Crate("std::io", format!("{:?}", error))
+    }
+}
+
 impl From<MnemonicError> for CLIError {
     fn from(error: MnemonicError) -> Self {
         CLIError::MnemonicError(error)
@@ -191,3 +376,84 @@ impl From<TransactionError> for CLIError {
         CLIError::TransactionError(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_json() {
+        let wallet = serde_json::json!({ "address": "1" });
+        assert_eq!(
+            serde_json::to_string_pretty(&wallet).unwrap(),
+            to_json(&[&wallet]).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_string_pretty(&[&wallet, &wallet]).unwrap(),
+            to_json(&[&wallet, &wallet]).unwrap()
+        );
+        assert_eq!("[]", to_json::<()>(&[]).unwrap());
+    }
+
+    #[test]
+    fn test_generate() {
+        let output = &mut Vec::new();
+        generate(output, 10, 3, true, |_, index| {
+            Ok(serde_json::json!({ "index": index }))
+        })
+        .unwrap();
+        let lines: Vec<String> = String::from_utf8(output.clone())
+            .unwrap()
+            .lines()
+            .map(String::from)
+            .collect();
+        let expected: Vec<String> = (0..10).map(|index| format!("{{\"index\":{}}}", index)).collect();
+        assert_eq!(expected, lines);
+
+        let output = &mut Vec::new();
+        let result = generate(output, 10, 3, true, |_, index| match index {
+            5 => Err(CLIError::UnsupportedLanguage),
+            index => Ok(serde_json::json!({ "index": index })),
+        });
+        assert!(result.is_err());
+        assert_eq!(5, String::from_utf8(output.clone()).unwrap().lines().count());
+    }
+
+    /// Returns the names and aliases of the given networks, in order.
+    fn names(networks: &[(&'static str, &'static [&'static str])]) -> Vec<&'static str> {
+        networks
+            .iter()
+            .flat_map(|(name, aliases)| core::iter::once(name).chain(aliases.iter()))
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_network_names() {
+        use crate::model::Network;
+        use crate::{bitcoin, monero, zcash};
+
+        assert_eq!(
+            names(&[
+                (bitcoin::Mainnet::NAME, bitcoin::Mainnet::ALIASES),
+                (bitcoin::Testnet::NAME, bitcoin::Testnet::ALIASES),
+            ]),
+            option::NETWORKS_BITCOIN
+        );
+        assert_eq!(
+            names(&[
+                (monero::Mainnet::NAME, monero::Mainnet::ALIASES),
+                (monero::Stagenet::NAME, monero::Stagenet::ALIASES),
+                (monero::Testnet::NAME, monero::Testnet::ALIASES),
+            ]),
+            option::NETWORKS_MONERO
+        );
+        assert_eq!(
+            names(&[
+                (zcash::Mainnet::NAME, zcash::Mainnet::ALIASES),
+                (zcash::Testnet::NAME, zcash::Testnet::ALIASES),
+            ]),
+            option::NETWORKS_ZCASH
+        );
+    }
+}
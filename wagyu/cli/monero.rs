@@ -1,22 +1,24 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{Mnemonic, PrivateKey, PublicKey};
+use crate::cli::{self, flag, generate, import, option, subcommand, to_json, types::*, CLIError, CLI};
+use crate::model::{Mnemonic, Network, PrivateKey, PublicKey};
 use crate::monero::{
     format::MoneroFormat, wordlist::*, Mainnet as MoneroMainnet, MoneroAddress, MoneroMnemonic, MoneroNetwork,
     MoneroPublicKey, MoneroWordlist, Stagenet as MoneroStagenet, Testnet as MoneroTestnet,
 };
+use crate::wallet::Wallet;
 
 use clap::{ArgMatches, Values};
 use colored::*;
 use core::{fmt, fmt::Display, str::FromStr};
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::io;
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct MoneroWallet {
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct MoneroWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -37,6 +39,10 @@ struct MoneroWallet {
     pub network: Option<String>,
 }
 
+impl Wallet for MoneroWallet {
+    const COIN: &'static str = "monero";
+}
+
 impl MoneroWallet {
     pub fn new<N: MoneroNetwork, W: MoneroWordlist, R: Rng>(
         rng: &mut R,
@@ -49,7 +55,7 @@ impl MoneroWallet {
         let public_key = private_key.to_public_key();
         let public_spend_key = public_key.to_public_spend_key().unwrap();
         let public_view_key = public_key.to_public_view_key().unwrap();
-        let address = public_key.to_address(format)?;
+        let address = private_key.to_address(format)?;
         Ok(Self {
             mnemonic: Some(mnemonic.to_string()),
             private_spend_key: Some(hex::encode(private_spend_key)),
@@ -75,7 +81,7 @@ impl MoneroWallet {
         let public_key = private_key.to_public_key();
         let public_spend_key = public_key.to_public_spend_key().unwrap();
         let public_view_key = public_key.to_public_view_key().unwrap();
-        let address = public_key.to_address(format)?;
+        let address = private_key.to_address(format)?;
         Ok(Self {
             mnemonic: Some(mnemonic.to_string()),
             private_spend_key: Some(hex::encode(private_spend_key)),
@@ -106,8 +112,9 @@ impl MoneroWallet {
         let public_key = private_key.to_public_key();
         let public_spend_key = public_key.to_public_spend_key().unwrap();
         let public_view_key = public_key.to_public_view_key().unwrap();
-        let address = public_key.to_address(format)?;
+        let address = private_key.to_address(format)?;
         Ok(Self {
+            mnemonic: Some(mnemonic.to_string()),
             private_spend_key: Some(hex::encode(private_spend_key)),
             private_view_key: Some(hex::encode(private_view_key)),
             public_spend_key: Some(hex::encode(public_spend_key)),
@@ -233,7 +240,9 @@ pub struct MoneroOptions {
     json: bool,
     language: String,
     network: String,
+    sample_payment_id: bool,
     subcommand: Option<String>,
+    threads: usize,
     // Import subcommand
     address: Option<String>,
     mnemonic: Option<String>,
@@ -252,7 +261,9 @@ impl Default for MoneroOptions {
             json: false,
             language: "english".into(),
             network: "mainnet".into(),
+            sample_payment_id: false,
             subcommand: None,
+            threads: 1,
             // Import subcommand
             address: None,
             mnemonic: None,
@@ -269,16 +280,17 @@ impl MoneroOptions {
         options.iter().for_each(|option| match *option {
             "address" => self.address(arguments.value_of(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "format" => self.format(arguments.value_of(option)),
             "integrated" => self.integrated(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
             "language" => self.language(arguments.value_of(option)),
-            "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
             "private spend" => self.private_spend(arguments.value_of(option)),
             "private view" => self.private_view(arguments.value_of(option)),
             "public spend" => self.public_spend(arguments.value_of(option)),
             "public view" => self.public_view(arguments.value_of(option)),
             "subaddress" => self.subaddress(arguments.values_of(option)),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
             _ => (),
         });
     }
@@ -298,6 +310,31 @@ impl MoneroOptions {
         }
     }
 
+    /// Sets `format` to the specified format, overriding its previous state.
+    /// An integrated address without a payment ID samples a random payment ID for each wallet,
+    /// and a subaddress without indices is the first subaddress of the primary account.
+    /// If the specified argument is `None`, then no change occurs.
+    fn format(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("integrated") => match self.format {
+                MoneroFormat::Integrated(_) => (),
+                _ => self.sample_payment_id = true,
+            },
+            Some("standard") => {
+                self.format = MoneroFormat::Standard;
+                self.sample_payment_id = false;
+            }
+            Some("subaddress") => match self.format {
+                MoneroFormat::Subaddress(_, _) => (),
+                _ => {
+                    self.format = MoneroFormat::Subaddress(0, 1);
+                    self.sample_payment_id = false;
+                }
+            },
+            _ => (),
+        };
+    }
+
     /// Sets `format` to an integrated address with the specified payment ID, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn integrated(&mut self, argument: Option<&str>) {
@@ -305,6 +342,7 @@ impl MoneroOptions {
             let mut payment_id = [0u8; 8];
             payment_id.copy_from_slice(&hex::decode(id).unwrap());
             self.format = MoneroFormat::Integrated(payment_id);
+            self.sample_payment_id = false;
         }
     }
 
@@ -346,9 +384,9 @@ impl MoneroOptions {
     /// If the specified argument is `None`, then no change occurs.
     fn network(&mut self, argument: Option<&str>) {
         match argument {
-            Some("mainnet") => self.network = "mainnet".into(),
-            Some("stagenet") => self.network = "stagenet".into(),
-            Some("testnet") => self.network = "testnet".into(),
+            Some(network) if MoneroMainnet::is_name(network) => self.network = MoneroMainnet::NAME.into(),
+            Some(network) if MoneroStagenet::is_name(network) => self.network = MoneroStagenet::NAME.into(),
+            Some(network) if MoneroTestnet::is_name(network) => self.network = MoneroTestnet::NAME.into(),
             _ => (),
         };
     }
@@ -394,6 +432,24 @@ impl MoneroOptions {
                 .map(|i| i.to_string().parse::<u32>().unwrap())
                 .collect();
             self.format = MoneroFormat::Subaddress(index[0], index[1]);
+            self.sample_payment_id = false;
+        }
+    }
+
+    /// Sets `threads` to the specified number of threads, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = threads;
+        }
+    }
+
+    /// Returns the format of a wallet, with a payment ID sampled from the given RNG if the
+    /// format is an integrated address without a specified payment ID.
+    fn to_format<R: Rng>(&self, rng: &mut R) -> MoneroFormat {
+        match self.sample_payment_id {
+            true => MoneroFormat::Integrated(rng.gen()),
+            false => self.format,
         }
     }
 }
@@ -408,10 +464,12 @@ impl CLI for MoneroCLI {
     const FLAGS: &'static [FlagType] = &[flag::JSON];
     const OPTIONS: &'static [OptionType] = &[
         option::COUNT,
+        option::FORMAT_MONERO,
         option::INTEGRATED_MONERO,
         option::LANGUAGE_MONERO,
         option::NETWORK_MONERO,
         option::SUBADDRESS_MONERO,
+        option::THREADS,
     ];
     const SUBCOMMANDS: &'static [SubCommandType] = &[subcommand::IMPORT_MONERO];
 
@@ -443,13 +501,13 @@ impl CLI for MoneroCLI {
                     arguments,
                     &[
                         "address",
-                        "mnemonic",
                         "private spend",
                         "private view",
                         "public spend",
                         "public view",
                     ],
                 );
+                options.mnemonic(cli::secret(arguments, "mnemonic", None)?.as_deref());
             }
             _ => {}
         };
@@ -464,61 +522,71 @@ impl CLI for MoneroCLI {
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
                     Some("import") => {
-                        if let Some(mnemonic) = options.mnemonic {
-                            vec![
-                                MoneroWallet::from_mnemonic::<N, ChineseSimplified>(&mnemonic, &options.format)
-                                    .or(MoneroWallet::from_mnemonic::<N, Dutch>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, English>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, EnglishOld>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Esperanto>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, French>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, German>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Italian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Japanese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Lojban>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Portuguese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Russian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Spanish>(&mnemonic, &options.format))?,
-                            ]
-                        } else if let Some(private_spend_key) = options.private_spend_key {
-                            vec![MoneroWallet::from_private_spend_key::<N, W>(
-                                &private_spend_key,
-                                &options.format,
-                            )?]
-                        } else if let Some(private_view_key) = options.private_view_key {
-                            vec![MoneroWallet::from_private_view_key::<N>(
-                                &private_view_key,
-                                &options.format,
-                            )?]
-                        } else if let Some(public_spend_key) = options.public_spend_key {
-                            if let Some(public_view_key) = options.public_view_key {
+                        let format = &options.to_format(&mut StdRng::from_entropy());
+                        if let Some(mnemonic) = &options.mnemonic {
+                            vec![MoneroWallet::from_mnemonic::<N, ChineseSimplified>(mnemonic, format)
+                                .or(MoneroWallet::from_mnemonic::<N, Dutch>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, English>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, EnglishOld>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Esperanto>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, French>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, German>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Italian>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Japanese>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Lojban>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Portuguese>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Russian>(mnemonic, format))
+                                .or(MoneroWallet::from_mnemonic::<N, Spanish>(mnemonic, format))?]
+                        } else if let Some(private_spend_key) = &options.private_spend_key {
+                            vec![MoneroWallet::from_private_spend_key::<N, W>(private_spend_key, format)?]
+                        } else if let Some(private_view_key) = &options.private_view_key {
+                            vec![MoneroWallet::from_private_view_key::<N>(private_view_key, format)?]
+                        } else if let Some(public_spend_key) = &options.public_spend_key {
+                            if let Some(public_view_key) = &options.public_view_key {
                                 vec![MoneroWallet::from_public_key::<N>(
-                                    &public_spend_key,
-                                    &public_view_key,
-                                    &options.format,
+                                    public_spend_key,
+                                    public_view_key,
+                                    format,
                                 )?]
                             } else {
                                 vec![]
                             }
-                        } else if let Some(address) = options.address {
-                            vec![MoneroWallet::from_address::<MoneroMainnet>(&address)
-                                .or(MoneroWallet::from_address::<MoneroTestnet>(&address))?]
+                        } else if let Some(address) = &options.address {
+                            vec![import(
+                                "address",
+                                N::NAME,
+                                &[
+                                    (MoneroMainnet::NAME, &|| {
+                                        MoneroWallet::from_address::<MoneroMainnet>(address)
+                                    }),
+                                    (MoneroStagenet::NAME, &|| {
+                                        MoneroWallet::from_address::<MoneroStagenet>(address)
+                                    }),
+                                    (MoneroTestnet::NAME, &|| {
+                                        MoneroWallet::from_address::<MoneroTestnet>(address)
+                                    }),
+                                ],
+                            )?]
                         } else {
                             vec![]
                         }
                     }
-                    _ => (0..options.count)
-                        .flat_map(|_| {
-                            match MoneroWallet::new::<N, W, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            }
-                        })
-                        .collect(),
+                    _ => {
+                        return generate(
+                            &mut io::stdout(),
+                            options.count,
+                            options.threads,
+                            options.json,
+                            |rng, _| {
+                                let format = options.to_format(rng);
+                                MoneroWallet::new::<N, W, _>(rng, &format)
+                            },
+                        )
+                    }
                 };
 
             match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
+                true => println!("{}\n", to_json(&wallets)?),
                 false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
             };
 
@@ -599,3 +667,85 @@ impl CLI for MoneroCLI {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::NetworkImport;
+
+    type N = MoneroMainnet;
+
+    const ADDRESS: &str =
+        "48fRSJiQSp3Da61k8NSR5J9ibWMBkrJHL3hGDxSaZJvsfK7jpigPWyyGy5jqs8MSgeCBQb1HR4NDS84goPRaLV2xTungkh5";
+
+    #[test]
+    fn test_json_schema() {
+        let format = &MoneroFormat::Standard;
+        let wallet = MoneroWallet::new::<N, English, _>(&mut StdRng::seed_from_u64(0), format).unwrap();
+        let expected = serde_json::json!({
+            "mnemonic": "hectare almost jockey lava peaches swiftly nimbly fazed tell rays aided vibrate begun hope error oatmeal syndrome soggy hexagon tail ongoing weekday pigment renting swiftly",
+            "private_spend_key": "bc3f37694ffe322ea0c35b8fa3c9cee8e22ffadfa3f464f8e92bbcc7bc8d220e",
+            "private_view_key": "b46ddd3d4ef1c47528b8c184aa8fe9fad95f45f7d00a39c8699a6290cee2a608",
+            "public_spend_key": "16df7ee7da45d7efedffced23d7de8b11280e813eee3025ae7763993c6fbbbfa",
+            "public_view_key": "69cbd36bbdd1ba5b332dac8f2888e6c2c8fd50b2d928ef18a231928944fcabec",
+            "address": "42VP7kU2qJ6h8csbX5czT9WcpjJfSxf3FGCtH8JnknskitKhS9pffQDGFkhX1yApSyZafFwzqBqLr57yds8mapZ8TfUfnvb",
+            "format": "standard",
+            "network": "mainnet"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+
+        let wallet = MoneroWallet::from_address::<N>(ADDRESS).unwrap();
+        let expected = serde_json::json!({
+            "address": ADDRESS,
+            "format": "standard",
+            "network": "mainnet"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+    }
+
+    #[test]
+    fn test_format() {
+        let mut options = MoneroOptions::default();
+        options.format(Some("integrated"));
+        assert_eq!(
+            MoneroFormat::Integrated([178, 214, 168, 126, 192, 105, 52, 255]),
+            options.to_format(&mut StdRng::seed_from_u64(0))
+        );
+
+        // A specified payment ID is used for each wallet.
+        options.integrated(Some("b2d6a87ec06934ff"));
+        options.format(Some("integrated"));
+        assert_eq!(options.format, options.to_format(&mut StdRng::seed_from_u64(1)));
+
+        let mut options = MoneroOptions::default();
+        options.format(Some("subaddress"));
+        assert_eq!(MoneroFormat::Subaddress(0, 1), options.format);
+        options.format(Some("standard"));
+        assert_eq!(MoneroFormat::Standard, options.to_format(&mut StdRng::seed_from_u64(0)));
+    }
+
+    #[test]
+    fn test_network_mismatch() {
+        let networks: &[NetworkImport<MoneroWallet>] = &[
+            (MoneroMainnet::NAME, &|| {
+                MoneroWallet::from_address::<MoneroMainnet>(ADDRESS)
+            }),
+            (MoneroStagenet::NAME, &|| {
+                MoneroWallet::from_address::<MoneroStagenet>(ADDRESS)
+            }),
+            (MoneroTestnet::NAME, &|| {
+                MoneroWallet::from_address::<MoneroTestnet>(ADDRESS)
+            }),
+        ];
+        match import("address", MoneroStagenet::NAME, networks) {
+            Err(error @ CLIError::NetworkMismatch(..)) => assert_eq!(
+                "the address is for mainnet, but stagenet was requested",
+                error.to_string()
+            ),
+            _ => panic!("expected a network mismatch"),
+        }
+
+        let wallet = import("address", MoneroMainnet::NAME, networks).unwrap();
+        assert_eq!(ADDRESS, wallet.address.unwrap());
+    }
+}
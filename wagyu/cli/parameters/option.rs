@@ -3,10 +3,15 @@ use crate::cli::types::*;
 // Format
 // (argument, conflicts, possible_values, requires)
 
+// The network names and their aliases, as accepted by `Network::is_name`
+pub const NETWORKS_BITCOIN: &[&str] = &["mainnet", "main", "bitcoin", "testnet", "test", "testnet3"];
+pub const NETWORKS_MONERO: &[&str] = &["mainnet", "main", "stagenet", "stage", "testnet", "test"];
+pub const NETWORKS_ZCASH: &[&str] = &["mainnet", "main", "testnet", "test"];
+
 // Global
 
 pub const COUNT: OptionType = (
-    "[count] -c --count=[count] 'Generates a specified number of wallets'",
+    "[count] -c --count=[count] 'Generates a specified number of wallets, or of sequential indices for an HD wallet'",
     &[],
     &[],
     &[],
@@ -23,6 +28,12 @@ pub const FORMAT_BITCOIN: OptionType = (
     &["bech32", "legacy", "segwit"],
     &[],
 );
+pub const FORMAT_MONERO: OptionType = (
+    "[format] -f --format=[format] 'Generates a wallet with a specified format'",
+    &[],
+    &["integrated", "standard", "subaddress"],
+    &[],
+);
 pub const FORMAT_ZCASH: OptionType = (
     "[format] -f --format=[format] 'Generates a wallet with a specified format'",
     &[],
@@ -57,19 +68,19 @@ pub const LANGUAGE_MONERO: OptionType = (
 pub const NETWORK_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Generates a wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    NETWORKS_BITCOIN,
     &[],
 );
 pub const NETWORK_MONERO: OptionType = (
     "[network] -n --network=[network] 'Generates a wallet for a specified network'",
     &[],
-    &["mainnet", "stagenet", "testnet"],
+    NETWORKS_MONERO,
     &[],
 );
 pub const NETWORK_ZCASH: OptionType = (
     "[network] -n --network=[network] 'Generates a wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    NETWORKS_ZCASH,
     &[],
 );
 pub const SUBADDRESS_MONERO: OptionType = (
@@ -78,12 +89,24 @@ pub const SUBADDRESS_MONERO: OptionType = (
     &[],
     &[],
 );
+pub const THREADS: OptionType = (
+    "[threads] -t --threads=[threads] 'Generates the specified number of wallets on a number of threads'",
+    &[],
+    &[],
+    &["count"],
+);
 
 // Import
 
 pub const ADDRESS: OptionType = (
     "[address] --address=[address] 'Imports a partial wallet for a specified address'",
-    &["count", "network", "private", "public"],
+    &["count", "private", "public"],
+    &[],
+    &[],
+);
+pub const ADDRESS_IMPORT_BITCOIN: OptionType = (
+    "[address] --address=[address] 'Imports a partial wallet for a specified address'",
+    &["count", "extended private", "mnemonic", "private", "public", "wif"],
     &[],
     &[],
 );
@@ -93,12 +116,24 @@ pub const DIVERSIFIER_IMPORT_ZCASH: OptionType = (
     &[],
     &[],
 );
+pub const EXTENDED_PRIVATE_KEY_IMPORT_BITCOIN: OptionType = (
+    "[extended private] --extended-private-key=[extended private key] 'Imports a wallet for a specified extended private key'",
+    &["address", "count", "mnemonic", "private", "public", "wif"],
+    &[],
+    &[],
+);
 pub const FORMAT_IMPORT_BITCOIN: OptionType = (
     "[format] -f --format=[format] 'Imports a wallet with a specified format'",
     &[],
     &["bech32", "legacy", "segwit"],
     &[],
 );
+pub const FORMAT_IMPORT_MONERO: OptionType = (
+    "[format] -f --format=[format] 'Imports a wallet with a specified format'",
+    &["address", "private view"],
+    &["integrated", "standard", "subaddress"],
+    &[],
+);
 pub const INTEGRATED_IMPORT_MONERO: OptionType = (
     "[integrated] -i --integrated=[PaymentID] 'Imports a wallet with a specified payment ID'",
     &["address", "private view", "subaddress"],
@@ -111,6 +146,12 @@ pub const LANGUAGE_IMPORT_MONERO: OptionType = (
     &["chinese_simplified", "dutch", "english", "esperanto", "french", "german", "italian", "japanese", "lojban", "portuguese", "russian", "spanish"],
     &["private spend"],
 );
+pub const MNEMONIC_IMPORT_BITCOIN: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Imports a wallet for a specified mnemonic (in quotes)'",
+    &["address", "count", "extended private", "private", "public", "wif"],
+    &[],
+    &[],
+);
 pub const MNEMONIC_IMPORT_MONERO: OptionType = (
     "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Imports a wallet for a specified mnemonic (in quotes)'",
     &[
@@ -124,21 +165,73 @@ pub const MNEMONIC_IMPORT_MONERO: OptionType = (
     &[],
     &[],
 );
+pub const MNEMONIC_STDIN_IMPORT_BITCOIN: OptionType = (
+    "[mnemonic stdin] --mnemonic-stdin 'Imports a wallet for a mnemonic read from standard input'",
+    &[
+        "address",
+        "count",
+        "extended private",
+        "mnemonic",
+        "private",
+        "public",
+        "wif",
+    ],
+    &[],
+    &[],
+);
+pub const MNEMONIC_STDIN_IMPORT_MONERO: OptionType = (
+    "[mnemonic stdin] --mnemonic-stdin 'Imports a wallet for a mnemonic read from standard input'",
+    &[
+        "address",
+        "count",
+        "mnemonic",
+        "public spend",
+        "public view",
+        "private spend",
+        "private view",
+    ],
+    &[],
+    &[],
+);
 pub const NETWORK_IMPORT_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Imports a wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    NETWORKS_BITCOIN,
     &[],
 );
 pub const NETWORK_IMPORT_MONERO: OptionType = (
     "[network] -n --network=[network] 'Imports a wallet for a specified network'",
     &[],
-    &["mainnet", "stagenet", "testnet"],
+    NETWORKS_MONERO,
+    &[],
+);
+pub const NETWORK_IMPORT_ZCASH: OptionType = (
+    "[network] -n --network=[network] 'Imports a wallet for a specified network'",
+    &[],
+    NETWORKS_ZCASH,
+    &[],
+);
+pub const PASSWORD_IMPORT_BITCOIN: OptionType = (
+    "[password] -p --password=[password] 'Imports a wallet with a specified mnemonic password'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const PASSWORD_STDIN_IMPORT_BITCOIN: OptionType = (
+    "[password stdin] --password-stdin 'Imports a wallet with a mnemonic password read from standard input'",
+    &["password"],
+    &[],
+    &[],
+);
+pub const PATH_IMPORT_BITCOIN: OptionType = (
+    "[path] --path=[\"path\"] 'Imports a wallet for a specified derivation path (in quotes)'",
+    &["address", "private", "public", "wif"],
+    &[],
     &[],
 );
 pub const PRIVATE: OptionType = (
     "[private] --private=[private key] 'Imports a wallet for a specified private key'",
-    &["address", "count", "network", "public"],
+    &["address", "count", "public"],
     &[],
     &[],
 );
@@ -178,6 +271,12 @@ pub const SUBADDRESS_IMPORT_MONERO: OptionType = (
     &[],
     &[],
 );
+pub const WIF_IMPORT_BITCOIN: OptionType = (
+    "[wif] --wif=[wif] 'Imports a wallet for a specified private key in wallet import format'",
+    &["address", "count", "extended private", "mnemonic", "private", "public"],
+    &[],
+    &[],
+);
 
 // HD
 
@@ -235,13 +334,13 @@ pub const LANGUAGE_HD: OptionType = (
 pub const NETWORK_HD_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Generates an HD wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    NETWORKS_BITCOIN,
     &[],
 );
 pub const NETWORK_HD_ZCASH: OptionType = (
     "[network] -n --network=[network] 'Generates an HD wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    NETWORKS_ZCASH,
     &[],
 );
 pub const PASSWORD_HD: OptionType = (
@@ -250,6 +349,12 @@ pub const PASSWORD_HD: OptionType = (
     &[],
     &[],
 );
+pub const PASSWORD_STDIN_HD: OptionType = (
+    "[password stdin] --password-stdin 'Generates an HD wallet with a password read from standard input'",
+    &["password"],
+    &[],
+    &[],
+);
 pub const WORD_COUNT: OptionType = (
     "[word count] -w --word-count=[word count] 'Generates an HD wallet with a specified word count'",
     &[],
@@ -311,7 +416,13 @@ pub const EXTENDED_PRIVATE: OptionType = (
 pub const NETWORK_IMPORT_HD_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Imports an HD wallet for a specified network'",
     &[],
-    &["mainnet", "testnet"],
+    NETWORKS_BITCOIN,
+    &[],
+);
+pub const NETWORK_IMPORT_HD_ZCASH: OptionType = (
+    "[network] -n --network=[network] 'Imports an HD wallet for a specified network'",
+    &[],
+    NETWORKS_ZCASH,
     &[],
 );
 pub const INDEX_IMPORT_HD: OptionType = (
@@ -338,6 +449,57 @@ pub const PASSWORD_IMPORT_HD: OptionType = (
     &[],
     &[],
 );
+pub const MNEMONIC_STDIN: OptionType = (
+    "[mnemonic stdin] --mnemonic-stdin 'Imports an HD wallet for a mnemonic read from standard input'",
+    &["count", "extended private", "extended public", "mnemonic"],
+    &[],
+    &[],
+);
+pub const PASSWORD_STDIN_IMPORT_HD: OptionType = (
+    "[password stdin] --password-stdin 'Imports an HD wallet with a password read from standard input'",
+    &["extended private", "extended public", "password"],
+    &[],
+    &[],
+);
+
+// Derive
+
+pub const COUNT_DERIVE: OptionType = (
+    "[count] -c --count=[count] 'Derives a specified number of sequential child addresses of the path'",
+    &[],
+    &[],
+    &[],
+);
+pub const EXTENDED_PUBLIC_KEY_DERIVE: OptionType = (
+    "<extended public> --extended-public-key=<extended public key> 'Derives the addresses of a specified extended public key'",
+    &[],
+    &[],
+    &[],
+);
+pub const FORMAT_DERIVE_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Derives the addresses with a specified format'",
+    &[],
+    &["bech32", "legacy", "segwit"],
+    &[],
+);
+pub const NETWORK_DERIVE_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Derives the addresses for a specified network'",
+    &[],
+    NETWORKS_BITCOIN,
+    &[],
+);
+pub const PATH_DERIVE: OptionType = (
+    "[path] --path=[\"path\"] 'Derives the addresses of a specified non-hardened path, which may end in a range (e.g. \"m/0/0..20\")'",
+    &[],
+    &[],
+    &[],
+);
+pub const START_DERIVE: OptionType = (
+    "[start] --start=[start] 'Derives the sequential child addresses of the path from a specified index'",
+    &[],
+    &[],
+    &[],
+);
 
 // Transaction
 
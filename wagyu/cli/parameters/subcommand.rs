@@ -5,6 +5,25 @@ use clap::AppSettings;
 // Format
 // (name, about, options, settings)
 
+pub const DERIVE_BITCOIN: SubCommandType = (
+    "derive",
+    "Derives watch-only wallets from an extended public key (include -h for more options)",
+    &[
+        option::COUNT_DERIVE,
+        option::EXTENDED_PUBLIC_KEY_DERIVE,
+        option::FORMAT_DERIVE_BITCOIN,
+        option::NETWORK_DERIVE_BITCOIN,
+        option::PATH_DERIVE,
+        option::START_DERIVE,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const HD_BITCOIN: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
@@ -14,7 +33,9 @@ pub const HD_BITCOIN: SubCommandType = (
         option::LANGUAGE_HD,
         option::NETWORK_HD_BITCOIN,
         option::PASSWORD_HD,
+        option::PASSWORD_STDIN_HD,
         option::WORD_COUNT,
+        option::THREADS,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -33,7 +54,9 @@ pub const HD_ETHEREUM: SubCommandType = (
         option::INDICES_HD,
         option::LANGUAGE_HD,
         option::PASSWORD_HD,
+        option::PASSWORD_STDIN_HD,
         option::WORD_COUNT,
+        option::THREADS,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -49,7 +72,12 @@ pub const HD_ZCASH: SubCommandType = (
         option::COUNT,
         option::DERIVATION_ZCASH,
         option::DIVERSIFIER_IMPORT_ZCASH,
+        option::LANGUAGE_HD,
         option::NETWORK_HD_ZCASH,
+        option::PASSWORD_HD,
+        option::PASSWORD_STDIN_HD,
+        option::WORD_COUNT,
+        option::THREADS,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -62,11 +90,18 @@ pub const IMPORT_BITCOIN: SubCommandType = (
     "import",
     "Imports a wallet (include -h for more options)",
     &[
-        option::ADDRESS,
+        option::ADDRESS_IMPORT_BITCOIN,
+        option::EXTENDED_PRIVATE_KEY_IMPORT_BITCOIN,
         option::FORMAT_IMPORT_BITCOIN,
+        option::MNEMONIC_IMPORT_BITCOIN,
+        option::MNEMONIC_STDIN_IMPORT_BITCOIN,
         option::NETWORK_IMPORT_BITCOIN,
+        option::PASSWORD_IMPORT_BITCOIN,
+        option::PASSWORD_STDIN_IMPORT_BITCOIN,
+        option::PATH_IMPORT_BITCOIN,
         option::PRIVATE,
         option::PUBLIC,
+        option::WIF_IMPORT_BITCOIN,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -93,9 +128,11 @@ pub const IMPORT_MONERO: SubCommandType = (
     "Imports a wallet (include -h for more options)",
     &[
         option::ADDRESS,
+        option::FORMAT_IMPORT_MONERO,
         option::INTEGRATED_IMPORT_MONERO,
         option::LANGUAGE_IMPORT_MONERO,
         option::MNEMONIC_IMPORT_MONERO,
+        option::MNEMONIC_STDIN_IMPORT_MONERO,
         option::NETWORK_IMPORT_MONERO,
         option::PRIVATE_SPEND_KEY_MONERO,
         option::PRIVATE_VIEW_KEY_MONERO,
@@ -117,6 +154,7 @@ pub const IMPORT_ZCASH: SubCommandType = (
     &[
         option::ADDRESS,
         option::DIVERSIFIER_IMPORT_ZCASH,
+        option::NETWORK_IMPORT_ZCASH,
         option::PRIVATE,
         option::PUBLIC,
     ],
@@ -140,7 +178,9 @@ pub const IMPORT_HD_BITCOIN: SubCommandType = (
         option::NETWORK_IMPORT_HD_BITCOIN,
         option::INDEX_IMPORT_HD,
         option::MNEMONIC,
+        option::MNEMONIC_STDIN,
         option::PASSWORD_IMPORT_HD,
+        option::PASSWORD_STDIN_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -160,7 +200,9 @@ pub const IMPORT_HD_ETHEREUM: SubCommandType = (
         option::INDEX_IMPORT_HD,
         option::INDICES_IMPORT_HD,
         option::MNEMONIC,
+        option::MNEMONIC_STDIN,
         option::PASSWORD_IMPORT_HD,
+        option::PASSWORD_STDIN_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -180,6 +222,11 @@ pub const IMPORT_HD_ZCASH: SubCommandType = (
         option::EXTENDED_PUBLIC,
         option::EXTENDED_PRIVATE,
         option::INDEX_IMPORT_HD,
+        option::MNEMONIC,
+        option::MNEMONIC_STDIN,
+        option::NETWORK_IMPORT_HD_ZCASH,
+        option::PASSWORD_IMPORT_HD,
+        option::PASSWORD_STDIN_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
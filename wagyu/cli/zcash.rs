@@ -1,5 +1,11 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{ExtendedPrivateKey, ExtendedPublicKey, PrivateKey, PublicKey, Transaction};
+use crate::bitcoin::{wordlist::*, BitcoinMnemonic, BitcoinWordlist, Mainnet as BitcoinMainnet};
+use crate::cli::{
+    self, batch_end, flag, generate, import, option, subcommand, to_json, types::*, CLIError, CLI, PASSWORD_VARIABLE,
+};
+use crate::model::{
+    ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, Network, PrivateKey, PublicKey, Transaction,
+};
+use crate::wallet::Wallet;
 use crate::zcash::{
     format::ZcashFormat, initialize_proving_context, initialize_verifying_context, load_sapling_parameters,
     Mainnet as ZcashMainnet, Outpoint, SignatureHash, Testnet as ZcashTestnet, ZcashAddress, ZcashAmount,
@@ -14,6 +20,7 @@ use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
+use std::io;
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
@@ -36,13 +43,17 @@ pub struct HdValues {
 }
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct ZcashWallet {
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ZcashWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub extended_private_key: Option<String>,
+    pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(rename = "xpriv", skip_serializing_if = "Option::is_none")]
+    pub extended_private_key: Option<String>,
+    #[serde(rename = "xpub", skip_serializing_if = "Option::is_none")]
     pub extended_public_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
@@ -64,6 +75,10 @@ struct ZcashWallet {
     pub transaction_hex: Option<String>,
 }
 
+impl Wallet for ZcashWallet {
+    const COIN: &'static str = "zcash";
+}
+
 impl ZcashWallet {
     pub fn new<N: ZcashNetwork, R: Rng>(rng: &mut R, format: &ZcashFormat) -> Result<Self, CLIError> {
         let private_key = match format {
@@ -85,29 +100,40 @@ impl ZcashWallet {
         })
     }
 
-    pub fn new_hd<N: ZcashNetwork, R: Rng>(rng: &mut R, path: &str, format: &ZcashFormat) -> Result<Self, CLIError> {
-        let seed: [u8; 32] = rng.gen();
-        let master_extended_private_key = ZcashExtendedPrivateKey::<N>::new_master(&seed, format)?;
-        let derivation_path = ZcashDerivationPath::from_str(path)?;
-        let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
-        let extended_public_key = extended_private_key.to_extended_public_key();
-        let private_key = extended_private_key.to_private_key();
-        let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(format)?;
+    /// Returns the wallet at the given path of the ZIP 32 master key of the seed of the given
+    /// BIP 39 mnemonic.
+    pub fn from_mnemonic<N: ZcashNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+        format: &ZcashFormat,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<BitcoinMainnet, W>::from_phrase(mnemonic)?;
+        let seed = mnemonic.to_seed(*password)?;
+        let master_extended_private_key = ZcashExtendedPrivateKey::<N>::new_master(&seed, format)?.to_string();
         Ok(Self {
-            path: Some(path.to_string()),
-            extended_private_key: Some(extended_private_key.to_string()),
-            extended_public_key: Some(extended_public_key.to_string()),
-            private_key: Some(private_key.to_string()),
-            public_key: Some(public_key.to_string()),
-            address: Some(address.to_string()),
-            format: Some(address.format().to_string()),
-            diversifier: address.to_diversifier(),
-            network: Some(N::NAME.to_string()),
-            ..Default::default()
+            password: password.map(String::from),
+            mnemonic: Some(mnemonic.to_string()),
+            ..Self::from_extended_private_key::<N>(&master_extended_private_key, &Some(path.into()), format)?
         })
     }
 
+    pub fn from_mnemonic_in_any_language<N: ZcashNetwork>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        path: &str,
+        format: &ZcashFormat,
+    ) -> Result<Self, CLIError> {
+        Self::from_mnemonic::<N, ChineseSimplified>(mnemonic, password, path, format)
+            .or_else(|_| Self::from_mnemonic::<N, ChineseTraditional>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, English>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, French>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Italian>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Japanese>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Korean>(mnemonic, password, path, format))
+            .or_else(|_| Self::from_mnemonic::<N, Spanish>(mnemonic, password, path, format))
+    }
+
     pub fn from_extended_private_key<N: ZcashNetwork>(
         extended_private_key: &str,
         path: &Option<String>,
@@ -326,6 +352,14 @@ impl Display for ZcashWallet {
                 Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
                 _ => "".to_owned(),
             },
+            match &self.password {
+                Some(password) => format!("      {}             {}\n", "Password".cyan().bold(), password),
+                _ => "".to_owned(),
+            },
+            match &self.mnemonic {
+                Some(mnemonic) => format!("      {}             {}\n", "Mnemonic".cyan().bold(), mnemonic),
+                _ => "".to_owned(),
+            },
             match &self.extended_private_key {
                 Some(extended_private_key) => format!(
                     "      {} {}\n",
@@ -416,6 +450,7 @@ pub struct ZcashOptions {
     json: bool,
     network: String,
     subcommand: Option<String>,
+    threads: usize,
     // HD and Import HD subcommands
     account: u32,
     chain: u32,
@@ -451,6 +486,7 @@ impl Default for ZcashOptions {
             json: false,
             network: "mainnet".into(),
             subcommand: None,
+            threads: 1,
             // HD and Import HD subcommands
             account: 0,
             chain: 0,
@@ -493,12 +529,15 @@ impl ZcashOptions {
             "format" => self.format(arguments.value_of(option)),
             "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "json" => self.json(arguments.is_present(option)),
+            "language" => self.language(arguments.value_of(option)),
             "lock time" => self.lock_time(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "network" => self.network(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "version" => self.version(arguments.value_of(option)),
+            "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
             _ => (),
         });
     }
@@ -618,6 +657,22 @@ impl ZcashOptions {
         self.json = argument;
     }
 
+    /// Sets `language` to the specified language, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn language(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("chinese_simplified") => self.language = "chinese_simplified".into(),
+            Some("chinese_traditional") => self.language = "chinese_traditional".into(),
+            Some("english") => self.language = "english".into(),
+            Some("french") => self.language = "french".into(),
+            Some("italian") => self.language = "italian".into(),
+            Some("japanese") => self.language = "japanese".into(),
+            Some("korean") => self.language = "korean".into(),
+            Some("spanish") => self.language = "spanish".into(),
+            _ => (),
+        };
+    }
+
     /// Sets `lock_time` to the specified transaction lock time, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn lock_time(&mut self, argument: Option<u32>) {
@@ -626,16 +681,32 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn mnemonic(&mut self, argument: Option<&str>) {
+        if let Some(mnemonic) = argument {
+            self.mnemonic = Some(mnemonic.to_string());
+        }
+    }
+
     /// Sets `network` to the specified network, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn network(&mut self, argument: Option<&str>) {
         match argument {
-            Some("mainnet") => self.network = "mainnet".into(),
-            Some("testnet") => self.network = "testnet".into(),
+            Some(network) if ZcashMainnet::is_name(network) => self.network = ZcashMainnet::NAME.into(),
+            Some(network) if ZcashTestnet::is_name(network) => self.network = ZcashTestnet::NAME.into(),
             _ => (),
         };
     }
 
+    /// Sets `password` to the specified password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.password = Some(password.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -662,6 +733,22 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `threads` to the specified number of threads, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = threads;
+        }
+    }
+
+    /// Sets `word_count` to the specified word count, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn word_count(&mut self, argument: Option<u8>) {
+        if let Some(word_count) = argument {
+            self.word_count = word_count;
+        }
+    }
+
     /// Returns the derivation path with the specified account, index, and path.
     /// If `default` is enabled, then return the default path if no derivation was provided.
     fn to_derivation_path(&self, default: bool) -> Option<String> {
@@ -683,6 +770,15 @@ impl ZcashOptions {
         }
     }
 
+    /// Returns the derivation path of a wallet of a batch of `count` wallets. A custom path is the
+    /// parent of a batch of more than one wallet, whose children are numbered from the index.
+    fn to_batch_derivation_path(&self, count: usize) -> Option<String> {
+        match (self.derivation.as_str(), &self.path) {
+            ("custom", Some(path)) if count > 1 => Some(format!("{}/{}", path, self.index)),
+            _ => self.to_derivation_path(true),
+        }
+    }
+
     /// Sets `version` to the specified transaction version, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn version(&mut self, argument: Option<&str>) {
@@ -705,6 +801,7 @@ impl CLI for ZcashCLI {
         option::DIVERSIFIER_ZCASH,
         option::FORMAT_ZCASH,
         option::NETWORK_ZCASH,
+        option::THREADS,
     ];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
         subcommand::HD_ZCASH,
@@ -717,13 +814,20 @@ impl CLI for ZcashCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
         let mut options = ZcashOptions::default();
-        options.parse(arguments, &["count", "diversifier", "format", "json", "network"]);
+        options.parse(
+            arguments,
+            &["count", "diversifier", "format", "json", "network", "threads"],
+        );
 
         match arguments.subcommand() {
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
-                options.parse(arguments, &["count", "diversifier", "format", "json", "network"]);
-                options.parse(arguments, &["derivation"]);
+                options.parse(
+                    arguments,
+                    &["count", "diversifier", "format", "json", "network", "threads"],
+                );
+                options.parse(arguments, &["derivation", "language", "word count"]);
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
@@ -737,6 +841,8 @@ impl CLI for ZcashCLI {
                     arguments,
                     &["account", "derivation", "extended private", "extended public", "index"],
                 );
+                options.mnemonic(cli::secret(arguments, "mnemonic", None)?.as_deref());
+                options.password(cli::secret(arguments, "password", Some(PASSWORD_VARIABLE))?.as_deref());
             }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
@@ -760,58 +866,132 @@ impl CLI for ZcashCLI {
     /// Generate the Zcash wallet and print the relevant fields
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError> {
-        fn output<N: ZcashNetwork>(options: ZcashOptions) -> Result<(), CLIError> {
+        fn output<N: ZcashNetwork, W: BitcoinWordlist>(options: ZcashOptions) -> Result<(), CLIError> {
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
-                    Some("hd") => match options.to_derivation_path(true) {
-                        Some(path) => (0..options.count)
-                            .flat_map(|_| {
-                                match ZcashWallet::new_hd::<N, _>(&mut StdRng::from_entropy(), &path, &options.format) {
-                                    Ok(wallet) => vec![wallet],
-                                    _ => vec![],
-                                }
-                            })
-                            .collect(),
-                        None => vec![],
-                    },
+                    Some("hd") => {
+                        // Sample one mnemonic and derive the wallets from sequential indices of its seed
+                        let mnemonic = BitcoinMnemonic::<BitcoinMainnet, W>::new_with_count(
+                            &mut StdRng::from_entropy(),
+                            options.word_count,
+                        )?;
+                        let password = options.password.as_deref();
+                        let seed = mnemonic.to_seed(password)?;
+                        let master_extended_private_key =
+                            ZcashExtendedPrivateKey::<N>::new_master(&seed, &options.format)?.to_string();
+                        let mnemonic = mnemonic.to_string();
+                        batch_end(options.index, options.count)?;
+
+                        return generate(
+                            &mut io::stdout(),
+                            options.count,
+                            options.threads,
+                            options.json,
+                            |_, index| {
+                                let mut options = options.clone();
+                                options.index(Some(options.index + index as u32));
+                                let path = &options.to_batch_derivation_path(options.count);
+                                Ok(ZcashWallet {
+                                    password: password.map(String::from),
+                                    mnemonic: Some(mnemonic.clone()),
+                                    ..ZcashWallet::from_extended_private_key::<N>(
+                                        &master_extended_private_key,
+                                        path,
+                                        &options.format,
+                                    )?
+                                })
+                            },
+                        );
+                    }
                     Some("import") => {
-                        if let Some(private_key) = options.private {
-                            vec![
-                                ZcashWallet::from_private_key::<ZcashMainnet>(&private_key, &options.format).or(
-                                    ZcashWallet::from_private_key::<ZcashTestnet>(&private_key, &options.format),
-                                )?,
-                            ]
-                        } else if let Some(public_key) = options.public {
-                            vec![
-                                ZcashWallet::from_public_key::<ZcashMainnet>(&public_key, &options.format).or(
-                                    ZcashWallet::from_public_key::<ZcashTestnet>(&public_key, &options.format),
-                                )?,
-                            ]
-                        } else if let Some(address) = options.address {
-                            vec![ZcashWallet::from_address::<ZcashMainnet>(&address)
-                                .or(ZcashWallet::from_address::<ZcashTestnet>(&address))?]
+                        let format = &options.format;
+                        if let Some(private_key) = &options.private {
+                            vec![import(
+                                "private key",
+                                N::NAME,
+                                &[
+                                    (ZcashMainnet::NAME, &|| {
+                                        ZcashWallet::from_private_key::<ZcashMainnet>(private_key, format)
+                                    }),
+                                    (ZcashTestnet::NAME, &|| {
+                                        ZcashWallet::from_private_key::<ZcashTestnet>(private_key, format)
+                                    }),
+                                ],
+                            )?]
+                        } else if let Some(public_key) = &options.public {
+                            vec![import(
+                                "public key",
+                                N::NAME,
+                                &[
+                                    (ZcashMainnet::NAME, &|| {
+                                        ZcashWallet::from_public_key::<ZcashMainnet>(public_key, format)
+                                    }),
+                                    (ZcashTestnet::NAME, &|| {
+                                        ZcashWallet::from_public_key::<ZcashTestnet>(public_key, format)
+                                    }),
+                                ],
+                            )?]
+                        } else if let Some(address) = &options.address {
+                            vec![import(
+                                "address",
+                                N::NAME,
+                                &[
+                                    (ZcashMainnet::NAME, &|| {
+                                        ZcashWallet::from_address::<ZcashMainnet>(address)
+                                    }),
+                                    (ZcashTestnet::NAME, &|| {
+                                        ZcashWallet::from_address::<ZcashTestnet>(address)
+                                    }),
+                                ],
+                            )?]
                         } else {
                             vec![]
                         }
                     }
                     Some("import-hd") => {
-                        if let Some(extended_private_key) = options.extended_private_key.clone() {
-                            let key = &extended_private_key;
+                        if let Some(mnemonic) = &options.mnemonic {
+                            let password = &options.password.as_deref();
+                            match options.to_derivation_path(true) {
+                                Some(path) => vec![ZcashWallet::from_mnemonic_in_any_language::<N>(
+                                    mnemonic,
+                                    password,
+                                    &path,
+                                    &options.format,
+                                )?],
+                                None => vec![],
+                            }
+                        } else if let Some(key) = &options.extended_private_key {
                             let path = &options.to_derivation_path(false);
                             let format = &options.format;
 
-                            vec![
-                                ZcashWallet::from_extended_private_key::<ZcashMainnet>(key, path, format).or(
-                                    ZcashWallet::from_extended_private_key::<ZcashTestnet>(key, path, format),
-                                )?,
-                            ]
-                        } else if let Some(extended_public_key) = options.extended_public_key.clone() {
-                            let key = &extended_public_key;
+                            vec![import(
+                                "extended private key",
+                                N::NAME,
+                                &[
+                                    (ZcashMainnet::NAME, &|| {
+                                        ZcashWallet::from_extended_private_key::<ZcashMainnet>(key, path, format)
+                                    }),
+                                    (ZcashTestnet::NAME, &|| {
+                                        ZcashWallet::from_extended_private_key::<ZcashTestnet>(key, path, format)
+                                    }),
+                                ],
+                            )?]
+                        } else if let Some(key) = &options.extended_public_key {
                             let path = &options.to_derivation_path(false);
                             let format = &options.format;
 
-                            vec![ZcashWallet::from_extended_public_key::<ZcashMainnet>(key, path, format)
-                                .or(ZcashWallet::from_extended_public_key::<ZcashTestnet>(key, path, format))?]
+                            vec![import(
+                                "extended public key",
+                                N::NAME,
+                                &[
+                                    (ZcashMainnet::NAME, &|| {
+                                        ZcashWallet::from_extended_public_key::<ZcashMainnet>(key, path, format)
+                                    }),
+                                    (ZcashTestnet::NAME, &|| {
+                                        ZcashWallet::from_extended_public_key::<ZcashTestnet>(key, path, format)
+                                    }),
+                                ],
+                            )?]
                         } else {
                             vec![]
                         }
@@ -855,27 +1035,169 @@ impl CLI for ZcashCLI {
                             vec![]
                         }
                     }
-                    _ => (0..options.count)
-                        .flat_map(
-                            |_| match ZcashWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            },
+                    _ => {
+                        return generate(
+                            &mut io::stdout(),
+                            options.count,
+                            options.threads,
+                            options.json,
+                            |rng, _| ZcashWallet::new::<N, _>(rng, &options.format),
                         )
-                        .collect(),
+                    }
                 };
 
             match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
+                true => println!("{}\n", to_json(&wallets)?),
                 false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
             };
 
             Ok(())
         }
 
-        match options.network.as_str() {
-            "testnet" => output::<ZcashTestnet>(options),
-            _ => output::<ZcashMainnet>(options),
+        match options.language.as_str() {
+            "chinese_simplified" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, ChineseSimplified>(options),
+                _ => output::<ZcashMainnet, ChineseSimplified>(options),
+            },
+            "chinese_traditional" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, ChineseTraditional>(options),
+                _ => output::<ZcashMainnet, ChineseTraditional>(options),
+            },
+            "english" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, English>(options),
+                _ => output::<ZcashMainnet, English>(options),
+            },
+            "french" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, French>(options),
+                _ => output::<ZcashMainnet, French>(options),
+            },
+            "italian" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, Italian>(options),
+                _ => output::<ZcashMainnet, Italian>(options),
+            },
+            "japanese" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, Japanese>(options),
+                _ => output::<ZcashMainnet, Japanese>(options),
+            },
+            "korean" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, Korean>(options),
+                _ => output::<ZcashMainnet, Korean>(options),
+            },
+            "spanish" => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, Spanish>(options),
+                _ => output::<ZcashMainnet, Spanish>(options),
+            },
+            _ => match options.network.as_str() {
+                "testnet" => output::<ZcashTestnet, English>(options),
+                _ => output::<ZcashMainnet, English>(options),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::NetworkImport;
+
+    type N = ZcashMainnet;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn sapling(diversifier: &str) -> ZcashFormat {
+        let mut data = [0u8; 11];
+        data.copy_from_slice(&hex::decode(diversifier).unwrap());
+        ZcashFormat::Sapling(Some(data))
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let wallet = ZcashWallet::new::<N, _>(&mut StdRng::seed_from_u64(0), &ZcashFormat::P2PKH).unwrap();
+        let expected = serde_json::json!({
+            "private_key": "L3DMEDQAWzzCpWcqh6KKXtznL3eBGwL4BHsTsSo2eTXBjxuTh4WJ",
+            "public_key": "033ae15bc1fc542421072790183f4b9faa675662d32550a5925fba77b3bddf175a",
+            "address": "t1UcweLprNE6M86whrMrkiAobdMHjrohErz",
+            "format": "p2pkh",
+            "network": "mainnet"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+
+        let format = &sapling("64a5cf3da52e7df3033fa0");
+        let wallet = ZcashWallet::new::<N, _>(&mut StdRng::seed_from_u64(0), format).unwrap();
+        let expected = serde_json::json!({
+            "private_key": "secret-spending-key-main1ktt2slkqdy607kzhueudqucqphjzl7kl506xf78f9w7v00ydythqw0knc4",
+            "public_key": "zviews130aycy0l9r8qaz8fjw3m8mfas0jq5katkhg4zer6ar847n25jdqq6e2trsr8ulwl5wuad6pm6r0z9uqcr40n7f52wd95u68ufks2ujsajemwmuxztzmmu2aqnlaujacmu4vssqmuack9dq84t6g8w0ye9vdjxqmd",
+            "address": "zs1vjju70d99e7lxqel5qfzc2x0vlc4qhqd96qg883c990pfs4sqhhcm644upm030h6939g7glhyvs",
+            "format": "sapling",
+            "diversifier": "64a5cf3da52e7df3033fa0",
+            "network": "mainnet"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+
+        let mnemonic = BitcoinMnemonic::<BitcoinMainnet, English>::new_with_count(&mut StdRng::seed_from_u64(0), 12)
+            .unwrap()
+            .to_string();
+        let wallet = ZcashWallet::from_mnemonic::<N, English>(&mnemonic, &None, "m/32'/133'/0'/0", format).unwrap();
+        let expected = serde_json::json!({
+            "path": "m/32'/133'/0'/0",
+            "mnemonic": "ready relief cabbage liar nation leisure genuine wolf juice logic scale boy",
+            "xpriv": "secret-extended-key-main1q3n92kr4qqqqqq82edugzfc0ps222k3ayhe5yw3s32pr4zyfem9yaynaz4jstjhy4m5s3xdlszc6zhsfs3flq5c7y0tnky6aq680p8mkwef32kzhz44qyysmrjy59l5hvcajvvlf7q7tn7d2gx9q2jgsvu3caeq2rvua7kstcrn45ggvkhr0etn7c56u7rplrvy8w0smlvm9jdz44t77wmn2xuvxf9r2nul5vch789jcj7v0xdn7jfk5a685z8u55exs6wvdh4lhqps890q8v",
+            "xpub": "zxviews1q3n92kr4qqqqqq82edugzfc0ps222k3ayhe5yw3s32pr4zyfem9yaynaz4jstjhy46vsuautkdwwydegue0u9xjjx3vzmawmem6600u7eqegldcq8t7fwnjh8vlrtwph6vz5lqxk9j96pfe0e6rew7f9r508r6n4feuxnxejcrn45ggvkhr0etn7c56u7rplrvy8w0smlvm9jdz44t77wmn2xuvxf9r2nul5vch789jcj7v0xdn7jfk5a685z8u55exs6wvdh4lhqpsql2w27",
+            "private_key": "e90899bf80b1a15e098453f0531e23d73b135d068ef09f767653155857156a02121b1c8942fe97663b2633e9f03cb9f9aa418a05491067238ee40a1b39df5a0bc0e75a210cb5c6fcae7ec535cf0c3f1b08773e1bfb36593455aafde76e6a3718",
+            "public_key": "zviews1ny880zantn3rw28xtlpf5535tqkltk7w7knml8kgx28mwqp6ljt5u4em8c6msd7nq48cp43v3ws2wt7ws7thjfgarec75a2w0p5ekvkquadzzr94cm72ulk9xh8sc0cmppmnuxlmxevng4d2lhnku63hrqvkay9u",
+            "address": "zs1vjju70d99e7lxqel5z3lcl76vjlmnkd858zxvtcetxty8qux7jhz9elryjd5lra3drjrxg4emjz",
+            "format": "sapling",
+            "diversifier": "64a5cf3da52e7df3033fa0",
+            "network": "mainnet"
+        });
+        assert_eq!(expected, serde_json::to_value(&wallet).unwrap());
+    }
+
+    #[test]
+    fn test_import_mnemonic() {
+        let format = &sapling("480f9056e5aa6fc3afbfb7");
+        let wallet = ZcashWallet::from_mnemonic_in_any_language::<N>(PHRASE, &None, "m/32'/133'/0'/0", format).unwrap();
+        assert_eq!(PHRASE, wallet.mnemonic.unwrap());
+        assert_eq!(
+            "zs1fq8eq4h94fhu8talklnc50ca2ht6xxm24mdk22thlp3y255gj9hyd52hzqqxk06pcn9vc6gcfmp",
+            wallet.address.unwrap()
+        );
+
+        let options = ZcashOptions {
+            network: "testnet".into(),
+            ..Default::default()
+        };
+        let path = options.to_derivation_path(true).unwrap();
+        let format = &sapling("7f3934b64bcb20edb35e06");
+        let wallet = ZcashWallet::from_mnemonic::<ZcashTestnet, English>(PHRASE, &None, &path, format).unwrap();
+        assert_eq!("m/32'/1'/0'/0", wallet.path.unwrap());
+        assert_eq!(
+            "ztestsapling10uunfdjtevswmv67qmg3nejgr5z0vr3sh6rj0tquhpa2yveya44ftpspqxzz2a4ym9msvakwycv",
+            wallet.address.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_network_mismatch() {
+        let address = "ztestsapling10uunfdjtevswmv67qmg3nejgr5z0vr3sh6rj0tquhpa2yveya44ftpspqxzz2a4ym9msvakwycv";
+        let networks: &[NetworkImport<ZcashWallet>] = &[
+            (ZcashMainnet::NAME, &|| {
+                ZcashWallet::from_address::<ZcashMainnet>(address)
+            }),
+            (ZcashTestnet::NAME, &|| {
+                ZcashWallet::from_address::<ZcashTestnet>(address)
+            }),
+        ];
+        match import("address", ZcashMainnet::NAME, networks) {
+            Err(error @ CLIError::NetworkMismatch(..)) => assert_eq!(
+                "the address is for testnet, but mainnet was requested",
+                error.to_string()
+            ),
+            _ => panic!("expected a network mismatch"),
         }
+
+        let wallet = import("address", ZcashTestnet::NAME, networks).unwrap();
+        assert_eq!("testnet", wallet.network.unwrap());
     }
 }
@@ -12,3 +12,5 @@ pub extern crate wagyu_zcash as zcash;
 
 #[cfg_attr(tarpaulin, skip)]
 pub mod cli;
+
+pub mod wallet;
@@ -6,12 +6,13 @@ use wagyu::cli::bitcoin::BitcoinCLI;
 use wagyu::cli::ethereum::EthereumCLI;
 use wagyu::cli::monero::MoneroCLI;
 use wagyu::cli::zcash::ZcashCLI;
-use wagyu::cli::{CLIError, CLI};
+use wagyu::cli::{is_json, CLI};
 
 use clap::{App, AppSettings};
+use std::process;
 
 #[cfg_attr(tarpaulin, skip)]
-fn main() -> Result<(), CLIError> {
+fn main() {
     let arguments = App::new("wagyu")
         .version("v0.6.3")
         .about("Generate a wallet for Bitcoin, Ethereum, Monero, and Zcash")
@@ -31,11 +32,28 @@ fn main() -> Result<(), CLIError> {
         .set_term_width(0)
         .get_matches();
 
-    match arguments.subcommand() {
-        ("bitcoin", Some(arguments)) => BitcoinCLI::print(BitcoinCLI::parse(arguments)?),
-        ("ethereum", Some(arguments)) => EthereumCLI::print(EthereumCLI::parse(arguments)?),
-        ("monero", Some(arguments)) => MoneroCLI::print(MoneroCLI::parse(arguments)?),
-        ("zcash", Some(arguments)) => ZcashCLI::print(ZcashCLI::parse(arguments)?),
+    let (json, result) = match arguments.subcommand() {
+        ("bitcoin", Some(arguments)) => (
+            is_json(arguments),
+            BitcoinCLI::parse(arguments).and_then(BitcoinCLI::print),
+        ),
+        ("ethereum", Some(arguments)) => (
+            is_json(arguments),
+            EthereumCLI::parse(arguments).and_then(EthereumCLI::print),
+        ),
+        ("monero", Some(arguments)) => (
+            is_json(arguments),
+            MoneroCLI::parse(arguments).and_then(MoneroCLI::print),
+        ),
+        ("zcash", Some(arguments)) => (is_json(arguments), ZcashCLI::parse(arguments).and_then(ZcashCLI::print)),
         _ => unreachable!(),
+    };
+
+    if let Err(error) = result {
+        match json {
+            true => eprintln!("{}", serde_json::json!({ "error": error.to_string() })),
+            false => eprintln!("Error: {}", error),
+        };
+        process::exit(1);
     }
 }
@@ -0,0 +1,531 @@
+//!
+//! Wallet
+//!
+//! This module defines the versioned JSON file format for persisting wallets of every coin.
+//! The secret fields of a wallet are stored in plaintext, or, given a password, are encrypted
+//! with XChaCha20-Poly1305 under a key derived with Argon2id.
+//!
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::model::no_std::{format, BTreeMap, String, Vec};
+
+/// The version of the wallet file format written by this crate
+pub const WALLET_FILE_VERSION: u32 = 1;
+
+/// The Argon2id memory cost in KiB, iteration count, and degree of parallelism for new wallet files
+const ARGON2_PARAMS: (u32, u32, u32) = (19456, 2, 1);
+
+/// The largest Argon2id parameters accepted from a wallet file, four times those of new wallet files,
+/// so that an untrusted file cannot demand an unbounded amount of memory or time
+const MAX_ARGON2_PARAMS: (u32, u32, u32) = (4 * ARGON2_PARAMS.0, 4 * ARGON2_PARAMS.1, 4 * ARGON2_PARAMS.2);
+
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER_ALGORITHM: &str = "xchacha20-poly1305";
+
+/// The serialized names of the wallet fields that are stored in the secret section
+const SECRET_FIELDS: [&str; 7] = [
+    "mnemonic",
+    "password",
+    "xpriv",
+    "private_key",
+    "private_spend_key",
+    "private_view_key",
+    "outgoing_view_key",
+];
+
+#[derive(Debug, Fail)]
+pub enum WalletError {
+    #[fail(display = "coin mismatch: {{ expected: {:?}, found: {:?} }}", expected, found)]
+    CoinMismatch { expected: String, found: String },
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(
+        display = "invalid kdf parameters: {{ memory: {:?}, iterations: {:?}, parallelism: {:?} }}",
+        _0, _1, _2
+    )]
+    InvalidKdfParameters(u32, u32, u32),
+
+    #[fail(display = "invalid password or corrupted wallet file")]
+    InvalidPassword,
+
+    #[fail(display = "invalid wallet: {}", _0)]
+    InvalidWallet(String),
+
+    #[fail(display = "the wallet file is encrypted and requires a password")]
+    MissingPassword,
+
+    #[fail(display = "the wallet file is not encrypted and does not take a password")]
+    UnexpectedPassword,
+
+    #[fail(display = "unsupported algorithm: {}", _0)]
+    UnsupportedAlgorithm(String),
+
+    #[fail(display = "unsupported wallet file version: {}", _0)]
+    UnsupportedVersion(u32),
+}
+
+impl From<argon2::Error> for WalletError {
+    fn from(error: argon2::Error) -> Self {
+        WalletError::Crate("argon2", format!("{:?}", error))
+    }
+}
+
+impl From<hex::FromHexError> for WalletError {
+    fn from(error: hex::FromHexError) -> Self {
+        WalletError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<io::Error> for WalletError {
+    fn from(error: io::Error) -> Self {
+        WalletError::Crate("std::io", format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for WalletError {
+    fn from(error: serde_json::error::Error) -> Self {
+        WalletError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+/// The interface for a wallet that can be persisted to a wallet file
+pub trait Wallet: Serialize + DeserializeOwned {
+    /// The name of the coin recorded in the wallet file
+    const COIN: &'static str;
+
+    /// Writes the wallet to a wallet file at the given path, encrypting its secret fields
+    /// if a password is given.
+    fn to_file<P: AsRef<Path>>(&self, path: P, password: Option<&str>) -> Result<(), WalletError> {
+        let contents = WalletFile::new(self, password)?.to_string()?;
+
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path)?;
+        file.write_all(contents.as_bytes())?;
+        Ok(file.write_all(b"\n")?)
+    }
+
+    /// Returns the wallet read from the wallet file at the given path, decrypting its secret
+    /// fields with the given password if the file is encrypted.
+    fn from_file<P: AsRef<Path>>(path: P, password: Option<&str>) -> Result<Self, WalletError> {
+        WalletFile::from_str(&fs::read_to_string(path)?)?.to_wallet(password)
+    }
+}
+
+/// Represents the software and time that created a wallet file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WalletCreation {
+    pub software: String,
+    /// The creation time in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+/// Represents the key derivation function parameters of an encrypted wallet file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WalletKdf {
+    pub algorithm: String,
+    /// The memory cost in KiB
+    pub memory: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub salt: String,
+}
+
+/// Represents the cipher parameters of an encrypted wallet file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WalletCipher {
+    pub algorithm: String,
+    pub nonce: String,
+}
+
+/// Represents the encrypted secret fields of a wallet file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WalletEncryption {
+    pub kdf: WalletKdf,
+    pub cipher: WalletCipher,
+    pub ciphertext: String,
+}
+
+/// Represents a wallet file, with the secret fields of the wallet separated from its public
+/// fields, and either stored in plaintext or encrypted
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct WalletFile {
+    pub version: u32,
+    pub coin: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    pub created: WalletCreation,
+    pub wallet: BTreeMap<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<BTreeMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted: Option<WalletEncryption>,
+}
+
+impl WalletFile {
+    /// Returns the wallet file of the given wallet, encrypting its secret fields if a password is given.
+    pub fn new<W: Wallet>(wallet: &W, password: Option<&str>) -> Result<Self, WalletError> {
+        let mut fields = match serde_json::to_value(wallet)? {
+            Value::Object(fields) => fields,
+            _ => return Err(WalletError::InvalidWallet("expected a JSON object".into())),
+        };
+        let network = match fields.remove("network") {
+            Some(Value::String(network)) => Some(network),
+            Some(Value::Null) | None => None,
+            Some(network) => return Err(WalletError::InvalidWallet(format!("invalid network {}", network))),
+        };
+
+        let (secret, public): (BTreeMap<_, _>, BTreeMap<_, _>) = fields
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .partition(|(name, _)| SECRET_FIELDS.contains(&name.as_str()));
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        let mut file = Self {
+            version: WALLET_FILE_VERSION,
+            coin: W::COIN.into(),
+            network,
+            created: WalletCreation {
+                software: format!("wagyu v{}", env!("CARGO_PKG_VERSION")),
+                timestamp,
+            },
+            wallet: public,
+            secret: None,
+            encrypted: None,
+        };
+
+        match password {
+            Some(password) => file.encrypted = Some(file.encrypt(&secret, password)?),
+            None => file.secret = Some(secret),
+        };
+        Ok(file)
+    }
+
+    /// Returns the wallet stored in the wallet file, decrypting its secret fields with the given
+    /// password if the file is encrypted.
+    pub fn to_wallet<W: Wallet>(&self, password: Option<&str>) -> Result<W, WalletError> {
+        if self.version != WALLET_FILE_VERSION {
+            return Err(WalletError::UnsupportedVersion(self.version));
+        }
+        if self.coin != W::COIN {
+            return Err(WalletError::CoinMismatch {
+                expected: W::COIN.into(),
+                found: self.coin.clone(),
+            });
+        }
+
+        let secret = match (&self.secret, &self.encrypted, password) {
+            (Some(_), Some(_), _) => return Err(WalletError::InvalidWallet("duplicate secret fields".into())),
+            (Some(_), None, Some(_)) => return Err(WalletError::UnexpectedPassword),
+            (Some(secret), None, None) => secret.clone(),
+            (None, Some(_), None) => return Err(WalletError::MissingPassword),
+            (None, Some(encrypted), Some(password)) => self.decrypt(encrypted, password)?,
+            (None, None, _) => BTreeMap::new(),
+        };
+
+        let mut fields = serde_json::Map::new();
+        fields.extend(self.wallet.clone());
+        fields.extend(secret);
+        if let Some(network) = &self.network {
+            fields.insert("network".into(), Value::String(network.clone()));
+        }
+        Ok(serde_json::from_value(Value::Object(fields))?)
+    }
+
+    /// Returns the wallet file as pretty-printed JSON.
+    pub fn to_string(&self) -> Result<String, WalletError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Returns the associated data authenticated alongside the secret fields, which is the
+    /// canonical JSON of the wallet file without its secret fields.
+    fn associated_data(&self) -> Result<Vec<u8>, WalletError> {
+        Ok(serde_json::to_vec(&Self {
+            secret: None,
+            encrypted: None,
+            ..self.clone()
+        })?)
+    }
+
+    /// Returns the secret fields encrypted with a key derived from the given password.
+    fn encrypt(&self, secret: &BTreeMap<String, Value>, password: &str) -> Result<WalletEncryption, WalletError> {
+        let (memory, iterations, parallelism) = ARGON2_PARAMS;
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 24];
+        OsRng.fill_bytes(&mut salt);
+        OsRng.fill_bytes(&mut nonce);
+
+        let kdf = WalletKdf {
+            algorithm: KDF_ALGORITHM.into(),
+            memory,
+            iterations,
+            parallelism,
+            salt: hex::encode(salt),
+        };
+        let cipher = WalletCipher {
+            algorithm: CIPHER_ALGORITHM.into(),
+            nonce: hex::encode(nonce),
+        };
+
+        let payload = Payload {
+            msg: &serde_json::to_vec(secret)?,
+            aad: &self.associated_data()?,
+        };
+        let ciphertext = Self::cipher(&kdf, password)?
+            .encrypt(XNonce::from_slice(&nonce), payload)
+            .map_err(|error| WalletError::Crate("chacha20poly1305", format!("{:?}", error)))?;
+
+        Ok(WalletEncryption {
+            kdf,
+            cipher,
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Returns the secret fields decrypted with a key derived from the given password.
+    fn decrypt(&self, encrypted: &WalletEncryption, password: &str) -> Result<BTreeMap<String, Value>, WalletError> {
+        if encrypted.cipher.algorithm != CIPHER_ALGORITHM {
+            return Err(WalletError::UnsupportedAlgorithm(encrypted.cipher.algorithm.clone()));
+        }
+
+        let nonce = hex::decode(&encrypted.cipher.nonce)?;
+        if nonce.len() != 24 {
+            return Err(WalletError::InvalidWallet(format!(
+                "invalid nonce length {}",
+                nonce.len()
+            )));
+        }
+
+        let payload = Payload {
+            msg: &hex::decode(&encrypted.ciphertext)?,
+            aad: &self.associated_data()?,
+        };
+        let secret = Self::cipher(&encrypted.kdf, password)?
+            .decrypt(XNonce::from_slice(&nonce), payload)
+            .map_err(|_| WalletError::InvalidPassword)?;
+
+        Ok(serde_json::from_slice(&secret)?)
+    }
+
+    /// Returns the cipher keyed with the given password and key derivation function parameters.
+    fn cipher(kdf: &WalletKdf, password: &str) -> Result<XChaCha20Poly1305, WalletError> {
+        if kdf.algorithm != KDF_ALGORITHM {
+            return Err(WalletError::UnsupportedAlgorithm(kdf.algorithm.clone()));
+        }
+        let (max_memory, max_iterations, max_parallelism) = MAX_ARGON2_PARAMS;
+        if kdf.memory > max_memory || kdf.iterations > max_iterations || kdf.parallelism > max_parallelism {
+            return Err(WalletError::InvalidKdfParameters(
+                kdf.memory,
+                kdf.iterations,
+                kdf.parallelism,
+            ));
+        }
+
+        let params = Params::new(kdf.memory, kdf.iterations, kdf.parallelism, Some(32))?;
+        let mut key = [0u8; 32];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params).hash_password_into(
+            password.as_bytes(),
+            &hex::decode(&kdf.salt)?,
+            &mut key,
+        )?;
+        Ok(XChaCha20Poly1305::new(&key.into()))
+    }
+}
+
+impl FromStr for WalletFile {
+    type Err = WalletError;
+
+    fn from_str(file: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(file)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::{format::BitcoinFormat, wordlist::English, Mainnet};
+    use crate::cli::{bitcoin::BitcoinWallet, ethereum::EthereumWallet};
+
+    const FIXTURE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/wallet_v1.json"));
+    const ENCRYPTED_FIXTURE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/wallet_v1_encrypted.json"
+    ));
+    const PASSWORD: &str = "wagyu";
+
+    fn wallet() -> BitcoinWallet {
+        BitcoinWallet::from_mnemonic::<Mainnet, English>(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            &None,
+            "m/84'/0'/0'/0/0",
+            Some(&BitcoinFormat::Bech32),
+        )
+        .unwrap()
+    }
+
+    fn to_value<W: Wallet>(wallet: &W) -> Value {
+        serde_json::to_value(wallet).unwrap()
+    }
+
+    #[test]
+    fn fixture() {
+        let file = WalletFile::from_str(FIXTURE).unwrap();
+        assert_eq!(FIXTURE.trim_end(), file.to_string().unwrap());
+        assert_eq!(
+            to_value(&wallet()),
+            to_value(&file.to_wallet::<BitcoinWallet>(None).unwrap())
+        );
+
+        let mut new = WalletFile::new(&wallet(), None).unwrap();
+        new.created.timestamp = file.created.timestamp;
+        assert_eq!(file, new);
+
+        let file = WalletFile::from_str(ENCRYPTED_FIXTURE).unwrap();
+        assert_eq!(ENCRYPTED_FIXTURE.trim_end(), file.to_string().unwrap());
+        assert_eq!(
+            to_value(&wallet()),
+            to_value(&file.to_wallet::<BitcoinWallet>(Some(PASSWORD)).unwrap())
+        );
+    }
+
+    #[test]
+    fn to_file() {
+        let path = std::env::temp_dir().join(format!("wagyu-wallet-{}.json", std::process::id()));
+        for password in &[None, Some(PASSWORD)] {
+            wallet().to_file(&path, *password).unwrap();
+            let contents = fs::read_to_string(&path).unwrap();
+            assert_eq!(password.is_some(), !contents.contains("about"));
+            assert!(contents.contains("zpub6uWj3N2LbHteHkuNPXs9bwnQGZ3RDnr5GtGmPo8aouYQLe6zQghcBDS78p221mbYb5eVgviZ2mEkdgMvLfSmvzsSe6nMYVaALaL6rZ9pTbq"));
+            assert_eq!(
+                to_value(&wallet()),
+                to_value(&BitcoinWallet::from_file(&path, *password).unwrap())
+            );
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn invalid_password() {
+        let file = WalletFile::from_str(ENCRYPTED_FIXTURE).unwrap();
+        assert!(matches!(
+            file.to_wallet::<BitcoinWallet>(Some("wrong")),
+            Err(WalletError::InvalidPassword)
+        ));
+        assert!(matches!(
+            file.to_wallet::<BitcoinWallet>(None),
+            Err(WalletError::MissingPassword)
+        ));
+
+        let file = WalletFile::from_str(FIXTURE).unwrap();
+        assert!(matches!(
+            file.to_wallet::<BitcoinWallet>(Some(PASSWORD)),
+            Err(WalletError::UnexpectedPassword)
+        ));
+    }
+
+    #[test]
+    fn tampered() {
+        let file = WalletFile::from_str(ENCRYPTED_FIXTURE).unwrap();
+
+        let mut tampered = file.clone();
+        let encrypted = tampered.encrypted.as_mut().unwrap();
+        let byte = if encrypted.ciphertext.starts_with('0') {
+            "1"
+        } else {
+            "0"
+        };
+        encrypted.ciphertext.replace_range(..1, byte);
+        assert!(matches!(
+            tampered.to_wallet::<BitcoinWallet>(Some(PASSWORD)),
+            Err(WalletError::InvalidPassword)
+        ));
+
+        // The public fields are authenticated along with the secret fields
+        let mut tampered = file.clone();
+        tampered
+            .wallet
+            .insert("address".into(), "bc1qzjl8uccsf2ep7ev8xjmtazdxce8ydwpd3e8aer".into());
+        assert!(matches!(
+            tampered.to_wallet::<BitcoinWallet>(Some(PASSWORD)),
+            Err(WalletError::InvalidPassword)
+        ));
+
+        let mut tampered = file.clone();
+        tampered.encrypted.as_mut().unwrap().kdf.iterations = 3;
+        assert!(matches!(
+            tampered.to_wallet::<BitcoinWallet>(Some(PASSWORD)),
+            Err(WalletError::InvalidPassword)
+        ));
+
+        let mut tampered = file;
+        tampered.encrypted.as_mut().unwrap().cipher.nonce.truncate(46);
+        assert!(tampered.to_wallet::<BitcoinWallet>(Some(PASSWORD)).is_err());
+    }
+
+    #[test]
+    fn excessive_kdf_parameters() {
+        let file = WalletFile::from_str(ENCRYPTED_FIXTURE).unwrap();
+        let (memory, iterations, parallelism) = MAX_ARGON2_PARAMS;
+
+        for (excessive_memory, excessive_iterations, excessive_parallelism) in [
+            (memory + 1, iterations, parallelism),
+            (memory, iterations + 1, parallelism),
+            (memory, iterations, parallelism + 1),
+            (u32::max_value(), u32::max_value(), u32::max_value()),
+        ]
+        .iter()
+        {
+            let mut tampered = file.clone();
+            let kdf = &mut tampered.encrypted.as_mut().unwrap().kdf;
+            kdf.memory = *excessive_memory;
+            kdf.iterations = *excessive_iterations;
+            kdf.parallelism = *excessive_parallelism;
+            assert!(matches!(
+                tampered.to_wallet::<BitcoinWallet>(Some(PASSWORD)),
+                Err(WalletError::InvalidKdfParameters(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn invalid_file() {
+        let mut file = WalletFile::from_str(FIXTURE).unwrap();
+        assert!(matches!(
+            file.to_wallet::<EthereumWallet>(None),
+            Err(WalletError::CoinMismatch { .. })
+        ));
+
+        file.version = 2;
+        assert!(matches!(
+            file.to_wallet::<BitcoinWallet>(None),
+            Err(WalletError::UnsupportedVersion(2))
+        ));
+
+        assert!(WalletFile::from_str("{}").is_err());
+        assert!(WalletFile::from_str(&FIXTURE.replace("\"coin\"", "\"coins\"")).is_err());
+    }
+}
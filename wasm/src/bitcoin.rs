@@ -0,0 +1,84 @@
+use wagyu_bitcoin::{BitcoinExtendedPrivateKey, BitcoinNetwork, Mainnet, Testnet};
+use wagyu_model::{ExtendedPrivateKey, Network};
+
+use core::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Returns the address of the given extended private key derived at the given path, in the
+/// address format of the version bytes of the extended private key, e.g. "zprv..." for Bech32.
+fn derive_address<N: BitcoinNetwork>(extended_private_key: &str, path: &str) -> Result<String, String> {
+    let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)
+        .and_then(|extended_private_key| extended_private_key.derive_path(path))
+        .map_err(|error| error.to_string())?;
+    let address = extended_private_key
+        .to_address(&extended_private_key.format())
+        .map_err(|error| error.to_string())?;
+    Ok(address.to_string())
+}
+
+/// Returns the Bitcoin address of the given extended private key on the given network,
+/// derived at the given path, e.g. "m/84'/0'/0'/0/0".
+pub fn bitcoin_derive_address(network: &str, extended_private_key: &str, path: &str) -> Result<String, String> {
+    match network {
+        network if Mainnet::is_name(network) => derive_address::<Mainnet>(extended_private_key, path),
+        network if Testnet::is_name(network) => derive_address::<Testnet>(extended_private_key, path),
+        network => Err(format!("unsupported bitcoin network: {}", network)),
+    }
+}
+
+/// Returns the Bitcoin address of the given extended private key on the given network,
+/// derived at the given path, or throws the error message.
+#[wasm_bindgen(js_name = bitcoinDeriveAddress)]
+pub fn bitcoin_derive_address_js(network: &str, extended_private_key: &str, path: &str) -> Result<String, JsValue> {
+    bitcoin_derive_address(network, extended_private_key, path).map_err(|error| JsValue::from_str(&error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// The master extended private keys of the mnemonic "abandon abandon ... about".
+    const XPRV: &str = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+    const YPRV: &str = "yprvABrGsX5C9jantZVwdwcQhDXkqsu4RoSAZKBwPnLA3uyeVM3C3fvTuqzru4fovMSLqYSqALGe9MBqCf7Pg7Y7CTsjoNnLYg6HxR2Xo44NX7E";
+    const ZPRV: &str = "zprvAWgYBBk7JR8Gjrh4UJQ2uJdG1r3WNRRfURiABBE3RvMXYSrRJL62XuezvGdPvG6GFBZduosCc1YP5wixPox7zhZLfiUm8aunE96BBa4Kei5";
+    const VPRV: &str = "vprv9DMUxX4ShgxMLfvb8sFY4xFFKyTibwTfoydH3beVutr1L3bWHhRn3f2SqSo3vdUacd6QuuUxmN8BYoGhX2J4okpwCMh4nwdq9EqbdGgioRF";
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn derive_address() {
+        [
+            ("mainnet", XPRV, "m/44'/0'/0'/0/0", "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA"),
+            ("mainnet", YPRV, "m/49'/0'/0'/0/0", "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf"),
+            (
+                "bitcoin",
+                ZPRV,
+                "m/84'/0'/0'/0/0",
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            (
+                "testnet",
+                VPRV,
+                "m/84'/1'/0'/0/0",
+                "tb1q6rz28mcfaxtmd6v789l9rrlrusdprr9pqcpvkl",
+            ),
+        ]
+        .iter()
+        .for_each(|(network, extended_private_key, path, address)| {
+            assert_eq!(
+                *address,
+                bitcoin_derive_address(network, extended_private_key, path).unwrap()
+            );
+        });
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn derive_address_invalid() {
+        assert!(bitcoin_derive_address("testnet", XPRV, "m/0").is_err());
+        assert!(bitcoin_derive_address("mainnet", XPRV, "m/0/").is_err());
+        assert!(bitcoin_derive_address("mainnet", &XPRV[1..], "m/0").is_err());
+        assert!(bitcoin_derive_address("litecoin", XPRV, "m/0").is_err());
+    }
+}
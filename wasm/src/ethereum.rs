@@ -0,0 +1,45 @@
+use wagyu_ethereum::{EthereumExtendedPrivateKey, EthereumFormat, Mainnet};
+use wagyu_model::ExtendedPrivateKey;
+
+use core::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Returns the Ethereum address of the given extended private key, derived at the given path,
+/// e.g. "m/44'/60'/0'/0/0".
+pub fn ethereum_derive_address(extended_private_key: &str, path: &str) -> Result<String, String> {
+    let extended_private_key = EthereumExtendedPrivateKey::<Mainnet>::from_str(extended_private_key)
+        .and_then(|extended_private_key| extended_private_key.derive_path(path))
+        .map_err(|error| error.to_string())?;
+    let address = extended_private_key
+        .to_address(&EthereumFormat::Standard)
+        .map_err(|error| error.to_string())?;
+    Ok(address.to_string())
+}
+
+/// Returns the Ethereum address of the given extended private key, derived at the given path,
+/// or throws the error message.
+#[wasm_bindgen(js_name = ethereumDeriveAddress)]
+pub fn ethereum_derive_address_js(extended_private_key: &str, path: &str) -> Result<String, JsValue> {
+    ethereum_derive_address(extended_private_key, path).map_err(|error| JsValue::from_str(&error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::*;
+
+    /// The master extended private key of the mnemonic "abandon abandon ... about".
+    const XPRV: &str = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn derive_address() {
+        assert_eq!(
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+            ethereum_derive_address(XPRV, "m/44'/60'/0'/0/0").unwrap()
+        );
+        assert!(ethereum_derive_address(XPRV, "m/44'/60'/0'/0/").is_err());
+        assert!(ethereum_derive_address(&XPRV[1..], "m/0").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! # Wasm
+//!
+//! WebAssembly bindings for deriving wallet addresses, built for `wasm32-unknown-unknown`
+//! and callable from JavaScript in a browser or Node.
+
+#![warn(unused_extern_crates, dead_code)]
+
+pub mod bitcoin;
+pub use self::bitcoin::*;
+
+pub mod ethereum;
+pub use self::ethereum::*;
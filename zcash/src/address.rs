@@ -1,5 +1,6 @@
 use crate::format::ZcashFormat;
-use crate::librustzcash::sapling_crypto::primitives::Diversifier;
+use crate::librustzcash::algebra::curve::bls12_381::Bls12;
+use crate::librustzcash::sapling_crypto::primitives::{Diversifier, PaymentAddress};
 use crate::librustzcash::JUBJUB;
 use crate::network::ZcashNetwork;
 use crate::private_key::ZcashPrivateKey;
@@ -18,6 +19,9 @@ use curve25519_dalek::scalar::Scalar;
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 
+/// The error of deriving a P2SH address from a key alone.
+const P2SH_REDEEM_SCRIPT_REQUIRED: &str = "a p2sh address requires a redeem script, see ZcashAddress::p2sh";
+
 /// Represents a Zcash address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ZcashAddress<N: ZcashNetwork> {
@@ -38,7 +42,7 @@ impl<N: ZcashNetwork> Address for ZcashAddress<N> {
     fn from_private_key(private_key: &Self::PrivateKey, format: &Self::Format) -> Result<Self, AddressError> {
         match private_key.to_public_key() {
             ZcashPublicKey::<N>::P2PKH(public_key) => Ok(Self::p2pkh(&public_key)),
-            ZcashPublicKey::<N>::P2SH(_) => Ok(Self::p2sh()),
+            ZcashPublicKey::<N>::P2SH(_) => Err(AddressError::Message(P2SH_REDEEM_SCRIPT_REQUIRED.into())),
             ZcashPublicKey::<N>::Sprout(public_key) => Self::sprout(&public_key),
             ZcashPublicKey::<N>::Sapling(public_key) => Self::sapling(&public_key, format),
         }
@@ -48,7 +52,7 @@ impl<N: ZcashNetwork> Address for ZcashAddress<N> {
     fn from_public_key(public_key: &Self::PublicKey, format: &Self::Format) -> Result<Self, AddressError> {
         match public_key {
             ZcashPublicKey::<N>::P2PKH(public_key) => Ok(Self::p2pkh(&public_key)),
-            ZcashPublicKey::<N>::P2SH(_) => Ok(Self::p2sh()),
+            ZcashPublicKey::<N>::P2SH(_) => Err(AddressError::Message(P2SH_REDEEM_SCRIPT_REQUIRED.into())),
             ZcashPublicKey::<N>::Sprout(public_key) => Self::sprout(&public_key),
             ZcashPublicKey::<N>::Sapling(public_key) => Self::sapling(&public_key, format),
         }
@@ -58,28 +62,36 @@ impl<N: ZcashNetwork> Address for ZcashAddress<N> {
 impl<N: ZcashNetwork> ZcashAddress<N> {
     /// Returns a transparent address from a given Zcash public key.
     pub fn p2pkh(public_key: &P2PKHViewingKey) -> Self {
+        Self::transparent(
+            &hash160(&match public_key.compressed {
+                true => public_key.public_key.serialize_compressed().to_vec(),
+                false => public_key.public_key.serialize().to_vec(),
+            }),
+            ZcashFormat::P2PKH,
+        )
+    }
+
+    /// Returns a transparent multisignature address from a given redeem script.
+    pub fn p2sh(redeem_script: &[u8]) -> Self {
+        Self::transparent(&hash160(redeem_script), ZcashFormat::P2SH)
+    }
+
+    /// Returns a transparent address of the given hash, with the two-byte prefix of the given format.
+    fn transparent(hash: &[u8], format: ZcashFormat) -> Self {
         let mut address = [0u8; 26];
-        address[0..2].copy_from_slice(&N::to_address_prefix(&ZcashFormat::P2PKH));
-        address[2..22].copy_from_slice(&hash160(&match public_key.compressed {
-            true => public_key.public_key.serialize_compressed().to_vec(),
-            false => public_key.public_key.serialize().to_vec(),
-        }));
+        address[0..2].copy_from_slice(&N::to_address_prefix(&format));
+        address[2..22].copy_from_slice(hash);
 
         let sum = &checksum(&address[0..22])[0..4];
         address[22..26].copy_from_slice(sum);
 
         Self {
             address: address.to_base58(),
-            format: ZcashFormat::P2PKH,
+            format,
             _network: PhantomData,
         }
     }
 
-    /// Returns a P2SH address.
-    pub fn p2sh() -> Self {
-        unimplemented!("p2sh addresses are unimplemented");
-    }
-
     /// Returns a shielded address from a given Zcash public key.
     pub fn sprout(public_key: &SproutViewingKey) -> Result<Self, AddressError> {
         let pk = &Scalar::from_bits(public_key.key_b) * &ED25519_BASEPOINT_TABLE;
@@ -110,27 +122,29 @@ impl<N: ZcashNetwork> ZcashAddress<N> {
             _ => rng.gen(),
         };
 
-        let address;
-        let diversifier;
         loop {
-            if let Some(output) = public_key.vk.into_payment_address(Diversifier(data), &JUBJUB) {
-                address = output;
-                diversifier = data;
-                break;
+            if let Some(address) = public_key.vk.into_payment_address(Diversifier(data), &JUBJUB) {
+                return Self::from_payment_address(&address);
             }
             data = rng.gen();
         }
+    }
+
+    /// Returns a shielded address from a given Zcash Sapling payment address, the diversifier
+    /// and diversified transmission key pk_d.
+    pub fn from_payment_address(payment_address: &PaymentAddress<Bls12>) -> Result<Self, AddressError> {
+        let diversifier = payment_address.diversifier.0;
 
         let mut checked_data = vec![0; 43];
         checked_data[..11].copy_from_slice(&diversifier);
-        address.pk_d.write(checked_data[11..].as_mut())?;
+        payment_address.pk_d.write(checked_data[11..].as_mut())?;
 
         let format = ZcashFormat::Sapling(Some(diversifier));
         let prefix = N::to_address_prefix(&format);
 
         Ok(Self {
             address: Bech32::new(String::from(str::from_utf8(&prefix)?), checked_data.to_base32())?.to_string(),
-            format: ZcashFormat::Sapling(Some(diversifier)),
+            format,
             _network: PhantomData,
         })
     }
@@ -187,15 +201,28 @@ impl<N: ZcashNetwork> FromStr for ZcashAddress<N> {
         // Transparent
         if &address[0..=0] == "t" && address.len() < 40 {
             match &address[1..=1] {
-                "1" | "m" => {
+                "1" | "m" | "3" | "2" => {
                     let data = address.from_base58()?;
                     if data.len() != 26 {
                         return Err(AddressError::InvalidByteLength(data.len()));
                     }
 
-                    // Check that the network bytes correspond with the correct network.
-                    let _ = N::from_address_prefix(&data[0..2].to_vec())?;
-                    let format = ZcashFormat::from_address_prefix(&data[0..2].to_vec())?;
+                    let expected_checksum = &checksum(&data[0..22])[0..4];
+                    if expected_checksum != &data[22..26] {
+                        return Err(AddressError::InvalidChecksum(
+                            [&data[0..22], expected_checksum].concat().to_base58(),
+                            address.into(),
+                        ));
+                    }
+
+                    // Check that both bytes of the prefix correspond with a transparent format of the correct network.
+                    let prefix = data[0..2].to_vec();
+                    let _ = N::from_address_prefix(&prefix)?;
+                    let format = ZcashFormat::from_address_prefix(&prefix)?;
+                    match format {
+                        ZcashFormat::P2PKH | ZcashFormat::P2SH if N::to_address_prefix(&format) == prefix => {}
+                        _ => return Err(AddressError::InvalidPrefix(prefix)),
+                    };
 
                     return Ok(Self {
                         address: address.into(),
@@ -203,9 +230,6 @@ impl<N: ZcashNetwork> FromStr for ZcashAddress<N> {
                         _network: PhantomData,
                     });
                 }
-                "3" | "2" => {
-                    unimplemented!("");
-                }
                 _ => return Err(AddressError::InvalidAddress(address.into())),
             }
         }
@@ -547,6 +571,71 @@ mod tests {
         }
     }
 
+    mod p2sh {
+        use super::*;
+
+        // OP_1 <public key> OP_1 OP_CHECKMULTISIG
+        const REDEEM_SCRIPT: &str = "51210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f8179851ae";
+
+        fn test_p2sh<N: ZcashNetwork>(prefix: &str) {
+            let redeem_script = hex::decode(REDEEM_SCRIPT).unwrap();
+            let address = ZcashAddress::<N>::p2sh(&redeem_script);
+            assert!(address.to_string().starts_with(prefix));
+            assert_eq!(ZcashFormat::P2SH, address.format());
+
+            let data = address.to_string().from_base58().unwrap();
+            assert_eq!(N::to_address_prefix(&ZcashFormat::P2SH), data[0..2].to_vec());
+            assert_eq!(hash160(&redeem_script), data[2..22].to_vec());
+
+            test_from_str::<N>(&address.to_string(), &ZcashFormat::P2SH);
+        }
+
+        #[test]
+        fn mainnet() {
+            test_p2sh::<Mainnet>("t3");
+        }
+
+        #[test]
+        fn testnet() {
+            test_p2sh::<Testnet>("t2");
+        }
+
+        #[test]
+        fn from_str() {
+            // A founders' reward address of the Zcash mainnet
+            test_from_str::<Mainnet>("t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd", &ZcashFormat::P2SH);
+        }
+
+        #[test]
+        fn from_public_key() {
+            let public_key = ZcashPublicKey::<Mainnet>::P2SH(crate::public_key::P2SHViewingKey {});
+            assert!(ZcashAddress::from_public_key(&public_key, &ZcashFormat::P2SH).is_err());
+        }
+    }
+
+    mod transparent_invalid {
+        use super::*;
+
+        #[test]
+        fn invalid_checksum() {
+            let address = "t1MoMR1XdnPqLBWf5XkchWTkGNrveYLCaiN";
+            match ZcashAddress::<Mainnet>::from_str(address) {
+                Err(AddressError::InvalidChecksum(expected, found)) => {
+                    assert_eq!("t1MoMR1XdnPqLBWf5XkchWTkGNrveYLCaiM", expected);
+                    assert_eq!(address, found);
+                }
+                result => panic!("unexpected result: {:?}", result),
+            };
+        }
+
+        #[test]
+        fn invalid_network() {
+            assert!(ZcashAddress::<Testnet>::from_str("t1MoMR1XdnPqLBWf5XkchWTkGNrveYLCaiM").is_err());
+            assert!(ZcashAddress::<Mainnet>::from_str("t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd").is_ok());
+            assert!(ZcashAddress::<Testnet>::from_str("t3Vz22vK5z2LcKEdg16Yv4FFneEL1zg9ojd").is_err());
+        }
+    }
+
     mod sprout_mainnet {
         use super::*;
 
@@ -1,6 +1,6 @@
 use crate::network::ZcashNetwork;
-use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
-use wagyu_model::no_std::{vec, ToString, Vec};
+use wagyu_model::derivation_path::{parse_derivation_path, ChildIndex, DerivationPath, DerivationPathError};
+use wagyu_model::no_std::{vec, Vec};
 
 use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
 
@@ -61,14 +61,7 @@ impl<N: ZcashNetwork> FromStr for ZcashDerivationPath<N> {
     type Err = DerivationPathError;
 
     fn from_str(path: &str) -> Result<Self, Self::Err> {
-        let mut parts = path.split("/");
-
-        if parts.next().unwrap() != "m" {
-            return Err(DerivationPathError::InvalidDerivationPath(path.to_string()));
-        }
-
-        let path: Result<Vec<ChildIndex>, Self::Err> = parts.map(str::parse).collect();
-        Self::from_vec(&path?)
+        Self::from_vec(&parse_derivation_path(path)?)
     }
 }
 
@@ -263,15 +256,15 @@ mod tests {
 
         assert_eq!(
             ZcashDerivationPath::<N>::from_str("m/0x"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x".into(), 1))
         );
         assert_eq!(
             ZcashDerivationPath::<N>::from_str("m/0x0"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x0".into(), 1))
         );
         assert_eq!(
             ZcashDerivationPath::<N>::from_str("m/0x00"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("0x00".into(), 1))
         );
 
         assert_eq!(
@@ -280,12 +273,24 @@ mod tests {
         );
         assert_eq!(
             ZcashDerivationPath::<N>::from_str("m//0"),
-            Err(DerivationPathError::InvalidChildNumberFormat)
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            ZcashDerivationPath::<N>::from_str("m/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 1))
+        );
+        assert_eq!(
+            ZcashDerivationPath::<N>::from_str("m/0/"),
+            Err(DerivationPathError::InvalidPathSegment("".into(), 2))
         );
         assert_eq!(
             ZcashDerivationPath::<N>::from_str("m/2147483648"),
             Err(DerivationPathError::InvalidChildNumber(2147483648))
         );
+        assert_eq!(
+            ZcashDerivationPath::<N>::from_str("M/32'/133'/0'"),
+            Err(DerivationPathError::ExpectedNormalPath)
+        );
 
         assert_eq!(
             ZcashDerivationPath::<N>::from_str("m"),
@@ -87,6 +87,21 @@ impl<N: ZcashNetwork> ZcashExtendedPrivateKey<N> {
     pub fn to_extended_spending_key(&self) -> ExtendedSpendingKey<N> {
         self.extended_spending_key.clone()
     }
+
+    /// Returns the depth of the Zcash extended private key.
+    pub fn depth(&self) -> u8 {
+        self.extended_spending_key.depth()
+    }
+
+    /// Returns the parent full viewing key tag of the Zcash extended private key.
+    pub fn parent_tag(&self) -> [u8; 4] {
+        self.extended_spending_key.parent_fvk_tag()
+    }
+
+    /// Returns the child index of the Zcash extended private key.
+    pub fn child_index(&self) -> ChildIndex {
+        self.extended_spending_key.child_index()
+    }
 }
 
 impl<N: ZcashNetwork> FromStr for ZcashExtendedPrivateKey<N> {
@@ -264,6 +279,28 @@ mod tests {
                 test_to_string::<N>(extended_private_key, seed, path);
             });
         }
+
+        #[test]
+        fn depth_and_child_index() {
+            KEYPAIRS.iter().for_each(|(path, seed, _, _, _)| {
+                let seed = hex::decode(seed).unwrap();
+                let master = ZcashExtendedPrivateKey::<N>::new_master(&seed, &ZcashFormat::Sapling(None)).unwrap();
+                assert_eq!(0, master.depth());
+                assert_eq!([0u8; 4], master.parent_tag());
+
+                let path = ZcashDerivationPath::<N>::from_str(path).unwrap();
+                let indices = path.to_vec().unwrap();
+                let extended_private_key = master.derive(&path).unwrap();
+                assert_eq!(indices.len() as u8, extended_private_key.depth());
+                assert_eq!(*indices.last().unwrap(), extended_private_key.child_index());
+                assert_ne!([0u8; 4], extended_private_key.parent_tag());
+
+                let extended_private_key =
+                    ZcashExtendedPrivateKey::<N>::from_str(&extended_private_key.to_string()).unwrap();
+                assert_eq!(indices.len() as u8, extended_private_key.depth());
+                assert_eq!(*indices.last().unwrap(), extended_private_key.child_index());
+            });
+        }
     }
 
     mod sapling_testnet {
@@ -54,3 +54,6 @@ pub use self::public_key::*;
 
 pub mod transaction;
 pub use self::transaction::*;
+
+pub mod unified_address;
+pub use self::unified_address::*;
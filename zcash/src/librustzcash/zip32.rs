@@ -109,8 +109,16 @@ impl DiversifierIndex {
     }
 }
 
+impl From<u64> for DiversifierIndex {
+    fn from(index: u64) -> Self {
+        let mut j = [0u8; 11];
+        LittleEndian::write_u64(&mut j[..8], index);
+        DiversifierIndex(j)
+    }
+}
+
 /// A key used to derive diversifiers for a particular child key
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DiversifierKey(pub [u8; 32]);
 
 impl DiversifierKey {
@@ -217,6 +225,21 @@ impl<N: ZcashNetwork> ExtendedSpendingKey<N> {
         Ok(())
     }
 
+    /// Returns the depth of the extended spending key
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the tag of the parent full viewing key
+    pub fn parent_fvk_tag(&self) -> [u8; 4] {
+        self.parent_fvk_tag.0
+    }
+
+    /// Returns the child index of the extended spending key
+    pub fn child_index(&self) -> ChildIndex {
+        self.child_index
+    }
+
     /// Returns the child key corresponding to the path derived from the master key
     pub fn from_path(master: &Self, path: &[ChildIndex]) -> Self {
         let mut xsk = master.clone();
@@ -382,6 +405,10 @@ impl<N: ZcashNetwork> ExtendedFullViewingKey<N> {
         })
     }
 
+    pub fn diversifier_key(&self) -> DiversifierKey {
+        self.dk
+    }
+
     pub fn address(&self, j: DiversifierIndex) -> Result<(DiversifierIndex, PaymentAddress<Bls12>), ()> {
         let (j, d_j) = match self.dk.diversifier(j) {
             Ok(ret) => ret,
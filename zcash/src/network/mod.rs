@@ -1,5 +1,5 @@
 use crate::format::ZcashFormat;
-use wagyu_model::no_std::{String, Vec};
+use wagyu_model::no_std::{format, String, Vec};
 use wagyu_model::{AddressError, ChildIndex, Network, NetworkError, PrivateKeyError};
 
 pub mod mainnet;
@@ -37,6 +37,16 @@ pub trait ZcashNetwork: Network {
     /// Returns the Sapling viewing key prefix of the given network.
     fn to_sapling_viewing_key_prefix() -> String;
 
+    /// Returns the unified address prefix of the given network, following the ZIP-316 pattern of
+    /// "u" for mainnet and "u" followed by the network name without "net" otherwise, such as
+    /// "utest" for testnet and "uregtest" for regtest.
+    fn to_unified_address_prefix() -> String {
+        match Self::NAME {
+            "mainnet" => "u".into(),
+            name => format!("u{}", name.trim_end_matches("net")),
+        }
+    }
+
     /// Returns the extended private key prefix of the given network.
     fn to_extended_private_key_prefix() -> String;
 
@@ -49,3 +59,32 @@ pub trait ZcashNetwork: Network {
     /// Returns the network of the given extended public key prefix.
     fn from_extended_public_key_prefix(prefix: &str) -> Result<Self, NetworkError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn test_from_str<N: ZcashNetwork + FromStr<Err = NetworkError>>(names: &[&str]) {
+        names.iter().for_each(|name| {
+            assert_eq!(N::NAME, N::from_str(name).unwrap().to_string());
+            assert_eq!(N::NAME, N::from_str(&name.to_uppercase()).unwrap().to_string());
+        });
+    }
+
+    #[test]
+    fn from_str() {
+        test_from_str::<Mainnet>(&["mainnet", "main"]);
+        test_from_str::<Testnet>(&["testnet", "test"]);
+
+        assert!(Mainnet::from_str("test").is_err());
+        assert!(Testnet::from_str("mainnet").is_err());
+    }
+
+    #[test]
+    fn invalid_wif_prefix() {
+        assert!(Mainnet::from_wif_prefix(0x80).is_ok());
+        assert!(Mainnet::from_wif_prefix(0xEF).is_err());
+        assert!(Testnet::from_wif_prefix(0x00).is_err());
+    }
+}
@@ -10,6 +10,7 @@ pub struct Testnet;
 
 impl Network for Testnet {
     const NAME: &'static str = "testnet";
+    const ALIASES: &'static [&'static str] = &["test"];
 }
 
 impl ZcashNetwork for Testnet {
@@ -105,9 +106,9 @@ impl FromStr for Testnet {
     type Err = NetworkError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            Self::NAME => Ok(Self),
-            _ => Err(NetworkError::InvalidNetwork(s.into())),
+        match Self::is_name(s) {
+            true => Ok(Self),
+            false => Err(NetworkError::InvalidNetwork(s.into())),
         }
     }
 }
@@ -187,6 +187,12 @@ impl<N: ZcashNetwork> SaplingSpendingKey<N> {
             .expect("should be able to serialize an ExpandedSpendingKey");
         result
     }
+
+    /// Returns the outgoing viewing key ovk, the first 32 bytes of PRF^expand(sk, [0x02]) for a
+    /// spending key sk, or of the parent outgoing viewing key and child key for a derived key.
+    pub fn to_outgoing_viewing_key(&self) -> SaplingOutgoingViewingKey {
+        self.ovk
+    }
 }
 
 impl<N: ZcashNetwork> Debug for SaplingSpendingKey<N> {
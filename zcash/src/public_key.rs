@@ -1,10 +1,12 @@
 use crate::address::ZcashAddress;
 use crate::format::ZcashFormat;
 use crate::librustzcash::algebra::curve::bls12_381::Bls12;
+use crate::librustzcash::algebra::field::{PrimeField, PrimeFieldRepr};
 use crate::librustzcash::sapling_crypto::{
     jubjub::{edwards, FixedGenerators, JubjubBls12, JubjubEngine, JubjubParams, Unknown},
-    primitives::ViewingKey as SaplingViewingKey,
+    primitives::{Diversifier, ViewingKey},
 };
+use crate::librustzcash::zip32::{DiversifierIndex, DiversifierKey, ExtendedFullViewingKey};
 use crate::librustzcash::JUBJUB;
 use crate::network::ZcashNetwork;
 use crate::private_key::{SaplingOutgoingViewingKey, SaplingSpendingKey, ZcashPrivateKey};
@@ -25,6 +27,9 @@ use core::{
 use crypto::sha2::sha256_digest_block;
 use secp256k1;
 
+/// The error of a diversifier key with no valid diversifier left from a given index.
+const DIVERSIFIER_SPACE_EXHAUSTED: &str = "no valid diversifier from the given diversifier index";
+
 static H256: [u32; 8] = [
     0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
 ];
@@ -93,7 +98,7 @@ impl SproutViewingKey {
 
 #[derive(Debug)]
 pub struct SaplingFullViewingKey<N: ZcashNetwork> {
-    pub(super) vk: SaplingViewingKey<Bls12>,
+    pub(super) vk: ViewingKey<Bls12>,
     pub(super) ovk: SaplingOutgoingViewingKey,
     pub(super) _network: PhantomData<N>,
 }
@@ -101,7 +106,7 @@ pub struct SaplingFullViewingKey<N: ZcashNetwork> {
 impl<N: ZcashNetwork> SaplingFullViewingKey<N> {
     pub fn from_spending_key(key: &SaplingSpendingKey<N>, params: &<Bls12 as JubjubEngine>::Params) -> Self {
         Self {
-            vk: SaplingViewingKey {
+            vk: ViewingKey {
                 ak: params
                     .generator(FixedGenerators::SpendingKeyGenerator)
                     .mul(key.ask, params),
@@ -144,7 +149,7 @@ impl<N: ZcashNetwork> SaplingFullViewingKey<N> {
         reader.read_exact(&mut ovk)?;
 
         Ok(Self {
-            vk: SaplingViewingKey { ak, nk },
+            vk: ViewingKey { ak, nk },
             ovk: SaplingOutgoingViewingKey(ovk),
             _network: PhantomData,
         })
@@ -164,6 +169,23 @@ impl<N: ZcashNetwork> SaplingFullViewingKey<N> {
             .expect("should be able to serialize a FullViewingKey");
         result
     }
+
+    /// Returns the incoming viewing key ivk = CRH^ivk(ak, nk), the BLAKE2s hash of ak and nk
+    /// reduced to its low 251 bits, as a little-endian scalar.
+    pub fn to_incoming_viewing_key(&self) -> [u8; 32] {
+        let mut ivk = [0u8; 32];
+        self.vk
+            .ivk()
+            .into_repr()
+            .write_le(&mut ivk[..])
+            .expect("should be able to serialize an incoming viewing key");
+        ivk
+    }
+
+    /// Returns the outgoing viewing key of the expanded spending key.
+    pub fn to_outgoing_viewing_key(&self) -> SaplingOutgoingViewingKey {
+        self.ovk
+    }
 }
 
 impl<N: ZcashNetwork> PartialEq for SaplingFullViewingKey<N> {
@@ -177,7 +199,7 @@ impl<N: ZcashNetwork> Eq for SaplingFullViewingKey<N> {}
 impl<N: ZcashNetwork> Clone for SaplingFullViewingKey<N> {
     fn clone(&self) -> Self {
         Self {
-            vk: SaplingViewingKey {
+            vk: ViewingKey {
                 ak: self.vk.ak.clone(),
                 nk: self.vk.nk.clone(),
             },
@@ -187,6 +209,78 @@ impl<N: ZcashNetwork> Clone for SaplingFullViewingKey<N> {
     }
 }
 
+/// Represents a Zcash Sapling viewing key, the full viewing key and the diversifier key of an
+/// extended full viewing key, which derives the payment addresses of the key by diversifier index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SaplingViewingKey<N: ZcashNetwork> {
+    /// The full viewing key
+    full_viewing_key: SaplingFullViewingKey<N>,
+    /// The diversifier key
+    diversifier_key: DiversifierKey,
+}
+
+impl<N: ZcashNetwork> SaplingViewingKey<N> {
+    /// Returns the viewing key of the given extended full viewing key.
+    pub fn from_extended_full_viewing_key(extended_full_viewing_key: &ExtendedFullViewingKey<N>) -> Self {
+        Self {
+            full_viewing_key: extended_full_viewing_key.fvk.clone(),
+            diversifier_key: extended_full_viewing_key.diversifier_key(),
+        }
+    }
+
+    /// Returns the full viewing key.
+    pub fn to_full_viewing_key(&self) -> SaplingFullViewingKey<N> {
+        self.full_viewing_key.clone()
+    }
+
+    /// Returns the incoming viewing key ivk.
+    pub fn to_incoming_viewing_key(&self) -> [u8; 32] {
+        self.full_viewing_key.to_incoming_viewing_key()
+    }
+
+    /// Returns the outgoing viewing key ovk.
+    pub fn to_outgoing_viewing_key(&self) -> SaplingOutgoingViewingKey {
+        self.full_viewing_key.to_outgoing_viewing_key()
+    }
+
+    /// Returns the first diversifier index from the given index that yields a valid diversifier,
+    /// and its diversifier, the FF1-AES256 encryption of the index under the diversifier key.
+    /// Roughly half of all diversifiers are invalid, so the diversifier of an index is the
+    /// diversifier of the next index with a valid one.
+    pub fn to_diversifier(&self, index: DiversifierIndex) -> Result<(DiversifierIndex, [u8; 11]), AddressError> {
+        match self.diversifier_key.diversifier(index) {
+            Ok((index, diversifier)) => Ok((index, diversifier.0)),
+            Err(()) => Err(AddressError::Message(DIVERSIFIER_SPACE_EXHAUSTED.into())),
+        }
+    }
+
+    /// Returns the diversifier index and the payment address of the first valid diversifier
+    /// from the given index.
+    pub fn to_address(&self, index: DiversifierIndex) -> Result<(DiversifierIndex, ZcashAddress<N>), AddressError> {
+        let (index, diversifier) = self.to_diversifier(index)?;
+        match self
+            .full_viewing_key
+            .vk
+            .into_payment_address(Diversifier(diversifier), &JUBJUB)
+        {
+            Some(payment_address) => Ok((index, ZcashAddress::<N>::from_payment_address(&payment_address)?)),
+            None => Err(AddressError::Message(DIVERSIFIER_SPACE_EXHAUSTED.into())),
+        }
+    }
+
+    /// Returns the diversifier index and the payment address of the first valid diversifier,
+    /// the default address of the key.
+    pub fn to_default_address(&self) -> Result<(DiversifierIndex, ZcashAddress<N>), AddressError> {
+        self.to_address(DiversifierIndex::new())
+    }
+}
+
+impl<N: ZcashNetwork> From<&ExtendedFullViewingKey<N>> for SaplingViewingKey<N> {
+    fn from(extended_full_viewing_key: &ExtendedFullViewingKey<N>) -> Self {
+        Self::from_extended_full_viewing_key(extended_full_viewing_key)
+    }
+}
+
 /// Represents a Zcash public key
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ZcashPublicKey<N: ZcashNetwork> {
@@ -840,6 +934,114 @@ mod tests {
         }
     }
 
+    mod sapling_viewing_key {
+        use super::*;
+        use crate::derivation_path::ZcashDerivationPath;
+        use crate::extended_private_key::ZcashExtendedPrivateKey;
+        use crate::librustzcash::zip32::ExtendedSpendingKey;
+        use wagyu_model::{ChildIndex, ExtendedPrivateKey};
+
+        type N = Mainnet;
+
+        // The seed of the ZIP 32 test vectors, from
+        // https://github.com/zcash-hackworks/zcash-test-vectors/blob/master/sapling_zip32.py
+        const SEED: [u8; 32] = [
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28,
+            29, 30, 31,
+        ];
+
+        fn master() -> SaplingViewingKey<N> {
+            let extended_spending_key = ExtendedSpendingKey::<N>::master(&SEED);
+            SaplingViewingKey::from(&ExtendedFullViewingKey::from(&extended_spending_key))
+        }
+
+        #[test]
+        fn viewing_keys() {
+            let viewing_key = master();
+            assert_eq!(
+                "4847a130e799d3dbea36a1c16467d621fb2d80e30b3b1d1a426893415dad6601",
+                hex::encode(viewing_key.to_incoming_viewing_key())
+            );
+            assert_eq!(
+                "395884890323b9d4933c021db89bcf767df21977b2ff0683848321a4df4afb21",
+                hex::encode(viewing_key.to_outgoing_viewing_key().0)
+            );
+
+            let extended_spending_key = ExtendedSpendingKey::<N>::master(&SEED);
+            assert_eq!(
+                viewing_key.to_outgoing_viewing_key(),
+                extended_spending_key.expsk.to_outgoing_viewing_key()
+            );
+        }
+
+        #[test]
+        fn diversifiers() {
+            let viewing_key = master();
+            let (index, diversifier) = viewing_key.to_diversifier(DiversifierIndex::from(0)).unwrap();
+            assert_eq!(DiversifierIndex::new(), index);
+            assert_eq!("d8621b981cf300e9d4cc89", hex::encode(diversifier));
+            let (index, diversifier) = viewing_key.to_diversifier(DiversifierIndex::from(1)).unwrap();
+            assert_eq!(DiversifierIndex::from(1), index);
+            assert_eq!("48ea17a199c84bd1baa5d4", hex::encode(diversifier));
+
+            // The diversifier of index 2 is invalid, and is skipped the same way every time.
+            let (index, diversifier) = viewing_key.to_diversifier(DiversifierIndex::from(2)).unwrap();
+            assert_ne!(DiversifierIndex::from(2), index);
+            assert_eq!(
+                (index, diversifier),
+                viewing_key.to_diversifier(DiversifierIndex::from(2)).unwrap()
+            );
+            assert_eq!((index, diversifier), viewing_key.to_diversifier(index).unwrap());
+
+            // The diversifier of index 1 of m/1 is invalid, so index 1 maps to index 2.
+            let extended_spending_key = ExtendedSpendingKey::<N>::master(&SEED).derive_child(ChildIndex::Normal(1));
+            let viewing_key = SaplingViewingKey::from(&ExtendedFullViewingKey::from(&extended_spending_key));
+            let (index, diversifier) = viewing_key.to_diversifier(DiversifierIndex::from(1)).unwrap();
+            assert_eq!(DiversifierIndex::from(2), index);
+            assert_eq!("5749a13352bc223e308078", hex::encode(diversifier));
+        }
+
+        #[test]
+        fn addresses() {
+            // The default address of the master key has the diversifier d0 of the test vectors.
+            let viewing_key = master();
+            let (index, address) = viewing_key.to_default_address().unwrap();
+            assert_eq!(DiversifierIndex::new(), index);
+            assert_eq!(
+                "zs1mp3phxqu7vqwn4xv38yu4ujdtr0zf8uhxg798utekase0xj8p5qre5646d9rgfetsfzqye4gayt",
+                address.to_string()
+            );
+            assert_eq!(Some("d8621b981cf300e9d4cc89".to_string()), address.to_diversifier());
+            let format = ZcashFormat::Sapling(Some(hex!("d8621b981cf300e9d4cc89")));
+            assert_eq!(
+                ZcashAddress::<N>::from_public_key(
+                    &ZcashPublicKey::Sapling(viewing_key.to_full_viewing_key()),
+                    &format
+                )
+                .unwrap(),
+                address
+            );
+
+            let (index, address) = viewing_key.to_address(DiversifierIndex::from(2)).unwrap();
+            assert_eq!(DiversifierIndex::from(5), index);
+            assert_eq!(
+                "zs1rphkv32zffxfxhqz2f9s50r4f3udvj2y7a7f5ejddt34sz2cujgvdcwn4fx8cmlvjx5e27sl9qg",
+                address.to_string()
+            );
+
+            // The default address of the account key m/32'/133'/0', as derived by zcashd.
+            let path = ZcashDerivationPath::from_str("m/32'/133'/0'").unwrap();
+            let extended_private_key = ZcashExtendedPrivateKey::<N>::new(&SEED, &ZcashFormat::Sapling(None), &path)
+                .unwrap()
+                .to_extended_spending_key();
+            let viewing_key = SaplingViewingKey::from(&ExtendedFullViewingKey::from(&extended_private_key));
+            assert_eq!(
+                "zs1mrhc9y7jdh5r9ece8u5khgvj9kg0zgkxzdduyv0whkg7lkcrkx5xqem3e48avjq9wn2rukydkwn",
+                viewing_key.to_default_address().unwrap().1.to_string()
+            );
+        }
+    }
+
     #[test]
     fn test_p2pkh_invalid() {
         type N = Mainnet;
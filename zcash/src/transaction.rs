@@ -11,16 +11,18 @@ use wagyu_model::no_std::{
     io::{self, BufReader, Read},
     vec, String, ToString, Vec,
 };
-use wagyu_model::{ExtendedPrivateKey, PrivateKey, Transaction, TransactionError, TransactionId};
+use wagyu_model::{
+    crypto::{blake2b, sha256d},
+    ExtendedPrivateKey, PrivateKey, Transaction, TransactionError, TransactionId,
+};
 
 use base58::FromBase58;
-use blake2b_simd::{Hash, Params, State};
+use blake2b_simd::State;
 use core::{fmt, str::FromStr};
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use secp256k1;
 use serde::Serialize;
-use sha2::{Digest, Sha256};
 
 // librustzcash crates
 use bellman::groth16::{prepare_verifying_key, Parameters, PreparedVerifyingKey, Proof};
@@ -226,7 +228,7 @@ fn fetch_header_and_version_group_id(version: &str) -> (u32, u32) {
 }
 
 /// Returns a Blake256 hash of a given personalization, message, and optional Zcash version
-fn blake2_256_hash(personalization: &str, message: Vec<u8>, version: Option<&str>) -> Hash {
+fn blake2_256_hash(personalization: &str, message: Vec<u8>, version: Option<&str>) -> [u8; 32] {
     let personalization = match version {
         Some("sapling") => [personalization.as_bytes(), &(0x76b809bb as u32).to_le_bytes()].concat(),
         Some("overwinter") => [personalization.as_bytes(), &(0x5ba81b19 as u32).to_le_bytes()].concat(),
@@ -234,12 +236,9 @@ fn blake2_256_hash(personalization: &str, message: Vec<u8>, version: Option<&str
         None => personalization.as_bytes().to_vec(),
     };
 
-    Params::new()
-        .hash_length(32)
-        .personal(&personalization)
-        .to_state()
-        .update(&message)
-        .finalize()
+    let mut padded = [0u8; 16];
+    padded[..personalization.len()].copy_from_slice(&personalization);
+    blake2b(&padded, &message)
 }
 
 /// Represents the signature hash opcode
@@ -1126,7 +1125,7 @@ impl<N: ZcashNetwork> ZcashTransactionParameters<N> {
                 let seed: [u8; 32] = rng.gen();
                 let hash = blake2_256_hash("ZcTaddrToSapling", seed.to_vec(), None);
                 let mut ovk = [0u8; 32];
-                ovk.copy_from_slice(&prf_expand(hash.as_bytes(), &[0x01]).as_bytes()[0..32]);
+                ovk.copy_from_slice(&prf_expand(&hash, &[0x01]).as_bytes()[0..32]);
 
                 SaplingOutgoingViewingKey(ovk)
             }
@@ -1249,7 +1248,7 @@ impl<N: ZcashNetwork> Transaction for ZcashTransaction<N> {
                 let mut signature = match &private_key {
                     ZcashPrivateKey::<N>::P2PKH(p2pkh_spending_key) => {
                         let (signature, _) = secp256k1::sign(
-                            &secp256k1::Message::parse_slice(&transaction_hash.as_bytes())?,
+                            &secp256k1::Message::parse_slice(&transaction_hash)?,
                             &p2pkh_spending_key.to_secp256k1_secret_key(),
                         );
                         signature.serialize_der().as_ref().to_vec()
@@ -1349,7 +1348,7 @@ impl<N: ZcashNetwork> Transaction for ZcashTransaction<N> {
 
     /// Returns the transaction id.
     fn to_transaction_id(&self) -> Result<Self::TransactionId, TransactionError> {
-        let mut txid = Sha256::digest(&Sha256::digest(&self.to_transaction_bytes()?)).to_vec();
+        let mut txid = sha256d(&self.to_transaction_bytes()?).to_vec();
         txid.reverse();
 
         Ok(Self::TransactionId { txid })
@@ -1386,7 +1385,7 @@ impl<N: ZcashNetwork> ZcashTransaction<N> {
         };
 
         let mut sighash = [0u8; 32];
-        sighash.copy_from_slice(self.generate_sighash(None, SignatureHash::SIGHASH_ALL)?.as_bytes());
+        sighash.copy_from_slice(&self.generate_sighash(None, SignatureHash::SIGHASH_ALL)?);
 
         self.generate_spend_auth_signatures(verifying_ctx, spend_vk, &sighash)?;
         self.generate_binding_sig(proving_ctx, verifying_ctx, &sighash)?;
@@ -1491,7 +1490,7 @@ impl<N: ZcashNetwork> ZcashTransaction<N> {
         &self,
         input_index: Option<usize>,
         sighash_code: SignatureHash,
-    ) -> Result<Hash, TransactionError> {
+    ) -> Result<[u8; 32], TransactionError> {
         let mut prev_outputs = vec![];
         let mut prev_sequences = vec![];
         let mut outputs = vec![];
@@ -1520,9 +1519,7 @@ impl<N: ZcashNetwork> ZcashTransaction<N> {
                         spend_descriptions.extend(description.serialize(true)?);
                     };
                 }
-                blake2_256_hash("ZcashSSpendsHash", spend_descriptions, None)
-                    .as_bytes()
-                    .to_vec()
+                blake2_256_hash("ZcashSSpendsHash", spend_descriptions, None).to_vec()
             }
         };
 
@@ -1535,18 +1532,16 @@ impl<N: ZcashNetwork> ZcashTransaction<N> {
                         output_descriptions.extend(description.serialize()?);
                     }
                 }
-                blake2_256_hash("ZcashSOutputHash", output_descriptions, None)
-                    .as_bytes()
-                    .to_vec()
+                blake2_256_hash("ZcashSOutputHash", output_descriptions, None).to_vec()
             }
         };
 
         let mut preimage = vec![];
         preimage.extend(&self.parameters.header.to_le_bytes());
         preimage.extend(&self.parameters.version_group_id.to_le_bytes());
-        preimage.extend(hash_prev_outputs.as_bytes());
-        preimage.extend(hash_sequence.as_bytes());
-        preimage.extend(hash_outputs.as_bytes());
+        preimage.extend(&hash_prev_outputs);
+        preimage.extend(&hash_sequence);
+        preimage.extend(&hash_outputs);
         preimage.extend(&hash_joinsplits);
         preimage.extend(&hash_shielded_spends);
         preimage.extend(&hash_shielded_outputs);
@@ -3200,12 +3195,7 @@ mod tests {
 
             let mut verifying_ctx = initialize_verifying_context();
             let mut sighash = [0u8; 32];
-            sighash.copy_from_slice(
-                transaction
-                    .generate_sighash(None, SignatureHash::SIGHASH_ALL)
-                    .unwrap()
-                    .as_bytes(),
-            );
+            sighash.copy_from_slice(&transaction.generate_sighash(None, SignatureHash::SIGHASH_ALL).unwrap());
 
             assert!(transaction
                 .generate_spend_auth_signatures(&mut verifying_ctx, &mut spend_vk, &sighash)
@@ -0,0 +1,639 @@
+//!
+//! ZIP-316 - Unified Addresses
+//! https://zips.z.cash/zip-0316
+//!
+//! A unified address bundles the receivers of one wallet into a single "u1..." string, from
+//! which a sender picks the most private receiver it supports. The receivers are encoded in
+//! ascending typecode order, padded with the human-readable part, mixed with F4Jumble so that
+//! a partially matching address is not mistaken for another, and encoded with Bech32m.
+//!
+
+use crate::address::ZcashAddress;
+use crate::format::ZcashFormat;
+use crate::network::ZcashNetwork;
+use wagyu_model::no_std::*;
+use wagyu_model::{
+    bech32m::{self, Variant},
+    crypto::checksum,
+    AddressError,
+};
+
+use base58::{FromBase58, ToBase58};
+use bech32::{Bech32, FromBase32, ToBase32};
+use blake2b_simd::Params;
+use core::{fmt, marker::PhantomData, str::FromStr};
+
+/// The length in bytes of the padding of the human-readable part.
+const PADDING_LENGTH: usize = 16;
+/// The minimum length in bytes of an F4Jumble message.
+const MIN_F4JUMBLE_LENGTH: usize = 48;
+/// The maximum length in bytes of an F4Jumble message.
+const MAX_F4JUMBLE_LENGTH: usize = 4_194_368;
+
+/// Represents a receiver of a unified address
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Receiver {
+    /// Transparent P2PKH receiver, the hash160 of the public key - typecode 0x00
+    P2PKH([u8; 20]),
+    /// Transparent P2SH receiver, the hash160 of the redeem script - typecode 0x01
+    P2SH([u8; 20]),
+    /// Sapling receiver, the diversifier and diversified transmission key - typecode 0x02
+    Sapling([u8; 43]),
+    /// Orchard receiver, the diversifier and diversified transmission key - typecode 0x03
+    Orchard([u8; 43]),
+    /// Receiver of a typecode this crate does not know, which is preserved as is
+    Unknown { typecode: u32, data: Vec<u8> },
+}
+
+impl Receiver {
+    /// Returns the receiver of the given typecode and data.
+    pub fn new(typecode: u32, data: &[u8]) -> Result<Self, AddressError> {
+        let invalid_length = || AddressError::InvalidReceiverLength(typecode, data.len());
+        Ok(match typecode {
+            0x00 | 0x01 => {
+                let mut hash = [0u8; 20];
+                if data.len() != hash.len() {
+                    return Err(invalid_length());
+                }
+                hash.copy_from_slice(data);
+                match typecode {
+                    0x00 => Receiver::P2PKH(hash),
+                    _ => Receiver::P2SH(hash),
+                }
+            }
+            0x02 | 0x03 => {
+                let mut address = [0u8; 43];
+                if data.len() != address.len() {
+                    return Err(invalid_length());
+                }
+                address.copy_from_slice(data);
+                match typecode {
+                    0x02 => Receiver::Sapling(address),
+                    _ => Receiver::Orchard(address),
+                }
+            }
+            _ => Receiver::Unknown {
+                typecode,
+                data: data.to_vec(),
+            },
+        })
+    }
+
+    /// Returns the typecode of the receiver.
+    pub fn typecode(&self) -> u32 {
+        match self {
+            Receiver::P2PKH(_) => 0x00,
+            Receiver::P2SH(_) => 0x01,
+            Receiver::Sapling(_) => 0x02,
+            Receiver::Orchard(_) => 0x03,
+            Receiver::Unknown { typecode, .. } => *typecode,
+        }
+    }
+
+    /// Returns the encoded data of the receiver.
+    pub fn data(&self) -> &[u8] {
+        match self {
+            Receiver::P2PKH(hash) | Receiver::P2SH(hash) => hash,
+            Receiver::Sapling(address) | Receiver::Orchard(address) => address,
+            Receiver::Unknown { data, .. } => data,
+        }
+    }
+
+    /// Returns `true` if the receiver is a shielded receiver.
+    pub fn is_shielded(&self) -> bool {
+        match self {
+            Receiver::P2PKH(_) | Receiver::P2SH(_) => false,
+            Receiver::Sapling(_) | Receiver::Orchard(_) => true,
+            // Every typecode defined after the transparent typecodes is shielded.
+            Receiver::Unknown { .. } => true,
+        }
+    }
+
+    /// Returns the receiver of the given transparent or Sapling address.
+    pub fn from_address<N: ZcashNetwork>(address: &ZcashAddress<N>) -> Result<Self, AddressError> {
+        match address.format() {
+            ZcashFormat::P2PKH => Self::new(0x00, &address.to_string().from_base58()?[2..22]),
+            ZcashFormat::P2SH => Self::new(0x01, &address.to_string().from_base58()?[2..22]),
+            ZcashFormat::Sapling(_) => {
+                let data: Vec<u8> = FromBase32::from_base32(Bech32::from_str(&address.to_string())?.data())?;
+                Self::new(0x02, &data)
+            }
+            format => Err(AddressError::IncompatibleFormats(format.to_string(), "unified".into())),
+        }
+    }
+
+    /// Returns the transparent or Sapling address of the receiver, or `None` for any other receiver.
+    pub fn to_address<N: ZcashNetwork>(&self) -> Result<Option<ZcashAddress<N>>, AddressError> {
+        let address = match self {
+            Receiver::P2PKH(hash) | Receiver::P2SH(hash) => {
+                let format = match self {
+                    Receiver::P2PKH(_) => ZcashFormat::P2PKH,
+                    _ => ZcashFormat::P2SH,
+                };
+                let mut address = N::to_address_prefix(&format);
+                address.extend_from_slice(hash);
+                let sum = checksum(&address);
+                address.extend_from_slice(&sum[0..4]);
+                address.to_base58()
+            }
+            Receiver::Sapling(address) => {
+                let prefix = N::to_address_prefix(&ZcashFormat::Sapling(None));
+                let prefix = String::from_utf8(prefix).map_err(|error| AddressError::Message(error.to_string()))?;
+                Bech32::new(prefix, address.to_base32())?.to_string()
+            }
+            Receiver::Orchard(_) | Receiver::Unknown { .. } => return Ok(None),
+        };
+        Ok(Some(ZcashAddress::from_str(&address)?))
+    }
+}
+
+/// Represents a Zcash unified address
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ZcashUnifiedAddress<N: ZcashNetwork> {
+    /// The receivers, in ascending typecode order
+    receivers: Vec<Receiver>,
+    /// PhantomData
+    _network: PhantomData<N>,
+}
+
+impl<N: ZcashNetwork> ZcashUnifiedAddress<N> {
+    /// Returns a unified address of the given receivers, in any order.
+    pub fn new(mut receivers: Vec<Receiver>) -> Result<Self, AddressError> {
+        receivers.sort_by_key(Receiver::typecode);
+        Self::validate(&receivers)?;
+        Ok(Self {
+            receivers,
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns a unified address of the receivers of the given transparent and Sapling addresses.
+    pub fn from_addresses(addresses: &[ZcashAddress<N>]) -> Result<Self, AddressError> {
+        Self::new(
+            addresses
+                .iter()
+                .map(Receiver::from_address)
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    }
+
+    /// Returns the receivers of the unified address, in ascending typecode order.
+    pub fn receivers(&self) -> &[Receiver] {
+        &self.receivers
+    }
+
+    /// Returns the receiver of the given typecode, if the unified address has one.
+    pub fn receiver(&self, typecode: u32) -> Option<&Receiver> {
+        self.receivers.iter().find(|receiver| receiver.typecode() == typecode)
+    }
+
+    /// Returns the transparent and Sapling addresses of the receivers of the unified address.
+    pub fn to_addresses(&self) -> Result<Vec<ZcashAddress<N>>, AddressError> {
+        Ok(self
+            .receivers
+            .iter()
+            .map(Receiver::to_address)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Checks the ZIP-316 requirements of the given receivers, in encoding order.
+    fn validate(receivers: &[Receiver]) -> Result<(), AddressError> {
+        for pair in receivers.windows(2) {
+            let (previous, next) = (pair[0].typecode(), pair[1].typecode());
+            if previous == next {
+                return Err(AddressError::DuplicateTypecode(next));
+            }
+            if previous > next {
+                return Err(AddressError::InvalidTypecodeOrder(next, previous));
+            }
+        }
+
+        if receivers.iter().all(|receiver| !receiver.is_shielded()) {
+            return Err(AddressError::InvalidAddress(
+                "a unified address requires a shielded receiver".into(),
+            ));
+        }
+        if receivers.iter().any(|receiver| receiver.typecode() == 0x00)
+            && receivers.iter().any(|receiver| receiver.typecode() == 0x01)
+        {
+            return Err(AddressError::InvalidAddress(
+                "a unified address cannot have both a P2PKH and a P2SH receiver".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the padding of the human-readable part of the network.
+    fn padding() -> [u8; PADDING_LENGTH] {
+        let mut padding = [0u8; PADDING_LENGTH];
+        let prefix = N::to_unified_address_prefix();
+        padding[..prefix.len()].copy_from_slice(prefix.as_bytes());
+        padding
+    }
+}
+
+impl<N: ZcashNetwork> FromStr for ZcashUnifiedAddress<N> {
+    type Err = AddressError;
+
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        let (prefix, jumbled) = bech32m_decode(address)?;
+        if prefix != N::to_unified_address_prefix() {
+            return Err(AddressError::InvalidNetwork(N::to_unified_address_prefix(), prefix));
+        }
+
+        let raw = f4jumble_inv(&jumbled)?;
+        let (items, padding) = raw.split_at(raw.len() - PADDING_LENGTH);
+        if padding != Self::padding() {
+            return Err(AddressError::InvalidPadding);
+        }
+
+        let mut receivers = vec![];
+        let mut items = items;
+        while !items.is_empty() {
+            let typecode = read_compact_size(&mut items)?;
+            let length = read_compact_size(&mut items)? as usize;
+            if length > items.len() {
+                return Err(AddressError::InvalidReceiverLength(typecode, items.len()));
+            }
+            let (data, remaining) = items.split_at(length);
+            receivers.push(Receiver::new(typecode, data)?);
+            items = remaining;
+        }
+
+        Self::validate(&receivers)?;
+        Ok(Self {
+            receivers,
+            _network: PhantomData,
+        })
+    }
+}
+
+impl<N: ZcashNetwork> fmt::Display for ZcashUnifiedAddress<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut raw = vec![];
+        for receiver in &self.receivers {
+            write_compact_size(&mut raw, receiver.typecode());
+            write_compact_size(&mut raw, receiver.data().len() as u32);
+            raw.extend_from_slice(receiver.data());
+        }
+        raw.extend_from_slice(&Self::padding());
+
+        let jumbled = f4jumble(&raw).map_err(|_| fmt::Error)?;
+        write!(f, "{}", bech32m_encode(&N::to_unified_address_prefix(), &jumbled))
+    }
+}
+
+/// Returns the given message mixed by the F4Jumble permutation.
+pub fn f4jumble(message: &[u8]) -> Result<Vec<u8>, AddressError> {
+    let (a, b) = split_jumble(message)?;
+    let x = xor(b, &f4jumble_g(0, a, b.len()));
+    let y = xor(a, &f4jumble_h(0, &x, a.len()));
+    let d = xor(&x, &f4jumble_g(1, &y, b.len()));
+    let c = xor(&y, &f4jumble_h(1, &d, a.len()));
+    Ok([c, d].concat())
+}
+
+/// Returns the message of the given output of the F4Jumble permutation.
+pub fn f4jumble_inv(message: &[u8]) -> Result<Vec<u8>, AddressError> {
+    let (c, d) = split_jumble(message)?;
+    let y = xor(c, &f4jumble_h(1, d, c.len()));
+    let x = xor(d, &f4jumble_g(1, &y, d.len()));
+    let a = xor(&y, &f4jumble_h(0, &x, c.len()));
+    let b = xor(&x, &f4jumble_g(0, &a, d.len()));
+    Ok([a, b].concat())
+}
+
+/// Returns the left and right halves of an F4Jumble message, of which the left is at most 64 bytes.
+fn split_jumble(message: &[u8]) -> Result<(&[u8], &[u8]), AddressError> {
+    if message.len() < MIN_F4JUMBLE_LENGTH || message.len() > MAX_F4JUMBLE_LENGTH {
+        return Err(AddressError::InvalidByteLength(message.len()));
+    }
+    Ok(message.split_at(core::cmp::min(64, message.len() / 2)))
+}
+
+/// Returns the F4Jumble round function H_i, a BLAKE2b hash of the length of the left half.
+fn f4jumble_h(round: u8, data: &[u8], length: usize) -> Vec<u8> {
+    let mut personalization = *b"UA_F4Jumble_H___";
+    personalization[13..].copy_from_slice(&[round, 0, 0]);
+    Params::new()
+        .hash_length(length)
+        .personal(&personalization)
+        .hash(data)
+        .as_bytes()
+        .to_vec()
+}
+
+/// Returns the F4Jumble round function G_i, the concatenated BLAKE2b-512 hashes truncated to the length of the right half.
+fn f4jumble_g(round: u8, data: &[u8], length: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(length + 64);
+    let mut counter = 0u16;
+    while output.len() < length {
+        let mut personalization = *b"UA_F4Jumble_G___";
+        personalization[13] = round;
+        personalization[14..].copy_from_slice(&counter.to_le_bytes());
+        output.extend_from_slice(
+            Params::new()
+                .hash_length(64)
+                .personal(&personalization)
+                .hash(data)
+                .as_bytes(),
+        );
+        counter += 1;
+    }
+    output.truncate(length);
+    output
+}
+
+/// Returns the bytewise XOR of the given byte slices of equal length.
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(a, b)| a ^ b).collect()
+}
+
+/// Appends the Bitcoin compact size encoding of the given integer.
+fn write_compact_size(buffer: &mut Vec<u8>, value: u32) {
+    match value {
+        0..=0xfc => buffer.push(value as u8),
+        0xfd..=0xffff => {
+            buffer.push(0xfd);
+            buffer.extend_from_slice(&(value as u16).to_le_bytes());
+        }
+        _ => {
+            buffer.push(0xfe);
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+}
+
+/// Returns the integer of the Bitcoin compact size encoding at the start of the buffer, and advances the buffer.
+fn read_compact_size(buffer: &mut &[u8]) -> Result<u32, AddressError> {
+    let truncated = || AddressError::InvalidAddress("truncated compact size".into());
+    let (&first, rest) = buffer.split_first().ok_or_else(truncated)?;
+    let (value, length, minimum) = match first {
+        0xfd if rest.len() >= 2 => (u16::from_le_bytes([rest[0], rest[1]]) as u32, 2, 0xfd),
+        0xfe if rest.len() >= 4 => (u32::from_le_bytes([rest[0], rest[1], rest[2], rest[3]]), 4, 0x10000),
+        0xfd..=0xff => return Err(truncated()),
+        _ => (first as u32, 0, 0),
+    };
+    if value < minimum {
+        return Err(AddressError::InvalidAddress("non-canonical compact size".into()));
+    }
+    *buffer = &rest[length..];
+    Ok(value)
+}
+
+/// Returns the Bech32m encoding of the given bytes with the given human-readable part.
+fn bech32m_encode(hrp: &str, data: &[u8]) -> String {
+    let data = bech32m::convert_bits(data, 8, 5, true).expect("padded conversion never fails");
+    bech32m::encode(hrp, &data, Variant::Bech32m)
+}
+
+/// Returns the human-readable part and bytes of the given Bech32m string.
+fn bech32m_decode(encoding: &str) -> Result<(String, Vec<u8>), AddressError> {
+    match bech32m::decode(encoding)? {
+        (hrp, data, Variant::Bech32m) => Ok((hrp, bech32m::convert_bits(&data, 5, 8, false)?)),
+        (_, _, Variant::Bech32) => Err(AddressError::InvalidChecksum("bech32m".into(), encoding.to_lowercase())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    use rand::{rngs::StdRng, Rng};
+    use rand_core::SeedableRng;
+
+    const TRANSPARENT: &str = "t1MoMR1XdnPqLBWf5XkchWTkGNrveYLCaiM";
+    const SAPLING: &str = "zs1dq9dlh6u6hna0u96aqtynxt3acddtgkgdx4re65500nmc2aze0my65ky36vaqvj4hkc9ut66eyf";
+
+    fn addresses() -> Vec<ZcashAddress<Mainnet>> {
+        vec![
+            ZcashAddress::from_str(SAPLING).unwrap(),
+            ZcashAddress::from_str(TRANSPARENT).unwrap(),
+        ]
+    }
+
+    /// Mainnet unified addresses with their receivers as (typecode, hex data).
+    /// https://github.com/zcash-hackworks/zcash-test-vectors/blob/master/unified_address.py
+    const VECTORS: [(&str, &[(u32, &str)]); 2] = [
+        (
+            "u1l8xunezsvhq8fgzfl7404m450nwnd76zshscn6nfys7vyz2ywyh4cc5daaq0c7q2su5lqfh23sp7fkf3kt27ve5948mzpfdvckzaect2jtte308mkwlycj2u0eac077wu70vqcetkxf",
+            &[
+                (0x00, "7bb83570b8fae146e03c5331a020b1e0892f631d"),
+                (0x02, "d8ef8293d26de832e7193f296ba1922d90f122c6135bc231eebd91efdb03b1a8606771cd4fd6480574d43e"),
+            ],
+        ),
+        (
+            "u1pg2aaph7jp8rpf6yhsza25722sg5fcn3vaca6ze27hqjw7jvvhhuxkpcg0ge9xh6drsgdkda8qjq5chpehkcpxf87rnjryjqwymdheptpvnljqqrjqzjwkc2ma6hcq666kgwfytxwac8eyex6ndgr6ezte66706e3vaqrd25dzvzkc69kw0jgywtd0cmq52q5lkw6uh7hyvzjse8ksx",
+            &[
+                (0x00, "cad268758c5e71493066446b98e71df9d1d6a5ca"),
+                (0x02, "9f6e0bf90a18fc0b9b83ae9f23ad4358648638482b5def8975635b66fd8a708335f9235a3186ec0f033f84"),
+                (0x03, "cecbe5e689a453a3fe10ccf7617e6c1fb382819d7fc9200a1f42092ac84a30378f8c1fb90dff71a6d5042d"),
+            ],
+        ),
+    ];
+
+    #[test]
+    fn vectors() {
+        VECTORS.iter().for_each(|(encoding, expected)| {
+            let unified_address = ZcashUnifiedAddress::<Mainnet>::from_str(encoding).unwrap();
+            assert_eq!(
+                expected
+                    .iter()
+                    .map(|(typecode, data)| (*typecode, data.to_string()))
+                    .collect::<Vec<_>>(),
+                unified_address
+                    .receivers()
+                    .iter()
+                    .map(|receiver| (receiver.typecode(), hex::encode(receiver.data())))
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(*encoding, unified_address.to_string());
+
+            let receivers = expected
+                .iter()
+                .map(|(typecode, data)| Receiver::new(*typecode, &hex::decode(data).unwrap()).unwrap())
+                .collect();
+            assert_eq!(
+                *encoding,
+                ZcashUnifiedAddress::<Mainnet>::new(receivers).unwrap().to_string()
+            );
+            assert!(ZcashUnifiedAddress::<Testnet>::from_str(encoding).is_err());
+        });
+    }
+
+    #[test]
+    fn from_addresses() {
+        let unified_address = ZcashUnifiedAddress::<Mainnet>::from_addresses(&addresses()).unwrap();
+        let encoding = unified_address.to_string();
+        assert!(encoding.starts_with("u1"));
+
+        // The receivers are in ascending typecode order, regardless of the order of the addresses.
+        let receivers = unified_address.receivers();
+        assert_eq!(
+            vec![0x00, 0x02],
+            receivers.iter().map(Receiver::typecode).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![TRANSPARENT, SAPLING],
+            unified_address
+                .to_addresses()
+                .unwrap()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        );
+
+        let parsed = ZcashUnifiedAddress::<Mainnet>::from_str(&encoding).unwrap();
+        assert_eq!(unified_address, parsed);
+        assert_eq!(
+            unified_address,
+            ZcashUnifiedAddress::from_str(&encoding.to_uppercase()).unwrap()
+        );
+        assert!(parsed.receiver(0x03).is_none());
+        assert_eq!(20, parsed.receiver(0x00).unwrap().data().len());
+    }
+
+    #[test]
+    fn testnet() {
+        let receivers = vec![Receiver::Sapling([7u8; 43]), Receiver::P2SH([1u8; 20])];
+        let unified_address = ZcashUnifiedAddress::<Testnet>::new(receivers).unwrap();
+        let encoding = unified_address.to_string();
+        assert!(encoding.starts_with("utest1"));
+        assert_eq!(unified_address, ZcashUnifiedAddress::from_str(&encoding).unwrap());
+
+        match ZcashUnifiedAddress::<Mainnet>::from_str(&encoding) {
+            Err(AddressError::InvalidNetwork(expected, found)) => assert_eq!(("u", "utest"), (&*expected, &*found)),
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn unknown_receivers() {
+        let receivers = vec![
+            Receiver::Orchard([3u8; 43]),
+            Receiver::new(0x1234, &[9u8; 10]).unwrap(),
+            Receiver::new(0x20000, &[8u8; 3]).unwrap(),
+        ];
+        let unified_address = ZcashUnifiedAddress::<Mainnet>::new(receivers).unwrap();
+        let parsed = ZcashUnifiedAddress::<Mainnet>::from_str(&unified_address.to_string()).unwrap();
+        assert_eq!(unified_address, parsed);
+        assert_eq!(
+            vec![0x03, 0x1234, 0x20000],
+            parsed.receivers().iter().map(Receiver::typecode).collect::<Vec<_>>()
+        );
+        assert!(parsed.to_addresses().unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_receivers() {
+        match ZcashUnifiedAddress::<Mainnet>::new(vec![Receiver::Sapling([0u8; 43]), Receiver::Sapling([1u8; 43])]) {
+            Err(AddressError::DuplicateTypecode(0x02)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+        assert!(ZcashUnifiedAddress::<Mainnet>::new(vec![Receiver::P2PKH([0u8; 20])]).is_err());
+        assert!(ZcashUnifiedAddress::<Mainnet>::new(vec![
+            Receiver::P2PKH([0u8; 20]),
+            Receiver::P2SH([0u8; 20]),
+            Receiver::Sapling([0u8; 43]),
+        ])
+        .is_err());
+        match Receiver::new(0x00, &[0u8; 21]) {
+            Err(AddressError::InvalidReceiverLength(0x00, 21)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    fn encode(items: &[(u32, &[u8])], padding: &[u8]) -> String {
+        let mut raw = vec![];
+        for (typecode, data) in items {
+            write_compact_size(&mut raw, *typecode);
+            write_compact_size(&mut raw, data.len() as u32);
+            raw.extend_from_slice(data);
+        }
+        raw.extend_from_slice(padding);
+        bech32m_encode("u", &f4jumble(&raw).unwrap())
+    }
+
+    #[test]
+    fn invalid_encodings() {
+        let padding = ZcashUnifiedAddress::<Mainnet>::padding();
+        let (sapling, orchard) = ([1u8; 43], [2u8; 43]);
+        assert!(ZcashUnifiedAddress::<Mainnet>::from_str(&encode(&[(0x02, &sapling)], &padding)).is_ok());
+
+        match ZcashUnifiedAddress::<Mainnet>::from_str(&encode(&[(0x03, &orchard), (0x02, &sapling)], &padding)) {
+            Err(AddressError::InvalidTypecodeOrder(0x02, 0x03)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+        match ZcashUnifiedAddress::<Mainnet>::from_str(&encode(&[(0x02, &sapling), (0x02, &sapling)], &padding)) {
+            Err(AddressError::DuplicateTypecode(0x02)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+
+        let mut invalid_padding = padding;
+        invalid_padding[15] = 1;
+        match ZcashUnifiedAddress::<Mainnet>::from_str(&encode(&[(0x02, &sapling)], &invalid_padding)) {
+            Err(AddressError::InvalidPadding) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+        let testnet_padding = ZcashUnifiedAddress::<Testnet>::padding();
+        assert!(ZcashUnifiedAddress::<Mainnet>::from_str(&encode(&[(0x02, &sapling)], &testnet_padding)).is_err());
+
+        // A receiver with a length beyond the end of the address
+        assert!(ZcashUnifiedAddress::<Mainnet>::from_str(&encode(&[(0x02, &sapling[..])], &padding[1..])).is_err());
+
+        // A corrupted checksum
+        let mut encoding = encode(&[(0x02, &sapling)], &padding).into_bytes();
+        let last = encoding.len() - 1;
+        encoding[last] = if encoding[last] == b'q' { b'p' } else { b'q' };
+        match ZcashUnifiedAddress::<Mainnet>::from_str(&String::from_utf8(encoding).unwrap()) {
+            Err(AddressError::InvalidChecksum(_, _)) => {}
+            result => panic!("unexpected result: {:?}", result),
+        };
+    }
+
+    #[test]
+    fn f4jumble_vectors() {
+        // (message, jumbled message)
+        // https://github.com/zcash-hackworks/zcash-test-vectors/blob/master/f4jumble.py
+        [(
+            "5d7a8f739a2d9e945b0ce152a8049e294c4d6e66b164939daffa2ef6ee6921481cdd86b3cc4318d9614fc820905d042b",
+            "0304d029141b995da5387c125970673504d6c764d91ea6c082123770c7139ccd88ee27368cd0c0921a0444c8e5858d22",
+        )]
+        .iter()
+        .for_each(|(message, jumbled)| {
+            let message = hex::decode(message).unwrap();
+            assert_eq!(*jumbled, hex::encode(f4jumble(&message).unwrap()));
+            assert_eq!(message, f4jumble_inv(&hex::decode(jumbled).unwrap()).unwrap());
+        });
+    }
+
+    #[test]
+    fn f4jumble_round_trip() {
+        let rng = &mut StdRng::seed_from_u64(1231275789u64);
+        [48, 64, 127, 128, 129, 200, 1000].iter().for_each(|length| {
+            let message = (0..*length).map(|_| rng.gen()).collect::<Vec<u8>>();
+            let jumbled = f4jumble(&message).unwrap();
+            assert_eq!(message.len(), jumbled.len());
+            assert_ne!(message, jumbled);
+            assert_eq!(message, f4jumble_inv(&jumbled).unwrap());
+        });
+        assert!(f4jumble(&[0u8; 47]).is_err());
+    }
+
+    #[test]
+    fn bech32m() {
+        // BIP-350 test vectors
+        ["a1lqfn3a", "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx", "?1v759aa"]
+            .iter()
+            .for_each(|encoding| {
+                let (hrp, data) = bech32m_decode(encoding).unwrap();
+                assert_eq!(*encoding, bech32m_encode(&hrp, &data));
+            });
+        // A valid Bech32 string is not a valid Bech32m string.
+        assert!(bech32m_decode("a12uel5l").is_err());
+    }
+}